@@ -1,5 +1,8 @@
 mod client;
 mod tonic;
+mod validate;
+
+pub(crate) use validate::append_validation_to_out_dir;
 
 use convert_case::{Case, Casing};
 use proc_macro2::{Ident, Span, TokenStream};
@@ -70,6 +73,7 @@ pub(super) struct ServiceInfo {
 #[derive(Debug, Clone)]
 pub(super) struct MethodInfo {
     pub method_name: Ident,
+    pub proto_name: String,
     pub request_type: TokenStream,
     pub response_type: TokenStream,
     pub path: String,
@@ -85,6 +89,7 @@ pub struct AxumConnectServiceGenerator {
     include_connect_server: bool,
     include_tonic: bool,
     include_connect_client: bool,
+    include_client_mock: bool,
 }
 
 impl AxumConnectServiceGenerator {
@@ -107,6 +112,11 @@ impl AxumConnectServiceGenerator {
         self
     }
 
+    pub fn with_client_mock(mut self, include: bool) -> Self {
+        self.include_client_mock = include;
+        self
+    }
+
     pub fn append_to_out_dir(&self, schema: &SchemaSet, out_dir: &str) -> Result<()> {
         let mut generated_by_file = BTreeMap::<String, String>::new();
 
@@ -174,6 +184,37 @@ impl AxumConnectServiceGenerator {
         let nested_method_info = build_method_info(schema, service, 1)?;
         let root_method_info = build_method_info(schema, service, 0)?;
 
+        // Fully-qualified proto service name (e.g., hello.HelloWorldService)
+        let service_full_name = if service.package.is_empty() {
+            service.proto_name.clone()
+        } else {
+            format!("{}.{}", service.package, service.proto_name)
+        };
+
+        // Static descriptor table for middleware keyed by procedure path.
+        let method_descriptors: Vec<_> = nested_method_info
+            .iter()
+            .map(|method| {
+                let name = &method.proto_name;
+                let path = &method.path;
+                let client_streaming = method.client_streaming;
+                let server_streaming = method.server_streaming;
+                let idempotency_tokens = &method.idempotency_tokens;
+                quote! {
+                    connectrpc_axum::MethodDescriptor {
+                        service: SERVICE_NAME,
+                        name: #name,
+                        path: #path,
+                        kind: connectrpc_axum::MethodKind::from_streaming(
+                            #client_streaming,
+                            #server_streaming
+                        ),
+                        idempotency: #idempotency_tokens,
+                    }
+                }
+            })
+            .collect();
+
         // Generate Connect-only builder methods for all RPC types.
         let connect_builder_methods: Vec<_> = nested_method_info
             .iter()
@@ -209,6 +250,8 @@ impl AxumConnectServiceGenerator {
                 let request_type = &method.request_type;
                 let response_type = &method.response_type;
                 let method_name = &method.method_name;
+                let with_config_name = derived_method_ident(method_name, "with_config");
+                let with_layer_name = derived_method_ident(method_name, "with_layer");
                 let path = &method.path;
                 let idempotency_tokens = &method.idempotency_tokens;
 
@@ -262,6 +305,66 @@ impl AxumConnectServiceGenerator {
                             router: self.router.route(#path, method_router),
                         }
                     }
+
+                    /// Register a handler with per-method overrides of the server
+                    /// configuration (limits, timeout, compression).
+                    pub fn #with_config_name<F, T>(
+                        self,
+                        handler: F,
+                        config: connectrpc_axum::MethodConfig,
+                    ) -> #service_builder_name<S>
+                    where
+                        connectrpc_axum::handler::ConnectHandlerWrapper<
+                            F,
+                            #request_type,
+                            #response_type,
+                        >: axum::handler::Handler<T, S>,
+                        F: Clone + Send + Sync + 'static,
+                        T: 'static,
+                    {
+                        let method_router = #method_router_expr;
+                        #service_builder_name {
+                            router: self.router.route(#path, method_router.layer(config)),
+                        }
+                    }
+
+                    /// Register a handler wrapped in a Tower layer that applies
+                    /// to this RPC method only.
+                    pub fn #with_layer_name<F, T, L>(
+                        self,
+                        handler: F,
+                        layer: L,
+                    ) -> #service_builder_name<S>
+                    where
+                        connectrpc_axum::handler::ConnectHandlerWrapper<
+                            F,
+                            #request_type,
+                            #response_type,
+                        >: axum::handler::Handler<T, S>,
+                        F: Clone + Send + Sync + 'static,
+                        T: 'static,
+                        L: connectrpc_axum::tower::Layer<axum::routing::Route>
+                            + Clone
+                            + Send
+                            + Sync
+                            + 'static,
+                        L::Service: connectrpc_axum::tower::Service<axum::extract::Request>
+                            + Clone
+                            + Send
+                            + Sync
+                            + 'static,
+                        <L::Service as connectrpc_axum::tower::Service<axum::extract::Request>>::Response:
+                            axum::response::IntoResponse + 'static,
+                        <L::Service as connectrpc_axum::tower::Service<axum::extract::Request>>::Error:
+                            Into<::std::convert::Infallible> + 'static,
+                        <L::Service as connectrpc_axum::tower::Service<axum::extract::Request>>::Future:
+                            Send + 'static,
+                    {
+                        let method_router = #method_router_expr;
+                        #service_builder_name {
+                            router: self.router.route(#path, method_router.layer(layer)),
+                        }
+                    }
                 }
             })
             .collect();
@@ -286,6 +389,14 @@ impl AxumConnectServiceGenerator {
                     #[allow(unused_imports)]
                     use super::*;
 
+                    /// Fully-qualified proto name of this service.
+                    pub const SERVICE_NAME: &str = #service_full_name;
+
+                    /// Descriptors of every RPC method in this service, in proto order.
+                    pub const METHODS: &[connectrpc_axum::MethodDescriptor] = &[
+                        #(#method_descriptors),*
+                    ];
+
                     /// Connect-only service builder (flexible extractors)
                     pub struct #service_builder_name<S = ()> {
                         pub router: axum::Router<S>,
@@ -352,7 +463,11 @@ impl AxumConnectServiceGenerator {
         }
 
         if self.include_connect_client {
-            let client_code = client::generate_connect_client(&service_info, &nested_method_info);
+            let client_code = client::generate_connect_client(
+                &service_info,
+                &nested_method_info,
+                self.include_client_mock,
+            );
             buf.push_str(&client_code.to_string());
         }
 
@@ -408,6 +523,7 @@ fn build_method_info(
 
             Ok(MethodInfo {
                 method_name,
+                proto_name: method.proto_name.clone(),
                 request_type,
                 response_type,
                 path: method.route_path.clone(),
@@ -28,9 +28,12 @@ impl RpcType {
 }
 
 /// Generate the Connect RPC client code.
+///
+/// With `include_mock`, a mock implementing the client trait is generated too.
 pub fn generate_connect_client(
     service: &ServiceInfo,
     method_info: &[MethodInfo],
+    include_mock: bool,
 ) -> proc_macro2::TokenStream {
     // Client module name (e.g., hello_world_service_connect_client)
     let client_module_name = format_ident!("{}_connect_client", service.name.to_case(Case::Snake));
@@ -267,6 +270,7 @@ pub fn generate_connect_client(
             let const_name = method_const_ident(method_name);
             let procedure_path = quote! { super::#procedures_mod_name::#const_name };
             let interceptors_field = derived_method_ident(method_name, "interceptors");
+            let with_options_name = derived_method_ident(method_name, "with_options");
             let rpc_type = RpcType::from_streaming(method.server_streaming, method.client_streaming);
 
             match rpc_type {
@@ -278,6 +282,17 @@ pub fn generate_connect_client(
                         pub async fn #method_name(
                             &self,
                             request: &#request_type,
+                        ) -> Result<connectrpc_axum_client::ConnectResponse<#response_type>, connectrpc_axum_client::ClientError> {
+                            self.#with_options_name(request, connectrpc_axum_client::CallOptions::new()).await
+                        }
+
+                        /// Make a unary RPC call to this method with per-call options.
+                        ///
+                        /// Headers set by the `before` interceptor override headers in `options`.
+                        pub async fn #with_options_name(
+                            &self,
+                            request: &#request_type,
+                            mut options: connectrpc_axum_client::CallOptions,
                         ) -> Result<connectrpc_axum_client::ConnectResponse<#response_type>, connectrpc_axum_client::ClientError> {
                             let mut request = request.clone();
 
@@ -288,7 +303,7 @@ pub fn generate_connect_client(
                                 interceptor.intercept(&mut ctx, &mut request)?;
                             }
 
-                            let options = connectrpc_axum_client::CallOptions::new().headers(interceptor_headers);
+                            options.headers_mut().extend(interceptor_headers);
                             let mut response: connectrpc_axum_client::ConnectResponse<#response_type> =
                                 self.inner.call_unary_with_options(#procedure_path, &request, options).await?;
 
@@ -327,6 +342,28 @@ pub fn generate_connect_client(
                                 >
                             >,
                             connectrpc_axum_client::ClientError
+                        > {
+                            self.#with_options_name(request, connectrpc_axum_client::CallOptions::new()).await
+                        }
+
+                        /// Make a server streaming RPC call to this method with per-call options.
+                        ///
+                        /// Headers set by the `before` interceptor override headers in `options`.
+                        pub async fn #with_options_name(
+                            &self,
+                            request: &#request_type,
+                            mut options: connectrpc_axum_client::CallOptions,
+                        ) -> Result<
+                            connectrpc_axum_client::ConnectResponse<
+                                connectrpc_axum_client::TypedReceiveStreaming<
+                                    connectrpc_axum_client::FrameDecoder<
+                                        impl ::futures::Stream<Item = Result<connectrpc_axum_client::Bytes, connectrpc_axum_client::ClientError>> + Unpin + use<'_>,
+                                        #response_type
+                                    >,
+                                    #response_type
+                                >
+                            >,
+                            connectrpc_axum_client::ClientError
                         > {
                             let mut request = request.clone();
 
@@ -337,7 +374,7 @@ pub fn generate_connect_client(
                                 interceptor.intercept(&mut ctx, &mut request)?;
                             }
 
-                            let options = connectrpc_axum_client::CallOptions::new().headers(interceptor_headers.clone());
+                            options.headers_mut().extend(interceptor_headers.clone());
                             let response = self.inner.call_server_stream_with_options(#procedure_path, &request, options).await?;
 
                             // Get headers for context
@@ -380,6 +417,18 @@ pub fn generate_connect_client(
                             &self,
                             request: S,
                         ) -> Result<connectrpc_axum_client::ConnectResponse<#response_type>, connectrpc_axum_client::ClientError>
+                        where
+                            S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static,
+                        {
+                            self.#with_options_name(request, connectrpc_axum_client::CallOptions::new()).await
+                        }
+
+                        /// Make a client streaming RPC call to this method with per-call options.
+                        pub async fn #with_options_name<S>(
+                            &self,
+                            request: S,
+                            options: connectrpc_axum_client::CallOptions,
+                        ) -> Result<connectrpc_axum_client::ConnectResponse<#response_type>, connectrpc_axum_client::ClientError>
                         where
                             S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static,
                         {
@@ -390,7 +439,7 @@ pub fn generate_connect_client(
                             let interceptor_error: ::std::sync::Arc<::std::sync::Mutex<Option<connectrpc_axum_client::ClientError>>> =
                                 ::std::sync::Arc::new(::std::sync::Mutex::new(None));
                             let err_capture = interceptor_error.clone();
-                            let request_headers = connectrpc_axum_client::HeaderMap::new();
+                            let request_headers = options.get_headers().clone();
 
                             // Use scan to apply interceptor; abort stream on first error
                             let wrapped = request.scan((), move |_state, mut msg| {
@@ -414,7 +463,6 @@ pub fn generate_connect_client(
                                 }
                             });
 
-                            let options = connectrpc_axum_client::CallOptions::new();
                             let mut response: connectrpc_axum_client::ConnectResponse<#response_type> =
                                 self.inner.call_client_stream_with_options(#procedure_path, wrapped, options).await?;
 
@@ -473,6 +521,29 @@ pub fn generate_connect_client(
                             >,
                             connectrpc_axum_client::ClientError
                         >
+                        where
+                            S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static,
+                        {
+                            self.#with_options_name(request, connectrpc_axum_client::CallOptions::new()).await
+                        }
+
+                        /// Make a bidirectional streaming RPC call to this method with per-call options.
+                        pub async fn #with_options_name<S>(
+                            &self,
+                            request: S,
+                            options: connectrpc_axum_client::CallOptions,
+                        ) -> Result<
+                            connectrpc_axum_client::ConnectResponse<
+                                connectrpc_axum_client::TypedReceiveStreaming<
+                                    connectrpc_axum_client::FrameDecoder<
+                                        impl ::futures::Stream<Item = Result<connectrpc_axum_client::Bytes, connectrpc_axum_client::ClientError>> + Unpin + use<'_, S>,
+                                        #response_type
+                                    >,
+                                    #response_type
+                                >
+                            >,
+                            connectrpc_axum_client::ClientError
+                        >
                         where
                             S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static,
                         {
@@ -481,7 +552,7 @@ pub fn generate_connect_client(
                             // Wrap the input stream with on_send interceptor
                             let on_send = self.#interceptors_field.on_send.clone();
                             let procedure = #procedure_path.to_string();
-                            let request_headers = connectrpc_axum_client::HeaderMap::new();
+                            let request_headers = options.get_headers().clone();
 
                             // Use scan to apply interceptor; abort stream on first error
                             let wrapped = request.scan((), move |_state, mut msg| {
@@ -501,12 +572,11 @@ pub fn generate_connect_client(
                                 }
                             });
 
-                            let options = connectrpc_axum_client::CallOptions::new();
+                            let req_headers = options.get_headers().clone();
                             let response = self.inner.call_bidi_stream_with_options(#procedure_path, wrapped, options).await?;
 
                             // Get headers for context
                             let response_headers = response.metadata().headers().clone();
-                            let req_headers = connectrpc_axum_client::HeaderMap::new();
 
                             // Wrap the response stream with typed interceptor
                             let on_receive = self.#interceptors_field.on_receive.clone();
@@ -527,6 +597,13 @@ pub fn generate_connect_client(
         })
         .collect();
 
+    let client_trait = generate_client_trait(service, method_info);
+    let client_mock = if include_mock {
+        generate_client_mock(service, method_info, &procedures_mod_name)
+    } else {
+        quote! {}
+    };
+
     quote! {
         /// Procedure path constants for the service.
         #[allow(dead_code)]
@@ -670,12 +747,67 @@ pub fn generate_connect_client(
                     self
                 }
 
+                /// Set the accepted compression encodings for responses, most preferred first.
+                pub fn accept_encodings(mut self, encodings: impl IntoIterator<Item = connectrpc_axum_client::CompressionEncoding>) -> Self {
+                    self.inner = self.inner.accept_encodings(encodings);
+                    self
+                }
+
                 /// Set the default timeout for all requests.
                 pub fn timeout(mut self, timeout: ::std::time::Duration) -> Self {
                     self.inner = self.inner.timeout(timeout);
                     self
                 }
 
+                /// Add a header sent with every call.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the header name or value is invalid.
+                pub fn default_header<K, V>(mut self, name: K, value: V) -> Self
+                where
+                    K: TryInto<connectrpc_axum_client::HeaderName>,
+                    K::Error: ::std::fmt::Debug,
+                    V: TryInto<connectrpc_axum_client::HeaderValue>,
+                    V::Error: ::std::fmt::Debug,
+                {
+                    self.inner = self.inner.default_header(name, value);
+                    self
+                }
+
+                /// Set the `User-Agent` header of requests.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the value is invalid.
+                pub fn user_agent<V>(mut self, value: V) -> Self
+                where
+                    V: TryInto<connectrpc_axum_client::HeaderValue>,
+                    V::Error: ::std::fmt::Debug,
+                {
+                    self.inner = self.inner.user_agent(value);
+                    self
+                }
+
+                /// Set the maximum size of a received message, in bytes, before and after
+                /// decompression (default 64 MiB).
+                pub fn max_receive_message_size(mut self, bytes: usize) -> Self {
+                    self.inner = self.inner.max_receive_message_size(bytes);
+                    self
+                }
+
+                /// Set the maximum decompressed size of a response, in bytes.
+                #[deprecated(note = "use `max_receive_message_size`, which sets the same limit")]
+                pub fn with_decompression_limit(self, bytes: usize) -> Self {
+                    self.max_receive_message_size(bytes)
+                }
+
+                /// Set the TLS configuration (custom roots, mTLS, minimum version).
+                pub fn with_tls_config(mut self, config: connectrpc_axum_client::TlsClientConfig) -> Self {
+                    self.inner = self.inner.with_tls_config(config);
+                    self
+                }
+
                 /// Enable HTTP/2 prior knowledge (h2c) for plain HTTP URLs.
                 ///
                 /// Required for bidirectional streaming over `http://` URLs.
@@ -685,6 +817,20 @@ pub fn generate_connect_client(
                     self
                 }
 
+                /// Use HTTP/1.1 only, e.g. behind proxies that don't support HTTP/2.
+                ///
+                /// Bidirectional streaming and gRPC are unavailable over HTTP/1.1.
+                pub fn http1_only(mut self) -> Self {
+                    self.inner = self.inner.http1_only();
+                    self
+                }
+
+                /// Set the HTTP versions the transport speaks.
+                pub fn http_version(mut self, policy: connectrpc_axum_client::HttpVersionPolicy) -> Self {
+                    self.inner = self.inner.http_version(policy);
+                    self
+                }
+
                 #(#builder_interceptor_methods)*
 
                 /// Build the client.
@@ -695,6 +841,408 @@ pub fn generate_connect_client(
                     })
                 }
             }
+
+            #client_trait
+
+            #client_mock
+        }
+    }
+}
+
+/// Boxed response stream type used by the client trait and mock.
+fn boxed_response_stream(response_type: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! {
+        ::futures::stream::BoxStream<'_, Result<#response_type, connectrpc_axum_client::ClientError>>
+    }
+}
+
+/// Generate the client trait (e.g., `HelloWorldServiceClientTrait`) and its
+/// implementation for the generated client.
+///
+/// Streaming responses are boxed so that the trait can be implemented by
+/// mocks and test doubles.
+fn generate_client_trait(
+    service: &ServiceInfo,
+    method_info: &[MethodInfo],
+) -> proc_macro2::TokenStream {
+    let client_name = format_ident!("{}Client", service.name);
+    let trait_name = format_ident!("{}ClientTrait", service.name);
+
+    let mut trait_methods = Vec::new();
+    let mut impl_methods = Vec::new();
+    for method in method_info {
+        let method_name = &method.method_name;
+        let request_type = &method.request_type;
+        let response_type = &method.response_type;
+        let with_options_name = derived_method_ident(method_name, "with_options");
+        let rpc_type = RpcType::from_streaming(method.server_streaming, method.client_streaming);
+
+        match rpc_type {
+            RpcType::Unary => {
+                let output = quote! {
+                    Result<connectrpc_axum_client::ConnectResponse<#response_type>, connectrpc_axum_client::ClientError>
+                };
+                trait_methods.push(quote! {
+                    /// Make a unary RPC call to this method.
+                    fn #method_name(
+                        &self,
+                        request: &#request_type,
+                    ) -> impl ::std::future::Future<Output = #output> + Send {
+                        self.#with_options_name(request, connectrpc_axum_client::CallOptions::new())
+                    }
+
+                    /// Make a unary RPC call to this method with per-call options.
+                    fn #with_options_name(
+                        &self,
+                        request: &#request_type,
+                        options: connectrpc_axum_client::CallOptions,
+                    ) -> impl ::std::future::Future<Output = #output> + Send;
+                });
+                impl_methods.push(quote! {
+                    fn #with_options_name(
+                        &self,
+                        request: &#request_type,
+                        options: connectrpc_axum_client::CallOptions,
+                    ) -> impl ::std::future::Future<Output = #output> + Send {
+                        #client_name::#with_options_name(self, request, options)
+                    }
+                });
+            }
+            RpcType::ServerStream => {
+                let stream = boxed_response_stream(response_type);
+                let output = quote! {
+                    Result<connectrpc_axum_client::ConnectResponse<#stream>, connectrpc_axum_client::ClientError>
+                };
+                trait_methods.push(quote! {
+                    /// Make a server streaming RPC call to this method.
+                    fn #method_name(
+                        &self,
+                        request: &#request_type,
+                    ) -> impl ::std::future::Future<Output = #output> + Send {
+                        self.#with_options_name(request, connectrpc_axum_client::CallOptions::new())
+                    }
+
+                    /// Make a server streaming RPC call to this method with per-call options.
+                    fn #with_options_name(
+                        &self,
+                        request: &#request_type,
+                        options: connectrpc_axum_client::CallOptions,
+                    ) -> impl ::std::future::Future<Output = #output> + Send;
+                });
+                impl_methods.push(quote! {
+                    fn #with_options_name(
+                        &self,
+                        request: &#request_type,
+                        options: connectrpc_axum_client::CallOptions,
+                    ) -> impl ::std::future::Future<Output = #output> + Send {
+                        async move {
+                            let response = #client_name::#with_options_name(self, request, options).await?;
+                            Ok(response.map(::futures::StreamExt::boxed))
+                        }
+                    }
+                });
+            }
+            RpcType::ClientStream => {
+                let output = quote! {
+                    Result<connectrpc_axum_client::ConnectResponse<#response_type>, connectrpc_axum_client::ClientError>
+                };
+                trait_methods.push(quote! {
+                    /// Make a client streaming RPC call to this method.
+                    fn #method_name<S>(
+                        &self,
+                        request: S,
+                    ) -> impl ::std::future::Future<Output = #output> + Send
+                    where
+                        S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static,
+                    {
+                        self.#with_options_name(request, connectrpc_axum_client::CallOptions::new())
+                    }
+
+                    /// Make a client streaming RPC call to this method with per-call options.
+                    fn #with_options_name<S>(
+                        &self,
+                        request: S,
+                        options: connectrpc_axum_client::CallOptions,
+                    ) -> impl ::std::future::Future<Output = #output> + Send
+                    where
+                        S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static;
+                });
+                impl_methods.push(quote! {
+                    fn #with_options_name<S>(
+                        &self,
+                        request: S,
+                        options: connectrpc_axum_client::CallOptions,
+                    ) -> impl ::std::future::Future<Output = #output> + Send
+                    where
+                        S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static,
+                    {
+                        #client_name::#with_options_name(self, request, options)
+                    }
+                });
+            }
+            RpcType::BidiStream => {
+                let stream = boxed_response_stream(response_type);
+                let output = quote! {
+                    Result<connectrpc_axum_client::ConnectResponse<#stream>, connectrpc_axum_client::ClientError>
+                };
+                trait_methods.push(quote! {
+                    /// Make a bidirectional streaming RPC call to this method.
+                    fn #method_name<S>(
+                        &self,
+                        request: S,
+                    ) -> impl ::std::future::Future<Output = #output> + Send
+                    where
+                        S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static,
+                    {
+                        self.#with_options_name(request, connectrpc_axum_client::CallOptions::new())
+                    }
+
+                    /// Make a bidirectional streaming RPC call to this method with per-call options.
+                    fn #with_options_name<S>(
+                        &self,
+                        request: S,
+                        options: connectrpc_axum_client::CallOptions,
+                    ) -> impl ::std::future::Future<Output = #output> + Send
+                    where
+                        S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static;
+                });
+                impl_methods.push(quote! {
+                    fn #with_options_name<S>(
+                        &self,
+                        request: S,
+                        options: connectrpc_axum_client::CallOptions,
+                    ) -> impl ::std::future::Future<Output = #output> + Send
+                    where
+                        S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static,
+                    {
+                        async move {
+                            let response = #client_name::#with_options_name(self, request, options).await?;
+                            Ok(response.map(::futures::StreamExt::boxed))
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    let trait_doc = format!(
+        " The RPC methods of [`{client_name}`], as a trait.\n\n \
+         Depend on this trait instead of the client to swap in a mock or\n \
+         another implementation in tests. Streaming responses are boxed."
+    );
+
+    quote! {
+        #[doc = #trait_doc]
+        pub trait #trait_name: Send + Sync {
+            #(#trait_methods)*
+        }
+
+        impl #trait_name for #client_name {
+            #(#impl_methods)*
+        }
+    }
+}
+
+/// Generate a mock of the client trait (e.g., `HelloWorldServiceClientMock`).
+///
+/// Each method answers with the handler set for it, or fails with
+/// `unimplemented` when none is set.
+fn generate_client_mock(
+    service: &ServiceInfo,
+    method_info: &[MethodInfo],
+    procedures_mod_name: &proc_macro2::Ident,
+) -> proc_macro2::TokenStream {
+    let trait_name = format_ident!("{}ClientTrait", service.name);
+    let mock_name = format_ident!("{}ClientMock", service.name);
+
+    let mut fields = Vec::new();
+    let mut field_names = Vec::new();
+    let mut setters = Vec::new();
+    let mut impl_methods = Vec::new();
+    for method in method_info {
+        let method_name = &method.method_name;
+        let request_type = &method.request_type;
+        let response_type = &method.response_type;
+        let with_options_name = derived_method_ident(method_name, "with_options");
+        let field_name = derived_method_ident(method_name, "handler");
+        let setter_name = prefixed_method_ident("on", method_name);
+        let const_name = method_const_ident(method_name);
+        let procedure_path = quote! { super::#procedures_mod_name::#const_name };
+        let rpc_type = RpcType::from_streaming(method.server_streaming, method.client_streaming);
+
+        let handler_bounds = match rpc_type {
+            // Bidi handlers answer each request message with one response
+            RpcType::Unary | RpcType::BidiStream => quote! {
+                Fn(#request_type) -> Result<#response_type, connectrpc_axum_client::ClientError> + Send + Sync
+            },
+            RpcType::ServerStream => quote! {
+                Fn(#request_type) -> Result<
+                    Vec<Result<#response_type, connectrpc_axum_client::ClientError>>,
+                    connectrpc_axum_client::ClientError,
+                > + Send + Sync
+            },
+            RpcType::ClientStream => quote! {
+                Fn(Vec<#request_type>) -> Result<#response_type, connectrpc_axum_client::ClientError> + Send + Sync
+            },
+        };
+        fields.push(quote! {
+            #field_name: Option<::std::sync::Arc<dyn #handler_bounds>>
+        });
+        field_names.push(field_name.clone());
+
+        let setter_doc = match rpc_type {
+            RpcType::Unary => " Answer calls to this method with `handler`.",
+            RpcType::ServerStream => {
+                " Answer calls to this method with `handler`, which returns the messages to stream back."
+            }
+            RpcType::ClientStream => {
+                " Answer calls to this method with `handler`, which is passed all request messages."
+            }
+            RpcType::BidiStream => {
+                " Answer calls to this method with `handler`, which is called once per request message."
+            }
+        };
+        setters.push(quote! {
+            #[doc = #setter_doc]
+            pub fn #setter_name<F>(mut self, handler: F) -> Self
+            where
+                F: #handler_bounds + 'static,
+            {
+                self.#field_name = Some(::std::sync::Arc::new(handler));
+                self
+            }
+        });
+
+        let not_mocked = quote! {
+            connectrpc_axum_client::ClientError::new(
+                connectrpc_axum_client::Code::Unimplemented,
+                format!("no mock handler for {}", #procedure_path),
+            )
+        };
+        let method_impl = match rpc_type {
+            RpcType::Unary => quote! {
+                fn #with_options_name(
+                    &self,
+                    request: &#request_type,
+                    _options: connectrpc_axum_client::CallOptions,
+                ) -> impl ::std::future::Future<
+                    Output = Result<connectrpc_axum_client::ConnectResponse<#response_type>, connectrpc_axum_client::ClientError>,
+                > + Send {
+                    let result = match &self.#field_name {
+                        Some(handler) => handler(request.clone()).map(mock_response),
+                        None => Err(#not_mocked),
+                    };
+                    ::std::future::ready(result)
+                }
+            },
+            RpcType::ServerStream => {
+                let stream = boxed_response_stream(response_type);
+                quote! {
+                    fn #with_options_name(
+                        &self,
+                        request: &#request_type,
+                        _options: connectrpc_axum_client::CallOptions,
+                    ) -> impl ::std::future::Future<
+                        Output = Result<connectrpc_axum_client::ConnectResponse<#stream>, connectrpc_axum_client::ClientError>,
+                    > + Send {
+                        let result = match &self.#field_name {
+                            Some(handler) => handler(request.clone()).map(|messages| {
+                                mock_response(::futures::StreamExt::boxed(::futures::stream::iter(messages)))
+                            }),
+                            None => Err(#not_mocked),
+                        };
+                        ::std::future::ready(result)
+                    }
+                }
+            }
+            RpcType::ClientStream => quote! {
+                fn #with_options_name<S>(
+                    &self,
+                    request: S,
+                    _options: connectrpc_axum_client::CallOptions,
+                ) -> impl ::std::future::Future<
+                    Output = Result<connectrpc_axum_client::ConnectResponse<#response_type>, connectrpc_axum_client::ClientError>,
+                > + Send
+                where
+                    S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static,
+                {
+                    let handler = self.#field_name.clone();
+                    async move {
+                        let handler = handler.ok_or_else(|| #not_mocked)?;
+                        let messages = ::futures::StreamExt::collect::<Vec<_>>(request).await;
+                        handler(messages).map(mock_response)
+                    }
+                }
+            },
+            RpcType::BidiStream => {
+                let stream = boxed_response_stream(response_type);
+                quote! {
+                    fn #with_options_name<S>(
+                        &self,
+                        request: S,
+                        _options: connectrpc_axum_client::CallOptions,
+                    ) -> impl ::std::future::Future<
+                        Output = Result<connectrpc_axum_client::ConnectResponse<#stream>, connectrpc_axum_client::ClientError>,
+                    > + Send
+                    where
+                        S: ::futures::Stream<Item = #request_type> + Send + Unpin + 'static,
+                    {
+                        let result = match self.#field_name.clone() {
+                            Some(handler) => Ok(mock_response(::futures::StreamExt::boxed(
+                                ::futures::StreamExt::map(request, move |message| handler(message)),
+                            ))),
+                            None => Err(#not_mocked),
+                        };
+                        ::std::future::ready(result)
+                    }
+                }
+            }
+        };
+        impl_methods.push(method_impl);
+    }
+
+    let mock_doc = format!(
+        " Mock implementation of [`{trait_name}`] for unit tests.\n\n \
+         Set a handler per method to return canned responses; methods without\n \
+         a handler fail with `unimplemented`. Responses have empty metadata.\n\n \
+         # Example\n\n \
+         ```ignore\n \
+         let mock = {mock_name}::default()\n \
+         \x20   .on_say_hello(|request| Ok(HelloResponse {{ message: format!(\"Hello, {{}}!\", request.name) }}));\n\n \
+         // Code under test takes `&impl {trait_name}`\n \
+         greet(&mock).await?;\n \
+         ```"
+    );
+
+    quote! {
+        #[doc = #mock_doc]
+        #[derive(Clone, Default)]
+        pub struct #mock_name {
+            #(#fields,)*
+        }
+
+        impl ::std::fmt::Debug for #mock_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.debug_struct(stringify!(#mock_name))
+                    #(.field(stringify!(#field_names), &self.#field_names.is_some()))*
+                    .finish()
+            }
+        }
+
+        impl #mock_name {
+            #(#setters)*
+        }
+
+        /// Wrap a mocked message in a response with empty metadata.
+        fn mock_response<T>(message: T) -> connectrpc_axum_client::ConnectResponse<T> {
+            connectrpc_axum_client::ConnectResponse::new(
+                message,
+                connectrpc_axum_client::Metadata::new(connectrpc_axum_client::HeaderMap::new()),
+            )
+        }
+
+        impl #trait_name for #mock_name {
+            #(#impl_methods)*
         }
     }
 }
@@ -119,6 +119,10 @@ fn test_no_tonic_codegen() {
     assert!(buf.contains("HelloWorldServiceBuilder"));
     assert!(buf.contains("say_hello"));
     assert!(buf.contains("ConnectHandlerWrapper"));
+    assert!(buf.contains("pub fn say_hello_with_config"));
+    assert!(buf.contains("connectrpc_axum :: MethodConfig"));
+    assert!(buf.contains("pub fn say_hello_with_layer < F , T , L >"));
+    assert!(buf.contains("L : connectrpc_axum :: tower :: Layer < axum :: routing :: Route >"));
 }
 
 #[test]
@@ -144,6 +148,8 @@ fn test_with_tonic_codegen() {
     assert!(buf.contains("HelloWorldTonicService"));
     assert!(buf.contains("BoxedCall"));
     assert!(buf.contains("HelloWorldServiceBuilder"));
+    assert!(buf.contains("pub fn with_tonic_interceptor < I >"));
+    assert!(buf.contains("interceptor . intercept (request) ?"));
 }
 
 #[test]
@@ -240,6 +246,46 @@ fn test_no_side_effects_enables_get_routing() {
     assert!(buf.contains("GET+POST enabled"));
 }
 
+#[test]
+fn test_method_descriptor_table() {
+    let mut method_options = MethodOptions::default();
+    method_options.idempotency_level = Some(IdempotencyLevel::NoSideEffects as i32);
+
+    let buf = render_service(
+        "user",
+        "UserService",
+        vec![
+            method(
+                "user",
+                "GetUser",
+                "GetUserRequest",
+                "GetUserResponse",
+                false,
+                false,
+                method_options,
+            ),
+            method(
+                "user",
+                "WatchUsers",
+                "WatchUsersRequest",
+                "WatchUsersResponse",
+                false,
+                true,
+                Default::default(),
+            ),
+        ],
+        AxumConnectServiceGenerator::new().with_connect_server(true),
+    );
+
+    assert!(buf.contains("pub const SERVICE_NAME : & str = \"user.UserService\""));
+    assert!(buf.contains("pub const METHODS : & [connectrpc_axum :: MethodDescriptor]"));
+    assert!(buf.contains("name : \"GetUser\""));
+    assert!(buf.contains("path : \"/user.UserService/GetUser\""));
+    assert!(buf.contains("path : \"/user.UserService/WatchUsers\""));
+    assert!(buf.contains("MethodKind :: from_streaming (false , true)"));
+    assert!(buf.contains("idempotency : connectrpc_axum :: IdempotencyLevel :: NoSideEffects"));
+}
+
 #[test]
 fn test_connect_codegen_enforces_exact_handler_signatures() {
     let buf = render_service(
@@ -325,8 +371,11 @@ fn test_keyword_method_names_generate_valid_rust_identifiers() {
 
     assert!(buf.contains("pub fn r#move"));
     assert!(buf.contains("pub async fn r#move"));
+    assert!(buf.contains("pub async fn move_with_options"));
     assert!(buf.contains("move_interceptors"));
     assert!(!buf.contains("r#move_interceptors"));
+    assert!(buf.contains("pub fn move_with_config"));
+    assert!(buf.contains("pub fn move_with_layer"));
     assert!(buf.contains("pub const MOVE"));
 }
 
@@ -376,3 +425,58 @@ fn test_keyword_method_names_append_to_existing_output_file() {
     assert!(generated.contains("pub async fn r#move"));
     assert!(generated.contains("pub const MOVE"));
 }
+
+#[test]
+fn test_client_trait_and_mock_codegen() {
+    let methods = || {
+        vec![
+            method(
+                "hello",
+                "SayHello",
+                "HelloRequest",
+                "HelloResponse",
+                false,
+                false,
+                Default::default(),
+            ),
+            method(
+                "hello",
+                "Chat",
+                "HelloRequest",
+                "HelloResponse",
+                true,
+                true,
+                Default::default(),
+            ),
+        ]
+    };
+
+    let buf = render_service(
+        "hello",
+        "Greeter",
+        methods(),
+        AxumConnectServiceGenerator::new()
+            .with_connect_client(true)
+            .with_client_mock(true),
+    );
+    assert!(
+        buf.contains("pub trait GreeterClientTrait"),
+        "Generated:\n{}",
+        buf
+    );
+    assert!(buf.contains("impl GreeterClientTrait for GreeterClient"));
+    assert!(buf.contains("pub struct GreeterClientMock"));
+    assert!(buf.contains("impl GreeterClientTrait for GreeterClientMock"));
+    assert!(buf.contains("pub fn on_say_hello"));
+    assert!(buf.contains("pub fn on_chat"));
+
+    // The trait is always generated with the client; the mock is opt-in
+    let buf = render_service(
+        "hello",
+        "Greeter",
+        methods(),
+        AxumConnectServiceGenerator::new().with_connect_client(true),
+    );
+    assert!(buf.contains("pub trait GreeterClientTrait"));
+    assert!(!buf.contains("GreeterClientMock"));
+}
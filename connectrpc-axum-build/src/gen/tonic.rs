@@ -79,6 +79,7 @@ pub fn generate_tonic_code(
                                 #tonic_builder_name {
                                     #(#field_assignments,)*
                                     router: self.router,
+                                    interceptor: self.interceptor,
                                 }
                             }
                         }
@@ -110,6 +111,7 @@ pub fn generate_tonic_code(
                                 #tonic_builder_name {
                                     #(#field_assignments,)*
                                     router: self.router,
+                                    interceptor: self.interceptor,
                                 }
                             }
                         }
@@ -141,6 +143,7 @@ pub fn generate_tonic_code(
                                 #tonic_builder_name {
                                     #(#field_assignments,)*
                                     router: self.router,
+                                    interceptor: self.interceptor,
                                 }
                             }
                         }
@@ -172,6 +175,7 @@ pub fn generate_tonic_code(
                                 #tonic_builder_name {
                                     #(#field_assignments,)*
                                     router: self.router,
+                                    interceptor: self.interceptor,
                                 }
                             }
                         }
@@ -389,6 +393,12 @@ pub fn generate_tonic_code(
                                 &self,
                                 request: ::tonic::Request<#request_type>,
                             ) -> Result<::tonic::Response<#response_type>, ::tonic::Status> {
+                                // Run the tonic interceptor, if any, before anything reads the request
+                                let request = match &self.interceptor {
+                                    Some(interceptor) => interceptor.intercept(request)?,
+                                    None => request,
+                                };
+
                                 // Check if captured parts exist (FromRequestPartsLayer middleware applied)
                                 let captured = request.extensions()
                                     .get::<connectrpc_axum::tonic::CapturedParts>()
@@ -423,6 +433,12 @@ pub fn generate_tonic_code(
                                 &self,
                                 request: ::tonic::Request<#request_type>,
                             ) -> Result<::tonic::Response<Self::#assoc>, ::tonic::Status> {
+                                // Run the tonic interceptor, if any, before anything reads the request
+                                let request = match &self.interceptor {
+                                    Some(interceptor) => interceptor.intercept(request)?,
+                                    None => request,
+                                };
+
                                 // Check if captured parts exist (FromRequestPartsLayer middleware applied)
                                 let captured = request.extensions()
                                     .get::<connectrpc_axum::tonic::CapturedParts>()
@@ -467,6 +483,12 @@ pub fn generate_tonic_code(
                                 &self,
                                 request: ::tonic::Request<::tonic::Streaming<#request_type>>,
                             ) -> Result<::tonic::Response<#response_type>, ::tonic::Status> {
+                                // Run the tonic interceptor, if any, before anything reads the request
+                                let request = match &self.interceptor {
+                                    Some(interceptor) => interceptor.intercept(request)?,
+                                    None => request,
+                                };
+
                                 // Check if captured parts exist (FromRequestPartsLayer middleware applied)
                                 let captured = request.extensions()
                                     .get::<connectrpc_axum::tonic::CapturedParts>()
@@ -503,6 +525,12 @@ pub fn generate_tonic_code(
                                 &self,
                                 request: ::tonic::Request<::tonic::Streaming<#request_type>>,
                             ) -> Result<::tonic::Response<Self::#assoc>, ::tonic::Status> {
+                                // Run the tonic interceptor, if any, before anything reads the request
+                                let request = match &self.interceptor {
+                                    Some(interceptor) => interceptor.intercept(request)?,
+                                    None => request,
+                                };
+
                                 // Check if captured parts exist (FromRequestPartsLayer middleware applied)
                                 let captured = request.extensions()
                                     .get::<connectrpc_axum::tonic::CapturedParts>()
@@ -551,12 +579,14 @@ pub fn generate_tonic_code(
         pub struct #tonic_builder_name<S = ()> {
             #(#tonic_handler_fields,)*
             pub router: axum::Router<S>,
+            pub interceptor: Option<connectrpc_axum::tonic::TonicInterceptor>,
         }
 
         /// Server-side builder with concrete handlers (state captured)
         pub struct #tonic_server_builder_name<S = ()> {
             #(#tonic_server_handler_fields,)*
             pub router: axum::Router<S>,
+            pub interceptor: Option<connectrpc_axum::tonic::TonicInterceptor>,
         }
 
         impl<S> #tonic_builder_name<S>
@@ -567,17 +597,41 @@ pub fn generate_tonic_code(
                 Self {
                     #(#tonic_field_init,)*
                     router: axum::Router::new(),
+                    interceptor: None,
                 }
             }
 
             #(#tonic_builder_methods)*
 
+            /// Run a tonic interceptor on every call, over Connect and gRPC alike.
+            ///
+            /// For Connect calls the interceptor sees the HTTP headers as metadata;
+            /// extensions it inserts can be read by handlers with `Extension<T>`.
+            /// Interceptors run in the order they are added. Only routes registered
+            /// on this builder are intercepted.
+            pub fn with_tonic_interceptor<I>(mut self, interceptor: I) -> Self
+            where
+                I: ::tonic::service::Interceptor + Clone + Send + Sync + 'static,
+            {
+                let interceptor = connectrpc_axum::tonic::TonicInterceptor::new(interceptor);
+                self.interceptor = Some(match self.interceptor {
+                    Some(first) => first.then(interceptor),
+                    None => interceptor,
+                });
+                self
+            }
+
             /// Apply state to router and handlers, returning server builder with concrete handlers
             pub fn with_state<S2>(self, state: S) -> #tonic_server_builder_name<S2> {
-                let router = self.router.with_state(state.clone());
+                let router = match &self.interceptor {
+                    Some(interceptor) => self.router.layer(interceptor.clone()),
+                    None => self.router,
+                };
+                let router = router.with_state(state.clone());
                 #tonic_server_builder_name {
                     #(#with_state_field_mapping,)*
                     router,
+                    interceptor: self.interceptor,
                 }
             }
         }
@@ -594,11 +648,15 @@ pub fn generate_tonic_code(
                 axum::Router,
                 #server_mod_name::#tonic_server_type_name<#tonic_service_name>
             ) {
-                let router = self.router;
+                let router = match &self.interceptor {
+                    Some(interceptor) => self.router.layer(interceptor.clone()),
+                    None => self.router,
+                };
                 #(#tonic_build_handlers_no_state)*
 
                 let tonic_service = #tonic_service_name {
                     #(#service_field_names,)*
+                    interceptor: self.interceptor,
                 };
 
                 let grpc_server = #server_mod_name::#tonic_server_type_name::new(tonic_service);
@@ -617,6 +675,7 @@ pub fn generate_tonic_code(
 
                 let tonic_service = #tonic_service_name {
                     #(#service_field_names,)*
+                    interceptor: self.interceptor,
                 };
 
                 let grpc_server = #server_mod_name::#tonic_server_type_name::new(tonic_service);
@@ -751,6 +810,7 @@ pub fn generate_tonic_code(
         #[allow(dead_code)]
         pub struct #tonic_service_name {
             #(#tonic_service_handler_fields,)*
+            interceptor: Option<connectrpc_axum::tonic::TonicInterceptor>,
         }
 
         // Implement the tonic service trait for the generated boxed service.
@@ -0,0 +1,1748 @@
+//! `Validate` impls generated from `buf.validate` annotations.
+//!
+//! prost drops extensions when decoding descriptors, so the descriptor set is
+//! decoded again here with minimal message definitions that keep the
+//! `buf.validate` options (extension number 1159 on field, oneof and message
+//! options).
+//!
+//! The standard rules for scalars, strings, bytes, enums, repeated fields and
+//! maps are supported, along with `required` and `ignore`. Rules that need a
+//! runtime (CEL expressions, `pattern`, well-known string formats, ...) fail
+//! the build rather than being skipped silently.
+
+use convert_case::{Case, Casing};
+use proc_macro2::{Literal, TokenStream};
+use prost::Message;
+use quote::quote;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Display;
+use std::io::Result;
+
+use super::rust_ident;
+use crate::merge::append_generated_section;
+use crate::schema::sanitize_identifier;
+use descriptor::{DescriptorProto, FieldDescriptorProto, FileDescriptorSet};
+use rules::{FieldRules, RuleType};
+
+/// Generate `Validate` impls for every message with `buf.validate` rules and
+/// append them, with a `register_validators` function, to each package file.
+pub(crate) fn append_validation_to_out_dir(descriptor_bytes: &[u8], out_dir: &str) -> Result<()> {
+    let fds = FileDescriptorSet::decode(descriptor_bytes)
+        .map_err(|e| std::io::Error::other(format!("decode descriptor: {e}")))?;
+
+    for (file_stem, generated) in generate(&fds).map_err(std::io::Error::other)? {
+        let path = format!("{out_dir}/{file_stem}.rs");
+        if !std::path::Path::new(&path).exists() {
+            println!(
+                "cargo:warning=Skipping generated validation code for '{}': no matching prost output file.",
+                file_stem
+            );
+            continue;
+        }
+
+        append_generated_section(
+            std::path::Path::new(&path),
+            "// --- buf.validate rules ---",
+            &generated,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Generated code keyed by package file stem.
+fn generate(fds: &FileDescriptorSet) -> std::result::Result<BTreeMap<String, String>, String> {
+    let index = Index::new(fds);
+    let needs = index.messages_needing_validation()?;
+
+    let mut impls = BTreeMap::<String, Vec<TokenStream>>::new();
+    let mut registered = BTreeMap::<String, Vec<TokenStream>>::new();
+    for (fqn, message) in &index.messages {
+        if !needs.contains(fqn.as_str()) {
+            continue;
+        }
+
+        let file_stem = if message.package.is_empty() {
+            "_".to_string()
+        } else {
+            message.package.clone()
+        };
+        let ty = message.rust_path();
+        impls
+            .entry(file_stem.clone())
+            .or_default()
+            .push(MessageGen::new(&index, &needs, fqn, message).generate()?);
+        registered
+            .entry(file_stem)
+            .or_default()
+            .push(quote! { .message::<#ty>() });
+    }
+
+    Ok(impls
+        .into_iter()
+        .map(|(file_stem, impls)| {
+            let registered = &registered[&file_stem];
+            let code = quote! {
+                #(#impls)*
+
+                /// Register the `buf.validate` rules of this package's messages.
+                pub fn register_validators(
+                    interceptor: connectrpc_axum::validate::ValidationInterceptor,
+                ) -> connectrpc_axum::validate::ValidationInterceptor {
+                    interceptor #(#registered)*
+                }
+            };
+            (file_stem, code.to_string())
+        })
+        .collect())
+}
+
+// ============================================================================
+// Descriptor index
+// ============================================================================
+
+struct Index<'a> {
+    messages: BTreeMap<String, MessageInfo<'a>>,
+    enums: HashMap<String, Vec<i32>>,
+}
+
+struct MessageInfo<'a> {
+    package: String,
+    scoped_name: Vec<String>,
+    explicit_presence: bool,
+    descriptor: &'a DescriptorProto,
+}
+
+impl MessageInfo<'_> {
+    /// Path of the prost type, relative to its package module.
+    fn rust_path(&self) -> TokenStream {
+        let (name, parents) = self.scoped_name.split_last().expect("message has a name");
+        let modules = parents.iter().map(|parent| module_ident(parent));
+        let name = rust_ident(&sanitize_identifier(&name.to_case(Case::Pascal)));
+        quote! { #(#modules::)* #name }
+    }
+
+    /// Path of the prost module holding the message's nested types.
+    fn module_path(&self) -> TokenStream {
+        let modules = self.scoped_name.iter().map(|name| module_ident(name));
+        quote! { #(#modules)::* }
+    }
+
+    fn is_map_entry(&self) -> bool {
+        self.descriptor
+            .options
+            .as_ref()
+            .and_then(|options| options.map_entry)
+            .unwrap_or(false)
+    }
+}
+
+fn module_ident(name: &str) -> proc_macro2::Ident {
+    rust_ident(&sanitize_identifier(&name.to_case(Case::Snake)))
+}
+
+impl<'a> Index<'a> {
+    fn new(fds: &'a FileDescriptorSet) -> Self {
+        let mut index = Self {
+            messages: BTreeMap::new(),
+            enums: HashMap::new(),
+        };
+        for file in &fds.file {
+            let package = file.package.clone().unwrap_or_default();
+            let explicit_presence = file.syntax.as_deref() != Some("proto3");
+            for message in &file.message_type {
+                index.register_message(&package, explicit_presence, &[], message);
+            }
+            for en in &file.enum_type {
+                index.register_enum(&package, &[], en);
+            }
+        }
+        index
+    }
+
+    fn register_message(
+        &mut self,
+        package: &str,
+        explicit_presence: bool,
+        parents: &[String],
+        message: &'a DescriptorProto,
+    ) {
+        let Some(name) = message.name.clone() else {
+            return;
+        };
+        let mut scoped_name = parents.to_vec();
+        scoped_name.push(name);
+
+        for nested in &message.nested_type {
+            self.register_message(package, explicit_presence, &scoped_name, nested);
+        }
+        for en in &message.enum_type {
+            self.register_enum(package, &scoped_name, en);
+        }
+        self.messages.insert(
+            qualified_name(package, &scoped_name),
+            MessageInfo {
+                package: package.to_string(),
+                scoped_name,
+                explicit_presence,
+                descriptor: message,
+            },
+        );
+    }
+
+    fn register_enum(
+        &mut self,
+        package: &str,
+        parents: &[String],
+        en: &descriptor::EnumDescriptorProto,
+    ) {
+        let Some(name) = en.name.clone() else {
+            return;
+        };
+        let mut scoped_name = parents.to_vec();
+        scoped_name.push(name);
+        self.enums.insert(
+            qualified_name(package, &scoped_name),
+            en.value.iter().filter_map(|value| value.number).collect(),
+        );
+    }
+
+    /// Messages with rules of their own or in a message field, transitively.
+    fn messages_needing_validation(&self) -> std::result::Result<HashSet<&str>, String> {
+        let mut needs = HashSet::new();
+        for (fqn, message) in &self.messages {
+            if message.is_map_entry() {
+                continue;
+            }
+            if message_rules(message.descriptor, fqn)?.disabled == Some(true) {
+                continue;
+            }
+            let has_rules = message.descriptor.field.iter().any(|field| {
+                field
+                    .options
+                    .as_ref()
+                    .is_some_and(|options| options.rules.is_some())
+            }) || message.descriptor.oneof_decl.iter().any(|oneof| {
+                oneof
+                    .options
+                    .as_ref()
+                    .is_some_and(|options| options.rules.is_some())
+            });
+            if has_rules {
+                needs.insert(fqn.as_str());
+            }
+        }
+
+        // Messages whose fields hold messages that need validation
+        loop {
+            let mut changed = false;
+            for (fqn, message) in &self.messages {
+                if needs.contains(fqn.as_str())
+                    || message.is_map_entry()
+                    || message_rules(message.descriptor, fqn)?.disabled == Some(true)
+                {
+                    continue;
+                }
+                let nested = message.descriptor.field.iter().any(|field| {
+                    self.nested_message(field)
+                        .is_some_and(|nested| needs.contains(nested))
+                });
+                if nested {
+                    needs.insert(fqn.as_str());
+                    changed = true;
+                }
+            }
+            if !changed {
+                return Ok(needs);
+            }
+        }
+    }
+
+    /// The message type a field holds, looking through map entries to the value.
+    fn nested_message(&self, field: &FieldDescriptorProto) -> Option<&str> {
+        let type_name = field.type_name.as_deref()?;
+        if field.r#type != Some(TYPE_MESSAGE) {
+            return None;
+        }
+        match self.messages.get_key_value(type_name) {
+            Some((_, entry)) if entry.is_map_entry() => {
+                let value = entry
+                    .descriptor
+                    .field
+                    .iter()
+                    .find(|f| f.number == Some(2))?;
+                self.nested_message(value)
+            }
+            Some((fqn, _)) => Some(fqn.as_str()),
+            None => None,
+        }
+    }
+
+    fn map_entry(&self, field: &FieldDescriptorProto) -> Option<&MessageInfo<'a>> {
+        if field.label != Some(LABEL_REPEATED) || field.r#type != Some(TYPE_MESSAGE) {
+            return None;
+        }
+        self.messages
+            .get(field.type_name.as_deref()?)
+            .filter(|entry| entry.is_map_entry())
+    }
+}
+
+fn qualified_name(package: &str, scoped_name: &[String]) -> String {
+    if package.is_empty() {
+        format!(".{}", scoped_name.join("."))
+    } else {
+        format!(".{package}.{}", scoped_name.join("."))
+    }
+}
+
+// ============================================================================
+// Code generation
+// ============================================================================
+
+const TYPE_DOUBLE: i32 = 1;
+const TYPE_FLOAT: i32 = 2;
+const TYPE_INT64: i32 = 3;
+const TYPE_UINT64: i32 = 4;
+const TYPE_INT32: i32 = 5;
+const TYPE_FIXED64: i32 = 6;
+const TYPE_FIXED32: i32 = 7;
+const TYPE_BOOL: i32 = 8;
+const TYPE_STRING: i32 = 9;
+const TYPE_MESSAGE: i32 = 11;
+const TYPE_BYTES: i32 = 12;
+const TYPE_UINT32: i32 = 13;
+const TYPE_ENUM: i32 = 14;
+const TYPE_SFIXED32: i32 = 15;
+const TYPE_SFIXED64: i32 = 16;
+const TYPE_SINT32: i32 = 17;
+const TYPE_SINT64: i32 = 18;
+
+const LABEL_REPEATED: i32 = 3;
+
+/// `buf.validate.Ignore` values.
+const IGNORE_IF_ZERO_VALUE: i32 = 1;
+const IGNORE_IF_DEFAULT_VALUE: i32 = 2;
+const IGNORE_ALWAYS: i32 = 3;
+
+/// How a field is laid out in the prost struct.
+enum Shape<'a> {
+    /// `T`
+    Implicit,
+    /// `Option<T>`
+    Optional,
+    /// `Vec<T>`
+    Repeated,
+    /// `HashMap<K, V>` / `BTreeMap<K, V>`
+    Map {
+        key: &'a FieldDescriptorProto,
+        value: &'a FieldDescriptorProto,
+    },
+    /// A variant of the prost enum for the oneof.
+    Oneof {
+        field: TokenStream,
+        variant: TokenStream,
+    },
+}
+
+struct MessageGen<'a, 'b> {
+    index: &'b Index<'a>,
+    needs: &'b HashSet<&'b str>,
+    fqn: &'b str,
+    message: &'b MessageInfo<'a>,
+}
+
+impl<'a, 'b> MessageGen<'a, 'b> {
+    fn new(
+        index: &'b Index<'a>,
+        needs: &'b HashSet<&'b str>,
+        fqn: &'b str,
+        message: &'b MessageInfo<'a>,
+    ) -> Self {
+        Self {
+            index,
+            needs,
+            fqn,
+            message,
+        }
+    }
+
+    fn generate(&self) -> std::result::Result<TokenStream, String> {
+        let ty = self.message.rust_path();
+        let mut checks = Vec::new();
+
+        for (i, oneof) in self.message.descriptor.oneof_decl.iter().enumerate() {
+            let Some(raw) = oneof.options.as_ref().and_then(|o| o.rules.as_deref()) else {
+                continue;
+            };
+            let name = oneof.name.clone().unwrap_or_default();
+            check_tags(raw, &[1])
+                .map_err(|rule| self.unsupported(&name, &format!("oneof rule #{rule}")))?;
+            let rules = rules::OneofRules::decode(raw).map_err(|e| e.to_string())?;
+            let is_synthetic = self.message.descriptor.field.iter().any(|field| {
+                field.oneof_index == Some(i as i32) && field.proto3_optional == Some(true)
+            });
+            if rules.required == Some(true) && !is_synthetic {
+                let field = module_ident(&name);
+                checks.push(quote! {
+                    if self.#field.is_none() {
+                        violations.push(connectrpc_axum::validate::FieldViolation::new(
+                            connectrpc_axum::validate::field_path(prefix, #name),
+                            "exactly one field is required in oneof",
+                        ));
+                    }
+                });
+            }
+        }
+
+        for field in &self.message.descriptor.field {
+            checks.push(self.field(field)?);
+        }
+
+        Ok(quote! {
+            impl connectrpc_axum::validate::Validate for #ty {
+                #[allow(clippy::all)]
+                fn collect_violations(
+                    &self,
+                    prefix: &str,
+                    violations: &mut ::std::vec::Vec<connectrpc_axum::validate::FieldViolation>,
+                ) {
+                    #[allow(unused_imports)]
+                    use connectrpc_axum::validate::Validate as _;
+                    #(#checks)*
+                }
+            }
+        })
+    }
+
+    fn unsupported(&self, field: &str, rule: &str) -> String {
+        format!(
+            "unsupported buf.validate rule on {}.{field}: {rule}",
+            self.fqn.trim_start_matches('.')
+        )
+    }
+
+    fn field(&self, field: &FieldDescriptorProto) -> std::result::Result<TokenStream, String> {
+        let name = field.name.clone().unwrap_or_default();
+        let raw = field.options.as_ref().and_then(|o| o.rules.as_deref());
+        if let Some(raw) = raw {
+            check_field_rules(raw, false).map_err(|rule| self.unsupported(&name, &rule))?;
+        }
+        let rules = match raw {
+            Some(raw) => FieldRules::decode(raw).map_err(|e| e.to_string())?,
+            None => FieldRules::default(),
+        };
+        if rules.ignore == Some(IGNORE_ALWAYS) {
+            return Ok(quote! {});
+        }
+        let ignore_zero = matches!(
+            rules.ignore,
+            Some(IGNORE_IF_ZERO_VALUE | IGNORE_IF_DEFAULT_VALUE)
+        );
+
+        let ident = rust_ident(&sanitize_identifier(&name.to_case(Case::Snake)));
+        let path = quote! { connectrpc_axum::validate::field_path(prefix, #name) };
+        let shape = self.shape(field);
+        let nested = self
+            .index
+            .nested_message(field)
+            .is_some_and(|nested| self.needs.contains(nested));
+        let required = rules.required == Some(true);
+
+        match shape {
+            Shape::Implicit | Shape::Optional | Shape::Oneof { .. } => {
+                if matches!(rules.r#type, Some(RuleType::Repeated(_) | RuleType::Map(_))) {
+                    return Err(self.unsupported(&name, "repeated/map rules on a singular field"));
+                }
+                let checks = match &rules.r#type {
+                    Some(rule_type) => self.value_checks(&name, field, rule_type, &path)?,
+                    None => quote! {},
+                };
+                let recurse = if nested {
+                    quote! { value.collect_violations(&#path, violations); }
+                } else {
+                    quote! {}
+                };
+
+                Ok(match shape {
+                    Shape::Implicit => {
+                        let zero =
+                            zero_check(field, &name).map_err(|e| self.unsupported(&name, &e))?;
+                        let required = required.then(|| {
+                            quote! {
+                                if #zero {
+                                    violations.push(connectrpc_axum::validate::FieldViolation::new(
+                                        #path,
+                                        "value is required",
+                                    ));
+                                }
+                            }
+                        });
+                        let checks = if ignore_zero {
+                            quote! { if !(#zero) { #checks } }
+                        } else {
+                            checks
+                        };
+                        quote! {
+                            {
+                                let value = &self.#ident;
+                                #required
+                                #checks
+                            }
+                        }
+                    }
+                    Shape::Optional => {
+                        let required = required.then(|| {
+                            quote! {
+                                if self.#ident.is_none() {
+                                    violations.push(connectrpc_axum::validate::FieldViolation::new(
+                                        #path,
+                                        "value is required",
+                                    ));
+                                }
+                            }
+                        });
+                        quote! {
+                            #required
+                            if let Some(value) = &self.#ident {
+                                #checks
+                                #recurse
+                            }
+                        }
+                    }
+                    Shape::Oneof { field, variant } => {
+                        let required = required.then(|| {
+                            quote! {
+                                if !matches!(&self.#field, Some(#variant(_))) {
+                                    violations.push(connectrpc_axum::validate::FieldViolation::new(
+                                        #path,
+                                        "value is required",
+                                    ));
+                                }
+                            }
+                        });
+                        quote! {
+                            #required
+                            if let Some(#variant(value)) = &self.#field {
+                                #checks
+                                #recurse
+                            }
+                        }
+                    }
+                    Shape::Repeated | Shape::Map { .. } => unreachable!(),
+                })
+            }
+            Shape::Repeated => {
+                let (bounds, items) = match &rules.r#type {
+                    Some(RuleType::Repeated(repeated)) => {
+                        let mut bounds = Vec::new();
+                        if let Some(min) = repeated.min_items {
+                            let description = format!("value must contain at least {min} item(s)");
+                            let min = Literal::u64_unsuffixed(min);
+                            bounds.push(violation(
+                                quote! { values.len() < #min },
+                                &path,
+                                &description,
+                            ));
+                        }
+                        if let Some(max) = repeated.max_items {
+                            let description =
+                                format!("value must contain no more than {max} item(s)");
+                            let max = Literal::u64_unsuffixed(max);
+                            bounds.push(violation(
+                                quote! { values.len() > #max },
+                                &path,
+                                &description,
+                            ));
+                        }
+                        if repeated.unique == Some(true) {
+                            if field.r#type == Some(TYPE_MESSAGE) {
+                                return Err(self.unsupported(&name, "repeated.unique on messages"));
+                            }
+                            bounds.push(violation(
+                                quote! {
+                                    values.iter().enumerate().any(|(i, item)| values[..i].contains(item))
+                                },
+                                &path,
+                                "repeated value must contain unique items",
+                            ));
+                        }
+                        let items = match repeated.items.as_deref() {
+                            Some(items) => self.element_checks(&name, field, items)?,
+                            None => quote! {},
+                        };
+                        (bounds, items)
+                    }
+                    Some(_) => {
+                        return Err(self.unsupported(
+                            &name,
+                            "scalar rules on a repeated field (use repeated.items)",
+                        ));
+                    }
+                    None => (Vec::new(), quote! {}),
+                };
+
+                let required = required
+                    .then(|| violation(quote! { values.is_empty() }, &path, "value is required"));
+                let bounds = if ignore_zero && !bounds.is_empty() {
+                    quote! { if !values.is_empty() { #(#bounds)* } }
+                } else {
+                    quote! { #(#bounds)* }
+                };
+                let element_path = quote! { format!("{}[{}]", #path, index) };
+                let recurse = nested.then(|| {
+                    quote! { value.collect_violations(&#element_path, violations); }
+                });
+                let items = if items.is_empty() && recurse.is_none() {
+                    quote! {}
+                } else {
+                    let items = replace_path(items, &element_path);
+                    quote! {
+                        for (index, value) in values.iter().enumerate() {
+                            #items
+                            #recurse
+                        }
+                    }
+                };
+
+                Ok(quote! {
+                    {
+                        let values = &self.#ident;
+                        #required
+                        #bounds
+                        #items
+                    }
+                })
+            }
+            Shape::Map { key, value } => {
+                let (bounds, key_checks, value_checks) = match &rules.r#type {
+                    Some(RuleType::Map(map)) => {
+                        let mut bounds = Vec::new();
+                        if let Some(min) = map.min_pairs {
+                            let description = format!("map must be at least {min} entries");
+                            let min = Literal::u64_unsuffixed(min);
+                            bounds.push(violation(
+                                quote! { values.len() < #min },
+                                &path,
+                                &description,
+                            ));
+                        }
+                        if let Some(max) = map.max_pairs {
+                            let description = format!("map must be at most {max} entries");
+                            let max = Literal::u64_unsuffixed(max);
+                            bounds.push(violation(
+                                quote! { values.len() > #max },
+                                &path,
+                                &description,
+                            ));
+                        }
+                        let keys = match map.keys.as_deref() {
+                            Some(keys) => self.element_checks(&name, key, keys)?,
+                            None => quote! {},
+                        };
+                        let values = match map.values.as_deref() {
+                            Some(values) => self.element_checks(&name, value, values)?,
+                            None => quote! {},
+                        };
+                        (bounds, keys, values)
+                    }
+                    Some(_) => return Err(self.unsupported(&name, "non-map rules on a map field")),
+                    None => (Vec::new(), quote! {}, quote! {}),
+                };
+
+                let required = required
+                    .then(|| violation(quote! { values.is_empty() }, &path, "value is required"));
+                let bounds = if ignore_zero && !bounds.is_empty() {
+                    quote! { if !values.is_empty() { #(#bounds)* } }
+                } else {
+                    quote! { #(#bounds)* }
+                };
+                let entry_path = quote! { format!("{}[{:?}]", #path, key) };
+                let recurse = nested.then(|| {
+                    quote! { value.collect_violations(&#entry_path, violations); }
+                });
+                let entries =
+                    if key_checks.is_empty() && value_checks.is_empty() && recurse.is_none() {
+                        quote! {}
+                    } else {
+                        let key_checks = replace_path(key_checks, &entry_path);
+                        let value_checks = replace_path(value_checks, &entry_path);
+                        quote! {
+                            for (key, value) in values.iter() {
+                                {
+                                    let value = key;
+                                    #key_checks
+                                }
+                                #value_checks
+                                #recurse
+                            }
+                        }
+                    };
+
+                Ok(quote! {
+                    {
+                        let values = &self.#ident;
+                        #required
+                        #bounds
+                        #entries
+                    }
+                })
+            }
+        }
+    }
+
+    fn shape(&self, field: &'b FieldDescriptorProto) -> Shape<'b> {
+        if let Some(entry) = self.index.map_entry(field) {
+            let key = entry.descriptor.field.iter().find(|f| f.number == Some(1));
+            let value = entry.descriptor.field.iter().find(|f| f.number == Some(2));
+            if let (Some(key), Some(value)) = (key, value) {
+                return Shape::Map { key, value };
+            }
+        }
+        if field.label == Some(LABEL_REPEATED) {
+            return Shape::Repeated;
+        }
+        if field.proto3_optional == Some(true) {
+            return Shape::Optional;
+        }
+        if let Some(i) = field.oneof_index
+            && let Some(oneof) = self.message.descriptor.oneof_decl.get(i as usize)
+        {
+            let oneof_name = oneof.name.clone().unwrap_or_default();
+            let oneof_field = module_ident(&oneof_name);
+            let module = self.message.module_path();
+            let oneof_ty = rust_ident(&sanitize_identifier(&oneof_name.to_case(Case::Pascal)));
+            let variant = rust_ident(&sanitize_identifier(
+                &field.name.clone().unwrap_or_default().to_case(Case::Pascal),
+            ));
+            return Shape::Oneof {
+                field: quote! { #oneof_field },
+                variant: quote! { #module::#oneof_ty::#variant },
+            };
+        }
+        if field.r#type == Some(TYPE_MESSAGE) || self.message.explicit_presence {
+            return Shape::Optional;
+        }
+        Shape::Implicit
+    }
+
+    /// Checks for the elements of a repeated field or the keys/values of a map,
+    /// with `value` bound to the element and the path left as `__path__`.
+    fn element_checks(
+        &self,
+        name: &str,
+        field: &FieldDescriptorProto,
+        rules: &FieldRules,
+    ) -> std::result::Result<TokenStream, String> {
+        if rules.ignore == Some(IGNORE_ALWAYS) {
+            return Ok(quote! {});
+        }
+        let Some(rule_type) = &rules.r#type else {
+            return Ok(quote! {});
+        };
+        if matches!(rule_type, RuleType::Repeated(_) | RuleType::Map(_)) {
+            return Err(self.unsupported(name, "nested repeated/map rules"));
+        }
+        let checks = self.value_checks(name, field, rule_type, &quote! { __path__ })?;
+        if matches!(
+            rules.ignore,
+            Some(IGNORE_IF_ZERO_VALUE | IGNORE_IF_DEFAULT_VALUE)
+        ) {
+            let zero = zero_check(field, name).map_err(|e| self.unsupported(name, &e))?;
+            return Ok(quote! { if !(#zero) { #checks } });
+        }
+        Ok(checks)
+    }
+
+    /// Checks of a type's rules against `value`, a reference to the field value.
+    fn value_checks(
+        &self,
+        name: &str,
+        field: &FieldDescriptorProto,
+        rule_type: &RuleType,
+        path: &TokenStream,
+    ) -> std::result::Result<TokenStream, String> {
+        let ty = field.r#type.unwrap_or_default();
+        let mismatch = || {
+            self.unsupported(
+                name,
+                &format!("{} rules on a field of another type", rule_type.name()),
+            )
+        };
+        let checks = match rule_type {
+            RuleType::Float(r) if ty == TYPE_FLOAT => numeric_checks(&r.view(), path, true),
+            RuleType::Double(r) if ty == TYPE_DOUBLE => numeric_checks(&r.view(), path, true),
+            RuleType::Int32(r) if ty == TYPE_INT32 => numeric_checks(&r.view(), path, false),
+            RuleType::Int64(r) if ty == TYPE_INT64 => numeric_checks(&r.view(), path, false),
+            RuleType::Uint32(r) if ty == TYPE_UINT32 => numeric_checks(&r.view(), path, false),
+            RuleType::Uint64(r) if ty == TYPE_UINT64 => numeric_checks(&r.view(), path, false),
+            RuleType::Sint32(r) if ty == TYPE_SINT32 => numeric_checks(&r.view(), path, false),
+            RuleType::Sint64(r) if ty == TYPE_SINT64 => numeric_checks(&r.view(), path, false),
+            RuleType::Fixed32(r) if ty == TYPE_FIXED32 => numeric_checks(&r.view(), path, false),
+            RuleType::Fixed64(r) if ty == TYPE_FIXED64 => numeric_checks(&r.view(), path, false),
+            RuleType::Sfixed32(r) if ty == TYPE_SFIXED32 => numeric_checks(&r.view(), path, false),
+            RuleType::Sfixed64(r) if ty == TYPE_SFIXED64 => numeric_checks(&r.view(), path, false),
+            RuleType::Bool(r) if ty == TYPE_BOOL => Ok(r
+                .equal
+                .map(|equal| {
+                    violation(
+                        quote! { *value != #equal },
+                        path,
+                        &format!("value must equal {equal}"),
+                    )
+                })
+                .into_iter()
+                .collect()),
+            RuleType::String(r) if ty == TYPE_STRING => string_checks(r, path),
+            RuleType::Bytes(r) if ty == TYPE_BYTES => bytes_checks(r, path),
+            RuleType::Enum(r) if ty == TYPE_ENUM => {
+                let type_name = field.type_name.as_deref().unwrap_or_default();
+                let defined = self.index.enums.get(type_name);
+                if r.defined_only == Some(true) && defined.is_none() {
+                    return Err(self.unsupported(name, &format!("unknown enum {type_name}")));
+                }
+                enum_checks(r, defined.map(Vec::as_slice).unwrap_or_default(), path)
+            }
+            _ => return Err(mismatch()),
+        }?;
+        Ok(quote! { #(#checks)* })
+    }
+}
+
+/// Condition that is true when `value` holds the zero value of the field's type.
+fn zero_check(
+    field: &FieldDescriptorProto,
+    name: &str,
+) -> std::result::Result<TokenStream, String> {
+    Ok(match field.r#type.unwrap_or_default() {
+        TYPE_FLOAT | TYPE_DOUBLE => quote! { *value == 0.0 },
+        TYPE_BOOL => quote! { !*value },
+        TYPE_STRING | TYPE_BYTES => quote! { value.is_empty() },
+        TYPE_MESSAGE => return Err(format!("no zero value for message field {name}")),
+        _ => quote! { *value == 0 },
+    })
+}
+
+fn violation(condition: TokenStream, path: &TokenStream, description: &str) -> TokenStream {
+    quote! {
+        if #condition {
+            violations.push(connectrpc_axum::validate::FieldViolation::new(#path, #description));
+        }
+    }
+}
+
+/// Substitute the `__path__` placeholder left by [`MessageGen::element_checks`].
+fn replace_path(tokens: TokenStream, path: &TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) if ident == "__path__" => path.clone(),
+            proc_macro2::TokenTree::Group(group) => {
+                let mut replaced =
+                    proc_macro2::Group::new(group.delimiter(), replace_path(group.stream(), path));
+                replaced.set_span(group.span());
+                proc_macro2::TokenTree::Group(replaced).into()
+            }
+            token => token.into(),
+        })
+        .collect()
+}
+
+/// A numeric bound rendered into generated code.
+trait Bound: Copy + PartialOrd + Display {
+    fn literal(self) -> Option<Literal>;
+}
+
+macro_rules! int_bound {
+    ($($ty:ty => $literal:ident),*) => {
+        $(impl Bound for $ty {
+            fn literal(self) -> Option<Literal> {
+                Some(Literal::$literal(self))
+            }
+        })*
+    };
+}
+
+int_bound!(i32 => i32_unsuffixed, i64 => i64_unsuffixed, u32 => u32_unsuffixed, u64 => u64_unsuffixed);
+
+impl Bound for f32 {
+    fn literal(self) -> Option<Literal> {
+        self.is_finite().then(|| Literal::f32_unsuffixed(self))
+    }
+}
+
+impl Bound for f64 {
+    fn literal(self) -> Option<Literal> {
+        self.is_finite().then(|| Literal::f64_unsuffixed(self))
+    }
+}
+
+struct NumericView<T> {
+    equal: Option<T>,
+    lt: Option<T>,
+    lte: Option<T>,
+    gt: Option<T>,
+    gte: Option<T>,
+    one_of: Vec<T>,
+    not_in: Vec<T>,
+    finite: bool,
+}
+
+fn literal<T: Bound>(value: T) -> std::result::Result<Literal, String> {
+    value
+        .literal()
+        .ok_or_else(|| format!("non-finite bound {value}"))
+}
+
+fn list<T: Display>(values: &[T]) -> String {
+    let values: Vec<String> = values.iter().map(ToString::to_string).collect();
+    format!("[{}]", values.join(", "))
+}
+
+fn numeric_checks<T: Bound>(
+    rules: &NumericView<T>,
+    path: &TokenStream,
+    float: bool,
+) -> std::result::Result<Vec<TokenStream>, String> {
+    let mut checks = Vec::new();
+    // NaN fails every comparison, so it never satisfies a bound
+    let nan = if float {
+        quote! { value.is_nan() || }
+    } else {
+        quote! {}
+    };
+
+    if let Some(equal) = rules.equal {
+        let lit = literal(equal)?;
+        checks.push(violation(
+            quote! { *value != #lit },
+            path,
+            &format!("value must equal {equal}"),
+        ));
+    }
+
+    let lower = rules
+        .gt
+        .map(|v| (v, true))
+        .or(rules.gte.map(|v| (v, false)));
+    let upper = rules
+        .lt
+        .map(|v| (v, true))
+        .or(rules.lte.map(|v| (v, false)));
+    let below = |(bound, exclusive): (T, bool)| -> std::result::Result<TokenStream, String> {
+        let lit = literal(bound)?;
+        Ok(if exclusive {
+            quote! { *value <= #lit }
+        } else {
+            quote! { *value < #lit }
+        })
+    };
+    let above = |(bound, exclusive): (T, bool)| -> std::result::Result<TokenStream, String> {
+        let lit = literal(bound)?;
+        Ok(if exclusive {
+            quote! { *value >= #lit }
+        } else {
+            quote! { *value > #lit }
+        })
+    };
+    let describe_lower = |(bound, exclusive): (T, bool)| {
+        if exclusive {
+            format!("greater than {bound}")
+        } else {
+            format!("greater than or equal to {bound}")
+        }
+    };
+    let describe_upper = |(bound, exclusive): (T, bool)| {
+        if exclusive {
+            format!("less than {bound}")
+        } else {
+            format!("less than or equal to {bound}")
+        }
+    };
+
+    match (lower, upper) {
+        (Some(lo), None) => {
+            let below = below(lo)?;
+            checks.push(violation(
+                quote! { #nan #below },
+                path,
+                &format!("value must be {}", describe_lower(lo)),
+            ));
+        }
+        (None, Some(hi)) => {
+            let above = above(hi)?;
+            checks.push(violation(
+                quote! { #nan #above },
+                path,
+                &format!("value must be {}", describe_upper(hi)),
+            ));
+        }
+        (Some(lo), Some(hi)) if lo.0 <= hi.0 => {
+            let (below, above) = (below(lo)?, above(hi)?);
+            checks.push(violation(
+                quote! { #nan #below || #above },
+                path,
+                &format!(
+                    "value must be {} and {}",
+                    describe_lower(lo),
+                    describe_upper(hi)
+                ),
+            ));
+        }
+        (Some(lo), Some(hi)) => {
+            // Exclusive range: the value must lie outside [hi, lo]
+            let (below, above) = (below(lo)?, above(hi)?);
+            checks.push(violation(
+                quote! { #nan #below && #above },
+                path,
+                &format!(
+                    "value must be {} or {}",
+                    describe_lower(lo),
+                    describe_upper(hi)
+                ),
+            ));
+        }
+        (None, None) => {}
+    }
+
+    if !rules.one_of.is_empty() {
+        let lits = rules
+            .one_of
+            .iter()
+            .map(|v| literal(*v))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        checks.push(violation(
+            quote! { ![#(#lits),*].contains(value) },
+            path,
+            &format!("value must be in list {}", list(&rules.one_of)),
+        ));
+    }
+    if !rules.not_in.is_empty() {
+        let lits = rules
+            .not_in
+            .iter()
+            .map(|v| literal(*v))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        checks.push(violation(
+            quote! { [#(#lits),*].contains(value) },
+            path,
+            &format!("value must not be in list {}", list(&rules.not_in)),
+        ));
+    }
+    if rules.finite {
+        checks.push(violation(
+            quote! { !value.is_finite() },
+            path,
+            "value must be finite",
+        ));
+    }
+
+    Ok(checks)
+}
+
+fn string_checks(
+    rules: &rules::StringRules,
+    path: &TokenStream,
+) -> std::result::Result<Vec<TokenStream>, String> {
+    let mut checks = Vec::new();
+    if let Some(equal) = &rules.equal {
+        checks.push(violation(
+            quote! { value.as_str() != #equal },
+            path,
+            &format!("value must equal `{equal}`"),
+        ));
+    }
+    let lengths = [
+        (
+            rules.len,
+            quote! { != },
+            "value length must be {} characters",
+        ),
+        (
+            rules.min_len,
+            quote! { < },
+            "value length must be at least {} characters",
+        ),
+        (
+            rules.max_len,
+            quote! { > },
+            "value length must be at most {} characters",
+        ),
+    ];
+    for (bound, op, description) in lengths {
+        if let Some(bound) = bound {
+            let lit = Literal::u64_unsuffixed(bound);
+            checks.push(violation(
+                quote! { value.chars().count() #op #lit },
+                path,
+                &description.replace("{}", &bound.to_string()),
+            ));
+        }
+    }
+    let byte_lengths = [
+        (
+            rules.len_bytes,
+            quote! { != },
+            "value length must be {} bytes",
+        ),
+        (
+            rules.min_bytes,
+            quote! { < },
+            "value length must be at least {} bytes",
+        ),
+        (
+            rules.max_bytes,
+            quote! { > },
+            "value length must be at most {} bytes",
+        ),
+    ];
+    for (bound, op, description) in byte_lengths {
+        if let Some(bound) = bound {
+            let lit = Literal::u64_unsuffixed(bound);
+            checks.push(violation(
+                quote! { value.len() #op #lit },
+                path,
+                &description.replace("{}", &bound.to_string()),
+            ));
+        }
+    }
+    if let Some(prefix) = &rules.prefix {
+        checks.push(violation(
+            quote! { !value.starts_with(#prefix) },
+            path,
+            &format!("value does not have prefix `{prefix}`"),
+        ));
+    }
+    if let Some(suffix) = &rules.suffix {
+        checks.push(violation(
+            quote! { !value.ends_with(#suffix) },
+            path,
+            &format!("value does not have suffix `{suffix}`"),
+        ));
+    }
+    if let Some(contains) = &rules.contains {
+        checks.push(violation(
+            quote! { !value.contains(#contains) },
+            path,
+            &format!("value does not contain substring `{contains}`"),
+        ));
+    }
+    if let Some(not_contains) = &rules.not_contains {
+        checks.push(violation(
+            quote! { value.contains(#not_contains) },
+            path,
+            &format!("value contains substring `{not_contains}`"),
+        ));
+    }
+    if !rules.one_of.is_empty() {
+        let items = &rules.one_of;
+        checks.push(violation(
+            quote! { ![#(#items),*].contains(&value.as_str()) },
+            path,
+            &format!("value must be in list {}", list(items)),
+        ));
+    }
+    if !rules.not_in.is_empty() {
+        let items = &rules.not_in;
+        checks.push(violation(
+            quote! { [#(#items),*].contains(&value.as_str()) },
+            path,
+            &format!("value must not be in list {}", list(items)),
+        ));
+    }
+    Ok(checks)
+}
+
+fn bytes_checks(
+    rules: &rules::BytesRules,
+    path: &TokenStream,
+) -> std::result::Result<Vec<TokenStream>, String> {
+    let mut checks = Vec::new();
+    let bytes = quote! { ::core::convert::AsRef::<[u8]>::as_ref(value) };
+    let describe = |bytes: &[u8]| bytes.escape_ascii().to_string();
+
+    if let Some(equal) = &rules.equal {
+        let lit = Literal::byte_string(equal);
+        checks.push(violation(
+            quote! { #bytes != #lit },
+            path,
+            &format!("value must be {}", describe(equal)),
+        ));
+    }
+    let lengths = [
+        (rules.len, quote! { != }, "value length must be {} bytes"),
+        (
+            rules.min_len,
+            quote! { < },
+            "value length must be at least {} bytes",
+        ),
+        (
+            rules.max_len,
+            quote! { > },
+            "value length must be at most {} bytes",
+        ),
+    ];
+    for (bound, op, description) in lengths {
+        if let Some(bound) = bound {
+            let lit = Literal::u64_unsuffixed(bound);
+            checks.push(violation(
+                quote! { #bytes.len() #op #lit },
+                path,
+                &description.replace("{}", &bound.to_string()),
+            ));
+        }
+    }
+    if let Some(prefix) = &rules.prefix {
+        let lit = Literal::byte_string(prefix);
+        checks.push(violation(
+            quote! { !#bytes.starts_with(#lit) },
+            path,
+            &format!("value does not have prefix {}", describe(prefix)),
+        ));
+    }
+    if let Some(suffix) = &rules.suffix {
+        let lit = Literal::byte_string(suffix);
+        checks.push(violation(
+            quote! { !#bytes.ends_with(#lit) },
+            path,
+            &format!("value does not have suffix {}", describe(suffix)),
+        ));
+    }
+    if let Some(contains) = rules.contains.as_ref().filter(|c| !c.is_empty()) {
+        let lit = Literal::byte_string(contains);
+        let len = Literal::usize_unsuffixed(contains.len());
+        checks.push(violation(
+            quote! { !#bytes.windows(#len).any(|window| window == #lit) },
+            path,
+            &format!("value does not contain {}", describe(contains)),
+        ));
+    }
+    Ok(checks)
+}
+
+fn enum_checks(
+    rules: &rules::EnumRules,
+    defined: &[i32],
+    path: &TokenStream,
+) -> std::result::Result<Vec<TokenStream>, String> {
+    let mut checks = Vec::new();
+    if let Some(equal) = rules.equal {
+        checks.push(violation(
+            quote! { *value != #equal },
+            path,
+            &format!("value must equal {equal}"),
+        ));
+    }
+    if rules.defined_only == Some(true) {
+        checks.push(violation(
+            quote! { ![#(#defined),*].contains(value) },
+            path,
+            "value must be one of the defined enum values",
+        ));
+    }
+    if !rules.one_of.is_empty() {
+        let items = &rules.one_of;
+        checks.push(violation(
+            quote! { ![#(#items),*].contains(value) },
+            path,
+            &format!("value must be in list {}", list(items)),
+        ));
+    }
+    if !rules.not_in.is_empty() {
+        let items = &rules.not_in;
+        checks.push(violation(
+            quote! { [#(#items),*].contains(value) },
+            path,
+            &format!("value must not be in list {}", list(items)),
+        ));
+    }
+    Ok(checks)
+}
+
+// ============================================================================
+// Supported rules
+// ============================================================================
+
+const NUMERIC_TAGS: &[u32] = &[1, 2, 3, 4, 5, 6, 7];
+const FLOAT_TAGS: &[u32] = &[1, 2, 3, 4, 5, 6, 7, 8];
+const STRING_TAGS: &[u32] = &[1, 2, 3, 4, 5, 7, 8, 9, 10, 11, 19, 20, 23];
+const BYTES_TAGS: &[u32] = &[1, 2, 3, 5, 6, 7, 13];
+const ENUM_TAGS: &[u32] = &[1, 2, 3, 4];
+
+/// Message rules: `disabled`.
+const MESSAGE_TAGS: &[u32] = &[1];
+
+/// Check that a `FieldRules` message only uses supported rules, returning
+/// the first unsupported one.
+fn check_field_rules(raw: &[u8], element: bool) -> std::result::Result<(), String> {
+    for (tag, payload) in wire_fields(raw)? {
+        let type_name = RuleType::name_of(tag);
+        let nested = match tag {
+            1 | 2 => check_tags(payload, FLOAT_TAGS),
+            3..=12 => check_tags(payload, NUMERIC_TAGS),
+            13 => check_tags(payload, &[1]),
+            14 => check_tags(payload, STRING_TAGS),
+            15 => check_tags(payload, BYTES_TAGS),
+            16 => check_tags(payload, ENUM_TAGS),
+            18 | 19 => {
+                let (bounds, nested): (&[u32], &[u32]) = if tag == 18 {
+                    (&[1, 2, 3], &[4])
+                } else {
+                    (&[1, 2], &[4, 5])
+                };
+                for (rule, payload) in wire_fields(payload)? {
+                    if nested.contains(&rule) {
+                        check_field_rules(payload, true)?;
+                    } else if !bounds.contains(&rule) {
+                        return Err(format!("{type_name} rule #{rule}"));
+                    }
+                }
+                Ok(())
+            }
+            25 if !element => Ok(()),
+            27 => Ok(()),
+            23 => return Err("cel".to_string()),
+            _ => return Err(format!("field rule #{tag}")),
+        };
+        nested.map_err(|rule| match (tag, rule) {
+            (14, 6) | (15, 4) => format!("{type_name}.pattern"),
+            (14, 12..=18 | 21 | 22 | 24..) => format!("{type_name} well-known format #{rule}"),
+            _ => format!("{type_name} rule #{rule}"),
+        })?;
+    }
+    Ok(())
+}
+
+fn message_rules(
+    message: &DescriptorProto,
+    fqn: &str,
+) -> std::result::Result<rules::MessageRules, String> {
+    let Some(raw) = message.options.as_ref().and_then(|o| o.rules.as_deref()) else {
+        return Ok(rules::MessageRules::default());
+    };
+    check_tags(raw, MESSAGE_TAGS).map_err(|rule| {
+        format!(
+            "unsupported buf.validate message rule on {}: #{rule}",
+            fqn.trim_start_matches('.')
+        )
+    })?;
+    rules::MessageRules::decode(raw).map_err(|e| e.to_string())
+}
+
+/// Check that a message only sets the given field numbers, returning the
+/// first other one.
+fn check_tags(raw: &[u8], allowed: &[u32]) -> std::result::Result<(), u32> {
+    let fields = wire_fields(raw).map_err(|_| 0u32)?;
+    match fields.iter().find(|(tag, _)| !allowed.contains(tag)) {
+        Some((tag, _)) => Err(*tag),
+        None => Ok(()),
+    }
+}
+
+/// Split an encoded message into field numbers and length-delimited payloads.
+fn wire_fields(mut buf: &[u8]) -> std::result::Result<Vec<(u32, &[u8])>, String> {
+    fn varint(buf: &mut &[u8]) -> std::result::Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = buf.split_first().ok_or("truncated varint")?;
+            *buf = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("invalid varint".to_string())
+    }
+    fn take<'a>(buf: &mut &'a [u8], len: usize) -> std::result::Result<&'a [u8], String> {
+        if buf.len() < len {
+            return Err("truncated field".to_string());
+        }
+        let (head, rest) = buf.split_at(len);
+        *buf = rest;
+        Ok(head)
+    }
+
+    let mut fields = Vec::new();
+    while !buf.is_empty() {
+        let key = varint(&mut buf)?;
+        let tag = (key >> 3) as u32;
+        let payload: &[u8] = match key & 0x7 {
+            0 => {
+                varint(&mut buf)?;
+                &[]
+            }
+            1 => take(&mut buf, 8)?,
+            2 => {
+                let len = varint(&mut buf)? as usize;
+                take(&mut buf, len)?
+            }
+            5 => take(&mut buf, 4)?,
+            wire_type => return Err(format!("unsupported wire type {wire_type}")),
+        };
+        fields.push((tag, payload));
+    }
+    Ok(fields)
+}
+
+// ============================================================================
+// Message definitions
+// ============================================================================
+
+/// The parts of `google/protobuf/descriptor.proto` needed here, keeping the
+/// `buf.validate` extensions as raw bytes.
+mod descriptor {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct FileDescriptorSet {
+        #[prost(message, repeated, tag = "1")]
+        pub file: Vec<FileDescriptorProto>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct FileDescriptorProto {
+        #[prost(string, optional, tag = "2")]
+        pub package: Option<String>,
+        #[prost(message, repeated, tag = "4")]
+        pub message_type: Vec<DescriptorProto>,
+        #[prost(message, repeated, tag = "5")]
+        pub enum_type: Vec<EnumDescriptorProto>,
+        #[prost(string, optional, tag = "12")]
+        pub syntax: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct DescriptorProto {
+        #[prost(string, optional, tag = "1")]
+        pub name: Option<String>,
+        #[prost(message, repeated, tag = "2")]
+        pub field: Vec<FieldDescriptorProto>,
+        #[prost(message, repeated, tag = "3")]
+        pub nested_type: Vec<DescriptorProto>,
+        #[prost(message, repeated, tag = "4")]
+        pub enum_type: Vec<EnumDescriptorProto>,
+        #[prost(message, optional, tag = "7")]
+        pub options: Option<MessageOptions>,
+        #[prost(message, repeated, tag = "8")]
+        pub oneof_decl: Vec<OneofDescriptorProto>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct FieldDescriptorProto {
+        #[prost(string, optional, tag = "1")]
+        pub name: Option<String>,
+        #[prost(int32, optional, tag = "3")]
+        pub number: Option<i32>,
+        #[prost(int32, optional, tag = "4")]
+        pub label: Option<i32>,
+        #[prost(int32, optional, tag = "5")]
+        pub r#type: Option<i32>,
+        #[prost(string, optional, tag = "6")]
+        pub type_name: Option<String>,
+        #[prost(message, optional, tag = "8")]
+        pub options: Option<FieldOptions>,
+        #[prost(int32, optional, tag = "9")]
+        pub oneof_index: Option<i32>,
+        #[prost(bool, optional, tag = "17")]
+        pub proto3_optional: Option<bool>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct OneofDescriptorProto {
+        #[prost(string, optional, tag = "1")]
+        pub name: Option<String>,
+        #[prost(message, optional, tag = "2")]
+        pub options: Option<OneofOptions>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct EnumDescriptorProto {
+        #[prost(string, optional, tag = "1")]
+        pub name: Option<String>,
+        #[prost(message, repeated, tag = "2")]
+        pub value: Vec<EnumValueDescriptorProto>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct EnumValueDescriptorProto {
+        #[prost(string, optional, tag = "1")]
+        pub name: Option<String>,
+        #[prost(int32, optional, tag = "2")]
+        pub number: Option<i32>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct MessageOptions {
+        #[prost(bool, optional, tag = "7")]
+        pub map_entry: Option<bool>,
+        /// `(buf.validate.message)`
+        #[prost(bytes = "vec", optional, tag = "1159")]
+        pub rules: Option<Vec<u8>>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct FieldOptions {
+        /// `(buf.validate.field)`
+        #[prost(bytes = "vec", optional, tag = "1159")]
+        pub rules: Option<Vec<u8>>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct OneofOptions {
+        /// `(buf.validate.oneof)`
+        #[prost(bytes = "vec", optional, tag = "1159")]
+        pub rules: Option<Vec<u8>>,
+    }
+}
+
+/// The supported subset of `buf/validate/validate.proto`.
+mod rules {
+    use super::NumericView;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct MessageRules {
+        #[prost(bool, optional, tag = "1")]
+        pub disabled: Option<bool>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct OneofRules {
+        #[prost(bool, optional, tag = "1")]
+        pub required: Option<bool>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct FieldRules {
+        #[prost(bool, optional, tag = "25")]
+        pub required: Option<bool>,
+        #[prost(int32, optional, tag = "27")]
+        pub ignore: Option<i32>,
+        #[prost(
+            oneof = "RuleType",
+            tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 18, 19"
+        )]
+        pub r#type: Option<RuleType>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub(super) enum RuleType {
+        #[prost(message, tag = "1")]
+        Float(FloatRules),
+        #[prost(message, tag = "2")]
+        Double(DoubleRules),
+        #[prost(message, tag = "3")]
+        Int32(Int32Rules),
+        #[prost(message, tag = "4")]
+        Int64(Int64Rules),
+        #[prost(message, tag = "5")]
+        Uint32(UInt32Rules),
+        #[prost(message, tag = "6")]
+        Uint64(UInt64Rules),
+        #[prost(message, tag = "7")]
+        Sint32(SInt32Rules),
+        #[prost(message, tag = "8")]
+        Sint64(SInt64Rules),
+        #[prost(message, tag = "9")]
+        Fixed32(Fixed32Rules),
+        #[prost(message, tag = "10")]
+        Fixed64(Fixed64Rules),
+        #[prost(message, tag = "11")]
+        Sfixed32(SFixed32Rules),
+        #[prost(message, tag = "12")]
+        Sfixed64(SFixed64Rules),
+        #[prost(message, tag = "13")]
+        Bool(BoolRules),
+        #[prost(message, tag = "14")]
+        String(StringRules),
+        #[prost(message, tag = "15")]
+        Bytes(BytesRules),
+        #[prost(message, tag = "16")]
+        Enum(EnumRules),
+        #[prost(message, tag = "18")]
+        Repeated(Box<RepeatedRules>),
+        #[prost(message, tag = "19")]
+        Map(Box<MapRules>),
+    }
+
+    impl RuleType {
+        const NAMES: [&'static str; 20] = [
+            "", "float", "double", "int32", "int64", "uint32", "uint64", "sint32", "sint64",
+            "fixed32", "fixed64", "sfixed32", "sfixed64", "bool", "string", "bytes", "enum", "",
+            "repeated", "map",
+        ];
+
+        pub(super) fn name_of(tag: u32) -> &'static str {
+            Self::NAMES.get(tag as usize).copied().unwrap_or_default()
+        }
+
+        pub(super) fn name(&self) -> &'static str {
+            Self::name_of(match self {
+                Self::Float(_) => 1,
+                Self::Double(_) => 2,
+                Self::Int32(_) => 3,
+                Self::Int64(_) => 4,
+                Self::Uint32(_) => 5,
+                Self::Uint64(_) => 6,
+                Self::Sint32(_) => 7,
+                Self::Sint64(_) => 8,
+                Self::Fixed32(_) => 9,
+                Self::Fixed64(_) => 10,
+                Self::Sfixed32(_) => 11,
+                Self::Sfixed64(_) => 12,
+                Self::Bool(_) => 13,
+                Self::String(_) => 14,
+                Self::Bytes(_) => 15,
+                Self::Enum(_) => 16,
+                Self::Repeated(_) => 18,
+                Self::Map(_) => 19,
+            })
+        }
+    }
+
+    macro_rules! numeric_rules {
+        ($($name:ident($proto:ident, $ty:ty)),* $(,)?) => {
+            $(
+                #[derive(Clone, PartialEq, prost::Message)]
+                pub(super) struct $name {
+                    #[prost($proto, optional, tag = "1")]
+                    pub equal: Option<$ty>,
+                    #[prost($proto, optional, tag = "2")]
+                    pub lt: Option<$ty>,
+                    #[prost($proto, optional, tag = "3")]
+                    pub lte: Option<$ty>,
+                    #[prost($proto, optional, tag = "4")]
+                    pub gt: Option<$ty>,
+                    #[prost($proto, optional, tag = "5")]
+                    pub gte: Option<$ty>,
+                    #[prost($proto, repeated, packed = "false", tag = "6")]
+                    pub one_of: Vec<$ty>,
+                    #[prost($proto, repeated, packed = "false", tag = "7")]
+                    pub not_in: Vec<$ty>,
+                    #[prost(bool, optional, tag = "8")]
+                    pub finite: Option<bool>,
+                }
+
+                impl $name {
+                    pub(super) fn view(&self) -> NumericView<$ty> {
+                        NumericView {
+                            equal: self.equal,
+                            lt: self.lt,
+                            lte: self.lte,
+                            gt: self.gt,
+                            gte: self.gte,
+                            one_of: self.one_of.clone(),
+                            not_in: self.not_in.clone(),
+                            finite: self.finite == Some(true),
+                        }
+                    }
+                }
+            )*
+        };
+    }
+
+    numeric_rules!(
+        FloatRules(float, f32),
+        DoubleRules(double, f64),
+        Int32Rules(int32, i32),
+        Int64Rules(int64, i64),
+        UInt32Rules(uint32, u32),
+        UInt64Rules(uint64, u64),
+        SInt32Rules(sint32, i32),
+        SInt64Rules(sint64, i64),
+        Fixed32Rules(fixed32, u32),
+        Fixed64Rules(fixed64, u64),
+        SFixed32Rules(sfixed32, i32),
+        SFixed64Rules(sfixed64, i64),
+    );
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct BoolRules {
+        #[prost(bool, optional, tag = "1")]
+        pub equal: Option<bool>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct StringRules {
+        #[prost(string, optional, tag = "1")]
+        pub equal: Option<String>,
+        #[prost(uint64, optional, tag = "19")]
+        pub len: Option<u64>,
+        #[prost(uint64, optional, tag = "2")]
+        pub min_len: Option<u64>,
+        #[prost(uint64, optional, tag = "3")]
+        pub max_len: Option<u64>,
+        #[prost(uint64, optional, tag = "20")]
+        pub len_bytes: Option<u64>,
+        #[prost(uint64, optional, tag = "4")]
+        pub min_bytes: Option<u64>,
+        #[prost(uint64, optional, tag = "5")]
+        pub max_bytes: Option<u64>,
+        #[prost(string, optional, tag = "7")]
+        pub prefix: Option<String>,
+        #[prost(string, optional, tag = "8")]
+        pub suffix: Option<String>,
+        #[prost(string, optional, tag = "9")]
+        pub contains: Option<String>,
+        #[prost(string, optional, tag = "23")]
+        pub not_contains: Option<String>,
+        #[prost(string, repeated, tag = "10")]
+        pub one_of: Vec<String>,
+        #[prost(string, repeated, tag = "11")]
+        pub not_in: Vec<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct BytesRules {
+        #[prost(bytes = "vec", optional, tag = "1")]
+        pub equal: Option<Vec<u8>>,
+        #[prost(uint64, optional, tag = "13")]
+        pub len: Option<u64>,
+        #[prost(uint64, optional, tag = "2")]
+        pub min_len: Option<u64>,
+        #[prost(uint64, optional, tag = "3")]
+        pub max_len: Option<u64>,
+        #[prost(bytes = "vec", optional, tag = "5")]
+        pub prefix: Option<Vec<u8>>,
+        #[prost(bytes = "vec", optional, tag = "6")]
+        pub suffix: Option<Vec<u8>>,
+        #[prost(bytes = "vec", optional, tag = "7")]
+        pub contains: Option<Vec<u8>>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct EnumRules {
+        #[prost(int32, optional, tag = "1")]
+        pub equal: Option<i32>,
+        #[prost(bool, optional, tag = "2")]
+        pub defined_only: Option<bool>,
+        #[prost(int32, repeated, packed = "false", tag = "3")]
+        pub one_of: Vec<i32>,
+        #[prost(int32, repeated, packed = "false", tag = "4")]
+        pub not_in: Vec<i32>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct RepeatedRules {
+        #[prost(uint64, optional, tag = "1")]
+        pub min_items: Option<u64>,
+        #[prost(uint64, optional, tag = "2")]
+        pub max_items: Option<u64>,
+        #[prost(bool, optional, tag = "3")]
+        pub unique: Option<bool>,
+        #[prost(message, optional, boxed, tag = "4")]
+        pub items: Option<Box<FieldRules>>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub(super) struct MapRules {
+        #[prost(uint64, optional, tag = "1")]
+        pub min_pairs: Option<u64>,
+        #[prost(uint64, optional, tag = "2")]
+        pub max_pairs: Option<u64>,
+        #[prost(message, optional, boxed, tag = "4")]
+        pub keys: Option<Box<FieldRules>>,
+        #[prost(message, optional, boxed, tag = "5")]
+        pub values: Option<Box<FieldRules>>,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,199 @@
+use super::descriptor::{
+    DescriptorProto, FieldDescriptorProto, FieldOptions, FileDescriptorProto, FileDescriptorSet,
+    MessageOptions,
+};
+use super::generate;
+use super::rules::{FieldRules, Int32Rules, MessageRules, RepeatedRules, RuleType, StringRules};
+use prost::Message;
+
+const TYPE_INT32: i32 = 5;
+const TYPE_STRING: i32 = 9;
+const TYPE_MESSAGE: i32 = 11;
+const LABEL_OPTIONAL: i32 = 1;
+const LABEL_REPEATED: i32 = 3;
+
+fn field(name: &str, number: i32, ty: i32, rules: Option<FieldRules>) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        label: Some(LABEL_OPTIONAL),
+        r#type: Some(ty),
+        options: rules.map(|rules| FieldOptions {
+            rules: Some(rules.encode_to_vec()),
+        }),
+        ..Default::default()
+    }
+}
+
+fn message(name: &str, field: Vec<FieldDescriptorProto>) -> DescriptorProto {
+    DescriptorProto {
+        name: Some(name.to_string()),
+        field,
+        ..Default::default()
+    }
+}
+
+fn render(messages: Vec<DescriptorProto>) -> Result<String, String> {
+    let fds = FileDescriptorSet {
+        file: vec![FileDescriptorProto {
+            package: Some("hello".to_string()),
+            message_type: messages,
+            syntax: Some("proto3".to_string()),
+            ..Default::default()
+        }],
+    };
+    let mut generated = generate(&fds)?;
+    Ok(generated.remove("hello").unwrap_or_default())
+}
+
+fn string_rules(rules: StringRules) -> FieldRules {
+    FieldRules {
+        r#type: Some(RuleType::String(rules)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn generates_checks_and_registration() {
+    let name = field(
+        "name",
+        1,
+        TYPE_STRING,
+        Some(string_rules(StringRules {
+            min_len: Some(2),
+            ..Default::default()
+        })),
+    );
+    let age = field(
+        "age",
+        2,
+        TYPE_INT32,
+        Some(FieldRules {
+            r#type: Some(RuleType::Int32(Int32Rules {
+                gte: Some(0),
+                lt: Some(150),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }),
+    );
+    let code = render(vec![message("HelloRequest", vec![name, age])]).unwrap();
+
+    assert!(code.contains("impl connectrpc_axum :: validate :: Validate for HelloRequest"));
+    assert!(code.contains("value . chars () . count () < 2"));
+    assert!(code.contains("\"value length must be at least 2 characters\""));
+    assert!(code.contains("* value < 0 || * value >= 150"));
+    assert!(code.contains("\"value must be greater than or equal to 0 and less than 150\""));
+    assert!(code.contains("pub fn register_validators"));
+    assert!(code.contains(". message :: < HelloRequest > ()"));
+}
+
+#[test]
+fn recurses_into_messages_with_rules() {
+    let email = field(
+        "email",
+        1,
+        TYPE_STRING,
+        Some(string_rules(StringRules {
+            contains: Some("@".to_string()),
+            ..Default::default()
+        })),
+    );
+    let contact = FieldDescriptorProto {
+        type_name: Some(".hello.Contact".to_string()),
+        ..field("contacts", 1, TYPE_MESSAGE, None)
+    };
+    let contacts = FieldDescriptorProto {
+        label: Some(LABEL_REPEATED),
+        options: Some(FieldOptions {
+            rules: Some(
+                FieldRules {
+                    r#type: Some(RuleType::Repeated(Box::new(RepeatedRules {
+                        min_items: Some(1),
+                        ..Default::default()
+                    }))),
+                    ..Default::default()
+                }
+                .encode_to_vec(),
+            ),
+        }),
+        ..contact.clone()
+    };
+    let code = render(vec![
+        message("Contact", vec![email]),
+        message("Book", vec![contacts]),
+        message("Unchecked", vec![field("note", 1, TYPE_STRING, None)]),
+    ])
+    .unwrap();
+
+    assert!(code.contains("Validate for Book"));
+    assert!(code.contains("\"value must contain at least 1 item(s)\""));
+    assert!(code.contains("value . collect_violations (& format ! (\"{}[{}]\""));
+    assert!(!code.contains("Validate for Unchecked"));
+    assert!(!code.contains("message :: < Unchecked >"));
+}
+
+#[test]
+fn skips_disabled_messages() {
+    let mut request = message(
+        "HelloRequest",
+        vec![field(
+            "name",
+            1,
+            TYPE_STRING,
+            Some(FieldRules {
+                required: Some(true),
+                ..Default::default()
+            }),
+        )],
+    );
+    request.options = Some(MessageOptions {
+        rules: Some(
+            MessageRules {
+                disabled: Some(true),
+            }
+            .encode_to_vec(),
+        ),
+        ..Default::default()
+    });
+
+    assert_eq!(render(vec![request]).unwrap(), "");
+}
+
+#[test]
+fn rejects_unsupported_rules() {
+    // `string.pattern` (field 6) needs a regex engine at runtime
+    let mut rules = FieldRules::default().encode_to_vec();
+    let pattern = [0x32, 0x01, b'a'];
+    rules.extend([0x72, pattern.len() as u8]);
+    rules.extend(pattern);
+    let name = FieldDescriptorProto {
+        options: Some(FieldOptions { rules: Some(rules) }),
+        ..field("name", 1, TYPE_STRING, None)
+    };
+
+    let err = render(vec![message("HelloRequest", vec![name])]).unwrap_err();
+    assert_eq!(
+        err,
+        "unsupported buf.validate rule on hello.HelloRequest.name: string.pattern"
+    );
+}
+
+#[test]
+fn rejects_rules_for_another_type() {
+    let age = field(
+        "age",
+        1,
+        TYPE_INT32,
+        Some(string_rules(StringRules {
+            min_len: Some(1),
+            ..Default::default()
+        })),
+    );
+
+    let err = render(vec![message("HelloRequest", vec![age])]).unwrap_err();
+    assert!(
+        err.contains("string rules on a field of another type"),
+        "{err}"
+    );
+}
@@ -87,11 +87,14 @@ pub struct CompileBuilder<
     source: Source,
     out_dir: Option<PathBuf>,
     include_file: Option<PathBuf>,
+    file_descriptor_set: Option<PathBuf>,
     extern_reexports: Vec<(String, String)>,
     #[cfg(feature = "fetch-protoc")]
     protoc_path: Option<PathBuf>,
     prost_config: Option<Box<dyn Fn(&mut prost_build::Config)>>,
     pbjson_config: Option<Box<dyn Fn(&mut pbjson_build::Builder)>>,
+    validation: bool,
+    client_mocks: bool,
     #[cfg(feature = "tonic")]
     tonic_config: Option<Box<dyn Fn(tonic_prost_build::Builder) -> tonic_prost_build::Builder>>,
     #[cfg(feature = "tonic-client")]
@@ -124,12 +127,15 @@ impl<C, T, TC, CC> CompileBuilder<NoSource, C, T, TC, CC> {
             source: WithSource(ProtoSource::Directory(dir.as_ref().to_path_buf())),
             out_dir: self.out_dir,
             include_file: self.include_file,
+            file_descriptor_set: self.file_descriptor_set,
             extern_reexports: self.extern_reexports,
 
             #[cfg(feature = "fetch-protoc")]
             protoc_path: self.protoc_path,
             prost_config: self.prost_config,
             pbjson_config: self.pbjson_config,
+            validation: self.validation,
+            client_mocks: self.client_mocks,
             #[cfg(feature = "tonic")]
             tonic_config: self.tonic_config,
             #[cfg(feature = "tonic-client")]
@@ -167,12 +173,15 @@ impl<C, T, TC, CC> CompileBuilder<NoSource, C, T, TC, CC> {
             }),
             out_dir: self.out_dir,
             include_file: self.include_file,
+            file_descriptor_set: self.file_descriptor_set,
             extern_reexports: self.extern_reexports,
 
             #[cfg(feature = "fetch-protoc")]
             protoc_path: self.protoc_path,
             prost_config: self.prost_config,
             pbjson_config: self.pbjson_config,
+            validation: self.validation,
+            client_mocks: self.client_mocks,
             #[cfg(feature = "tonic")]
             tonic_config: self.tonic_config,
             #[cfg(feature = "tonic-client")]
@@ -216,12 +225,15 @@ impl<S, T, TC, CC> CompileBuilder<S, Enabled, T, TC, CC> {
             source: self.source,
             out_dir: self.out_dir,
             include_file: self.include_file,
+            file_descriptor_set: self.file_descriptor_set,
             extern_reexports: self.extern_reexports,
 
             #[cfg(feature = "fetch-protoc")]
             protoc_path: self.protoc_path,
             prost_config: self.prost_config,
             pbjson_config: self.pbjson_config,
+            validation: self.validation,
+            client_mocks: self.client_mocks,
             #[cfg(feature = "tonic")]
             tonic_config: None,
             #[cfg(feature = "tonic-client")]
@@ -246,12 +258,15 @@ impl<S, TC, CC> CompileBuilder<S, Enabled, Disabled, TC, CC> {
             source: self.source,
             out_dir: self.out_dir,
             include_file: self.include_file,
+            file_descriptor_set: self.file_descriptor_set,
             extern_reexports: self.extern_reexports,
 
             #[cfg(feature = "fetch-protoc")]
             protoc_path: self.protoc_path,
             prost_config: self.prost_config,
             pbjson_config: self.pbjson_config,
+            validation: self.validation,
+            client_mocks: self.client_mocks,
             tonic_config: self.tonic_config,
             #[cfg(feature = "tonic-client")]
             tonic_client_config: self.tonic_client_config,
@@ -452,6 +467,28 @@ impl<S, C, T, TC, CC> CompileBuilder<S, C, T, TC, CC> {
         self
     }
 
+    /// Keep the compiled `FileDescriptorSet` as a file in the output directory.
+    ///
+    /// The descriptor set includes all imported files, so it can back the
+    /// server's `reflection` module for `grpcurl` and `buf curl`:
+    ///
+    /// ```rust,ignore
+    /// // build.rs
+    /// connectrpc_axum_build::compile_dir("proto")
+    ///     .file_descriptor_set("descriptor_set.bin")
+    ///     .compile()?;
+    ///
+    /// // main.rs
+    /// const FILE_DESCRIPTOR_SET: &[u8] =
+    ///     include_bytes!(concat!(env!("OUT_DIR"), "/descriptor_set.bin"));
+    /// ```
+    ///
+    /// The path is relative to the output directory.
+    pub fn file_descriptor_set(mut self, path: impl AsRef<Path>) -> Self {
+        self.file_descriptor_set = Some(path.as_ref().to_path_buf());
+        self
+    }
+
     /// Register an extern module re-export for the include file.
     ///
     /// When using `extern_path` in prost config to map a protobuf package to an
@@ -520,12 +557,15 @@ impl<S, C, T, CC> CompileBuilder<S, C, T, Disabled, CC> {
             source: self.source,
             out_dir: self.out_dir,
             include_file: self.include_file,
+            file_descriptor_set: self.file_descriptor_set,
             extern_reexports: self.extern_reexports,
 
             #[cfg(feature = "fetch-protoc")]
             protoc_path: self.protoc_path,
             prost_config: self.prost_config,
             pbjson_config: self.pbjson_config,
+            validation: self.validation,
+            client_mocks: self.client_mocks,
             #[cfg(feature = "tonic")]
             tonic_config: self.tonic_config,
             tonic_client_config: self.tonic_client_config,
@@ -605,19 +645,26 @@ impl<S, C, T, TC> CompileBuilder<S, C, T, TC, Disabled> {
     /// This generates:
     /// - `HELLO_WORLD_SERVICE_SERVICE_NAME` constant
     /// - `hello_world_service_procedures` module with procedure path constants
-    /// - `HelloWorldServiceClient` struct with typed `say_hello()` method
+    /// - `HelloWorldServiceClient` struct with typed `say_hello()` and
+    ///   `say_hello_with_options()` methods
     /// - `HelloWorldServiceClientBuilder` for configuration
+    /// - `HelloWorldServiceClientTrait` with the RPC methods, implemented by
+    ///   the client, for code that should also accept a mock
+    #[doc(alias = "with_client")]
     pub fn with_connect_client(self) -> CompileBuilder<S, C, T, TC, Enabled> {
         CompileBuilder {
             source: self.source,
             out_dir: self.out_dir,
             include_file: self.include_file,
+            file_descriptor_set: self.file_descriptor_set,
             extern_reexports: self.extern_reexports,
 
             #[cfg(feature = "fetch-protoc")]
             protoc_path: self.protoc_path,
             prost_config: self.prost_config,
             pbjson_config: self.pbjson_config,
+            validation: self.validation,
+            client_mocks: self.client_mocks,
             #[cfg(feature = "tonic")]
             tonic_config: self.tonic_config,
             #[cfg(feature = "tonic-client")]
@@ -627,6 +674,37 @@ impl<S, C, T, TC> CompileBuilder<S, C, T, TC, Disabled> {
     }
 }
 
+// ============================================================================
+// Methods available when ConnectClient = Enabled (configure connect client)
+// ============================================================================
+
+impl<S, C, T, TC> CompileBuilder<S, C, T, TC, Enabled> {
+    /// Also generate a mock of each Connect client.
+    ///
+    /// For a service `HelloWorldService`, this generates
+    /// `HelloWorldServiceClientMock`, which implements
+    /// `HelloWorldServiceClientTrait` with a handler per method returning
+    /// canned responses. Unit-test code that takes the trait without a live
+    /// server:
+    ///
+    /// ```rust,ignore
+    /// async fn greet(client: &impl HelloWorldServiceClientTrait) -> Result<String, ClientError> {
+    ///     let response = client.say_hello(&HelloRequest { name: "Ada".into() }).await?;
+    ///     Ok(response.into_inner().message)
+    /// }
+    ///
+    /// let mock = HelloWorldServiceClientMock::default()
+    ///     .on_say_hello(|req| Ok(HelloResponse { message: format!("Hi {}", req.name) }));
+    /// assert_eq!(greet(&mock).await?, "Hi Ada");
+    /// ```
+    ///
+    /// Methods without a handler fail with `unimplemented`.
+    pub fn with_client_mocks(mut self) -> Self {
+        self.client_mocks = true;
+        self
+    }
+}
+
 // ============================================================================
 // Compile method - only available when Source = WithSource
 // ============================================================================
@@ -713,18 +791,29 @@ impl<C: BuildMarker, T: BuildMarker, TC: BuildMarker, CC: BuildMarker>
         let descriptor_bytes = fs::read(&descriptor_path)
             .map_err(|e| std::io::Error::other(format!("read descriptor: {e}")))?;
 
+        if let Some(ref path) = self.file_descriptor_set {
+            fs::write(Path::new(&out_dir).join(path), &descriptor_bytes)
+                .map_err(|e| std::io::Error::other(format!("write descriptor set: {e}")))?;
+        }
+
         let schema = SchemaSet::from_descriptor_bytes(&descriptor_bytes)?;
 
         let connect_generator = AxumConnectServiceGenerator::new()
             .with_connect_server(generate_handlers)
             .with_tonic(grpc)
-            .with_connect_client(connect_client);
+            .with_connect_client(connect_client)
+            .with_client_mock(connect_client && self.client_mocks);
 
         connect_generator.append_to_out_dir(&schema, &out_dir)?;
 
         // -------- Pass 1.5: pbjson serde implementations (always) --------
         Self::generate_pbjson(&out_dir, &descriptor_bytes, self.pbjson_config.as_ref())?;
 
+        // -------- Pass 1.6: buf.validate rules (user requested) --------
+        if self.validation {
+            r#gen::append_validation_to_out_dir(&descriptor_bytes, &out_dir)?;
+        }
+
         // -------- Pass 2: tonic server-only (feature + user requested) --------
         #[cfg(feature = "tonic")]
         if grpc {
@@ -993,12 +1082,15 @@ pub fn builder() -> CompileBuilder {
         source: NoSource,
         out_dir: None,
         include_file: None,
+        file_descriptor_set: None,
         extern_reexports: Vec::new(),
 
         #[cfg(feature = "fetch-protoc")]
         protoc_path: None,
         prost_config: None,
         pbjson_config: None,
+        validation: false,
+        client_mocks: false,
         #[cfg(feature = "tonic")]
         tonic_config: None,
         #[cfg(feature = "tonic-client")]
@@ -1037,12 +1129,15 @@ pub fn compile_dir(dir: impl AsRef<Path>) -> CompileBuilder<WithSource> {
         source: WithSource(ProtoSource::Directory(dir.as_ref().to_path_buf())),
         out_dir: None,
         include_file: None,
+        file_descriptor_set: None,
         extern_reexports: Vec::new(),
 
         #[cfg(feature = "fetch-protoc")]
         protoc_path: None,
         prost_config: None,
         pbjson_config: None,
+        validation: false,
+        client_mocks: false,
         #[cfg(feature = "tonic")]
         tonic_config: None,
         #[cfg(feature = "tonic-client")]
@@ -1077,12 +1172,15 @@ pub fn compile_protos<P: AsRef<Path>>(protos: &[P], includes: &[P]) -> CompileBu
         }),
         out_dir: None,
         include_file: None,
+        file_descriptor_set: None,
         extern_reexports: Vec::new(),
 
         #[cfg(feature = "fetch-protoc")]
         protoc_path: None,
         prost_config: None,
         pbjson_config: None,
+        validation: false,
+        client_mocks: false,
         #[cfg(feature = "tonic")]
         tonic_config: None,
         #[cfg(feature = "tonic-client")]
@@ -7,7 +7,7 @@ use std::io::Result;
 
 mod prost;
 
-pub(crate) use prost::ProstSchemaResolver;
+pub(crate) use prost::{ProstSchemaResolver, sanitize_identifier};
 
 /// Normalized protobuf schema facts for connectrpc-axum-build.
 ///
@@ -78,7 +78,7 @@ impl<'a> ProstSchemaResolver<'a> {
     }
 }
 
-fn sanitize_identifier(ident: &str) -> String {
+pub(crate) fn sanitize_identifier(ident: &str) -> String {
     match ident {
         "as" | "break" | "const" | "continue" | "else" | "enum" | "false" | "fn" | "for" | "if"
         | "impl" | "in" | "let" | "loop" | "match" | "mod" | "move" | "mut" | "pub" | "ref"
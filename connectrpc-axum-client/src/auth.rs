@@ -0,0 +1,289 @@
+//! Bearer token authentication.
+//!
+//! A [`TokenProvider`] supplies the access token sent in the `authorization`
+//! header of every call. Wrap a provider that fetches tokens (e.g. from an
+//! OAuth2 token endpoint) in a [`CachedTokenProvider`] so a token is reused
+//! until shortly before it expires, and install it with
+//! [`ClientBuilder::with_token_provider`](crate::ClientBuilder::with_token_provider):
+//!
+//! ```ignore
+//! use connectrpc_axum_client::{CachedTokenProvider, ClientError, Token, TokenProvider};
+//! use std::time::Duration;
+//!
+//! struct OAuthProvider {
+//!     http: MyHttpClient,
+//! }
+//!
+//! impl TokenProvider for OAuthProvider {
+//!     async fn token(&self) -> Result<Token, ClientError> {
+//!         let grant = self.http.client_credentials().await?;
+//!         Ok(Token::new(grant.access_token).expires_in(Duration::from_secs(grant.expires_in)))
+//!     }
+//! }
+//!
+//! let client = ConnectClient::builder("https://api.example.com")
+//!     .with_token_provider(CachedTokenProvider::new(OAuthProvider { http }))
+//!     .build()?;
+//! ```
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::lock::Mutex;
+use http::{HeaderValue, header};
+
+use crate::ClientError;
+use crate::config::{AsyncInterceptor, RequestContext};
+
+/// How long before expiry [`CachedTokenProvider`] refreshes a token by
+/// default (30 seconds).
+pub const DEFAULT_REFRESH_BEFORE: Duration = Duration::from_secs(30);
+
+/// An access token and its lifetime.
+#[derive(Clone)]
+pub struct Token {
+    value: String,
+    expires_in: Option<Duration>,
+}
+
+impl Token {
+    /// Create a token that doesn't expire.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            expires_in: None,
+        }
+    }
+
+    /// Set how long the token stays valid from when it was issued.
+    pub fn expires_in(mut self, expires_in: Duration) -> Self {
+        self.expires_in = Some(expires_in);
+        self
+    }
+
+    /// The token value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// How long the token stays valid, if it expires.
+    pub fn get_expires_in(&self) -> Option<Duration> {
+        self.expires_in
+    }
+}
+
+impl std::fmt::Debug for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Keep the secret out of logs
+        f.debug_struct("Token")
+            .field("value", &"<redacted>")
+            .field("expires_in", &self.expires_in)
+            .finish()
+    }
+}
+
+/// Source of access tokens.
+///
+/// An error returned by [`token`](Self::token) fails the call it was fetched
+/// for.
+pub trait TokenProvider: Send + Sync + 'static {
+    /// Get a token for the next call.
+    fn token(&self) -> impl Future<Output = Result<Token, ClientError>> + Send;
+}
+
+/// A [`TokenProvider`] that caches the tokens of another provider.
+///
+/// A token is reused until [`refresh_before`](Self::refresh_before) its
+/// expiry, then fetched again. Concurrent calls that find no fresh token
+/// wait for a single refresh instead of each fetching their own. Tokens
+/// without an expiry are kept until [`invalidate`](Self::invalidate) is
+/// called.
+pub struct CachedTokenProvider<P> {
+    inner: P,
+    refresh_before: Duration,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+/// A cached token and when to refresh it, in `rt::now()` time.
+struct CachedToken {
+    token: Token,
+    refresh_at: Option<Duration>,
+}
+
+impl<P: TokenProvider> CachedTokenProvider<P> {
+    /// Cache the tokens of `inner`.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            refresh_before: DEFAULT_REFRESH_BEFORE,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Set how long before expiry the token is refreshed.
+    ///
+    /// Defaults to [`DEFAULT_REFRESH_BEFORE`]. Tokens that live shorter than
+    /// this are fetched on every call.
+    pub fn refresh_before(mut self, margin: Duration) -> Self {
+        self.refresh_before = margin;
+        self
+    }
+
+    /// Drop the cached token, so the next call fetches a new one.
+    ///
+    /// Use this when the server rejects a token before it expires, e.g. after
+    /// an `unauthenticated` error.
+    pub async fn invalidate(&self) {
+        *self.cached.lock().await = None;
+    }
+}
+
+impl<P> std::fmt::Debug for CachedTokenProvider<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedTokenProvider")
+            .field("refresh_before", &self.refresh_before)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: TokenProvider> TokenProvider for CachedTokenProvider<P> {
+    async fn token(&self) -> Result<Token, ClientError> {
+        // Held across the refresh, so concurrent callers share its result
+        let mut cached = self.cached.lock().await;
+        let now = crate::rt::now();
+        let fresh = cached
+            .as_ref()
+            .filter(|cached| cached.refresh_at.is_none_or(|at| now < at));
+        if let Some(fresh) = fresh {
+            return Ok(fresh.token.clone());
+        }
+
+        let token = self.inner.token().await?;
+        let refresh_at = token
+            .expires_in
+            .map(|expires_in| now + expires_in.saturating_sub(self.refresh_before));
+        *cached = Some(CachedToken {
+            token: token.clone(),
+            refresh_at,
+        });
+        Ok(token)
+    }
+}
+
+/// Interceptor that sends the tokens of a [`TokenProvider`] as
+/// `authorization: Bearer <token>`.
+///
+/// Installed by
+/// [`ClientBuilder::with_token_provider`](crate::ClientBuilder::with_token_provider).
+pub struct TokenInterceptor<P>(Arc<P>);
+
+impl<P: TokenProvider> TokenInterceptor<P> {
+    /// Send the tokens of `provider`.
+    pub fn new(provider: P) -> Self {
+        Self(Arc::new(provider))
+    }
+}
+
+impl<P> Clone for TokenInterceptor<P> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<P> std::fmt::Debug for TokenInterceptor<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenInterceptor").finish_non_exhaustive()
+    }
+}
+
+impl<P: TokenProvider> AsyncInterceptor for TokenInterceptor<P> {
+    async fn on_request(&self, ctx: &mut RequestContext<'_>) -> Result<(), ClientError> {
+        let token = self.0.token().await?;
+        let mut value = HeaderValue::try_from(format!("Bearer {}", token.value()))
+            .map_err(|_| ClientError::Protocol("token is not a valid header value".into()))?;
+        value.set_sensitive(true);
+        ctx.headers.insert(header::AUTHORIZATION, value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Issues `token-1`, `token-2`, ... valid for `expires_in`.
+    struct CountingProvider {
+        fetches: Arc<AtomicUsize>,
+        expires_in: Option<Duration>,
+    }
+
+    impl TokenProvider for CountingProvider {
+        async fn token(&self) -> Result<Token, ClientError> {
+            let n = self.fetches.fetch_add(1, Ordering::SeqCst) + 1;
+            tokio::task::yield_now().await;
+            let token = Token::new(format!("token-{n}"));
+            Ok(match self.expires_in {
+                Some(expires_in) => token.expires_in(expires_in),
+                None => token,
+            })
+        }
+    }
+
+    fn cached(
+        expires_in: Option<Duration>,
+    ) -> (CachedTokenProvider<CountingProvider>, Arc<AtomicUsize>) {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let provider = CachedTokenProvider::new(CountingProvider {
+            fetches: fetches.clone(),
+            expires_in,
+        });
+        (provider, fetches)
+    }
+
+    #[tokio::test]
+    async fn test_cached_token_provider_reuses_token() {
+        let (provider, fetches) = cached(Some(Duration::from_secs(3600)));
+        let provider = Arc::new(provider);
+
+        let tokens = futures::future::join_all((0..8).map(|_| {
+            let provider = provider.clone();
+            async move { provider.token().await.unwrap() }
+        }))
+        .await;
+        assert!(tokens.iter().all(|t| t.value() == "token-1"));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        provider.invalidate().await;
+        assert_eq!(provider.token().await.unwrap().value(), "token-2");
+    }
+
+    #[tokio::test]
+    async fn test_cached_token_provider_refreshes_before_expiry() {
+        // Expires within the refresh margin, so every call refreshes
+        let (provider, fetches) = cached(Some(Duration::from_secs(10)));
+        assert_eq!(provider.token().await.unwrap().value(), "token-1");
+        assert_eq!(provider.token().await.unwrap().value(), "token-2");
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        let provider = provider.refresh_before(Duration::ZERO);
+        assert_eq!(provider.token().await.unwrap().value(), "token-3");
+        assert_eq!(provider.token().await.unwrap().value(), "token-3");
+    }
+
+    #[tokio::test]
+    async fn test_token_interceptor() {
+        let (provider, _) = cached(None);
+        let interceptor = TokenInterceptor::new(provider);
+
+        let mut headers = http::HeaderMap::new();
+        let mut ctx = RequestContext::new("test/Method", &mut headers);
+        interceptor.on_request(&mut ctx).await.unwrap();
+
+        let value = headers.get(header::AUTHORIZATION).unwrap();
+        assert_eq!(value, "Bearer token-1");
+        assert!(value.is_sensitive());
+        assert!(!format!("{:?}", Token::new("secret")).contains("secret"));
+    }
+}
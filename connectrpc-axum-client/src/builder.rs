@@ -2,18 +2,38 @@
 //!
 //! Provides a fluent API for configuring and building a [`ConnectClient`].
 
+use crate::auth::{TokenInterceptor, TokenProvider};
+use crate::cache::ResponseCache;
 use crate::client::ConnectClient;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::client::DEFAULT_USER_AGENT;
 use crate::config::{
-    Chain, HeaderWrapper, Interceptor, InterceptorInternal, MessageInterceptor, MessageWrapper,
+    AsyncInterceptor, AsyncWrapper, Chain, CircuitBreaker, HeaderWrapper, Interceptor,
+    InterceptorInternal, MessageInterceptor, MessageWrapper, RetryBudget, RetryConfig, RetryPolicy,
+};
+use crate::negotiation::EncodingCache;
+use crate::protocol::Protocol;
+use crate::response::DEFAULT_DECOMPRESSION_LIMIT;
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+use crate::transport::FetchTransport;
+use crate::transport::{Balancer, DefaultTransport, Transport};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::transport::{
+    CookieStore, HttpVersionPolicy, HyperTransport, HyperTransportBuilder, LayeredTransport,
+    RustlsClientConfig, TlsClientConfig,
 };
-use crate::transport::{HyperTransport, HyperTransportBuilder, TlsClientConfig};
 use connectrpc_axum_core::{CompressionConfig, CompressionEncoding};
+use http::{HeaderMap, HeaderName, HeaderValue};
 use std::time::Duration;
+use tower::layer::util::Identity;
+#[cfg(not(target_arch = "wasm32"))]
+use tower::{Layer, layer::util::Stack};
 
 /// Builder for creating a [`ConnectClient`].
 ///
-/// The builder is generic over `I`: the interceptor chain type.
-/// This defaults to `()` (no interceptors).
+/// The builder is generic over `I`: the interceptor chain type, and `L`:
+/// the tower layers wrapping the transport. These default to `()` (no
+/// interceptors) and [`Identity`] (no layers).
 ///
 /// Interceptors are added via:
 /// - [`with_interceptor`](Self::with_interceptor): Header-level interceptors (simple, no message bounds)
@@ -32,37 +52,67 @@ use std::time::Duration;
 ///     .accept_encoding(CompressionEncoding::Gzip)
 ///     .build()?;
 /// ```
-pub struct ClientBuilder<I = ()> {
+pub struct ClientBuilder<I = (), L = Identity> {
     /// Base URL for the service (e.g., "http://localhost:3000").
     base_url: String,
     /// Optional pre-configured transport.
-    transport: Option<HyperTransport>,
+    transport: Option<DefaultTransport>,
     /// Transport builder for when transport is not directly provided.
+    #[cfg(not(target_arch = "wasm32"))]
     transport_builder: HyperTransportBuilder,
     /// Use protobuf encoding (true) or JSON encoding (false).
     use_proto: bool,
+    /// Wire protocol.
+    protocol: Protocol,
     /// Compression configuration for outgoing requests.
     compression: Option<CompressionConfig>,
     /// Compression encoding for outgoing request bodies.
     request_encoding: CompressionEncoding,
-    /// Accepted compression encodings for responses.
-    accept_encoding: Option<CompressionEncoding>,
+    /// Accepted compression encodings for responses, most preferred first.
+    accept_encodings: Vec<CompressionEncoding>,
     /// Default timeout for RPC calls.
     default_timeout: Option<Duration>,
+    /// Headers sent with every call.
+    default_headers: HeaderMap,
+    /// `User-Agent` of requests that don't set one.
+    user_agent: Option<HeaderValue>,
+    /// Maximum decompressed size of a response body or streaming message.
+    decompression_limit: usize,
+    /// Circuit breaker guarding every call.
+    circuit_breaker: Option<CircuitBreaker>,
+    /// Balancer picking the endpoint of each call.
+    balancer: Option<Balancer>,
+    /// Retry policies and budget for unary calls.
+    retry: RetryConfig,
+    /// Cache of Connect GET responses.
+    response_cache: Option<ResponseCache>,
+    /// Encodings accepted by servers, for request compression negotiation.
+    encodings: Option<EncodingCache>,
     /// Unified interceptor chain (compile-time composed).
     interceptor: I,
+    /// Tower layers wrapping the transport (compile-time composed).
+    layer: L,
 }
 
-impl<I> std::fmt::Debug for ClientBuilder<I> {
+impl<I, L> std::fmt::Debug for ClientBuilder<I, L> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ClientBuilder")
             .field("base_url", &self.base_url)
             .field("transport", &self.transport.is_some())
             .field("use_proto", &self.use_proto)
+            .field("protocol", &self.protocol)
             .field("compression", &self.compression)
             .field("request_encoding", &self.request_encoding)
-            .field("accept_encoding", &self.accept_encoding)
+            .field("accept_encodings", &self.accept_encodings)
             .field("default_timeout", &self.default_timeout)
+            .field("default_headers", &self.default_headers)
+            .field("user_agent", &self.user_agent)
+            .field("decompression_limit", &self.decompression_limit)
+            .field("circuit_breaker", &self.circuit_breaker)
+            .field("balancer", &self.balancer)
+            .field("retry", &self.retry)
+            .field("response_cache", &self.response_cache)
+            .field("encodings", &self.encodings)
             .finish_non_exhaustive()
     }
 }
@@ -82,18 +132,33 @@ impl ClientBuilder<()> {
         Self {
             base_url: base_url.into(),
             transport: None,
+            #[cfg(not(target_arch = "wasm32"))]
             transport_builder: HyperTransportBuilder::new(),
             use_proto: false, // Default to JSON for broader compatibility
+            protocol: Protocol::Connect,
             compression: None,
             request_encoding: CompressionEncoding::Identity,
-            accept_encoding: None,
+            accept_encodings: Vec::new(),
             default_timeout: None,
+            default_headers: HeaderMap::new(),
+            // Browsers set their own, and would need CORS to allow this one
+            #[cfg(not(target_arch = "wasm32"))]
+            user_agent: Some(HeaderValue::from_static(DEFAULT_USER_AGENT)),
+            #[cfg(target_arch = "wasm32")]
+            user_agent: None,
+            decompression_limit: DEFAULT_DECOMPRESSION_LIMIT,
+            circuit_breaker: None,
+            balancer: None,
+            retry: RetryConfig::default(),
+            response_cache: None,
+            encodings: None,
             interceptor: (),
+            layer: Identity::new(),
         }
     }
 }
 
-impl<I: InterceptorInternal> ClientBuilder<I> {
+impl<I: InterceptorInternal, L> ClientBuilder<I, L> {
     /// Use a pre-configured HyperTransport.
     ///
     /// This allows you to configure TLS, HTTP/2, connection pooling, etc.
@@ -114,11 +179,33 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
     ///     .with_transport(transport)
     ///     .build()?;
     /// ```
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn with_transport(mut self, transport: HyperTransport) -> Self {
         self.transport = Some(transport);
         self
     }
 
+    /// Use a pre-configured [`FetchTransport`].
+    ///
+    /// Only available on wasm32 targets, where it replaces the default
+    /// `FetchTransport::new()`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{ClientBuilder, FetchTransport};
+    /// use web_sys::RequestCredentials;
+    ///
+    /// let client = ClientBuilder::new("https://api.example.com")
+    ///     .with_transport(FetchTransport::new().credentials(RequestCredentials::Include))
+    ///     .build()?;
+    /// ```
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    pub fn with_transport(mut self, transport: FetchTransport) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
     /// Use JSON encoding for requests and responses.
     ///
     /// This is the default encoding.
@@ -136,6 +223,67 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
         self
     }
 
+    /// Speak the gRPC protocol instead of Connect.
+    ///
+    /// Requests use the `application/grpc` content types (`application/grpc`
+    /// with [`use_proto()`](Self::use_proto), `application/grpc+json`
+    /// otherwise), every message is length-prefixed, and the call status is
+    /// read from the `grpc-status` trailers. This lets the client call any
+    /// gRPC server, such as tonic, as well as connectrpc-axum servers.
+    ///
+    /// gRPC requires HTTP/2, so this also enables
+    /// [`http2_prior_knowledge()`](Self::http2_prior_knowledge) on the
+    /// transport the builder creates. A transport provided with
+    /// [`with_transport()`](Self::with_transport) must speak HTTP/2 itself;
+    /// [`build()`](ClientBuilder::build) fails if it is HTTP/1.1 only.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let client = ClientBuilder::new("http://localhost:50051")
+    ///     .use_grpc()
+    ///     .use_proto()
+    ///     .build()?;
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn use_grpc(mut self) -> Self {
+        self.protocol = Protocol::Grpc;
+        self.transport_builder = self.transport_builder.http2_only(true);
+        self
+    }
+
+    /// Speak the gRPC-Web protocol instead of Connect.
+    ///
+    /// Requests use `application/grpc-web+proto` (or `+json`) and the call
+    /// status is read from the trailer frame at the end of the response body.
+    /// This works over HTTP/1.1 and through gRPC-Web proxies such as Envoy.
+    ///
+    /// gRPC-Web only supports unary and server-streaming calls; client and
+    /// bidi streaming calls fail with `Unimplemented`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let client = ClientBuilder::new("https://envoy.example.com")
+    ///     .use_grpc_web()
+    ///     .use_proto()
+    ///     .build()?;
+    /// ```
+    pub fn use_grpc_web(mut self) -> Self {
+        self.protocol = Protocol::GrpcWeb;
+        self
+    }
+
+    /// Speak gRPC-Web in text mode (`application/grpc-web-text`), where
+    /// request and response bodies are base64-encoded.
+    ///
+    /// Some gRPC-Web deployments only accept text mode. Otherwise this is the
+    /// same as [`use_grpc_web()`](Self::use_grpc_web).
+    pub fn use_grpc_web_text(mut self) -> Self {
+        self.protocol = Protocol::GrpcWebText;
+        self
+    }
+
     /// Configure compression for outgoing requests.
     ///
     /// # Arguments
@@ -183,7 +331,35 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
     ///     .build()?;
     /// ```
     pub fn accept_encoding(mut self, encoding: CompressionEncoding) -> Self {
-        self.accept_encoding = Some(encoding);
+        self.accept_encodings = vec![encoding];
+        self
+    }
+
+    /// Set the accepted compression encodings for responses, most preferred
+    /// first.
+    ///
+    /// The encodings are sent in the `Accept-Encoding` header (or its
+    /// streaming and gRPC counterparts) with decreasing q-values, e.g.
+    /// `zstd, br;q=0.9, gzip;q=0.8`, so the server can pick the best one it
+    /// supports. Replaces any encoding set with
+    /// [`accept_encoding`](Self::accept_encoding).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .accept_encodings([
+    ///         CompressionEncoding::Zstd,
+    ///         CompressionEncoding::Brotli,
+    ///         CompressionEncoding::Gzip,
+    ///     ])
+    ///     .build()?;
+    /// ```
+    pub fn accept_encodings(
+        mut self,
+        encodings: impl IntoIterator<Item = CompressionEncoding>,
+    ) -> Self {
+        self.accept_encodings = encodings.into_iter().collect();
         self
     }
 
@@ -222,6 +398,292 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
         self
     }
 
+    /// Add a header sent with every call, such as an API version or tenant ID.
+    ///
+    /// Default headers are added before interceptors run, and a header of the
+    /// same name set with [`CallOptions::header`] replaces them for that call.
+    /// Can be called repeatedly; setting a name again replaces its value.
+    /// Protocol-reserved headers are filtered, as for [`CallOptions::header`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the header name or value is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .default_header("x-api-version", "2024-06-01")
+    ///     .default_header("x-tenant-id", "acme")
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`CallOptions::header`]: crate::CallOptions::header
+    pub fn default_header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: TryInto<HeaderName>,
+        K::Error: std::fmt::Debug,
+        V: TryInto<HeaderValue>,
+        V::Error: std::fmt::Debug,
+    {
+        let name = name.try_into().expect("invalid header name");
+        let value = value.try_into().expect("invalid header value");
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Set the `User-Agent` header of requests.
+    ///
+    /// Defaults to [`DEFAULT_USER_AGENT`] (`connectrpc-axum-client/<version>`),
+    /// except on wasm32, where the browser sets it. To keep the default and
+    /// add your own product, include it in the value. A `User-Agent` set with
+    /// [`default_header`](Self::default_header), [`CallOptions::header`] or an
+    /// interceptor takes precedence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{ClientBuilder, DEFAULT_USER_AGENT};
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .user_agent(format!("billing-service/1.4 {}", DEFAULT_USER_AGENT))
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`CallOptions::header`]: crate::CallOptions::header
+    pub fn user_agent<V>(mut self, value: V) -> Self
+    where
+        V: TryInto<HeaderValue>,
+        V::Error: std::fmt::Debug,
+    {
+        self.user_agent = Some(value.try_into().expect("invalid user agent"));
+        self
+    }
+
+    /// Set the maximum decompressed size of a response, in bytes.
+    ///
+    /// Deprecated alias of
+    /// [`max_receive_message_size`](Self::max_receive_message_size), which
+    /// also caps the size of messages before decompression.
+    #[deprecated(note = "use `max_receive_message_size`, which sets the same limit")]
+    pub fn with_decompression_limit(self, bytes: usize) -> Self {
+        self.max_receive_message_size(bytes)
+    }
+
+    /// Set the maximum size of a received message, in bytes.
+    ///
+    /// Applies to unary response bodies, error bodies and each message of a
+    /// streaming response, both as received and after decompression. Larger
+    /// messages fail the call with [`Code::ResourceExhausted`] without being
+    /// buffered in full, and decompression stops as soon as the output passes
+    /// the limit, so a small compressed payload from a malicious or
+    /// misbehaving server can't expand to gigabytes.
+    ///
+    /// Defaults to [`DEFAULT_DECOMPRESSION_LIMIT`] (64 MiB).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .accept_encoding(CompressionEncoding::Gzip)
+    ///     .max_receive_message_size(4 * 1024 * 1024)
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`Code::ResourceExhausted`]: crate::Code::ResourceExhausted
+    pub fn max_receive_message_size(mut self, bytes: usize) -> Self {
+        self.decompression_limit = bytes;
+        self
+    }
+
+    /// Guard every call of the client with a circuit breaker.
+    ///
+    /// While the circuit is open, calls fail immediately with
+    /// [`Code::Unavailable`] instead of reaching the backend. Transport
+    /// errors, client timeouts and HTTP 5xx responses count as failures.
+    /// Clones of a breaker share their state, so one breaker can guard
+    /// several clients of the same backend.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{CircuitBreaker, ClientBuilder};
+    /// use std::time::Duration;
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .with_circuit_breaker(CircuitBreaker::new().open_duration(Duration::from_secs(10)))
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`Code::Unavailable`]: crate::Code::Unavailable
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// Spread calls over several endpoints with a [`Balancer`].
+    ///
+    /// Each call goes to the endpoint the balancer picks, in place of the
+    /// base URL passed to [`new`](ClientBuilder::new). Endpoints whose
+    /// requests fail with a transport error are skipped for a while.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{Balancer, ClientBuilder, LeastInFlight};
+    ///
+    /// let client = ClientBuilder::new("http://backend")
+    ///     .with_balancer(
+    ///         Balancer::new(["http://10.0.0.1:3000", "http://10.0.0.2:3000"])
+    ///             .policy(LeastInFlight),
+    ///     )
+    ///     .build()?;
+    /// ```
+    pub fn with_balancer(mut self, balancer: Balancer) -> Self {
+        self.balancer = Some(balancer);
+        self
+    }
+
+    /// Spread calls over several base URLs, round-robin.
+    ///
+    /// Shorthand for [`with_balancer`](Self::with_balancer) with a default
+    /// [`Balancer`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `urls` is empty.
+    pub fn with_endpoints<U, S>(self, urls: U) -> Self
+    where
+        U: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with_balancer(Balancer::new(urls))
+    }
+
+    /// Retry failed unary calls under `policy`.
+    ///
+    /// Applies to every unary call made with
+    /// [`call_unary`](ConnectClient::call_unary) or
+    /// [`call_unary_with_options`](ConnectClient::call_unary_with_options)
+    /// that has no policy of its own (see
+    /// [`retry_policy_for`](Self::retry_policy_for)). Streaming calls are
+    /// never retried. Combine with a [`retry_budget`](Self::retry_budget) to
+    /// avoid retry storms.
+    ///
+    /// Don't also wrap such calls in [`retry_with_policy`](crate::retry_with_policy),
+    /// which would retry each retry.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{ClientBuilder, RetryPolicy};
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .retry_policy(RetryPolicy::default())
+    ///     .build()?;
+    /// ```
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry.default = Some(policy);
+        self
+    }
+
+    /// Retry failed unary calls of one method or service under `policy`.
+    ///
+    /// `name` is either a procedure (`"pkg.Service/Method"`) or a service
+    /// (`"pkg.Service"`), like the method names of a gRPC service config. A
+    /// method's own policy wins over its service's, which wins over the
+    /// [`retry_policy`](Self::retry_policy) default. Use
+    /// [`RetryPolicy::no_retry`] to exempt a method from the default.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{ClientBuilder, RetryPolicy};
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .retry_policy_for("users.v1.UserService", RetryPolicy::default())
+    ///     .retry_policy_for("users.v1.UserService/CreateUser", RetryPolicy::no_retry())
+    ///     .build()?;
+    /// ```
+    pub fn retry_policy_for(mut self, name: impl Into<String>, policy: RetryPolicy) -> Self {
+        let name = name.into();
+        self.retry
+            .methods
+            .insert(name.trim_start_matches('/').to_string(), policy);
+        self
+    }
+
+    /// Limit the retries of the client to a fraction of its calls.
+    ///
+    /// Once the budget is spent, failed calls return their error instead of
+    /// being retried. Applies to retries configured on the builder and to
+    /// [`call_unary_with_retry`](ConnectClient::call_unary_with_retry).
+    /// Clones of a budget share it, so one budget can cap several clients.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{ClientBuilder, RetryBudget, RetryPolicy};
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .retry_policy(RetryPolicy::default())
+    ///     .retry_budget(RetryBudget::new(0.1))
+    ///     .build()?;
+    /// ```
+    pub fn retry_budget(mut self, budget: RetryBudget) -> Self {
+        self.retry.budget = Some(budget);
+        self
+    }
+
+    /// Cache the responses of unary calls sent as GET requests.
+    ///
+    /// Calls made with [`CallOptions::use_get`](crate::CallOptions::use_get)
+    /// are answered from the cache while their response is fresh, and
+    /// revalidated with `If-None-Match` once it's stale. See
+    /// [`ResponseCache`] for the caching rules.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{ClientBuilder, ResponseCache};
+    /// use std::time::Duration;
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .response_cache(ResponseCache::new().ttl(Duration::from_secs(30)))
+    ///     .build()?;
+    /// ```
+    pub fn response_cache(mut self, cache: ResponseCache) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Pick the request compression from the encodings the server accepts.
+    ///
+    /// The encodings servers list in their responses are remembered per
+    /// origin in `encodings`, and later calls compress their requests with
+    /// the best encoding both sides support, preferring the configured
+    /// [`request_encoding`](Self::request_encoding). The
+    /// [`compression`](Self::compression) threshold and level still apply.
+    /// See [`EncodingCache`] for the selection rules.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{ClientBuilder, EncodingCache};
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .negotiate_compression(EncodingCache::new())
+    ///     .build()?;
+    /// ```
+    pub fn negotiate_compression(mut self, encodings: EncodingCache) -> Self {
+        self.encodings = Some(encodings);
+        self
+    }
+
     /// Add a header-level interceptor to the client.
     ///
     /// Header interceptors can inspect and modify request/response headers
@@ -255,17 +717,28 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
     pub fn with_interceptor<J: Interceptor>(
         self,
         interceptor: J,
-    ) -> ClientBuilder<Chain<I, HeaderWrapper<J>>> {
+    ) -> ClientBuilder<Chain<I, HeaderWrapper<J>>, L> {
         ClientBuilder {
             base_url: self.base_url,
             transport: self.transport,
+            #[cfg(not(target_arch = "wasm32"))]
             transport_builder: self.transport_builder,
             use_proto: self.use_proto,
+            protocol: self.protocol,
             compression: self.compression,
             request_encoding: self.request_encoding,
-            accept_encoding: self.accept_encoding,
+            accept_encodings: self.accept_encodings,
             default_timeout: self.default_timeout,
+            default_headers: self.default_headers,
+            user_agent: self.user_agent,
+            decompression_limit: self.decompression_limit,
+            circuit_breaker: self.circuit_breaker,
+            balancer: self.balancer,
+            retry: self.retry,
+            response_cache: self.response_cache,
+            encodings: self.encodings,
             interceptor: Chain(self.interceptor, HeaderWrapper(interceptor)),
+            layer: self.layer,
         }
     }
 
@@ -316,20 +789,114 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
     pub fn with_message_interceptor<J: MessageInterceptor>(
         self,
         interceptor: J,
-    ) -> ClientBuilder<Chain<I, MessageWrapper<J>>> {
+    ) -> ClientBuilder<Chain<I, MessageWrapper<J>>, L> {
         ClientBuilder {
             base_url: self.base_url,
             transport: self.transport,
+            #[cfg(not(target_arch = "wasm32"))]
             transport_builder: self.transport_builder,
             use_proto: self.use_proto,
+            protocol: self.protocol,
             compression: self.compression,
             request_encoding: self.request_encoding,
-            accept_encoding: self.accept_encoding,
+            accept_encodings: self.accept_encodings,
             default_timeout: self.default_timeout,
+            default_headers: self.default_headers,
+            user_agent: self.user_agent,
+            decompression_limit: self.decompression_limit,
+            circuit_breaker: self.circuit_breaker,
+            balancer: self.balancer,
+            retry: self.retry,
+            response_cache: self.response_cache,
+            encodings: self.encodings,
             interceptor: Chain(self.interceptor, MessageWrapper(interceptor)),
+            layer: self.layer,
         }
     }
 
+    /// Add a header-level interceptor whose request hook is async.
+    ///
+    /// Use this when preparing a request needs I/O, such as fetching an OAuth
+    /// token. The call awaits the interceptor before the request is sent.
+    /// Async interceptors keep their place in the chain: they run after
+    /// interceptors added before them and before those added after them.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{AsyncInterceptor, ClientBuilder, ClientError, RequestContext};
+    ///
+    /// #[derive(Clone)]
+    /// struct OAuthInterceptor {
+    ///     tokens: Arc<TokenSource>,
+    /// }
+    ///
+    /// impl AsyncInterceptor for OAuthInterceptor {
+    ///     async fn on_request(&self, ctx: &mut RequestContext<'_>) -> Result<(), ClientError> {
+    ///         let token = self.tokens.token().await?;
+    ///         ctx.headers.insert("authorization", format!("Bearer {token}").parse().unwrap());
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .with_async_interceptor(OAuthInterceptor { tokens })
+    ///     .build()?;
+    /// ```
+    pub fn with_async_interceptor<J: AsyncInterceptor>(
+        self,
+        interceptor: J,
+    ) -> ClientBuilder<Chain<I, AsyncWrapper<J>>, L> {
+        ClientBuilder {
+            base_url: self.base_url,
+            transport: self.transport,
+            #[cfg(not(target_arch = "wasm32"))]
+            transport_builder: self.transport_builder,
+            use_proto: self.use_proto,
+            protocol: self.protocol,
+            compression: self.compression,
+            request_encoding: self.request_encoding,
+            accept_encodings: self.accept_encodings,
+            default_timeout: self.default_timeout,
+            default_headers: self.default_headers,
+            user_agent: self.user_agent,
+            decompression_limit: self.decompression_limit,
+            circuit_breaker: self.circuit_breaker,
+            balancer: self.balancer,
+            retry: self.retry,
+            response_cache: self.response_cache,
+            encodings: self.encodings,
+            interceptor: Chain(self.interceptor, AsyncWrapper(interceptor)),
+            layer: self.layer,
+        }
+    }
+
+    /// Authenticate every call with a bearer token from `provider`.
+    ///
+    /// The token is sent as `authorization: Bearer <token>`. Wrap providers
+    /// that fetch tokens over the network in a [`CachedTokenProvider`] so
+    /// tokens are reused until shortly before they expire. This adds a
+    /// [`TokenInterceptor`] to the chain, like
+    /// [`with_async_interceptor`](Self::with_async_interceptor).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{CachedTokenProvider, ClientBuilder};
+    ///
+    /// let client = ClientBuilder::new("https://api.example.com")
+    ///     .with_token_provider(CachedTokenProvider::new(OAuthProvider::new(credentials)))
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`CachedTokenProvider`]: crate::CachedTokenProvider
+    pub fn with_token_provider<P: TokenProvider>(
+        self,
+        provider: P,
+    ) -> ClientBuilder<Chain<I, AsyncWrapper<TokenInterceptor<P>>>, L> {
+        self.with_async_interceptor(TokenInterceptor::new(provider))
+    }
+
     /// Enable HTTP/2 prior knowledge (h2c) for unencrypted connections.
     ///
     /// When enabled, the client will use HTTP/2 directly without the HTTP/1.1
@@ -359,11 +926,59 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
     /// ```
     ///
     /// [`with_transport()`]: Self::with_transport
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn http2_prior_knowledge(mut self) -> Self {
         self.transport_builder = self.transport_builder.http2_only(true);
         self
     }
 
+    /// Use HTTP/1.1 only, e.g. behind legacy proxies that don't support
+    /// HTTP/2.
+    ///
+    /// Only HTTP/1.1 is offered via ALPN for `https://` URLs. Unary, client
+    /// and server streaming calls work over HTTP/1.1; bidi streaming calls
+    /// fail with `Unimplemented`, and [`build()`](ClientBuilder::build)
+    /// fails for gRPC (but not gRPC-Web), as both need HTTP/2.
+    ///
+    /// **Note:** This setting only applies when the builder creates the transport.
+    /// If you provide your own transport via [`with_transport()`], configure
+    /// [`HyperTransportBuilder::http1_only`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let client = ClientBuilder::new("https://legacy.example.com")
+    ///     .http1_only()
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`with_transport()`]: Self::with_transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http1_only(mut self) -> Self {
+        self.transport_builder = self.transport_builder.http1_only();
+        self
+    }
+
+    /// Set the HTTP versions the transport speaks.
+    ///
+    /// [`HttpVersionPolicy::Auto`] (the default) negotiates HTTP/2 or
+    /// HTTP/1.1 via ALPN for `https://` URLs and uses HTTP/1.1 for `http://`
+    /// URLs. [`HttpVersionPolicy::Http1Only`] is the same as
+    /// [`http1_only()`](Self::http1_only), and
+    /// [`HttpVersionPolicy::Http2PriorKnowledge`] the same as
+    /// [`http2_prior_knowledge()`](Self::http2_prior_knowledge).
+    ///
+    /// **Note:** This setting only applies when the builder creates the transport.
+    /// If you provide your own transport via [`with_transport()`], configure
+    /// [`HyperTransportBuilder::http_version`] instead.
+    ///
+    /// [`with_transport()`]: Self::with_transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http_version(mut self, policy: HttpVersionPolicy) -> Self {
+        self.transport_builder = self.transport_builder.http_version(policy);
+        self
+    }
+
     /// Set the connection pool idle timeout.
     ///
     /// Connections that have been idle for longer than this duration
@@ -384,15 +999,110 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
     /// ```
     ///
     /// [`with_transport()`]: Self::with_transport
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
         self.transport_builder = self.transport_builder.pool_idle_timeout(timeout);
         self
     }
 
-    /// Set a custom TLS configuration.
+    /// Send HTTP/2 PING frames at this interval to detect dead connections.
+    ///
+    /// Streams on a connection whose peer stops answering fail with a
+    /// transport error instead of hanging. Configure the ping timeout and
+    /// pinging idle connections on [`HyperTransportBuilder`].
+    ///
+    /// **Note:** This setting only applies when the builder creates the transport.
+    /// If you provide your own transport via [`with_transport()`], configure this
+    /// on that transport's builder instead.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use std::time::Duration;
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .http2_keep_alive_interval(Duration::from_secs(30))
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`with_transport()`]: Self::with_transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.transport_builder = self.transport_builder.h2_keep_alive_interval(interval);
+        self
+    }
+
+    /// Set the timeout for establishing a TCP connection.
+    ///
+    /// Bounds connecting separately from the call timeout, so an unreachable
+    /// server fails fast. IPv6 and IPv4 addresses of a host are raced
+    /// (Happy Eyeballs) regardless; see
+    /// [`HyperTransportBuilder::happy_eyeballs_timeout`].
+    ///
+    /// **Note:** This setting only applies when the builder creates the transport.
+    /// If you provide your own transport via [`with_transport()`], configure this
+    /// on that transport's builder instead.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use std::time::Duration;
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .connect_timeout(Duration::from_secs(2))
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`with_transport()`]: Self::with_transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.transport_builder = self.transport_builder.connect_timeout(timeout);
+        self
+    }
+
+    /// Keep cookies set by responses and send them with later requests.
+    ///
+    /// Use this for deployments with cookie-based session affinity. See
+    /// [`HyperTransportBuilder::cookie_store`].
+    ///
+    /// **Note:** This setting only applies when the builder creates the transport.
+    /// If you provide your own transport via [`with_transport()`], configure this
+    /// on that transport's builder instead.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let client = ClientBuilder::new("https://api.example.com")
+    ///     .cookie_store(true)
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`with_transport()`]: Self::with_transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cookie_store(mut self, enabled: bool) -> Self {
+        self.transport_builder = self.transport_builder.cookie_store(enabled);
+        self
+    }
+
+    /// Keep cookies in `store`, e.g. a [`CookieJar`](crate::CookieJar)
+    /// shared with other clients.
     ///
-    /// Use this to configure custom root certificates, client certificates for mTLS,
-    /// or other TLS settings.
+    /// **Note:** This setting only applies when the builder creates the transport.
+    /// If you provide your own transport via [`with_transport()`], configure this
+    /// on that transport's builder instead.
+    ///
+    /// [`with_transport()`]: Self::with_transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cookie_provider<C: CookieStore>(mut self, store: std::sync::Arc<C>) -> Self {
+        self.transport_builder = self.transport_builder.cookie_provider(store);
+        self
+    }
+
+    /// Set a custom rustls TLS configuration.
+    ///
+    /// This is the low-level escape hatch for TLS settings not covered by
+    /// [`with_tls_config()`]. Prefer [`with_tls_config()`] for custom roots,
+    /// mTLS, or minimum protocol versions.
     ///
     /// **Note:** This setting only applies when the builder creates the transport.
     /// If you provide your own transport via [`with_transport()`], configure TLS
@@ -401,9 +1111,9 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
     /// # Example
     ///
     /// ```ignore
-    /// use connectrpc_axum_client::TlsClientConfig;
+    /// use connectrpc_axum_client::RustlsClientConfig;
     ///
-    /// let tls_config = TlsClientConfig::builder()
+    /// let tls_config = RustlsClientConfig::builder()
     ///     .with_root_certificates(my_roots)
     ///     .with_no_client_auth();
     ///
@@ -412,12 +1122,46 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
     ///     .build()?;
     /// ```
     ///
+    /// [`with_tls_config()`]: Self::with_tls_config
     /// [`with_transport()`]: Self::with_transport
-    pub fn tls_config(mut self, config: TlsClientConfig) -> Self {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tls_config(mut self, config: RustlsClientConfig) -> Self {
         self.transport_builder = self.transport_builder.tls_config(config);
         self
     }
 
+    /// Set the TLS configuration for the underlying transport.
+    ///
+    /// Use this to trust private CAs or self-signed certificates, present a
+    /// client certificate for mTLS, or raise the minimum TLS version.
+    /// Invalid certificates or keys are reported by [`build()`](Self::build).
+    ///
+    /// **Note:** This setting only applies when the builder creates the transport.
+    /// If you provide your own transport via [`with_transport()`], configure TLS
+    /// on that transport's builder instead.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{ClientBuilder, TlsClientConfig, TlsVersion};
+    ///
+    /// let client = ClientBuilder::new("https://api.internal:8443")
+    ///     .with_tls_config(
+    ///         TlsClientConfig::new()
+    ///             .add_root_certificate(&std::fs::read("ca.pem")?)
+    ///             .identity(&std::fs::read("client.pem")?, &std::fs::read("client.key")?)
+    ///             .min_tls_version(TlsVersion::Tls13),
+    ///     )
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`with_transport()`]: Self::with_transport
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_tls_config(mut self, config: TlsClientConfig) -> Self {
+        self.transport_builder = self.transport_builder.with_tls_config(config);
+        self
+    }
+
     /// Accept invalid TLS certificates.
     ///
     /// # Warning
@@ -439,39 +1183,206 @@ impl<I: InterceptorInternal> ClientBuilder<I> {
     /// ```
     ///
     /// [`with_transport()`]: Self::with_transport
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn danger_accept_invalid_certs(mut self) -> Self {
         self.transport_builder = self.transport_builder.danger_accept_invalid_certs();
         self
     }
 
-    /// Build the ConnectClient.
+    /// Wrap the transport in a [`tower::Layer`].
     ///
-    /// # Errors
+    /// Every HTTP request the client sends, including each retry and hedged
+    /// attempt, passes through the layer, so standard `tower` and
+    /// `tower-http` middleware (timeouts, tracing, concurrency limits, ...)
+    /// can be reused on the client side. Layers run in the order they are
+    /// added, as with [`tower::ServiceBuilder`]: the first sees requests
+    /// first and responses last.
     ///
-    /// Returns an error if the HTTP transport cannot be created.
-    pub fn build(self) -> Result<ConnectClient<I>, ClientBuildError> {
-        // Create or use provided transport
-        let transport = match self.transport {
+    /// The layered service takes `http::Request<TransportBody>` and returns
+    /// an `http::Response` with a body of [`Bytes`](bytes::Bytes); the built
+    /// client sends its requests with a [`LayeredTransport`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use tower::limit::ConcurrencyLimitLayer;
+    /// use tower_http::trace::TraceLayer;
+    ///
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .layer(TraceLayer::new_for_http())
+    ///     .layer(ConcurrencyLimitLayer::new(64))
+    ///     .build()?;
+    /// ```
+    ///
+    /// [`TransportBody`]: crate::TransportBody
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn layer<M>(self, layer: M) -> ClientBuilder<I, Stack<M, L>> {
+        ClientBuilder {
+            base_url: self.base_url,
+            transport: self.transport,
+            transport_builder: self.transport_builder,
+            use_proto: self.use_proto,
+            protocol: self.protocol,
+            compression: self.compression,
+            request_encoding: self.request_encoding,
+            accept_encodings: self.accept_encodings,
+            default_timeout: self.default_timeout,
+            default_headers: self.default_headers,
+            user_agent: self.user_agent,
+            decompression_limit: self.decompression_limit,
+            circuit_breaker: self.circuit_breaker,
+            balancer: self.balancer,
+            retry: self.retry,
+            response_cache: self.response_cache,
+            encodings: self.encodings,
+            interceptor: self.interceptor,
+            layer: Stack::new(layer, self.layer),
+        }
+    }
+
+    /// Take the configured transport, or build the default one.
+    fn take_transport(&mut self) -> Result<DefaultTransport, ClientBuildError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let transport = match self.transport.take() {
             Some(t) => t,
-            None => self
-                .transport_builder
+            None => std::mem::take(&mut self.transport_builder)
                 .build()
                 .map_err(|e| ClientBuildError::Transport(e.to_string()))?,
         };
+        #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+        let transport = self.transport.take().unwrap_or_default();
 
+        Ok(transport)
+    }
+
+    /// Check that `transport` can carry the configured protocol, and get
+    /// whether it is HTTP/1.1 only.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn check_http_version(&self, transport: &HyperTransport) -> Result<bool, ClientBuildError> {
+        let http1_only = transport.http_version() == HttpVersionPolicy::Http1Only;
+        if http1_only && self.protocol.is_grpc() && !self.protocol.is_grpc_web() {
+            return Err(ClientBuildError::Transport(
+                "gRPC requires HTTP/2, but the transport is HTTP/1.1 only".to_string(),
+            ));
+        }
+        Ok(http1_only)
+    }
+
+    /// Create the client, sending its requests with `transport` as is.
+    ///
+    /// Bidi streaming calls fail up front if `http1_only`.
+    fn into_client<T: Transport>(self, transport: T, http1_only: bool) -> ConnectClient<I, T> {
         // Normalize base URL (remove trailing slash)
         let base_url = self.base_url.trim_end_matches('/').to_string();
 
-        Ok(ConnectClient::new(
+        ConnectClient::new(
             transport,
             base_url,
             self.use_proto,
+            self.protocol,
             self.compression.unwrap_or_default(),
             self.request_encoding,
-            self.accept_encoding,
+            self.accept_encodings,
             self.default_timeout,
+            self.default_headers,
+            self.user_agent,
+            self.decompression_limit,
+            self.circuit_breaker,
+            self.balancer,
+            self.retry,
+            self.response_cache,
+            self.encodings,
+            http1_only,
             self.interceptor,
-        ))
+        )
+    }
+}
+
+impl<I: InterceptorInternal> ClientBuilder<I> {
+    /// Build the ConnectClient.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP transport cannot be created.
+    pub fn build(mut self) -> Result<ConnectClient<I>, ClientBuildError> {
+        let transport = self.take_transport()?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let http1_only = self.check_http_version(&transport)?;
+        #[cfg(target_arch = "wasm32")]
+        let http1_only = false;
+        Ok(self.into_client(transport, http1_only))
+    }
+
+    /// Build a ConnectClient that sends its requests with a custom
+    /// [`Transport`].
+    ///
+    /// Use this for HTTP stacks other than the default, or for a test double.
+    /// Settings of the default transport, such as [`with_transport`] or the
+    /// HTTP/2 and TLS options, are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let client = ClientBuilder::new("http://localhost:3000")
+    ///     .use_proto()
+    ///     .build_with_transport(MyTransport::new())?;
+    /// ```
+    ///
+    /// [`with_transport`]: Self::with_transport
+    pub fn build_with_transport<T: Transport>(
+        self,
+        transport: T,
+    ) -> Result<ConnectClient<I, T>, ClientBuildError> {
+        Ok(self.into_client(transport, false))
+    }
+}
+
+/// Transport `T` wrapped in the layers `L`.
+#[cfg(not(target_arch = "wasm32"))]
+type Layered<L, T> = LayeredTransport<<L as Layer<T>>::Service>;
+
+/// Client sending its requests with transport `T` wrapped in the layers `L`.
+#[cfg(not(target_arch = "wasm32"))]
+type LayeredClient<I, L, T> = ConnectClient<I, Layered<L, T>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<I: InterceptorInternal, Inner, Outer> ClientBuilder<I, Stack<Inner, Outer>> {
+    /// Build the ConnectClient, sending its requests through the layers
+    /// added with [`layer`](Self::layer).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP transport cannot be created.
+    pub fn build(
+        mut self,
+    ) -> Result<LayeredClient<I, Stack<Inner, Outer>, DefaultTransport>, ClientBuildError>
+    where
+        Stack<Inner, Outer>: Layer<DefaultTransport>,
+        Layered<Stack<Inner, Outer>, DefaultTransport>: Transport,
+    {
+        let transport = self.take_transport()?;
+        let http1_only = self.check_http_version(&transport)?;
+        let transport = LayeredTransport::new(self.layer.layer(transport));
+        Ok(self.into_client(transport, http1_only))
+    }
+
+    /// Build a ConnectClient that sends its requests through the layers,
+    /// then with a custom [`Transport`].
+    ///
+    /// The transport must implement [`tower::Service`], like
+    /// [`HyperTransport`] does. To test in-process, pass the service itself,
+    /// e.g. an axum `Router`, instead of a
+    /// [`LoopbackTransport`](crate::LoopbackTransport).
+    pub fn build_with_transport<T>(
+        self,
+        transport: T,
+    ) -> Result<LayeredClient<I, Stack<Inner, Outer>, T>, ClientBuildError>
+    where
+        Stack<Inner, Outer>: Layer<T>,
+        Layered<Stack<Inner, Outer>, T>: Transport,
+    {
+        let transport = LayeredTransport::new(self.layer.layer(transport));
+        Ok(self.into_client(transport, false))
     }
 }
 
@@ -500,6 +1411,21 @@ mod tests {
         assert!(builder.use_proto);
     }
 
+    #[test]
+    fn test_builder_use_grpc() {
+        let builder = ClientBuilder::new("http://localhost:3000").use_grpc();
+        assert_eq!(builder.protocol, Protocol::Grpc);
+        assert!(builder.build().unwrap().protocol().is_grpc());
+    }
+
+    #[test]
+    fn test_builder_use_grpc_web() {
+        let builder = ClientBuilder::new("http://localhost:3000").use_grpc_web();
+        assert_eq!(builder.protocol, Protocol::GrpcWeb);
+        let builder = builder.use_grpc_web_text();
+        assert_eq!(builder.protocol, Protocol::GrpcWebText);
+    }
+
     #[test]
     fn test_builder_use_json() {
         let builder = ClientBuilder::new("http://localhost:3000")
@@ -508,12 +1434,37 @@ mod tests {
         assert!(!builder.use_proto);
     }
 
+    #[test]
+    fn test_builder_user_agent() {
+        let builder = ClientBuilder::new("http://localhost:3000");
+        assert_eq!(builder.user_agent.unwrap(), DEFAULT_USER_AGENT);
+        assert!(DEFAULT_USER_AGENT.starts_with("connectrpc-axum-client/"));
+
+        let builder = ClientBuilder::new("http://localhost:3000").user_agent("my-app/1.0");
+        assert_eq!(builder.user_agent.unwrap(), "my-app/1.0");
+    }
+
     #[cfg(feature = "compression-gzip-stream")]
     #[test]
     fn test_builder_accept_encoding() {
         let builder =
             ClientBuilder::new("http://localhost:3000").accept_encoding(CompressionEncoding::Gzip);
-        assert_eq!(builder.accept_encoding, Some(CompressionEncoding::Gzip));
+        assert_eq!(builder.accept_encodings, [CompressionEncoding::Gzip]);
+    }
+
+    #[cfg(all(
+        feature = "compression-gzip-stream",
+        feature = "compression-zstd-stream"
+    ))]
+    #[test]
+    fn test_builder_accept_encodings() {
+        let builder = ClientBuilder::new("http://localhost:3000")
+            .accept_encoding(CompressionEncoding::Gzip)
+            .accept_encodings([CompressionEncoding::Zstd, CompressionEncoding::Gzip]);
+        assert_eq!(
+            builder.accept_encodings,
+            [CompressionEncoding::Zstd, CompressionEncoding::Gzip]
+        );
     }
 
     #[cfg(feature = "compression-gzip-stream")]
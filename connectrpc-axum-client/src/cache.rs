@@ -0,0 +1,471 @@
+//! Client-side cache for Connect GET responses.
+//!
+//! Unary calls sent as GET requests (see
+//! [`CallOptions::use_get`](crate::CallOptions::use_get)) have no side
+//! effects, so their responses can be reused. A [`ResponseCache`] installed
+//! with [`ClientBuilder::response_cache`](crate::ClientBuilder::response_cache)
+//! keeps successful responses, keyed by the request URL:
+//!
+//! - A fresh response answers the call without a request.
+//! - A stale response with an `ETag` is revalidated with `If-None-Match`; a
+//!   `304 Not Modified` answer reuses it.
+//!
+//! Freshness comes from the response's `Cache-Control: max-age`, falling back
+//! to the cache's [`ttl`](ResponseCache::ttl). Responses marked `no-store`
+//! or `Vary: *` aren't kept, and `no-cache` responses are always revalidated.
+//! Calls sent with a `Cache-Control: no-cache` header revalidate, and calls
+//! with `no-store` bypass the cache.
+//!
+//! Entries are stored with a [`CacheStore`]; [`MemoryCacheStore`] is the
+//! default.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderValue, header};
+
+/// Freshness of responses without `Cache-Control: max-age` (60 seconds).
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Number of responses [`MemoryCacheStore`] keeps by default.
+pub const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// A cached response: its headers and raw body.
+#[derive(Clone, Debug)]
+pub struct CachedResponse {
+    headers: HeaderMap,
+    body: Bytes,
+    /// When the response goes stale, on the [`crate::rt::now`] clock.
+    expires_at: Duration,
+}
+
+impl CachedResponse {
+    /// The response headers.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// The response body, as received (possibly compressed).
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// The entity tag the response is revalidated with, if any.
+    pub fn etag(&self) -> Option<&HeaderValue> {
+        self.headers.get(header::ETAG)
+    }
+
+    /// Check if the response can be used without revalidation.
+    pub fn is_fresh(&self) -> bool {
+        crate::rt::now() < self.expires_at
+    }
+}
+
+/// Storage for a [`ResponseCache`].
+///
+/// Implement this to keep responses somewhere other than the default
+/// [`MemoryCacheStore`], e.g. to share them between clients or bound them
+/// by size.
+pub trait CacheStore: Send + Sync + 'static {
+    /// Get the response stored under `key`.
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Store `response` under `key`, replacing any previous one.
+    fn insert(&self, key: String, response: CachedResponse);
+
+    /// Remove the response stored under `key`.
+    fn remove(&self, key: &str);
+}
+
+/// In-memory [`CacheStore`] keeping a bounded number of responses.
+///
+/// When full, the response stored first is evicted.
+#[derive(Debug)]
+pub struct MemoryCacheStore {
+    capacity: usize,
+    entries: Mutex<MemoryEntries>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryEntries {
+    responses: HashMap<String, CachedResponse>,
+    /// Keys in insertion order, for eviction.
+    order: VecDeque<String>,
+}
+
+impl MemoryCacheStore {
+    /// Create a store keeping up to `capacity` responses.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "cache capacity must be non-zero");
+        Self {
+            capacity,
+            entries: Mutex::new(MemoryEntries::default()),
+        }
+    }
+
+    /// Get the number of stored responses.
+    pub fn len(&self) -> usize {
+        self.lock().responses.len()
+    }
+
+    /// Check if no responses are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MemoryEntries> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for MemoryCacheStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_CAPACITY)
+    }
+}
+
+impl CacheStore for MemoryCacheStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.lock().responses.get(key).cloned()
+    }
+
+    fn insert(&self, key: String, response: CachedResponse) {
+        let mut entries = self.lock();
+        if entries.responses.insert(key.clone(), response).is_some() {
+            return;
+        }
+        entries.order.push_back(key);
+        while entries.order.len() > self.capacity {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.responses.remove(&oldest);
+            }
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let mut entries = self.lock();
+        if entries.responses.remove(key).is_some() {
+            entries.order.retain(|k| k != key);
+        }
+    }
+}
+
+/// Cache of Connect GET responses.
+///
+/// Clones share their store. See the [module docs](self) for the caching
+/// rules.
+///
+/// # Example
+///
+/// ```ignore
+/// use connectrpc_axum_client::{ClientBuilder, MemoryCacheStore, ResponseCache};
+/// use std::time::Duration;
+///
+/// let client = ClientBuilder::new("http://localhost:3000")
+///     .response_cache(
+///         ResponseCache::with_store(MemoryCacheStore::new(256)).ttl(Duration::from_secs(30)),
+///     )
+///     .build()?;
+/// ```
+#[derive(Clone)]
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    ttl: Duration,
+}
+
+/// What the cache holds for a request.
+pub(crate) enum CacheLookup {
+    /// Nothing usable; send the request.
+    Miss,
+    /// A fresh response answering the call.
+    Fresh(CachedResponse),
+    /// A stale response, to revalidate with its entity tag.
+    Stale(CachedResponse, HeaderValue),
+}
+
+impl ResponseCache {
+    /// Create a cache backed by a default [`MemoryCacheStore`].
+    pub fn new() -> Self {
+        Self::with_store(MemoryCacheStore::default())
+    }
+
+    /// Create a cache backed by `store`.
+    pub fn with_store(store: impl CacheStore) -> Self {
+        Self {
+            store: Arc::new(store),
+            ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+
+    /// Set how long responses without `Cache-Control: max-age` stay fresh.
+    ///
+    /// Defaults to [`DEFAULT_CACHE_TTL`]. With a zero TTL, such responses
+    /// are only kept if they can be revalidated with an `ETag`.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Get the freshness of responses without `Cache-Control: max-age`.
+    pub fn get_ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Look up the response for the request to `key` with `headers`.
+    pub(crate) fn lookup(&self, key: &str, headers: &HeaderMap) -> CacheLookup {
+        let request = CacheControl::parse(headers);
+        if request.no_store {
+            return CacheLookup::Miss;
+        }
+        let Some(cached) = self.store.get(key) else {
+            return CacheLookup::Miss;
+        };
+        if cached.is_fresh() && !request.no_cache {
+            return CacheLookup::Fresh(cached);
+        }
+        match cached.etag().cloned() {
+            Some(etag) => CacheLookup::Stale(cached, etag),
+            None => CacheLookup::Miss,
+        }
+    }
+
+    /// Store the response to the request to `key` with `request_headers`,
+    /// if it may be cached.
+    pub(crate) fn store(
+        &self,
+        key: &str,
+        request_headers: &HeaderMap,
+        headers: &HeaderMap,
+        body: Bytes,
+    ) {
+        if CacheControl::parse(request_headers).no_store {
+            return;
+        }
+        match self.entry(headers.clone(), body) {
+            Some(cached) => self.store.insert(key.to_string(), cached),
+            None => self.store.remove(key),
+        }
+    }
+
+    /// Refresh `cached` from the headers of a `304 Not Modified` response
+    /// to the request to `key`, returning the response to answer with.
+    pub(crate) fn revalidated(
+        &self,
+        key: &str,
+        cached: CachedResponse,
+        headers: &HeaderMap,
+    ) -> CachedResponse {
+        let mut merged = cached.headers;
+        for name in headers.keys() {
+            merged.remove(name);
+        }
+        for (name, value) in headers {
+            merged.append(name, value.clone());
+        }
+        match self.entry(merged.clone(), cached.body.clone()) {
+            Some(refreshed) => {
+                self.store.insert(key.to_string(), refreshed.clone());
+                refreshed
+            }
+            None => {
+                self.store.remove(key);
+                CachedResponse {
+                    headers: merged,
+                    body: cached.body,
+                    expires_at: Duration::ZERO,
+                }
+            }
+        }
+    }
+
+    /// Build the entry for a response, or `None` if it can't be cached.
+    fn entry(&self, headers: HeaderMap, body: Bytes) -> Option<CachedResponse> {
+        let control = CacheControl::parse(&headers);
+        let vary_any = headers.get_all(header::VARY).iter().any(|v| {
+            v.to_str()
+                .is_ok_and(|v| v.split(',').any(|v| v.trim() == "*"))
+        });
+        if control.no_store || vary_any {
+            return None;
+        }
+        let ttl = if control.no_cache {
+            Duration::ZERO
+        } else {
+            control.max_age.unwrap_or(self.ttl)
+        };
+        if ttl.is_zero() && !headers.contains_key(header::ETAG) {
+            return None;
+        }
+        Some(CachedResponse {
+            headers,
+            body,
+            expires_at: crate::rt::now().saturating_add(ttl),
+        })
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The `Cache-Control` directives the cache honors.
+#[derive(Debug, Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<Duration>,
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> Self {
+        let mut control = Self::default();
+        let directives = headers
+            .get_all(header::CACHE_CONTROL)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','));
+        for directive in directives {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+            if name.eq_ignore_ascii_case("no-store") {
+                control.no_store = true;
+            } else if name.eq_ignore_ascii_case("no-cache") {
+                control.no_cache = true;
+            } else if name.eq_ignore_ascii_case("max-age")
+                && let Some(secs) = value.and_then(|v| v.parse().ok())
+            {
+                control.max_age = Some(Duration::from_secs(secs));
+            }
+        }
+        control
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_cache_control() {
+        let control = CacheControl::parse(&headers(&[
+            ("cache-control", "public, max-age=30"),
+            ("cache-control", "No-Cache"),
+        ]));
+        assert!(control.no_cache);
+        assert!(!control.no_store);
+        assert_eq!(control.max_age, Some(Duration::from_secs(30)));
+
+        let control = CacheControl::parse(&headers(&[("cache-control", "max-age=oops")]));
+        assert_eq!(control.max_age, None);
+    }
+
+    #[test]
+    fn test_cache_lookup() {
+        let cache = ResponseCache::new();
+        let none = HeaderMap::new();
+
+        // Fresh for the default TTL
+        cache.store("fresh", &none, &none, Bytes::from_static(b"a"));
+        assert!(matches!(cache.lookup("fresh", &none), CacheLookup::Fresh(c) if c.body() == "a"));
+        assert!(matches!(cache.lookup("missing", &none), CacheLookup::Miss));
+
+        // Requests can skip fresh responses
+        let no_cache = headers(&[("cache-control", "no-cache")]);
+        assert!(matches!(
+            cache.lookup("fresh", &no_cache),
+            CacheLookup::Miss
+        ));
+        let no_store = headers(&[("cache-control", "no-store")]);
+        assert!(matches!(
+            cache.lookup("fresh", &no_store),
+            CacheLookup::Miss
+        ));
+
+        // no-cache responses are revalidated with their entity tag
+        let revalidate = headers(&[("cache-control", "no-cache"), ("etag", "\"v1\"")]);
+        cache.store("etag", &none, &revalidate, Bytes::from_static(b"b"));
+        assert!(
+            matches!(cache.lookup("etag", &none), CacheLookup::Stale(_, etag) if etag == "\"v1\"")
+        );
+
+        // Responses that can't be reused aren't kept
+        for uncacheable in [
+            headers(&[("cache-control", "no-store")]),
+            headers(&[("cache-control", "max-age=0")]),
+            headers(&[("vary", "accept, *")]),
+        ] {
+            cache.store("fresh", &none, &uncacheable, Bytes::new());
+            assert!(matches!(cache.lookup("fresh", &none), CacheLookup::Miss));
+        }
+    }
+
+    #[test]
+    fn test_cache_revalidated() {
+        let cache = ResponseCache::new();
+        let none = HeaderMap::new();
+        let stored = headers(&[
+            ("cache-control", "no-cache"),
+            ("etag", "\"v1\""),
+            ("x-custom", "a"),
+        ]);
+        cache.store("key", &none, &stored, Bytes::from_static(b"body"));
+        let CacheLookup::Stale(cached, _) = cache.lookup("key", &none) else {
+            panic!("expected a stale response");
+        };
+
+        // The 304's headers replace the stored ones, making it fresh again
+        let not_modified = headers(&[("cache-control", "max-age=60"), ("etag", "\"v1\"")]);
+        let refreshed = cache.revalidated("key", cached, &not_modified);
+        assert_eq!(refreshed.body(), "body");
+        assert_eq!(refreshed.headers()["x-custom"], "a");
+        assert_eq!(refreshed.headers()["cache-control"], "max-age=60");
+        assert!(matches!(cache.lookup("key", &none), CacheLookup::Fresh(_)));
+    }
+
+    #[test]
+    fn test_memory_store_evicts_oldest() {
+        let store = MemoryCacheStore::new(2);
+        let response = CachedResponse {
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+            expires_at: Duration::MAX,
+        };
+        store.insert("a".into(), response.clone());
+        store.insert("b".into(), response.clone());
+        store.insert("a".into(), response.clone());
+        store.insert("c".into(), response);
+        assert_eq!(store.len(), 2);
+        assert!(store.get("a").is_none());
+        assert!(store.get("b").is_some());
+        assert!(store.get("c").is_some());
+
+        store.remove("b");
+        assert_eq!(store.len(), 1);
+    }
+}
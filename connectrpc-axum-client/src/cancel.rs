@@ -0,0 +1,119 @@
+//! Cancellation of in-flight calls.
+//!
+//! A call made with [`CallOptions::cancellation_token`] is aborted when the
+//! token is cancelled: a call still waiting for its response fails with
+//! [`Code::Canceled`], and the request and response streams of a streaming
+//! call end with that error, which drops the HTTP request.
+//!
+//! [`CallOptions::cancellation_token`]: crate::CallOptions::cancellation_token
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use futures::future::Either;
+use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
+
+use crate::{ClientError, Code};
+
+/// The error of a cancelled call.
+pub(crate) fn canceled() -> ClientError {
+    ClientError::new(Code::Canceled, "call was canceled")
+}
+
+/// Run `call` until `cancel` fires, failing with [`Code::Canceled`] then.
+pub(crate) async fn until_canceled<R>(
+    cancel: Option<CancellationToken>,
+    call: impl Future<Output = Result<R, ClientError>>,
+) -> Result<R, ClientError> {
+    let Some(cancel) = cancel else {
+        return call.await;
+    };
+    // The token is polled first, so a call cancelled up front isn't sent
+    let cancelled = std::pin::pin!(cancel.cancelled_owned());
+    match futures::future::select(cancelled, std::pin::pin!(call)).await {
+        Either::Left(_) => Err(canceled()),
+        Either::Right((result, _)) => result,
+    }
+}
+
+/// A stream that fails with [`Code::Canceled`] and ends once a token is
+/// cancelled.
+pub(crate) struct Cancelable<S> {
+    inner: S,
+    cancelled: Option<Pin<Box<WaitForCancellationFutureOwned>>>,
+    done: bool,
+}
+
+impl<S> Cancelable<S> {
+    /// Wrap `inner`, cancelled by `cancel` if set.
+    pub(crate) fn new(inner: S, cancel: Option<&CancellationToken>) -> Self {
+        Self {
+            inner,
+            cancelled: cancel.map(|cancel| Box::pin(cancel.clone().cancelled_owned())),
+            done: false,
+        }
+    }
+}
+
+impl<S, T> Stream for Cancelable<S>
+where
+    S: Stream<Item = Result<T, ClientError>> + Unpin,
+{
+    type Item = Result<T, ClientError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        if let Some(cancelled) = &mut this.cancelled
+            && cancelled.as_mut().poll(cx).is_ready()
+        {
+            this.done = true;
+            return Poll::Ready(Some(Err(canceled())));
+        }
+        Pin::new(&mut this.inner).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn test_until_canceled() {
+        let cancel = CancellationToken::new();
+        let result = until_canceled(Some(cancel.clone()), async { Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+
+        cancel.cancel();
+        let result = until_canceled(Some(cancel), async { Ok(1) }).await;
+        assert_eq!(result.unwrap_err().code(), Code::Canceled);
+
+        let result = until_canceled(None, async { Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancelable_stream() {
+        let cancel = CancellationToken::new();
+        let (tx, rx) = futures::channel::mpsc::unbounded::<Result<u32, ClientError>>();
+        let mut stream = Cancelable::new(rx, Some(&cancel));
+
+        tx.unbounded_send(Ok(1)).unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+
+        cancel.cancel();
+        tx.unbounded_send(Ok(2)).unwrap();
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::Canceled);
+        assert!(stream.next().await.is_none());
+    }
+}
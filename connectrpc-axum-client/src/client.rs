@@ -2,10 +2,11 @@
 //!
 //! This module provides the main [`ConnectClient`] type for making RPC calls.
 
+use base64::Engine;
 use bytes::Bytes;
-use http::{Method, Request, header};
+use http::{HeaderValue, Method, Request, header};
+use http_body::Body;
 use http_body_util::BodyExt;
-use tokio::time::timeout;
 
 use connectrpc_axum_core::{Code, CompressionConfig, CompressionEncoding, wrap_envelope};
 #[cfg(feature = "tracing")]
@@ -13,22 +14,34 @@ use tracing::info_span;
 
 use crate::ClientError;
 use crate::config::{
-    CallOptions, InterceptorInternal, RequestContext, ResponseContext, StreamType,
-    duration_to_timeout_header,
+    CallOptions, CircuitBreaker, ExponentialBackoff, HedgingPolicy, InterceptorInternal,
+    RequestContext, ResponseContext, RetryConfig, RetryPolicy, StreamType,
+    duration_to_grpc_timeout, duration_to_timeout_header, hedge, is_failure, next_retry,
+    retry_attempts,
 };
-use crate::transport::{HyperTransport, TransportBody};
+use crate::error::ErrorResponse;
+use crate::protocol::Protocol;
+use crate::rt::timeout;
+use crate::transport::{Balancer, DefaultTransport, Transport, TransportBody};
+use futures::future::Either;
 use futures::{Stream, StreamExt};
 use prost::Message;
 use serde::{Serialize, de::DeserializeOwned};
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 use crate::builder::ClientBuilder;
-use crate::request::FrameEncoder;
+use crate::cache::{CacheLookup, ResponseCache};
+use crate::cancel::{Cancelable, until_canceled};
+use crate::metrics::CallMetrics;
+use crate::negotiation::EncodingCache;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::request::{FrameEncoder, StreamSender};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::response::InterceptingSendStream;
 use crate::response::error_parser::parse_error_response;
-use crate::response::{
-    ConnectResponse, FrameDecoder, InterceptingSendStream, InterceptingStreaming, Metadata,
-    Streaming,
-};
+use crate::response::grpc::{self, GrpcStatusSource, TrailersSlot};
+use crate::response::{ConnectResponse, FrameDecoder, InterceptingStreaming, Metadata, Streaming};
 
 /// Header name for Connect protocol version.
 const CONNECT_PROTOCOL_VERSION_HEADER: &str = "connect-protocol-version";
@@ -39,6 +52,18 @@ const CONNECT_PROTOCOL_VERSION: &str = "1";
 /// Header name for Connect timeout in milliseconds.
 const CONNECT_TIMEOUT_HEADER: &str = "connect-timeout-ms";
 
+/// Header name for the gRPC timeout.
+const GRPC_TIMEOUT_HEADER: &str = "grpc-timeout";
+
+/// Header marking gRPC-Web requests.
+const GRPC_WEB_HEADER: &str = "x-grpc-web";
+
+/// Default `User-Agent` of requests: `connectrpc-axum-client/<version>`.
+pub const DEFAULT_USER_AGENT: &str = concat!("connectrpc-axum-client/", env!("CARGO_PKG_VERSION"));
+
+/// Longest URL sent as a Connect GET request; longer requests are POSTed.
+const MAX_GET_URL_LENGTH: usize = 8 * 1024;
+
 /// Check if a header name is reserved by the Connect protocol.
 ///
 /// Reserved headers should not be overwritten by user-provided CallOptions headers.
@@ -53,6 +78,33 @@ fn is_reserved_header(name: &http::header::HeaderName) -> bool {
         || name_str == "content-encoding"
         || name_str == "accept-encoding"
         || name_str == "content-length"
+        || name_str == "te"
+}
+
+/// Render accepted encodings, most preferred first, as an accept-encoding
+/// header value with decreasing q-values (`zstd, br;q=0.9, gzip;q=0.8`).
+///
+/// Duplicates are dropped; q-values stop decreasing at 0.1.
+fn accept_encoding_value(encodings: &[CompressionEncoding]) -> Option<HeaderValue> {
+    let mut names: Vec<&str> = Vec::new();
+    for encoding in encodings {
+        if !names.contains(&encoding.as_str()) {
+            names.push(encoding.as_str());
+        }
+    }
+    if names.is_empty() {
+        return None;
+    }
+    let value = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| match 10usize.saturating_sub(i).max(1) {
+            10 => name.to_string(),
+            tenths => format!("{};q=0.{}", name, tenths),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    HeaderValue::from_str(&value).ok()
 }
 
 /// Connect RPC client.
@@ -60,6 +112,10 @@ fn is_reserved_header(name: &http::header::HeaderName) -> bool {
 /// The client is generic over `I`: the interceptor chain type.
 /// This defaults to `()` (no interceptors).
 ///
+/// It is also generic over `T`, the [`Transport`] it sends requests with.
+/// This defaults to [`DefaultTransport`]; use
+/// [`ClientBuilder::build_with_transport`] to plug in another.
+///
 /// Interceptors are added via:
 /// - [`ClientBuilder::with_interceptor`]: Header-level interceptors (simple)
 /// - [`ClientBuilder::with_message_interceptor`]: Message-level interceptors (typed access)
@@ -84,21 +140,42 @@ fn is_reserved_header(name: &http::header::HeaderName) -> bool {
 /// ).await?;
 /// ```
 #[derive(Debug, Clone)]
-pub struct ConnectClient<I = ()> {
+pub struct ConnectClient<I = (), T = DefaultTransport> {
     /// HTTP transport.
-    transport: HyperTransport,
+    transport: T,
     /// Base URL for the service.
     base_url: String,
     /// Use protobuf encoding (true) or JSON encoding (false).
     use_proto: bool,
+    /// Wire protocol.
+    protocol: Protocol,
     /// Compression configuration for outgoing requests.
     compression: CompressionConfig,
     /// Compression encoding for outgoing request bodies.
     request_encoding: CompressionEncoding,
-    /// Accepted compression encodings for responses.
-    accept_encoding: Option<CompressionEncoding>,
+    /// Accepted compression encodings for responses, as a header value.
+    accept_encoding: Option<HeaderValue>,
     /// Default timeout for RPC calls.
     default_timeout: Option<Duration>,
+    /// Headers sent with every call.
+    default_headers: http::HeaderMap,
+    /// `User-Agent` of requests that don't set one.
+    user_agent: Option<HeaderValue>,
+    /// Maximum decompressed size of a response body or streaming message.
+    decompression_limit: usize,
+    /// Circuit breaker guarding every call.
+    circuit_breaker: Option<CircuitBreaker>,
+    /// Balancer picking the endpoint of each call.
+    balancer: Option<Balancer>,
+    /// Retry policies and budget for unary calls.
+    retry: RetryConfig,
+    /// Cache of Connect GET responses.
+    response_cache: Option<ResponseCache>,
+    /// Encodings accepted by servers, for request compression negotiation.
+    encodings: Option<EncodingCache>,
+    /// The transport is HTTP/1.1 only, so bidi streaming is unavailable.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    http1_only: bool,
     /// Unified interceptor chain (compile-time composed).
     interceptor: I,
 }
@@ -112,33 +189,70 @@ impl ConnectClient<()> {
     }
 }
 
-impl<I: InterceptorInternal> ConnectClient<I> {
+impl<I: InterceptorInternal, T: Transport> ConnectClient<I, T> {
     /// Create a new ConnectClient.
     ///
     /// This is called by [`ClientBuilder::build`]. Prefer using the builder API.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
-        transport: HyperTransport,
+        transport: T,
         base_url: String,
         use_proto: bool,
+        protocol: Protocol,
         compression: CompressionConfig,
         request_encoding: CompressionEncoding,
-        accept_encoding: Option<CompressionEncoding>,
+        accept_encodings: Vec<CompressionEncoding>,
         default_timeout: Option<Duration>,
+        default_headers: http::HeaderMap,
+        user_agent: Option<HeaderValue>,
+        decompression_limit: usize,
+        circuit_breaker: Option<CircuitBreaker>,
+        balancer: Option<Balancer>,
+        retry: RetryConfig,
+        response_cache: Option<ResponseCache>,
+        encodings: Option<EncodingCache>,
+        http1_only: bool,
         interceptor: I,
     ) -> Self {
         Self {
             transport,
             base_url,
             use_proto,
+            protocol,
             compression,
             request_encoding,
-            accept_encoding,
+            accept_encoding: accept_encoding_value(&accept_encodings),
             default_timeout,
+            default_headers,
+            user_agent,
+            decompression_limit,
+            circuit_breaker,
+            balancer,
+            retry,
+            response_cache,
+            encodings,
+            http1_only,
             interceptor,
         }
     }
 
+    /// Set the maximum size of a response, in bytes.
+    ///
+    /// Applies to unary response bodies and to each streaming response
+    /// message, before and after decompression. A response that is larger, or
+    /// that expands past this limit, fails with [`Code::ResourceExhausted`]
+    /// instead of being buffered in full.
+    ///
+    /// Defaults to [`DEFAULT_DECOMPRESSION_LIMIT`](crate::DEFAULT_DECOMPRESSION_LIMIT)
+    /// (64 MiB). See also [`ClientBuilder::max_receive_message_size`].
+    pub fn with_response_decompression_limit(mut self, bytes: usize) -> Self {
+        self.decompression_limit = bytes;
+        self
+    }
+
     /// Get the base URL.
+    ///
+    /// With a [`Balancer`], calls go to its endpoints instead.
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
@@ -148,6 +262,11 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         self.use_proto
     }
 
+    /// Get the wire protocol.
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
     /// Get the encoding name (for tracing/debugging).
     #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
     fn encoding_name(&self) -> &'static str {
@@ -156,26 +275,326 @@ impl<I: InterceptorInternal> ConnectClient<I> {
 
     /// Get the content type for unary requests.
     fn unary_content_type(&self) -> &'static str {
-        if self.use_proto {
-            "application/proto"
-        } else {
-            "application/json"
+        match (self.protocol, self.use_proto) {
+            (Protocol::Connect, true) => "application/proto",
+            (Protocol::Connect, false) => "application/json",
+            (Protocol::Grpc, true) => "application/grpc",
+            (Protocol::Grpc, false) => "application/grpc+json",
+            (Protocol::GrpcWeb, true) => "application/grpc-web+proto",
+            (Protocol::GrpcWeb, false) => "application/grpc-web+json",
+            (Protocol::GrpcWebText, true) => "application/grpc-web-text+proto",
+            (Protocol::GrpcWebText, false) => "application/grpc-web-text+json",
         }
     }
 
     /// Get the content type for streaming requests.
     fn streaming_content_type(&self) -> &'static str {
-        if self.use_proto {
-            "application/connect+proto"
+        match (self.protocol, self.use_proto) {
+            (Protocol::Connect, true) => "application/connect+proto",
+            (Protocol::Connect, false) => "application/connect+json",
+            _ => self.unary_content_type(),
+        }
+    }
+
+    /// Header naming the compression of request or response messages.
+    fn content_encoding_header(&self, streaming: bool) -> &'static str {
+        match (self.protocol, streaming) {
+            (Protocol::Connect, false) => "content-encoding",
+            (Protocol::Connect, true) => "connect-content-encoding",
+            _ => "grpc-encoding",
+        }
+    }
+
+    /// Header listing the compression encodings accepted for responses.
+    fn accept_encoding_header(&self, streaming: bool) -> &'static str {
+        match (self.protocol, streaming) {
+            (Protocol::Connect, false) => "accept-encoding",
+            (Protocol::Connect, true) => "connect-accept-encoding",
+            _ => "grpc-accept-encoding",
+        }
+    }
+
+    /// Headers in which servers list the encodings they accept for requests.
+    fn accepted_encodings_headers(&self) -> &'static [&'static str] {
+        match self.protocol {
+            Protocol::Connect => &["accept-encoding", "connect-accept-encoding"],
+            _ => &["grpc-accept-encoding"],
+        }
+    }
+
+    /// Get the compression encoding of request messages: the configured one,
+    /// or the one negotiated with the server if enabled.
+    fn negotiated_encoding(&self) -> CompressionEncoding {
+        let Some(encodings) = &self.encodings else {
+            return self.request_encoding;
+        };
+        match self.base_url.parse::<http::Uri>() {
+            Ok(uri) => encodings.select(&uri, self.request_encoding),
+            Err(_) => self.request_encoding,
+        }
+    }
+
+    /// Insert the protocol headers of a request: content type, protocol
+    /// version (Connect), `te` (gRPC) or `x-grpc-web` (gRPC-Web), timeout and
+    /// accepted encodings.
+    fn insert_protocol_headers(
+        &self,
+        headers: &mut http::HeaderMap,
+        streaming: bool,
+        timeout: Option<Duration>,
+    ) {
+        let content_type = if streaming {
+            self.streaming_content_type()
+        } else {
+            self.unary_content_type()
+        };
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+
+        match self.protocol {
+            Protocol::Connect => {
+                headers.insert(
+                    CONNECT_PROTOCOL_VERSION_HEADER,
+                    HeaderValue::from_static(CONNECT_PROTOCOL_VERSION),
+                );
+                if let Some(timeout_ms) = timeout.and_then(duration_to_timeout_header) {
+                    headers.insert(CONNECT_TIMEOUT_HEADER, timeout_ms.parse().unwrap());
+                }
+            }
+            Protocol::Grpc | Protocol::GrpcWeb | Protocol::GrpcWebText => {
+                if self.protocol.is_grpc_web() {
+                    headers.insert(GRPC_WEB_HEADER, HeaderValue::from_static("1"));
+                } else {
+                    headers.insert(header::TE, HeaderValue::from_static("trailers"));
+                }
+                if let Some(grpc_timeout) = timeout.and_then(duration_to_grpc_timeout) {
+                    headers.insert(GRPC_TIMEOUT_HEADER, grpc_timeout.parse().unwrap());
+                }
+            }
+        }
+
+        if let Some(accept) = &self.accept_encoding {
+            headers.insert(self.accept_encoding_header(streaming), accept.clone());
+        }
+
+        #[cfg(feature = "opentelemetry")]
+        crate::trace_context::inject_trace_context(headers);
+    }
+
+    /// Get the default headers of the client to send with a call: those not
+    /// reserved by the protocol nor replaced by the headers of `options`.
+    fn default_headers<'a>(
+        &'a self,
+        options: &'a CallOptions,
+    ) -> impl Iterator<Item = (&'a http::HeaderName, &'a HeaderValue)> {
+        self.default_headers
+            .iter()
+            .filter(|(name, _)| !is_reserved_header(name) && !options.headers.contains_key(*name))
+    }
+
+    /// Get the compression encoding of the response messages.
+    fn response_encoding(
+        &self,
+        headers: &http::HeaderMap,
+        streaming: bool,
+    ) -> Result<CompressionEncoding, ClientError> {
+        let content_encoding = headers
+            .get(self.content_encoding_header(streaming))
+            .and_then(|v| v.to_str().ok());
+
+        CompressionEncoding::from_header(content_encoding).ok_or_else(|| {
+            ClientError::Protocol(format!(
+                "unsupported response encoding: {:?}",
+                content_encoding
+            ))
+        })
+    }
+
+    /// Build the error for a response with a non-success HTTP status.
+    ///
+    /// The error keeps the HTTP status and headers of the response.
+    async fn error_response(&self, response: http::Response<T::Body>) -> ClientError {
+        let (parts, body) = response.into_parts();
+        let err = if self.protocol.is_grpc() {
+            grpc::http_status_error(parts.status, &parts.headers)
+        } else {
+            match read_body_limited(body, self.decompression_limit).await {
+                Ok(body) => decompress_and_parse_error(
+                    parts.status,
+                    &parts.headers,
+                    body,
+                    self.decompression_limit,
+                ),
+                Err(e) => e,
+            }
+        };
+        let metadata = match self.protocol {
+            Protocol::Connect => Metadata::from_unary_headers(parts.headers),
+            _ => Metadata::new(parts.headers),
+        };
+        err.with_response(ErrorResponse::new(parts.status, metadata))
+    }
+
+    /// Send a request, with the client-side timeout if configured.
+    ///
+    /// With a circuit breaker, fails fast while the circuit is open and
+    /// records transport errors, timeouts and HTTP 5xx responses as failures.
+    /// With a balancer, sends the request to the endpoint it picks and marks
+    /// that endpoint unhealthy on a transport error. With compression
+    /// negotiation, remembers the encodings the server accepts. Adds the
+    /// client's `User-Agent` if the request has none.
+    async fn send(
+        &self,
+        mut req: Request<TransportBody>,
+        effective_timeout: Option<Duration>,
+    ) -> Result<http::Response<T::Body>, ClientError> {
+        let permit = self
+            .circuit_breaker
+            .as_ref()
+            .map(CircuitBreaker::acquire)
+            .transpose()?;
+        if let Some(user_agent) = &self.user_agent
+            && !req.headers().contains_key(header::USER_AGENT)
+        {
+            req.headers_mut()
+                .insert(header::USER_AGENT, user_agent.clone());
+        }
+        let uri = self.encodings.is_some().then(|| req.uri().clone());
+        let picked = self.balancer.as_ref().map(Balancer::pick);
+        if let Some(picked) = &picked {
+            *req.uri_mut() = self.endpoint_uri(req.uri(), picked.url())?;
+        }
+        let result = match effective_timeout {
+            Some(t) => timeout(t, self.transport.request(req))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(ClientError::new(
+                        Code::DeadlineExceeded,
+                        "client timeout exceeded",
+                    ))
+                }),
+            None => self.transport.request(req).await,
+        };
+        if let Some(permit) = permit {
+            permit.record(match &result {
+                Ok(response) => !response.status().is_server_error(),
+                Err(e) => !is_failure(e),
+            });
+        }
+        if let (Some(picked), Err(ClientError::Transport(_))) = (&picked, &result) {
+            picked.mark_unhealthy();
+        }
+        if let (Some(encodings), Some(uri), Ok(response)) = (&self.encodings, &uri, &result) {
+            encodings.record(uri, response.headers(), self.accepted_encodings_headers());
+        }
+        result
+    }
+
+    /// Move a request URI from the base URL to `endpoint`.
+    fn endpoint_uri(&self, uri: &http::Uri, endpoint: &str) -> Result<http::Uri, ClientError> {
+        let uri = uri.to_string();
+        let rest = uri.strip_prefix(&self.base_url).unwrap_or(&uri);
+        format!("{}{}", endpoint, rest).parse().map_err(|e| {
+            ClientError::Transport(format!("invalid endpoint URL {}: {}", endpoint, e))
+        })
+    }
+
+    /// Wrap an enveloped response body in a [`FrameDecoder`].
+    ///
+    /// For gRPC the decoder reads the call status from the HTTP trailers (for
+    /// gRPC-Web, the trailer frame), or from `headers` for a trailers-only
+    /// response. gRPC-Web text bodies are base64-decoded first. The size of
+    /// each message is recorded in `metrics`, and the body fails with
+    /// `Canceled` once `cancel` fires.
+    fn response_decoder<Res>(
+        &self,
+        body: T::Body,
+        headers: &http::HeaderMap,
+        encoding: CompressionEncoding,
+        metrics: &CallMetrics,
+        cancel: Option<&CancellationToken>,
+    ) -> FrameDecoder<impl Stream<Item = Result<Bytes, ClientError>> + Unpin + use<I, T, Res>, Res>
+    {
+        let trailers = TrailersSlot::default();
+        let capture = (self.protocol == Protocol::Grpc).then(|| trailers.clone());
+        let byte_stream = body_to_stream(body, capture);
+        let byte_stream = if self.protocol == Protocol::GrpcWebText {
+            Either::Left(grpc::TextDecoder::new(byte_stream))
+        } else {
+            Either::Right(byte_stream)
+        };
+        let mut frames = metrics.response_frames();
+        let byte_stream = byte_stream.inspect(move |chunk| {
+            if let Ok(chunk) = chunk {
+                frames.data(chunk);
+            }
+        });
+        let byte_stream = Cancelable::new(byte_stream, cancel);
+
+        let decoder = FrameDecoder::new(byte_stream, self.use_proto, encoding)
+            .with_decompression_limit(self.decompression_limit);
+        match self.protocol {
+            Protocol::Connect => decoder,
+            Protocol::Grpc => {
+                decoder.with_grpc_status(GrpcStatusSource::new(trailers, headers.clone()))
+            }
+            Protocol::GrpcWeb | Protocol::GrpcWebText => {
+                decoder.with_grpc_status(GrpcStatusSource::web(headers.clone()))
+            }
+        }
+    }
+
+    /// Build the URL of a Connect GET request for `message`.
+    ///
+    /// The message is sent base64url-encoded in the `message` query
+    /// parameter, alongside the `connect`, `encoding` and (if compressed
+    /// with `compression`) `compression` parameters.
+    fn get_url(
+        &self,
+        url: &str,
+        message: &[u8],
+        compression: Option<CompressionEncoding>,
+    ) -> String {
+        let encoding = if self.use_proto { "proto" } else { "json" };
+        let message = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(message);
+        let mut get_url = format!(
+            "{}?connect=v{}&encoding={}&base64=1",
+            url, CONNECT_PROTOCOL_VERSION, encoding
+        );
+        if let Some(compression) = compression {
+            get_url.push_str("&compression=");
+            get_url.push_str(compression.as_str());
+        }
+        get_url.push_str("&message=");
+        get_url.push_str(&message);
+        get_url
+    }
+
+    /// Encode an enveloped request body for gRPC-Web text mode, if enabled.
+    fn encode_body_text(&self, body: Bytes) -> Bytes {
+        if self.protocol == Protocol::GrpcWebText {
+            grpc::encode_text(&body)
         } else {
-            "application/connect+json"
+            body
+        }
+    }
+
+    /// Fail a client or bidi streaming call in gRPC-Web mode, which only
+    /// supports unary and server-streaming calls.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn check_request_streaming(&self) -> Result<(), ClientError> {
+        if self.protocol.is_grpc_web() {
+            return Err(ClientError::new(
+                Code::Unimplemented,
+                "gRPC-Web does not support client or bidi streaming",
+            ));
         }
+        Ok(())
     }
 
     /// Encode a message for sending.
-    fn encode_message<T>(&self, msg: &T) -> Result<Bytes, ClientError>
+    fn encode_message<M>(&self, msg: &M) -> Result<Bytes, ClientError>
     where
-        T: Message + Serialize,
+        M: Message + Serialize,
     {
         if self.use_proto {
             Ok(Bytes::from(msg.encode_to_vec()))
@@ -187,12 +606,12 @@ impl<I: InterceptorInternal> ConnectClient<I> {
     }
 
     /// Decode a message from response bytes.
-    fn decode_message<T>(&self, bytes: &[u8]) -> Result<T, ClientError>
+    fn decode_message<M>(&self, bytes: &[u8]) -> Result<M, ClientError>
     where
-        T: Message + DeserializeOwned + Default,
+        M: Message + DeserializeOwned + Default,
     {
         if self.use_proto {
-            T::decode(bytes)
+            M::decode(bytes)
                 .map_err(|e| ClientError::Decode(format!("protobuf decoding failed: {}", e)))
         } else {
             serde_json::from_slice(bytes)
@@ -200,10 +619,14 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         }
     }
 
-    /// Compress request body if configured.
-    fn maybe_compress(&self, body: Bytes) -> Result<(Bytes, bool), ClientError> {
+    /// Compress request body with `encoding` if configured.
+    fn maybe_compress(
+        &self,
+        body: Bytes,
+        encoding: CompressionEncoding,
+    ) -> Result<(Bytes, bool), ClientError> {
         // Check if compression is enabled and body meets threshold
-        if self.request_encoding.is_identity() || self.compression.is_disabled() {
+        if encoding.is_identity() || self.compression.is_disabled() {
             return Ok((body, false));
         }
 
@@ -212,10 +635,7 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         }
 
         // Get codec for the encoding
-        let Some(codec) = self
-            .request_encoding
-            .codec_with_level(self.compression.level)
-        else {
+        let Some(codec) = encoding.codec_with_level(self.compression.level) else {
             return Ok((body, false));
         };
 
@@ -297,12 +717,63 @@ impl<I: InterceptorInternal> ConnectClient<I> {
     ///     options,
     /// ).await?;
     /// ```
+    ///
+    /// If the builder configured a retry policy for `procedure` (see
+    /// [`ClientBuilder::retry_policy_for`]), the call is retried under it as
+    /// with [`call_unary_with_retry`](Self::call_unary_with_retry).
     pub async fn call_unary_with_options<Req, Res>(
         &self,
         procedure: &str,
         request: &Req,
         options: CallOptions,
     ) -> Result<ConnectResponse<Res>, ClientError>
+    where
+        Req: Message + Serialize + Clone + 'static,
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        match self.retry.policy_for(procedure) {
+            Some(policy) => {
+                self.call_unary_with_retry(procedure, request, options, policy)
+                    .await
+            }
+            None => self.call_unary_once(procedure, request, options).await,
+        }
+    }
+
+    /// Make a unary RPC call without retries.
+    async fn call_unary_once<Req, Res>(
+        &self,
+        procedure: &str,
+        request: &Req,
+        options: CallOptions,
+    ) -> Result<ConnectResponse<Res>, ClientError>
+    where
+        Req: Message + Serialize + Clone + 'static,
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        let metrics = CallMetrics::start(procedure, self.protocol);
+        if options.previous_error.is_some() {
+            metrics.retry();
+        }
+        let cancel = options.cancel.clone();
+        let result = until_canceled(
+            cancel,
+            self.send_unary(procedure, request, options, &metrics),
+        )
+        .await;
+        metrics.finish(result.as_ref().map_or_else(ClientError::code, |_| Code::Ok));
+        result
+    }
+
+    /// Send a unary request and read its response, recording message sizes
+    /// in `metrics`.
+    async fn send_unary<Req, Res>(
+        &self,
+        procedure: &str,
+        request: &Req,
+        options: CallOptions,
+        metrics: &CallMetrics,
+    ) -> Result<ConnectResponse<Res>, ClientError>
     where
         Req: Message + Serialize + Clone + 'static,
         Res: Message + DeserializeOwned + Default + 'static,
@@ -318,25 +789,16 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         .entered();
 
         // 1. Build headers (before RPC interceptor so it can modify them)
+        // (options timeout overrides default)
+        let effective_timeout = options.timeout.or(self.default_timeout);
         let mut headers = http::HeaderMap::new();
-        headers.insert(
-            http::header::CONTENT_TYPE,
-            self.unary_content_type().parse().unwrap(),
-        );
-        headers.insert(
-            CONNECT_PROTOCOL_VERSION_HEADER,
-            CONNECT_PROTOCOL_VERSION.parse().unwrap(),
-        );
+        self.insert_protocol_headers(&mut headers, false, effective_timeout);
 
-        // Add Connect-Timeout-Ms header (options timeout overrides default)
-        let effective_timeout = options.timeout.or(self.default_timeout);
-        if let Some(t) = effective_timeout {
-            if let Some(timeout_ms) = duration_to_timeout_header(t) {
-                headers.insert(CONNECT_TIMEOUT_HEADER, timeout_ms.parse().unwrap());
-            }
+        // Add default headers, then custom headers from options (skip
+        // reserved protocol headers)
+        for (name, value) in self.default_headers(&options) {
+            headers.insert(name.clone(), value.clone());
         }
-
-        // Add custom headers from options (skip reserved protocol headers)
         for (name, value) in options.headers.iter() {
             if !is_reserved_header(name) {
                 headers.insert(name.clone(), value.clone());
@@ -346,120 +808,282 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         // 2. Apply interceptor to request
         let mut request = request.clone();
         {
-            let mut ctx = RequestContext::new(procedure, &mut headers);
-            self.interceptor.intercept_request(&mut ctx, &mut request)?;
+            let mut ctx = RequestContext::new(procedure, &mut headers)
+                .with_attempt(options.attempt.max(1), options.previous_error.as_ref());
+            self.interceptor
+                .intercept_request_async(&mut ctx, &mut request)
+                .await?;
+            options
+                .interceptors
+                .intercept_request(&mut ctx, Some(&mut request))?;
         }
 
         // 3. Encode request body
         let body = self.encode_message(&request)?;
 
         // 5. Maybe compress
-        let (body, compressed) = self.maybe_compress(body)?;
-
-        // Add Content-Encoding if compressed
-        if compressed {
-            headers.insert(
-                header::CONTENT_ENCODING,
-                self.request_encoding.as_str().parse().unwrap(),
-            );
-        }
+        let request_encoding = self.negotiated_encoding();
+        let (body, compressed) = self.maybe_compress(body, request_encoding)?;
+        metrics.request_message(body.len());
 
         // 6. Build URL (strip leading slash from procedure to avoid double slashes)
         let procedure = procedure.strip_prefix('/').unwrap_or(procedure);
         let url = format!("{}/{}", self.base_url, procedure);
 
-        // 7. Build HTTP request
-        let mut req_builder = Request::builder().method(Method::POST).uri(&url);
-
-        // Copy headers
-        for (name, value) in headers.iter() {
-            req_builder = req_builder.header(name, value);
+        // Connect GET: the message moves into the query string, unless the
+        // URL would be too long
+        let get_url = (options.use_get && self.protocol == Protocol::Connect)
+            .then(|| self.get_url(&url, &body, compressed.then_some(request_encoding)))
+            .filter(|get_url| get_url.len() <= MAX_GET_URL_LENGTH);
+
+        if get_url.is_some() {
+            // The query string carries the protocol version and encoding
+            headers.remove(header::CONTENT_TYPE);
+            headers.remove(CONNECT_PROTOCOL_VERSION_HEADER);
+        } else if compressed {
+            // Add Content-Encoding (grpc-encoding for gRPC)
+            headers.insert(
+                self.content_encoding_header(false),
+                HeaderValue::from_static(request_encoding.as_str()),
+            );
         }
 
-        // Add Accept-Encoding if configured
-        if let Some(accept) = self.accept_encoding {
-            req_builder = req_builder.header(header::ACCEPT_ENCODING, accept.as_str());
+        // Connect GET responses may be answered from, or revalidated
+        // against, the response cache
+        let cache = self.response_cache.as_ref().zip(get_url.clone());
+        let mut cached = None;
+        let mut stale = None;
+        if let Some((cache, key)) = &cache {
+            match cache.lookup(key, &headers) {
+                CacheLookup::Miss => {}
+                CacheLookup::Fresh(response) => cached = Some(response),
+                CacheLookup::Stale(response, etag) => {
+                    headers.insert(header::IF_NONE_MATCH, etag);
+                    stale = Some(response);
+                }
+            }
         }
 
-        // Build request with body
-        let req = req_builder
-            .body(TransportBody::full(body))
-            .map_err(|e| ClientError::Protocol(format!("failed to build request: {}", e)))?;
-
-        // 8. Send request (with client-side timeout if configured)
-        let response = if let Some(t) = effective_timeout {
-            timeout(t, self.transport.request(req))
-                .await
-                .map_err(|_| {
-                    ClientError::new(Code::DeadlineExceeded, "client timeout exceeded")
-                })??
+        // gRPC frames unary messages like streaming ones
+        let body = if self.protocol.is_grpc() {
+            self.encode_body_text(Bytes::from(wrap_envelope(&body, compressed)))
         } else {
-            self.transport.request(req).await?
+            body
         };
 
-        // 9. Check response status
-        let status = response.status();
-        let response_headers = response.headers().clone();
+        let (response_headers, body) = match cached {
+            Some(response) => (
+                response.headers().clone(),
+                UnaryBody::Cached(response.body().clone()),
+            ),
+            None => {
+                // 7. Build HTTP request
+                let (method, uri, body) = match get_url {
+                    Some(get_url) => (Method::GET, get_url, TransportBody::empty()),
+                    None => (Method::POST, url, TransportBody::full(body)),
+                };
+                let mut req_builder = Request::builder().method(method).uri(&uri);
+
+                // Copy headers
+                for (name, value) in headers.iter() {
+                    req_builder = req_builder.header(name, value);
+                }
 
-        if !status.is_success() {
-            let body_bytes = response
-                .into_body()
-                .collect()
-                .await
-                .map_err(|e| ClientError::Transport(format!("failed to read error body: {}", e)))?
-                .to_bytes();
-            return Err(decompress_and_parse_error(
-                status,
-                &response_headers,
-                body_bytes,
-            ));
-        }
+                // Build request with body
+                let req = req_builder.body(body).map_err(|e| {
+                    ClientError::Protocol(format!("failed to build request: {}", e))
+                })?;
+
+                // 8. Send request (with client-side timeout and circuit breaker)
+                let response = self.send(req, effective_timeout).await?;
+
+                // 9. Check response status
+                let status = response.status();
+                if status == http::StatusCode::NOT_MODIFIED
+                    && let (Some((cache, key)), Some(stale)) = (&cache, stale)
+                {
+                    let response = cache.revalidated(key, stale, response.headers());
+                    (
+                        response.headers().clone(),
+                        UnaryBody::Cached(response.body().clone()),
+                    )
+                } else if !status.is_success() {
+                    return Err(self.error_response(response).await);
+                } else {
+                    let response_headers = response.headers().clone();
+                    (response_headers, UnaryBody::Response(response.into_body()))
+                }
+            }
+        };
 
         // 10. Handle response decompression
-        let content_encoding = response_headers
-            .get(header::CONTENT_ENCODING)
-            .and_then(|v| v.to_str().ok());
+        let response_encoding = self.response_encoding(&response_headers, false)?;
 
-        let response_encoding =
-            CompressionEncoding::from_header(content_encoding).ok_or_else(|| {
-                ClientError::Protocol(format!(
-                    "unsupported response encoding: {:?}",
-                    content_encoding
-                ))
-            })?;
-
-        // 11. Get response body
-        let body_bytes = response
-            .into_body()
-            .collect()
-            .await
-            .map_err(|e| ClientError::Transport(format!("failed to read response body: {}", e)))?
-            .to_bytes();
-
-        // Decompress if needed
-        let body_bytes = if let Some(codec) = response_encoding.codec() {
-            codec
-                .decompress(&body_bytes)
-                .map_err(|e| ClientError::Decode(format!("decompression failed: {}", e)))?
-        } else {
-            body_bytes
+        // 11. Read and decode the response
+        let (mut message, trailers): (Res, _) = match body {
+            UnaryBody::Response(body) if self.protocol.is_grpc() => {
+                // A single framed message followed by the grpc-status trailers
+                let decoder = self.response_decoder(
+                    body,
+                    &response_headers,
+                    response_encoding,
+                    metrics,
+                    options.cancel.as_ref(),
+                );
+                let (message, trailers) = read_single_message(decoder).await?;
+                (message, Some(trailers))
+            }
+            body => {
+                let (body_bytes, fetched) = match body {
+                    UnaryBody::Cached(body_bytes) => (body_bytes, false),
+                    UnaryBody::Response(body) => (
+                        read_body_limited(body, self.decompression_limit).await?,
+                        true,
+                    ),
+                };
+                metrics.response_message(body_bytes.len());
+
+                // Decompress if needed, bounded by the decompression limit
+                let decompressed = if let Some(codec) = response_encoding.codec() {
+                    codec.decompress_limited(&body_bytes, self.decompression_limit)?
+                } else {
+                    body_bytes.clone()
+                };
+                let message = self.decode_message(&decompressed)?;
+
+                if fetched && let Some((cache, key)) = &cache {
+                    cache.store(key, &headers, &response_headers, body_bytes);
+                }
+                (message, None)
+            }
         };
 
-        // 12. Decode response
-        let mut message: Res = self.decode_message(&body_bytes)?;
-
         // 12. Apply interceptor to response
         {
             let ctx = ResponseContext::new(procedure, &response_headers);
+            options
+                .interceptors
+                .intercept_response(&ctx, &mut message)?;
             self.interceptor.intercept_response(&ctx, &mut message)?;
         }
 
-        // 14. Extract metadata
-        let metadata = Metadata::new(response_headers);
+        // 14. Extract metadata (Connect sends unary trailers as prefixed headers)
+        let metadata = match trailers {
+            Some(trailers) => Metadata::new(response_headers).with_trailers(trailers),
+            None => Metadata::from_unary_headers(response_headers),
+        };
 
         Ok(ConnectResponse::new(message, metadata))
     }
 
+    /// Make a unary RPC call, retrying retryable failures under `policy`.
+    ///
+    /// Unlike wrapping [`call_unary_with_options`](Self::call_unary_with_options)
+    /// in [`retry_with_policy`](crate::retry_with_policy), interceptors see
+    /// which attempt they are running for through
+    /// [`RequestContext::attempt`] and [`RequestContext::previous_error`].
+    /// [`RetryPolicy::on_retry`] is called before each retry.
+    ///
+    /// `policy` replaces any policy configured on the builder for
+    /// `procedure`. Retries still spend the client's
+    /// [`RetryBudget`](crate::RetryBudget), if it has one.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{CallOptions, RetryPolicy};
+    ///
+    /// let policy = RetryPolicy::new()
+    ///     .max_retries(3)
+    ///     .on_retry(|event| tracing::warn!(attempt = event.attempt, "retrying: {}", event.error));
+    ///
+    /// let response = client.call_unary_with_retry::<GetUserRequest, GetUserResponse>(
+    ///     "users.v1.UserService/GetUser",
+    ///     &GetUserRequest { id: "123".to_string() },
+    ///     CallOptions::new(),
+    ///     &policy,
+    /// ).await?;
+    /// ```
+    pub async fn call_unary_with_retry<Req, Res>(
+        &self,
+        procedure: &str,
+        request: &Req,
+        options: CallOptions,
+        policy: &RetryPolicy,
+    ) -> Result<ConnectResponse<Res>, ClientError>
+    where
+        Req: Message + Serialize + Clone + 'static,
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        retry_attempts(
+            policy,
+            self.retry.budget.as_ref(),
+            |attempt, previous_error| {
+                let mut options = options.clone();
+                options.attempt = attempt;
+                options.previous_error = previous_error;
+                self.call_unary_once(procedure, request, options)
+            },
+        )
+        .await
+    }
+
+    /// Make a unary RPC call, hedging it under `policy` if it is idempotent.
+    ///
+    /// When the call is marked with [`CallOptions::idempotent`] (or sent with
+    /// [`CallOptions::use_get`]), a duplicate request is sent each time
+    /// [`HedgingPolicy::delay`] passes without a response, up to
+    /// [`HedgingPolicy::max_attempts`] in total. The first successful
+    /// response is returned and the other requests are canceled. See
+    /// [`hedge`](crate::hedge) for how errors are handled.
+    ///
+    /// Calls that aren't idempotent are made with
+    /// [`call_unary_with_options`](Self::call_unary_with_options). Hedged
+    /// attempts are not retried, even if the builder configured a retry
+    /// policy.
+    ///
+    /// Interceptors see the hedged attempt number through
+    /// [`RequestContext::attempt`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{CallOptions, HedgingPolicy};
+    /// use std::time::Duration;
+    ///
+    /// let policy = HedgingPolicy::new(Duration::from_millis(50));
+    ///
+    /// let response = client.call_unary_with_hedging::<GetUserRequest, GetUserResponse>(
+    ///     "users.v1.UserService/GetUser",
+    ///     &GetUserRequest { id: "123".to_string() },
+    ///     CallOptions::new().idempotent(true),
+    ///     &policy,
+    /// ).await?;
+    /// ```
+    pub async fn call_unary_with_hedging<Req, Res>(
+        &self,
+        procedure: &str,
+        request: &Req,
+        options: CallOptions,
+        policy: &HedgingPolicy,
+    ) -> Result<ConnectResponse<Res>, ClientError>
+    where
+        Req: Message + Serialize + Clone + 'static,
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        if !options.is_idempotent() {
+            return self
+                .call_unary_with_options(procedure, request, options)
+                .await;
+        }
+        hedge(policy, |attempt| {
+            let mut options = options.clone();
+            options.attempt = attempt;
+            self.call_unary_once(procedure, request, options)
+        })
+        .await
+    }
+
     /// Make a server-streaming RPC call.
     ///
     /// The server sends multiple messages in response to a single request.
@@ -520,7 +1144,7 @@ impl<I: InterceptorInternal> ConnectClient<I> {
                 FrameDecoder<
                     impl futures::Stream<Item = Result<Bytes, ClientError>>
                     + Unpin
-                    + use<'_, I, Req, Res>,
+                    + use<'_, I, T, Req, Res>,
                     Res,
                 >,
                 Res,
@@ -565,7 +1189,7 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         ConnectResponse<
             InterceptingStreaming<
                 FrameDecoder<
-                    impl Stream<Item = Result<Bytes, ClientError>> + Unpin + use<'_, I, Req, Res>,
+                    impl Stream<Item = Result<Bytes, ClientError>> + Unpin + use<'_, I, T, Req, Res>,
                     Res,
                 >,
                 Res,
@@ -578,57 +1202,98 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         Req: Message + Serialize,
         Res: Message + DeserializeOwned + Default + 'static,
     {
-        #[cfg(feature = "tracing")]
-        let _span = info_span!(
-            "rpc.call",
-            rpc.method = %procedure,
-            rpc.type = "server_stream",
-            rpc.encoding = %self.encoding_name(),
-            otel.kind = "client",
+        let metrics = CallMetrics::start(procedure, self.protocol);
+        if options.previous_error.is_some() {
+            metrics.retry();
+        }
+        let cancel = options.cancel.clone();
+        let result = until_canceled(
+            cancel,
+            self.send_server_stream(procedure, request, options, &metrics),
         )
-        .entered();
-
-        // 1. Encode request body
-        let body = self.encode_message(request)?;
-
-        // 2. Maybe compress
-        let (body, compressed) = self.maybe_compress(body)?;
-
-        // 3. Wrap in envelope for streaming request
-        // Connect streaming protocol requires envelope framing even for single-message requests
-        let body = Bytes::from(wrap_envelope(&body, compressed));
+        .await;
+        if let Err(e) = &result {
+            metrics.finish(e.code());
+        }
+        result
+    }
 
-        // 4. Build URL (strip leading slash from procedure to avoid double slashes)
+    /// Send a server-streaming request, recording its messages and, once the
+    /// response stream ends, its outcome in `metrics`.
+    async fn send_server_stream<Req, Res>(
+        &self,
+        procedure: &str,
+        request: &Req,
+        options: CallOptions,
+        metrics: &CallMetrics,
+    ) -> Result<
+        ConnectResponse<
+            InterceptingStreaming<
+                FrameDecoder<
+                    impl Stream<Item = Result<Bytes, ClientError>> + Unpin + use<'_, I, T, Req, Res>,
+                    Res,
+                >,
+                Res,
+                I,
+            >,
+        >,
+        ClientError,
+    >
+    where
+        Req: Message + Serialize,
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        #[cfg(feature = "tracing")]
+        let _span = info_span!(
+            "rpc.call",
+            rpc.method = %procedure,
+            rpc.type = "server_stream",
+            rpc.encoding = %self.encoding_name(),
+            otel.kind = "client",
+        )
+        .entered();
+
+        // 1. Encode request body
+        let body = self.encode_message(request)?;
+
+        // 2. Maybe compress
+        let request_encoding = self.negotiated_encoding();
+        let (body, compressed) = self.maybe_compress(body, request_encoding)?;
+        metrics.request_message(body.len());
+
+        // 3. Wrap in envelope for streaming request
+        // Connect streaming protocol requires envelope framing even for single-message requests
+        let body = self.encode_body_text(Bytes::from(wrap_envelope(&body, compressed)));
+
+        // 4. Build URL (strip leading slash from procedure to avoid double slashes)
         let procedure = procedure.strip_prefix('/').unwrap_or(procedure);
         let url = format!("{}/{}", self.base_url, procedure);
 
         // 5. Build request with streaming content-type
-        let mut req_builder = Request::builder()
-            .method(Method::POST)
-            .uri(&url)
-            .header(CONNECT_PROTOCOL_VERSION_HEADER, CONNECT_PROTOCOL_VERSION)
-            .header(header::CONTENT_TYPE, self.streaming_content_type());
+        // (options timeout overrides default)
+        let effective_timeout = options.timeout.or(self.default_timeout);
+        let message_timeout = options.message_timeout;
+        let mut headers = http::HeaderMap::new();
+        self.insert_protocol_headers(&mut headers, true, effective_timeout);
 
         // Add Connect-Content-Encoding if compressed (streaming uses this header, not Content-Encoding)
         if compressed {
-            req_builder =
-                req_builder.header("connect-content-encoding", self.request_encoding.as_str());
+            headers.insert(
+                self.content_encoding_header(true),
+                HeaderValue::from_static(request_encoding.as_str()),
+            );
         }
 
-        // Add Accept-Encoding if configured
-        if let Some(accept) = &self.accept_encoding {
-            req_builder = req_builder.header("connect-accept-encoding", accept.as_str());
+        let mut req_builder = Request::builder().method(Method::POST).uri(&url);
+        for (name, value) in headers.iter() {
+            req_builder = req_builder.header(name, value);
         }
 
-        // Add Connect-Timeout-Ms header (options timeout overrides default)
-        let effective_timeout = options.timeout.or(self.default_timeout);
-        if let Some(t) = effective_timeout {
-            if let Some(timeout_ms) = duration_to_timeout_header(t) {
-                req_builder = req_builder.header(CONNECT_TIMEOUT_HEADER, timeout_ms);
-            }
+        // Add default headers, then custom headers from options (skip
+        // reserved protocol headers)
+        for (name, value) in self.default_headers(&options) {
+            req_builder = req_builder.header(name, value);
         }
-
-        // Add custom headers from options (skip reserved protocol headers)
         for (name, value) in options.headers.iter() {
             if !is_reserved_header(name) {
                 req_builder = req_builder.header(name, value);
@@ -640,7 +1305,10 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         {
             let mut ctx = RequestContext::new(procedure, &mut interceptor_headers);
             // Use a unit placeholder - streaming interceptors use on_stream_send for messages
-            self.interceptor.intercept_request(&mut ctx, &mut ())?;
+            self.interceptor
+                .intercept_request_async(&mut ctx, &mut ())
+                .await?;
+            options.interceptors.intercept_request(&mut ctx, None)?;
         }
         for (name, value) in interceptor_headers.iter() {
             req_builder = req_builder.header(name, value);
@@ -651,57 +1319,34 @@ impl<I: InterceptorInternal> ConnectClient<I> {
             .body(TransportBody::full(body))
             .map_err(|e| ClientError::Protocol(format!("failed to build request: {}", e)))?;
 
-        // 5. Send request (with client-side timeout if configured)
-        let response = if let Some(t) = effective_timeout {
-            timeout(t, self.transport.request(req))
-                .await
-                .map_err(|_| {
-                    ClientError::new(Code::DeadlineExceeded, "client timeout exceeded")
-                })??
-        } else {
-            self.transport.request(req).await?
-        };
+        // 5. Send request (with client-side timeout and circuit breaker)
+        let response = self.send(req, effective_timeout).await?;
 
         // 6. Check response status
         let status = response.status();
         let response_headers = response.headers().clone();
 
         if !status.is_success() {
-            let body_bytes = response
-                .into_body()
-                .collect()
-                .await
-                .map_err(|e| ClientError::Transport(format!("failed to read error body: {}", e)))?
-                .to_bytes();
-            return Err(decompress_and_parse_error(
-                status,
-                &response_headers,
-                body_bytes,
-            ));
+            return Err(self.error_response(response).await);
         }
 
         // 7. Get compression encoding from Connect-Content-Encoding header
-        let content_encoding = response_headers
-            .get("connect-content-encoding")
-            .and_then(|v| v.to_str().ok());
-
-        let response_encoding =
-            CompressionEncoding::from_header(content_encoding).ok_or_else(|| {
-                ClientError::Protocol(format!(
-                    "unsupported response encoding: {:?}",
-                    content_encoding
-                ))
-            })?;
-
-        // 8. Get the streaming body
-        let body = response.into_body();
-        let byte_stream = body_to_stream(body);
-
-        // 9. Wrap with FrameDecoder
-        let decoder = FrameDecoder::new(byte_stream, self.use_proto, response_encoding);
+        let response_encoding = self.response_encoding(&response_headers, true)?;
+
+        // 8. Wrap the streaming body with FrameDecoder
+        let decoder = self.response_decoder(
+            response.into_body(),
+            &response_headers,
+            response_encoding,
+            metrics,
+            options.cancel.as_ref(),
+        );
 
         // 10. Wrap with Streaming
-        let stream_body = Streaming::new(decoder);
+        let mut stream_body = Streaming::new(decoder).with_metrics(metrics.clone());
+        if let Some(timeout) = message_timeout {
+            stream_body = stream_body.with_message_timeout(timeout);
+        }
 
         // 11. Wrap with InterceptingStreaming for per-message interception
         let intercepting_stream = InterceptingStreaming::new(
@@ -719,6 +1364,156 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         Ok(ConnectResponse::new(intercepting_stream, metadata))
     }
 
+    /// Make a server-streaming RPC call that resumes after interruptions.
+    ///
+    /// When the call fails with a retryable error (see
+    /// [`ClientError::is_retryable`]), before or during the stream, it is
+    /// made again after a backoff from `policy`. If messages were received,
+    /// the next request is built by `resume` from the current request and the
+    /// last message, e.g. to move a cursor or offset past the messages
+    /// already seen; otherwise the same request is sent again. The result is
+    /// one stream of messages across reconnections.
+    ///
+    /// `policy.max_retries` bounds consecutive failures: the backoff starts
+    /// over once a message arrives, so a long-lived stream may reconnect any
+    /// number of times as long as it makes progress. Reconnects spend the
+    /// client's [`RetryBudget`](crate::RetryBudget), if it has one, and
+    /// interceptors see the attempt through [`RequestContext::attempt`].
+    /// A non-retryable error, or running out of retries, ends the stream
+    /// with that error.
+    ///
+    /// The call is made when the stream is first polled.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{CallOptions, RetryPolicy};
+    /// use futures::StreamExt;
+    ///
+    /// let events = client.call_server_stream_resumable::<WatchRequest, Event, _>(
+    ///     "events.v1.EventService/Watch",
+    ///     WatchRequest { after: 0 },
+    ///     CallOptions::new(),
+    ///     RetryPolicy::new().max_retries(5),
+    ///     |_request, last| WatchRequest { after: last.sequence },
+    /// );
+    ///
+    /// let mut events = std::pin::pin!(events);
+    /// while let Some(event) = events.next().await {
+    ///     handle(event?);
+    /// }
+    /// ```
+    pub fn call_server_stream_resumable<'a, Req, Res, F>(
+        &'a self,
+        procedure: &'a str,
+        request: Req,
+        options: CallOptions,
+        policy: RetryPolicy,
+        resume: F,
+    ) -> impl Stream<Item = Result<Res, ClientError>> + use<'a, I, T, Req, Res, F>
+    where
+        Req: Message + Serialize + 'static,
+        Res: Message + DeserializeOwned + Default + Clone + 'static,
+        F: FnMut(&Req, &Res) -> Req + 'a,
+    {
+        /// Progress of a resumable call, carried between messages.
+        struct Resumable<Req, Res, F, S> {
+            request: Req,
+            options: CallOptions,
+            policy: RetryPolicy,
+            backoff: ExponentialBackoff,
+            resume: F,
+            /// The stream of the current attempt, if connected.
+            stream: Option<S>,
+            /// The last message of the current attempt.
+            last: Option<Res>,
+            previous_error: Option<ClientError>,
+            started: bool,
+            done: bool,
+        }
+
+        let backoff = policy.backoff();
+        let state = Resumable {
+            request,
+            options,
+            policy,
+            backoff,
+            resume,
+            stream: None,
+            last: None,
+            previous_error: None,
+            started: false,
+            done: false,
+        };
+
+        futures::stream::unfold(state, move |mut state| async move {
+            if state.done {
+                return None;
+            }
+            let budget = self.retry.budget.as_ref();
+            if !state.started {
+                state.started = true;
+                if let Err(msg) = state.policy.validate() {
+                    state.done = true;
+                    return Some((Err(ClientError::new(Code::InvalidArgument, msg)), state));
+                }
+                if let Some(budget) = budget {
+                    budget.deposit();
+                }
+            }
+
+            loop {
+                // The connecting arm comes first, so the stream's type is inferred
+                // before it is polled
+                let error = match &mut state.stream {
+                    None => {
+                        let mut options = state.options.clone();
+                        options.attempt = state.backoff.attempts() + 1;
+                        options.previous_error = state.previous_error.take();
+                        match self
+                            .call_server_stream_with_options::<Req, Res>(
+                                procedure,
+                                &state.request,
+                                options,
+                            )
+                            .await
+                        {
+                            Ok(response) => {
+                                state.stream = Some(response.into_inner());
+                                continue;
+                            }
+                            Err(e) => e,
+                        }
+                    }
+                    Some(stream) => match stream.next().await {
+                        Some(Ok(message)) => {
+                            state.backoff.reset();
+                            state.last = Some(message.clone());
+                            return Some((Ok(message), state));
+                        }
+                        Some(Err(e)) => e,
+                        None => return None,
+                    },
+                };
+
+                state.stream = None;
+                match next_retry(&state.policy, &mut state.backoff, budget, &error) {
+                    Some(delay) => {
+                        if let Some(last) = state.last.take() {
+                            state.request = (state.resume)(&state.request, &last);
+                        }
+                        crate::rt::sleep(delay).await;
+                        state.previous_error = Some(error);
+                    }
+                    None => {
+                        state.done = true;
+                        return Some((Err(error), state));
+                    }
+                }
+            }
+        })
+    }
+
     /// Make a client-streaming RPC call.
     ///
     /// The client sends multiple messages and receives a single response.
@@ -757,6 +1552,7 @@ impl<I: InterceptorInternal> ConnectClient<I> {
     ///
     /// println!("Response: {:?}", response.into_inner());
     /// ```
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn call_client_stream<Req, Res, S>(
         &self,
         procedure: &str,
@@ -795,12 +1591,41 @@ impl<I: InterceptorInternal> ConnectClient<I> {
     ///     options,
     /// ).await?;
     /// ```
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn call_client_stream_with_options<Req, Res, S>(
         &self,
         procedure: &str,
         request: S,
         options: CallOptions,
     ) -> Result<ConnectResponse<Res>, ClientError>
+    where
+        Req: Message + Serialize + 'static,
+        Res: Message + DeserializeOwned + Default,
+        S: Stream<Item = Req> + Send + Unpin + 'static,
+    {
+        let metrics = CallMetrics::start(procedure, self.protocol);
+        if options.previous_error.is_some() {
+            metrics.retry();
+        }
+        let cancel = options.cancel.clone();
+        let result = until_canceled(
+            cancel,
+            self.send_client_stream(procedure, request, options, &metrics),
+        )
+        .await;
+        metrics.finish(result.as_ref().map_or_else(ClientError::code, |_| Code::Ok));
+        result
+    }
+
+    /// Send a client-streaming request, recording its messages in `metrics`.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn send_client_stream<Req, Res, S>(
+        &self,
+        procedure: &str,
+        request: S,
+        options: CallOptions,
+        metrics: &CallMetrics,
+    ) -> Result<ConnectResponse<Res>, ClientError>
     where
         Req: Message + Serialize + 'static,
         Res: Message + DeserializeOwned + Default,
@@ -816,6 +1641,8 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         )
         .entered();
 
+        self.check_request_streaming()?;
+
         // 1. Build URL (strip leading slash from procedure to avoid double slashes)
         let procedure = procedure.strip_prefix('/').unwrap_or(procedure);
         let url = format!("{}/{}", self.base_url, procedure);
@@ -825,7 +1652,10 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         {
             let mut ctx = RequestContext::new(procedure, &mut interceptor_headers);
             // Use a unit placeholder - streaming interceptors use on_stream_send for messages
-            self.interceptor.intercept_request(&mut ctx, &mut ())?;
+            self.interceptor
+                .intercept_request_async(&mut ctx, &mut ())
+                .await?;
+            options.interceptors.intercept_request(&mut ctx, None)?;
         }
 
         // 3. Wrap request stream with InterceptingSendStream for per-message interception
@@ -838,43 +1668,51 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         );
 
         // 4. Wrap with FrameEncoder
-        let encoder = FrameEncoder::new(
+        let request_encoding = self.negotiated_encoding();
+        let mut encoder = FrameEncoder::new(
             intercepting_stream,
             self.use_proto,
-            self.request_encoding,
+            request_encoding,
             self.compression,
         );
+        if self.protocol.is_grpc() {
+            // gRPC ends the request stream with the body, not an EndStream frame
+            encoder = encoder.without_end_stream();
+        }
 
         // 5. Create streaming body
+        let mut frames = metrics.request_frames();
+        let encoder = Cancelable::new(encoder, options.cancel.as_ref()).inspect(move |chunk| {
+            if let Ok(chunk) = chunk {
+                frames.data(chunk);
+            }
+        });
         let body = TransportBody::streaming(encoder);
 
         // 6. Build request with streaming content-type
-        let mut req_builder = Request::builder()
-            .method(Method::POST)
-            .uri(&url)
-            .header(CONNECT_PROTOCOL_VERSION_HEADER, CONNECT_PROTOCOL_VERSION)
-            .header(header::CONTENT_TYPE, self.streaming_content_type());
+        // (options timeout overrides default)
+        let effective_timeout = options.timeout.or(self.default_timeout);
+        let mut headers = http::HeaderMap::new();
+        self.insert_protocol_headers(&mut headers, true, effective_timeout);
 
         // Add Content-Encoding if compression is configured
-        if !self.request_encoding.is_identity() && !self.compression.is_disabled() {
-            req_builder =
-                req_builder.header("connect-content-encoding", self.request_encoding.as_str());
+        if !request_encoding.is_identity() && !self.compression.is_disabled() {
+            headers.insert(
+                self.content_encoding_header(true),
+                HeaderValue::from_static(request_encoding.as_str()),
+            );
         }
 
-        // Add Accept-Encoding if configured
-        if let Some(accept) = &self.accept_encoding {
-            req_builder = req_builder.header("connect-accept-encoding", accept.as_str());
+        let mut req_builder = Request::builder().method(Method::POST).uri(&url);
+        for (name, value) in headers.iter() {
+            req_builder = req_builder.header(name, value);
         }
 
-        // Add Connect-Timeout-Ms header (options timeout overrides default)
-        let effective_timeout = options.timeout.or(self.default_timeout);
-        if let Some(t) = effective_timeout
-            && let Some(timeout_ms) = duration_to_timeout_header(t)
-        {
-            req_builder = req_builder.header(CONNECT_TIMEOUT_HEADER, timeout_ms);
+        // Add default headers, then custom headers from options (skip
+        // reserved protocol headers)
+        for (name, value) in self.default_headers(&options) {
+            req_builder = req_builder.header(name, value);
         }
-
-        // Add custom headers from options (skip reserved protocol headers)
         for (name, value) in options.headers.iter() {
             if !is_reserved_header(name) {
                 req_builder = req_builder.header(name, value);
@@ -891,85 +1729,32 @@ impl<I: InterceptorInternal> ConnectClient<I> {
             .body(body)
             .map_err(|e| ClientError::Protocol(format!("failed to build request: {}", e)))?;
 
-        // 5. Send request (with client-side timeout if configured)
-        let response = if let Some(t) = effective_timeout {
-            timeout(t, self.transport.request(req))
-                .await
-                .map_err(|_| {
-                    ClientError::new(Code::DeadlineExceeded, "client timeout exceeded")
-                })??
-        } else {
-            self.transport.request(req).await?
-        };
+        // 5. Send request (with client-side timeout and circuit breaker)
+        let response = self.send(req, effective_timeout).await?;
 
         // 6. Check response status
         let status = response.status();
         let response_headers = response.headers().clone();
 
         if !status.is_success() {
-            let body_bytes = response
-                .into_body()
-                .collect()
-                .await
-                .map_err(|e| ClientError::Transport(format!("failed to read error body: {}", e)))?
-                .to_bytes();
-            return Err(decompress_and_parse_error(
-                status,
-                &response_headers,
-                body_bytes,
-            ));
+            return Err(self.error_response(response).await);
         }
 
         // 7. Get compression encoding from Connect-Content-Encoding header
-        let content_encoding = response_headers
-            .get("connect-content-encoding")
-            .and_then(|v| v.to_str().ok());
-
-        let response_encoding =
-            CompressionEncoding::from_header(content_encoding).ok_or_else(|| {
-                ClientError::Protocol(format!(
-                    "unsupported response encoding: {:?}",
-                    content_encoding
-                ))
-            })?;
+        let response_encoding = self.response_encoding(&response_headers, true)?;
 
         // 8. Get the streaming body and decode the single response message
-        let body = response.into_body();
-        let byte_stream = body_to_stream(body);
-        let mut decoder =
-            FrameDecoder::<_, Res>::new(byte_stream, self.use_proto, response_encoding);
-
-        // 9. Get the single response message
-        let message = match decoder.next().await {
-            Some(Ok(msg)) => msg,
-            Some(Err(e)) => return Err(e),
-            None => {
-                return Err(ClientError::Protocol(
-                    "expected response message but stream ended".to_string(),
-                ));
-            }
-        };
-
-        // 10. Consume the EndStream frame and check for errors
-        // The decoder will return an error if the EndStream frame contains an error
-        if let Some(result) = decoder.next().await {
-            match result {
-                Err(e) => {
-                    // EndStream contained an error - propagate it
-                    return Err(e);
-                }
-                Ok(_) => {
-                    // Protocol violation: got another message after the response
-                    return Err(ClientError::new(
-                        Code::Unimplemented,
-                        "unary response has multiple messages",
-                    ));
-                }
-            }
-        }
+        let decoder = self.response_decoder::<Res>(
+            response.into_body(),
+            &response_headers,
+            response_encoding,
+            metrics,
+            options.cancel.as_ref(),
+        );
+        let (message, trailers) = read_single_message(decoder).await?;
 
-        // 11. Extract metadata from response headers
-        let metadata = Metadata::new(response_headers);
+        // 9. Extract metadata from response headers and EndStream trailers
+        let metadata = Metadata::new(response_headers).with_trailers(trailers);
 
         Ok(ConnectResponse::new(message, metadata))
     }
@@ -1035,6 +1820,7 @@ impl<I: InterceptorInternal> ConnectClient<I> {
     ///     println!("Trailers: {:?}", trailers);
     /// }
     /// ```
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn call_bidi_stream<Req, Res, S>(
         &self,
         procedure: &str,
@@ -1045,7 +1831,7 @@ impl<I: InterceptorInternal> ConnectClient<I> {
                 FrameDecoder<
                     impl futures::Stream<Item = Result<Bytes, ClientError>>
                     + Unpin
-                    + use<'_, I, Req, Res, S>,
+                    + use<'_, I, T, Req, Res, S>,
                     Res,
                 >,
                 Res,
@@ -1087,6 +1873,7 @@ impl<I: InterceptorInternal> ConnectClient<I> {
     ///     options,
     /// ).await?;
     /// ```
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn call_bidi_stream_with_options<Req, Res, S>(
         &self,
         procedure: &str,
@@ -1098,7 +1885,52 @@ impl<I: InterceptorInternal> ConnectClient<I> {
                 FrameDecoder<
                     impl futures::Stream<Item = Result<Bytes, ClientError>>
                     + Unpin
-                    + use<'_, I, Req, Res, S>,
+                    + use<'_, I, T, Req, Res, S>,
+                    Res,
+                >,
+                Res,
+                I,
+            >,
+        >,
+        ClientError,
+    >
+    where
+        Req: Message + Serialize + 'static,
+        Res: Message + DeserializeOwned + Default + 'static,
+        S: Stream<Item = Req> + Send + Unpin + 'static,
+    {
+        let metrics = CallMetrics::start(procedure, self.protocol);
+        if options.previous_error.is_some() {
+            metrics.retry();
+        }
+        let cancel = options.cancel.clone();
+        let result = until_canceled(
+            cancel,
+            self.send_bidi_stream(procedure, request, options, &metrics),
+        )
+        .await;
+        if let Err(e) = &result {
+            metrics.finish(e.code());
+        }
+        result
+    }
+
+    /// Send a bidi streaming request, recording its messages and, once the
+    /// response stream ends, its outcome in `metrics`.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn send_bidi_stream<Req, Res, S>(
+        &self,
+        procedure: &str,
+        request: S,
+        options: CallOptions,
+        metrics: &CallMetrics,
+    ) -> Result<
+        ConnectResponse<
+            InterceptingStreaming<
+                FrameDecoder<
+                    impl futures::Stream<Item = Result<Bytes, ClientError>>
+                    + Unpin
+                    + use<'_, I, T, Req, Res, S>,
                     Res,
                 >,
                 Res,
@@ -1122,6 +1954,14 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         )
         .entered();
 
+        self.check_request_streaming()?;
+        if self.http1_only {
+            return Err(ClientError::new(
+                Code::Unimplemented,
+                "bidi streaming requires HTTP/2, but the transport is HTTP/1.1 only",
+            ));
+        }
+
         // 1. Build URL (strip leading slash from procedure to avoid double slashes)
         let procedure = procedure.strip_prefix('/').unwrap_or(procedure);
         let url = format!("{}/{}", self.base_url, procedure);
@@ -1131,7 +1971,10 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         {
             let mut ctx = RequestContext::new(procedure, &mut interceptor_headers);
             // Use a unit placeholder - streaming interceptors use on_stream_send for messages
-            self.interceptor.intercept_request(&mut ctx, &mut ())?;
+            self.interceptor
+                .intercept_request_async(&mut ctx, &mut ())
+                .await?;
+            options.interceptors.intercept_request(&mut ctx, None)?;
         }
 
         // 3. Wrap request stream with InterceptingSendStream for per-message interception
@@ -1144,43 +1987,52 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         );
 
         // 4. Wrap with FrameEncoder
-        let encoder = FrameEncoder::new(
+        let request_encoding = self.negotiated_encoding();
+        let mut encoder = FrameEncoder::new(
             intercepting_stream,
             self.use_proto,
-            self.request_encoding,
+            request_encoding,
             self.compression.clone(),
         );
+        if self.protocol.is_grpc() {
+            // gRPC ends the request stream with the body, not an EndStream frame
+            encoder = encoder.without_end_stream();
+        }
 
         // 5. Create streaming body
+        let mut frames = metrics.request_frames();
+        let encoder = Cancelable::new(encoder, options.cancel.as_ref()).inspect(move |chunk| {
+            if let Ok(chunk) = chunk {
+                frames.data(chunk);
+            }
+        });
         let body = TransportBody::streaming(encoder);
 
         // 6. Build request with streaming content-type
-        let mut req_builder = Request::builder()
-            .method(Method::POST)
-            .uri(&url)
-            .header(CONNECT_PROTOCOL_VERSION_HEADER, CONNECT_PROTOCOL_VERSION)
-            .header(header::CONTENT_TYPE, self.streaming_content_type());
+        // (options timeout overrides default)
+        let effective_timeout = options.timeout.or(self.default_timeout);
+        let message_timeout = options.message_timeout;
+        let mut headers = http::HeaderMap::new();
+        self.insert_protocol_headers(&mut headers, true, effective_timeout);
 
         // Add Content-Encoding if compression is configured
-        if !self.request_encoding.is_identity() && !self.compression.is_disabled() {
-            req_builder =
-                req_builder.header("connect-content-encoding", self.request_encoding.as_str());
+        if !request_encoding.is_identity() && !self.compression.is_disabled() {
+            headers.insert(
+                self.content_encoding_header(true),
+                HeaderValue::from_static(request_encoding.as_str()),
+            );
         }
 
-        // Add Accept-Encoding if configured
-        if let Some(accept) = &self.accept_encoding {
-            req_builder = req_builder.header("connect-accept-encoding", accept.as_str());
+        let mut req_builder = Request::builder().method(Method::POST).uri(&url);
+        for (name, value) in headers.iter() {
+            req_builder = req_builder.header(name, value);
         }
 
-        // Add Connect-Timeout-Ms header (options timeout overrides default)
-        let effective_timeout = options.timeout.or(self.default_timeout);
-        if let Some(t) = effective_timeout {
-            if let Some(timeout_ms) = duration_to_timeout_header(t) {
-                req_builder = req_builder.header(CONNECT_TIMEOUT_HEADER, timeout_ms);
-            }
+        // Add default headers, then custom headers from options (skip
+        // reserved protocol headers)
+        for (name, value) in self.default_headers(&options) {
+            req_builder = req_builder.header(name, value);
         }
-
-        // Add custom headers from options (skip reserved protocol headers)
         for (name, value) in options.headers.iter() {
             if !is_reserved_header(name) {
                 req_builder = req_builder.header(name, value);
@@ -1197,16 +2049,8 @@ impl<I: InterceptorInternal> ConnectClient<I> {
             .body(body)
             .map_err(|e| ClientError::Protocol(format!("failed to build request: {}", e)))?;
 
-        // 5. Send request (with client-side timeout if configured)
-        let response = if let Some(t) = effective_timeout {
-            timeout(t, self.transport.request(req))
-                .await
-                .map_err(|_| {
-                    ClientError::new(Code::DeadlineExceeded, "client timeout exceeded")
-                })??
-        } else {
-            self.transport.request(req).await?
-        };
+        // 5. Send request (with client-side timeout and circuit breaker)
+        let response = self.send(req, effective_timeout).await?;
 
         // 6. Verify HTTP/2 for bidirectional streaming
         // Bidi streaming requires HTTP/2 for full-duplex operation
@@ -1226,41 +2070,26 @@ impl<I: InterceptorInternal> ConnectClient<I> {
         let response_headers = response.headers().clone();
 
         if !status.is_success() {
-            let body_bytes = response
-                .into_body()
-                .collect()
-                .await
-                .map_err(|e| ClientError::Transport(format!("failed to read error body: {}", e)))?
-                .to_bytes();
-            return Err(decompress_and_parse_error(
-                status,
-                &response_headers,
-                body_bytes,
-            ));
+            return Err(self.error_response(response).await);
         }
 
         // 8. Get compression encoding from Connect-Content-Encoding header
-        let content_encoding = response_headers
-            .get("connect-content-encoding")
-            .and_then(|v| v.to_str().ok());
-
-        let response_encoding =
-            CompressionEncoding::from_header(content_encoding).ok_or_else(|| {
-                ClientError::Protocol(format!(
-                    "unsupported response encoding: {:?}",
-                    content_encoding
-                ))
-            })?;
-
-        // 9. Get the streaming body
-        let body = response.into_body();
-        let byte_stream = body_to_stream(body);
-
-        // 10. Wrap with FrameDecoder
-        let decoder = FrameDecoder::new(byte_stream, self.use_proto, response_encoding);
+        let response_encoding = self.response_encoding(&response_headers, true)?;
+
+        // 9. Wrap the streaming body with FrameDecoder
+        let decoder = self.response_decoder(
+            response.into_body(),
+            &response_headers,
+            response_encoding,
+            metrics,
+            options.cancel.as_ref(),
+        );
 
         // 11. Wrap with Streaming
-        let stream_body = Streaming::new(decoder);
+        let mut stream_body = Streaming::new(decoder).with_metrics(metrics.clone());
+        if let Some(timeout) = message_timeout {
+            stream_body = stream_body.with_message_timeout(timeout);
+        }
 
         // 12. Wrap with InterceptingStreaming for per-message interception
         let intercepting_stream = InterceptingStreaming::new(
@@ -1277,6 +2106,112 @@ impl<I: InterceptorInternal> ConnectClient<I> {
 
         Ok(ConnectResponse::new(intercepting_stream, metadata))
     }
+
+    /// Make a bidirectional streaming RPC call, sending messages through a
+    /// [`StreamSender`].
+    ///
+    /// Unlike [`call_bidi_stream`](Self::call_bidi_stream), which takes the
+    /// request messages as a `Stream`, this returns a sender next to the
+    /// response, so each message can depend on the responses received so far.
+    /// Dropping the sender, or calling [`StreamSender::close`], half-closes
+    /// the call; the response stream then runs until the server ends it.
+    ///
+    /// The call returns once the server has sent its response headers. A
+    /// server that waits for the first request message before sending them
+    /// needs [`call_bidi_stream`](Self::call_bidi_stream) with a stream that
+    /// yields that message right away.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use futures::StreamExt;
+    ///
+    /// let (mut sender, response) = client
+    ///     .call_bidi_stream_channel::<EchoRequest, EchoResponse>(
+    ///         "echo.v1.EchoService/EchoBidiStream",
+    ///     )
+    ///     .await?;
+    /// let mut stream = response.into_inner();
+    ///
+    /// sender.send(EchoRequest { message: "hello".to_string() }).await?;
+    /// while let Some(reply) = stream.next().await {
+    ///     let reply = reply?;
+    ///     if reply.message == "bye" {
+    ///         break;
+    ///     }
+    ///     sender.send(EchoRequest { message: reply.message }).await?;
+    /// }
+    /// sender.close();
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn call_bidi_stream_channel<Req, Res>(
+        &self,
+        procedure: &str,
+    ) -> Result<
+        (
+            StreamSender<Req>,
+            ConnectResponse<
+                InterceptingStreaming<
+                    FrameDecoder<
+                        impl futures::Stream<Item = Result<Bytes, ClientError>>
+                        + Unpin
+                        + use<'_, I, T, Req, Res>,
+                        Res,
+                    >,
+                    Res,
+                    I,
+                >,
+            >,
+        ),
+        ClientError,
+    >
+    where
+        Req: Message + Serialize + 'static,
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        self.call_bidi_stream_channel_with_options(procedure, CallOptions::default())
+            .await
+    }
+
+    /// Make a bidirectional streaming RPC call through a [`StreamSender`],
+    /// with custom options.
+    ///
+    /// This is the same as [`call_bidi_stream_channel`](Self::call_bidi_stream_channel)
+    /// but allows specifying per-call options like custom headers and timeout
+    /// overrides.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn call_bidi_stream_channel_with_options<Req, Res>(
+        &self,
+        procedure: &str,
+        options: CallOptions,
+    ) -> Result<
+        (
+            StreamSender<Req>,
+            ConnectResponse<
+                InterceptingStreaming<
+                    FrameDecoder<
+                        impl futures::Stream<Item = Result<Bytes, ClientError>>
+                        + Unpin
+                        + use<'_, I, T, Req, Res>,
+                        Res,
+                    >,
+                    Res,
+                    I,
+                >,
+            >,
+        ),
+        ClientError,
+    >
+    where
+        Req: Message + Serialize + 'static,
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        let (sender, messages) = StreamSender::channel();
+        let response = self
+            .call_bidi_stream_with_options(procedure, messages, options)
+            .await?;
+        Ok((sender, response))
+    }
 }
 
 /// Helper to decompress and parse error response body.
@@ -1293,6 +2228,7 @@ fn decompress_and_parse_error(
     status: http::StatusCode,
     headers: &http::HeaderMap,
     body_bytes: Bytes,
+    decompression_limit: usize,
 ) -> ClientError {
     // Check Content-Encoding header for potential compression
     let content_encoding = headers
@@ -1301,12 +2237,12 @@ fn decompress_and_parse_error(
 
     // If no Content-Encoding header, parse raw bytes
     let Some(encoding_str) = content_encoding else {
-        return parse_error_response(status, &body_bytes);
+        return parse_error_response(status, headers, &body_bytes);
     };
 
     // Empty or identity encoding means no compression
     if encoding_str.is_empty() || encoding_str == "identity" {
-        return parse_error_response(status, &body_bytes);
+        return parse_error_response(status, headers, &body_bytes);
     }
 
     // Try to get the compression encoding
@@ -1331,39 +2267,125 @@ fn decompress_and_parse_error(
     };
 
     // Decompress and parse
-    match codec.decompress(&body_bytes) {
-        Ok(decompressed) => parse_error_response(status, &decompressed),
+    match codec.decompress_limited(&body_bytes, decompression_limit) {
+        Ok(decompressed) => parse_error_response(status, headers, &decompressed),
         Err(_) => {
             // Decompression failed - fall back to error from HTTP status
             // (consistent with connect-go behavior when unmarshaling fails)
             ClientError::new(
-                crate::response::error_parser::http_status_to_code(status),
+                Code::from_http_status_connect(status),
                 format!("HTTP {}: decompression of error body failed", status),
             )
         }
     }
 }
 
-/// Convert a hyper Incoming body to a stream of bytes with ClientError.
-fn body_to_stream(
-    body: hyper::body::Incoming,
-) -> impl futures::Stream<Item = Result<Bytes, ClientError>> + Unpin {
-    use http_body_util::BodyExt;
+/// Read the single message of a unary-response stream, and its trailers.
+///
+/// Consumes the rest of the stream so that an error in the EndStream frame
+/// (or gRPC trailers) is returned, and rejects extra messages.
+async fn read_single_message<S, Res>(
+    mut decoder: FrameDecoder<S, Res>,
+) -> Result<(Res, http::HeaderMap), ClientError>
+where
+    S: Stream<Item = Result<Bytes, ClientError>> + Unpin,
+    Res: Message + DeserializeOwned + Default,
+{
+    // Get the single response message
+    let message = match decoder.next().await {
+        Some(Ok(msg)) => msg,
+        Some(Err(e)) => return Err(e),
+        None => {
+            return Err(ClientError::Protocol(
+                "expected response message but stream ended".to_string(),
+            ));
+        }
+    };
 
-    Box::pin(
-        futures::stream::unfold(body, |mut body| async move {
-            match body.frame().await {
-                Some(Ok(frame)) => {
-                    if let Ok(data) = frame.into_data() {
-                        Some((Ok(data), body))
-                    } else {
-                        // Trailers or other frame types - skip
-                        Some((Ok(Bytes::new()), body))
-                    }
-                }
+    // Consume the EndStream frame and check for errors
+    // The decoder will return an error if the EndStream frame contains an error
+    if let Some(result) = decoder.next().await {
+        match result {
+            Err(e) => {
+                // EndStream contained an error - propagate it
+                return Err(e);
+            }
+            Ok(_) => {
+                // Protocol violation: got another message after the response
+                return Err(ClientError::new(
+                    Code::Unimplemented,
+                    "unary response has multiple messages",
+                ));
+            }
+        }
+    }
+
+    let trailers = decoder
+        .take_trailers()
+        .map(Metadata::into_headers)
+        .unwrap_or_default();
+    Ok((message, trailers))
+}
+
+/// The body of a unary response.
+enum UnaryBody<B> {
+    /// Received from the server.
+    Response(B),
+    /// Answered from the response cache.
+    Cached(Bytes),
+}
+
+/// Read a whole response body, failing with `resource_exhausted` once it
+/// grows past `limit` bytes instead of buffering it in full.
+async fn read_body_limited<B>(mut body: B, limit: usize) -> Result<Bytes, ClientError>
+where
+    B: Body<Data = Bytes, Error: std::fmt::Display> + Unpin,
+{
+    let mut buf = bytes::BytesMut::new();
+    while let Some(frame) = body.frame().await {
+        let frame = frame
+            .map_err(|e| ClientError::Transport(format!("failed to read response body: {}", e)))?;
+        if let Ok(data) = frame.into_data() {
+            if buf.len() + data.len() > limit {
+                return Err(ClientError::new(
+                    Code::ResourceExhausted,
+                    format!("response body exceeds the limit of {limit} bytes"),
+                ));
+            }
+            buf.extend_from_slice(&data);
+        }
+    }
+    Ok(buf.freeze())
+}
+
+/// Convert a transport response body to a stream of bytes with ClientError.
+///
+/// HTTP trailers are stored in `trailers` if given, and skipped otherwise.
+fn body_to_stream<B>(
+    body: B,
+    trailers: Option<TrailersSlot>,
+) -> impl futures::Stream<Item = Result<Bytes, ClientError>> + Unpin
+where
+    B: Body<Data = Bytes, Error: std::fmt::Display> + Unpin,
+{
+    use http_body_util::BodyExt;
+
+    Box::pin(
+        futures::stream::unfold((body, trailers), |(mut body, trailers)| async move {
+            match body.frame().await {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => Some((Ok(data), (body, trailers))),
+                    Err(frame) => {
+                        if let (Some(slot), Ok(map)) = (&trailers, frame.into_trailers()) {
+                            slot.store(map);
+                        }
+                        // Trailers or other frame types - skip
+                        Some((Ok(Bytes::new()), (body, trailers)))
+                    }
+                },
                 Some(Err(e)) => Some((
                     Err(ClientError::Transport(format!("stream error: {}", e))),
-                    body,
+                    (body, trailers),
                 )),
                 None => None,
             }
@@ -1417,4 +2439,1221 @@ mod tests {
             .unwrap();
         assert_eq!(client.streaming_content_type(), "application/connect+proto");
     }
+
+    #[test]
+    fn test_accept_encoding_value() {
+        assert!(accept_encoding_value(&[]).is_none());
+        assert_eq!(
+            accept_encoding_value(&[CompressionEncoding::Identity]).unwrap(),
+            "identity"
+        );
+    }
+
+    #[cfg(feature = "compression-full-stream")]
+    #[test]
+    fn test_accept_encodings_header() {
+        let client = ConnectClient::builder("http://localhost:3000")
+            .accept_encodings([
+                CompressionEncoding::Zstd,
+                CompressionEncoding::Brotli,
+                CompressionEncoding::Zstd,
+                CompressionEncoding::Gzip,
+            ])
+            .build()
+            .unwrap();
+        let mut headers = http::HeaderMap::new();
+        client.insert_protocol_headers(&mut headers, false, None);
+        assert_eq!(headers["accept-encoding"], "zstd, br;q=0.9, gzip;q=0.8");
+        let mut headers = http::HeaderMap::new();
+        client.insert_protocol_headers(&mut headers, true, None);
+        assert_eq!(
+            headers["connect-accept-encoding"],
+            "zstd, br;q=0.9, gzip;q=0.8"
+        );
+    }
+
+    /// Spawn an HTTP/1.1 server answering unary calls with an empty message
+    /// and the request method and query in `x-method` and `x-query`.
+    async fn serve_echo_method() -> std::net::SocketAddr {
+        use hyper_util::rt::TokioIo;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let service = hyper::service::service_fn(|req: http::Request<_>| async move {
+                        http::Response::builder()
+                            .header(header::CONTENT_TYPE, "application/proto")
+                            .header("x-method", req.method().as_str())
+                            .header("x-query", req.uri().query().unwrap_or_default())
+                            .body(http_body_util::Empty::<Bytes>::new())
+                    });
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(tcp), service)
+                        .await;
+                });
+            }
+        });
+
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_unary_get() {
+        let addr = serve_echo_method().await;
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_proto()
+            .build()
+            .unwrap();
+
+        let response = client
+            .call_unary_with_options::<String, ()>(
+                "test.Service/Method",
+                &"hi".to_string(),
+                CallOptions::new().use_get(true),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.metadata().get("x-method"), Some("GET"));
+        // "hi" as a StringValue: field 1, length 2
+        assert_eq!(
+            response.metadata().get("x-query"),
+            Some("connect=v1&encoding=proto&base64=1&message=CgJoaQ")
+        );
+
+        // Too long for a URL: falls back to POST
+        let response = client
+            .call_unary_with_options::<String, ()>(
+                "test.Service/Method",
+                &"x".repeat(MAX_GET_URL_LENGTH),
+                CallOptions::new().use_get(true),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.metadata().get("x-method"), Some("POST"));
+    }
+
+    #[tokio::test]
+    async fn test_response_cache() {
+        use crate::ResponseCache;
+        use std::sync::{Arc, Mutex};
+
+        /// Answers with the StringValue "a", tagged "v1" and to be
+        /// revalidated, or 304 if the request has that tag. Records the
+        /// method and `If-None-Match` of each request.
+        #[derive(Clone, Default)]
+        struct EtagTransport(Arc<Mutex<Vec<String>>>);
+
+        impl Transport for EtagTransport {
+            type Body = http_body_util::Full<Bytes>;
+
+            async fn request(
+                &self,
+                request: Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+                let not_modified = if_none_match.as_ref().is_some_and(|v| v == "\"v1\"");
+                let tag = if_none_match.as_ref().map_or("-", |v| v.to_str().unwrap());
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", request.method(), tag));
+                let response = if not_modified {
+                    http::Response::builder()
+                        .status(http::StatusCode::NOT_MODIFIED)
+                        .header(header::CACHE_CONTROL, "max-age=60")
+                        .body(http_body_util::Full::new(Bytes::new()))
+                } else {
+                    http::Response::builder()
+                        .header(header::CONTENT_TYPE, "application/proto")
+                        .header(header::ETAG, "\"v1\"")
+                        .header(header::CACHE_CONTROL, "no-cache")
+                        .body(http_body_util::Full::new(Bytes::from_static(b"\x0a\x01a")))
+                };
+                Ok(response.unwrap())
+            }
+        }
+
+        let transport = EtagTransport::default();
+        let client = ConnectClient::builder("http://stub")
+            .use_proto()
+            .response_cache(ResponseCache::new())
+            .build_with_transport(transport.clone())
+            .unwrap();
+        let call = |options: CallOptions| {
+            client.call_unary_with_options::<(), String>("test.Service/Method", &(), options)
+        };
+
+        // Fetched, then revalidated, then fresh
+        for _ in 0..3 {
+            let response = call(CallOptions::new().use_get(true)).await.unwrap();
+            assert_eq!(response.into_inner(), "a");
+        }
+        // POSTs bypass the cache
+        call(CallOptions::new()).await.unwrap();
+
+        assert_eq!(
+            *transport.0.lock().unwrap(),
+            ["GET -", "GET \"v1\"", "POST -"]
+        );
+    }
+
+    #[cfg(feature = "compression-gzip-stream")]
+    #[tokio::test]
+    async fn test_negotiate_compression() {
+        use crate::EncodingCache;
+        use std::sync::{Arc, Mutex};
+
+        /// Answers with an empty message, accepting gzip requests. Records
+        /// the `Content-Encoding` of each request.
+        #[derive(Clone, Default)]
+        struct GzipTransport(Arc<Mutex<Vec<String>>>);
+
+        impl Transport for GzipTransport {
+            type Body = http_body_util::Full<Bytes>;
+
+            async fn request(
+                &self,
+                request: Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                let encoding = request.headers().get(header::CONTENT_ENCODING);
+                let encoding = encoding.map_or("-", |v| v.to_str().unwrap());
+                self.0.lock().unwrap().push(encoding.to_string());
+                Ok(http::Response::builder()
+                    .header(header::CONTENT_TYPE, "application/proto")
+                    .header(header::ACCEPT_ENCODING, "gzip")
+                    .body(http_body_util::Full::new(Bytes::new()))
+                    .unwrap())
+            }
+        }
+
+        let transport = GzipTransport::default();
+        let encodings = EncodingCache::new();
+        let client = ConnectClient::builder("http://stub")
+            .use_proto()
+            .negotiate_compression(encodings.clone())
+            .build_with_transport(transport.clone())
+            .unwrap();
+
+        // The first call is uncompressed, later ones use gzip
+        for _ in 0..2 {
+            client
+                .call_unary::<String, ()>("test.Service/Method", &"hello".to_string())
+                .await
+                .unwrap();
+        }
+        assert_eq!(*transport.0.lock().unwrap(), ["-", "gzip"]);
+        assert_eq!(encodings.accepted("http://stub").unwrap(), ["gzip"]);
+    }
+
+    #[tokio::test]
+    async fn test_default_headers_and_user_agent() {
+        use std::sync::{Arc, Mutex};
+
+        /// Answers with an empty message. Records the `x-api-version`,
+        /// `x-tenant-id` and `user-agent` of each request.
+        #[derive(Clone, Default)]
+        struct HeaderTransport(Arc<Mutex<Vec<String>>>);
+
+        impl Transport for HeaderTransport {
+            type Body = http_body_util::Full<Bytes>;
+
+            async fn request(
+                &self,
+                request: Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                let header = |name| {
+                    let values = request.headers().get_all(name).iter();
+                    values
+                        .map(|v| v.to_str().unwrap())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                };
+                let line = format!(
+                    "{} {} {}",
+                    header("x-api-version"),
+                    header("x-tenant-id"),
+                    header("user-agent")
+                );
+                self.0.lock().unwrap().push(line);
+                Ok(http::Response::builder()
+                    .header(header::CONTENT_TYPE, "application/proto")
+                    .body(http_body_util::Full::new(Bytes::new()))
+                    .unwrap())
+            }
+        }
+
+        let transport = HeaderTransport::default();
+        let client = ConnectClient::builder("http://stub")
+            .use_proto()
+            .default_header("x-api-version", "1")
+            .default_header("x-api-version", "2")
+            .default_header("x-tenant-id", "acme")
+            .default_header("content-type", "text/plain")
+            .build_with_transport(transport.clone())
+            .unwrap();
+
+        client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap();
+        // Call options replace default headers
+        let options = CallOptions::new().header("x-tenant-id", "other");
+        client
+            .call_unary_with_options::<(), ()>("test.Service/Method", &(), options)
+            .await
+            .unwrap();
+
+        // A per-call user agent replaces the client's
+        let options = CallOptions::new().header("user-agent", "test/1");
+        client
+            .call_unary_with_options::<(), ()>("test.Service/Method", &(), options)
+            .await
+            .unwrap();
+
+        let default = format!("2 acme {}", DEFAULT_USER_AGENT);
+        let other = format!("2 other {}", DEFAULT_USER_AGENT);
+        assert_eq!(
+            *transport.0.lock().unwrap(),
+            [default.as_str(), other.as_str(), "2 acme test/1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_response_context() {
+        /// Rejects every call with 429 and rate-limit headers.
+        #[derive(Clone)]
+        struct RateLimitedTransport;
+
+        impl Transport for RateLimitedTransport {
+            type Body = http_body_util::Full<Bytes>;
+
+            async fn request(
+                &self,
+                _request: Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                Ok(http::Response::builder()
+                    .status(http::StatusCode::TOO_MANY_REQUESTS)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header("x-ratelimit-remaining", "0")
+                    .header("trailer-x-request-id", "r1")
+                    .body(http_body_util::Full::new(Bytes::from_static(
+                        br#"{"code":"resource_exhausted","message":"slow down"}"#,
+                    )))
+                    .unwrap())
+            }
+        }
+
+        let client = ConnectClient::builder("http://stub")
+            .build_with_transport(RateLimitedTransport)
+            .unwrap();
+        let err = client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+        assert_eq!(err.http_status(), Some(http::StatusCode::TOO_MANY_REQUESTS));
+        let metadata = err.metadata().unwrap();
+        assert_eq!(metadata.get("x-ratelimit-remaining"), Some("0"));
+        assert_eq!(metadata.get_trailer("x-request-id"), Some("r1"));
+    }
+
+    /// Fails the first `failures` requests with `unavailable`.
+    async fn serve_flaky(failures: usize) -> std::net::SocketAddr {
+        use hyper_util::rt::TokioIo;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                let requests = requests.clone();
+                tokio::spawn(async move {
+                    let service = hyper::service::service_fn(move |_req| {
+                        let failed = requests.fetch_add(1, Ordering::SeqCst) < failures;
+                        async move {
+                            if failed {
+                                http::Response::builder()
+                                    .status(503)
+                                    .header(header::CONTENT_TYPE, "application/json")
+                                    .body(http_body_util::Full::new(Bytes::from_static(
+                                        br#"{"code":"unavailable","message":"try again"}"#,
+                                    )))
+                            } else {
+                                http::Response::builder()
+                                    .header(header::CONTENT_TYPE, "application/proto")
+                                    .body(http_body_util::Full::new(Bytes::new()))
+                            }
+                        }
+                    });
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(tcp), service)
+                        .await;
+                });
+            }
+        });
+
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_fails_fast() {
+        let addr = serve_flaky(2).await;
+        let breaker = CircuitBreaker::new().window_size(2).minimum_calls(2);
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_proto()
+            .with_circuit_breaker(breaker.clone())
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            let err = client
+                .call_unary::<(), ()>("test.Service/Method", &())
+                .await
+                .unwrap_err();
+            assert_eq!(err.message(), Some("try again"));
+        }
+        assert_eq!(breaker.state(), crate::CircuitState::Open);
+
+        // The server would now succeed, but the call never reaches it
+        let err = client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::Unavailable);
+        assert_eq!(err.message(), Some("circuit breaker is open"));
+    }
+
+    #[tokio::test]
+    async fn test_unary_with_retry_attempt_context() {
+        use crate::ClosureInterceptor;
+        use std::sync::{Arc, Mutex};
+
+        let addr = serve_flaky(2).await;
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_proto()
+            .with_interceptor(ClosureInterceptor::new(move |ctx: &mut RequestContext| {
+                let previous = ctx.previous_error.map(|e| e.code());
+                recorded.lock().unwrap().push((ctx.attempt, previous));
+                Ok(())
+            }))
+            .build()
+            .unwrap();
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_millis(1))
+            .jitter(0.0);
+
+        client
+            .call_unary_with_retry::<(), ()>(
+                "test.Service/Method",
+                &(),
+                CallOptions::new(),
+                &policy,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                (1, None),
+                (2, Some(Code::Unavailable)),
+                (3, Some(Code::Unavailable)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_builder_retry_policy_for() {
+        let addr = serve_flaky(2).await;
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_proto()
+            .retry_policy_for(
+                "test.Service/Method",
+                RetryPolicy::new()
+                    .base_delay(Duration::from_millis(1))
+                    .jitter(0.0),
+            )
+            .build()
+            .unwrap();
+
+        // Methods without a policy fail on the first error
+        let err = client
+            .call_unary::<(), ()>("test.Service/Other", &())
+            .await
+            .unwrap_err();
+        assert_eq!(err.message(), Some("try again"));
+
+        client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_balancer_skips_dead_endpoint() {
+        let healthy = serve_flaky(0).await;
+        let dead = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap()
+        };
+        let balancer = Balancer::new([format!("http://{dead}"), format!("http://{healthy}")])
+            .policy(crate::PickFirst);
+        let client = ConnectClient::builder("http://backend")
+            .use_proto()
+            .with_balancer(balancer.clone())
+            .build()
+            .unwrap();
+
+        let err = client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClientError::Transport(_)));
+        assert!(!balancer.endpoints()[0].is_healthy());
+
+        client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unary_with_hedging() {
+        use hyper_util::rt::TokioIo;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // The first request hangs; later ones answer right away
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                let counter = counter.clone();
+                tokio::spawn(async move {
+                    let service = hyper::service::service_fn(move |_req| {
+                        let first = counter.fetch_add(1, Ordering::SeqCst) == 0;
+                        async move {
+                            if first {
+                                tokio::time::sleep(Duration::from_secs(60)).await;
+                            }
+                            http::Response::builder()
+                                .header(header::CONTENT_TYPE, "application/proto")
+                                .body(http_body_util::Full::new(Bytes::new()))
+                        }
+                    });
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(tcp), service)
+                        .await;
+                });
+            }
+        });
+
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_proto()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let policy = HedgingPolicy::new(Duration::from_millis(20));
+
+        client
+            .call_unary_with_hedging::<(), ()>(
+                "test.Service/Method",
+                &(),
+                CallOptions::new().idempotent(true),
+                &policy,
+            )
+            .await
+            .unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+        // Calls that aren't idempotent are sent once
+        requests.store(0, Ordering::SeqCst);
+        let err = client
+            .call_unary_with_hedging::<(), ()>(
+                "test.Service/Method",
+                &(),
+                CallOptions::new().timeout(Duration::from_millis(200)),
+                &policy,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::DeadlineExceeded);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_grpc_content_types() {
+        let client = ConnectClient::builder("http://localhost:3000")
+            .use_grpc()
+            .use_proto()
+            .build()
+            .unwrap();
+        assert_eq!(client.unary_content_type(), "application/grpc");
+        assert_eq!(client.streaming_content_type(), "application/grpc");
+
+        let client = ConnectClient::builder("http://localhost:3000")
+            .use_grpc()
+            .build()
+            .unwrap();
+        assert_eq!(client.streaming_content_type(), "application/grpc+json");
+    }
+
+    #[test]
+    fn test_grpc_web_content_types() {
+        let client = ConnectClient::builder("http://localhost:3000")
+            .use_grpc_web()
+            .use_proto()
+            .build()
+            .unwrap();
+        assert_eq!(client.unary_content_type(), "application/grpc-web+proto");
+        assert_eq!(client.content_encoding_header(true), "grpc-encoding");
+
+        let client = ConnectClient::builder("http://localhost:3000")
+            .use_grpc_web_text()
+            .use_proto()
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.streaming_content_type(),
+            "application/grpc-web-text+proto"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_grpc_web_text_unary() {
+        use hyper_util::rt::TokioIo;
+
+        // An empty message, then the trailer frame, each base64-encoded
+        let mut trailer_frame = vec![grpc::GRPC_WEB_TRAILERS];
+        let block = b"grpc-status: 0\r\nx-served-by: test\r\n";
+        trailer_frame.extend_from_slice(&(block.len() as u32).to_be_bytes());
+        trailer_frame.extend_from_slice(block);
+        let body = [
+            grpc::encode_text(&wrap_envelope(&[], false)),
+            grpc::encode_text(&trailer_frame),
+        ]
+        .concat();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let service = hyper::service::service_fn(move |req: http::Request<_>| {
+                let body = body.clone();
+                async move {
+                    let grpc_web = req.headers().get(header::CONTENT_TYPE).unwrap()
+                        == "application/grpc-web-text+proto"
+                        && req.headers().get(GRPC_WEB_HEADER).unwrap() == "1";
+                    http::Response::builder()
+                        .status(if grpc_web { 200 } else { 415 })
+                        .header(header::CONTENT_TYPE, "application/grpc-web-text+proto")
+                        .body(http_body_util::Full::new(Bytes::from(body)))
+                }
+            });
+            let _ = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(tcp), service)
+                .await;
+        });
+
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_grpc_web_text()
+            .use_proto()
+            .build()
+            .unwrap();
+        let response = client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap();
+        assert_eq!(response.metadata().get_trailer("x-served-by"), Some("test"));
+    }
+
+    /// Spawn an HTTP/2 gRPC server that answers every request with an empty
+    /// message (if `message` is set) followed by `trailers`.
+    async fn serve_grpc(message: bool, trailers: http::HeaderMap) -> std::net::SocketAddr {
+        use http_body::Frame;
+        use hyper_util::rt::{TokioExecutor, TokioIo};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                let trailers = trailers.clone();
+                tokio::spawn(async move {
+                    let service = hyper::service::service_fn(move |req: http::Request<_>| {
+                        let trailers = trailers.clone();
+                        async move {
+                            let grpc = req.headers().get(header::CONTENT_TYPE).unwrap()
+                                == "application/grpc"
+                                && req.headers().get(header::TE).unwrap() == "trailers";
+                            let mut frames = Vec::new();
+                            if message {
+                                frames.push(Frame::data(Bytes::from(wrap_envelope(&[], false))));
+                            }
+                            frames.push(Frame::trailers(trailers));
+                            let body = http_body_util::StreamBody::new(futures::stream::iter(
+                                frames.into_iter().map(Ok::<_, std::convert::Infallible>),
+                            ));
+                            http::Response::builder()
+                                .status(if grpc { 200 } else { 415 })
+                                .header(header::CONTENT_TYPE, "application/grpc")
+                                .body(body)
+                        }
+                    });
+                    let _ = hyper::server::conn::http2::Builder::new(TokioExecutor::new())
+                        .serve_connection(TokioIo::new(tcp), service)
+                        .await;
+                });
+            }
+        });
+
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_grpc_unary() {
+        let mut trailers = http::HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        trailers.insert("x-checksum", HeaderValue::from_static("abc"));
+        let addr = serve_grpc(true, trailers).await;
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_grpc()
+            .use_proto()
+            .build()
+            .unwrap();
+
+        let response = client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap();
+        assert_eq!(response.metadata().get_trailer("x-checksum"), Some("abc"));
+        assert_eq!(response.metadata().get_trailer("grpc-status"), None);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_unary_error_status() {
+        let mut trailers = http::HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("5"));
+        trailers.insert("grpc-message", HeaderValue::from_static("no%20such%20item"));
+        let addr = serve_grpc(false, trailers).await;
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_grpc()
+            .use_proto()
+            .build()
+            .unwrap();
+
+        let err = client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::NotFound);
+        assert_eq!(err.message(), Some("no such item"));
+    }
+
+    /// Spawn an HTTP/1.1 server that answers every request with `body`,
+    /// gzip-compressed.
+    #[cfg(feature = "compression-gzip-stream")]
+    async fn serve_gzip(body: Vec<u8>) -> std::net::SocketAddr {
+        use hyper_util::rt::TokioIo;
+
+        let compressed = CompressionEncoding::Gzip
+            .codec()
+            .unwrap()
+            .compress(&body)
+            .unwrap();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                let compressed = compressed.clone();
+                tokio::spawn(async move {
+                    let service = hyper::service::service_fn(move |_req| {
+                        let compressed = compressed.clone();
+                        async move {
+                            http::Response::builder()
+                                .header(header::CONTENT_TYPE, "application/proto")
+                                .header(header::CONTENT_ENCODING, "gzip")
+                                .body(http_body_util::Full::new(compressed))
+                        }
+                    });
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(tcp), service)
+                        .await;
+                });
+            }
+        });
+
+        addr
+    }
+
+    #[cfg(feature = "compression-gzip-stream")]
+    #[tokio::test]
+    async fn test_unary_response_exceeds_decompression_limit() {
+        // 16 MiB of repeated bytes compresses to a few KiB.
+        let addr = serve_gzip(vec![0u8; 16 * 1024 * 1024]).await;
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_proto()
+            .max_receive_message_size(1024 * 1024)
+            .build()
+            .unwrap();
+
+        let err = client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+        assert_eq!(err.message(), Some("decompressed response exceeds limit"));
+    }
+
+    #[cfg(feature = "compression-gzip-stream")]
+    #[tokio::test]
+    async fn test_unary_response_at_decompression_limit() {
+        // 4 KiB of unknown varint fields, which decode cleanly as `()`.
+        let body = [0x08u8, 0x00].repeat(2048);
+        let addr = serve_gzip(body.clone()).await;
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_proto()
+            .build()
+            .unwrap()
+            .with_response_decompression_limit(body.len());
+
+        // Exactly at the limit succeeds.
+        client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap();
+
+        // One byte over fails.
+        let err = client
+            .with_response_decompression_limit(body.len() - 1)
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+    }
+
+    #[cfg(feature = "compression-gzip-stream")]
+    #[tokio::test]
+    async fn test_unary_response_exceeds_max_receive_message_size() {
+        let addr = serve_gzip([0x08u8, 0x00].repeat(2048)).await;
+        let client = ConnectClient::builder(format!("http://{addr}"))
+            .use_proto()
+            .max_receive_message_size(16)
+            .build()
+            .unwrap();
+
+        // The compressed body alone is over the limit.
+        let err = client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+        assert_eq!(
+            err.message(),
+            Some("response body exceeds the limit of 16 bytes")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_with_custom_transport() {
+        use std::sync::{Arc, Mutex};
+
+        /// Answers every request with an empty proto message, recording its URI.
+        #[derive(Clone, Default)]
+        struct StubTransport(Arc<Mutex<Vec<String>>>);
+
+        impl Transport for StubTransport {
+            type Body = http_body_util::Full<Bytes>;
+
+            async fn request(
+                &self,
+                request: Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                self.0.lock().unwrap().push(request.uri().to_string());
+                Ok(http::Response::builder()
+                    .header(header::CONTENT_TYPE, "application/proto")
+                    .body(http_body_util::Full::new(Bytes::new()))
+                    .unwrap())
+            }
+        }
+
+        let transport = StubTransport::default();
+        let client = ConnectClient::builder("http://stub/")
+            .use_proto()
+            .build_with_transport(transport.clone())
+            .unwrap();
+
+        client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap();
+        assert_eq!(
+            *transport.0.lock().unwrap(),
+            ["http://stub/test.Service/Method"]
+        );
+    }
+    #[tokio::test]
+    async fn test_server_stream_resumable() {
+        use std::sync::{Arc, Mutex};
+
+        /// Streams the messages after the requested one, up to "3"; the
+        /// first response breaks off after one message.
+        #[derive(Clone, Default)]
+        struct EventTransport(Arc<Mutex<Vec<String>>>);
+
+        impl Transport for EventTransport {
+            type Body = TransportBody;
+
+            async fn request(
+                &self,
+                request: Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                let body = request.into_body().collect().await?.to_bytes();
+                // The enveloped request carries a single-byte StringValue
+                let after = String::from_utf8(body[7..].to_vec()).unwrap();
+                let calls = {
+                    let mut requests = self.0.lock().unwrap();
+                    requests.push(after.clone());
+                    requests.len()
+                };
+
+                let start = after.parse::<u8>().unwrap() + 1;
+                let mut frames: Vec<Result<Bytes, ClientError>> = (start..=3)
+                    .map(|n| {
+                        let message = n.to_string().encode_to_vec();
+                        Ok(Bytes::from(wrap_envelope(&message, false)))
+                    })
+                    .collect();
+                if calls == 1 {
+                    frames.truncate(1);
+                    frames.push(Err(ClientError::Transport("connection reset".into())));
+                } else {
+                    frames.push(Ok(Bytes::from_static(b"\x02\x00\x00\x00\x02{}")));
+                }
+                Ok(http::Response::builder()
+                    .header(header::CONTENT_TYPE, "application/connect+proto")
+                    .body(TransportBody::streaming(futures::stream::iter(frames)))
+                    .unwrap())
+            }
+        }
+
+        let transport = EventTransport::default();
+        let client = ConnectClient::builder("http://stub")
+            .use_proto()
+            .build_with_transport(transport.clone())
+            .unwrap();
+        let policy = RetryPolicy::new()
+            .max_retries(1)
+            .base_delay(Duration::from_millis(1));
+
+        let events = client.call_server_stream_resumable::<String, String, _>(
+            "test.Service/Watch",
+            "0".to_string(),
+            CallOptions::new(),
+            policy,
+            |_, last| last.clone(),
+        );
+        let events: Vec<_> = events.collect().await;
+        let events: Vec<_> = events.into_iter().map(Result::unwrap).collect();
+        assert_eq!(events, ["1", "2", "3"]);
+        // The second call resumed after the last message received
+        assert_eq!(*transport.0.lock().unwrap(), ["0", "1"]);
+    }
+
+    #[tokio::test]
+    async fn test_bidi_stream_channel() {
+        /// Streams the request body back as the response body.
+        #[derive(Clone)]
+        struct EchoTransport;
+
+        impl Transport for EchoTransport {
+            type Body = TransportBody;
+
+            async fn request(
+                &self,
+                request: Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                Ok(http::Response::builder()
+                    .version(http::Version::HTTP_2)
+                    .header(header::CONTENT_TYPE, "application/connect+proto")
+                    .body(request.into_body())
+                    .unwrap())
+            }
+        }
+
+        let client = ConnectClient::builder("http://stub")
+            .use_proto()
+            .build_with_transport(EchoTransport)
+            .unwrap();
+        let (mut sender, response) = client
+            .call_bidi_stream_channel::<String, String>("test.Service/Method")
+            .await
+            .unwrap();
+        let mut stream = response.into_inner();
+
+        // Each message is sent after the previous reply arrived
+        sender.send("a".to_string()).await.unwrap();
+        for expected in ["a", "aa", "aaa"] {
+            let reply = stream.next().await.unwrap().unwrap();
+            assert_eq!(reply, expected);
+            sender.send(reply + "a").await.unwrap();
+        }
+
+        // Closing the sender ends the request stream, and with it the echo
+        sender.close();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "aaaa");
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_http1_only() {
+        // gRPC needs HTTP/2, gRPC-Web doesn't
+        let err = ConnectClient::builder("http://localhost:3000")
+            .use_grpc()
+            .http1_only()
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("gRPC requires HTTP/2"));
+        assert!(
+            ConnectClient::builder("http://localhost:3000")
+                .use_grpc_web()
+                .http1_only()
+                .build()
+                .is_ok()
+        );
+
+        // Bidi calls fail before a request is sent
+        let client = ConnectClient::builder("http://localhost:3000")
+            .use_proto()
+            .http1_only()
+            .build()
+            .unwrap();
+        let Err(err) = client
+            .call_bidi_stream::<String, String, _>("test.Service/Method", futures::stream::empty())
+            .await
+        else {
+            panic!("bidi call over HTTP/1.1 succeeded");
+        };
+        assert_eq!(err.code(), Code::Unimplemented);
+        assert!(err.message().unwrap().contains("requires HTTP/2"));
+    }
+
+    #[tokio::test]
+    async fn test_call_options_interceptors() {
+        use crate::{ClosureInterceptor, HeaderInterceptor, response_interceptor};
+        use std::sync::{Arc, Mutex};
+
+        /// Echoes the request body, and its `x-tenant` header as `x-seen`.
+        #[derive(Clone)]
+        struct EchoTransport;
+
+        impl Transport for EchoTransport {
+            type Body = TransportBody;
+
+            async fn request(
+                &self,
+                request: Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                let tenant = request.headers().get("x-tenant").cloned();
+                let mut response =
+                    http::Response::builder().header(header::CONTENT_TYPE, "application/json");
+                if let Some(tenant) = tenant {
+                    response = response.header("x-seen", tenant);
+                }
+                Ok(response.body(request.into_body()).unwrap())
+            }
+        }
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let recorded = order.clone();
+        let client = ConnectClient::builder("http://stub")
+            .with_interceptor(ClosureInterceptor::new(move |_: &mut RequestContext| {
+                recorded.lock().unwrap().push("client");
+                Ok(())
+            }))
+            .build_with_transport(EchoTransport)
+            .unwrap();
+
+        let recorded = order.clone();
+        let options = CallOptions::new()
+            .with_interceptor(HeaderInterceptor::new("x-tenant", "admin"))
+            .with_interceptor(ClosureInterceptor::new(move |_: &mut RequestContext| {
+                recorded.lock().unwrap().push("call");
+                Ok(())
+            }))
+            .with_request_interceptor(|_: &mut RequestContext<'_>, req: &mut String| {
+                req.push('!');
+                Ok(())
+            })
+            .with_response_interceptor(response_interceptor(|ctx, res: &mut String| {
+                res.push_str(ctx.headers["x-seen"].to_str().unwrap());
+                Ok(())
+            }));
+        let response = client
+            .call_unary_with_options::<String, String>(
+                "test.Service/Method",
+                &"hi".to_string(),
+                options,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner(), "hi!admin");
+        assert_eq!(*order.lock().unwrap(), ["client", "call"]);
+
+        // Interceptors of another message type fail the call
+        let options = CallOptions::new()
+            .with_request_interceptor(|_: &mut RequestContext<'_>, _: &mut u32| Ok(()));
+        let err = client
+            .call_unary_with_options::<String, String>(
+                "test.Service/Method",
+                &"hi".to_string(),
+                options,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::Internal);
+
+        // Options without interceptors leave the call alone
+        let response = client
+            .call_unary::<String, String>("test.Service/Method", &"hi".to_string())
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner(), "hi");
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_token() {
+        use futures::channel::mpsc;
+        use std::sync::{Arc, Mutex};
+
+        /// Never answers `Hang`; answers `Watch` with the frames sent to the
+        /// channel.
+        #[derive(Clone)]
+        struct WatchTransport(Arc<Mutex<Option<mpsc::UnboundedReceiver<Bytes>>>>);
+
+        impl Transport for WatchTransport {
+            type Body = TransportBody;
+
+            async fn request(
+                &self,
+                request: Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                if request.uri().path() == "/test.Service/Hang" {
+                    futures::future::pending::<()>().await;
+                }
+                let frames = self.0.lock().unwrap().take().unwrap();
+                Ok(http::Response::builder()
+                    .header(header::CONTENT_TYPE, "application/connect+proto")
+                    .body(TransportBody::streaming(frames.map(Ok)))
+                    .unwrap())
+            }
+        }
+
+        let (tx, rx) = mpsc::unbounded();
+        let client = ConnectClient::builder("http://stub")
+            .use_proto()
+            .build_with_transport(WatchTransport(Arc::new(Mutex::new(Some(rx)))))
+            .unwrap();
+
+        // A call waiting for its response
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            cancel.cancel();
+        });
+        let options = CallOptions::new().cancellation_token(token.clone());
+        let err = client
+            .call_unary_with_options::<(), ()>("test.Service/Hang", &(), options.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::Canceled);
+
+        // Already cancelled
+        let err = client
+            .call_unary_with_options::<(), ()>("test.Service/Hang", &(), options)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::Canceled);
+
+        // A response stream in progress
+        let token = CancellationToken::new();
+        let options = CallOptions::new().cancellation_token(token.clone());
+        let mut stream = client
+            .call_server_stream_with_options::<String, String>(
+                "test.Service/Watch",
+                &String::new(),
+                options,
+            )
+            .await
+            .unwrap()
+            .into_inner();
+        let message = "event".to_string().encode_to_vec();
+        tx.unbounded_send(Bytes::from(wrap_envelope(&message, false)))
+            .unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "event");
+
+        token.cancel();
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::Canceled);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_build_with_layers() {
+        use std::sync::{Arc, Mutex};
+        use tower::util::MapRequestLayer;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = seen.clone();
+        let service = tower::service_fn(move |request: Request<TransportBody>| {
+            let tags: Vec<_> = request
+                .headers()
+                .get_all("x-layer")
+                .iter()
+                .cloned()
+                .collect();
+            recorder.lock().unwrap().push(tags);
+            async {
+                Ok::<_, ClientError>(
+                    http::Response::builder()
+                        .header(header::CONTENT_TYPE, "application/proto")
+                        .body(http_body_util::Full::new(Bytes::new()))
+                        .unwrap(),
+                )
+            }
+        });
+        let tag = |value: &'static str| {
+            MapRequestLayer::new(move |mut request: Request<TransportBody>| {
+                request
+                    .headers_mut()
+                    .append("x-layer", HeaderValue::from_static(value));
+                request
+            })
+        };
+
+        let client = ConnectClient::builder("http://stub")
+            .use_proto()
+            .layer(tag("outer"))
+            .layer(tag("inner"))
+            .build_with_transport(service)
+            .unwrap();
+        client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap();
+
+        // The first layer added sees the request first
+        assert_eq!(*seen.lock().unwrap(), [["outer", "inner"]]);
+    }
 }
@@ -3,20 +3,35 @@
 //! This module contains request-level configuration:
 //! - [`CallOptions`]: Per-call timeout and headers
 //! - [`RetryPolicy`]: Retry behavior with exponential backoff
+//! - [`RetryBudget`]: Cap retries at a fraction of calls
+//! - [`HedgingPolicy`]: Duplicate slow idempotent calls
+//! - [`CircuitBreaker`]: Fail fast while a backend is failing
 //! - [`Interceptor`]: Header-level interception (simple, no message bounds)
 //! - [`MessageInterceptor`]: Message-level interception with typed access
+//! - [`AsyncInterceptor`]: Header-level interception that can await I/O
 
+mod circuit_breaker;
+mod hedging;
 mod interceptor;
 mod options;
 mod retry;
+mod retry_budget;
 
+pub(crate) use circuit_breaker::is_failure;
+pub use circuit_breaker::{CircuitBreaker, CircuitState, circuit_defaults};
+pub use hedging::{HedgingPolicy, hedge};
+pub(crate) use interceptor::CallInterceptors;
 pub use interceptor::{
-    BidiStreamInterceptors, Chain, ClientStreamInterceptors, ClosureInterceptor, HeaderInterceptor,
-    HeaderWrapper, Interceptor, InterceptorInternal, MessageInterceptor, MessageWrapper,
-    RequestContext, ResponseContext, ServerStreamInterceptors, StreamContext, StreamType,
-    TypedInterceptor, TypedMutInterceptor, UnaryInterceptors, response_interceptor,
-    stream_interceptor,
+    AsyncInterceptor, AsyncWrapper, BidiStreamInterceptors, Chain, ClientStreamInterceptors,
+    ClosureInterceptor, HeaderInterceptor, HeaderWrapper, Interceptor, InterceptorInternal,
+    MessageInterceptor, MessageWrapper, RequestContext, ResponseContext, ServerStreamInterceptors,
+    StreamContext, StreamType, TypedInterceptor, TypedMutInterceptor, UnaryInterceptors,
+    response_interceptor, stream_interceptor,
 };
 pub use options::CallOptions;
-pub(crate) use options::duration_to_timeout_header;
-pub use retry::{ExponentialBackoff, RetryExt, RetryPolicy, defaults, retry, retry_with_policy};
+pub(crate) use options::{duration_to_grpc_timeout, duration_to_timeout_header};
+pub use retry::{
+    ExponentialBackoff, RetryEvent, RetryExt, RetryPolicy, defaults, retry, retry_with_policy,
+};
+pub(crate) use retry::{RetryConfig, next_retry, retry_attempts};
+pub use retry_budget::{DEFAULT_RETRY_BURST, DEFAULT_RETRY_RATIO, RetryBudget};
@@ -0,0 +1,429 @@
+//! Client-side circuit breaker.
+//!
+//! A [`CircuitBreaker`] watches the outcome of recent calls. When too many of
+//! them fail, it *opens* and fails calls immediately with
+//! [`Code::Unavailable`] instead of sending them to a backend that is down.
+//! After [`open_duration`](CircuitBreaker::open_duration) it lets a few probe
+//! calls through (*half-open*); if they succeed it closes again, otherwise it
+//! stays open for another period.
+//!
+//! Configure one on the builder to guard every call of a client:
+//!
+//! ```ignore
+//! use connectrpc_axum_client::{CircuitBreaker, ConnectClient};
+//! use std::time::Duration;
+//!
+//! let client = ConnectClient::builder("http://localhost:3000")
+//!     .with_circuit_breaker(
+//!         CircuitBreaker::new()
+//!             .failure_rate_threshold(0.5)
+//!             .open_duration(Duration::from_secs(10)),
+//!     )
+//!     .build()?;
+//! ```
+//!
+//! or wrap individual calls with [`CircuitBreaker::call`].
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::ClientError;
+use connectrpc_axum_core::Code;
+
+/// Default configuration values for [`CircuitBreaker`].
+pub mod circuit_defaults {
+    use std::time::Duration;
+
+    /// Default failure rate at which the circuit opens.
+    pub const FAILURE_RATE_THRESHOLD: f64 = 0.5;
+
+    /// Default number of recent calls the failure rate is computed over.
+    pub const WINDOW_SIZE: usize = 20;
+
+    /// Default number of calls needed in the window before the circuit can open.
+    pub const MINIMUM_CALLS: usize = 10;
+
+    /// Default time the circuit stays open before probing.
+    pub const OPEN_DURATION: Duration = Duration::from_secs(30);
+
+    /// Default number of probe calls let through while half-open.
+    pub const HALF_OPEN_PROBES: u32 = 1;
+}
+
+/// State of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through and their outcomes are recorded.
+    Closed,
+    /// Calls fail immediately with [`Code::Unavailable`].
+    Open,
+    /// A limited number of probe calls go through to test the backend.
+    HalfOpen,
+}
+
+/// Circuit breaker that fails calls fast while a backend is failing.
+///
+/// A call counts as a failure when it fails with a transport error, a client
+/// timeout, an HTTP 5xx response, or one of the codes `unavailable`,
+/// `deadline_exceeded`, `internal`, `unknown` and `data_loss`. Other errors
+/// mean the backend answered, so they count as successes.
+///
+/// The breaker is cheap to clone; clones share their state, so one breaker
+/// can guard several clients of the same backend.
+///
+/// # Default Values
+///
+/// - `failure_rate_threshold`: 0.5
+/// - `window_size`: 20 calls
+/// - `minimum_calls`: 10
+/// - `open_duration`: 30 seconds
+/// - `half_open_probes`: 1
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    config: CircuitConfig,
+    state: Arc<Mutex<BreakerState>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CircuitConfig {
+    failure_rate_threshold: f64,
+    window_size: usize,
+    minimum_calls: usize,
+    open_duration: Duration,
+    half_open_probes: u32,
+}
+
+#[derive(Debug)]
+enum BreakerState {
+    /// Outcomes of the most recent calls, `true` for failures.
+    Closed {
+        outcomes: VecDeque<bool>,
+    },
+    /// Open until this `rt::now()` time.
+    Open {
+        until: Duration,
+    },
+    HalfOpen {
+        in_flight: u32,
+        successes: u32,
+    },
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            config: CircuitConfig {
+                failure_rate_threshold: circuit_defaults::FAILURE_RATE_THRESHOLD,
+                window_size: circuit_defaults::WINDOW_SIZE,
+                minimum_calls: circuit_defaults::MINIMUM_CALLS,
+                open_duration: circuit_defaults::OPEN_DURATION,
+                half_open_probes: circuit_defaults::HALF_OPEN_PROBES,
+            },
+            state: Arc::new(Mutex::new(BreakerState::Closed {
+                outcomes: VecDeque::new(),
+            })),
+        }
+    }
+}
+
+impl CircuitBreaker {
+    /// Create a circuit breaker with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the failure rate at which the circuit opens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is not greater than 0.0 and at most 1.0.
+    pub fn failure_rate_threshold(mut self, threshold: f64) -> Self {
+        assert!(
+            threshold > 0.0 && threshold <= 1.0,
+            "failure rate threshold must be in (0.0, 1.0]"
+        );
+        self.config.failure_rate_threshold = threshold;
+        self
+    }
+
+    /// Set the number of recent calls the failure rate is computed over.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn window_size(mut self, size: usize) -> Self {
+        assert!(size > 0, "window size must be at least 1");
+        self.config.window_size = size;
+        self
+    }
+
+    /// Set the number of calls the window must hold before the circuit can
+    /// open, so a single early failure doesn't trip it.
+    pub fn minimum_calls(mut self, calls: usize) -> Self {
+        self.config.minimum_calls = calls;
+        self
+    }
+
+    /// Set how long the circuit stays open before letting probe calls through.
+    pub fn open_duration(mut self, duration: Duration) -> Self {
+        self.config.open_duration = duration;
+        self
+    }
+
+    /// Set how many probe calls are let through while half-open. The circuit
+    /// closes once all of them succeed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `probes` is 0.
+    pub fn half_open_probes(mut self, probes: u32) -> Self {
+        assert!(probes > 0, "half-open probes must be at least 1");
+        self.config.half_open_probes = probes;
+        self
+    }
+
+    /// The current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        let mut state = self.lock();
+        self.advance(&mut state);
+        match *state {
+            BreakerState::Closed { .. } => CircuitState::Closed,
+            BreakerState::Open { .. } => CircuitState::Open,
+            BreakerState::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Run `call` through the breaker.
+    ///
+    /// Fails with [`Code::Unavailable`] without running `call` while the
+    /// circuit is open; otherwise records the outcome of `call`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let response = breaker
+    ///     .call(client.call_unary::<Req, Res>("service/Method", &request))
+    ///     .await?;
+    /// ```
+    pub async fn call<T, F>(&self, call: F) -> Result<T, ClientError>
+    where
+        F: Future<Output = Result<T, ClientError>>,
+    {
+        let permit = self.acquire()?;
+        let result = call.await;
+        permit.record_result(&result);
+        result
+    }
+
+    /// Admit a call, failing fast while the circuit is open.
+    pub(crate) fn acquire(&self) -> Result<Permit, ClientError> {
+        let mut state = self.lock();
+        self.advance(&mut state);
+        let probe = match &mut *state {
+            BreakerState::Closed { .. } => false,
+            BreakerState::HalfOpen { in_flight, .. }
+                if *in_flight < self.config.half_open_probes =>
+            {
+                *in_flight += 1;
+                true
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => {
+                return Err(ClientError::new(
+                    Code::Unavailable,
+                    "circuit breaker is open",
+                ));
+            }
+        };
+        Ok(Permit {
+            breaker: self.clone(),
+            probe,
+            recorded: false,
+        })
+    }
+
+    fn record(&self, probe: bool, success: bool) {
+        let mut state = self.lock();
+        match &mut *state {
+            BreakerState::Closed { outcomes } if !probe => {
+                outcomes.push_back(!success);
+                if outcomes.len() > self.config.window_size {
+                    outcomes.pop_front();
+                }
+                let failures = outcomes.iter().filter(|&&failed| failed).count();
+                if outcomes.len() >= self.config.minimum_calls
+                    && failures as f64 >= self.config.failure_rate_threshold * outcomes.len() as f64
+                {
+                    *state = self.open();
+                }
+            }
+            BreakerState::HalfOpen {
+                in_flight,
+                successes,
+            } if probe => {
+                *in_flight -= 1;
+                if !success {
+                    *state = self.open();
+                } else {
+                    *successes += 1;
+                    if *successes >= self.config.half_open_probes {
+                        *state = BreakerState::Closed {
+                            outcomes: VecDeque::new(),
+                        };
+                    }
+                }
+            }
+            // Outcomes of calls admitted before the last state change
+            _ => {}
+        }
+    }
+
+    /// Release the probe slot of a call that was dropped before finishing.
+    fn release(&self) {
+        if let BreakerState::HalfOpen { in_flight, .. } = &mut *self.lock() {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+    }
+
+    /// Move an open circuit whose open period has passed to half-open.
+    fn advance(&self, state: &mut BreakerState) {
+        if matches!(*state, BreakerState::Open { until } if crate::rt::now() >= until) {
+            *state = BreakerState::HalfOpen {
+                in_flight: 0,
+                successes: 0,
+            };
+        }
+    }
+
+    fn open(&self) -> BreakerState {
+        BreakerState::Open {
+            until: crate::rt::now() + self.config.open_duration,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A call admitted by a [`CircuitBreaker`].
+///
+/// Dropping a permit without recording an outcome, e.g. because the call
+/// was cancelled, frees its probe slot without counting it.
+#[derive(Debug)]
+pub(crate) struct Permit {
+    breaker: CircuitBreaker,
+    probe: bool,
+    recorded: bool,
+}
+
+impl Permit {
+    /// Record the outcome of the call.
+    pub(crate) fn record(mut self, success: bool) {
+        self.recorded = true;
+        self.breaker.record(self.probe, success);
+    }
+
+    /// Record the outcome of a call that ended with `result`.
+    pub(crate) fn record_result<T>(self, result: &Result<T, ClientError>) {
+        self.record(result.as_ref().err().is_none_or(|e| !is_failure(e)));
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if !self.recorded && self.probe {
+            self.breaker.release();
+        }
+    }
+}
+
+/// Whether `err` means the backend failed, rather than rejected the call.
+pub(crate) fn is_failure(err: &ClientError) -> bool {
+    match err {
+        ClientError::Transport(_) => true,
+        ClientError::Rpc(status, _) => matches!(
+            status.code(),
+            Code::Unavailable
+                | Code::DeadlineExceeded
+                | Code::Internal
+                | Code::Unknown
+                | Code::DataLoss
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn fail(breaker: &CircuitBreaker) -> ClientError {
+        breaker
+            .call(async { Err::<(), _>(ClientError::unavailable("down")) })
+            .await
+            .unwrap_err()
+    }
+
+    async fn succeed(breaker: &CircuitBreaker) {
+        breaker.call(async { Ok(()) }).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_opens_at_failure_rate() {
+        let breaker = CircuitBreaker::new().window_size(4).minimum_calls(4);
+
+        succeed(&breaker).await;
+        fail(&breaker).await;
+        succeed(&breaker).await;
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        // Non-failure errors count as successes
+        let err = breaker
+            .call(async { Err::<(), _>(ClientError::not_found("missing")) })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::NotFound);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        // The window now holds 2 failures out of 4
+        fail(&breaker).await;
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        let mut ran = false;
+        let err = breaker
+            .call(async {
+                ran = true;
+                Ok(())
+            })
+            .await
+            .unwrap_err();
+        assert!(!ran);
+        assert_eq!(err.code(), Code::Unavailable);
+        assert_eq!(err.message(), Some("circuit breaker is open"));
+    }
+
+    #[tokio::test]
+    async fn test_half_open_probes() {
+        let breaker = CircuitBreaker::new()
+            .window_size(1)
+            .minimum_calls(1)
+            .open_duration(Duration::ZERO);
+
+        fail(&breaker).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // One probe at a time; a cancelled probe frees its slot
+        let probe = breaker.acquire().unwrap();
+        assert!(breaker.acquire().is_err());
+        drop(probe);
+
+        // A failed probe reopens the circuit
+        fail(&breaker).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // A successful probe closes it
+        succeed(&breaker).await;
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
@@ -0,0 +1,203 @@
+//! Hedged requests for idempotent calls.
+//!
+//! Hedging cuts tail latency by not waiting for a slow attempt: if no
+//! response arrives within [`HedgingPolicy::delay`], a duplicate request is
+//! sent, and the first successful response wins. Attempts still running
+//! then are canceled by dropping them, which resets their HTTP streams.
+//!
+//! Because the server may process several copies of the same request, only
+//! hedge calls that are safe to repeat; mark them with
+//! [`CallOptions::idempotent`](crate::CallOptions::idempotent).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use connectrpc_axum_client::{CallOptions, HedgingPolicy};
+//! use std::time::Duration;
+//!
+//! let policy = HedgingPolicy::new(Duration::from_millis(50)).max_attempts(3);
+//!
+//! let response = client.call_unary_with_hedging::<GetUserRequest, User>(
+//!     "users.v1.UserService/GetUser",
+//!     &request,
+//!     CallOptions::new().idempotent(true),
+//!     &policy,
+//! ).await?;
+//! ```
+
+use std::future::Future;
+use std::pin::pin;
+use std::time::Duration;
+
+use futures::StreamExt;
+use futures::future::{Either, select};
+use futures::stream::FuturesUnordered;
+
+use crate::ClientError;
+
+/// Default maximum number of attempts, including the original request.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 2;
+
+/// Configuration for hedged requests.
+///
+/// # Example
+///
+/// ```
+/// use connectrpc_axum_client::HedgingPolicy;
+/// use std::time::Duration;
+///
+/// // Send a second copy if the first hasn't answered within 50ms
+/// let policy = HedgingPolicy::new(Duration::from_millis(50));
+///
+/// // Up to 3 copies in total, 50ms apart
+/// let policy = HedgingPolicy::new(Duration::from_millis(50)).max_attempts(3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HedgingPolicy {
+    /// How long to wait for a response before sending the next attempt.
+    pub delay: Duration,
+
+    /// Maximum number of attempts, including the original request.
+    pub max_attempts: u32,
+}
+
+impl HedgingPolicy {
+    /// Create a policy that sends a second attempt after `delay`.
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    /// Set the maximum number of attempts, including the original request.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_attempts` is 0.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        assert!(max_attempts >= 1, "max_attempts must be >= 1");
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Set how long to wait for a response before sending the next attempt.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Run `f` as hedged attempts under `policy`, returning the first success.
+///
+/// `f` is passed the attempt number, starting at 1. A new attempt starts
+/// each time [`delay`](HedgingPolicy::delay) passes without a response, and
+/// immediately when an attempt fails with a retryable error (see
+/// [`ClientError::is_retryable`]). A non-retryable error is returned at once.
+/// Once an attempt succeeds or fails for good, the others are dropped.
+///
+/// If every attempt fails, the last error is returned.
+///
+/// # Example
+///
+/// ```ignore
+/// use connectrpc_axum_client::{HedgingPolicy, hedge};
+/// use std::time::Duration;
+///
+/// let policy = HedgingPolicy::new(Duration::from_millis(50));
+///
+/// let response = hedge(&policy, |_attempt| async {
+///     client.call_unary::<Req, Res>("service/Method", &request).await
+/// }).await?;
+/// ```
+pub async fn hedge<F, Fut, T>(policy: &HedgingPolicy, mut f: F) -> Result<T, ClientError>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, ClientError>>,
+{
+    let mut in_flight = FuturesUnordered::new();
+    in_flight.push(f(1));
+    let mut started = 1;
+
+    loop {
+        let result = if started < policy.max_attempts {
+            let delay = pin!(crate::rt::sleep(policy.delay));
+            let finished = match select(in_flight.next(), delay).await {
+                Either::Left((result, _)) => Some(result),
+                Either::Right(_) => None,
+            };
+            let Some(result) = finished else {
+                started += 1;
+                #[cfg(feature = "tracing")]
+                tracing::debug!(attempt = started, "sending hedged attempt");
+                in_flight.push(f(started));
+                continue;
+            };
+            result
+        } else {
+            in_flight.next().await
+        };
+
+        match result.expect("a hedged attempt is always in flight") {
+            Ok(response) => return Ok(response),
+            Err(e) if e.is_retryable() && started < policy.max_attempts => {
+                started += 1;
+                in_flight.push(f(started));
+            }
+            Err(e) if e.is_retryable() && !in_flight.is_empty() => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Code;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_hedge_first_success_wins() {
+        let policy = HedgingPolicy::new(Duration::from_millis(20)).max_attempts(3);
+        let started = Mutex::new(Vec::new());
+
+        // The first attempt hangs; the second answers right away
+        let result = hedge(&policy, |attempt| {
+            started.lock().unwrap().push(attempt);
+            async move {
+                if attempt == 1 {
+                    std::future::pending::<()>().await;
+                }
+                Ok::<_, ClientError>(attempt)
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(*started.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_hedge_errors() {
+        let policy = HedgingPolicy::new(Duration::from_secs(60)).max_attempts(3);
+
+        // Retryable failures start the next attempt without waiting
+        let result = hedge(&policy, |attempt| async move {
+            Err::<(), _>(ClientError::new(
+                Code::Unavailable,
+                format!("attempt {attempt}"),
+            ))
+        })
+        .await;
+        assert_eq!(result.unwrap_err().message(), Some("attempt 3"));
+
+        // Others end the call
+        let mut attempts = 0;
+        let result = hedge(&policy, |_| {
+            attempts += 1;
+            async { Err::<(), _>(ClientError::new(Code::InvalidArgument, "bad")) }
+        })
+        .await;
+        assert_eq!(result.unwrap_err().code(), Code::InvalidArgument);
+        assert_eq!(attempts, 1);
+    }
+}
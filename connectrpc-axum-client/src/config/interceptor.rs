@@ -1,10 +1,12 @@
 //! Unified interceptor system for Connect RPC client.
 //!
-//! Two user-facing traits:
+//! Three user-facing traits:
 //! - [`Interceptor`]: Header-level access only (simple, no message bounds)
 //! - [`MessageInterceptor`]: Full typed message access
+//! - [`AsyncInterceptor`]: Header-level access that can await I/O, e.g. to
+//!   fetch an OAuth token before the request is sent
 //!
-//! Both are wrapped internally to a unified [`InterceptorInternal`] trait,
+//! All are wrapped internally to a unified [`InterceptorInternal`] trait,
 //! enabling zero-cost composition via [`Chain`].
 //!
 //! # Example
@@ -43,13 +45,15 @@
 //!     .build()?;
 //! ```
 
+use std::any::Any;
+use std::future::Future;
 use std::sync::Arc;
 
 use http::HeaderMap;
 use prost::Message;
 use serde::{Serialize, de::DeserializeOwned};
 
-use crate::ClientError;
+use crate::{ClientError, Code};
 
 // ============================================================================
 // Context Types
@@ -57,19 +61,43 @@ use crate::ClientError;
 
 /// Context for intercepting a request.
 ///
-/// Provides access to the procedure name and mutable headers.
+/// Provides access to the procedure name, mutable headers and, for calls
+/// made with [`ConnectClient::call_unary_with_retry`], the retry attempt.
+///
+/// [`ConnectClient::call_unary_with_retry`]: crate::ConnectClient::call_unary_with_retry
 #[derive(Debug)]
 pub struct RequestContext<'a> {
     /// The procedure being called (e.g., "package.Service/Method").
     pub procedure: &'a str,
     /// HTTP headers for the request (mutable).
     pub headers: &'a mut HeaderMap,
+    /// The attempt number, starting at 1 for the first attempt.
+    pub attempt: u32,
+    /// The error that failed the previous attempt, when this is a retry.
+    pub previous_error: Option<&'a ClientError>,
 }
 
 impl<'a> RequestContext<'a> {
-    /// Create a new request context.
+    /// Create a new request context for a first attempt.
     pub fn new(procedure: &'a str, headers: &'a mut HeaderMap) -> Self {
-        Self { procedure, headers }
+        Self {
+            procedure,
+            headers,
+            attempt: 1,
+            previous_error: None,
+        }
+    }
+
+    /// Set the attempt number and the error that failed the previous attempt.
+    pub fn with_attempt(mut self, attempt: u32, previous_error: Option<&'a ClientError>) -> Self {
+        self.attempt = attempt;
+        self.previous_error = previous_error;
+        self
+    }
+
+    /// Returns `true` if this request retries a failed attempt.
+    pub fn is_retry(&self) -> bool {
+        self.attempt > 1
     }
 }
 
@@ -258,6 +286,41 @@ pub trait MessageInterceptor: Send + Sync + Clone + 'static {
     }
 }
 
+/// Header-level interceptor whose request hook is async.
+///
+/// Use this when adding headers needs I/O, such as fetching or refreshing an
+/// OAuth token or signing the request with a remote key. The call waits for
+/// `on_request` before the request is sent. Async interceptors run in the
+/// order they were added, interleaved with the synchronous ones.
+///
+/// # Example
+///
+/// ```ignore
+/// use connectrpc_axum_client::{AsyncInterceptor, ClientError, RequestContext};
+///
+/// #[derive(Clone)]
+/// struct OAuthInterceptor {
+///     tokens: Arc<TokenSource>,
+/// }
+///
+/// impl AsyncInterceptor for OAuthInterceptor {
+///     async fn on_request(&self, ctx: &mut RequestContext<'_>) -> Result<(), ClientError> {
+///         let token = self.tokens.token().await?;
+///         ctx.headers.insert("authorization", format!("Bearer {token}").parse().unwrap());
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait AsyncInterceptor: Send + Sync + Clone + 'static {
+    /// Called before the request is sent.
+    ///
+    /// Can modify headers or return an error to abort the call.
+    fn on_request(
+        &self,
+        ctx: &mut RequestContext<'_>,
+    ) -> impl Future<Output = Result<(), ClientError>> + Send;
+}
+
 // ============================================================================
 // Internal Unified Trait
 // ============================================================================
@@ -276,6 +339,20 @@ pub trait InterceptorInternal: Send + Sync + Clone + 'static {
     where
         Req: Message + Serialize + 'static;
 
+    /// Intercept a request, awaiting any async interceptors in the chain.
+    ///
+    /// Defaults to [`intercept_request`](Self::intercept_request).
+    fn intercept_request_async<Req>(
+        &self,
+        ctx: &mut RequestContext<'_>,
+        request: &mut Req,
+    ) -> impl Future<Output = Result<(), ClientError>> + Send
+    where
+        Req: Message + Serialize + 'static,
+    {
+        async move { self.intercept_request(ctx, request) }
+    }
+
     /// Intercept a unary response.
     fn intercept_response<Res>(
         &self,
@@ -468,6 +545,68 @@ impl<I: MessageInterceptor> InterceptorInternal for MessageWrapper<I> {
     }
 }
 
+/// Wrapper that adapts an [`AsyncInterceptor`] to [`InterceptorInternal`].
+#[derive(Clone, Debug)]
+pub struct AsyncWrapper<I>(pub I);
+
+impl<I: AsyncInterceptor> InterceptorInternal for AsyncWrapper<I> {
+    fn intercept_request<Req>(
+        &self,
+        _ctx: &mut RequestContext,
+        _request: &mut Req,
+    ) -> Result<(), ClientError>
+    where
+        Req: Message + Serialize + 'static,
+    {
+        // Only reachable through `intercept_request_async`
+        Ok(())
+    }
+
+    async fn intercept_request_async<Req>(
+        &self,
+        ctx: &mut RequestContext<'_>,
+        _request: &mut Req,
+    ) -> Result<(), ClientError>
+    where
+        Req: Message + Serialize + 'static,
+    {
+        self.0.on_request(ctx).await
+    }
+
+    fn intercept_response<Res>(
+        &self,
+        _ctx: &ResponseContext,
+        _response: &mut Res,
+    ) -> Result<(), ClientError>
+    where
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        Ok(())
+    }
+
+    fn intercept_stream_send<Req>(
+        &self,
+        _ctx: &StreamContext,
+        _request: &mut Req,
+    ) -> Result<(), ClientError>
+    where
+        Req: Message + Serialize + 'static,
+    {
+        Ok(())
+    }
+
+    fn intercept_stream_receive<Res>(
+        &self,
+        _ctx: &StreamContext,
+        _response: &mut Res,
+    ) -> Result<(), ClientError>
+    where
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        Ok(())
+    }
+}
+
 // ============================================================================
 // Chain Combinator
 // ============================================================================
@@ -499,6 +638,18 @@ where
         self.1.intercept_request(ctx, request)
     }
 
+    async fn intercept_request_async<Req>(
+        &self,
+        ctx: &mut RequestContext<'_>,
+        request: &mut Req,
+    ) -> Result<(), ClientError>
+    where
+        Req: Message + Serialize + 'static,
+    {
+        self.0.intercept_request_async(ctx, request).await?;
+        self.1.intercept_request_async(ctx, request).await
+    }
+
     fn intercept_response<Res>(
         &self,
         ctx: &ResponseContext,
@@ -788,6 +939,170 @@ where
     f
 }
 
+// ============================================================================
+// Per-Call Interceptors
+// ============================================================================
+
+/// Interceptor added to a single call through [`CallOptions`](crate::CallOptions).
+///
+/// Object-safe counterpart of [`InterceptorInternal`], with messages passed
+/// as `Any` since `CallOptions` doesn't know the call's message types.
+trait CallInterceptor: Send + Sync {
+    /// Intercept a request; `request` is `None` for streaming calls.
+    fn intercept_request(
+        &self,
+        ctx: &mut RequestContext<'_>,
+        request: Option<&mut dyn Any>,
+    ) -> Result<(), ClientError>;
+
+    /// Intercept a unary response.
+    fn intercept_response(
+        &self,
+        ctx: &ResponseContext<'_>,
+        response: &mut dyn Any,
+    ) -> Result<(), ClientError>;
+}
+
+/// Per-call header-level interceptor.
+struct CallHeaders<I>(I);
+
+impl<I: Interceptor> CallInterceptor for CallHeaders<I> {
+    fn intercept_request(
+        &self,
+        ctx: &mut RequestContext<'_>,
+        _request: Option<&mut dyn Any>,
+    ) -> Result<(), ClientError> {
+        self.0.on_request(ctx)
+    }
+
+    fn intercept_response(
+        &self,
+        ctx: &ResponseContext<'_>,
+        _response: &mut dyn Any,
+    ) -> Result<(), ClientError> {
+        self.0.on_response(ctx)
+    }
+}
+
+/// Per-call interceptor of unary request messages of type `Req`.
+struct RequestMessage<Req>(Arc<dyn TypedMutInterceptor<Req>>);
+
+impl<Req: 'static> CallInterceptor for RequestMessage<Req> {
+    fn intercept_request(
+        &self,
+        ctx: &mut RequestContext<'_>,
+        request: Option<&mut dyn Any>,
+    ) -> Result<(), ClientError> {
+        match request {
+            Some(request) => self.0.intercept(ctx, downcast::<Req>(request)?),
+            None => Ok(()),
+        }
+    }
+
+    fn intercept_response(
+        &self,
+        _ctx: &ResponseContext<'_>,
+        _response: &mut dyn Any,
+    ) -> Result<(), ClientError> {
+        Ok(())
+    }
+}
+
+/// Per-call interceptor of unary response messages of type `Res`.
+struct ResponseMessage<Res>(Arc<dyn for<'a> TypedInterceptor<ResponseContext<'a>, Res>>);
+
+impl<Res: 'static> CallInterceptor for ResponseMessage<Res> {
+    fn intercept_request(
+        &self,
+        _ctx: &mut RequestContext<'_>,
+        _request: Option<&mut dyn Any>,
+    ) -> Result<(), ClientError> {
+        Ok(())
+    }
+
+    fn intercept_response(
+        &self,
+        ctx: &ResponseContext<'_>,
+        response: &mut dyn Any,
+    ) -> Result<(), ClientError> {
+        self.0.intercept(ctx, downcast::<Res>(response)?)
+    }
+}
+
+/// Downcast a message to the type a per-call interceptor was added for.
+fn downcast<T: 'static>(message: &mut dyn Any) -> Result<&mut T, ClientError> {
+    message.downcast_mut::<T>().ok_or_else(|| {
+        ClientError::new(
+            Code::Internal,
+            format!(
+                "per-call interceptor expects messages of type {}",
+                std::any::type_name::<T>()
+            ),
+        )
+    })
+}
+
+/// The interceptors of a single call, run after the client's for requests
+/// and before them for responses.
+#[derive(Clone, Default)]
+pub(crate) struct CallInterceptors(Vec<Arc<dyn CallInterceptor>>);
+
+impl CallInterceptors {
+    /// Add a header-level interceptor.
+    pub(crate) fn push_header<I: Interceptor>(&mut self, interceptor: I) {
+        self.0.push(Arc::new(CallHeaders(interceptor)));
+    }
+
+    /// Add an interceptor of unary request messages.
+    pub(crate) fn push_request<Req: 'static>(
+        &mut self,
+        interceptor: impl TypedMutInterceptor<Req>,
+    ) {
+        self.0.push(Arc::new(RequestMessage(Arc::new(interceptor))));
+    }
+
+    /// Add an interceptor of unary response messages.
+    pub(crate) fn push_response<Res: 'static>(
+        &mut self,
+        interceptor: impl for<'a> TypedInterceptor<ResponseContext<'a>, Res>,
+    ) {
+        self.0
+            .push(Arc::new(ResponseMessage(Arc::new(interceptor))));
+    }
+
+    /// Run the interceptors on a request, in the order they were added.
+    pub(crate) fn intercept_request(
+        &self,
+        ctx: &mut RequestContext<'_>,
+        mut request: Option<&mut dyn Any>,
+    ) -> Result<(), ClientError> {
+        for interceptor in &self.0 {
+            interceptor.intercept_request(ctx, request.as_deref_mut())?;
+        }
+        Ok(())
+    }
+
+    /// Run the interceptors on a unary response, in reverse order.
+    pub(crate) fn intercept_response(
+        &self,
+        ctx: &ResponseContext<'_>,
+        response: &mut dyn Any,
+    ) -> Result<(), ClientError> {
+        for interceptor in self.0.iter().rev() {
+            interceptor.intercept_response(ctx, response)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for CallInterceptors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallInterceptors")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
 // ============================================================================
 // Per-RPC Type Interceptor Storage
 // ============================================================================
@@ -1377,4 +1692,42 @@ mod tests {
         let err = chain.intercept_request(&mut ctx, &mut msg).unwrap_err();
         assert_eq!(err.message(), Some("stopped"));
     }
+
+    #[tokio::test]
+    async fn test_async_interceptor_in_chain() {
+        #[derive(Clone)]
+        struct TokenInterceptor;
+
+        impl AsyncInterceptor for TokenInterceptor {
+            async fn on_request(&self, ctx: &mut RequestContext<'_>) -> Result<(), ClientError> {
+                tokio::task::yield_now().await;
+                // Sees headers set by interceptors added before it
+                assert!(ctx.headers.contains_key("x-first"));
+                ctx.headers
+                    .insert("authorization", "Bearer t".parse().unwrap());
+                Ok(())
+            }
+        }
+
+        let chain = Chain(
+            Chain(
+                HeaderWrapper(HeaderInterceptor::new("x-first", "1")),
+                AsyncWrapper(TokenInterceptor),
+            ),
+            HeaderWrapper(ClosureInterceptor::new(|ctx: &mut RequestContext| {
+                assert!(ctx.headers.contains_key("authorization"));
+                Ok(())
+            })),
+        );
+
+        let mut headers = HeaderMap::new();
+        let mut ctx = RequestContext::new("test/Method", &mut headers);
+        let mut msg = TestMessage::default();
+
+        chain
+            .intercept_request_async(&mut ctx, &mut msg)
+            .await
+            .unwrap();
+        assert_eq!(headers.get("authorization").unwrap(), "Bearer t");
+    }
 }
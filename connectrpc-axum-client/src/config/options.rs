@@ -3,8 +3,15 @@
 //! This module provides [`CallOptions`] for configuring individual RPC calls
 //! with timeouts, custom headers, and other per-call settings.
 
+use base64::Engine;
 use http::{HeaderMap, HeaderName, HeaderValue};
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::ClientError;
+use crate::config::{
+    CallInterceptors, Interceptor, ResponseContext, TypedInterceptor, TypedMutInterceptor,
+};
 
 /// Options for configuring individual RPC calls.
 ///
@@ -33,8 +40,22 @@ pub struct CallOptions {
     /// Timeout for this specific call.
     /// If set, overrides the client's default timeout.
     pub(crate) timeout: Option<Duration>,
+    /// Deadline for each message of a response stream.
+    pub(crate) message_timeout: Option<Duration>,
     /// Custom headers for this specific call.
     pub(crate) headers: HeaderMap,
+    /// Send unary Connect calls as GET requests.
+    pub(crate) use_get: bool,
+    /// The call is safe to send more than once.
+    pub(crate) idempotent: bool,
+    /// Attempt number set by the retrying call API; 0 outside of it.
+    pub(crate) attempt: u32,
+    /// Error that failed the previous attempt, set by the retrying call API.
+    pub(crate) previous_error: Option<ClientError>,
+    /// Interceptors for this specific call.
+    pub(crate) interceptors: CallInterceptors,
+    /// Token that aborts the call when cancelled.
+    pub(crate) cancel: Option<CancellationToken>,
 }
 
 impl CallOptions {
@@ -78,6 +99,26 @@ impl CallOptions {
         self.timeout
     }
 
+    /// Set the deadline for each message of the response stream.
+    ///
+    /// For server-streaming and bidirectional RPCs, the stream yields an
+    /// error with [`Code::DeadlineExceeded`](crate::Code::DeadlineExceeded)
+    /// and ends if the next message doesn't arrive within `timeout` of
+    /// polling for it. Unlike [`timeout`](Self::timeout), this isn't sent to
+    /// the server and doesn't limit how long a stream that keeps sending may
+    /// run. Ignored by other RPC types.
+    ///
+    /// See [`Streaming::with_message_timeout`](crate::Streaming::with_message_timeout).
+    pub fn message_timeout(mut self, timeout: Duration) -> Self {
+        self.message_timeout = Some(timeout);
+        self
+    }
+
+    /// Get the configured per-message deadline, if any.
+    pub fn get_message_timeout(&self) -> Option<Duration> {
+        self.message_timeout
+    }
+
     /// Add a custom header for this call.
     ///
     /// Note: Protocol-reserved headers are filtered and will not be sent.
@@ -111,6 +152,37 @@ impl CallOptions {
         self
     }
 
+    /// Add a binary header for this call, such as `x-trace-bin`.
+    ///
+    /// Binary metadata keys end in `-bin` by convention; the value is sent
+    /// base64-encoded (standard alphabet, no padding), as gRPC and Connect
+    /// servers expect. Read such values back with
+    /// [`Metadata::get_bin`](crate::Metadata::get_bin).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the header name is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::CallOptions;
+    ///
+    /// let options = CallOptions::new().header_bin("x-trace-bin", &[0x01, 0x02, 0x03]);
+    /// ```
+    pub fn header_bin<K>(mut self, name: K, value: impl AsRef<[u8]>) -> Self
+    where
+        K: TryInto<HeaderName>,
+        K::Error: std::fmt::Debug,
+    {
+        let name = name.try_into().expect("invalid header name");
+        let encoded = base64::engine::general_purpose::STANDARD_NO_PAD.encode(value);
+        // Base64 output is always a valid header value
+        let value = HeaderValue::try_from(encoded).expect("base64 is a valid header value");
+        self.headers.insert(name, value);
+        self
+    }
+
     /// Try to add a custom header for this call.
     ///
     /// Returns `None` if the header name or value is invalid.
@@ -167,6 +239,151 @@ impl CallOptions {
         &self.headers
     }
 
+    /// Send this unary call as an HTTP GET request.
+    ///
+    /// Connect allows GET for methods without side effects
+    /// (`idempotency_level = NO_SIDE_EFFECTS`), which makes responses
+    /// cacheable by browsers and CDNs. The request message is sent in the
+    /// query string; if the URL would exceed 8 KiB, the call falls back to
+    /// POST.
+    ///
+    /// Only applies to unary calls in the Connect protocol; ignored otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::CallOptions;
+    ///
+    /// let response = client.call_unary_with_options::<GetUserRequest, User>(
+    ///     "users.v1.UserService/GetUser",
+    ///     &request,
+    ///     CallOptions::new().use_get(true),
+    /// ).await?;
+    /// ```
+    pub fn use_get(mut self, enabled: bool) -> Self {
+        self.use_get = enabled;
+        self
+    }
+
+    /// Check if unary calls are sent as GET requests.
+    pub fn uses_get(&self) -> bool {
+        self.use_get
+    }
+
+    /// Mark this call as idempotent: safe to send more than once.
+    ///
+    /// [`ConnectClient::call_unary_with_hedging`](crate::ConnectClient::call_unary_with_hedging)
+    /// only hedges idempotent calls. Calls sent with
+    /// [`use_get`](Self::use_get) have no side effects and count as
+    /// idempotent too.
+    pub fn idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
+
+    /// Check if this call is safe to send more than once.
+    pub fn is_idempotent(&self) -> bool {
+        self.idempotent || self.use_get
+    }
+
+    /// Abort the call when `token` is cancelled.
+    ///
+    /// A call still waiting for its response fails with
+    /// [`Code::Canceled`]; the request and response streams of a streaming
+    /// call end with that error, which drops the HTTP request. A token
+    /// cancelled before the call is made fails it without sending anything.
+    /// Cancelling one token aborts every call made with it, which gives
+    /// structured cancellation without a `select!` around each call.
+    ///
+    /// [`Code::Canceled`]: crate::Code::Canceled
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{CallOptions, CancellationToken};
+    ///
+    /// let token = CancellationToken::new();
+    /// let options = CallOptions::new().cancellation_token(token.child_token());
+    ///
+    /// // Elsewhere, e.g. on shutdown
+    /// token.cancel();
+    /// ```
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Get the cancellation token of this call, if any.
+    pub fn get_cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancel.as_ref()
+    }
+
+    /// Add a header-level interceptor to this call.
+    ///
+    /// It runs after the client's interceptors on the request, for every
+    /// kind of call, and before them on the response of unary calls. Use it
+    /// for one-off concerns, like an override header in a specific code
+    /// path. Interceptors added to the same options run in order.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{CallOptions, HeaderInterceptor};
+    ///
+    /// let options = CallOptions::new()
+    ///     .with_interceptor(HeaderInterceptor::new("x-tenant", "admin"));
+    /// ```
+    pub fn with_interceptor(mut self, interceptor: impl Interceptor) -> Self {
+        self.interceptors.push_header(interceptor);
+        self
+    }
+
+    /// Add an interceptor of the request message to this unary call.
+    ///
+    /// It runs after the client's interceptors and can modify the headers
+    /// and the message, or return an error to abort the call. `Req` must be
+    /// the call's request type, or the call fails with [`Code::Internal`].
+    /// Streaming calls skip it.
+    ///
+    /// [`Code::Internal`]: crate::Code::Internal
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::{CallOptions, RequestContext};
+    ///
+    /// let options = CallOptions::new().with_request_interceptor(
+    ///     |_ctx: &mut RequestContext<'_>, req: &mut GetUserRequest| {
+    ///         req.include_deleted = true;
+    ///         Ok(())
+    ///     },
+    /// );
+    /// ```
+    pub fn with_request_interceptor<Req: 'static>(
+        mut self,
+        interceptor: impl TypedMutInterceptor<Req>,
+    ) -> Self {
+        self.interceptors.push_request(interceptor);
+        self
+    }
+
+    /// Add an interceptor of the response message to this unary call.
+    ///
+    /// It runs before the client's interceptors. `Res` must be the call's
+    /// response type, or the call fails with [`Code::Internal`]. Streaming
+    /// calls skip it. Closures may need
+    /// [`response_interceptor`](crate::response_interceptor) for their
+    /// lifetimes to be inferred.
+    ///
+    /// [`Code::Internal`]: crate::Code::Internal
+    pub fn with_response_interceptor<Res: 'static>(
+        mut self,
+        interceptor: impl for<'a> TypedInterceptor<ResponseContext<'a>, Res>,
+    ) -> Self {
+        self.interceptors.push_response(interceptor);
+        self
+    }
+
     /// Get a mutable reference to the custom headers.
     ///
     /// This allows direct manipulation of the header map.
@@ -199,6 +416,28 @@ pub(crate) fn duration_to_timeout_header(duration: Duration) -> Option<String> {
     Some(millis.to_string())
 }
 
+/// Maximum number of digits in a `grpc-timeout` value.
+const MAX_GRPC_TIMEOUT_VALUE: u128 = 99_999_999;
+
+/// Convert a Duration to the `grpc-timeout` header value.
+///
+/// Uses the finest unit (milliseconds, seconds or hours) whose value fits in
+/// the 8 digits gRPC allows. Returns None for a zero duration.
+pub(crate) fn duration_to_grpc_timeout(duration: Duration) -> Option<String> {
+    let millis = duration.as_millis();
+    if millis == 0 {
+        return None;
+    }
+    if millis <= MAX_GRPC_TIMEOUT_VALUE {
+        return Some(format!("{}m", millis));
+    }
+    let secs = duration.as_secs() as u128;
+    if secs <= MAX_GRPC_TIMEOUT_VALUE {
+        return Some(format!("{}S", secs));
+    }
+    Some(format!("{}H", (secs / 3600).min(MAX_GRPC_TIMEOUT_VALUE)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,12 +448,32 @@ mod tests {
         assert!(options.timeout.is_none());
     }
 
+    #[test]
+    fn test_call_options_use_get() {
+        assert!(!CallOptions::new().uses_get());
+        assert!(CallOptions::new().use_get(true).uses_get());
+    }
+
+    #[test]
+    fn test_call_options_idempotent() {
+        assert!(!CallOptions::new().is_idempotent());
+        assert!(CallOptions::new().idempotent(true).is_idempotent());
+        assert!(CallOptions::new().use_get(true).is_idempotent());
+    }
+
     #[test]
     fn test_call_options_timeout() {
         let options = CallOptions::new().timeout(Duration::from_secs(30));
         assert_eq!(options.timeout, Some(Duration::from_secs(30)));
     }
 
+    #[test]
+    fn test_call_options_message_timeout() {
+        let options = CallOptions::new().message_timeout(Duration::from_secs(5));
+        assert_eq!(options.get_message_timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(options.get_timeout(), None);
+    }
+
     #[test]
     fn test_call_options_header() {
         let options = CallOptions::new()
@@ -228,6 +487,12 @@ mod tests {
         assert_eq!(options.headers.get("x-request-id").unwrap(), "abc-123");
     }
 
+    #[test]
+    fn test_call_options_header_bin() {
+        let options = CallOptions::new().header_bin("x-trace-bin", [1u8, 2, 3, 4]);
+        assert_eq!(options.headers.get("x-trace-bin").unwrap(), "AQIDBA");
+    }
+
     #[test]
     fn test_call_options_try_header() {
         let options = CallOptions::new()
@@ -283,6 +548,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_duration_to_grpc_timeout() {
+        assert_eq!(duration_to_grpc_timeout(Duration::ZERO), None);
+        assert_eq!(
+            duration_to_grpc_timeout(Duration::from_millis(1500)),
+            Some("1500m".to_string())
+        );
+        assert_eq!(
+            duration_to_grpc_timeout(Duration::from_secs(200_000)),
+            Some("200000S".to_string())
+        );
+        assert_eq!(
+            duration_to_grpc_timeout(Duration::from_secs(u64::MAX)),
+            Some("99999999H".to_string())
+        );
+    }
+
     #[test]
     fn test_duration_to_timeout_header() {
         // Normal case
@@ -37,10 +37,20 @@
 //!
 //! Non-retryable errors (e.g., `InvalidArgument`, `NotFound`, `PermissionDenied`)
 //! are returned immediately without retry.
+//!
+//! # Server Retry Guidance
+//!
+//! When an error carries a `google.rpc.RetryInfo` detail or the response had
+//! a `Retry-After` header (see [`ClientError::retry_delay`]), that delay is
+//! used instead of the backoff delay, capped at
+//! [`max_delay`](RetryPolicy::max_delay).
 
+use std::collections::HashMap;
 use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
 
+use super::retry_budget::RetryBudget;
 use crate::ClientError;
 use connectrpc_axum_core::Code;
 
@@ -108,6 +118,31 @@ pub struct RetryPolicy {
 
     /// Maximum number of retry attempts (not counting the initial request).
     pub max_retries: u32,
+
+    /// Called before each retry.
+    on_retry: Option<RetryCallback>,
+}
+
+/// A failed attempt that is about to be retried, passed to
+/// [`RetryPolicy::on_retry`].
+#[derive(Debug)]
+pub struct RetryEvent<'a> {
+    /// The attempt that failed, starting at 1.
+    pub attempt: u32,
+    /// The error that failed it.
+    pub error: &'a ClientError,
+    /// How long the next attempt waits.
+    pub delay: Duration,
+}
+
+/// Callback set with [`RetryPolicy::on_retry`].
+#[derive(Clone)]
+struct RetryCallback(Arc<dyn Fn(&RetryEvent<'_>) + Send + Sync>);
+
+impl std::fmt::Debug for RetryCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RetryCallback")
+    }
 }
 
 impl Default for RetryPolicy {
@@ -118,6 +153,7 @@ impl Default for RetryPolicy {
             jitter: defaults::JITTER,
             max_delay: defaults::MAX_DELAY,
             max_retries: defaults::MAX_RETRIES,
+            on_retry: None,
         }
     }
 }
@@ -211,6 +247,30 @@ impl RetryPolicy {
         self
     }
 
+    /// Set a callback to run before each retry, e.g. for logging or metrics.
+    ///
+    /// The callback runs after a retryable failure, before the backoff sleep.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use connectrpc_axum_client::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new().on_retry(|event| {
+    ///     eprintln!(
+    ///         "attempt {} failed: {}; retrying in {:?}",
+    ///         event.attempt, event.error, event.delay
+    ///     );
+    /// });
+    /// ```
+    pub fn on_retry<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&RetryEvent<'_>) + Send + Sync + 'static,
+    {
+        self.on_retry = Some(RetryCallback(Arc::new(callback)));
+        self
+    }
+
     /// Validate the policy configuration.
     ///
     /// Returns an error if the configuration is invalid.
@@ -298,7 +358,7 @@ impl ExponentialBackoff {
         // Apply jitter: delay * (1 + jitter * random(-1, 1))
         let jittered = if self.policy.jitter > 0.0 {
             let jitter_range = self.policy.jitter * 2.0;
-            let random_factor = rand::random::<f64>() * jitter_range - self.policy.jitter;
+            let random_factor = crate::rt::random() * jitter_range - self.policy.jitter;
             delay * (1.0 + random_factor)
         } else {
             delay
@@ -372,6 +432,23 @@ pub async fn retry_with_policy<F, Fut, T>(policy: &RetryPolicy, f: F) -> Result<
 where
     F: Fn() -> Fut,
     Fut: Future<Output = Result<T, ClientError>>,
+{
+    retry_attempts(policy, None, |_, _| f()).await
+}
+
+/// Run `f` under `policy`, passing it the attempt number (starting at 1) and
+/// the error that failed the previous attempt.
+///
+/// With a `budget`, the call is recorded in it and each retry must be paid
+/// for; once the budget is spent, the last error is returned.
+pub(crate) async fn retry_attempts<F, Fut, T>(
+    policy: &RetryPolicy,
+    budget: Option<&RetryBudget>,
+    mut f: F,
+) -> Result<T, ClientError>
+where
+    F: FnMut(u32, Option<ClientError>) -> Fut,
+    Fut: Future<Output = Result<T, ClientError>>,
 {
     // Validate policy configuration
     if let Err(msg) = policy.validate() {
@@ -379,26 +456,91 @@ where
     }
 
     let mut backoff = policy.backoff();
+    let mut previous_error = None;
+    if let Some(budget) = budget {
+        budget.deposit();
+    }
 
     loop {
-        match f().await {
+        let attempt = backoff.attempts() + 1;
+        match f(attempt, previous_error.take()).await {
             Ok(result) => return Ok(result),
-            Err(e) if e.is_retryable() && backoff.can_retry() => {
-                let delay = backoff.next_delay();
-                #[cfg(feature = "tracing")]
-                tracing::debug!(
-                    error = %e,
-                    attempt = backoff.attempts(),
-                    delay_ms = delay.as_millis(),
-                    "retrying after transient error"
-                );
-                tokio::time::sleep(delay).await;
-            }
-            Err(e) => return Err(e),
+            Err(e) => match next_retry(policy, &mut backoff, budget, &e) {
+                Some(delay) => {
+                    crate::rt::sleep(delay).await;
+                    previous_error = Some(e);
+                }
+                None => return Err(e),
+            },
         }
     }
 }
 
+/// The delay before retrying after `e` failed an attempt, or `None` if it
+/// can't be retried.
+///
+/// Advances `backoff`, pays for the retry from `budget` and runs the
+/// [`RetryPolicy::on_retry`] callback.
+pub(crate) fn next_retry(
+    policy: &RetryPolicy,
+    backoff: &mut ExponentialBackoff,
+    budget: Option<&RetryBudget>,
+    e: &ClientError,
+) -> Option<Duration> {
+    if !e.is_retryable() || !backoff.can_retry() {
+        return None;
+    }
+    let attempt = backoff.attempts() + 1;
+    if !budget.is_none_or(RetryBudget::withdraw) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(error = %e, attempt, "retry budget exhausted");
+        return None;
+    }
+    // Server guidance replaces the backoff delay, within max_delay
+    let backoff_delay = backoff.next_delay();
+    let delay = e
+        .retry_delay()
+        .map_or(backoff_delay, |delay| delay.min(policy.max_delay));
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        error = %e,
+        attempt = backoff.attempts(),
+        delay_ms = delay.as_millis(),
+        "retrying after transient error"
+    );
+    if let Some(callback) = &policy.on_retry {
+        (callback.0)(&RetryEvent {
+            attempt,
+            error: e,
+            delay,
+        });
+    }
+    Some(delay)
+}
+
+/// Retry settings of a client, set on the builder.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RetryConfig {
+    /// Policy for methods without one of their own.
+    pub(crate) default: Option<RetryPolicy>,
+    /// Policies by procedure (`pkg.Service/Method`) or service (`pkg.Service`).
+    pub(crate) methods: HashMap<String, RetryPolicy>,
+    /// Budget shared by all retries of the client.
+    pub(crate) budget: Option<RetryBudget>,
+}
+
+impl RetryConfig {
+    /// The policy for `procedure`: its own, its service's, or the default.
+    pub(crate) fn policy_for(&self, procedure: &str) -> Option<&RetryPolicy> {
+        let procedure = procedure.trim_start_matches('/');
+        let service = procedure.split_once('/').map(|(service, _)| service);
+        self.methods
+            .get(procedure)
+            .or_else(|| service.and_then(|service| self.methods.get(service)))
+            .or(self.default.as_ref())
+    }
+}
+
 /// Extension trait for adding retry capabilities to clients.
 ///
 /// This trait is not yet implemented but reserved for future use
@@ -652,4 +794,126 @@ mod tests {
         // Initial attempt + 2 retries = 3 total
         assert_eq!(attempts.load(Ordering::SeqCst), 3);
     }
+
+    #[tokio::test]
+    async fn test_retry_attempts_and_on_retry() {
+        use std::sync::Mutex;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let policy = RetryPolicy::new()
+            .max_retries(2)
+            .base_delay(Duration::from_millis(1))
+            .multiplier(1.0)
+            .jitter(0.0)
+            .on_retry(move |event| {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((event.attempt, event.error.code(), event.delay));
+            });
+
+        let mut seen = Vec::new();
+        let result = retry_attempts(&policy, None, |attempt, previous| {
+            seen.push((attempt, previous.map(|e| e.code())));
+            async move {
+                if attempt < 3 {
+                    Err(ClientError::unavailable("temporary failure"))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(
+            seen,
+            vec![
+                (1, None),
+                (2, Some(Code::Unavailable)),
+                (3, Some(Code::Unavailable)),
+            ]
+        );
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                (1, Code::Unavailable, Duration::from_millis(1)),
+                (2, Code::Unavailable, Duration::from_millis(1)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_honors_server_retry_delay() {
+        use connectrpc_axum_core::ErrorDetail;
+        use connectrpc_axum_core::error_details::RetryInfo;
+        use std::sync::Mutex;
+
+        let delays = Arc::new(Mutex::new(Vec::new()));
+        let recorded = delays.clone();
+        let policy = RetryPolicy::new()
+            .max_retries(2)
+            .base_delay(Duration::from_millis(1))
+            .max_delay(Duration::from_millis(20))
+            .jitter(0.0)
+            .on_retry(move |event| recorded.lock().unwrap().push(event.delay));
+
+        // The second delay asked for is capped at max_delay
+        let asked = [Duration::from_millis(5), Duration::from_secs(3600)];
+        let result = retry_attempts(&policy, None, |attempt, _| async move {
+            match asked.get(attempt as usize - 1) {
+                Some(&delay) => Err(ClientError::unavailable("slow down")
+                    .add_error_detail(ErrorDetail::from_message(&RetryInfo::new(delay)))),
+                None => Ok(()),
+            }
+        })
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            *delays.lock().unwrap(),
+            vec![Duration::from_millis(5), Duration::from_millis(20)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_attempts_with_budget() {
+        let policy = RetryPolicy::new()
+            .max_retries(5)
+            .base_delay(Duration::from_millis(1))
+            .jitter(0.0);
+        let budget = RetryBudget::new(0.0).burst(2);
+
+        let mut attempts = 0;
+        let result = retry_attempts(&policy, Some(&budget), |_, _| {
+            attempts += 1;
+            async { Err::<(), _>(ClientError::unavailable("down")) }
+        })
+        .await;
+        assert_eq!(result.unwrap_err().code(), Code::Unavailable);
+        assert_eq!(attempts, 3);
+        assert_eq!(budget.tokens(), 0.0);
+    }
+
+    #[test]
+    fn test_retry_config_policy_for() {
+        let mut config = RetryConfig::default();
+        assert!(config.policy_for("pkg.Service/Method").is_none());
+
+        config.default = Some(RetryPolicy::new().max_retries(1));
+        config
+            .methods
+            .insert("pkg.Service".into(), RetryPolicy::new().max_retries(2));
+        config.methods.insert(
+            "pkg.Service/Method".into(),
+            RetryPolicy::new().max_retries(3),
+        );
+
+        let max_retries = |procedure| config.policy_for(procedure).unwrap().max_retries;
+        assert_eq!(max_retries("pkg.Service/Method"), 3);
+        assert_eq!(max_retries("/pkg.Service/Method"), 3);
+        assert_eq!(max_retries("pkg.Service/Other"), 2);
+        assert_eq!(max_retries("pkg.Other/Method"), 1);
+    }
 }
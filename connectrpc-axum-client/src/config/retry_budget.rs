@@ -0,0 +1,131 @@
+//! Retry budget shared by the retries of a client.
+//!
+//! Retrying every failed call is dangerous when a backend is overloaded:
+//! each retry adds load, and a fleet of clients retrying at once turns a
+//! slowdown into an outage. A [`RetryBudget`] caps retries at a fraction of
+//! the calls a client makes, so built-in retries can be enabled client-wide
+//! safely.
+//!
+//! ```ignore
+//! use connectrpc_axum_client::{ConnectClient, RetryBudget, RetryPolicy};
+//!
+//! let client = ConnectClient::builder("http://localhost:3000")
+//!     .retry_policy(RetryPolicy::default())
+//!     // Retries may add at most 10% to the calls made
+//!     .retry_budget(RetryBudget::new(0.1))
+//!     .build()?;
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+/// Default fraction of calls that may be retried.
+pub const DEFAULT_RETRY_RATIO: f64 = 0.2;
+
+/// Default number of retries that may be made in a burst.
+pub const DEFAULT_RETRY_BURST: u32 = 10;
+
+/// Limits retries to a fraction of the calls made.
+///
+/// The budget is a bucket of retry tokens. Every call adds
+/// [`ratio`](Self::new) tokens, up to [`burst`](Self::burst), and every
+/// retry spends one. A retry that finds less than one token is not made;
+/// the call fails with the error of its last attempt instead.
+///
+/// The budget is cheap to clone; clones share their tokens, so one budget
+/// can cap the retries of several clients.
+///
+/// # Default Values
+///
+/// - `ratio`: 0.2 (retries add at most 20% to the calls made)
+/// - `burst`: 10 retries
+#[derive(Clone, Debug)]
+pub struct RetryBudget {
+    ratio: f64,
+    burst: u32,
+    /// Retry tokens left.
+    tokens: Arc<Mutex<f64>>,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETRY_RATIO)
+    }
+}
+
+impl RetryBudget {
+    /// Create a budget that lets `ratio` retries be made per call.
+    ///
+    /// The budget starts full, with [`burst`](Self::burst) tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not between 0.0 and 1.0.
+    pub fn new(ratio: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&ratio),
+            "ratio must be between 0.0 and 1.0"
+        );
+        Self {
+            ratio,
+            burst: DEFAULT_RETRY_BURST,
+            tokens: Arc::new(Mutex::new(DEFAULT_RETRY_BURST as f64)),
+        }
+    }
+
+    /// Set how many retries can be made in a burst, e.g. right after the
+    /// client starts or after a quiet period. Refills the budget.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self.tokens = Arc::new(Mutex::new(burst as f64));
+        self
+    }
+
+    /// Retry tokens left; a retry needs one.
+    pub fn tokens(&self) -> f64 {
+        *self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record a call, adding `ratio` tokens.
+    pub(crate) fn deposit(&self) {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        *tokens = (*tokens + self.ratio).min(self.burst as f64);
+    }
+
+    /// Spend a token on a retry. Returns `false` if the budget is spent.
+    pub(crate) fn withdraw(&self) -> bool {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        if *tokens < 1.0 {
+            return false;
+        }
+        *tokens -= 1.0;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_budget() {
+        let budget = RetryBudget::new(0.5).burst(2);
+        let shared = budget.clone();
+
+        // The burst is spent first
+        assert!(budget.withdraw());
+        assert!(shared.withdraw());
+        assert!(!budget.withdraw());
+
+        // Then every two calls earn one retry
+        budget.deposit();
+        assert!(!budget.withdraw());
+        shared.deposit();
+        assert!(budget.withdraw());
+
+        // Deposits stop at the burst size
+        for _ in 0..10 {
+            budget.deposit();
+        }
+        assert_eq!(budget.tokens(), 2.0);
+    }
+}
@@ -2,7 +2,13 @@
 //!
 //! This module provides [`ClientError`], the error type for Connect RPC client operations.
 
-use connectrpc_axum_core::{Code, EnvelopeError, ErrorDetail, Status};
+use std::time::Duration;
+
+use connectrpc_axum_core::error_details::RetryInfo;
+use connectrpc_axum_core::{Code, DecompressError, EnvelopeError, ErrorDetail, Status};
+use http::StatusCode;
+
+use crate::Metadata;
 
 /// Client-side Connect protocol error variants.
 ///
@@ -13,9 +19,10 @@ pub enum ClientError {
     /// RPC status error from the server.
     ///
     /// This variant wraps the core [`Status`] type which contains
-    /// the error code, message, and optional details.
+    /// the error code, message, and optional details, along with the HTTP
+    /// response the error was read from, if any.
     #[error("{0}")]
-    Rpc(Status),
+    Rpc(Status, Option<Box<ErrorResponse>>),
 
     /// Transport-level error (connection failed, timeout, etc.).
     #[error("transport error: {0}")]
@@ -34,15 +41,43 @@ pub enum ClientError {
     Protocol(String),
 }
 
+/// The HTTP response an [`ClientError::Rpc`] error was read from.
+///
+/// Kept for responses with a non-success HTTP status, so error handling can
+/// look at the status and headers such as `WWW-Authenticate` or rate limits.
+#[derive(Clone, Debug)]
+pub struct ErrorResponse {
+    status: StatusCode,
+    metadata: Metadata,
+}
+
+impl ErrorResponse {
+    /// Create the context of an error read from a response.
+    pub fn new(status: StatusCode, metadata: Metadata) -> Self {
+        Self { status, metadata }
+    }
+
+    /// The HTTP status of the response.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// The headers (and, for Connect, the `Trailer-` prefixed trailers) of
+    /// the response.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+}
+
 impl ClientError {
     /// Create a new status error with a code and message.
     pub fn new<S: Into<String>>(code: Code, message: S) -> Self {
-        ClientError::Rpc(Status::new(code, message))
+        ClientError::Rpc(Status::new(code, message), None)
     }
 
     /// Create a new status error with just a code.
     pub fn from_code(code: Code) -> Self {
-        ClientError::Rpc(Status::from_code(code))
+        ClientError::Rpc(Status::from_code(code), None)
     }
 
     /// Get the error code.
@@ -53,7 +88,7 @@ impl ClientError {
     /// - Protocol: `InvalidArgument`
     pub fn code(&self) -> Code {
         match self {
-            ClientError::Rpc(status) => status.code(),
+            ClientError::Rpc(status, _) => status.code(),
             ClientError::Transport(_) => Code::Unavailable,
             ClientError::Encode(_) | ClientError::Decode(_) => Code::Internal,
             ClientError::Protocol(_) => Code::InvalidArgument,
@@ -63,7 +98,7 @@ impl ClientError {
     /// Get the error message.
     pub fn message(&self) -> Option<&str> {
         match self {
-            ClientError::Rpc(status) => status.message(),
+            ClientError::Rpc(status, _) => status.message(),
             ClientError::Transport(msg)
             | ClientError::Encode(msg)
             | ClientError::Decode(msg)
@@ -74,15 +109,76 @@ impl ClientError {
     /// Get the error details (only for Rpc variant).
     pub fn details(&self) -> &[ErrorDetail] {
         match self {
-            ClientError::Rpc(status) => status.details(),
+            ClientError::Rpc(status, _) => status.details(),
             _ => &[],
         }
     }
 
+    /// Find the first detail of message type `M` and decode it.
+    ///
+    /// Always `None` for non-Rpc variants. See [`Status::find_detail`].
+    pub fn find_detail<M: prost::Message + prost::Name + Default>(&self) -> Option<M> {
+        self.status()?.find_detail()
+    }
+
+    /// Decode all details of message type `M`, like connect-go's
+    /// `Error.Details()` filtered by type.
+    ///
+    /// Details come from the JSON body of Connect errors or the
+    /// `grpc-status-details-bin` trailer of gRPC errors. Always empty for
+    /// non-Rpc variants. See [`Status::find_details`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use connectrpc_axum_client::error_details::{BadRequest, FieldViolation};
+    /// use connectrpc_axum_client::{ClientError, ErrorDetail};
+    ///
+    /// let violation = FieldViolation {
+    ///     field: "name".to_string(),
+    ///     description: "required".to_string(),
+    /// };
+    /// let err = ClientError::invalid_argument("bad request").add_error_detail(
+    ///     ErrorDetail::from_message(&BadRequest {
+    ///         field_violations: vec![violation],
+    ///     }),
+    /// );
+    ///
+    /// let bad_requests = err.find_details::<BadRequest>();
+    /// assert_eq!(bad_requests[0].field_violations[0].field, "name");
+    /// ```
+    pub fn find_details<M: prost::Message + prost::Name + Default>(&self) -> Vec<M> {
+        self.status().map(Status::find_details).unwrap_or_default()
+    }
+
+    /// How long the server asked the client to wait before retrying.
+    ///
+    /// Read from the `google.rpc.RetryInfo` detail, which the client also
+    /// fills in from a `Retry-After` response header. `None` if there is
+    /// no such detail or its delay is negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use connectrpc_axum_client::error_details::RetryInfo;
+    /// use connectrpc_axum_client::{ClientError, ErrorDetail};
+    /// use std::time::Duration;
+    ///
+    /// let err = ClientError::unavailable("overloaded")
+    ///     .add_error_detail(ErrorDetail::from_message(&RetryInfo::new(Duration::from_secs(2))));
+    /// assert_eq!(err.retry_delay(), Some(Duration::from_secs(2)));
+    /// ```
+    pub fn retry_delay(&self) -> Option<Duration> {
+        let info = self.find_detail::<RetryInfo>()?;
+        info.retry_delay?.try_into().ok()
+    }
+
     /// Add an error detail with type URL and protobuf-encoded bytes.
     pub fn add_detail<S: Into<String>>(self, type_url: S, value: Vec<u8>) -> Self {
         match self {
-            ClientError::Rpc(status) => ClientError::Rpc(status.add_detail(type_url, value)),
+            ClientError::Rpc(status, response) => {
+                ClientError::Rpc(status.add_detail(type_url, value), response)
+            }
             other => other,
         }
     }
@@ -90,7 +186,9 @@ impl ClientError {
     /// Add a pre-constructed ErrorDetail.
     pub fn add_error_detail(self, detail: ErrorDetail) -> Self {
         match self {
-            ClientError::Rpc(status) => ClientError::Rpc(status.add_error_detail(detail)),
+            ClientError::Rpc(status, response) => {
+                ClientError::Rpc(status.add_error_detail(detail), response)
+            }
             other => other,
         }
     }
@@ -98,7 +196,7 @@ impl ClientError {
     /// Get the inner Status if this is an Rpc error.
     pub fn status(&self) -> Option<&Status> {
         match self {
-            ClientError::Rpc(status) => Some(status),
+            ClientError::Rpc(status, _) => Some(status),
             _ => None,
         }
     }
@@ -106,56 +204,102 @@ impl ClientError {
     /// Convert into the inner Status if this is an Rpc error.
     pub fn into_status(self) -> Option<Status> {
         match self {
-            ClientError::Rpc(status) => Some(status),
+            ClientError::Rpc(status, _) => Some(status),
+            _ => None,
+        }
+    }
+
+    /// Get the HTTP response the error was read from, if any.
+    pub fn response(&self) -> Option<&ErrorResponse> {
+        match self {
+            ClientError::Rpc(_, response) => response.as_deref(),
             _ => None,
         }
     }
 
+    /// Get the HTTP status of the response the error was read from.
+    ///
+    /// Set for errors of responses with a non-success HTTP status; `None`
+    /// for errors sent in trailers or end-of-stream messages, and for
+    /// non-Rpc variants.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// match client.call_unary::<Req, Res>("pkg.Service/Method", &req).await {
+    ///     Err(err) if err.http_status() == Some(StatusCode::UNAUTHORIZED) => {
+    ///         let challenge = err.metadata().and_then(|m| m.get("www-authenticate"));
+    ///         // ...
+    ///     }
+    ///     // ...
+    /// }
+    /// ```
+    pub fn http_status(&self) -> Option<StatusCode> {
+        self.response().map(ErrorResponse::status)
+    }
+
+    /// Get the headers of the response the error was read from.
+    ///
+    /// Set whenever [`http_status`](Self::http_status) is.
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.response().map(ErrorResponse::metadata)
+    }
+
+    /// Attach the HTTP response an Rpc error was read from.
+    ///
+    /// Other variants are returned unchanged.
+    pub fn with_response(self, response: ErrorResponse) -> Self {
+        match self {
+            ClientError::Rpc(status, _) => ClientError::Rpc(status, Some(Box::new(response))),
+            other => other,
+        }
+    }
+
     // Convenience constructors
 
     /// Create an unimplemented error.
     pub fn unimplemented<S: Into<String>>(message: S) -> Self {
-        ClientError::Rpc(Status::unimplemented(message))
+        ClientError::Rpc(Status::unimplemented(message), None)
     }
 
     /// Create an invalid argument error.
     pub fn invalid_argument<S: Into<String>>(message: S) -> Self {
-        ClientError::Rpc(Status::invalid_argument(message))
+        ClientError::Rpc(Status::invalid_argument(message), None)
     }
 
     /// Create a not found error.
     pub fn not_found<S: Into<String>>(message: S) -> Self {
-        ClientError::Rpc(Status::not_found(message))
+        ClientError::Rpc(Status::not_found(message), None)
     }
 
     /// Create a permission denied error.
     pub fn permission_denied<S: Into<String>>(message: S) -> Self {
-        ClientError::Rpc(Status::permission_denied(message))
+        ClientError::Rpc(Status::permission_denied(message), None)
     }
 
     /// Create an unauthenticated error.
     pub fn unauthenticated<S: Into<String>>(message: S) -> Self {
-        ClientError::Rpc(Status::unauthenticated(message))
+        ClientError::Rpc(Status::unauthenticated(message), None)
     }
 
     /// Create an internal error.
     pub fn internal<S: Into<String>>(message: S) -> Self {
-        ClientError::Rpc(Status::internal(message))
+        ClientError::Rpc(Status::internal(message), None)
     }
 
     /// Create an unavailable error.
     pub fn unavailable<S: Into<String>>(message: S) -> Self {
-        ClientError::Rpc(Status::unavailable(message))
+        ClientError::Rpc(Status::unavailable(message), None)
     }
 
     /// Create a resource exhausted error.
     pub fn resource_exhausted<S: Into<String>>(message: S) -> Self {
-        ClientError::Rpc(Status::resource_exhausted(message))
+        ClientError::Rpc(Status::resource_exhausted(message), None)
     }
 
     /// Create a data loss error.
     pub fn data_loss<S: Into<String>>(message: S) -> Self {
-        ClientError::Rpc(Status::data_loss(message))
+        ClientError::Rpc(Status::data_loss(message), None)
     }
 
     /// Returns whether this error indicates a transient condition that may
@@ -185,7 +329,7 @@ impl ClientError {
 
 impl From<Status> for ClientError {
     fn from(status: Status) -> Self {
-        ClientError::Rpc(status)
+        ClientError::Rpc(status, None)
     }
 }
 
@@ -209,6 +353,18 @@ impl From<EnvelopeError> for ClientError {
     }
 }
 
+impl From<DecompressError> for ClientError {
+    fn from(err: DecompressError) -> Self {
+        match err {
+            DecompressError::Io(e) => ClientError::Decode(format!("decompression failed: {}", e)),
+            DecompressError::TooLarge { .. } => ClientError::new(
+                Code::ResourceExhausted,
+                "decompressed response exceeds limit",
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,13 +456,41 @@ mod tests {
         assert_eq!(status.unwrap().code(), Code::Internal);
     }
 
+    #[test]
+    fn test_client_error_response() {
+        let err = ClientError::unauthenticated("no token");
+        assert!(err.http_status().is_none());
+        assert!(err.metadata().is_none());
+
+        let mut headers = http::HeaderMap::new();
+        headers.insert("www-authenticate", "Bearer".parse().unwrap());
+        let err = err
+            .with_response(ErrorResponse::new(
+                StatusCode::UNAUTHORIZED,
+                Metadata::new(headers),
+            ))
+            .add_detail("test.Type", vec![1]);
+        assert_eq!(err.http_status(), Some(StatusCode::UNAUTHORIZED));
+        assert_eq!(
+            err.metadata().unwrap().get("www-authenticate").unwrap(),
+            "Bearer"
+        );
+
+        // Only Rpc errors carry a response
+        let err = ClientError::Transport("reset".into()).with_response(ErrorResponse::new(
+            StatusCode::BAD_GATEWAY,
+            Metadata::default(),
+        ));
+        assert!(err.http_status().is_none());
+    }
+
     #[test]
     fn test_client_error_rpc_variant_pattern_matching() {
         let err = ClientError::new(Code::NotFound, "user not found");
 
         // Users can pattern match on the Rpc variant
         match err {
-            ClientError::Rpc(status) => {
+            ClientError::Rpc(status, _) => {
                 assert_eq!(status.code(), Code::NotFound);
                 assert_eq!(status.message(), Some("user not found"));
             }
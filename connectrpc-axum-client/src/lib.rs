@@ -12,6 +12,9 @@
 //! - Both protobuf and JSON encoding support
 //! - Request compression (gzip, brotli, zstd)
 //! - Response decompression
+//! - gRPC and gRPC-Web protocol modes (`ClientBuilder::use_grpc()`, `use_grpc_web()`)
+//! - Bearer token authentication with cached refresh (`ClientBuilder::with_token_provider()`)
+//! - WebAssembly (`wasm32`) support for unary and server streaming calls
 //!
 //! ## Example
 //!
@@ -197,12 +200,15 @@
 //! }
 //!
 //! // Gracefully drain remaining messages (enables connection reuse)
-//! stream.drain().await;
+//! let drained = stream.drain().await;
 //!
 //! // Or with a timeout to prevent hanging
-//! stream.drain_timeout(Duration::from_secs(5)).await;
+//! let drained = stream.drain_timeout(Duration::from_secs(5)).await;
 //! ```
 //!
+//! The returned [`Drained`] carries the trailers and the error the stream
+//! ended with, so the final status of the RPC isn't lost.
+//!
 //! ## Feature Flags
 //!
 //! All features are opt-in. The default configuration enables JSON and Protobuf
@@ -226,6 +232,8 @@
 //! | Feature | Description | Dependencies |
 //! |---------|-------------|--------------|
 //! | `tracing` | Tracing spans for RPC calls | `tracing` |
+//! | `opentelemetry` | W3C trace context headers (implies `tracing`) | `opentelemetry`, `tracing-opentelemetry` |
+//! | `metrics` | Call counts, latencies and message sizes | `metrics` |
 //!
 //! When enabled, each RPC call creates a span with:
 //! - `rpc.method`: Full procedure name (e.g., "package.Service/Method")
@@ -233,6 +241,14 @@
 //! - `rpc.encoding`: Message encoding ("json" or "proto")
 //! - `otel.kind`: "client"
 //!
+//! With `opentelemetry`, requests also carry the `traceparent` and
+//! `tracestate` headers of that span's OpenTelemetry context, as recorded by
+//! a `tracing-opentelemetry` layer.
+//!
+//! With `metrics`, every call records `rpc_client_*` counters and histograms
+//! through the [`metrics`](https://docs.rs/metrics) facade, labelled by
+//! service, method and protocol, like the server's `MetricsLayer`.
+//!
 //! ### Example: Common Configurations
 //!
 //! ```toml
@@ -432,43 +448,37 @@
 //!
 //! ## TLS Configuration
 //!
-//! The client uses [rustls](https://docs.rs/rustls) for TLS by default (via reqwest).
-//! For advanced TLS configuration, provide a pre-configured `reqwest::Client`:
+//! The client uses [rustls](https://docs.rs/rustls) for TLS. Use
+//! [`ClientBuilder::with_tls_config`] with a [`TlsClientConfig`] for common
+//! TLS customizations. Certificates and keys may be PEM or DER.
 //!
 //! ### Custom Root Certificates
 //!
 //! ```ignore
-//! use std::fs;
-//!
-//! // Load a custom CA certificate
-//! let cert = fs::read("ca.pem")?;
-//! let cert = reqwest::Certificate::from_pem(&cert)?;
+//! use connectrpc_axum_client::{ConnectClient, TlsClientConfig};
 //!
-//! let http_client = reqwest::Client::builder()
-//!     .add_root_certificate(cert)
-//!     .build()?;
+//! // Trust a private CA in addition to the default roots
+//! let ca = std::fs::read("ca.pem")?;
 //!
 //! let client = ConnectClient::builder("https://internal-service:3000")
-//!     .client(http_client)
+//!     .with_tls_config(TlsClientConfig::new().add_root_certificate(&ca))
 //!     .build()?;
 //! ```
 //!
 //! ### Client Certificates (mTLS)
 //!
 //! ```ignore
-//! use std::fs;
+//! use connectrpc_axum_client::{ConnectClient, TlsClientConfig, TlsVersion};
 //!
-//! // Load client certificate and private key
-//! let cert = fs::read("client.pem")?;
-//! let key = fs::read("client-key.pem")?;
-//! let identity = reqwest::Identity::from_pem(&[cert, key].concat())?;
-//!
-//! let http_client = reqwest::Client::builder()
-//!     .identity(identity)
-//!     .build()?;
+//! let cert = std::fs::read("client.pem")?;
+//! let key = std::fs::read("client-key.pem")?;
 //!
 //! let client = ConnectClient::builder("https://mtls-server:3000")
-//!     .client(http_client)
+//!     .with_tls_config(
+//!         TlsClientConfig::new()
+//!             .identity(&cert, &key)
+//!             .min_tls_version(TlsVersion::Tls13),
+//!     )
 //!     .build()?;
 //! ```
 //!
@@ -476,15 +486,14 @@
 //!
 //! ```ignore
 //! // WARNING: Only use for development/testing!
-//! let http_client = reqwest::Client::builder()
-//!     .danger_accept_invalid_certs(true)
-//!     .build()?;
-//!
 //! let client = ConnectClient::builder("https://self-signed:3000")
-//!     .client(http_client)
+//!     .with_tls_config(TlsClientConfig::new().danger_accept_invalid_certs(true))
 //!     .build()?;
 //! ```
 //!
+//! For settings not covered by [`TlsClientConfig`], pass a [`RustlsClientConfig`]
+//! to [`ClientBuilder::tls_config`].
+//!
 //! ## Proxy Configuration
 //!
 //! Configure HTTP/HTTPS/SOCKS5 proxies via the underlying `reqwest::Client`:
@@ -551,73 +560,112 @@
 //!     .build()?;
 //! ```
 //!
-//! ## WASM Compatibility
-//!
-//! **Note:** This client does not currently support WebAssembly (wasm32) targets.
-//!
-//! ### Current Limitations
-//!
-//! The client relies on reqwest features that are not available in WASM:
+//! ## WASM Support
 //!
-//! 1. **Streaming bodies**: The `Body::wrap_stream` API used for client/bidi
-//!    streaming is not available in WASM's `fetch`-based implementation.
+//! On `wasm32-unknown-unknown` (browsers and web workers), enable the `wasm`
+//! feature and disable the default TLS features:
 //!
-//! 2. **HTTP/2 prior knowledge**: The `http2_prior_knowledge()` option is not
-//!    available in browser environments (browsers handle protocol negotiation).
-//!
-//! 3. **TCP keep-alive**: Low-level TCP options are not exposed in browser APIs.
-//!
-//! ### Unary Calls in WASM
+//! ```toml
+//! [target.'cfg(target_arch = "wasm32")'.dependencies]
+//! connectrpc-axum-client = { version = "0.1", default-features = false, features = ["wasm"] }
+//! ```
 //!
-//! For WASM targets that only need unary RPC calls, a future version may provide
-//! conditional compilation to exclude streaming methods. Contributions welcome.
+//! The client then sends requests with the `fetch` API through
+//! `FetchTransport`; the browser handles TLS,
+//! HTTP/2 and connection reuse. Timeouts and retry backoff use `setTimeout`.
 //!
-//! ### Alternatives for WASM
+//! Only unary and server-streaming calls are available, since `fetch` can't
+//! stream request bodies. `call_client_stream` and `call_bidi_stream`,
+//! `TransportBody::streaming`, and the hyper-specific builder options
+//! (`http2_prior_knowledge`, `pool_idle_timeout` and the TLS settings), are
+//! not compiled on `wasm32`, so using them there is a compile error.
 //!
-//! For browser-based Connect clients, consider:
-//! - [Connect-ES](https://connectrpc.com/docs/web/getting-started): Official
-//!   TypeScript/JavaScript client for browsers
-//! - [connect-query](https://connectrpc.com/docs/web/connect-query): TanStack
-//!   Query integration for React/Vue/Svelte
+//! For cross-origin requests, the server must allow the Connect headers in its
+//! CORS configuration and list response headers the client reads, such as
+//! custom metadata, in `Access-Control-Expose-Headers`.
 
+mod auth;
 mod builder;
+mod cache;
+mod cancel;
 mod client;
 pub mod config;
 mod error;
+mod metrics;
+mod negotiation;
+mod payload_log;
+mod protocol;
 pub mod request;
 pub mod response;
+mod rt;
+#[cfg(feature = "opentelemetry")]
+mod trace_context;
 pub mod transport;
 
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("connectrpc-axum-client requires the `wasm` feature on wasm32 targets");
+
+pub use auth::{
+    CachedTokenProvider, DEFAULT_REFRESH_BEFORE, Token, TokenInterceptor, TokenProvider,
+};
 pub use builder::{ClientBuildError, ClientBuilder};
-pub use client::ConnectClient;
-pub use error::ClientError;
+pub use cache::{
+    CacheStore, CachedResponse, DEFAULT_CACHE_CAPACITY, DEFAULT_CACHE_TTL, MemoryCacheStore,
+    ResponseCache,
+};
+pub use client::{ConnectClient, DEFAULT_USER_AGENT};
+pub use error::{ClientError, ErrorResponse};
+pub use negotiation::EncodingCache;
+#[cfg(feature = "tracing")]
+pub use payload_log::TracingPayloadLog;
+pub use payload_log::{PayloadDirection, PayloadLogRecord, PayloadLogSink, PayloadLogger};
+pub use protocol::Protocol;
 
 // Re-export from config module
 pub use config::{
-    BidiStreamInterceptors, CallOptions, Chain, ClientStreamInterceptors, ClosureInterceptor,
-    ExponentialBackoff, HeaderInterceptor, HeaderWrapper, Interceptor, InterceptorInternal,
-    MessageInterceptor, MessageWrapper, RequestContext, ResponseContext, RetryPolicy,
-    ServerStreamInterceptors, StreamContext, StreamType, TypedInterceptor, TypedMutInterceptor,
-    UnaryInterceptors, response_interceptor, retry, retry_with_policy, stream_interceptor,
+    AsyncInterceptor, AsyncWrapper, BidiStreamInterceptors, CallOptions, Chain, CircuitBreaker,
+    CircuitState, ClientStreamInterceptors, ClosureInterceptor, DEFAULT_RETRY_BURST,
+    DEFAULT_RETRY_RATIO, ExponentialBackoff, HeaderInterceptor, HeaderWrapper, HedgingPolicy,
+    Interceptor, InterceptorInternal, MessageInterceptor, MessageWrapper, RequestContext,
+    ResponseContext, RetryBudget, RetryEvent, RetryPolicy, ServerStreamInterceptors, StreamContext,
+    StreamType, TypedInterceptor, TypedMutInterceptor, UnaryInterceptors, hedge,
+    response_interceptor, retry, retry_with_policy, stream_interceptor,
 };
 
 // Re-export from request module
-pub use request::FrameEncoder;
+pub use request::{FrameEncoder, StreamSender};
 
 // Re-export from response module
 pub use response::{
-    ConnectResponse, FrameDecoder, InterceptingSendStream, InterceptingStream,
-    InterceptingStreaming, Metadata, Streaming, TypedReceiveStreaming,
+    ConnectResponse, DEFAULT_DECOMPRESSION_LIMIT, Drained, FrameDecoder, InterceptingSendStream,
+    InterceptingStream, InterceptingStreaming, MapOk, Metadata, Streaming, StreamingReader,
+    TryFilter, TypedReceiveStreaming,
 };
 
 // Re-export transport types at the top level for convenience
-pub use transport::{HyperTransport, HyperTransportBuilder, TlsClientConfig, TransportBody};
+pub use transport::{
+    BalancePolicy, Balancer, DefaultTransport, Endpoint, LeastInFlight, PickFirst, RoundRobin,
+    Transport, TransportBody,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use transport::{
+    CookieJar, CookieStore, HttpVersionPolicy, HyperTransport, HyperTransportBuilder,
+    LayeredTransport, LoopbackTransport, RustlsClientConfig, TlsClientConfig, TlsVersion,
+};
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub use transport::{FetchBody, FetchTransport};
 
 // Re-export core types that users need
 pub use connectrpc_axum_core::{
     Code, CompressionConfig, CompressionEncoding, CompressionLevel, ErrorDetail, Status,
 };
 
+/// Standard `google.rpc` error detail messages, decodable with [`ClientError::find_detail`].
+pub use connectrpc_axum_core::error_details;
+
 // Re-export types needed for generated streaming code
 pub use bytes::Bytes;
-pub use http::HeaderMap;
+pub use http::{HeaderMap, HeaderName, HeaderValue};
+
+/// Token for [`CallOptions::cancellation_token`].
+pub use tokio_util::sync::CancellationToken;
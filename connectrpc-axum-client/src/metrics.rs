@@ -0,0 +1,479 @@
+//! Client RPC metrics.
+//!
+//! With the `metrics` feature, every call records metrics through the
+//! [`metrics`](https://docs.rs/metrics) facade, so whichever exporter the
+//! application installs (e.g. `metrics-exporter-prometheus`) collects them:
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `rpc_client_started_total` | counter | `service`, `method`, `protocol` |
+//! | `rpc_client_handled_total` | counter | `service`, `method`, `protocol`, `code` |
+//! | `rpc_client_handling_seconds` | histogram | `service`, `method`, `protocol` |
+//! | `rpc_client_request_message_bytes` | histogram | `service`, `method`, `protocol` |
+//! | `rpc_client_response_message_bytes` | histogram | `service`, `method`, `protocol` |
+//! | `rpc_client_retries_total` | counter | `service`, `method`, `protocol` |
+//!
+//! They mirror the `rpc_server_*` metrics of the server's `MetricsLayer`.
+//! Each attempt of a retried or hedged call counts as a call. A streaming
+//! call is handled when its response stream ends, or `canceled` if the stream
+//! is dropped first. Message sizes are as sent on the wire, after
+//! compression.
+//!
+//! Without the feature, the same API does nothing.
+
+#[cfg(feature = "metrics")]
+pub(crate) use enabled::*;
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) use disabled::*;
+
+#[cfg(feature = "metrics")]
+mod enabled {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use metrics::{Label, counter, histogram};
+
+    use crate::Code;
+    use crate::protocol::Protocol;
+
+    const STARTED_TOTAL: &str = "rpc_client_started_total";
+    const HANDLED_TOTAL: &str = "rpc_client_handled_total";
+    const HANDLING_SECONDS: &str = "rpc_client_handling_seconds";
+    const REQUEST_MESSAGE_BYTES: &str = "rpc_client_request_message_bytes";
+    const RESPONSE_MESSAGE_BYTES: &str = "rpc_client_response_message_bytes";
+    const RETRIES_TOTAL: &str = "rpc_client_retries_total";
+
+    /// Flags of envelope frames that end a stream (Connect EndStream, gRPC-Web
+    /// trailers) rather than carry a message.
+    const END_FLAGS: u8 = 0x02 | 0x80;
+
+    /// Metrics of one call, shared by the call and its response stream.
+    #[derive(Clone, Debug)]
+    pub(crate) struct CallMetrics(Arc<Call>);
+
+    #[derive(Debug)]
+    struct Call {
+        labels: Vec<Label>,
+        start: Duration,
+        handled: AtomicBool,
+    }
+
+    impl CallMetrics {
+        /// Record the start of a call of `procedure`.
+        pub(crate) fn start(procedure: &str, protocol: Protocol) -> Self {
+            let path = procedure.trim_start_matches('/');
+            let (service, method) = path.rsplit_once('/').unwrap_or((path, ""));
+            let labels = vec![
+                Label::new("service", service.to_owned()),
+                Label::new("method", method.to_owned()),
+                Label::new("protocol", protocol.as_str()),
+            ];
+            counter!(STARTED_TOTAL, labels.iter()).increment(1);
+            Self(Arc::new(Call {
+                labels,
+                start: crate::rt::now(),
+                handled: AtomicBool::new(false),
+            }))
+        }
+
+        /// Record that the call retries a failed one.
+        pub(crate) fn retry(&self) {
+            counter!(RETRIES_TOTAL, self.0.labels.iter()).increment(1);
+        }
+
+        /// Record a request message of `len` bytes.
+        pub(crate) fn request_message(&self, len: usize) {
+            histogram!(REQUEST_MESSAGE_BYTES, self.0.labels.iter()).record(len as f64);
+        }
+
+        /// Record a response message of `len` bytes.
+        pub(crate) fn response_message(&self, len: usize) {
+            histogram!(RESPONSE_MESSAGE_BYTES, self.0.labels.iter()).record(len as f64);
+        }
+
+        /// Record the messages of a framed request body as it is sent.
+        #[cfg(not(target_arch = "wasm32"))]
+        pub(crate) fn request_frames(&self) -> FrameSizes {
+            FrameSizes::new(self.clone(), true)
+        }
+
+        /// Record the messages of a framed response body as it arrives.
+        pub(crate) fn response_frames(&self) -> FrameSizes {
+            FrameSizes::new(self.clone(), false)
+        }
+
+        /// Record the end of the call with `code`. Only the first end counts.
+        pub(crate) fn finish(&self, code: Code) {
+            self.0.finish(code);
+        }
+    }
+
+    impl Call {
+        fn finish(&self, code: Code) {
+            if self.handled.swap(true, Ordering::Relaxed) {
+                return;
+            }
+            let mut labels = self.labels.clone();
+            labels.push(Label::new("code", code.as_str()));
+            counter!(HANDLED_TOTAL, labels).increment(1);
+            let elapsed = crate::rt::now().saturating_sub(self.start);
+            histogram!(HANDLING_SECONDS, self.labels.iter()).record(elapsed);
+        }
+    }
+
+    impl Drop for Call {
+        fn drop(&mut self) {
+            // Neither the call nor its response stream got to the end
+            self.finish(Code::Canceled);
+        }
+    }
+
+    /// Splits a body into envelope frames, recording each message's size.
+    pub(crate) struct FrameSizes {
+        metrics: CallMetrics,
+        request: bool,
+        header: [u8; 5],
+        header_len: usize,
+        /// Bytes left of the current frame's payload.
+        remaining: usize,
+    }
+
+    impl FrameSizes {
+        fn new(metrics: CallMetrics, request: bool) -> Self {
+            Self {
+                metrics,
+                request,
+                header: [0; 5],
+                header_len: 0,
+                remaining: 0,
+            }
+        }
+
+        /// Scan the next chunk of the body.
+        pub(crate) fn data(&mut self, mut data: &[u8]) {
+            while !data.is_empty() {
+                if self.remaining > 0 {
+                    let skip = self.remaining.min(data.len());
+                    self.remaining -= skip;
+                    data = &data[skip..];
+                    continue;
+                }
+
+                let take = (5 - self.header_len).min(data.len());
+                self.header[self.header_len..self.header_len + take].copy_from_slice(&data[..take]);
+                self.header_len += take;
+                data = &data[take..];
+                if self.header_len < 5 {
+                    return;
+                }
+
+                self.header_len = 0;
+                let len = u32::from_be_bytes([
+                    self.header[1],
+                    self.header[2],
+                    self.header[3],
+                    self.header[4],
+                ]) as usize;
+                self.remaining = len;
+                if self.header[0] & END_FLAGS == 0 {
+                    if self.request {
+                        self.metrics.request_message(len);
+                    } else {
+                        self.metrics.response_message(len);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+mod disabled {
+    use crate::Code;
+    use crate::protocol::Protocol;
+
+    /// Metrics of one call; records nothing without the `metrics` feature.
+    #[derive(Clone, Debug)]
+    pub(crate) struct CallMetrics;
+
+    impl CallMetrics {
+        pub(crate) fn start(_procedure: &str, _protocol: Protocol) -> Self {
+            Self
+        }
+
+        pub(crate) fn retry(&self) {}
+
+        pub(crate) fn request_message(&self, _len: usize) {}
+
+        pub(crate) fn response_message(&self, _len: usize) {}
+
+        #[cfg(not(target_arch = "wasm32"))]
+        pub(crate) fn request_frames(&self) -> FrameSizes {
+            FrameSizes
+        }
+
+        pub(crate) fn response_frames(&self) -> FrameSizes {
+            FrameSizes
+        }
+
+        pub(crate) fn finish(&self, _code: Code) {}
+    }
+
+    pub(crate) struct FrameSizes;
+
+    impl FrameSizes {
+        pub(crate) fn data(&mut self, _data: &[u8]) {}
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::*;
+    use crate::{ClientError, ConnectClient, Transport, TransportBody};
+    use bytes::Bytes;
+    use futures::StreamExt;
+    use metrics::{
+        Counter, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder, SharedString,
+        Unit,
+    };
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Recorder that keeps counters and histogram samples by `name{labels}`.
+    #[derive(Default)]
+    struct TestRecorder {
+        counters: Mutex<HashMap<String, Arc<AtomicU64>>>,
+        histograms: Mutex<HashMap<String, Arc<Samples>>>,
+    }
+
+    #[derive(Default)]
+    struct Samples(Mutex<Vec<f64>>);
+
+    impl HistogramFn for Samples {
+        fn record(&self, value: f64) {
+            self.0.lock().unwrap().push(value);
+        }
+    }
+
+    fn key_string(key: &Key) -> String {
+        let labels: Vec<_> = key
+            .labels()
+            .map(|label| format!("{}={}", label.key(), label.value()))
+            .collect();
+        format!("{}{{{}}}", key.name(), labels.join(","))
+    }
+
+    impl TestRecorder {
+        fn counter(&self, key: &str) -> u64 {
+            self.counters
+                .lock()
+                .unwrap()
+                .get(key)
+                .map_or(0, |counter| counter.load(Ordering::Relaxed))
+        }
+
+        fn samples(&self, key: &str) -> Vec<f64> {
+            self.histograms
+                .lock()
+                .unwrap()
+                .get(key)
+                .map_or_else(Vec::new, |samples| samples.0.lock().unwrap().clone())
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let mut counters = self.counters.lock().unwrap();
+            Counter::from_arc(counters.entry(key_string(key)).or_default().clone())
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            let mut histograms = self.histograms.lock().unwrap();
+            Histogram::from_arc(histograms.entry(key_string(key)).or_default().clone())
+        }
+    }
+
+    fn frame(flags: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![flags];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    /// Answers `Echo` with a unary message, `Stream` with two messages, and
+    /// anything else with `unavailable`.
+    #[derive(Clone)]
+    struct StubTransport;
+
+    impl Transport for StubTransport {
+        type Body = http_body_util::Full<Bytes>;
+
+        async fn request(
+            &self,
+            request: http::Request<TransportBody>,
+        ) -> Result<http::Response<Self::Body>, ClientError> {
+            let response = http::Response::builder();
+            let response = match request.uri().path() {
+                "/test.v1.Svc/Echo" => response
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(Bytes::from_static(b"\"hi\"")),
+                "/test.v1.Svc/Stream" => {
+                    let mut body = frame(0, b"\"hey\"");
+                    body.extend(frame(0, b"\"hey\""));
+                    body.extend(frame(0x02, b"{}"));
+                    response
+                        .header(http::header::CONTENT_TYPE, "application/connect+json")
+                        .body(Bytes::from(body))
+                }
+                _ => response
+                    .status(http::StatusCode::SERVICE_UNAVAILABLE)
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(Bytes::from_static(b"{\"code\":\"unavailable\"}")),
+            };
+            Ok(response.unwrap().map(http_body_util::Full::new))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_rpc_metrics() {
+        let recorder = TestRecorder::default();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let client = ConnectClient::builder("http://stub")
+            .build_with_transport(StubTransport)
+            .unwrap();
+
+        client
+            .call_unary::<String, String>("test.v1.Svc/Echo", &"hi".to_string())
+            .await
+            .unwrap();
+        let stream = client
+            .call_server_stream::<String, String>("test.v1.Svc/Stream", &"hey".to_string())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(stream.count().await, 2);
+        client
+            .call_unary::<String, String>("test.v1.Svc/Missing", &String::new())
+            .await
+            .unwrap_err();
+
+        // A stream dropped before its end is canceled
+        let stream = client
+            .call_server_stream::<String, String>("test.v1.Svc/Stream", &"hey".to_string())
+            .await
+            .unwrap();
+        drop(stream);
+
+        let echo = "service=test.v1.Svc,method=Echo,protocol=connect";
+        let stream = "service=test.v1.Svc,method=Stream,protocol=connect";
+        let missing = "service=test.v1.Svc,method=Missing,protocol=connect";
+        assert_eq!(
+            recorder.counter(&format!("rpc_client_started_total{{{echo}}}")),
+            1
+        );
+        assert_eq!(
+            recorder.counter(&format!("rpc_client_handled_total{{{echo},code=ok}}")),
+            1
+        );
+        assert_eq!(
+            recorder.counter(&format!("rpc_client_handled_total{{{stream},code=ok}}")),
+            1
+        );
+        assert_eq!(
+            recorder.counter(&format!(
+                "rpc_client_handled_total{{{stream},code=canceled}}"
+            )),
+            1
+        );
+        assert_eq!(
+            recorder.counter(&format!(
+                "rpc_client_handled_total{{{missing},code=unavailable}}"
+            )),
+            1
+        );
+        assert_eq!(
+            recorder
+                .samples(&format!("rpc_client_handling_seconds{{{stream}}}"))
+                .len(),
+            2
+        );
+        assert_eq!(
+            recorder.samples(&format!("rpc_client_request_message_bytes{{{echo}}}")),
+            [4.0]
+        );
+        assert_eq!(
+            recorder.samples(&format!("rpc_client_response_message_bytes{{{echo}}}")),
+            [4.0]
+        );
+        assert_eq!(
+            recorder.samples(&format!("rpc_client_response_message_bytes{{{stream}}}")),
+            [5.0, 5.0]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_records_retries() {
+        let recorder = TestRecorder::default();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let client = ConnectClient::builder("http://stub")
+            .build_with_transport(StubTransport)
+            .unwrap();
+
+        let policy = crate::RetryPolicy::new()
+            .max_retries(2)
+            .base_delay(std::time::Duration::from_millis(1));
+        client
+            .call_unary_with_retry::<String, String>(
+                "test.v1.Svc/Missing",
+                &String::new(),
+                crate::CallOptions::new(),
+                &policy,
+            )
+            .await
+            .unwrap_err();
+
+        let missing = "service=test.v1.Svc,method=Missing,protocol=connect";
+        assert_eq!(
+            recorder.counter(&format!("rpc_client_started_total{{{missing}}}")),
+            3
+        );
+        assert_eq!(
+            recorder.counter(&format!("rpc_client_retries_total{{{missing}}}")),
+            2
+        );
+    }
+
+    #[test]
+    fn test_frame_sizes_split_chunks() {
+        let recorder = TestRecorder::default();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let metrics = CallMetrics::start("/test.v1.Svc/Stream", crate::Protocol::Grpc);
+        let mut body = frame(0, &[1; 3]);
+        body.extend(frame(1, &[2; 300]));
+        body.extend(frame(0x80, b"grpc-status: 0"));
+        let mut frames = metrics.response_frames();
+        for chunk in body.chunks(2) {
+            frames.data(chunk);
+        }
+        drop(frames);
+
+        assert_eq!(
+            recorder.samples(
+                "rpc_client_response_message_bytes{service=test.v1.Svc,method=Stream,protocol=grpc}"
+            ),
+            [3.0, 300.0]
+        );
+    }
+}
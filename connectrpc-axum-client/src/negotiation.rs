@@ -0,0 +1,244 @@
+//! Negotiation of request compression with the server.
+//!
+//! Servers list the encodings they accept for requests in each response:
+//! `Accept-Encoding` for Connect unary calls, `Connect-Accept-Encoding` for
+//! Connect streaming calls and `grpc-accept-encoding` for gRPC. An
+//! [`EncodingCache`] installed with
+//! [`ClientBuilder::negotiate_compression`](crate::ClientBuilder::negotiate_compression)
+//! remembers these lists per origin (scheme, host and port), and later calls
+//! to the origin compress their requests with the best encoding both sides
+//! support:
+//!
+//! - The configured [`request_encoding`](crate::ClientBuilder::request_encoding),
+//!   if the server accepts it.
+//! - Otherwise the first encoding of `zstd`, `br`, `gzip` and `deflate`
+//!   that is compiled in and accepted by the server.
+//! - Otherwise no compression.
+//!
+//! Until a response from the origin is seen, the configured encoding is used.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use connectrpc_axum_core::CompressionEncoding;
+use http::{HeaderMap, Uri};
+
+/// Built-in encodings, from most to least preferred.
+const PREFERRED_ENCODINGS: [&str; 4] = ["zstd", "br", "gzip", "deflate"];
+
+/// Encodings accepted by servers, remembered per origin.
+///
+/// Clones share their entries, so one cache can serve several clients.
+///
+/// # Example
+///
+/// ```ignore
+/// use connectrpc_axum_client::{ClientBuilder, EncodingCache};
+///
+/// let encodings = EncodingCache::new();
+/// let client = ClientBuilder::new("http://localhost:3000")
+///     .negotiate_compression(encodings.clone())
+///     .build()?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EncodingCache {
+    /// Encoding names accepted by each origin, in the server's order.
+    origins: Arc<Mutex<HashMap<String, Vec<String>>>>,
+}
+
+impl EncodingCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the encoding names accepted by `origin` (e.g.
+    /// `"https://api.example.com"`), if a response from it listed them.
+    pub fn accepted(&self, origin: &str) -> Option<Vec<String>> {
+        self.lock().get(origin).cloned()
+    }
+
+    /// Forget the encodings of all origins.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Vec<String>>> {
+        self.origins.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Remember the encodings listed by a response to `uri`, in the first of
+    /// the `header_names` present.
+    ///
+    /// Responses without the header leave the origin's entry unchanged.
+    pub(crate) fn record(&self, uri: &Uri, headers: &HeaderMap, header_names: &[&str]) {
+        let Some(origin) = origin(uri) else {
+            return;
+        };
+        let Some(value) = header_names
+            .iter()
+            .find_map(|name| headers.get(*name))
+            .and_then(|value| value.to_str().ok())
+        else {
+            return;
+        };
+        self.lock().insert(origin, parse_encodings(value));
+    }
+
+    /// Select the request encoding for a call to `uri`, given the
+    /// `configured` one.
+    pub(crate) fn select(&self, uri: &Uri, configured: CompressionEncoding) -> CompressionEncoding {
+        let Some(origin) = origin(uri) else {
+            return configured;
+        };
+        let origins = self.lock();
+        let Some(accepted) = origins.get(&origin) else {
+            return configured;
+        };
+        let accepts = |name: &str| accepted.iter().any(|accepted| accepted == name);
+
+        if !configured.is_identity() && accepts(configured.as_str()) {
+            return configured;
+        }
+        PREFERRED_ENCODINGS
+            .iter()
+            .filter(|name| accepts(name))
+            .filter_map(|name| CompressionEncoding::from_header(Some(name)))
+            .next()
+            .unwrap_or(CompressionEncoding::Identity)
+    }
+}
+
+/// The origin of `uri`: its scheme and authority.
+fn origin(uri: &Uri) -> Option<String> {
+    Some(format!("{}://{}", uri.scheme_str()?, uri.authority()?))
+}
+
+/// Parse an accept-encoding list, skipping encodings with `q=0`.
+fn parse_encodings(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .filter_map(|token| {
+            let mut params = token.split(';');
+            let name = params.next()?.trim().to_ascii_lowercase();
+            let rejected = params.any(|param| {
+                param
+                    .trim()
+                    .strip_prefix("q=")
+                    .and_then(|q| q.trim().parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+            (!name.is_empty() && !rejected).then_some(name)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uri(s: &str) -> Uri {
+        s.parse().unwrap()
+    }
+
+    fn headers(name: &'static str, value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_parse_encodings() {
+        assert_eq!(
+            parse_encodings("gzip, BR;q=0.5, zstd;q=0, , identity"),
+            vec!["gzip", "br", "identity"]
+        );
+    }
+
+    #[test]
+    fn test_record_per_origin() {
+        let cache = EncodingCache::new();
+        let service = uri("https://api.example.com/pkg.Service/Method");
+        cache.record(
+            &service,
+            &headers("accept-encoding", "gzip"),
+            &["accept-encoding"],
+        );
+        assert_eq!(
+            cache.accepted("https://api.example.com").unwrap(),
+            vec!["gzip"]
+        );
+        assert!(cache.accepted("http://api.example.com").is_none());
+
+        // Responses without the header keep the entry
+        cache.record(&service, &HeaderMap::new(), &["accept-encoding"]);
+        assert!(cache.accepted("https://api.example.com").is_some());
+
+        // The first header present wins
+        cache.record(
+            &service,
+            &headers("grpc-accept-encoding", "deflate"),
+            &["connect-accept-encoding", "grpc-accept-encoding"],
+        );
+        assert_eq!(
+            cache.accepted("https://api.example.com").unwrap(),
+            vec!["deflate"]
+        );
+
+        cache.clear();
+        assert!(cache.accepted("https://api.example.com").is_none());
+    }
+
+    #[test]
+    fn test_select() {
+        let cache = EncodingCache::new();
+        let service = uri("http://localhost:3000/pkg.Service/Method");
+        let identity = CompressionEncoding::Identity;
+
+        // Unknown origins use the configured encoding
+        assert_eq!(cache.select(&service, identity), identity);
+
+        // No encoding in common
+        cache.record(
+            &service,
+            &headers("accept-encoding", "lz4"),
+            &["accept-encoding"],
+        );
+        assert_eq!(cache.select(&service, identity), identity);
+    }
+
+    #[cfg(all(
+        feature = "compression-gzip-stream",
+        feature = "compression-zstd-stream"
+    ))]
+    #[test]
+    fn test_select_upgrades() {
+        let cache = EncodingCache::new();
+        let service = uri("http://localhost:3000/pkg.Service/Method");
+        cache.record(
+            &service,
+            &headers("accept-encoding", "gzip, zstd"),
+            &["accept-encoding"],
+        );
+
+        assert_eq!(
+            cache.select(&service, CompressionEncoding::Identity),
+            CompressionEncoding::Zstd
+        );
+        // The configured encoding is kept while the server accepts it
+        assert_eq!(
+            cache.select(&service, CompressionEncoding::Gzip),
+            CompressionEncoding::Gzip
+        );
+
+        cache.record(
+            &service,
+            &headers("accept-encoding", "zstd"),
+            &["accept-encoding"],
+        );
+        assert_eq!(
+            cache.select(&service, CompressionEncoding::Gzip),
+            CompressionEncoding::Zstd
+        );
+    }
+}
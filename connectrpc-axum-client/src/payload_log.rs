@@ -0,0 +1,479 @@
+//! Request and response payload logging.
+//!
+//! [`PayloadLogger`] is a [`MessageInterceptor`] that serializes the
+//! messages of a call (unary or streaming, in either direction) to JSON and
+//! hands them to a [`PayloadLogSink`] as [`PayloadLogRecord`]s. Fields that may
+//! carry credentials or personal data are replaced by `"***"` before the
+//! sink sees them, and logging can be limited to some procedures.
+//!
+//! Interceptors see response messages as `DeserializeOwned` only, so
+//! response types to log are registered with
+//! [`PayloadLogger::response`]:
+//!
+//! ```ignore
+//! use connectrpc_axum_client::{PayloadLogger, TracingPayloadLog};
+//!
+//! let logger = PayloadLogger::new(TracingPayloadLog)
+//!     .response::<GetUserResponse>()
+//!     .redact("password")
+//!     .redact("user.email")
+//!     .skip("auth.v1.AuthService/Login");
+//!
+//! let client = ConnectClient::builder("http://localhost:3000")
+//!     .with_message_interceptor(logger)
+//!     .build()?;
+//! ```
+//!
+//! Logging never fails a call: a message that can't be serialized is skipped.
+
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use prost::Message;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::ClientError;
+use crate::config::{
+    MessageInterceptor, RequestContext, ResponseContext, StreamContext, StreamType,
+};
+
+/// Value that replaces redacted fields.
+const REDACTED: &str = "***";
+
+/// Whether a logged message was sent or received.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayloadDirection {
+    /// A request message sent to the server.
+    Request,
+    /// A response message received from the server.
+    Response,
+}
+
+/// One logged message.
+#[derive(Debug, Clone)]
+pub struct PayloadLogRecord {
+    /// The procedure being called (e.g., "package.Service/Method").
+    pub procedure: String,
+    /// Whether the message was sent or received.
+    pub direction: PayloadDirection,
+    /// The type of stream the message belongs to, or `None` for unary calls.
+    pub stream_type: Option<StreamType>,
+    /// The message as JSON, with redacted fields replaced by `"***"`.
+    pub message: Value,
+}
+
+/// Destination of payload log records.
+///
+/// Implemented for closures taking a `&PayloadLogRecord`.
+pub trait PayloadLogSink: Send + Sync + 'static {
+    /// Record one message.
+    fn log(&self, record: &PayloadLogRecord);
+}
+
+impl<F> PayloadLogSink for F
+where
+    F: Fn(&PayloadLogRecord) + Send + Sync + 'static,
+{
+    fn log(&self, record: &PayloadLogRecord) {
+        self(record)
+    }
+}
+
+/// Sink that emits each record as a `DEBUG` event with target
+/// `connectrpc_axum_client::payload`.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracingPayloadLog;
+
+#[cfg(feature = "tracing")]
+impl PayloadLogSink for TracingPayloadLog {
+    fn log(&self, record: &PayloadLogRecord) {
+        let direction = match record.direction {
+            PayloadDirection::Request => "request",
+            PayloadDirection::Response => "response",
+        };
+        tracing::debug!(
+            target: "connectrpc_axum_client::payload",
+            procedure = %record.procedure,
+            direction,
+            message = %record.message,
+            "rpc message"
+        );
+    }
+}
+
+/// Serializes a message of the type it was registered for.
+type ToJson = fn(&dyn Any) -> Option<Value>;
+
+/// Interceptor that logs request and response messages as JSON.
+///
+/// Install it with
+/// [`ClientBuilder::with_message_interceptor`](crate::ClientBuilder::with_message_interceptor).
+/// See the [module documentation](self) for an example.
+#[derive(Clone)]
+pub struct PayloadLogger {
+    sink: Arc<dyn PayloadLogSink>,
+    /// Serializers of the response types to log.
+    responses: Arc<HashMap<TypeId, ToJson>>,
+    /// Field paths to redact, split into field names.
+    redact: Arc<Vec<Vec<String>>>,
+    /// Procedures to log; all of them if empty.
+    only: Arc<HashSet<String>>,
+    /// Procedures never to log.
+    skip: Arc<HashSet<String>>,
+}
+
+impl PayloadLogger {
+    /// Create a logger that sends every message to `sink`.
+    pub fn new(sink: impl PayloadLogSink) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            responses: Arc::default(),
+            redact: Arc::default(),
+            only: Arc::default(),
+            skip: Arc::default(),
+        }
+    }
+
+    /// Log response messages of type `T`.
+    ///
+    /// Request messages are always logged, but response messages only reach
+    /// interceptors as `DeserializeOwned`, so each response type to log has
+    /// to be registered. Responses of other types are not logged.
+    pub fn response<T: Serialize + 'static>(mut self) -> Self {
+        fn to_json<T: Serialize + 'static>(message: &dyn Any) -> Option<Value> {
+            serde_json::to_value(message.downcast_ref::<T>()?).ok()
+        }
+        Arc::make_mut(&mut self.responses).insert(TypeId::of::<T>(), to_json::<T>);
+        self
+    }
+
+    /// Replace the field at `path` with `"***"` in logged messages.
+    ///
+    /// `path` is a dot-separated list of JSON field names, e.g.
+    /// `"user.password"`, so it uses the message's JSON names (usually
+    /// `lowerCamelCase` for generated types). Repeated fields are walked
+    /// into, so `"users.email"` redacts the email of every user. Applies
+    /// to requests and responses alike.
+    pub fn redact(mut self, path: &str) -> Self {
+        let path = path.split('.').map(str::to_owned).collect();
+        Arc::make_mut(&mut self.redact).push(path);
+        self
+    }
+
+    /// Log only `procedure` (e.g. `"package.Service/Method"`) and the others
+    /// passed to `only`.
+    ///
+    /// By default, every procedure is logged.
+    pub fn only(mut self, procedure: &str) -> Self {
+        Arc::make_mut(&mut self.only).insert(normalize(procedure).to_owned());
+        self
+    }
+
+    /// Never log `procedure` (e.g. `"package.Service/Method"`), even if
+    /// passed to [`only`](Self::only).
+    pub fn skip(mut self, procedure: &str) -> Self {
+        Arc::make_mut(&mut self.skip).insert(normalize(procedure).to_owned());
+        self
+    }
+
+    /// Returns `true` if messages of `procedure` are logged.
+    pub fn is_logged(&self, procedure: &str) -> bool {
+        let procedure = normalize(procedure);
+        (self.only.is_empty() || self.only.contains(procedure)) && !self.skip.contains(procedure)
+    }
+
+    /// Log a request message if `procedure` is logged.
+    fn log_request<M: Serialize + 'static>(
+        &self,
+        procedure: &str,
+        stream_type: Option<StreamType>,
+        message: &M,
+    ) {
+        // Streaming calls pass a unit placeholder to `on_request`; their
+        // messages are logged one by one as they are sent
+        if TypeId::of::<M>() == TypeId::of::<()>() || !self.is_logged(procedure) {
+            return;
+        }
+        if let Ok(message) = serde_json::to_value(message) {
+            self.log(procedure, PayloadDirection::Request, stream_type, message);
+        }
+    }
+
+    /// Log a response message if its type is registered and `procedure` is
+    /// logged.
+    fn log_response<M: 'static>(
+        &self,
+        procedure: &str,
+        stream_type: Option<StreamType>,
+        message: &M,
+    ) {
+        let Some(to_json) = self.responses.get(&TypeId::of::<M>()) else {
+            return;
+        };
+        if !self.is_logged(procedure) {
+            return;
+        }
+        if let Some(message) = to_json(message) {
+            self.log(procedure, PayloadDirection::Response, stream_type, message);
+        }
+    }
+
+    /// Redact `message` and send it to the sink.
+    fn log(
+        &self,
+        procedure: &str,
+        direction: PayloadDirection,
+        stream_type: Option<StreamType>,
+        mut message: Value,
+    ) {
+        for path in self.redact.iter() {
+            redact(&mut message, path);
+        }
+        self.sink.log(&PayloadLogRecord {
+            procedure: normalize(procedure).to_owned(),
+            direction,
+            stream_type,
+            message,
+        });
+    }
+}
+
+impl std::fmt::Debug for PayloadLogger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadLogger")
+            .field("responses", &self.responses.len())
+            .field("redact", &self.redact)
+            .field("only", &self.only)
+            .field("skip", &self.skip)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MessageInterceptor for PayloadLogger {
+    fn on_request<Req>(
+        &self,
+        ctx: &mut RequestContext,
+        request: &mut Req,
+    ) -> Result<(), ClientError>
+    where
+        Req: Message + Serialize + 'static,
+    {
+        self.log_request(ctx.procedure, None, request);
+        Ok(())
+    }
+
+    fn on_response<Res>(&self, ctx: &ResponseContext, response: &mut Res) -> Result<(), ClientError>
+    where
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        self.log_response(ctx.procedure, None, response);
+        Ok(())
+    }
+
+    fn on_stream_send<Req>(&self, ctx: &StreamContext, request: &mut Req) -> Result<(), ClientError>
+    where
+        Req: Message + Serialize + 'static,
+    {
+        self.log_request(ctx.procedure, Some(ctx.stream_type), request);
+        Ok(())
+    }
+
+    fn on_stream_receive<Res>(
+        &self,
+        ctx: &StreamContext,
+        response: &mut Res,
+    ) -> Result<(), ClientError>
+    where
+        Res: Message + DeserializeOwned + Default + 'static,
+    {
+        self.log_response(ctx.procedure, Some(ctx.stream_type), response);
+        Ok(())
+    }
+}
+
+/// Strip the leading slash of a procedure path.
+fn normalize(procedure: &str) -> &str {
+    procedure.strip_prefix('/').unwrap_or(procedure)
+}
+
+/// Replace the field at `path` in `value` with `"***"`.
+fn redact(value: &mut Value, path: &[String]) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                redact(item, path);
+            }
+        }
+        Value::Object(fields) => {
+            let Some((name, rest)) = path.split_first() else {
+                return;
+            };
+            let Some(field) = fields.get_mut(name) else {
+                return;
+            };
+            if rest.is_empty() {
+                *field = Value::String(REDACTED.to_owned());
+            } else {
+                redact(field, rest);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectClient, Transport, TransportBody};
+    use bytes::Bytes;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Sink that keeps every record.
+    fn recording() -> (PayloadLogger, Arc<Mutex<Vec<PayloadLogRecord>>>) {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = records.clone();
+        let logger = PayloadLogger::new(move |record: &PayloadLogRecord| {
+            sink.lock().unwrap().push(record.clone());
+        });
+        (logger, records)
+    }
+
+    #[test]
+    fn test_redact() {
+        let mut message = json!({
+            "password": "hunter2",
+            "user": {"name": "ann", "email": "ann@example.com"},
+            "users": [{"email": "bob@example.com"}, {"name": "eve"}],
+        });
+        redact(&mut message, &["password".to_owned()]);
+        redact(&mut message, &["user".to_owned(), "email".to_owned()]);
+        redact(&mut message, &["users".to_owned(), "email".to_owned()]);
+        redact(&mut message, &["missing".to_owned(), "field".to_owned()]);
+        assert_eq!(
+            message,
+            json!({
+                "password": "***",
+                "user": {"name": "ann", "email": "***"},
+                "users": [{"email": "***"}, {"name": "eve"}],
+            })
+        );
+    }
+
+    #[test]
+    fn test_procedure_filter() {
+        let (logger, _) = recording();
+        assert!(logger.is_logged("pkg.Svc/A"));
+
+        let logger = logger
+            .only("/pkg.Svc/A")
+            .only("pkg.Svc/B")
+            .skip("pkg.Svc/B");
+        assert!(logger.is_logged("pkg.Svc/A"));
+        assert!(logger.is_logged("/pkg.Svc/A"));
+        assert!(!logger.is_logged("pkg.Svc/B"));
+        assert!(!logger.is_logged("pkg.Svc/C"));
+    }
+
+    #[test]
+    fn test_logs_registered_responses() {
+        let (logger, records) = recording();
+        let mut headers = http::HeaderMap::new();
+
+        logger
+            .on_request(
+                &mut RequestContext::new("/pkg.Svc/A", &mut headers),
+                &mut 1u32,
+            )
+            .unwrap();
+        // Streaming calls' placeholder request
+        logger
+            .on_request(&mut RequestContext::new("pkg.Svc/A", &mut headers), &mut ())
+            .unwrap();
+        logger
+            .on_response(&ResponseContext::new("pkg.Svc/A", &headers), &mut 2u32)
+            .unwrap();
+        let logger = logger.response::<u32>();
+        logger
+            .on_response(&ResponseContext::new("pkg.Svc/A", &headers), &mut 3u32)
+            .unwrap();
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].procedure, "pkg.Svc/A");
+        assert_eq!(records[0].direction, PayloadDirection::Request);
+        assert_eq!(records[0].message, json!(1));
+        assert_eq!(records[1].direction, PayloadDirection::Response);
+        assert_eq!(records[1].message, json!(3));
+    }
+
+    #[derive(Clone, PartialEq, prost::Message, Serialize, serde::Deserialize)]
+    struct LoginRequest {
+        #[prost(string, tag = "1")]
+        user: String,
+        #[prost(string, tag = "2")]
+        password: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message, Serialize, serde::Deserialize)]
+    struct LoginResponse {
+        #[prost(string, tag = "1")]
+        token: String,
+        #[prost(uint32, tag = "2")]
+        ttl: u32,
+    }
+
+    #[tokio::test]
+    async fn test_client_logs_payloads() {
+        /// Answers with a fixed JSON message.
+        #[derive(Clone)]
+        struct StubTransport;
+
+        impl Transport for StubTransport {
+            type Body = http_body_util::Full<Bytes>;
+
+            async fn request(
+                &self,
+                _request: http::Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                Ok(http::Response::builder()
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(http_body_util::Full::new(Bytes::from_static(
+                        br#"{"token":"abc","ttl":60}"#,
+                    )))
+                    .unwrap())
+            }
+        }
+
+        let (logger, records) = recording();
+        let logger = logger
+            .response::<LoginResponse>()
+            .redact("password")
+            .redact("token");
+        let client = ConnectClient::builder("http://stub")
+            .with_message_interceptor(logger)
+            .build_with_transport(StubTransport)
+            .unwrap();
+
+        let request = LoginRequest {
+            user: "ann".to_owned(),
+            password: "hunter2".to_owned(),
+        };
+        client
+            .call_unary::<LoginRequest, LoginResponse>("auth.v1.Auth/Login", &request)
+            .await
+            .unwrap();
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[0].message,
+            json!({"user": "ann", "password": "***"})
+        );
+        assert_eq!(records[1].message, json!({"token": "***", "ttl": 60}));
+    }
+}
@@ -0,0 +1,52 @@
+//! Wire protocols spoken by the client.
+
+/// Wire protocol used for RPC calls.
+///
+/// Set with [`ClientBuilder::use_grpc`](crate::ClientBuilder::use_grpc),
+/// [`ClientBuilder::use_grpc_web`](crate::ClientBuilder::use_grpc_web) or
+/// [`ClientBuilder::use_grpc_web_text`](crate::ClientBuilder::use_grpc_web_text).
+/// The encoding (protobuf or JSON) is chosen separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Protocol {
+    /// The Connect protocol (the default).
+    #[default]
+    Connect,
+    /// gRPC over HTTP/2 (`application/grpc`).
+    ///
+    /// Every call is length-prefixed and the status is read from the
+    /// `grpc-status` trailers, so the client can call plain gRPC servers
+    /// such as tonic.
+    Grpc,
+    /// gRPC-Web (`application/grpc-web`).
+    ///
+    /// Like gRPC, but the status arrives in a trailer frame at the end of the
+    /// body, so it works over HTTP/1.1 and through gRPC-Web proxies such as
+    /// Envoy. Client and bidi streaming are not supported.
+    GrpcWeb,
+    /// gRPC-Web text mode (`application/grpc-web-text`): gRPC-Web with
+    /// base64-encoded bodies.
+    GrpcWebText,
+}
+
+impl Protocol {
+    /// Name of the protocol: `connect`, `grpc`, `grpc-web` or `grpc-web-text`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Connect => "connect",
+            Self::Grpc => "grpc",
+            Self::GrpcWeb => "grpc-web",
+            Self::GrpcWebText => "grpc-web-text",
+        }
+    }
+
+    /// Returns `true` for gRPC and gRPC-Web, which share message framing and
+    /// status handling.
+    pub fn is_grpc(&self) -> bool {
+        !matches!(self, Self::Connect)
+    }
+
+    /// Returns `true` for gRPC-Web, in binary or text mode.
+    pub fn is_grpc_web(&self) -> bool {
+        matches!(self, Self::GrpcWeb | Self::GrpcWebText)
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! This module contains request-side encoding:
 //! - [`FrameEncoder`]: Encodes messages into Connect protocol envelope frames
+//! - [`StreamSender`]: Sends the messages of a request stream one at a time
 
 mod encoder;
+mod sender;
 
 pub use encoder::FrameEncoder;
+pub use sender::StreamSender;
@@ -76,6 +76,8 @@ pub struct FrameEncoder<S, T> {
     compression: CompressionConfig,
     /// Current encoder state.
     state: EncoderState,
+    /// Send an EndStream frame after the last message.
+    end_stream: bool,
     /// Type marker for the message type.
     _marker: PhantomData<T>,
 }
@@ -101,10 +103,19 @@ impl<S, T> FrameEncoder<S, T> {
             encoding,
             compression,
             state: EncoderState::Streaming,
+            end_stream: true,
             _marker: PhantomData,
         }
     }
 
+    /// Don't send an EndStream frame after the last message.
+    ///
+    /// gRPC request streams end when the request body ends.
+    pub fn without_end_stream(mut self) -> Self {
+        self.end_stream = false;
+        self
+    }
+
     /// Get the compression encoding used by this encoder.
     pub fn encoding(&self) -> CompressionEncoding {
         self.encoding
@@ -200,7 +211,11 @@ where
                         }
                         Poll::Ready(None) => {
                             // Inner stream exhausted, need to send EndStream
-                            this.state = EncoderState::SendEndStream;
+                            this.state = if this.end_stream {
+                                EncoderState::SendEndStream
+                            } else {
+                                EncoderState::Done
+                            };
                             // Continue to next iteration
                         }
                         Poll::Pending => {
@@ -414,4 +429,25 @@ mod tests {
         // Done
         assert!(encoder.next().await.is_none());
     }
+    #[tokio::test]
+    async fn test_encode_without_end_stream() {
+        let messages = stream::iter(vec![TestMessage {
+            value: "hello".to_string(),
+        }]);
+
+        let mut encoder = FrameEncoder::new(
+            messages,
+            true,
+            CompressionEncoding::Identity,
+            CompressionConfig::disabled(),
+        )
+        .without_end_stream();
+
+        let frame = encoder.next().await.unwrap().unwrap();
+        assert_eq!(frame[0], 0x00);
+
+        // No EndStream frame
+        assert!(encoder.next().await.is_none());
+        assert!(encoder.is_finished());
+    }
 }
@@ -0,0 +1,92 @@
+//! Sender half of a request stream.
+//!
+//! [`StreamSender`] feeds messages into a streaming call one at a time, so a
+//! bidirectional stream can decide what to send next based on what it has
+//! received. See [`ConnectClient::call_bidi_stream_channel`].
+//!
+//! [`ConnectClient::call_bidi_stream_channel`]: crate::ConnectClient::call_bidi_stream_channel
+
+use futures::SinkExt;
+use futures::channel::mpsc;
+
+use crate::{ClientError, Code};
+
+/// Number of messages buffered before [`StreamSender::send`] waits for the
+/// request body to take them.
+#[cfg(not(target_arch = "wasm32"))]
+const CHANNEL_CAPACITY: usize = 1;
+
+/// Sends the request messages of a streaming call.
+///
+/// Dropping the sender, or calling [`close`](Self::close), half-closes the
+/// call: the request stream ends, while responses keep arriving until the
+/// server ends its stream.
+pub struct StreamSender<T> {
+    tx: mpsc::Sender<T>,
+}
+
+impl<T> StreamSender<T> {
+    /// Create a sender and the stream of messages it sends.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn channel() -> (Self, mpsc::Receiver<T>) {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        (Self { tx }, rx)
+    }
+
+    /// Send a message.
+    ///
+    /// Waits while the request body is behind, e.g. when HTTP/2 flow control
+    /// holds it back.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Code::Canceled`] if the request stream is gone because the
+    /// call has ended; the response stream carries the reason.
+    pub async fn send(&mut self, message: T) -> Result<(), ClientError> {
+        self.tx
+            .send(message)
+            .await
+            .map_err(|_| ClientError::new(Code::Canceled, "request stream is closed"))
+    }
+
+    /// Half-close the call, ending the request stream.
+    pub fn close(mut self) {
+        self.tx.close_channel();
+    }
+
+    /// Whether the request stream is gone, so [`send`](Self::send) would fail.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+}
+
+impl<T> std::fmt::Debug for StreamSender<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamSender")
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn test_send_and_close() {
+        let (mut sender, mut rx) = StreamSender::channel();
+        sender.send(1).await.unwrap();
+        assert_eq!(rx.next().await, Some(1));
+
+        sender.close();
+        assert_eq!(rx.next().await, None);
+
+        // Sending after the stream is gone fails
+        let (mut sender, rx) = StreamSender::channel();
+        drop(rx);
+        assert!(sender.is_closed());
+        let err = sender.send(1).await.unwrap_err();
+        assert_eq!(err.code(), Code::Canceled);
+    }
+}
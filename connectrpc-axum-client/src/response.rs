@@ -4,19 +4,24 @@
 //! - [`ConnectResponse`]: Response wrapper with metadata
 //! - [`Metadata`]: HTTP headers wrapper
 //! - [`Streaming`]: Streaming response wrapper
+//! - [`Drained`]: Outcome of draining a [`Streaming`]
 //! - [`FrameDecoder`]: Decodes Connect protocol envelope frames
 //! - [`InterceptingStream`]: Stream wrapper for message-level interception
 //! - [`InterceptingSendStream`]: Stream wrapper for outgoing message interception
+//! - [`MapOk`], [`TryFilter`], [`StreamingReader`]: Adapters over streaming responses
 
+mod adapters;
 mod decoder;
 pub(crate) mod error_parser;
+pub(crate) mod grpc;
 mod intercepting;
 mod streaming;
 mod types;
 
-pub use decoder::FrameDecoder;
+pub use adapters::{MapOk, StreamingReader, TryFilter};
+pub use decoder::{DEFAULT_DECOMPRESSION_LIMIT, FrameDecoder};
 pub use intercepting::{
     InterceptingSendStream, InterceptingStream, InterceptingStreaming, TypedReceiveStreaming,
 };
-pub use streaming::Streaming;
+pub use streaming::{Drained, Streaming};
 pub use types::{ConnectResponse, Metadata};
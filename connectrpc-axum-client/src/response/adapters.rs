@@ -0,0 +1,332 @@
+//! Adapters over streaming responses.
+//!
+//! - [`MapOk`]: Maps each received message
+//! - [`TryFilter`]: Skips messages rejected by a predicate
+//! - [`StreamingReader`]: Reads the byte chunks carried by messages as an
+//!   `AsyncRead`, e.g. for chunked download RPCs
+//!
+//! Each adapter owns the stream it wraps, so trailers and
+//! [`drain()`](super::Streaming::drain) stay reachable through `get_ref()` and
+//! `get_mut()`.
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, Bytes};
+use futures::Stream;
+
+use crate::ClientError;
+
+/// Stream adapter that maps each received message.
+///
+/// Errors are passed through unchanged. Created by
+/// [`Streaming::map_ok`](super::Streaming::map_ok).
+pub struct MapOk<S, F> {
+    /// The underlying stream.
+    inner: S,
+    /// Maps each message.
+    f: F,
+}
+
+impl<S, F> MapOk<S, F> {
+    /// Create a new adapter mapping the messages of `inner` with `f`.
+    pub fn new(inner: S, f: F) -> Self {
+        Self { inner, f }
+    }
+
+    /// Get a reference to the inner stream.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner stream.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume the adapter and return the inner stream.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, F> Unpin for MapOk<S, F> where S: Unpin {}
+
+impl<S, T, U, F> Stream for MapOk<S, F>
+where
+    S: Stream<Item = Result<T, ClientError>> + Unpin,
+    F: FnMut(T) -> U,
+{
+    type Item = Result<U, ClientError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.inner)
+            .poll_next(cx)
+            .map(|item| item.map(|result| result.map(&mut this.f)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Stream adapter that skips messages rejected by a predicate.
+///
+/// Errors are always passed through. Created by
+/// [`Streaming::try_filter`](super::Streaming::try_filter).
+pub struct TryFilter<S, F> {
+    /// The underlying stream.
+    inner: S,
+    /// Keeps the messages it returns `true` for.
+    predicate: F,
+}
+
+impl<S, F> TryFilter<S, F> {
+    /// Create a new adapter keeping the messages of `inner` matching `predicate`.
+    pub fn new(inner: S, predicate: F) -> Self {
+        Self { inner, predicate }
+    }
+
+    /// Get a reference to the inner stream.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner stream.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume the adapter and return the inner stream.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, F> Unpin for TryFilter<S, F> where S: Unpin {}
+
+impl<S, T, F> Stream for TryFilter<S, F>
+where
+    S: Stream<Item = Result<T, ClientError>> + Unpin,
+    F: FnMut(&T) -> bool,
+{
+    type Item = Result<T, ClientError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(msg))) if !(this.predicate)(&msg) => continue,
+                poll => return poll,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+/// Reader over the byte chunks carried by the messages of a stream.
+///
+/// Implements [`futures::io::AsyncRead`], and `tokio::io::AsyncRead` outside
+/// of wasm32. Reading ends at the end of the stream. An error of the stream
+/// fails the read with an [`io::Error`] wrapping the [`ClientError`], which
+/// can be recovered with `get_ref()` and `downcast_ref()`. Created by
+/// [`Streaming::into_async_read`](super::Streaming::into_async_read).
+pub struct StreamingReader<S, F> {
+    /// The underlying stream.
+    inner: S,
+    /// Extracts the chunk of each message.
+    f: F,
+    /// The rest of the current chunk.
+    chunk: Bytes,
+    /// The stream ended.
+    done: bool,
+}
+
+impl<S, F> StreamingReader<S, F> {
+    /// Create a new reader over the chunks `f` extracts from `inner`'s messages.
+    pub fn new(inner: S, f: F) -> Self {
+        Self {
+            inner,
+            f,
+            chunk: Bytes::new(),
+            done: false,
+        }
+    }
+
+    /// Get a reference to the inner stream.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner stream.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume the reader and return the inner stream.
+    ///
+    /// Bytes of the current chunk that weren't read yet are discarded.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, F> Unpin for StreamingReader<S, F> where S: Unpin {}
+
+impl<S, T, B, F> StreamingReader<S, F>
+where
+    S: Stream<Item = Result<T, ClientError>> + Unpin,
+    F: FnMut(T) -> B,
+    B: Into<Bytes>,
+{
+    /// Poll until a chunk with bytes is buffered, or the stream ended if the
+    /// buffer is left empty.
+    fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.chunk.has_remaining() && !self.done {
+            match Pin::new(&mut self.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(msg))) => self.chunk = (self.f)(msg).into(),
+                Poll::Ready(Some(Err(e))) => {
+                    self.done = true;
+                    return Poll::Ready(Err(io::Error::other(e)));
+                }
+                Poll::Ready(None) => self.done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    /// Copy buffered bytes into `buf`, returning how many were copied.
+    fn copy_to(&mut self, buf: &mut [u8]) -> usize {
+        let n = buf.len().min(self.chunk.len());
+        self.chunk.copy_to_slice(&mut buf[..n]);
+        n
+    }
+}
+
+impl<S, T, B, F> futures::io::AsyncRead for StreamingReader<S, F>
+where
+    S: Stream<Item = Result<T, ClientError>> + Unpin,
+    F: FnMut(T) -> B,
+    B: Into<Bytes>,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        match this.poll_chunk(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(this.copy_to(buf))),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<S, T, B, F> tokio::io::AsyncRead for StreamingReader<S, F>
+where
+    S: Stream<Item = Result<T, ClientError>> + Unpin,
+    F: FnMut(T) -> B,
+    B: Into<Bytes>,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        match this.poll_chunk(cx) {
+            Poll::Ready(Ok(())) => {
+                let n = this.copy_to(buf.initialize_unfilled());
+                buf.advance(n);
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Code;
+    use futures::{StreamExt, stream};
+
+    fn messages(
+        items: Vec<Result<&'static str, ClientError>>,
+    ) -> impl Stream<Item = Result<&'static str, ClientError>> + Unpin {
+        stream::iter(items)
+    }
+
+    #[tokio::test]
+    async fn test_map_ok_and_try_filter() {
+        let error = ClientError::new(Code::Internal, "boom");
+        let stream = messages(vec![Ok("a"), Ok(""), Ok("bc"), Err(error)]);
+        let mut stream = MapOk::new(
+            TryFilter::new(stream, |msg: &&str| !msg.is_empty()),
+            str::len,
+        );
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+        assert_eq!(stream.next().await.unwrap().unwrap(), 2);
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::Internal);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reader() {
+        use futures::AsyncReadExt;
+
+        let stream = messages(vec![Ok("hello "), Ok(""), Ok("world")]);
+        let mut reader = StreamingReader::new(stream, |msg: &str| msg.as_bytes().to_vec());
+        let mut out = String::new();
+        reader.read_to_string(&mut out).await.unwrap();
+        assert_eq!(out, "hello world");
+
+        // Small reads span chunks
+        let stream = messages(vec![Ok("abc"), Ok("de")]);
+        let mut reader =
+            StreamingReader::new(stream, |msg: &str| Bytes::copy_from_slice(msg.as_bytes()));
+        let mut buf = [0u8; 2];
+        assert_eq!(reader.read(&mut buf).await.unwrap(), 2);
+        assert_eq!(&buf, b"ab");
+        assert_eq!(reader.read(&mut buf).await.unwrap(), 1);
+        assert_eq!(&buf[..1], b"c");
+        assert_eq!(reader.read(&mut buf).await.unwrap(), 2);
+        assert_eq!(reader.read(&mut buf).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_reader_error() {
+        use tokio::io::AsyncReadExt;
+
+        let error = ClientError::new(Code::DataLoss, "truncated");
+        let stream = messages(vec![Ok("abc"), Err(error)]);
+        let mut reader =
+            StreamingReader::new(stream, |msg: &str| Bytes::copy_from_slice(msg.as_bytes()));
+        let mut out = Vec::new();
+        let err = reader.read_to_end(&mut out).await.unwrap_err();
+        assert_eq!(out, b"abc");
+        let err = err
+            .get_ref()
+            .unwrap()
+            .downcast_ref::<ClientError>()
+            .unwrap();
+        assert_eq!(err.code(), Code::DataLoss);
+    }
+}
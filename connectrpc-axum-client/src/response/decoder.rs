@@ -10,17 +10,25 @@ use std::task::{Context, Poll};
 use base64::Engine;
 use bytes::{Bytes, BytesMut};
 use connectrpc_axum_core::{
-    Code, CompressionEncoding, ENVELOPE_HEADER_SIZE, ErrorDetail, envelope_flags,
-    parse_envelope_header, process_envelope_payload,
+    Code, CompressionEncoding, ENVELOPE_HEADER_SIZE, EnvelopeError, ErrorDetail, envelope_flags,
+    parse_envelope_header,
 };
 
 use crate::ClientError;
 use crate::response::Metadata;
+use crate::response::grpc::{self, GRPC_WEB_TRAILERS, GrpcStatusSource};
 use futures::Stream;
 use prost::Message;
 use serde::Deserialize;
 use serde::de::DeserializeOwned;
 
+/// Default cap on the decompressed size of a response body or streaming message
+/// (64 MiB).
+///
+/// Guards against decompression bombs: a small compressed payload from the
+/// server that would otherwise expand to gigabytes in memory.
+pub const DEFAULT_DECOMPRESSION_LIMIT: usize = 64 * 1024 * 1024;
+
 /// Decoded streaming frame result.
 enum DecodedFrame<T> {
     /// A message frame containing a decoded message.
@@ -47,6 +55,10 @@ enum DecodedFrame<T> {
 /// - `0x02`: End of stream (END_STREAM bit)
 /// - `0x03`: Compressed end-of-stream frame (COMPRESSED | END_STREAM)
 ///
+/// gRPC responses use the same message frames but no EndStream frame; the
+/// status is read from the HTTP trailers once the body ends, or for gRPC-Web
+/// from the trailer frame (`0x80`).
+///
 /// # Example
 ///
 /// ```ignore
@@ -68,12 +80,17 @@ pub struct FrameDecoder<S, T> {
     use_proto: bool,
     /// Compression encoding for decompression.
     encoding: CompressionEncoding,
+    /// Maximum size of a single frame payload, before and after
+    /// decompression, in bytes.
+    decompression_limit: usize,
     /// Stored trailers from EndStream frame.
     trailers: Option<Metadata>,
     /// Whether the stream has finished (received EndStream or error).
     finished: bool,
     /// Error from the EndStream frame, if any.
     end_stream_error: Option<ClientError>,
+    /// Status source of a gRPC response; `None` for Connect.
+    grpc: Option<GrpcStatusSource>,
     /// Type marker for the message type.
     _marker: PhantomData<T>,
 }
@@ -92,13 +109,31 @@ impl<S, T> FrameDecoder<S, T> {
             buffer: BytesMut::new(),
             use_proto,
             encoding,
+            decompression_limit: DEFAULT_DECOMPRESSION_LIMIT,
             trailers: None,
             finished: false,
             end_stream_error: None,
+            grpc: None,
             _marker: PhantomData,
         }
     }
 
+    /// Set the maximum size of a single frame payload.
+    ///
+    /// Frames that are larger, or that decompress past this limit, fail with
+    /// [`Code::ResourceExhausted`]. Defaults to [`DEFAULT_DECOMPRESSION_LIMIT`].
+    pub fn with_decompression_limit(mut self, limit: usize) -> Self {
+        self.decompression_limit = limit;
+        self
+    }
+
+    /// Decode a gRPC response, whose status is read from `source` when the
+    /// body ends instead of from an EndStream frame.
+    pub(crate) fn with_grpc_status(mut self, source: GrpcStatusSource) -> Self {
+        self.grpc = Some(source);
+        self
+    }
+
     /// Get the trailers received in the EndStream frame.
     ///
     /// Returns `None` if the stream hasn't finished or if no trailers were sent.
@@ -133,14 +168,13 @@ impl<S, T> FrameDecoder<S, T> {
     /// Decompress an envelope payload using the decoder's configured encoding.
     ///
     /// Used for frames whose COMPRESSED bit (0x01) is set. Identity passes the
-    /// bytes through unchanged.
+    /// bytes through unchanged. Output is capped at the decoder's
+    /// decompression limit.
     fn decompress(&self, payload: Bytes) -> Result<Bytes, ClientError> {
         let Some(codec) = self.encoding.codec() else {
             return Ok(payload); // identity: passthrough
         };
-        codec
-            .decompress(&payload)
-            .map_err(|e| ClientError::Decode(format!("decompression failed: {}", e)))
+        Ok(codec.decompress_limited(&payload, self.decompression_limit)?)
     }
 
     /// Try to parse a complete frame from the buffer.
@@ -160,6 +194,16 @@ impl<S, T> FrameDecoder<S, T> {
 
         // Parse header
         let (flags, length) = parse_envelope_header(&self.buffer)?;
+        // Reject oversized frames up front rather than buffering them
+        if length as usize > self.decompression_limit {
+            return Err(ClientError::new(
+                Code::ResourceExhausted,
+                format!(
+                    "message size {} exceeds the limit of {} bytes",
+                    length, self.decompression_limit
+                ),
+            ));
+        }
         let frame_size = ENVELOPE_HEADER_SIZE + length as usize;
 
         // Check if we have the complete frame
@@ -171,12 +215,32 @@ impl<S, T> FrameDecoder<S, T> {
         let frame_bytes = self.buffer.split_to(frame_size);
         let payload = Bytes::copy_from_slice(&frame_bytes[ENVELOPE_HEADER_SIZE..]);
 
+        // gRPC-Web: the trailer frame holds the status as a header block
+        let web = self.grpc.as_ref().is_some_and(GrpcStatusSource::is_web);
+        if web && flags & GRPC_WEB_TRAILERS != 0 {
+            let payload = if flags & envelope_flags::COMPRESSED != 0 {
+                self.decompress(payload)?
+            } else {
+                payload
+            };
+            let block = grpc::parse_trailer_block(&payload)?;
+            if let Some(grpc) = self.grpc.as_mut() {
+                grpc.store_trailers(block);
+                let (status, trailers) = grpc.finish();
+                self.trailers = Some(trailers);
+                self.end_stream_error = status.err();
+            }
+            self.finished = true;
+
+            return Ok(Some(DecodedFrame::EndStream));
+        }
+
         // Flags are a bitfield: the EndStream frame is identified by the
         // END_STREAM bit (0x02), which may be combined with the COMPRESSED bit
         // (0x03) when the server compresses the trailer payload. Test the bit
         // rather than matching the whole byte so compressed EndStream frames are
         // not mistaken for messages.
-        if flags & envelope_flags::END_STREAM != 0 {
+        if flags & envelope_flags::END_STREAM != 0 && self.grpc.is_none() {
             // Decompress the trailer payload first if the COMPRESSED bit is set.
             let payload = if flags & envelope_flags::COMPRESSED != 0 {
                 self.decompress(payload)?
@@ -197,9 +261,23 @@ impl<S, T> FrameDecoder<S, T> {
             return Ok(Some(DecodedFrame::EndStream));
         }
 
-        // Process message frame (validate flags, decompress)
-        let decompressed = process_envelope_payload(flags, payload, self.encoding)?
-            .ok_or_else(|| ClientError::Protocol("unexpected None from message frame".into()))?;
+        // Message frame: reject flags with bits outside the defined set
+        // (COMPRESSED | END_STREAM, or only COMPRESSED for gRPC).
+        let known_flags = if self.grpc.is_some() {
+            envelope_flags::COMPRESSED
+        } else {
+            envelope_flags::COMPRESSED | envelope_flags::END_STREAM
+        };
+        if flags & !known_flags != 0 {
+            return Err(EnvelopeError::InvalidFlags(flags).into());
+        }
+
+        // Decompress the message payload if the COMPRESSED bit is set.
+        let decompressed = if flags & envelope_flags::COMPRESSED != 0 {
+            self.decompress(payload)?
+        } else {
+            payload
+        };
 
         // Decode message
         let message = self.decode_message(&decompressed)?;
@@ -277,6 +355,12 @@ where
                             ),
                         ))));
                     }
+                    // gRPC: the status follows the body in the trailers
+                    if let Some(grpc) = this.grpc.as_mut() {
+                        let (status, trailers) = grpc.finish();
+                        this.trailers = Some(trailers);
+                        return Poll::Ready(status.err().map(Err));
+                    }
                     // Stream ended cleanly but without EndStream frame - protocol error
                     return Poll::Ready(Some(Err(ClientError::Protocol(
                         "stream ended without EndStream frame".into(),
@@ -618,6 +702,79 @@ mod tests {
         assert_eq!(trailers.get("x-t"), Some("1"));
     }
 
+    #[tokio::test]
+    async fn test_decode_grpc_web_trailer_frame() {
+        let message = make_frame(0x00, br#"{"value":"hi"}"#);
+        let trailer = make_frame(
+            GRPC_WEB_TRAILERS,
+            b"grpc-status: 7\r\ngrpc-message: denied\r\nx-t: 1\r\n",
+        );
+
+        let stream = stream::iter(vec![Ok::<_, ClientError>(message), Ok(trailer)]);
+        let mut decoder =
+            FrameDecoder::<_, TestMessage>::new(stream, false, CompressionEncoding::Identity)
+                .with_grpc_status(GrpcStatusSource::web(http::HeaderMap::new()));
+
+        assert_eq!(decoder.next().await.unwrap().unwrap().value, "hi");
+        let err = decoder.next().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::PermissionDenied);
+        assert_eq!(err.message(), Some("denied"));
+        assert!(decoder.next().await.is_none());
+        assert_eq!(decoder.trailers().unwrap().get("x-t"), Some("1"));
+    }
+
+    /// A small gzip frame that expands past the limit must fail with
+    /// ResourceExhausted instead of being buffered in full.
+    #[cfg(feature = "compression-gzip-stream")]
+    #[tokio::test]
+    async fn test_decode_compressed_message_exceeds_decompression_limit() {
+        let codec = CompressionEncoding::Gzip.codec().unwrap();
+        let bomb = codec.compress(&vec![b'a'; 1024 * 1024]).unwrap();
+        assert!(bomb.len() < 8 * 1024);
+        let frame = make_frame(0x01, &bomb);
+
+        let stream = stream::iter(vec![Ok::<_, ClientError>(frame)]);
+        let mut decoder =
+            FrameDecoder::<_, TestMessage>::new(stream, false, CompressionEncoding::Gzip)
+                .with_decompression_limit(64 * 1024);
+
+        let err = decoder.next().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+        assert_eq!(err.message(), Some("decompressed response exceeds limit"));
+    }
+
+    #[cfg(feature = "compression-gzip-stream")]
+    #[tokio::test]
+    async fn test_decode_compressed_message_within_decompression_limit() {
+        let codec = CompressionEncoding::Gzip.codec().unwrap();
+        let value = "hello".repeat(64);
+        let payload = format!(r#"{{"value":"{value}"}}"#);
+        let frame = make_frame(0x01, &codec.compress(payload.as_bytes()).unwrap());
+
+        let stream = stream::iter(vec![Ok::<_, ClientError>(frame)]);
+        let mut decoder =
+            FrameDecoder::<_, TestMessage>::new(stream, false, CompressionEncoding::Gzip)
+                .with_decompression_limit(payload.len());
+
+        let msg = decoder.next().await.unwrap().unwrap();
+        assert_eq!(msg.value, value);
+    }
+
+    /// Frames larger than the limit are rejected from the header alone.
+    #[tokio::test]
+    async fn test_decode_message_exceeds_limit() {
+        let mut header = vec![0x00];
+        header.extend_from_slice(&(1u32 << 30).to_be_bytes());
+
+        let stream = stream::iter(vec![Ok::<_, ClientError>(Bytes::from(header))]);
+        let mut decoder =
+            FrameDecoder::<_, TestMessage>::new(stream, false, CompressionEncoding::Identity)
+                .with_decompression_limit(1024);
+
+        let err = decoder.next().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+    }
+
     #[tokio::test]
     async fn test_chunked_data() {
         // Split a frame across multiple chunks
@@ -2,9 +2,12 @@
 //!
 //! Parses JSON error responses from Connect servers into [`ClientError`].
 
+use std::time::Duration;
+
 use base64::Engine;
+use connectrpc_axum_core::error_details::RetryInfo;
 use connectrpc_axum_core::{Code, ErrorDetail};
-use http::StatusCode;
+use http::{HeaderMap, StatusCode, header};
 use serde::Deserialize;
 
 use crate::ClientError;
@@ -24,14 +27,26 @@ use crate::ClientError;
 ///
 /// If the response body cannot be parsed as a Connect error, falls back to
 /// creating an error based on the HTTP status code.
-pub fn parse_error_response(status: StatusCode, body_bytes: &[u8]) -> ClientError {
+///
+/// A `Retry-After` header is kept as a `google.rpc.RetryInfo` detail, unless
+/// the error already has one (see [`with_retry_after`]).
+pub fn parse_error_response(
+    status: StatusCode,
+    headers: &HeaderMap,
+    body_bytes: &[u8],
+) -> ClientError {
+    with_retry_after(parse_error_body(status, body_bytes), headers)
+}
+
+/// Parse the body of an error response.
+fn parse_error_body(status: StatusCode, body_bytes: &[u8]) -> ClientError {
     // Try to parse as Connect error JSON
     match serde_json::from_slice::<ErrorResponseJson>(body_bytes) {
         Ok(error_json) => {
             // Parse error code
             let code = error_json.code.parse().unwrap_or_else(|_| {
                 // Fall back to deriving code from HTTP status
-                http_status_to_code(status)
+                Code::from_http_status_connect(status)
             });
 
             // Build ClientError
@@ -52,7 +67,7 @@ pub fn parse_error_response(status: StatusCode, body_bytes: &[u8]) -> ClientErro
         }
         Err(_) => {
             // Couldn't parse as JSON, fall back to HTTP status code
-            let code = http_status_to_code(status);
+            let code = Code::from_http_status_connect(status);
             let message = if body_bytes.is_empty() {
                 status.canonical_reason().unwrap_or("Unknown error")
             } else {
@@ -64,6 +79,28 @@ pub fn parse_error_response(status: StatusCode, body_bytes: &[u8]) -> ClientErro
     }
 }
 
+/// Add the delay of a `Retry-After` header to `err` as a
+/// `google.rpc.RetryInfo` detail, so retries honor it.
+///
+/// A `RetryInfo` sent by the server takes precedence. Only the
+/// delay-in-seconds form of the header is understood; HTTP dates are
+/// ignored.
+pub(crate) fn with_retry_after(err: ClientError, headers: &HeaderMap) -> ClientError {
+    if err.retry_delay().is_some() {
+        return err;
+    }
+    match retry_after(headers) {
+        Some(delay) => err.add_error_detail(ErrorDetail::from_message(&RetryInfo::new(delay))),
+        None => err,
+    }
+}
+
+/// Parse a `Retry-After: <seconds>` header.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = headers.get(header::RETRY_AFTER)?.to_str().ok()?;
+    seconds.trim().parse().ok().map(Duration::from_secs)
+}
+
 /// JSON structure for Connect error responses.
 #[derive(Deserialize)]
 struct ErrorResponseJson {
@@ -98,75 +135,77 @@ fn parse_error_detail(json: &ErrorDetailJson) -> Option<ErrorDetail> {
     Some(ErrorDetail::new(&json.type_url, value))
 }
 
-/// Map HTTP status code to Connect error code.
-///
-/// This is used as a fallback when the response body doesn't contain
-/// a valid Connect error JSON.
-pub(crate) fn http_status_to_code(status: StatusCode) -> Code {
-    match status.as_u16() {
-        200 => Code::Ok,
-        400 => Code::InvalidArgument,
-        401 => Code::Unauthenticated,
-        403 => Code::PermissionDenied,
-        404 => Code::NotFound,
-        408 => Code::DeadlineExceeded,
-        409 => Code::AlreadyExists,
-        412 => Code::FailedPrecondition,
-        416 => Code::OutOfRange,
-        429 => Code::ResourceExhausted,
-        499 => Code::Canceled, // Client Closed Request (nginx)
-        500 => Code::Internal,
-        501 => Code::Unimplemented,
-        502..=504 => Code::Unavailable,
-        _ => Code::Unknown,
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_http_status_to_code() {
-        assert!(matches!(http_status_to_code(StatusCode::OK), Code::Ok));
         assert!(matches!(
-            http_status_to_code(StatusCode::BAD_REQUEST),
-            Code::InvalidArgument
+            Code::from_http_status_connect(StatusCode::OK),
+            Code::Unknown
+        ));
+        assert!(matches!(
+            Code::from_http_status_connect(StatusCode::BAD_REQUEST),
+            Code::Internal
         ));
         assert!(matches!(
-            http_status_to_code(StatusCode::UNAUTHORIZED),
+            Code::from_http_status_connect(StatusCode::UNAUTHORIZED),
             Code::Unauthenticated
         ));
         assert!(matches!(
-            http_status_to_code(StatusCode::FORBIDDEN),
+            Code::from_http_status_connect(StatusCode::FORBIDDEN),
             Code::PermissionDenied
         ));
         assert!(matches!(
-            http_status_to_code(StatusCode::NOT_FOUND),
-            Code::NotFound
+            Code::from_http_status_connect(StatusCode::NOT_FOUND),
+            Code::Unimplemented
         ));
         assert!(matches!(
-            http_status_to_code(StatusCode::CONFLICT),
-            Code::AlreadyExists
+            Code::from_http_status_connect(StatusCode::CONFLICT),
+            Code::Unknown
         ));
         assert!(matches!(
-            http_status_to_code(StatusCode::TOO_MANY_REQUESTS),
-            Code::ResourceExhausted
+            Code::from_http_status_connect(StatusCode::TOO_MANY_REQUESTS),
+            Code::Unavailable
         ));
         assert!(matches!(
-            http_status_to_code(StatusCode::INTERNAL_SERVER_ERROR),
-            Code::Internal
+            Code::from_http_status_connect(StatusCode::INTERNAL_SERVER_ERROR),
+            Code::Unknown
         ));
         assert!(matches!(
-            http_status_to_code(StatusCode::NOT_IMPLEMENTED),
-            Code::Unimplemented
+            Code::from_http_status_connect(StatusCode::NOT_IMPLEMENTED),
+            Code::Unknown
         ));
         assert!(matches!(
-            http_status_to_code(StatusCode::SERVICE_UNAVAILABLE),
+            Code::from_http_status_connect(StatusCode::SERVICE_UNAVAILABLE),
             Code::Unavailable
         ));
     }
 
+    #[test]
+    fn test_parse_error_response_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::RETRY_AFTER, "7".parse().unwrap());
+        let body = br#"{"code":"unavailable","message":"slow down"}"#;
+        let err = parse_error_response(StatusCode::SERVICE_UNAVAILABLE, &headers, body);
+        assert_eq!(err.retry_delay(), Some(Duration::from_secs(7)));
+
+        // RetryInfo sent by the server wins
+        let body = br#"{"code":"unavailable","details":[{"type":"google.rpc.RetryInfo","value":"CgIIAg"}]}"#;
+        let err = parse_error_response(StatusCode::SERVICE_UNAVAILABLE, &headers, body);
+        assert_eq!(err.retry_delay(), Some(Duration::from_secs(2)));
+        assert_eq!(err.details().len(), 1);
+
+        // HTTP dates are ignored
+        headers.insert(
+            header::RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        let err = parse_error_response(StatusCode::SERVICE_UNAVAILABLE, &headers, b"");
+        assert_eq!(err.retry_delay(), None);
+    }
+
     #[test]
     fn test_parse_error_detail() {
         let json = ErrorDetailJson {
@@ -214,7 +253,7 @@ mod tests {
     #[test]
     fn test_parse_error_response_with_json() {
         let body = br#"{"code":"not_found","message":"resource not found"}"#;
-        let err = parse_error_response(StatusCode::NOT_FOUND, body);
+        let err = parse_error_response(StatusCode::NOT_FOUND, &HeaderMap::new(), body);
         assert_eq!(err.code(), Code::NotFound);
         assert_eq!(err.message(), Some("resource not found"));
     }
@@ -222,16 +261,16 @@ mod tests {
     #[test]
     fn test_parse_error_response_invalid_json() {
         let body = b"Plain text error";
-        let err = parse_error_response(StatusCode::INTERNAL_SERVER_ERROR, body);
-        assert_eq!(err.code(), Code::Internal);
+        let err = parse_error_response(StatusCode::INTERNAL_SERVER_ERROR, &HeaderMap::new(), body);
+        assert_eq!(err.code(), Code::Unknown);
         assert_eq!(err.message(), Some("Plain text error"));
     }
 
     #[test]
     fn test_parse_error_response_empty_body() {
         let body = b"";
-        let err = parse_error_response(StatusCode::NOT_FOUND, body);
-        assert_eq!(err.code(), Code::NotFound);
+        let err = parse_error_response(StatusCode::NOT_FOUND, &HeaderMap::new(), body);
+        assert_eq!(err.code(), Code::Unimplemented);
         assert_eq!(err.message(), Some("Not Found"));
     }
 }
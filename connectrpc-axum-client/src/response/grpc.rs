@@ -0,0 +1,406 @@
+//! gRPC call status handling.
+//!
+//! gRPC reports the outcome of a call out of band: `grpc-status`,
+//! `grpc-message` and `grpc-status-details-bin` arrive in the HTTP trailers,
+//! or in the response headers of a trailers-only response (a call that fails
+//! before sending any message).
+//!
+//! gRPC-Web sends the same fields in a trailer frame (flag `0x80`) at the end
+//! of the body instead, and its text mode base64-encodes the whole body.
+
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, ready};
+
+use base64::Engine;
+use bytes::{Bytes, BytesMut};
+use connectrpc_axum_core::{Code, ErrorDetail};
+use futures::Stream;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+
+use crate::ClientError;
+use crate::response::Metadata;
+use crate::response::error_parser::with_retry_after;
+use crate::response::types::decode_bin;
+
+/// Header carrying the gRPC status code.
+pub(crate) const GRPC_STATUS: &str = "grpc-status";
+
+/// Header carrying the percent-encoded gRPC status message.
+pub(crate) const GRPC_MESSAGE: &str = "grpc-message";
+
+/// Header carrying the binary `google.rpc.Status` with error details.
+pub(crate) const GRPC_STATUS_DETAILS_BIN: &str = "grpc-status-details-bin";
+
+/// Envelope flag of the gRPC-Web trailer frame.
+pub(crate) const GRPC_WEB_TRAILERS: u8 = 0x80;
+
+/// Slot the response body stream stores the HTTP trailers in.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TrailersSlot(Arc<Mutex<Option<HeaderMap>>>);
+
+impl TrailersSlot {
+    /// Store the trailers, merging them with any stored earlier.
+    pub(crate) fn store(&self, trailers: HeaderMap) {
+        let mut slot = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        match slot.as_mut() {
+            Some(existing) => existing.extend(trailers),
+            None => *slot = Some(trailers),
+        }
+    }
+
+    fn take(&self) -> Option<HeaderMap> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+/// Where a gRPC [`FrameDecoder`](crate::FrameDecoder) reads the call status
+/// from once the response body has ended.
+#[derive(Debug)]
+pub(crate) struct GrpcStatusSource {
+    trailers: TrailersSlot,
+    headers: HeaderMap,
+    web: bool,
+}
+
+impl GrpcStatusSource {
+    /// Read the status from the trailers stored in `trailers`, falling back
+    /// to the response `headers` for trailers-only responses.
+    pub(crate) fn new(trailers: TrailersSlot, headers: HeaderMap) -> Self {
+        Self {
+            trailers,
+            headers,
+            web: false,
+        }
+    }
+
+    /// Read the status from the gRPC-Web trailer frame, falling back to the
+    /// response `headers` for trailers-only responses.
+    pub(crate) fn web(headers: HeaderMap) -> Self {
+        Self {
+            trailers: TrailersSlot::default(),
+            headers,
+            web: true,
+        }
+    }
+
+    /// Returns `true` if the status arrives in a gRPC-Web trailer frame.
+    pub(crate) fn is_web(&self) -> bool {
+        self.web
+    }
+
+    /// Store the trailers of a gRPC-Web trailer frame.
+    pub(crate) fn store_trailers(&self, trailers: HeaderMap) {
+        self.trailers.store(trailers);
+    }
+
+    /// The status of the finished call and its trailers.
+    ///
+    /// The status fields are removed from the returned trailers.
+    pub(crate) fn finish(&mut self) -> (Result<(), ClientError>, Metadata) {
+        let mut trailers = match self.trailers.take() {
+            Some(trailers) => trailers,
+            None => std::mem::take(&mut self.headers),
+        };
+        let status = parse_status(&trailers).unwrap_or_else(|| {
+            Err(ClientError::Protocol(
+                "gRPC response ended without grpc-status".into(),
+            ))
+        });
+        for name in [GRPC_STATUS, GRPC_MESSAGE, GRPC_STATUS_DETAILS_BIN] {
+            trailers.remove(name);
+        }
+        (status, Metadata::new(trailers))
+    }
+}
+
+/// Parse the gRPC status in `metadata`.
+///
+/// Returns `None` if there is no `grpc-status`.
+pub(crate) fn parse_status(metadata: &HeaderMap) -> Option<Result<(), ClientError>> {
+    let code = metadata.get(GRPC_STATUS)?;
+    let code = code
+        .to_str()
+        .ok()
+        .and_then(|code| code.parse::<u32>().ok())
+        .map_or(Code::Unknown, |code| {
+            Code::from_grpc_code(code).unwrap_or(Code::Unknown)
+        });
+    if code == Code::Ok {
+        return Some(Ok(()));
+    }
+
+    let message = metadata
+        .get(GRPC_MESSAGE)
+        .map(|message| percent_decode(message.as_bytes()))
+        .unwrap_or_default();
+    let mut err = if message.is_empty() {
+        ClientError::from_code(code)
+    } else {
+        ClientError::new(code, message)
+    };
+
+    if let Some(status) = metadata
+        .get(GRPC_STATUS_DETAILS_BIN)
+        .and_then(|value| decode_bin(value.as_bytes()))
+        .and_then(|bytes| <RpcStatus as prost::Message>::decode(bytes.as_slice()).ok())
+    {
+        for detail in status.details {
+            err = err.add_error_detail(ErrorDetail::new(detail.type_url, detail.value));
+        }
+    }
+    Some(Err(err))
+}
+
+/// The error for a gRPC response with a non-200 HTTP status.
+///
+/// A `grpc-status` in the headers takes precedence; otherwise the code
+/// comes from [`Code::from_http_status_connect`].
+pub(crate) fn http_status_error(status: StatusCode, headers: &HeaderMap) -> ClientError {
+    if let Some(Err(err)) = parse_status(headers) {
+        return err;
+    }
+    let code = Code::from_http_status_connect(status);
+    with_retry_after(ClientError::new(code, format!("HTTP {}", status)), headers)
+}
+
+/// Parse the payload of a gRPC-Web trailer frame: an HTTP/1-style header
+/// block of `name: value` lines.
+pub(crate) fn parse_trailer_block(payload: &[u8]) -> Result<HeaderMap, ClientError> {
+    let invalid = || ClientError::Protocol("invalid gRPC-Web trailer frame".into());
+    let mut trailers = HeaderMap::new();
+    for line in payload.split(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.is_empty() {
+            continue;
+        }
+        let colon = line.iter().position(|&b| b == b':').ok_or_else(invalid)?;
+        // Header names are case-insensitive; HeaderName wants lowercase
+        let name =
+            HeaderName::from_bytes(&line[..colon].to_ascii_lowercase()).map_err(|_| invalid())?;
+        let value =
+            HeaderValue::from_bytes(line[colon + 1..].trim_ascii()).map_err(|_| invalid())?;
+        trailers.append(name, value);
+    }
+    Ok(trailers)
+}
+
+/// Encode a request body for gRPC-Web text mode.
+pub(crate) fn encode_text(body: &[u8]) -> Bytes {
+    Bytes::from(base64::engine::general_purpose::STANDARD.encode(body))
+}
+
+/// Stream adapter decoding a gRPC-Web text (base64) response body.
+///
+/// Yields the decoded bytes as complete base64 quads arrive. The body may be
+/// a concatenation of separately padded chunks.
+pub(crate) struct TextDecoder<S> {
+    inner: S,
+    buffer: BytesMut,
+}
+
+impl<S> TextDecoder<S> {
+    pub(crate) fn new(inner: S) -> Self {
+        Self {
+            inner,
+            buffer: BytesMut::new(),
+        }
+    }
+}
+
+impl<S> Stream for TextDecoder<S>
+where
+    S: Stream<Item = Result<Bytes, ClientError>> + Unpin,
+{
+    type Item = Result<Bytes, ClientError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match ready!(Pin::new(&mut this.inner).poll_next(cx)) {
+                Some(Ok(chunk)) => {
+                    this.buffer
+                        .extend(chunk.iter().filter(|b| !b.is_ascii_whitespace()));
+                    let decoded = decode_quads(&mut this.buffer)?;
+                    if !decoded.is_empty() {
+                        return Poll::Ready(Some(Ok(decoded)));
+                    }
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None if this.buffer.is_empty() => return Poll::Ready(None),
+                None => {
+                    this.buffer.clear();
+                    return Poll::Ready(Some(Err(invalid_text())));
+                }
+            }
+        }
+    }
+}
+
+/// Decode the complete base64 quads at the start of `buffer`.
+fn decode_quads(buffer: &mut BytesMut) -> Result<Bytes, ClientError> {
+    let input = buffer.split_to(buffer.len() / 4 * 4);
+    let mut decoded = Vec::with_capacity(input.len() / 4 * 3);
+    // A padded quad ends a chunk, so decode chunk by chunk
+    let mut start = 0;
+    for end in (4..=input.len()).step_by(4) {
+        if input[end - 1] == b'=' || end == input.len() {
+            base64::engine::general_purpose::STANDARD
+                .decode_vec(&input[start..end], &mut decoded)
+                .map_err(|_| invalid_text())?;
+            start = end;
+        }
+    }
+    Ok(Bytes::from(decoded))
+}
+
+fn invalid_text() -> ClientError {
+    ClientError::Protocol("gRPC-Web text response is not valid base64".into())
+}
+
+/// Decode a percent-encoded `grpc-message` value.
+fn percent_decode(value: &[u8]) -> String {
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut i = 0;
+    while i < value.len() {
+        if value[i] == b'%'
+            && let Some(byte) = value
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(value[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// `google.rpc.Status`, sent base64-encoded in `grpc-status-details-bin`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<Any>,
+}
+
+/// `google.protobuf.Any`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct Any {
+    #[prost(string, tag = "1")]
+    type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    value: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use connectrpc_axum_core::error_details::ErrorInfo;
+    use http::HeaderValue;
+    use prost::Message;
+
+    #[test]
+    fn test_parse_status() {
+        let mut headers = HeaderMap::new();
+        assert!(parse_status(&headers).is_none());
+
+        headers.insert(GRPC_STATUS, HeaderValue::from_static("0"));
+        assert!(parse_status(&headers).unwrap().is_ok());
+
+        let info = ErrorInfo::new("QUOTA", "example.com");
+        let status = RpcStatus {
+            code: 8,
+            message: "slow down".into(),
+            details: vec![Any {
+                type_url: "type.googleapis.com/google.rpc.ErrorInfo".into(),
+                value: info.encode_to_vec(),
+            }],
+        };
+        let bin = base64::engine::general_purpose::STANDARD_NO_PAD.encode(status.encode_to_vec());
+        headers.insert(GRPC_STATUS, HeaderValue::from_static("8"));
+        headers.insert(
+            GRPC_MESSAGE,
+            HeaderValue::from_static("slow%20down%E2%80%A6"),
+        );
+        headers.insert(
+            GRPC_STATUS_DETAILS_BIN,
+            HeaderValue::from_str(&bin).unwrap(),
+        );
+
+        let err = parse_status(&headers).unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+        assert_eq!(err.message(), Some("slow down\u{2026}"));
+        assert_eq!(err.find_detail::<ErrorInfo>(), Some(info));
+    }
+
+    #[test]
+    fn test_status_source_prefers_trailers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(GRPC_STATUS, HeaderValue::from_static("13"));
+        let slot = TrailersSlot::default();
+        let mut trailers = HeaderMap::new();
+        trailers.insert(GRPC_STATUS, HeaderValue::from_static("0"));
+        trailers.insert("x-checksum", HeaderValue::from_static("abc"));
+        slot.store(trailers);
+
+        let (status, trailers) = GrpcStatusSource::new(slot, headers.clone()).finish();
+        assert!(status.is_ok());
+        assert_eq!(trailers.get("x-checksum"), Some("abc"));
+        assert_eq!(trailers.get(GRPC_STATUS), None);
+
+        // Trailers-only response
+        let (status, _) = GrpcStatusSource::new(TrailersSlot::default(), headers).finish();
+        assert_eq!(status.unwrap_err().code(), Code::Internal);
+    }
+
+    #[test]
+    fn test_parse_trailer_block() {
+        let trailers = parse_trailer_block(
+            b"grpc-status: 3\r\nGrpc-Message: bad%20input\r\nx-a: 1\r\nx-a: 2\r\n",
+        )
+        .unwrap();
+        let err = parse_status(&trailers).unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert_eq!(err.message(), Some("bad input"));
+        assert_eq!(trailers.get_all("x-a").iter().count(), 2);
+
+        assert!(parse_trailer_block(b"no colon\r\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_text_decoder() {
+        use futures::StreamExt;
+
+        // Two padded chunks, split mid-quad across reads
+        let body = [encode_text(b"hello"), encode_text(b"world!")].concat();
+        let chunks = body
+            .chunks(3)
+            .map(|c| Ok(Bytes::copy_from_slice(c)))
+            .collect::<Vec<_>>();
+        let decoded = TextDecoder::new(futures::stream::iter(chunks))
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await
+            .concat();
+        assert_eq!(decoded, b"helloworld!");
+
+        let truncated = futures::stream::iter(vec![Ok(Bytes::from_static(b"aGVsb"))]);
+        let results = TextDecoder::new(truncated).collect::<Vec<_>>().await;
+        assert!(results.last().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_http_status_error() {
+        let err = http_status_error(StatusCode::SERVICE_UNAVAILABLE, &HeaderMap::new());
+        assert_eq!(err.code(), Code::Unavailable);
+        let err = http_status_error(StatusCode::NOT_FOUND, &HeaderMap::new());
+        assert_eq!(err.code(), Code::Unimplemented);
+    }
+}
@@ -17,8 +17,9 @@ use serde::de::DeserializeOwned;
 use crate::ClientError;
 use crate::config::{InterceptorInternal, StreamContext, StreamType, TypedInterceptor};
 
+use super::adapters::{MapOk, StreamingReader, TryFilter};
 use super::decoder::FrameDecoder;
-use super::streaming::Streaming;
+use super::streaming::{Drained, Streaming};
 use super::types::Metadata;
 
 /// A stream wrapper that intercepts incoming messages.
@@ -270,21 +271,52 @@ impl<S, T, I> InterceptingStreaming<FrameDecoder<S, T>, T, I> {
     }
 }
 
-impl<S, T, I> InterceptingStreaming<S, T, I>
+impl<S, T, I> InterceptingStreaming<FrameDecoder<S, T>, T, I>
 where
-    S: Stream<Item = Result<T, ClientError>> + Unpin,
+    FrameDecoder<S, T>: Stream<Item = Result<T, ClientError>> + Unpin,
 {
     /// Drain remaining messages.
-    pub async fn drain(&mut self) -> usize {
+    pub async fn drain(&mut self) -> Drained {
         self.inner.drain().await
     }
 
     /// Drain with timeout.
-    pub async fn drain_timeout(&mut self, timeout: std::time::Duration) -> Result<usize, usize> {
+    pub async fn drain_timeout(
+        &mut self,
+        timeout: std::time::Duration,
+    ) -> Result<Drained, Drained> {
         self.inner.drain_timeout(timeout).await
     }
 }
 
+impl<S, T, I> InterceptingStreaming<S, T, I> {
+    /// Map each received message with `f`. See [`Streaming::map_ok`].
+    pub fn map_ok<U, F>(self, f: F) -> MapOk<Self, F>
+    where
+        F: FnMut(T) -> U,
+    {
+        MapOk::new(self, f)
+    }
+
+    /// Skip received messages rejected by `predicate`. See [`Streaming::try_filter`].
+    pub fn try_filter<F>(self, predicate: F) -> TryFilter<Self, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        TryFilter::new(self, predicate)
+    }
+
+    /// Read the byte chunks of the received messages as an `AsyncRead`.
+    /// See [`Streaming::into_async_read`].
+    pub fn into_async_read<B, F>(self, f: F) -> StreamingReader<Self, F>
+    where
+        F: FnMut(T) -> B,
+        B: Into<bytes::Bytes>,
+    {
+        StreamingReader::new(self, f)
+    }
+}
+
 impl<S, T, I> Unpin for InterceptingStreaming<S, T, I> where Streaming<S>: Unpin {}
 
 impl<S, T, I> Stream for InterceptingStreaming<S, T, I>
@@ -386,21 +418,52 @@ impl<S, T> TypedReceiveStreaming<FrameDecoder<S, T>, T> {
     }
 }
 
-impl<S, T> TypedReceiveStreaming<S, T>
+impl<S, T> TypedReceiveStreaming<FrameDecoder<S, T>, T>
 where
-    S: Stream<Item = Result<T, ClientError>> + Unpin,
+    FrameDecoder<S, T>: Stream<Item = Result<T, ClientError>> + Unpin,
 {
     /// Drain remaining messages.
-    pub async fn drain(&mut self) -> usize {
+    pub async fn drain(&mut self) -> Drained {
         self.inner.drain().await
     }
 
     /// Drain with timeout.
-    pub async fn drain_timeout(&mut self, timeout: std::time::Duration) -> Result<usize, usize> {
+    pub async fn drain_timeout(
+        &mut self,
+        timeout: std::time::Duration,
+    ) -> Result<Drained, Drained> {
         self.inner.drain_timeout(timeout).await
     }
 }
 
+impl<S, T> TypedReceiveStreaming<S, T> {
+    /// Map each received message with `f`. See [`Streaming::map_ok`].
+    pub fn map_ok<U, F>(self, f: F) -> MapOk<Self, F>
+    where
+        F: FnMut(T) -> U,
+    {
+        MapOk::new(self, f)
+    }
+
+    /// Skip received messages rejected by `predicate`. See [`Streaming::try_filter`].
+    pub fn try_filter<F>(self, predicate: F) -> TryFilter<Self, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        TryFilter::new(self, predicate)
+    }
+
+    /// Read the byte chunks of the received messages as an `AsyncRead`.
+    /// See [`Streaming::into_async_read`].
+    pub fn into_async_read<B, F>(self, f: F) -> StreamingReader<Self, F>
+    where
+        F: FnMut(T) -> B,
+        B: Into<bytes::Bytes>,
+    {
+        StreamingReader::new(self, f)
+    }
+}
+
 impl<S, T> Unpin for TypedReceiveStreaming<S, T> where Streaming<S>: Unpin {}
 
 impl<S, T> Stream for TypedReceiveStreaming<S, T>
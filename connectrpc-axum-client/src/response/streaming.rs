@@ -10,16 +10,21 @@
 //! or HTTP/2 RST_STREAM frame.
 //!
 //! For cooperative cancellation with timeouts, use [`CallOptions::timeout`]
-//! which sends the `Connect-Timeout-Ms` header to the server.
+//! which sends the `Connect-Timeout-Ms` header to the server. To fail a
+//! stream that goes quiet, use [`Streaming::with_message_timeout`].
 //!
 //! [`CallOptions::timeout`]: crate::CallOptions::timeout
 
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
-use crate::ClientError;
+use crate::metrics::CallMetrics;
+use crate::rt::BoxSleep;
+use crate::{ClientError, Code};
 use futures::Stream;
 
+use super::adapters::{MapOk, StreamingReader, TryFilter};
 use super::decoder::FrameDecoder;
 use super::types::Metadata;
 
@@ -67,12 +72,54 @@ use super::types::Metadata;
 pub struct Streaming<S> {
     /// The underlying frame decoder.
     inner: S,
+    /// Per-message deadline, if set.
+    message_timeout: Option<Duration>,
+    /// Deadline for the message being waited for.
+    sleep: Option<BoxSleep>,
+    /// The deadline elapsed, ending the stream.
+    timed_out: bool,
+    /// Metrics of the call, finished when the stream ends.
+    metrics: Option<CallMetrics>,
 }
 
 impl<S> Streaming<S> {
     /// Create a new Streaming wrapping the given stream.
     pub fn new(inner: S) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            message_timeout: None,
+            sleep: None,
+            timed_out: false,
+            metrics: None,
+        }
+    }
+
+    /// Apply a per-message deadline to the stream.
+    ///
+    /// If the next message does not arrive within `timeout`, the stream
+    /// yields an error with [`Code::DeadlineExceeded`] and ends. The window
+    /// starts when the stream is polled for the next message, so time spent
+    /// processing the previous one doesn't count against the server.
+    ///
+    /// This is independent of the call timeout, which bounds the whole call;
+    /// use it to fail deterministically on servers that stop sending without
+    /// ending the stream. Applied by the client when the call is made with
+    /// [`CallOptions::message_timeout`](crate::CallOptions::message_timeout).
+    pub fn with_message_timeout(mut self, timeout: Duration) -> Self {
+        self.message_timeout = Some(timeout);
+        self.sleep = None;
+        self
+    }
+
+    /// Record the outcome of the call in `metrics` when the stream ends.
+    pub(crate) fn with_metrics(mut self, metrics: CallMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Get the per-message deadline, if any.
+    pub fn get_message_timeout(&self) -> Option<Duration> {
+        self.message_timeout
     }
 
     /// Get a reference to the inner stream.
@@ -113,25 +160,26 @@ impl<S, T> Streaming<FrameDecoder<S, T>> {
 }
 
 /// Graceful shutdown methods for streaming responses.
-impl<S, T> Streaming<S>
+impl<S, T> Streaming<FrameDecoder<S, T>>
 where
-    S: Stream<Item = Result<T, ClientError>> + Unpin,
+    FrameDecoder<S, T>: Stream<Item = Result<T, ClientError>> + Unpin,
 {
     /// Gracefully drain all remaining messages from the stream.
     ///
     /// This method consumes all remaining messages without processing them,
-    /// allowing for graceful connection cleanup and reuse. After draining,
-    /// trailers will be available via [`trailers()`](Streaming::trailers)
-    /// if the inner stream is a `FrameDecoder`.
+    /// allowing for graceful connection cleanup and reuse.
     ///
-    /// Returns the number of messages that were drained (not including errors).
+    /// Returns a [`Drained`] with the number of messages that were drained
+    /// (not including errors), the trailers of the EndStream frame, and the
+    /// error that ended the RPC, if any. The trailers also stay available via
+    /// [`trailers()`](Streaming::trailers).
     ///
     /// # When to Use
     ///
     /// Use `drain()` when you want to:
     /// - Gracefully close a stream without processing remaining messages
     /// - Ensure connection reuse in HTTP/2
-    /// - Access trailers after deciding to stop processing early
+    /// - Observe the final status of the RPC after deciding to stop processing early
     ///
     /// # Example
     ///
@@ -151,22 +199,27 @@ where
     ///
     /// // Gracefully drain remaining messages
     /// let drained = stream.drain().await;
-    /// println!("Drained {} remaining messages", drained);
+    /// println!("Drained {} remaining messages", drained.messages());
     ///
-    /// // Trailers are now available
-    /// if let Some(trailers) = stream.trailers() {
+    /// if let Some(err) = drained.error() {
+    ///     eprintln!("RPC failed: {}", err);
+    /// }
+    /// if let Some(trailers) = drained.trailers() {
     ///     println!("Trailers: {:?}", trailers);
     /// }
     /// ```
-    pub async fn drain(&mut self) -> usize {
+    pub async fn drain(&mut self) -> Drained {
         use futures::StreamExt;
-        let mut count = 0;
-        while let Some(result) = self.inner.next().await {
-            if result.is_ok() {
-                count += 1;
+        let mut drained = Drained::default();
+        loop {
+            let result = self.inner.next().await;
+            self.record(&result);
+            match result {
+                Some(Ok(_)) => drained.messages += 1,
+                Some(Err(e)) => drained.error = Some(e),
+                None => return self.finish_drain(drained),
             }
         }
-        count
     }
 
     /// Gracefully drain remaining messages with a timeout.
@@ -174,9 +227,9 @@ where
     /// Like [`drain()`](Self::drain), but returns early if the timeout expires.
     /// This prevents hanging indefinitely on slow or stuck streams.
     ///
-    /// Returns `Ok(count)` if the stream was fully drained, or `Err(count)`
-    /// if the timeout expired (where `count` is the number of messages drained
-    /// before the timeout).
+    /// Returns `Ok(drained)` if the stream was fully drained, or `Err(drained)`
+    /// if the timeout expired. After a timeout, the [`Drained`] holds what was
+    /// seen before the deadline, and has no trailers.
     ///
     /// # Example
     ///
@@ -187,33 +240,149 @@ where
     ///
     /// // Drain with a 5-second timeout
     /// match stream.drain_timeout(Duration::from_secs(5)).await {
-    ///     Ok(count) => println!("Fully drained {} messages", count),
-    ///     Err(count) => println!("Timeout after draining {} messages", count),
+    ///     Ok(drained) => println!("Fully drained {} messages", drained.messages()),
+    ///     Err(drained) => println!("Timeout after draining {} messages", drained.messages()),
     /// }
     /// ```
-    pub async fn drain_timeout(&mut self, timeout: std::time::Duration) -> Result<usize, usize> {
+    pub async fn drain_timeout(
+        &mut self,
+        timeout: std::time::Duration,
+    ) -> Result<Drained, Drained> {
         use futures::StreamExt;
-        let mut count = 0;
-        let deadline = tokio::time::Instant::now() + timeout;
+        use futures::future::{Either, select};
 
-        loop {
-            tokio::select! {
-                biased;
-
-                _ = tokio::time::sleep_until(deadline) => {
-                    return Err(count);
-                }
+        let mut drained = Drained::default();
+        let mut deadline = std::pin::pin!(crate::rt::sleep(timeout));
 
-                item = self.inner.next() => {
+        loop {
+            // The deadline is polled first, so a stream that is always ready
+            // can't starve it
+            match select(deadline.as_mut(), self.inner.next()).await {
+                Either::Left(_) => return Err(drained),
+                Either::Right((item, _)) => {
+                    self.record(&item);
                     match item {
-                        Some(Ok(_)) => count += 1,
-                        Some(Err(_)) => {}
-                        None => return Ok(count),
+                        Some(Ok(_)) => drained.messages += 1,
+                        Some(Err(e)) => drained.error = Some(e),
+                        None => return Ok(self.finish_drain(drained)),
                     }
                 }
             }
         }
     }
+
+    /// Attach the trailers of the ended stream to `drained`.
+    fn finish_drain(&self, mut drained: Drained) -> Drained {
+        drained.trailers = self.inner.trailers().cloned();
+        drained
+    }
+}
+
+/// The outcome of draining a [`Streaming`].
+///
+/// Returned by [`Streaming::drain`] and [`Streaming::drain_timeout`], so
+/// callers that stop reading early still observe how the RPC ended.
+#[derive(Debug, Default)]
+pub struct Drained {
+    /// Number of messages discarded.
+    messages: usize,
+    /// Trailers of the EndStream frame.
+    trailers: Option<Metadata>,
+    /// The error that ended the RPC.
+    error: Option<ClientError>,
+}
+
+impl Drained {
+    /// Get the number of messages that were drained.
+    pub fn messages(&self) -> usize {
+        self.messages
+    }
+
+    /// Get the trailers received in the EndStream frame, if any.
+    pub fn trailers(&self) -> Option<&Metadata> {
+        self.trailers.as_ref()
+    }
+
+    /// Get the error that ended the RPC, if it failed.
+    ///
+    /// This is the error of the EndStream frame, or the error that otherwise
+    /// ended the stream while draining.
+    pub fn error(&self) -> Option<&ClientError> {
+        self.error.as_ref()
+    }
+
+    /// Check if the RPC ended without an error.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Convert into the final status of the RPC: the trailers on success,
+    /// or the error it ended with.
+    pub fn into_result(self) -> Result<Option<Metadata>, ClientError> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.trailers),
+        }
+    }
+}
+
+/// Adapters over the received messages.
+impl<S, T> Streaming<S>
+where
+    S: Stream<Item = Result<T, ClientError>> + Unpin,
+{
+    /// Map each received message with `f`, passing errors through.
+    ///
+    /// The returned stream still gives access to this `Streaming`, e.g. for
+    /// trailers, via [`MapOk::get_ref`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut names = response.into_inner().map_ok(|item: ListItem| item.name);
+    /// while let Some(name) = names.next().await {
+    ///     println!("{}", name?);
+    /// }
+    /// ```
+    pub fn map_ok<U, F>(self, f: F) -> MapOk<Self, F>
+    where
+        F: FnMut(T) -> U,
+    {
+        MapOk::new(self, f)
+    }
+
+    /// Skip received messages for which `predicate` returns `false`,
+    /// passing errors through.
+    pub fn try_filter<F>(self, predicate: F) -> TryFilter<Self, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        TryFilter::new(self, predicate)
+    }
+
+    /// Read the byte chunks `f` extracts from the received messages as an
+    /// `AsyncRead`.
+    ///
+    /// Turns a chunked download RPC into a reader, e.g. for
+    /// `tokio::io::copy`. An error of the stream fails the read with an
+    /// [`std::io::Error`] wrapping the [`ClientError`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let response = client
+    ///     .call_server_stream::<DownloadRequest, Chunk>("files.v1.FileService/Download", &req)
+    ///     .await?;
+    /// let mut reader = response.into_inner().into_async_read(|chunk: Chunk| chunk.data);
+    /// tokio::io::copy(&mut reader, &mut file).await?;
+    /// ```
+    pub fn into_async_read<B, F>(self, f: F) -> StreamingReader<Self, F>
+    where
+        F: FnMut(T) -> B,
+        B: Into<bytes::Bytes>,
+    {
+        StreamingReader::new(self, f)
+    }
 }
 
 impl<S, T> Stream for Streaming<S>
@@ -222,8 +391,13 @@ where
 {
     type Item = Result<T, ClientError>;
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        Pin::new(&mut self.inner).poll_next(cx)
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let poll = this.poll_message(cx);
+        if let Poll::Ready(item) = &poll {
+            this.record(item);
+        }
+        poll
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -231,6 +405,54 @@ where
     }
 }
 
+impl<S, T> Streaming<S>
+where
+    S: Stream<Item = Result<T, ClientError>> + Unpin,
+{
+    /// Record the end of the call in its metrics, if `item` ends it.
+    fn record(&self, item: &Option<Result<T, ClientError>>) {
+        if let Some(metrics) = &self.metrics {
+            match item {
+                Some(Ok(_)) => {}
+                Some(Err(e)) => metrics.finish(e.code()),
+                None => metrics.finish(Code::Ok),
+            }
+        }
+    }
+
+    /// Poll the next message, within the per-message deadline if set.
+    fn poll_message(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<T, ClientError>>> {
+        let this = self;
+        if this.timed_out {
+            return Poll::Ready(None);
+        }
+
+        let poll = Pin::new(&mut this.inner).poll_next(cx);
+        let Some(timeout) = this.message_timeout else {
+            return poll;
+        };
+        if poll.is_ready() {
+            this.sleep = None;
+            return poll;
+        }
+
+        let sleep = this
+            .sleep
+            .get_or_insert_with(|| Box::pin(crate::rt::sleep(timeout)));
+        match sleep.as_mut().poll(cx) {
+            Poll::Ready(()) => {
+                this.timed_out = true;
+                this.sleep = None;
+                Poll::Ready(Some(Err(ClientError::new(
+                    Code::DeadlineExceeded,
+                    format!("no message received within {:?}", timeout),
+                ))))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,6 +582,35 @@ mod tests {
         assert_eq!(trailers.get("x-custom"), Some("value"));
     }
 
+    #[tokio::test]
+    async fn test_streaming_message_timeout() {
+        use std::time::Duration;
+
+        /// Yields a message after each of `delays`.
+        fn delayed(delays: &[u64]) -> impl Stream<Item = Result<u64, ClientError>> + Unpin + use<> {
+            Box::pin(stream::iter(delays.to_vec()).then(|ms| async move {
+                tokio::time::sleep(Duration::from_millis(ms)).await;
+                Ok(ms)
+            }))
+        }
+
+        let mut streaming = Streaming::new(delayed(&[10, 20, 500, 10]))
+            .with_message_timeout(Duration::from_millis(100));
+        assert_eq!(streaming.next().await.unwrap().unwrap(), 10);
+        assert_eq!(streaming.next().await.unwrap().unwrap(), 20);
+        let err = streaming.next().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::DeadlineExceeded);
+        assert!(streaming.next().await.is_none());
+
+        // Time between polls doesn't count
+        let mut streaming =
+            Streaming::new(delayed(&[10, 10])).with_message_timeout(Duration::from_millis(100));
+        assert_eq!(streaming.next().await.unwrap().unwrap(), 10);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(streaming.next().await.unwrap().unwrap(), 10);
+        assert!(streaming.next().await.is_none());
+    }
+
     #[tokio::test]
     async fn test_streaming_drain() {
         // Create multiple message frames and end frame
@@ -385,7 +636,8 @@ mod tests {
 
         // Drain remaining messages (should drain msg2 and msg3)
         let drained = streaming.drain().await;
-        assert_eq!(drained, 2);
+        assert_eq!(drained.messages(), 2);
+        assert!(drained.is_ok());
 
         // Stream should be finished
         assert!(streaming.is_finished());
@@ -410,9 +662,50 @@ mod tests {
         let result = streaming
             .drain_timeout(std::time::Duration::from_secs(5))
             .await;
-        assert_eq!(result, Ok(1)); // One message drained
+        let drained = result.unwrap();
+        assert_eq!(drained.messages(), 1); // One message drained
 
         // Stream should be finished
         assert!(streaming.is_finished());
     }
+
+    #[tokio::test]
+    async fn test_streaming_drain_end_stream() {
+        let frame = make_frame(0x00, br#"{"value":"msg1"}"#);
+        let end_payload = br#"{"error":{"code":"resource_exhausted","message":"quota"},"metadata":{"x-custom":["value"]}}"#;
+        let end_frame = make_frame(0x02, end_payload);
+
+        let mut all_data = frame.to_vec();
+        all_data.extend_from_slice(&end_frame);
+
+        let byte_stream = stream::iter(vec![Ok::<_, ClientError>(Bytes::from(all_data))]);
+        let decoder =
+            FrameDecoder::<_, TestMessage>::new(byte_stream, false, CompressionEncoding::Identity);
+        let mut streaming = Streaming::new(decoder);
+
+        let drained = streaming.drain().await;
+        assert_eq!(drained.messages(), 1);
+        assert!(!drained.is_ok());
+        assert_eq!(drained.error().unwrap().code(), Code::ResourceExhausted);
+        assert_eq!(drained.trailers().unwrap().get("x-custom"), Some("value"));
+
+        // The trailers are still on the stream
+        assert!(streaming.trailers().is_some());
+        let err = drained.into_result().unwrap_err();
+        assert_eq!(err.message(), Some("quota"));
+
+        // A clean end yields the trailers
+        let end_frame = make_frame(0x02, br#"{"metadata":{"x-custom":["value"]}}"#);
+        let byte_stream = stream::iter(vec![Ok::<_, ClientError>(end_frame)]);
+        let decoder =
+            FrameDecoder::<_, TestMessage>::new(byte_stream, false, CompressionEncoding::Identity);
+        let trailers = Streaming::new(decoder)
+            .drain_timeout(std::time::Duration::from_secs(5))
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap()
+            .unwrap();
+        assert_eq!(trailers.get("x-custom"), Some("value"));
+    }
 }
@@ -1,11 +1,15 @@
 //! Response types for Connect client.
 //!
 //! This module provides the [`ConnectResponse`] type which wraps RPC responses
-//! along with metadata (headers) from the server.
+//! along with metadata (headers and trailers) from the server.
 
-use http::HeaderMap;
+use base64::Engine;
+use http::{HeaderMap, HeaderName};
 use std::ops::Deref;
 
+/// Prefix of the response headers carrying unary Connect trailers.
+const TRAILER_PREFIX: &str = "trailer-";
+
 /// Response wrapper for Connect RPC client calls.
 ///
 /// Contains the response message and associated metadata (HTTP headers)
@@ -107,15 +111,49 @@ impl<T> AsRef<T> for ConnectResponse<T> {
 /// Response metadata wrapper around HTTP headers.
 ///
 /// Provides convenient access to response headers returned by the server.
+/// The accessors (`get`, `contains`, ...) read the headers; trailers are
+/// available separately via [`trailers()`](Self::trailers).
+///
+/// For unary Connect calls, trailers arrive as `Trailer-`-prefixed headers
+/// and are exposed without the prefix. For gRPC and for client-streaming
+/// calls, they are the HTTP trailers or EndStream metadata.
 #[derive(Debug, Clone, Default)]
 pub struct Metadata {
     headers: HeaderMap,
+    trailers: HeaderMap,
 }
 
 impl Metadata {
     /// Create new metadata from HTTP headers.
     pub fn new(headers: HeaderMap) -> Self {
-        Self { headers }
+        Self {
+            headers,
+            trailers: HeaderMap::new(),
+        }
+    }
+
+    /// Set the trailers.
+    pub fn with_trailers(mut self, trailers: HeaderMap) -> Self {
+        self.trailers = trailers;
+        self
+    }
+
+    /// Create metadata from the headers of a unary Connect response, moving
+    /// `Trailer-`-prefixed headers into the trailers without the prefix.
+    pub(crate) fn from_unary_headers(response_headers: HeaderMap) -> Self {
+        let mut headers = HeaderMap::with_capacity(response_headers.len());
+        let mut trailers = HeaderMap::new();
+        for (name, value) in response_headers.iter() {
+            match name
+                .as_str()
+                .strip_prefix(TRAILER_PREFIX)
+                .and_then(|trailer| HeaderName::from_bytes(trailer.as_bytes()).ok())
+            {
+                Some(trailer) => trailers.append(trailer, value.clone()),
+                None => headers.append(name.clone(), value.clone()),
+            };
+        }
+        Self { headers, trailers }
     }
 
     /// Create empty metadata.
@@ -135,6 +173,15 @@ impl Metadata {
         self.headers.get(key).map(|v| v.as_bytes())
     }
 
+    /// Get a binary header value, such as `x-trace-bin`.
+    ///
+    /// Binary metadata (keys ending in `-bin`) is sent base64-encoded; this
+    /// decodes it, accepting values with or without padding. Returns `None`
+    /// if the header is not present or is not valid base64.
+    pub fn get_bin(&self, key: &str) -> Option<Vec<u8>> {
+        self.headers.get(key).and_then(|v| decode_bin(v.as_bytes()))
+    }
+
     /// Check if a header exists.
     pub fn contains(&self, key: &str) -> bool {
         self.headers.contains_key(key)
@@ -179,6 +226,41 @@ impl Metadata {
     pub fn len(&self) -> usize {
         self.headers.len()
     }
+
+    /// Get the trailers.
+    pub fn trailers(&self) -> &HeaderMap {
+        &self.trailers
+    }
+
+    /// Get a mutable reference to the trailers.
+    pub fn trailers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.trailers
+    }
+
+    /// Get a trailer value by name.
+    ///
+    /// Returns `None` if the trailer is not present or cannot be converted to a string.
+    pub fn get_trailer(&self, key: &str) -> Option<&str> {
+        self.trailers.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    /// Get a binary trailer value, decoded like [`get_bin()`](Self::get_bin).
+    pub fn get_trailer_bin(&self, key: &str) -> Option<Vec<u8>> {
+        self.trailers
+            .get(key)
+            .and_then(|v| decode_bin(v.as_bytes()))
+    }
+}
+
+/// Decode a base64 binary metadata value, padding optional.
+pub(crate) fn decode_bin(value: &[u8]) -> Option<Vec<u8>> {
+    let unpadded = value
+        .iter()
+        .rposition(|&b| b != b'=')
+        .map_or(&value[..0], |end| &value[..=end]);
+    base64::engine::general_purpose::STANDARD_NO_PAD
+        .decode(unpadded)
+        .ok()
 }
 
 impl From<HeaderMap> for Metadata {
@@ -245,6 +327,35 @@ mod tests {
         assert!(!metadata.contains("x-absent"));
     }
 
+    #[test]
+    fn test_metadata_from_unary_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-custom", HeaderValue::from_static("value"));
+        headers.insert("trailer-x-checksum", HeaderValue::from_static("abc"));
+        headers.append("trailer-x-page", HeaderValue::from_static("1"));
+        headers.append("trailer-x-page", HeaderValue::from_static("2"));
+        let metadata = Metadata::from_unary_headers(headers);
+
+        assert_eq!(metadata.get("x-custom"), Some("value"));
+        assert!(!metadata.contains("trailer-x-checksum"));
+        assert_eq!(metadata.get_trailer("x-checksum"), Some("abc"));
+        assert_eq!(metadata.trailers().get_all("x-page").iter().count(), 2);
+    }
+
+    #[test]
+    fn test_metadata_get_bin() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-trace-bin", HeaderValue::from_static("AQID"));
+        headers.insert("x-padded-bin", HeaderValue::from_static("AQ=="));
+        headers.insert("x-bad-bin", HeaderValue::from_static("not base64!"));
+        let metadata = Metadata::new(headers);
+
+        assert_eq!(metadata.get_bin("x-trace-bin"), Some(vec![1, 2, 3]));
+        assert_eq!(metadata.get_bin("x-padded-bin"), Some(vec![1]));
+        assert_eq!(metadata.get_bin("x-bad-bin"), None);
+        assert_eq!(metadata.get_bin("missing-bin"), None);
+    }
+
     #[test]
     fn test_connect_response_into_parts() {
         let mut headers = HeaderMap::new();
@@ -0,0 +1,95 @@
+//! Timers and randomness for the target runtime.
+//!
+//! Natively these are tokio's timers and `rand`. On `wasm32` there is no
+//! tokio runtime, so timers are driven by the JavaScript event loop
+//! (`setTimeout`), randomness comes from `Math.random()` and the clock is
+//! `Date.now()`.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::*;
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub(crate) use wasm::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::OnceLock;
+    use std::time::{Duration, Instant};
+
+    pub(crate) use tokio::time::{sleep, timeout};
+
+    /// A boxed [`sleep`] future, for types that store one.
+    pub(crate) type BoxSleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    /// A random number in `[0, 1)`.
+    pub(crate) fn random() -> f64 {
+        rand::random::<f64>()
+    }
+
+    /// Monotonic time since an arbitrary, fixed starting point.
+    pub(crate) fn now() -> Duration {
+        static START: OnceLock<Instant> = OnceLock::new();
+        START.get_or_init(Instant::now).elapsed()
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+mod wasm {
+    use std::future::Future;
+    use std::time::Duration;
+
+    use futures::future::{Either, select};
+    use js_sys::{Function, Promise};
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen_futures::JsFuture;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_name = setTimeout)]
+        fn set_timeout(handler: &Function, timeout: i32) -> JsValue;
+    }
+
+    /// A boxed [`sleep`] future, for types that store one. JavaScript
+    /// promises aren't `Send`.
+    pub(crate) type BoxSleep = std::pin::Pin<Box<dyn Future<Output = ()>>>;
+
+    /// Error returned by [`timeout`] when the deadline elapses first.
+    #[derive(Debug)]
+    pub(crate) struct Elapsed;
+
+    /// Wait until `duration` has elapsed.
+    pub(crate) async fn sleep(duration: Duration) {
+        let millis = duration.as_millis().min(i32::MAX as u128) as i32;
+        let promise = Promise::new(&mut |resolve, _reject| {
+            set_timeout(&resolve, millis);
+        });
+        // The promise is never rejected
+        let _ = JsFuture::from(promise).await;
+    }
+
+    /// Run `future`, giving up after `duration`.
+    pub(crate) async fn timeout<F: Future>(
+        duration: Duration,
+        future: F,
+    ) -> Result<F::Output, Elapsed> {
+        let future = std::pin::pin!(future);
+        let deadline = std::pin::pin!(sleep(duration));
+        match select(future, deadline).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(Elapsed),
+        }
+    }
+
+    /// A random number in `[0, 1)`.
+    pub(crate) fn random() -> f64 {
+        js_sys::Math::random()
+    }
+
+    /// Time since the Unix epoch. `std::time::Instant` is unavailable on
+    /// `wasm32-unknown-unknown`.
+    pub(crate) fn now() -> Duration {
+        Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+    }
+}
@@ -0,0 +1,135 @@
+//! W3C trace context propagation.
+//!
+//! With the `opentelemetry` feature, requests carry the
+//! [`traceparent`](https://www.w3.org/TR/trace-context/#traceparent-header)
+//! and `tracestate` headers of the current span's OpenTelemetry context, as
+//! recorded by a `tracing-opentelemetry` layer. The server's spans then join
+//! the caller's trace, without an interceptor.
+//!
+//! The headers are written from the span context directly, so no global
+//! propagator has to be installed.
+
+use http::{HeaderMap, HeaderValue};
+use opentelemetry::trace::TraceContextExt;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Header carrying the trace ID, parent span ID and trace flags.
+const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Header carrying vendor-specific trace state.
+const TRACESTATE_HEADER: &str = "tracestate";
+
+/// Insert the trace context of the current span into `headers`.
+///
+/// Does nothing if the span isn't tracked by an OpenTelemetry layer.
+pub(crate) fn inject_trace_context(headers: &mut HeaderMap) {
+    let context = tracing::Span::current().context();
+    let span = context.span();
+    let span_context = span.span_context();
+    if !span_context.is_valid() {
+        return;
+    }
+
+    let traceparent = format!(
+        "00-{:032x}-{:016x}-{:02x}",
+        span_context.trace_id(),
+        span_context.span_id(),
+        span_context.trace_flags().to_u8()
+    );
+    if let Ok(value) = HeaderValue::from_str(&traceparent) {
+        headers.insert(TRACEPARENT_HEADER, value);
+    }
+
+    let tracestate = span_context.trace_state().header();
+    if !tracestate.is_empty()
+        && let Ok(value) = HeaderValue::from_str(&tracestate)
+    {
+        headers.insert(TRACESTATE_HEADER, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TracerProvider;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn subscriber() -> impl tracing::Subscriber + Send + Sync {
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")))
+    }
+
+    #[test]
+    fn test_inject_trace_context() {
+        tracing::subscriber::with_default(subscriber(), || {
+            // Outside of a span
+            let mut headers = HeaderMap::new();
+            inject_trace_context(&mut headers);
+            assert!(headers.is_empty());
+
+            let span = tracing::info_span!("caller");
+            let _guard = span.enter();
+            let span_context = span.context().span().span_context().clone();
+
+            inject_trace_context(&mut headers);
+            assert_eq!(
+                headers[TRACEPARENT_HEADER],
+                format!(
+                    "00-{:032x}-{:016x}-01",
+                    span_context.trace_id(),
+                    span_context.span_id()
+                )
+            );
+            assert!(!headers.contains_key(TRACESTATE_HEADER));
+        });
+    }
+
+    #[tokio::test]
+    async fn test_client_sends_trace_context() {
+        use crate::{ClientError, ConnectClient, Transport, TransportBody};
+        use bytes::Bytes;
+        use std::sync::{Arc, Mutex};
+
+        /// Records the request headers, answering with an empty message.
+        #[derive(Clone, Default)]
+        struct RecordingTransport(Arc<Mutex<Option<HeaderMap>>>);
+
+        impl Transport for RecordingTransport {
+            type Body = http_body_util::Full<Bytes>;
+
+            async fn request(
+                &self,
+                request: http::Request<TransportBody>,
+            ) -> Result<http::Response<Self::Body>, ClientError> {
+                *self.0.lock().unwrap() = Some(request.headers().clone());
+                Ok(http::Response::builder()
+                    .header(http::header::CONTENT_TYPE, "application/proto")
+                    .body(http_body_util::Full::new(Bytes::new()))
+                    .unwrap())
+            }
+        }
+
+        let _default = tracing::subscriber::set_default(subscriber());
+        let transport = RecordingTransport::default();
+        let client = ConnectClient::builder("http://stub")
+            .use_proto()
+            .build_with_transport(transport.clone())
+            .unwrap();
+
+        let span = tracing::info_span!("caller");
+        let caller = span.context().span().span_context().clone();
+        let _guard = span.enter();
+        client
+            .call_unary::<(), ()>("test.Service/Method", &())
+            .await
+            .unwrap();
+
+        // The parent is the client's `rpc.call` span, in the caller's trace
+        let headers = transport.0.lock().unwrap().take().unwrap();
+        let traceparent = headers[TRACEPARENT_HEADER].to_str().unwrap().to_string();
+        let parts: Vec<_> = traceparent.split('-').collect();
+        assert_eq!(parts[1], format!("{:032x}", caller.trace_id()));
+        assert_ne!(parts[2], format!("{:016x}", caller.span_id()));
+    }
+}
@@ -1,13 +1,20 @@
 //! HTTP transport layer for Connect RPC client.
 //!
-//! This module provides the [`HyperTransport`] type, which handles HTTP communication
-//! using hyper_util's legacy client. It supports:
+//! On native targets this module provides the [`HyperTransport`] type, which
+//! handles HTTP communication using hyper_util's legacy client. It supports:
 //!
 //! - HTTP/1.1 and HTTP/2 with automatic protocol negotiation
-//! - TLS with rustls (feature-gated)
+//! - TLS with rustls (feature-gated), configurable via [`TlsClientConfig`]
 //! - Connection pooling
+//! - Cookies, e.g. for session affinity, via a [`CookieStore`]
 //! - Tower service integration for middleware
 //!
+//! [`LoopbackTransport`] calls a `tower::Service`, such as an axum `Router`,
+//! in-process instead, and [`LayeredTransport`] sends requests through
+//! tower middleware.
+//!
+//! [`Balancer`] spreads calls over several endpoints on every target.
+//!
 //! # Feature Flags
 //!
 //! TLS support requires enabling the appropriate features:
@@ -16,6 +23,10 @@
 //! - `tls-ring` / `tls-aws-lc` - Crypto providers
 //! - `tls-native-roots` / `tls-webpki-roots` - Root certificates
 //!
+//! On `wasm32` targets the `wasm` feature provides `FetchTransport` instead,
+//! which sends requests with the `fetch` API. TLS and connection management
+//! are then up to the browser.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -32,11 +43,38 @@
 //!     .build()?;
 //! ```
 
+use std::future::Future;
+
+use bytes::Bytes;
+use http_body::Body;
+
+use crate::ClientError;
+
+mod balancer;
 mod body;
+#[cfg(not(target_arch = "wasm32"))]
 mod connector;
+#[cfg(not(target_arch = "wasm32"))]
+mod cookie;
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+mod fetch;
+#[cfg(not(target_arch = "wasm32"))]
 mod hyper;
+#[cfg(not(target_arch = "wasm32"))]
+mod layered;
+#[cfg(not(target_arch = "wasm32"))]
+mod limit;
+#[cfg(not(target_arch = "wasm32"))]
+mod loopback;
+#[cfg(not(target_arch = "wasm32"))]
+mod tls;
 
+pub use balancer::{
+    BalancePolicy, Balancer, DEFAULT_UNHEALTHY_DURATION, Endpoint, LeastInFlight, PickFirst,
+    RoundRobin,
+};
 pub use body::TransportBody;
+#[cfg(not(target_arch = "wasm32"))]
 pub use connector::{
     // Traits
     CryptoProvider,
@@ -54,22 +92,126 @@ pub use connector::{
 };
 
 // Feature-gated exports
-#[cfg(feature = "tls-ring")]
+#[cfg(all(not(target_arch = "wasm32"), feature = "tls-ring"))]
 pub use connector::RingProvider;
 
-#[cfg(feature = "tls-aws-lc")]
+#[cfg(all(not(target_arch = "wasm32"), feature = "tls-aws-lc"))]
 pub use connector::AwsLcProvider;
 
-#[cfg(feature = "tls-native-roots")]
+#[cfg(all(not(target_arch = "wasm32"), feature = "tls-native-roots"))]
 pub use connector::NativeRoots;
 
-#[cfg(feature = "tls-webpki-roots")]
+#[cfg(all(not(target_arch = "wasm32"), feature = "tls-webpki-roots"))]
 pub use connector::WebpkiRoots;
 
-#[cfg(any(feature = "tls-native-roots", feature = "tls-webpki-roots"))]
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "tls-native-roots", feature = "tls-webpki-roots")
+))]
 pub use connector::default_tls_config;
 
-pub use hyper::{HyperTransport, HyperTransportBuilder};
+#[cfg(not(target_arch = "wasm32"))]
+pub use cookie::{CookieJar, CookieStore};
+#[cfg(not(target_arch = "wasm32"))]
+pub use hyper::{
+    DEFAULT_HAPPY_EYEBALLS_TIMEOUT, HttpVersionPolicy, HyperTransport, HyperTransportBuilder,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use layered::LayeredTransport;
+#[cfg(not(target_arch = "wasm32"))]
+pub use loopback::LoopbackTransport;
+#[cfg(not(target_arch = "wasm32"))]
+pub use tls::{TlsClientConfig, TlsVersion};
+
+// Re-export rustls types that users might need for low-level TLS configuration
+#[cfg(not(target_arch = "wasm32"))]
+pub use rustls::ClientConfig as RustlsClientConfig;
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub use fetch::{FetchBody, FetchTransport};
+
+/// HTTP client that [`ConnectClient`](crate::ConnectClient) sends its
+/// requests with.
+///
+/// [`ConnectClient`](crate::ConnectClient) is generic over its transport,
+/// defaulting to [`DefaultTransport`]. Implement this trait to use another
+/// HTTP stack, a proxy, or a test double, and pass it to
+/// [`ClientBuilder::build_with_transport`](crate::ClientBuilder::build_with_transport).
+///
+/// Client-streaming and bidi calls send a [`TransportBody::Streaming`]
+/// body, and gRPC needs HTTP/2 with trailers; a transport that can't do
+/// this should fail such requests with [`ClientError::Transport`].
+///
+/// # Example
+///
+/// ```ignore
+/// use connectrpc_axum_client::{ClientError, HyperTransport, Transport, TransportBody};
+/// use hyper::body::Incoming;
+///
+/// /// Logs every request before sending it.
+/// #[derive(Clone)]
+/// struct LoggingTransport(HyperTransport);
+///
+/// impl Transport for LoggingTransport {
+///     type Body = Incoming;
+///
+///     async fn request(
+///         &self,
+///         request: http::Request<TransportBody>,
+///     ) -> Result<http::Response<Incoming>, ClientError> {
+///         println!("{} {}", request.method(), request.uri());
+///         self.0.request(request).await
+///     }
+/// }
+///
+/// let client = ClientBuilder::new("http://localhost:3000")
+///     .build_with_transport(LoggingTransport(HyperTransport::new()?))?;
+/// ```
+pub trait Transport: Clone + 'static {
+    /// Body of the responses.
+    type Body: Body<Data = Bytes, Error: std::fmt::Display> + Unpin + 'static;
+
+    /// Send a request, resolving once the response headers arrive.
+    ///
+    /// Dropping the returned future, or the response body, should cancel
+    /// the request.
+    fn request(
+        &self,
+        request: http::Request<TransportBody>,
+    ) -> impl Future<Output = Result<http::Response<Self::Body>, ClientError>>;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Transport for HyperTransport {
+    type Body = ::hyper::body::Incoming;
+
+    fn request(
+        &self,
+        request: http::Request<TransportBody>,
+    ) -> impl Future<Output = Result<http::Response<Self::Body>, ClientError>> {
+        HyperTransport::request(self, request)
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+impl Transport for FetchTransport {
+    type Body = FetchBody;
+
+    fn request(
+        &self,
+        request: http::Request<TransportBody>,
+    ) -> impl Future<Output = Result<http::Response<Self::Body>, ClientError>> {
+        FetchTransport::request(self, request)
+    }
+}
 
-// Re-export rustls types that users might need for TLS configuration
-pub use rustls::ClientConfig as TlsClientConfig;
+/// The transport [`ConnectClient`](crate::ConnectClient) uses unless
+/// another is given: [`HyperTransport`] natively, `FetchTransport` on
+/// `wasm32`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type DefaultTransport = HyperTransport;
+/// The transport [`ConnectClient`](crate::ConnectClient) uses unless
+/// another is given: `HyperTransport` natively, [`FetchTransport`] on
+/// `wasm32`.
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub type DefaultTransport = FetchTransport;
@@ -0,0 +1,332 @@
+//! Client-side load balancing across several endpoints.
+//!
+//! A [`Balancer`] holds the base URLs of a set of equivalent servers and
+//! picks one for each call with a [`BalancePolicy`]. Endpoints whose
+//! requests fail with a transport error are skipped for a while, so a dead
+//! server stops receiving calls until it has had time to come back.
+//!
+//! ```ignore
+//! use connectrpc_axum_client::{Balancer, ClientBuilder, LeastInFlight};
+//!
+//! let client = ClientBuilder::new("http://backend")
+//!     .with_balancer(
+//!         Balancer::new(["http://10.0.0.1:3000", "http://10.0.0.2:3000"])
+//!             .policy(LeastInFlight),
+//!     )
+//!     .build()?;
+//! ```
+//!
+//! To follow a changing set of servers, e.g. from DNS or a service registry,
+//! keep a clone of the balancer and call [`Balancer::set_endpoints`] when the
+//! set changes.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+/// Default time an endpoint is skipped after a transport error.
+pub const DEFAULT_UNHEALTHY_DURATION: Duration = Duration::from_secs(10);
+
+/// A server a [`Balancer`] can send calls to.
+#[derive(Debug)]
+pub struct Endpoint {
+    url: String,
+    in_flight: AtomicUsize,
+    /// Skipped until this `rt::now()` time.
+    unhealthy_until: Mutex<Option<Duration>>,
+}
+
+impl Endpoint {
+    fn new(url: String) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            in_flight: AtomicUsize::new(0),
+            unhealthy_until: Mutex::new(None),
+        }
+    }
+
+    /// The base URL of the endpoint.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Number of requests sent to the endpoint that are waiting for their
+    /// response headers.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Returns `false` while the endpoint is skipped after a transport error.
+    pub fn is_healthy(&self) -> bool {
+        let until = *self
+            .unhealthy_until
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        until.is_none_or(|until| crate::rt::now() >= until)
+    }
+}
+
+/// Strategy for picking the endpoint of a call.
+///
+/// Implement this to plug in your own strategy, e.g. weighted or
+/// locality-aware picking.
+pub trait BalancePolicy: std::fmt::Debug + Send + Sync + 'static {
+    /// Pick one of `candidates`, returning its index.
+    ///
+    /// `candidates` is never empty. It holds the healthy endpoints in the
+    /// configured order, or all endpoints if none is healthy.
+    fn pick(&self, candidates: &[&Endpoint]) -> usize;
+}
+
+/// Send calls to each endpoint in turn (the default).
+#[derive(Debug, Default)]
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl BalancePolicy for RoundRobin {
+    fn pick(&self, candidates: &[&Endpoint]) -> usize {
+        self.next.fetch_add(1, Ordering::Relaxed) % candidates.len()
+    }
+}
+
+/// Send every call to the first healthy endpoint, moving on to the next
+/// only while it is unhealthy.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PickFirst;
+
+impl BalancePolicy for PickFirst {
+    fn pick(&self, _candidates: &[&Endpoint]) -> usize {
+        0
+    }
+}
+
+/// Send each call to the endpoint with the fewest requests in flight.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LeastInFlight;
+
+impl BalancePolicy for LeastInFlight {
+    fn pick(&self, candidates: &[&Endpoint]) -> usize {
+        candidates
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, endpoint)| endpoint.in_flight())
+            .map_or(0, |(index, _)| index)
+    }
+}
+
+/// Spreads calls over several endpoints.
+///
+/// Configure one with
+/// [`ClientBuilder::with_balancer`](crate::ClientBuilder::with_balancer).
+/// The balancer is cheap to clone; clones share their endpoints and state.
+///
+/// # Default Values
+///
+/// - `policy`: [`RoundRobin`]
+/// - `unhealthy_duration`: 10 seconds
+#[derive(Clone, Debug)]
+pub struct Balancer {
+    endpoints: Arc<RwLock<Arc<[Arc<Endpoint>]>>>,
+    policy: Arc<dyn BalancePolicy>,
+    unhealthy_duration: Duration,
+}
+
+impl Balancer {
+    /// Create a balancer over the given base URLs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `urls` is empty.
+    pub fn new<I, S>(urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            endpoints: Arc::new(RwLock::new(endpoints(urls))),
+            policy: Arc::new(RoundRobin::default()),
+            unhealthy_duration: DEFAULT_UNHEALTHY_DURATION,
+        }
+    }
+
+    /// Set the strategy for picking endpoints.
+    pub fn policy(mut self, policy: impl BalancePolicy) -> Self {
+        self.policy = Arc::new(policy);
+        self
+    }
+
+    /// Set how long an endpoint is skipped after a transport error.
+    pub fn unhealthy_duration(mut self, duration: Duration) -> Self {
+        self.unhealthy_duration = duration;
+        self
+    }
+
+    /// Replace the endpoints, e.g. after resolving the service again.
+    ///
+    /// Endpoints that are kept keep their state. Requests already sent to
+    /// removed endpoints complete normally.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `urls` is empty.
+    pub fn set_endpoints<I, S>(&self, urls: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut current = self.endpoints.write().unwrap_or_else(|e| e.into_inner());
+        let updated: Vec<_> = endpoints(urls)
+            .iter()
+            .map(|new| {
+                current
+                    .iter()
+                    .find(|old| old.url == new.url)
+                    .unwrap_or(new)
+                    .clone()
+            })
+            .collect();
+        *current = updated.into();
+    }
+
+    /// The current endpoints.
+    pub fn endpoints(&self) -> Vec<Arc<Endpoint>> {
+        self.endpoints
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .to_vec()
+    }
+
+    /// Pick the endpoint of a call. The returned guard counts the request as
+    /// in flight until dropped.
+    pub(crate) fn pick(&self) -> Picked {
+        let endpoints = self
+            .endpoints
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let healthy: Vec<usize> = (0..endpoints.len())
+            .filter(|&index| endpoints[index].is_healthy())
+            .collect();
+        // With no healthy endpoint, try them all rather than failing
+        let indices = if healthy.is_empty() {
+            (0..endpoints.len()).collect()
+        } else {
+            healthy
+        };
+        let candidates: Vec<&Endpoint> = indices.iter().map(|&index| &*endpoints[index]).collect();
+        let picked = self.policy.pick(&candidates).min(indices.len() - 1);
+        let endpoint = endpoints[indices[picked]].clone();
+
+        endpoint.in_flight.fetch_add(1, Ordering::Relaxed);
+        Picked {
+            endpoint,
+            unhealthy_duration: self.unhealthy_duration,
+        }
+    }
+}
+
+/// Build endpoints from base URLs.
+fn endpoints<I, S>(urls: I) -> Arc<[Arc<Endpoint>]>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let endpoints: Arc<[Arc<Endpoint>]> = urls
+        .into_iter()
+        .map(|url| Arc::new(Endpoint::new(url.into())))
+        .collect();
+    assert!(
+        !endpoints.is_empty(),
+        "a balancer needs at least one endpoint"
+    );
+    endpoints
+}
+
+/// An endpoint picked for a request.
+#[derive(Debug)]
+pub(crate) struct Picked {
+    endpoint: Arc<Endpoint>,
+    unhealthy_duration: Duration,
+}
+
+impl Picked {
+    /// The base URL of the picked endpoint.
+    pub(crate) fn url(&self) -> &str {
+        &self.endpoint.url
+    }
+
+    /// Skip the endpoint for a while, after a transport error.
+    pub(crate) fn mark_unhealthy(&self) {
+        let mut until = self
+            .endpoint
+            .unhealthy_until
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        *until = Some(crate::rt::now() + self.unhealthy_duration);
+    }
+}
+
+impl Drop for Picked {
+    fn drop(&mut self) {
+        self.endpoint.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(picks: &[Picked]) -> Vec<&str> {
+        picks.iter().map(Picked::url).collect()
+    }
+
+    #[test]
+    fn test_round_robin_skips_unhealthy() {
+        let balancer = Balancer::new(["http://a", "http://b/", "http://c"]);
+        let picks: Vec<_> = (0..4).map(|_| balancer.pick()).collect();
+        assert_eq!(
+            urls(&picks),
+            ["http://a", "http://b", "http://c", "http://a"]
+        );
+
+        picks[1].mark_unhealthy();
+        let picks: Vec<_> = (0..2).map(|_| balancer.pick()).collect();
+        assert!(!urls(&picks).contains(&"http://b"));
+
+        // With every endpoint unhealthy, all are tried
+        for pick in &picks {
+            pick.mark_unhealthy();
+        }
+        assert!(!balancer.endpoints().iter().any(|e| e.is_healthy()));
+        balancer.pick();
+    }
+
+    #[test]
+    fn test_pick_first_and_least_in_flight() {
+        let balancer = Balancer::new(["http://a", "http://b"]).policy(PickFirst);
+        let first = balancer.pick();
+        assert_eq!(balancer.pick().url(), "http://a");
+        first.mark_unhealthy();
+        assert_eq!(balancer.pick().url(), "http://b");
+
+        let balancer = Balancer::new(["http://a", "http://b"]).policy(LeastInFlight);
+        let a = balancer.pick();
+        let b = balancer.pick();
+        assert_eq!((a.url(), b.url()), ("http://a", "http://b"));
+        drop(a);
+        assert_eq!(balancer.pick().url(), "http://a");
+        assert_eq!(balancer.endpoints()[1].in_flight(), 1);
+    }
+
+    #[test]
+    fn test_set_endpoints_keeps_state() {
+        let balancer = Balancer::new(["http://a", "http://b"]);
+        let kept = balancer.endpoints()[1].clone();
+
+        balancer.set_endpoints(["http://b", "http://c"]);
+        let endpoints = balancer.endpoints();
+        assert!(Arc::ptr_eq(&endpoints[0], &kept));
+        assert_eq!(endpoints[1].url(), "http://c");
+    }
+}
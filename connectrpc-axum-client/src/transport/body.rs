@@ -7,33 +7,29 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use bytes::Bytes;
+#[cfg(not(target_arch = "wasm32"))]
 use futures::Stream;
 use http_body::{Body, Frame};
-use pin_project_lite::pin_project;
 
 use crate::ClientError;
 
-pin_project! {
-    /// A request body for Connect RPC calls.
-    ///
-    /// This type can represent:
-    /// - Empty bodies (for some GET requests)
-    /// - Full bodies (for unary requests with complete message)
-    /// - Streaming bodies (for client/bidi streaming requests)
-    #[project = TransportBodyProj]
-    pub enum TransportBody {
-        /// Empty request body.
-        Empty,
-        /// Full request body with all data available.
-        Full {
-            data: Option<Bytes>,
-        },
-        /// Streaming request body from an async stream.
-        Streaming {
-            #[pin]
-            stream: Pin<Box<dyn Stream<Item = Result<Bytes, ClientError>> + Send>>,
-        },
-    }
+/// A request body for Connect RPC calls.
+///
+/// This type can represent:
+/// - Empty bodies (for some GET requests)
+/// - Full bodies (for unary requests with complete message)
+/// - Streaming bodies (for client/bidi streaming requests), except on
+///   wasm32, where `fetch` can't stream request bodies
+pub enum TransportBody {
+    /// Empty request body.
+    Empty,
+    /// Full request body with all data available.
+    Full { data: Option<Bytes> },
+    /// Streaming request body from an async stream.
+    #[cfg(not(target_arch = "wasm32"))]
+    Streaming {
+        stream: Pin<Box<dyn Stream<Item = Result<Bytes, ClientError>> + Send>>,
+    },
 }
 
 impl TransportBody {
@@ -48,6 +44,7 @@ impl TransportBody {
     }
 
     /// Create a streaming body from the given stream.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn streaming<S>(stream: S) -> Self
     where
         S: Stream<Item = Result<Bytes, ClientError>> + Send + 'static,
@@ -62,17 +59,21 @@ impl Body for TransportBody {
     type Data = Bytes;
     type Error = ClientError;
 
+    // Only the streaming variant, which wasm32 lacks, needs the context
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
     fn poll_frame(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
-        match self.project() {
-            TransportBodyProj::Empty => Poll::Ready(None),
-            TransportBodyProj::Full { data } => {
+        // Every variant is `Unpin`; the stream is already pinned in its box
+        match self.get_mut() {
+            TransportBody::Empty => Poll::Ready(None),
+            TransportBody::Full { data } => {
                 let result = data.take().map(|d| Ok(Frame::data(d)));
                 Poll::Ready(result)
             }
-            TransportBodyProj::Streaming { stream } => match stream.poll_next(cx) {
+            #[cfg(not(target_arch = "wasm32"))]
+            TransportBody::Streaming { stream } => match stream.as_mut().poll_next(cx) {
                 Poll::Ready(Some(Ok(data))) => Poll::Ready(Some(Ok(Frame::data(data)))),
                 Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
                 Poll::Ready(None) => Poll::Ready(None),
@@ -85,6 +86,7 @@ impl Body for TransportBody {
         match self {
             TransportBody::Empty => true,
             TransportBody::Full { data } => data.is_none(),
+            #[cfg(not(target_arch = "wasm32"))]
             TransportBody::Streaming { .. } => false, // Can't know without polling
         }
     }
@@ -99,6 +101,7 @@ impl Body for TransportBody {
                     http_body::SizeHint::with_exact(0)
                 }
             }
+            #[cfg(not(target_arch = "wasm32"))]
             TransportBody::Streaming { .. } => http_body::SizeHint::default(),
         }
     }
@@ -118,6 +121,7 @@ impl std::fmt::Debug for TransportBody {
                 .debug_struct("TransportBody::Full")
                 .field("data_len", &data.as_ref().map(|d| d.len()))
                 .finish(),
+            #[cfg(not(target_arch = "wasm32"))]
             TransportBody::Streaming { .. } => write!(f, "TransportBody::Streaming"),
         }
     }
@@ -33,9 +33,10 @@
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_rustls::{FixedServerNameResolver, HttpsConnector, HttpsConnectorBuilder};
 use hyper_util::client::legacy::connect::HttpConnector;
 use rustls::ClientConfig;
+use rustls::pki_types::ServerName;
 
 // ============================================================================
 // Auto TLS Configuration (feature-gated)
@@ -53,42 +54,37 @@ pub const fn has_tls_support() -> bool {
         ))
 }
 
-/// Try to get a crypto provider ConfigBuilder.
+/// Try to get a crypto provider.
 ///
 /// Priority:
 /// 1. Feature-gated provider (tls-ring or tls-aws-lc)
 /// 2. User-installed global default provider
 /// 3. None if no provider available
-fn try_get_crypto_provider_builder()
--> Option<rustls::ConfigBuilder<ClientConfig, rustls::WantsVerifier>> {
+pub(crate) fn try_get_crypto_provider() -> Option<Arc<rustls::crypto::CryptoProvider>> {
     // Priority 1: feature-gated providers
     #[cfg(feature = "tls-ring")]
-    return Some({
-        let provider = Arc::new(rustls::crypto::ring::default_provider());
-        ClientConfig::builder_with_provider(provider)
-            .with_safe_default_protocol_versions()
-            .expect("safe default protocol versions should be valid")
-    });
+    return Some(Arc::new(rustls::crypto::ring::default_provider()));
 
     #[cfg(all(feature = "tls-aws-lc", not(feature = "tls-ring")))]
-    return Some({
-        let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
-        ClientConfig::builder_with_provider(provider)
-            .with_safe_default_protocol_versions()
-            .expect("safe default protocol versions should be valid")
-    });
+    return Some(Arc::new(rustls::crypto::aws_lc_rs::default_provider()));
 
     // Priority 2: global default (when no feature-gated provider)
     #[cfg(not(any(feature = "tls-ring", feature = "tls-aws-lc")))]
     {
-        rustls::crypto::CryptoProvider::get_default().map(|provider| {
-            ClientConfig::builder_with_provider(provider.clone())
-                .with_safe_default_protocol_versions()
-                .expect("safe default protocol versions should be valid")
-        })
+        rustls::crypto::CryptoProvider::get_default().cloned()
     }
 }
 
+/// Try to get a crypto provider ConfigBuilder with safe default protocol versions.
+fn try_get_crypto_provider_builder()
+-> Option<rustls::ConfigBuilder<ClientConfig, rustls::WantsVerifier>> {
+    try_get_crypto_provider().map(|provider| {
+        ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .expect("safe default protocol versions should be valid")
+    })
+}
+
 /// Build the default TLS configuration.
 ///
 /// Uses feature-gated root certificates (native or webpki) and either
@@ -105,30 +101,38 @@ pub fn default_tls_config() -> Option<ClientConfig> {
 
 /// Build the root certificate store from enabled features.
 #[cfg(any(feature = "tls-native-roots", feature = "tls-webpki-roots"))]
-fn build_root_store() -> rustls::RootCertStore {
+pub(crate) fn build_root_store() -> rustls::RootCertStore {
     let mut roots = rustls::RootCertStore::empty();
 
     // Load native roots if enabled (prefer native over webpki if both enabled)
     #[cfg(feature = "tls-native-roots")]
-    {
-        let native_certs = rustls_native_certs::load_native_certs();
-        if !native_certs.errors.is_empty() {
-            // Log errors but continue - some certs may have loaded successfully
-            #[cfg(feature = "tracing")]
-            tracing::debug!("errors loading native certs: {:?}", native_certs.errors);
-        }
-        roots.add_parsable_certificates(native_certs.certs);
-    }
+    add_native_roots(&mut roots);
 
     // Load webpki roots if enabled and native roots are not
     #[cfg(all(feature = "tls-webpki-roots", not(feature = "tls-native-roots")))]
-    {
-        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
-    }
+    add_webpki_roots(&mut roots);
 
     roots
 }
 
+/// Add the certificates of the platform's trust store to `roots`.
+#[cfg(feature = "tls-native-roots")]
+pub(crate) fn add_native_roots(roots: &mut rustls::RootCertStore) {
+    let native_certs = rustls_native_certs::load_native_certs();
+    if !native_certs.errors.is_empty() {
+        // Log errors but continue - some certs may have loaded successfully
+        #[cfg(feature = "tracing")]
+        tracing::debug!("errors loading native certs: {:?}", native_certs.errors);
+    }
+    roots.add_parsable_certificates(native_certs.certs);
+}
+
+/// Add the bundled Mozilla root certificates to `roots`.
+#[cfg(feature = "tls-webpki-roots")]
+pub(crate) fn add_webpki_roots(roots: &mut rustls::RootCertStore) {
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+}
+
 // ============================================================================
 // HTTPS Connector Builder
 // ============================================================================
@@ -148,7 +152,27 @@ fn build_root_store() -> rustls::RootCertStore {
 /// - No root certificate features enabled, OR
 /// - No crypto provider available (neither feature-gated nor global default)
 pub fn build_https_connector(tls_config: Option<ClientConfig>) -> HttpsConnector<HttpConnector> {
-    let config = match tls_config {
+    wrap_https_connector(tls_config, build_http_connector(), None, false)
+}
+
+/// Build an HTTPS connector over a configured [`HttpConnector`].
+///
+/// Same as [`build_https_connector`], but TCP connections are made by
+/// `http`, e.g. one with a connect timeout. With a `server_name`, it is sent
+/// as SNI and verified against the server certificate instead of the host of
+/// the URL.
+///
+/// ALPN protocols set in the config pick the HTTP versions to offer: `h2`
+/// and `http/1.1` are recognized, others are ignored. HTTP/2 is preferred
+/// when both are offered, and both are offered when neither is listed.
+/// With `http1_only`, only HTTP/1.1 is offered.
+pub(crate) fn wrap_https_connector(
+    tls_config: Option<ClientConfig>,
+    http: HttpConnector,
+    server_name: Option<ServerName<'static>>,
+    http1_only: bool,
+) -> HttpsConnector<HttpConnector> {
+    let mut config = match tls_config {
         Some(config) => config,
         None => {
             #[cfg(any(feature = "tls-native-roots", feature = "tls-webpki-roots"))]
@@ -177,11 +201,25 @@ pub fn build_https_connector(tls_config: Option<ClientConfig>) -> HttpsConnector
         }
     };
 
-    HttpsConnectorBuilder::new()
+    // The connector builder sets ALPN itself from the enabled HTTP versions
+    let alpn = std::mem::take(&mut config.alpn_protocols);
+    let offered = |protocol: &[u8]| alpn.iter().any(|p| p == protocol);
+
+    let builder = HttpsConnectorBuilder::new()
         .with_tls_config(config)
-        .https_or_http()
-        .enable_all_versions()
-        .build()
+        .https_or_http();
+    let builder = match server_name {
+        Some(name) => builder.with_server_name_resolver(FixedServerNameResolver::new(name)),
+        None => builder,
+    };
+    if http1_only {
+        return builder.enable_http1().wrap_connector(http);
+    }
+    match (offered(b"http/1.1"), offered(b"h2")) {
+        (true, false) => builder.enable_http1().wrap_connector(http),
+        (false, true) => builder.enable_http2().wrap_connector(http),
+        _ => builder.enable_all_versions().wrap_connector(http),
+    }
 }
 
 /// Build an HTTP-only connector (no TLS).
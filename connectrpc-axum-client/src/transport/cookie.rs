@@ -0,0 +1,335 @@
+//! Cookie storage for [`HyperTransport`](super::HyperTransport).
+//!
+//! Some deployments pin a client to a backend with a session-affinity cookie
+//! set by the load balancer. With a cookie store, the transport keeps the
+//! cookies of `Set-Cookie` response headers and sends them back, in a
+//! `Cookie` header, on later requests they apply to.
+//!
+//! [`CookieJar`] follows the matching rules of RFC 6265: a cookie is sent to
+//! the host that set it (or the hosts its `Domain` covers), under its `Path`,
+//! over HTTPS only if it is `Secure`, until it expires. Public suffixes
+//! aren't checked, so a server can set cookies for its parent domains.
+
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use http::{HeaderValue, Uri};
+
+/// Storage of the cookies a transport sends.
+///
+/// Implement this to persist cookies or share them with another HTTP
+/// client; [`CookieJar`] keeps them in memory.
+pub trait CookieStore: Send + Sync + 'static {
+    /// Store the cookies of the `Set-Cookie` headers of a response to `url`.
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Uri);
+
+    /// Get the `Cookie` header for a request to `url`, if any cookie applies.
+    fn cookies(&self, url: &Uri) -> Option<HeaderValue>;
+}
+
+/// In-memory [`CookieStore`].
+///
+/// # Example
+///
+/// ```ignore
+/// use connectrpc_axum_client::transport::{CookieJar, HyperTransportBuilder};
+/// use std::sync::Arc;
+///
+/// let jar = Arc::new(CookieJar::new());
+/// let transport = HyperTransportBuilder::new()
+///     .cookie_provider(jar.clone())
+///     .build()?;
+/// ```
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    cookies: Mutex<Vec<Cookie>>,
+}
+
+/// A stored cookie.
+#[derive(Debug, Clone)]
+struct Cookie {
+    name: String,
+    value: String,
+    /// Lowercase domain, without a leading dot.
+    domain: String,
+    /// Only sent to exactly `domain`, as no `Domain` attribute was set.
+    host_only: bool,
+    path: String,
+    secure: bool,
+    /// When the cookie expires, on the [`crate::rt::now`] clock; `None` for
+    /// session cookies.
+    expires_at: Option<Duration>,
+}
+
+impl Cookie {
+    /// Parse a `Set-Cookie` header of a response to `url`.
+    ///
+    /// Returns `None` if the header is malformed or sets a cookie for
+    /// another domain.
+    fn parse(header: &str, url: &Uri) -> Option<Self> {
+        let host = url.host()?.to_ascii_lowercase();
+        let mut parts = header.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            name: name.to_string(),
+            value: value.trim().to_string(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(url.path()),
+            secure: false,
+            expires_at: None,
+        };
+        let mut max_age = None;
+        let mut expires = None;
+        for attribute in parts {
+            let (key, value) = match attribute.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (attribute.trim(), ""),
+            };
+            if key.eq_ignore_ascii_case("domain") {
+                let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                if !domain.is_empty() {
+                    if !domain_matches(&host, &domain) {
+                        return None;
+                    }
+                    cookie.domain = domain;
+                    cookie.host_only = false;
+                }
+            } else if key.eq_ignore_ascii_case("path") {
+                if value.starts_with('/') {
+                    cookie.path = value.to_string();
+                }
+            } else if key.eq_ignore_ascii_case("secure") {
+                cookie.secure = true;
+            } else if key.eq_ignore_ascii_case("max-age") {
+                max_age = value.parse::<i64>().ok();
+            } else if key.eq_ignore_ascii_case("expires") {
+                expires = httpdate::parse_http_date(value).ok();
+            }
+        }
+
+        // Max-Age takes precedence over Expires; a past expiry deletes the
+        // cookie
+        let now = crate::rt::now();
+        cookie.expires_at = match (max_age, expires) {
+            (Some(secs), _) => Some(now.saturating_add(Duration::from_secs(secs.max(0) as u64))),
+            (None, Some(expires)) => Some(
+                now.saturating_add(
+                    expires
+                        .duration_since(SystemTime::now())
+                        .unwrap_or(Duration::ZERO),
+                ),
+            ),
+            (None, None) => None,
+        };
+        Some(cookie)
+    }
+
+    fn is_expired(&self, now: Duration) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Check if the cookie is sent with a request to `url`.
+    fn matches(&self, url: &Uri, host: &str) -> bool {
+        let domain = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(host, &self.domain)
+        };
+        domain
+            && path_matches(url.path(), &self.path)
+            && (!self.secure || url.scheme_str() == Some("https"))
+    }
+}
+
+/// Check if `host` is `domain` or one of its subdomains.
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain || (host.ends_with(domain) && host[..host.len() - domain.len()].ends_with('.'))
+}
+
+/// Check if a request to `path` is under the cookie path `cookie_path`.
+fn path_matches(path: &str, cookie_path: &str) -> bool {
+    let path = if path.is_empty() { "/" } else { path };
+    path == cookie_path
+        || (path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || path[cookie_path.len()..].starts_with('/')))
+}
+
+/// The path of a cookie set without a `Path` attribute: the directory of
+/// the request path.
+fn default_path(path: &str) -> String {
+    match path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(end) => path[..end].to_string(),
+    }
+}
+
+impl CookieJar {
+    /// Create an empty cookie jar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a cookie, given as a `Set-Cookie` header value, as if a response
+    /// to `url` had set it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let jar = CookieJar::new();
+    /// jar.add_cookie_str("session=abc; Path=/", &"https://api.example.com".parse()?);
+    /// ```
+    pub fn add_cookie_str(&self, cookie: &str, url: &Uri) {
+        if let Some(cookie) = Cookie::parse(cookie, url) {
+            self.store(cookie);
+        }
+    }
+
+    /// Remove all cookies.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Get the number of stored cookies, including expired ones not yet
+    /// removed.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Check if no cookies are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Cookie>> {
+        self.cookies.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Store `cookie`, replacing the one with the same name, domain and path.
+    fn store(&self, cookie: Cookie) {
+        let now = crate::rt::now();
+        let mut cookies = self.lock();
+        let replaced = |c: &Cookie| {
+            c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path
+        };
+        cookies.retain(|c| !c.is_expired(now) && !replaced(c));
+        if !cookie.is_expired(now) {
+            cookies.push(cookie);
+        }
+    }
+}
+
+impl CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Uri) {
+        for header in cookie_headers {
+            if let Ok(header) = header.to_str() {
+                self.add_cookie_str(header, url);
+            }
+        }
+    }
+
+    fn cookies(&self, url: &Uri) -> Option<HeaderValue> {
+        let host = url.host()?.to_ascii_lowercase();
+        let now = crate::rt::now();
+        let mut matching: Vec<_> = self
+            .lock()
+            .iter()
+            .filter(|c| !c.is_expired(now) && c.matches(url, &host))
+            .cloned()
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        // Cookies with longer paths are listed first
+        matching.sort_by_key(|c| std::cmp::Reverse(c.path.len()));
+        let header = matching
+            .iter()
+            .map(|c| format!("{}={}", c.name, c.value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        HeaderValue::from_str(&header).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uri(s: &str) -> Uri {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_cookie_jar_matching() {
+        let jar = CookieJar::new();
+        let origin = uri("https://api.example.com/pkg.Service/Method");
+        jar.add_cookie_str("affinity=a1; Path=/", &origin);
+        jar.add_cookie_str("service=s1", &origin);
+        jar.add_cookie_str("shared=x; Domain=.example.com; Path=/; Secure", &origin);
+        // Other domains can't be set
+        jar.add_cookie_str("evil=1; Domain=other.com", &origin);
+        assert_eq!(jar.len(), 3);
+
+        assert_eq!(
+            jar.cookies(&origin).unwrap(),
+            "service=s1; affinity=a1; shared=x"
+        );
+        // The default path is the service
+        assert_eq!(
+            jar.cookies(&uri("https://api.example.com/other.Service/Method"))
+                .unwrap(),
+            "affinity=a1; shared=x"
+        );
+        // Host-only cookies stay on their host; secure ones on HTTPS
+        assert_eq!(
+            jar.cookies(&uri("https://www.example.com/")).unwrap(),
+            "shared=x"
+        );
+        assert_eq!(
+            jar.cookies(&uri("http://api.example.com/")).unwrap(),
+            "affinity=a1"
+        );
+        assert!(jar.cookies(&uri("https://example.org/")).is_none());
+    }
+
+    #[test]
+    fn test_cookie_jar_replace_and_expire() {
+        let jar = CookieJar::new();
+        let origin = uri("http://localhost:3000/");
+        jar.add_cookie_str("a=1", &origin);
+        jar.add_cookie_str("a=2", &origin);
+        assert_eq!(jar.cookies(&origin).unwrap(), "a=2");
+
+        // Expired cookies are deleted
+        jar.add_cookie_str("a=3; Max-Age=0", &origin);
+        assert!(jar.cookies(&origin).is_none());
+        jar.add_cookie_str("b=1; Expires=Thu, 01 Jan 1970 00:00:00 GMT", &origin);
+        assert!(jar.is_empty());
+
+        // Max-Age wins over Expires
+        jar.add_cookie_str(
+            "c=1; Max-Age=60; Expires=Thu, 01 Jan 1970 00:00:00 GMT",
+            &origin,
+        );
+        assert_eq!(jar.cookies(&origin).unwrap(), "c=1");
+
+        jar.clear();
+        assert!(jar.is_empty());
+    }
+
+    #[test]
+    fn test_path_matches() {
+        assert!(path_matches("/pkg.Service/Method", "/"));
+        assert!(path_matches("/pkg.Service/Method", "/pkg.Service"));
+        assert!(path_matches("/pkg.Service", "/pkg.Service"));
+        assert!(!path_matches("/pkg.ServiceV2/Method", "/pkg.Service"));
+        assert_eq!(default_path("/pkg.Service/Method"), "/pkg.Service");
+        assert_eq!(default_path("/Method"), "/");
+        assert_eq!(default_path(""), "/");
+    }
+}
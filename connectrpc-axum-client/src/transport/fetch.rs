@@ -0,0 +1,236 @@
+//! Fetch-based HTTP transport for wasm32 targets.
+//!
+//! This module provides [`FetchTransport`], which sends requests with the
+//! global `fetch` function, so it works in browser windows and web workers.
+//!
+//! `fetch` can't stream request bodies, so only unary and server-streaming
+//! calls are supported; the client-streaming and bidi APIs, and
+//! `TransportBody::streaming`, are not compiled on wasm32. Response bodies
+//! are read incrementally from the response's `ReadableStream`, which is what
+//! server streaming needs.
+//!
+//! For cross-origin requests, response headers the server doesn't list in
+//! `Access-Control-Expose-Headers` are hidden from the client.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use http_body::{Body, Frame};
+use js_sys::{Array, Promise, Reflect, Uint8Array};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AbortController, Headers, ReadableStreamDefaultReader, RequestCredentials, RequestInit,
+};
+
+use super::body::TransportBody;
+use crate::ClientError;
+
+#[wasm_bindgen]
+extern "C" {
+    // The global `fetch`, present in windows and workers alike
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_with_request(request: &web_sys::Request) -> Promise;
+}
+
+/// HTTP transport using the `fetch` API.
+///
+/// This is the transport [`ClientBuilder`](crate::ClientBuilder) uses on
+/// wasm32 targets. Configure one explicitly to change how credentials such
+/// as cookies are sent:
+///
+/// ```ignore
+/// use connectrpc_axum_client::{ClientBuilder, FetchTransport};
+/// use web_sys::RequestCredentials;
+///
+/// let transport = FetchTransport::new().credentials(RequestCredentials::Include);
+///
+/// let client = ClientBuilder::new("https://api.example.com")
+///     .with_transport(transport)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FetchTransport {
+    credentials: Option<RequestCredentials>,
+}
+
+impl FetchTransport {
+    /// Create a transport with the browser's default fetch settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the request's credentials mode.
+    ///
+    /// Use [`RequestCredentials::Include`] to send cookies on cross-origin
+    /// requests. Defaults to the browser's default, `same-origin`.
+    pub fn credentials(mut self, credentials: RequestCredentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Send an HTTP request and return the response.
+    ///
+    /// The request is aborted if the returned future, or the response body,
+    /// is dropped before it completes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::Transport`] when the request fails.
+    pub async fn request(
+        &self,
+        request: http::Request<TransportBody>,
+    ) -> Result<http::Response<FetchBody>, ClientError> {
+        let (parts, body) = request.into_parts();
+        let body = match body {
+            TransportBody::Empty => None,
+            TransportBody::Full { data } => data,
+        };
+
+        let headers = Headers::new().map_err(js_error)?;
+        for (name, value) in &parts.headers {
+            let value = value.to_str().map_err(|_| {
+                ClientError::Transport(format!("header {} is not valid ASCII", name))
+            })?;
+            headers.append(name.as_str(), value).map_err(js_error)?;
+        }
+
+        let controller = AbortController::new().map_err(js_error)?;
+        let init = RequestInit::new();
+        init.set_method(parts.method.as_str());
+        init.set_headers(&headers);
+        init.set_signal(Some(&controller.signal()));
+        if let Some(body) = &body {
+            init.set_body(&Uint8Array::from(body.as_ref()));
+        }
+        if let Some(credentials) = self.credentials {
+            init.set_credentials(credentials);
+        }
+        let abort = AbortOnDrop(controller);
+
+        let request = web_sys::Request::new_with_str_and_init(&parts.uri.to_string(), &init)
+            .map_err(js_error)?;
+        let response: web_sys::Response = JsFuture::from(fetch_with_request(&request))
+            .await
+            .map_err(js_error)?
+            .unchecked_into();
+
+        let mut builder = http::Response::builder().status(response.status());
+        let entries = js_sys::try_iter(&response.headers())
+            .map_err(js_error)?
+            .ok_or_else(|| ClientError::Transport("response headers are not iterable".into()))?;
+        for entry in entries {
+            let entry: Array = entry.map_err(js_error)?.unchecked_into();
+            if let (Some(name), Some(value)) = (entry.get(0).as_string(), entry.get(1).as_string())
+            {
+                builder = builder.header(name, value);
+            }
+        }
+
+        let reader = response.body().map(|stream| {
+            stream
+                .get_reader()
+                .unchecked_into::<ReadableStreamDefaultReader>()
+        });
+        builder
+            .body(FetchBody {
+                reader,
+                pending: None,
+                _abort: abort,
+            })
+            .map_err(|e| ClientError::Transport(format!("invalid response: {}", e)))
+    }
+}
+
+/// Aborts the fetch when dropped. Aborting a completed fetch has no effect.
+struct AbortOnDrop(AbortController);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Response body of a [`FetchTransport`] request.
+///
+/// Yields the chunks of the response's `ReadableStream` as they arrive.
+pub struct FetchBody {
+    /// Reader of the response stream; `None` once the stream is finished.
+    reader: Option<ReadableStreamDefaultReader>,
+    /// Pending `reader.read()` call.
+    pending: Option<JsFuture>,
+    _abort: AbortOnDrop,
+}
+
+impl std::fmt::Debug for FetchBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FetchBody")
+            .field("finished", &self.reader.is_none())
+            .finish()
+    }
+}
+
+impl Body for FetchBody {
+    type Data = Bytes;
+    type Error = ClientError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let Some(reader) = &this.reader else {
+            return Poll::Ready(None);
+        };
+
+        let pending = this
+            .pending
+            .get_or_insert_with(|| JsFuture::from(reader.read()));
+        let result = ready!(Pin::new(pending).poll(cx));
+        this.pending = None;
+
+        match result
+            .map_err(js_error)
+            .and_then(|chunk| chunk_bytes(&chunk))
+        {
+            Ok(Some(data)) => Poll::Ready(Some(Ok(Frame::data(data)))),
+            Ok(None) => {
+                this.reader = None;
+                Poll::Ready(None)
+            }
+            Err(e) => {
+                this.reader = None;
+                Poll::Ready(Some(Err(e)))
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.reader.is_none()
+    }
+}
+
+/// Extract the data of a `ReadableStream` read result, `None` when done.
+fn chunk_bytes(chunk: &JsValue) -> Result<Option<Bytes>, ClientError> {
+    let done = Reflect::get(chunk, &JsValue::from_str("done")).map_err(js_error)?;
+    if done.is_truthy() {
+        return Ok(None);
+    }
+    let value = Reflect::get(chunk, &JsValue::from_str("value")).map_err(js_error)?;
+    let value: Uint8Array = value
+        .dyn_into()
+        .map_err(|_| ClientError::Transport("response chunk is not a Uint8Array".into()))?;
+    Ok(Some(Bytes::from(value.to_vec())))
+}
+
+/// Convert a JavaScript exception into a transport error.
+fn js_error(err: JsValue) -> ClientError {
+    let message = match err.dyn_ref::<js_sys::Error>() {
+        Some(error) => String::from(error.message()),
+        None => err.as_string().unwrap_or_else(|| format!("{:?}", err)),
+    };
+    ClientError::Transport(format!("request failed: {}", message))
+}
@@ -4,7 +4,9 @@
 //! using hyper_util's legacy client.
 
 use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Duration;
 
@@ -16,11 +18,16 @@ use rustls::ClientConfig;
 use tower_service::Service;
 
 use super::body::TransportBody;
-use super::connector::{build_https_connector, danger_accept_invalid_certs_config};
+use super::connector::{
+    build_http_connector, danger_accept_invalid_certs_config, wrap_https_connector,
+};
+use super::cookie::{CookieJar, CookieStore};
+use super::limit::LimitedConnector;
+use super::tls::TlsClientConfig;
 use crate::ClientError;
 
 /// Type alias for the hyper client with HTTPS connector.
-type HyperClient = Client<HttpsConnector<HttpConnector>, TransportBody>;
+type HyperClient = Client<LimitedConnector<HttpsConnector<HttpConnector>>, TransportBody>;
 
 /// HTTP transport using hyper_util's legacy client.
 ///
@@ -43,14 +50,17 @@ type HyperClient = Client<HttpsConnector<HttpConnector>, TransportBody>;
 #[derive(Clone)]
 pub struct HyperTransport {
     client: HyperClient,
-    /// Whether HTTP/2 only mode is enabled.
-    http2_only: bool,
+    /// HTTP versions the transport speaks.
+    http_version: HttpVersionPolicy,
+    /// Cookies sent with requests and stored from responses.
+    cookies: Option<Arc<dyn CookieStore>>,
 }
 
 impl std::fmt::Debug for HyperTransport {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("HyperTransport")
-            .field("http2_only", &self.http2_only)
+            .field("http_version", &self.http_version)
+            .field("cookies", &self.cookies.is_some())
             .finish_non_exhaustive()
     }
 }
@@ -71,16 +81,34 @@ impl HyperTransport {
         &self,
         request: http::Request<TransportBody>,
     ) -> Result<http::Response<Incoming>, ClientError> {
-        self.client
-            .request(request)
-            .await
-            .map_err(|e| ClientError::Transport(format!("request failed: {}", e)))
+        send(&self.client, self.cookies.as_deref(), request).await
     }
 
     /// Check if this transport is configured for HTTP/2 only.
     pub fn is_http2_only(&self) -> bool {
-        self.http2_only
+        self.http_version == HttpVersionPolicy::Http2PriorKnowledge
     }
+
+    /// Get the HTTP versions this transport speaks.
+    pub fn http_version(&self) -> HttpVersionPolicy {
+        self.http_version
+    }
+}
+
+/// HTTP versions a [`HyperTransport`] speaks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HttpVersionPolicy {
+    /// HTTP/1.1 for `http://` URLs. For `https://` URLs, HTTP/2 or HTTP/1.1
+    /// as negotiated with the server via ALPN.
+    #[default]
+    Auto,
+    /// HTTP/1.1 only, e.g. behind proxies that don't support HTTP/2.
+    ///
+    /// Bidirectional streaming and gRPC need HTTP/2 and are unavailable.
+    Http1Only,
+    /// HTTP/2 only, without negotiation: h2c (HTTP/2 over cleartext) for
+    /// `http://` URLs. Servers that don't speak HTTP/2 fail the connection.
+    Http2PriorKnowledge,
 }
 
 impl Default for HyperTransport {
@@ -108,24 +136,65 @@ impl Default for HyperTransport {
 pub struct HyperTransportBuilder {
     /// Custom TLS configuration.
     tls_config: Option<ClientConfig>,
-    /// Force HTTP/2 only (for h2c or when HTTP/2 is required).
-    http2_only: bool,
+    /// High-level TLS configuration, converted to rustls at build time.
+    tls: Option<TlsClientConfig>,
+    /// HTTP versions to speak.
+    http_version: HttpVersionPolicy,
     /// Connection pool idle timeout.
     pool_idle_timeout: Option<Duration>,
     /// Maximum idle connections per host.
     pool_max_idle_per_host: usize,
+    /// Maximum open connections per host.
+    pool_max_connections_per_host: Option<usize>,
     /// HTTP/2 initial stream window size.
     h2_initial_stream_window_size: Option<u32>,
     /// HTTP/2 initial connection window size.
     h2_initial_connection_window_size: Option<u32>,
+    /// Use BDP-based adaptive HTTP/2 flow control windows.
+    h2_adaptive_window: bool,
+    /// HTTP/2 maximum frame size.
+    h2_max_frame_size: Option<u32>,
     /// HTTP/2 keep-alive interval.
     h2_keep_alive_interval: Option<Duration>,
     /// HTTP/2 keep-alive timeout.
     h2_keep_alive_timeout: Option<Duration>,
+    /// Send HTTP/2 keep-alive pings on connections without open streams.
+    h2_keep_alive_while_idle: bool,
+    /// Timeout for establishing a TCP connection.
+    connect_timeout: Option<Duration>,
+    /// Delay before racing the other address family (Happy Eyeballs).
+    happy_eyeballs_timeout: Option<Duration>,
+    /// Idle time before TCP keepalive probes are sent.
+    tcp_keepalive: Option<Duration>,
+    /// Time between unacknowledged TCP keepalive probes.
+    tcp_keepalive_interval: Option<Duration>,
+    /// Unacknowledged TCP keepalive probes before the connection is dropped.
+    tcp_keepalive_retries: Option<u32>,
+    /// Set `TCP_NODELAY`, disabling Nagle's algorithm.
+    tcp_nodelay: bool,
+    /// Local IPv4 address sockets are bound to.
+    local_address_ipv4: Option<Ipv4Addr>,
+    /// Local IPv6 address sockets are bound to.
+    local_address_ipv6: Option<Ipv6Addr>,
+    /// Network interface sockets are bound to.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos"
+    ))]
+    interface: Option<String>,
     /// Whether to accept invalid certificates (dangerous!).
     danger_accept_invalid_certs: bool,
+    /// Cookie store, if cookies are kept.
+    cookies: Option<Arc<dyn CookieStore>>,
 }
 
+/// Default delay before a connection attempt over the other IP family starts
+/// (300 milliseconds, as recommended by RFC 6555).
+pub const DEFAULT_HAPPY_EYEBALLS_TIMEOUT: Duration = Duration::from_millis(300);
+
 impl Default for HyperTransportBuilder {
     fn default() -> Self {
         Self::new()
@@ -137,14 +206,36 @@ impl HyperTransportBuilder {
     pub fn new() -> Self {
         Self {
             tls_config: None,
-            http2_only: false,
+            tls: None,
+            http_version: HttpVersionPolicy::Auto,
             pool_idle_timeout: Some(Duration::from_secs(90)),
             pool_max_idle_per_host: 32,
+            pool_max_connections_per_host: None,
             h2_initial_stream_window_size: None,
             h2_initial_connection_window_size: None,
+            h2_adaptive_window: false,
+            h2_max_frame_size: None,
             h2_keep_alive_interval: None,
             h2_keep_alive_timeout: None,
+            h2_keep_alive_while_idle: false,
+            connect_timeout: None,
+            happy_eyeballs_timeout: Some(DEFAULT_HAPPY_EYEBALLS_TIMEOUT),
+            tcp_keepalive: None,
+            tcp_keepalive_interval: None,
+            tcp_keepalive_retries: None,
+            tcp_nodelay: false,
+            local_address_ipv4: None,
+            local_address_ipv6: None,
+            #[cfg(any(
+                target_os = "android",
+                target_os = "fuchsia",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos"
+            ))]
+            interface: None,
             danger_accept_invalid_certs: false,
+            cookies: None,
         }
     }
 
@@ -168,6 +259,27 @@ impl HyperTransportBuilder {
     /// ```
     pub fn tls_config(mut self, config: ClientConfig) -> Self {
         self.tls_config = Some(config);
+        self.tls = None;
+        self
+    }
+
+    /// Set a high-level TLS configuration.
+    ///
+    /// Replaces any config previously set via [`tls_config()`](Self::tls_config).
+    /// The configuration is validated when [`build()`](Self::build) is called.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use connectrpc_axum_client::transport::{HyperTransportBuilder, TlsClientConfig};
+    ///
+    /// let transport = HyperTransportBuilder::new()
+    ///     .with_tls_config(TlsClientConfig::new().add_root_certificate(&ca_pem))
+    ///     .build()?;
+    /// ```
+    pub fn with_tls_config(mut self, config: TlsClientConfig) -> Self {
+        self.tls = Some(config);
+        self.tls_config = None;
         self
     }
 
@@ -180,8 +292,35 @@ impl HyperTransportBuilder {
     ///
     /// For HTTPS connections, HTTP/2 is typically negotiated via ALPN,
     /// so this setting is often not needed.
+    ///
+    /// Same as [`http_version()`](Self::http_version) with
+    /// [`HttpVersionPolicy::Http2PriorKnowledge`] if enabled, or
+    /// [`HttpVersionPolicy::Auto`] if not.
     pub fn http2_only(mut self, enabled: bool) -> Self {
-        self.http2_only = enabled;
+        self.http_version = if enabled {
+            HttpVersionPolicy::Http2PriorKnowledge
+        } else {
+            HttpVersionPolicy::Auto
+        };
+        self
+    }
+
+    /// Use HTTP/1.1 only.
+    ///
+    /// Only HTTP/1.1 is offered via ALPN for HTTPS connections, even if the
+    /// TLS config lists other protocols. Same as
+    /// [`http_version()`](Self::http_version) with
+    /// [`HttpVersionPolicy::Http1Only`].
+    pub fn http1_only(mut self) -> Self {
+        self.http_version = HttpVersionPolicy::Http1Only;
+        self
+    }
+
+    /// Set the HTTP versions to speak.
+    ///
+    /// Default: [`HttpVersionPolicy::Auto`].
+    pub fn http_version(mut self, policy: HttpVersionPolicy) -> Self {
+        self.http_version = policy;
         self
     }
 
@@ -212,10 +351,35 @@ impl HyperTransportBuilder {
         self
     }
 
+    /// Set the maximum number of connections open to a host at once.
+    ///
+    /// Without a limit, a connection is opened for every request that finds
+    /// no idle one, so bursts of HTTP/1.1 requests can open many sockets.
+    /// With a limit, requests beyond it wait for a connection to become idle
+    /// instead. Idle pooled connections count toward the limit until
+    /// [`pool_idle_timeout`](Self::pool_idle_timeout) closes them. HTTP/2
+    /// requests share connections, so the limit rarely applies to them.
+    ///
+    /// Hosts are told apart by scheme, host and port.
+    ///
+    /// Default: none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is 0.
+    pub fn pool_max_connections_per_host(mut self, max: usize) -> Self {
+        assert!(max >= 1, "max connections per host must be >= 1");
+        self.pool_max_connections_per_host = Some(max);
+        self
+    }
+
     /// Set the HTTP/2 initial stream window size.
     ///
-    /// This controls flow control at the stream level.
+    /// This controls flow control at the stream level: how many bytes the
+    /// server may send on one stream before the client acknowledges them.
     /// Larger values may improve throughput for high-latency connections.
+    ///
+    /// Default: 2 MiB.
     pub fn h2_initial_stream_window_size(mut self, size: u32) -> Self {
         self.h2_initial_stream_window_size = Some(size);
         self
@@ -225,15 +389,55 @@ impl HyperTransportBuilder {
     ///
     /// This controls flow control at the connection level.
     /// Larger values may improve throughput for multiplexed streams.
+    ///
+    /// Default: 5 MiB.
     pub fn h2_initial_connection_window_size(mut self, size: u32) -> Self {
         self.h2_initial_connection_window_size = Some(size);
         self
     }
 
+    /// Size the HTTP/2 flow control windows from the measured
+    /// bandwidth-delay product.
+    ///
+    /// The windows then grow as far as the connection needs, so streaming
+    /// over fast, high-latency links isn't throttled by a fixed window.
+    /// Overrides the initial window sizes.
+    ///
+    /// Default: false.
+    pub fn h2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.h2_adaptive_window = enabled;
+        self
+    }
+
+    /// Set the largest HTTP/2 frame payload the client accepts.
+    ///
+    /// Larger frames mean fewer frames, and less overhead, for large
+    /// messages.
+    ///
+    /// Default: 16 KiB.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not between 16,384 and 16,777,215 bytes, the
+    /// range HTTP/2 allows.
+    pub fn h2_max_frame_size(mut self, size: u32) -> Self {
+        assert!(
+            (16_384..=16_777_215).contains(&size),
+            "max frame size must be between 16384 and 16777215"
+        );
+        self.h2_max_frame_size = Some(size);
+        self
+    }
+
     /// Set the HTTP/2 keep-alive interval.
     ///
     /// If set, the transport will send HTTP/2 PING frames at this interval
-    /// to keep the connection alive and detect dead connections.
+    /// to keep the connection alive and detect dead connections. A
+    /// connection whose peer stops answering is closed, failing the streams
+    /// on it with a transport error, so long-lived streams over flaky
+    /// networks don't hang until the OS gives up on the TCP connection.
+    ///
+    /// Default: none (no pings).
     pub fn h2_keep_alive_interval(mut self, interval: Duration) -> Self {
         self.h2_keep_alive_interval = Some(interval);
         self
@@ -243,11 +447,148 @@ impl HyperTransportBuilder {
     ///
     /// How long to wait for a PING response before considering the connection dead.
     /// Only effective if `h2_keep_alive_interval` is also set.
+    ///
+    /// Default: 20 seconds.
     pub fn h2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
         self.h2_keep_alive_timeout = Some(timeout);
         self
     }
 
+    /// Send HTTP/2 keep-alive pings while a connection has no open streams.
+    ///
+    /// By default pings are only sent while requests or streams are in
+    /// flight. Enable this to also detect dead pooled connections before a
+    /// call picks them up. Only effective if `h2_keep_alive_interval` is
+    /// also set.
+    ///
+    /// Default: false.
+    pub fn h2_keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.h2_keep_alive_while_idle = enabled;
+        self
+    }
+
+    /// Set the timeout for establishing a TCP connection.
+    ///
+    /// Unlike the call timeout, this only bounds connecting, so an
+    /// unreachable server fails fast with a transport error even when calls
+    /// may take long. If a host resolves to several addresses, the timeout is
+    /// divided evenly between them.
+    ///
+    /// Default: none.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the Happy Eyeballs (RFC 6555) delay.
+    ///
+    /// When a host resolves to both IPv6 and IPv4 addresses, a connection
+    /// attempt over the other family starts if the first family hasn't
+    /// connected within this delay, and the first connection to succeed is
+    /// used. This keeps clients on networks with broken IPv6 from stalling.
+    ///
+    /// Default: 300 milliseconds.
+    pub fn happy_eyeballs_timeout(mut self, timeout: Duration) -> Self {
+        self.happy_eyeballs_timeout = Some(timeout);
+        self
+    }
+
+    /// Disable Happy Eyeballs: try addresses one at a time, in the order the
+    /// resolver returned them.
+    pub fn happy_eyeballs_timeout_none(mut self) -> Self {
+        self.happy_eyeballs_timeout = None;
+        self
+    }
+
+    /// Enable TCP keepalive, sending probes after a connection has been idle
+    /// for `time`.
+    ///
+    /// Keepalive detects peers that went away without closing the
+    /// connection, and keeps NATs and firewalls from dropping idle pooled
+    /// connections. Unlike HTTP/2 keep-alive pings, it works for HTTP/1.1
+    /// connections too.
+    ///
+    /// Default: disabled.
+    pub fn tcp_keepalive(mut self, time: Duration) -> Self {
+        self.tcp_keepalive = Some(time);
+        self
+    }
+
+    /// Set the time between TCP keepalive probes that aren't acknowledged.
+    ///
+    /// Only effective if [`tcp_keepalive()`](Self::tcp_keepalive) is set.
+    ///
+    /// Default: the operating system's.
+    pub fn tcp_keepalive_interval(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive_interval = Some(interval);
+        self
+    }
+
+    /// Set how many TCP keepalive probes may go unacknowledged before the
+    /// connection is dropped.
+    ///
+    /// Only effective if [`tcp_keepalive()`](Self::tcp_keepalive) is set.
+    ///
+    /// Default: the operating system's.
+    pub fn tcp_keepalive_retries(mut self, retries: u32) -> Self {
+        self.tcp_keepalive_retries = Some(retries);
+        self
+    }
+
+    /// Set `TCP_NODELAY` on connections.
+    ///
+    /// Disabling Nagle's algorithm sends small writes, such as unary
+    /// requests and streaming messages, right away instead of batching them,
+    /// which lowers latency at the cost of more packets.
+    ///
+    /// Default: false.
+    pub fn tcp_nodelay(mut self, enabled: bool) -> Self {
+        self.tcp_nodelay = enabled;
+        self
+    }
+
+    /// Bind sockets to a local address before connecting.
+    ///
+    /// On multi-homed hosts, this picks the address, and with it the
+    /// network, connections originate from. Connections over the other
+    /// family (IPv4 or IPv6) aren't bound; use
+    /// [`local_addresses()`](Self::local_addresses) to bind both families.
+    ///
+    /// Default: none, the operating system picks the address.
+    pub fn local_address(mut self, addr: IpAddr) -> Self {
+        (self.local_address_ipv4, self.local_address_ipv6) = match addr {
+            IpAddr::V4(addr) => (Some(addr), None),
+            IpAddr::V6(addr) => (None, Some(addr)),
+        };
+        self
+    }
+
+    /// Bind sockets to `ipv4` or `ipv6` before connecting, depending on the
+    /// family of the server address.
+    pub fn local_addresses(mut self, ipv4: Ipv4Addr, ipv6: Ipv6Addr) -> Self {
+        self.local_address_ipv4 = Some(ipv4);
+        self.local_address_ipv6 = Some(ipv6);
+        self
+    }
+
+    /// Bind sockets to a network interface (e.g. `"eth1"`) before
+    /// connecting.
+    ///
+    /// Uses `SO_BINDTODEVICE` on Linux, Android and Fuchsia, which usually
+    /// needs the `CAP_NET_RAW` capability, and `IP_BOUND_IF` on macOS and
+    /// iOS. Connections fail if the socket can't be bound.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "fuchsia",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos"
+    ))]
+    pub fn interface(mut self, interface: impl Into<String>) -> Self {
+        self.interface = Some(interface.into());
+        self
+    }
+
     /// Accept invalid TLS certificates.
     ///
     /// # Warning
@@ -259,24 +600,91 @@ impl HyperTransportBuilder {
         self
     }
 
+    /// Keep cookies in a new [`CookieJar`].
+    ///
+    /// Cookies set by responses, e.g. a load balancer's session-affinity
+    /// cookie, are sent back with later requests they apply to. Passing
+    /// `false` removes any cookie store.
+    ///
+    /// Default: disabled.
+    pub fn cookie_store(mut self, enabled: bool) -> Self {
+        self.cookies = enabled.then(|| Arc::new(CookieJar::new()) as Arc<dyn CookieStore>);
+        self
+    }
+
+    /// Keep cookies in `store`.
+    ///
+    /// Like [`cookie_store`](Self::cookie_store), with a store that can be
+    /// shared with other transports, seeded, or inspected.
+    pub fn cookie_provider<C: CookieStore>(mut self, store: Arc<C>) -> Self {
+        self.cookies = Some(store);
+        self
+    }
+
     /// Build the transport.
     ///
     /// # Panics
     ///
     /// Panics if no custom TLS config is provided and TLS features are not enabled.
     /// Either enable the `tls` feature or provide a custom config via `tls_config()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`TlsClientConfig`] contains invalid certificates or keys.
     pub fn build(self) -> Result<HyperTransport, ClientError> {
-        // Create TLS config
-        let tls_config = if self.danger_accept_invalid_certs {
-            Some(danger_accept_invalid_certs_config())
-        } else {
-            self.tls_config
+        // Server name to use instead of the host of the URL
+        let server_name = match &self.tls {
+            Some(tls) => tls
+                .parsed_server_name()
+                .map_err(|e| ClientError::Transport(format!("invalid TLS configuration: {}", e)))?,
+            None => None,
         };
 
+        // Create TLS config
+        let tls_config =
+            if self.danger_accept_invalid_certs {
+                Some(danger_accept_invalid_certs_config())
+            } else if let Some(tls) = &self.tls {
+                Some(tls.build().map_err(|e| {
+                    ClientError::Transport(format!("invalid TLS configuration: {}", e))
+                })?)
+            } else {
+                self.tls_config
+            };
+
         // Create HTTPS connector
-        // If tls_config is None, build_https_connector will use default config
+        // If tls_config is None, wrap_https_connector will use default config
         // (if TLS features enabled) or panic with helpful message
-        let https_connector = build_https_connector(tls_config);
+        let mut http_connector = build_http_connector();
+        http_connector.set_connect_timeout(self.connect_timeout);
+        http_connector.set_happy_eyeballs_timeout(self.happy_eyeballs_timeout);
+        http_connector.set_keepalive(self.tcp_keepalive);
+        http_connector.set_keepalive_interval(self.tcp_keepalive_interval);
+        http_connector.set_keepalive_retries(self.tcp_keepalive_retries);
+        http_connector.set_nodelay(self.tcp_nodelay);
+        match (self.local_address_ipv4, self.local_address_ipv6) {
+            (Some(ipv4), Some(ipv6)) => http_connector.set_local_addresses(ipv4, ipv6),
+            (ipv4, ipv6) => {
+                http_connector.set_local_address(ipv4.map(IpAddr::V4).or(ipv6.map(IpAddr::V6)))
+            }
+        }
+        #[cfg(any(
+            target_os = "android",
+            target_os = "fuchsia",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos"
+        ))]
+        if let Some(interface) = self.interface {
+            http_connector.set_interface(interface);
+        }
+        let https_connector = wrap_https_connector(
+            tls_config,
+            http_connector,
+            server_name,
+            self.http_version == HttpVersionPolicy::Http1Only,
+        );
+        let connector = LimitedConnector::new(https_connector, self.pool_max_connections_per_host);
 
         // Create client builder
         let mut builder = Client::builder(TokioExecutor::new());
@@ -284,6 +692,9 @@ impl HyperTransportBuilder {
         // Configure connection pool timer (required for pool_idle_timeout to work)
         builder.pool_timer(TokioTimer::new());
 
+        // Configure connection timer (required for HTTP/2 keep-alive to work)
+        builder.timer(TokioTimer::new());
+
         // Configure connection pool
         if let Some(timeout) = self.pool_idle_timeout {
             builder.pool_idle_timeout(timeout);
@@ -291,7 +702,7 @@ impl HyperTransportBuilder {
         builder.pool_max_idle_per_host(self.pool_max_idle_per_host);
 
         // Configure HTTP/2
-        if self.http2_only {
+        if self.http_version == HttpVersionPolicy::Http2PriorKnowledge {
             builder.http2_only(true);
         }
 
@@ -303,6 +714,12 @@ impl HyperTransportBuilder {
             builder.http2_initial_connection_window_size(size);
         }
 
+        builder.http2_adaptive_window(self.h2_adaptive_window);
+
+        if let Some(size) = self.h2_max_frame_size {
+            builder.http2_max_frame_size(size);
+        }
+
         if let Some(interval) = self.h2_keep_alive_interval {
             builder.http2_keep_alive_interval(interval);
         }
@@ -311,23 +728,32 @@ impl HyperTransportBuilder {
             builder.http2_keep_alive_timeout(timeout);
         }
 
+        builder.http2_keep_alive_while_idle(self.h2_keep_alive_while_idle);
+
         // Build client
-        let client = builder.build(https_connector);
+        let client = builder.build(connector);
 
         Ok(HyperTransport {
             client,
-            http2_only: self.http2_only,
+            http_version: self.http_version,
+            cookies: self.cookies,
         })
     }
 }
 
 impl std::fmt::Debug for HyperTransportBuilder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("HyperTransportBuilder")
+        let mut debug = f.debug_struct("HyperTransportBuilder");
+        debug
             .field("tls_config", &self.tls_config.is_some())
-            .field("http2_only", &self.http2_only)
+            .field("tls", &self.tls)
+            .field("http_version", &self.http_version)
             .field("pool_idle_timeout", &self.pool_idle_timeout)
             .field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field(
+                "pool_max_connections_per_host",
+                &self.pool_max_connections_per_host,
+            )
             .field(
                 "h2_initial_stream_window_size",
                 &self.h2_initial_stream_window_size,
@@ -336,12 +762,33 @@ impl std::fmt::Debug for HyperTransportBuilder {
                 "h2_initial_connection_window_size",
                 &self.h2_initial_connection_window_size,
             )
+            .field("h2_adaptive_window", &self.h2_adaptive_window)
+            .field("h2_max_frame_size", &self.h2_max_frame_size)
             .field("h2_keep_alive_interval", &self.h2_keep_alive_interval)
             .field("h2_keep_alive_timeout", &self.h2_keep_alive_timeout)
+            .field("h2_keep_alive_while_idle", &self.h2_keep_alive_while_idle)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("happy_eyeballs_timeout", &self.happy_eyeballs_timeout)
+            .field("tcp_keepalive", &self.tcp_keepalive)
+            .field("tcp_keepalive_interval", &self.tcp_keepalive_interval)
+            .field("tcp_keepalive_retries", &self.tcp_keepalive_retries)
+            .field("tcp_nodelay", &self.tcp_nodelay)
+            .field("local_address_ipv4", &self.local_address_ipv4)
+            .field("local_address_ipv6", &self.local_address_ipv6);
+        #[cfg(any(
+            target_os = "android",
+            target_os = "fuchsia",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos"
+        ))]
+        debug.field("interface", &self.interface);
+        debug
             .field(
                 "danger_accept_invalid_certs",
                 &self.danger_accept_invalid_certs,
             )
+            .field("cookies", &self.cookies.is_some())
             .finish()
     }
 }
@@ -359,15 +806,47 @@ impl Service<http::Request<TransportBody>> for HyperTransport {
 
     fn call(&mut self, req: http::Request<TransportBody>) -> Self::Future {
         let client = self.client.clone();
-        Box::pin(async move {
-            client
-                .request(req)
-                .await
-                .map_err(|e| ClientError::Transport(format!("request failed: {}", e)))
-        })
+        let cookies = self.cookies.clone();
+        Box::pin(async move { send(&client, cookies.as_deref(), req).await })
     }
 }
 
+/// Send `request` with `client`, adding the cookies that apply to it and
+/// storing those the response sets.
+async fn send(
+    client: &HyperClient,
+    cookies: Option<&dyn CookieStore>,
+    mut request: http::Request<TransportBody>,
+) -> Result<http::Response<Incoming>, ClientError> {
+    let uri = cookies.map(|_| request.uri().clone());
+    if let (Some(cookies), Some(uri)) = (cookies, &uri)
+        && let Some(cookie) = cookies.cookies(uri)
+    {
+        // Cookies of the call's own headers come first
+        let cookie = match request.headers().get(http::header::COOKIE) {
+            Some(existing) => {
+                let mut merged = existing.as_bytes().to_vec();
+                merged.extend_from_slice(b"; ");
+                merged.extend_from_slice(cookie.as_bytes());
+                http::HeaderValue::from_bytes(&merged).unwrap_or(cookie)
+            }
+            None => cookie,
+        };
+        request.headers_mut().insert(http::header::COOKIE, cookie);
+    }
+
+    let response = client
+        .request(request)
+        .await
+        .map_err(|e| ClientError::Transport(format!("request failed: {}", e)))?;
+
+    if let (Some(cookies), Some(uri)) = (cookies, &uri) {
+        let mut set_cookies = response.headers().get_all(http::header::SET_COOKIE).iter();
+        cookies.set_cookies(&mut set_cookies, uri);
+    }
+    Ok(response)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -375,7 +854,7 @@ mod tests {
     #[test]
     fn test_builder_defaults() {
         let builder = HyperTransportBuilder::new();
-        assert!(!builder.http2_only);
+        assert_eq!(builder.http_version, HttpVersionPolicy::Auto);
         assert_eq!(builder.pool_max_idle_per_host, 32);
         assert!(builder.pool_idle_timeout.is_some());
     }
@@ -383,16 +862,190 @@ mod tests {
     #[test]
     fn test_builder_http2_only() {
         let builder = HyperTransportBuilder::new().http2_only(true);
-        assert!(builder.http2_only);
+        assert_eq!(builder.http_version, HttpVersionPolicy::Http2PriorKnowledge);
+        let builder = builder.http2_only(false);
+        assert_eq!(builder.http_version, HttpVersionPolicy::Auto);
     }
 
     #[test]
     fn test_builder_pool_settings() {
         let builder = HyperTransportBuilder::new()
             .pool_idle_timeout(Duration::from_secs(60))
-            .pool_max_idle_per_host(10);
+            .pool_max_idle_per_host(10)
+            .pool_max_connections_per_host(4);
         assert_eq!(builder.pool_idle_timeout, Some(Duration::from_secs(60)));
         assert_eq!(builder.pool_max_idle_per_host, 10);
+        assert_eq!(builder.pool_max_connections_per_host, Some(4));
+    }
+
+    #[tokio::test]
+    async fn test_pool_max_connections_per_host() {
+        use hyper_util::rt::TokioIo;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // An HTTP/1.1 server counting connections, answering slowly so
+        // concurrent requests can't share one
+        async fn serve() -> (std::net::SocketAddr, Arc<AtomicUsize>) {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let accepted = Arc::new(AtomicUsize::new(0));
+            let counter = accepted.clone();
+            tokio::spawn(async move {
+                while let Ok((tcp, _)) = listener.accept().await {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::spawn(async move {
+                        let service = hyper::service::service_fn(|_| async {
+                            tokio::time::sleep(Duration::from_millis(20)).await;
+                            Ok::<_, std::convert::Infallible>(http::Response::new(String::new()))
+                        });
+                        let _ = hyper::server::conn::http1::Builder::new()
+                            .serve_connection(TokioIo::new(tcp), service)
+                            .await;
+                    });
+                }
+            });
+            (addr, accepted)
+        }
+
+        async fn send_concurrently(transport: &HyperTransport, addr: std::net::SocketAddr) {
+            let requests = (0..4).map(|_| {
+                let request = http::Request::builder()
+                    .uri(format!("http://{addr}/"))
+                    .body(TransportBody::empty())
+                    .unwrap();
+                transport.request(request)
+            });
+            for response in futures::future::join_all(requests).await {
+                assert_eq!(response.unwrap().status(), http::StatusCode::OK);
+            }
+        }
+
+        let (addr, accepted) = serve().await;
+        send_concurrently(&HyperTransport::new().unwrap(), addr).await;
+        assert_eq!(accepted.load(Ordering::SeqCst), 4);
+
+        // With a limit, requests queue for the connections there are
+        let (addr, accepted) = serve().await;
+        let transport = HyperTransportBuilder::new()
+            .pool_max_connections_per_host(2)
+            .build()
+            .unwrap();
+        send_concurrently(&transport, addr).await;
+        assert_eq!(accepted.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cookie_store() {
+        use hyper_util::rt::TokioIo;
+
+        // Sets an affinity cookie, echoing the Cookie header it receives
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let service =
+                        hyper::service::service_fn(|req: http::Request<Incoming>| async move {
+                            let cookie = req
+                                .headers()
+                                .get(http::header::COOKIE)
+                                .map(|v| v.to_str().unwrap().to_string())
+                                .unwrap_or_default();
+                            let response = http::Response::builder()
+                                .header(http::header::SET_COOKIE, "affinity=backend-1; Path=/")
+                                .body(cookie)
+                                .unwrap();
+                            Ok::<_, std::convert::Infallible>(response)
+                        });
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(tcp), service)
+                        .await;
+                });
+            }
+        });
+
+        async fn send(transport: &HyperTransport, addr: std::net::SocketAddr) -> String {
+            use http_body_util::BodyExt;
+
+            let request = http::Request::builder()
+                .uri(format!("http://{addr}/pkg.Service/Method"))
+                .header(http::header::COOKIE, "own=1")
+                .body(TransportBody::empty())
+                .unwrap();
+            let body = transport.request(request).await.unwrap().into_body();
+            String::from_utf8(body.collect().await.unwrap().to_bytes().to_vec()).unwrap()
+        }
+
+        let jar = Arc::new(CookieJar::new());
+        let transport = HyperTransportBuilder::new()
+            .cookie_provider(jar.clone())
+            .build()
+            .unwrap();
+        assert_eq!(send(&transport, addr).await, "own=1");
+        assert_eq!(send(&transport, addr).await, "own=1; affinity=backend-1");
+        assert_eq!(jar.len(), 1);
+
+        // Without a store, cookies aren't kept
+        let transport = HyperTransport::new().unwrap();
+        send(&transport, addr).await;
+        assert_eq!(send(&transport, addr).await, "own=1");
+    }
+
+    #[test]
+    fn test_builder_connect_settings() {
+        let builder = HyperTransportBuilder::new();
+        assert_eq!(builder.connect_timeout, None);
+        assert_eq!(
+            builder.happy_eyeballs_timeout,
+            Some(DEFAULT_HAPPY_EYEBALLS_TIMEOUT)
+        );
+
+        let builder = builder
+            .connect_timeout(Duration::from_secs(2))
+            .happy_eyeballs_timeout_none();
+        assert_eq!(builder.connect_timeout, Some(Duration::from_secs(2)));
+        assert_eq!(builder.happy_eyeballs_timeout, None);
+        assert!(builder.build().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_tcp_options() {
+        use hyper_util::rt::TokioIo;
+
+        // Answers with the address the connection came from
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (tcp, peer) = listener.accept().await.unwrap();
+            let service = hyper::service::service_fn(move |_| async move {
+                Ok::<_, std::convert::Infallible>(http::Response::new(peer.ip().to_string()))
+            });
+            let _ = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(tcp), service)
+                .await;
+        });
+
+        let builder = HyperTransportBuilder::new()
+            .tcp_keepalive(Duration::from_secs(60))
+            .tcp_keepalive_interval(Duration::from_secs(10))
+            .tcp_keepalive_retries(3)
+            .tcp_nodelay(true)
+            .local_addresses(Ipv4Addr::LOCALHOST, Ipv6Addr::LOCALHOST)
+            .local_address(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(builder.tcp_keepalive, Some(Duration::from_secs(60)));
+        assert!(builder.tcp_nodelay);
+        // A single address replaces both
+        assert_eq!(builder.local_address_ipv6, None);
+
+        let transport = builder.build().unwrap();
+        let request = http::Request::builder()
+            .uri(format!("http://{addr}/"))
+            .body(TransportBody::empty())
+            .unwrap();
+        let body = transport.request(request).await.unwrap().into_body();
+        let body = http_body_util::BodyExt::collect(body).await.unwrap();
+        assert_eq!(body.to_bytes(), "127.0.0.1");
     }
 
     #[test]
@@ -400,19 +1053,66 @@ mod tests {
         let builder = HyperTransportBuilder::new()
             .h2_initial_stream_window_size(1024 * 1024)
             .h2_initial_connection_window_size(2 * 1024 * 1024)
+            .h2_adaptive_window(true)
+            .h2_max_frame_size(64 * 1024)
             .h2_keep_alive_interval(Duration::from_secs(10))
-            .h2_keep_alive_timeout(Duration::from_secs(5));
+            .h2_keep_alive_timeout(Duration::from_secs(5))
+            .h2_keep_alive_while_idle(true);
 
         assert_eq!(builder.h2_initial_stream_window_size, Some(1024 * 1024));
         assert_eq!(
             builder.h2_initial_connection_window_size,
             Some(2 * 1024 * 1024)
         );
+        assert!(builder.h2_adaptive_window);
+        assert_eq!(builder.h2_max_frame_size, Some(64 * 1024));
         assert_eq!(
             builder.h2_keep_alive_interval,
             Some(Duration::from_secs(10))
         );
         assert_eq!(builder.h2_keep_alive_timeout, Some(Duration::from_secs(5)));
+        assert!(builder.h2_keep_alive_while_idle);
+    }
+
+    #[test]
+    #[should_panic(expected = "max frame size")]
+    fn test_builder_h2_max_frame_size_range() {
+        HyperTransportBuilder::new().h2_max_frame_size(1024);
+    }
+
+    #[tokio::test]
+    async fn test_h2_settings_request() {
+        use hyper_util::rt::TokioIo;
+
+        // An h2c server answering slowly, so pings are sent during the request
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let service = hyper::service::service_fn(|_| async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                Ok::<_, std::convert::Infallible>(http::Response::new(String::new()))
+            });
+            let _ = hyper::server::conn::http2::Builder::new(TokioExecutor::new())
+                .serve_connection(TokioIo::new(tcp), service)
+                .await;
+        });
+
+        let transport = HyperTransportBuilder::new()
+            .http2_only(true)
+            .h2_keep_alive_interval(Duration::from_millis(10))
+            .h2_keep_alive_timeout(Duration::from_secs(1))
+            .h2_keep_alive_while_idle(true)
+            .h2_adaptive_window(true)
+            .h2_max_frame_size(1024 * 1024)
+            .build()
+            .unwrap();
+        let request = http::Request::builder()
+            .uri(format!("http://{addr}/"))
+            .body(TransportBody::empty())
+            .unwrap();
+        let response = transport.request(request).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
     }
 
     #[test]
@@ -427,4 +1127,11 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().is_http2_only());
     }
+
+    #[test]
+    fn test_build_transport_http1_only() {
+        let transport = HyperTransportBuilder::new().http1_only().build().unwrap();
+        assert_eq!(transport.http_version(), HttpVersionPolicy::Http1Only);
+        assert!(!transport.is_http2_only());
+    }
 }
@@ -0,0 +1,122 @@
+//! Transport wrapped in tower middleware.
+//!
+//! This module provides [`LayeredTransport`], which sends requests through a
+//! [`tower::Service`] built by applying [`tower::Layer`]s to another
+//! transport. Standard middleware, such as the timeout, tracing and
+//! concurrency limit layers of `tower` and `tower-http`, then runs on every
+//! HTTP request the client makes.
+//!
+//! The usual way to get one is [`ClientBuilder::layer`](crate::ClientBuilder::layer):
+//!
+//! ```ignore
+//! use connectrpc_axum_client::ConnectClient;
+//! use tower::limit::ConcurrencyLimitLayer;
+//! use tower_http::trace::TraceLayer;
+//!
+//! let client = ConnectClient::builder("http://localhost:3000")
+//!     .layer(TraceLayer::new_for_http())
+//!     .layer(ConcurrencyLimitLayer::new(64))
+//!     .build()?;
+//! ```
+
+use std::fmt::Display;
+
+use bytes::Bytes;
+use http_body::Body;
+use tower::BoxError;
+use tower::ServiceExt;
+use tower_service::Service;
+
+use super::{Transport, TransportBody};
+use crate::ClientError;
+
+/// A [`Transport`] that sends requests through a [`tower::Service`].
+///
+/// The service is typically a transport such as
+/// [`HyperTransport`](super::HyperTransport), which implements
+/// [`tower::Service`], wrapped in layers.
+///
+/// Errors of the service that are a [`ClientError`], like those of
+/// `HyperTransport`, are returned as they are, so their codes survive the
+/// layers. Other errors, e.g. from a timeout layer, become
+/// [`ClientError::Transport`].
+#[derive(Clone, Debug)]
+pub struct LayeredTransport<S> {
+    service: S,
+}
+
+impl<S> LayeredTransport<S> {
+    /// Create a transport that sends requests through `service`.
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+
+    /// Get the underlying service.
+    pub fn get_ref(&self) -> &S {
+        &self.service
+    }
+}
+
+impl<S, B> Transport for LayeredTransport<S>
+where
+    S: Service<http::Request<TransportBody>, Response = http::Response<B>> + Clone + 'static,
+    S::Error: Into<BoxError>,
+    B: Body<Data = Bytes, Error: Display> + Unpin + 'static,
+{
+    type Body = B;
+
+    async fn request(
+        &self,
+        request: http::Request<TransportBody>,
+    ) -> Result<http::Response<B>, ClientError> {
+        self.service.clone().oneshot(request).await.map_err(|e| {
+            match e.into().downcast::<ClientError>() {
+                Ok(e) => *e,
+                Err(e) => ClientError::Transport(format!("request failed: {}", e)),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Code;
+    use http_body_util::Full;
+    use std::convert::Infallible;
+
+    #[tokio::test]
+    async fn test_layered_errors() {
+        let request = || http::Request::new(TransportBody::empty());
+
+        // Client errors keep their code
+        let service = tower::service_fn(|_| async {
+            Err::<http::Response<Full<Bytes>>, _>(ClientError::new(Code::Unauthenticated, "no"))
+        });
+        let err = LayeredTransport::new(service)
+            .request(request())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::Unauthenticated);
+
+        // Others are transport errors
+        let service = tower::service_fn(|_| async {
+            Err::<http::Response<Full<Bytes>>, BoxError>("overloaded".into())
+        });
+        let err = LayeredTransport::new(service)
+            .request(request())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ClientError::Transport(ref m) if m == "request failed: overloaded"));
+
+        let service = tower::service_fn(|_| async {
+            Ok::<_, Infallible>(http::Response::new(Full::new(Bytes::from("ok"))))
+        });
+        assert!(
+            LayeredTransport::new(service)
+                .request(request())
+                .await
+                .is_ok()
+        );
+    }
+}
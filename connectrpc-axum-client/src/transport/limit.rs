@@ -0,0 +1,156 @@
+//! Per-host limit on open connections.
+//!
+//! hyper_util's pool bounds idle connections but opens as many connections
+//! as there are concurrent requests. [`LimitedConnector`] wraps a connector
+//! so each connection holds a permit of its host's semaphore until it is
+//! closed; connecting waits while all permits are taken, and a request that
+//! waits is handed the first connection that becomes idle.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use http::Uri;
+use hyper::rt::{Read, ReadBufCursor, Write};
+use hyper_util::client::legacy::connect::{Connected, Connection};
+use pin_project_lite::pin_project;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower_service::Service;
+
+/// Connector that opens at most `max` connections per host.
+#[derive(Clone, Debug)]
+pub(crate) struct LimitedConnector<C> {
+    inner: C,
+    /// Connection semaphores per host, or `None` without a limit.
+    hosts: Option<Arc<Hosts>>,
+}
+
+#[derive(Debug)]
+struct Hosts {
+    max: usize,
+    semaphores: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl<C> LimitedConnector<C> {
+    /// Wrap `inner`, limiting connections per host to `max` if set.
+    pub(crate) fn new(inner: C, max: Option<usize>) -> Self {
+        Self {
+            inner,
+            hosts: max.map(|max| {
+                Arc::new(Hosts {
+                    max,
+                    semaphores: Mutex::new(HashMap::new()),
+                })
+            }),
+        }
+    }
+}
+
+impl Hosts {
+    /// The semaphore of the host of `uri`.
+    fn semaphore(&self, uri: &Uri) -> Arc<Semaphore> {
+        let key = format!(
+            "{}://{}",
+            uri.scheme_str().unwrap_or_default(),
+            uri.authority().map_or("", |a| a.as_str())
+        );
+        let mut semaphores = self.semaphores.lock().unwrap_or_else(|e| e.into_inner());
+        semaphores
+            .entry(key)
+            .or_insert_with(|| Arc::new(Semaphore::new(self.max)))
+            .clone()
+    }
+}
+
+impl<C> Service<Uri> for LimitedConnector<C>
+where
+    C: Service<Uri>,
+    C::Future: Send + 'static,
+{
+    type Response = LimitedConnection<C::Response>;
+    type Error = C::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let semaphore = self.hosts.as_ref().map(|hosts| hosts.semaphore(&uri));
+        let connecting = self.inner.call(uri);
+        Box::pin(async move {
+            let permit = match semaphore {
+                Some(semaphore) => Some(
+                    semaphore
+                        .acquire_owned()
+                        .await
+                        .expect("connection semaphores are never closed"),
+                ),
+                None => None,
+            };
+            let io = connecting.await?;
+            Ok(LimitedConnection {
+                io,
+                _permit: permit,
+            })
+        })
+    }
+}
+
+pin_project! {
+    /// A connection that releases its host's permit when dropped.
+    pub(crate) struct LimitedConnection<T> {
+        #[pin]
+        io: T,
+        _permit: Option<OwnedSemaphorePermit>,
+    }
+}
+
+impl<T: Read> Read for LimitedConnection<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.project().io.poll_read(cx, buf)
+    }
+}
+
+impl<T: Write> Write for LimitedConnection<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.project().io.poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().io.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().io.poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.io.is_write_vectored()
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.project().io.poll_write_vectored(cx, bufs)
+    }
+}
+
+impl<T: Connection> Connection for LimitedConnection<T> {
+    fn connected(&self) -> Connected {
+        self.io.connected()
+    }
+}
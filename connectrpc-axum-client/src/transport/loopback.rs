@@ -0,0 +1,124 @@
+//! In-memory transport that calls a [`tower::Service`] directly.
+//!
+//! This module provides [`LoopbackTransport`], which hands every request
+//! straight to a service such as an axum `Router` built with
+//! `MakeServiceBuilder`, without opening a socket. Client and server then run
+//! in the same process, which makes integration tests fast and deterministic
+//! and lets an application call its own RPC handlers through the client.
+//!
+//! ```ignore
+//! use connectrpc_axum::MakeServiceBuilder;
+//! use connectrpc_axum_client::{ConnectClient, LoopbackTransport};
+//!
+//! let app = MakeServiceBuilder::new()
+//!     .add_router(hello_world_service_connect::router(HelloWorldServer))
+//!     .build();
+//!
+//! // The base URL is only used to build request URIs
+//! let client = ConnectClient::builder("http://loopback")
+//!     .use_proto()
+//!     .build_with_transport(LoopbackTransport::new(app))?;
+//! ```
+
+use std::fmt::Display;
+
+use bytes::Bytes;
+use http_body::Body;
+use tower::ServiceExt;
+use tower_service::Service;
+
+use super::{Transport, TransportBody};
+use crate::ClientError;
+
+/// A [`Transport`] that passes requests to a [`tower::Service`] in-process.
+///
+/// Every request is sent to a clone of the service, which needn't speak
+/// HTTP/2: all protocols, including gRPC trailers and bidi streaming, work
+/// over the loopback. Requests keep their absolute URI, so services that
+/// route by path, like an axum `Router`, see the procedure path as usual.
+///
+/// Errors of the service become [`ClientError::Transport`]; an axum `Router`
+/// never fails this way.
+#[derive(Clone, Debug)]
+pub struct LoopbackTransport<S> {
+    service: S,
+}
+
+impl<S> LoopbackTransport<S> {
+    /// Create a transport that sends requests to `service`.
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+
+    /// Get the underlying service.
+    pub fn get_ref(&self) -> &S {
+        &self.service
+    }
+}
+
+impl<S, B> Transport for LoopbackTransport<S>
+where
+    S: Service<http::Request<TransportBody>, Response = http::Response<B>> + Clone + 'static,
+    S::Error: Display,
+    B: Body<Data = Bytes, Error: Display> + Unpin + 'static,
+{
+    type Body = B;
+
+    async fn request(
+        &self,
+        request: http::Request<TransportBody>,
+    ) -> Result<http::Response<B>, ClientError> {
+        self.service
+            .clone()
+            .oneshot(request)
+            .await
+            .map_err(|e| ClientError::Transport(format!("request failed: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Code, ConnectClient};
+    use axum::Router;
+    use connectrpc_axum::{
+        Code as ServerCode, ConnectError, ConnectRequest, ConnectResponse, MakeServiceBuilder,
+        post_connect,
+    };
+
+    fn app() -> Router {
+        let greet = |ConnectRequest(name): ConnectRequest<String>| async move {
+            if name.is_empty() {
+                return Err(ConnectError::new(ServerCode::InvalidArgument, "no name"));
+            }
+            Ok(ConnectResponse::new(format!("Hello, {name}!")))
+        };
+        MakeServiceBuilder::new()
+            .add_router(Router::new().route("/test.Greeter/Greet", post_connect(greet)))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_loopback_unary() {
+        for grpc in [false, true] {
+            let builder = ConnectClient::builder("http://loopback").use_proto();
+            let builder = if grpc { builder.use_grpc() } else { builder };
+            let client = builder
+                .build_with_transport(LoopbackTransport::new(app()))
+                .unwrap();
+
+            let response = client
+                .call_unary::<String, String>("test.Greeter/Greet", &"Ada".to_string())
+                .await
+                .unwrap();
+            assert_eq!(response.into_inner(), "Hello, Ada!");
+
+            let err = client
+                .call_unary::<String, String>("test.Greeter/Greet", &String::new())
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), Code::InvalidArgument);
+            assert_eq!(err.message(), Some("no name"));
+        }
+    }
+}
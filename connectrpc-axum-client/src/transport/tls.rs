@@ -0,0 +1,690 @@
+//! High-level TLS configuration for the Connect client.
+//!
+//! [`TlsClientConfig`] covers the common TLS knobs (private CAs and the
+//! choice of built-in roots, client certificates for mTLS, self-signed
+//! development certs, protocol versions, ALPN and SNI) without requiring
+//! users to assemble a [`rustls::ClientConfig`] by hand. It is converted into a rustls config when the transport is built.
+//!
+//! For anything not covered here, build a [`rustls::ClientConfig`] directly
+//! and pass it to [`HyperTransportBuilder::tls_config`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use connectrpc_axum_client::{ClientBuilder, TlsClientConfig, TlsVersion};
+//!
+//! let ca = std::fs::read("ca.pem")?;
+//! let cert = std::fs::read("client.pem")?;
+//! let key = std::fs::read("client.key")?;
+//!
+//! let client = ClientBuilder::new("https://api.internal:8443")
+//!     .with_tls_config(
+//!         TlsClientConfig::new()
+//!             .add_root_certificate(&ca)
+//!             .identity(&cert, &key)
+//!             .min_tls_version(TlsVersion::Tls13),
+//!     )
+//!     .build()?;
+//! ```
+//!
+//! [`HyperTransportBuilder::tls_config`]: crate::transport::HyperTransportBuilder::tls_config
+
+use std::sync::Arc;
+
+use rustls::ClientConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+
+use super::connector::{DangerousAcceptAnyCertVerifier, try_get_crypto_provider};
+
+/// TLS protocol version, used as the minimum or maximum the client accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum TlsVersion {
+    /// TLS 1.2 (the default minimum).
+    #[default]
+    Tls12,
+    /// TLS 1.3 (the default maximum).
+    Tls13,
+}
+
+static TLS12_AND_UP: &[&rustls::SupportedProtocolVersion] =
+    &[&rustls::version::TLS13, &rustls::version::TLS12];
+static TLS12_ONLY: &[&rustls::SupportedProtocolVersion] = &[&rustls::version::TLS12];
+static TLS13_ONLY: &[&rustls::SupportedProtocolVersion] = &[&rustls::version::TLS13];
+
+/// The rustls protocol versions from `min` to `max`.
+fn protocol_versions(
+    min: TlsVersion,
+    max: TlsVersion,
+) -> Result<&'static [&'static rustls::SupportedProtocolVersion], rustls::Error> {
+    match (min, max) {
+        (TlsVersion::Tls12, TlsVersion::Tls13) => Ok(TLS12_AND_UP),
+        (TlsVersion::Tls12, TlsVersion::Tls12) => Ok(TLS12_ONLY),
+        (TlsVersion::Tls13, TlsVersion::Tls13) => Ok(TLS13_ONLY),
+        (TlsVersion::Tls13, TlsVersion::Tls12) => Err(rustls::Error::General(
+            "minimum TLS version is above the maximum".into(),
+        )),
+    }
+}
+
+/// Programmatic TLS configuration for [`ClientBuilder::with_tls_config`].
+///
+/// Certificates and keys are accepted as PEM or DER bytes. Parsing is deferred
+/// until the transport is built, so invalid input surfaces as a
+/// [`ClientBuildError`](crate::ClientBuildError) from `build()`.
+///
+/// Root certificates added here are trusted *in addition to* the feature-gated
+/// built-in roots (`tls-native-roots` / `tls-webpki-roots`), unless those are
+/// turned off with [`built_in_roots`](Self::built_in_roots).
+///
+/// [`ClientBuilder::with_tls_config`]: crate::ClientBuilder::with_tls_config
+#[derive(Clone)]
+pub struct TlsClientConfig {
+    root_certificates: Vec<Vec<u8>>,
+    native_roots: bool,
+    webpki_roots: bool,
+    identity: Option<(Vec<u8>, Vec<u8>)>,
+    danger_accept_invalid_certs: bool,
+    min_tls_version: TlsVersion,
+    max_tls_version: TlsVersion,
+    alpn_protocols: Vec<Vec<u8>>,
+    server_name: Option<String>,
+    sni: bool,
+}
+
+/// Whether native roots are trusted by default.
+const DEFAULT_NATIVE_ROOTS: bool = cfg!(feature = "tls-native-roots");
+
+/// Whether webpki roots are trusted by default: only as the fallback when
+/// native roots aren't enabled.
+const DEFAULT_WEBPKI_ROOTS: bool = cfg!(all(
+    feature = "tls-webpki-roots",
+    not(feature = "tls-native-roots")
+));
+
+impl Default for TlsClientConfig {
+    fn default() -> Self {
+        Self {
+            root_certificates: Vec::new(),
+            native_roots: DEFAULT_NATIVE_ROOTS,
+            webpki_roots: DEFAULT_WEBPKI_ROOTS,
+            identity: None,
+            danger_accept_invalid_certs: false,
+            min_tls_version: TlsVersion::Tls12,
+            max_tls_version: TlsVersion::Tls13,
+            alpn_protocols: Vec::new(),
+            server_name: None,
+            sni: true,
+        }
+    }
+}
+
+impl TlsClientConfig {
+    /// Create an empty configuration that uses the default roots.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust the built-in roots enabled by the `tls-native-roots` and
+    /// `tls-webpki-roots` features.
+    ///
+    /// Disable this to trust only the certificates added with
+    /// [`add_root_certificate`](Self::add_root_certificate), e.g. for a
+    /// service that must only accept its private CA.
+    ///
+    /// Default: true.
+    pub fn built_in_roots(mut self, enabled: bool) -> Self {
+        self.native_roots = enabled && DEFAULT_NATIVE_ROOTS;
+        self.webpki_roots = enabled && DEFAULT_WEBPKI_ROOTS;
+        self
+    }
+
+    /// Trust the certificates of the platform's trust store.
+    ///
+    /// Default: true.
+    #[cfg(feature = "tls-native-roots")]
+    pub fn native_roots(mut self, enabled: bool) -> Self {
+        self.native_roots = enabled;
+        self
+    }
+
+    /// Trust the Mozilla root certificates bundled by `webpki-roots`.
+    ///
+    /// This gives the same trust on every machine, e.g. in minimal
+    /// containers without a CA bundle.
+    ///
+    /// Default: true only if `tls-native-roots` isn't enabled.
+    #[cfg(feature = "tls-webpki-roots")]
+    pub fn webpki_roots(mut self, enabled: bool) -> Self {
+        self.webpki_roots = enabled;
+        self
+    }
+
+    /// Trust an additional root certificate.
+    ///
+    /// Accepts a PEM bundle (one or more `CERTIFICATE` blocks) or a single
+    /// DER-encoded certificate. Use this for private CAs and self-signed
+    /// development certificates.
+    pub fn add_root_certificate(mut self, cert: &[u8]) -> Self {
+        self.root_certificates.push(cert.to_vec());
+        self
+    }
+
+    /// Present a client certificate for mutual TLS.
+    ///
+    /// `cert` is the certificate chain (leaf first) as PEM, or a single DER
+    /// certificate. `key` is the matching private key as PEM (PKCS#8, PKCS#1
+    /// or SEC1) or DER.
+    pub fn identity(mut self, cert: &[u8], key: &[u8]) -> Self {
+        self.identity = Some((cert.to_vec(), key.to_vec()));
+        self
+    }
+
+    /// Skip server certificate verification.
+    ///
+    /// # Warning
+    ///
+    /// This is extremely dangerous and should only be used for development/testing!
+    /// It makes the connection vulnerable to man-in-the-middle attacks.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    /// Set the minimum TLS protocol version.
+    ///
+    /// Default: [`TlsVersion::Tls12`].
+    pub fn min_tls_version(mut self, version: TlsVersion) -> Self {
+        self.min_tls_version = version;
+        self
+    }
+
+    /// Set the maximum TLS protocol version.
+    ///
+    /// Default: [`TlsVersion::Tls13`].
+    pub fn max_tls_version(mut self, version: TlsVersion) -> Self {
+        self.max_tls_version = version;
+        self
+    }
+
+    /// Set the protocols offered with ALPN.
+    ///
+    /// The negotiated protocol decides between HTTP/2 (`h2`) and HTTP/1.1
+    /// (`http/1.1`); other protocols are ignored. Offering both is the
+    /// default, with `h2` preferred whatever the order given here. List only
+    /// one to insist on it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use connectrpc_axum_client::TlsClientConfig;
+    ///
+    /// let config = TlsClientConfig::new().alpn_protocols(["h2", "http/1.1"]);
+    /// ```
+    pub fn alpn_protocols<I, P>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        self.alpn_protocols = protocols.into_iter().map(|p| p.as_ref().to_vec()).collect();
+        self
+    }
+
+    /// Send `name` as the server name (SNI) and verify the server
+    /// certificate against it, instead of the host of the URL.
+    ///
+    /// Use this to connect by IP address, or through a tunnel or proxy, to a
+    /// server whose certificate names another host. `name` may be a DNS name
+    /// or an IP address.
+    pub fn server_name(mut self, name: impl Into<String>) -> Self {
+        self.server_name = Some(name.into());
+        self
+    }
+
+    /// Send the server name indication (SNI) extension.
+    ///
+    /// The server certificate is verified against the server name either
+    /// way. Disable this for servers that reject SNI.
+    ///
+    /// Default: true.
+    pub fn sni(mut self, enabled: bool) -> Self {
+        self.sni = enabled;
+        self
+    }
+
+    /// Build the underlying rustls client configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no crypto provider is available, or if a
+    /// certificate or key cannot be parsed.
+    pub fn build(&self) -> Result<ClientConfig, rustls::Error> {
+        let provider = try_get_crypto_provider().ok_or_else(|| {
+            rustls::Error::General(
+                "no crypto provider available; enable `tls-ring` or `tls-aws-lc`".into(),
+            )
+        })?;
+
+        let builder = ClientConfig::builder_with_provider(provider).with_protocol_versions(
+            protocol_versions(self.min_tls_version, self.max_tls_version)?,
+        )?;
+
+        let builder = if self.danger_accept_invalid_certs {
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(DangerousAcceptAnyCertVerifier))
+        } else {
+            builder.with_root_certificates(self.root_store()?)
+        };
+
+        let mut config = match &self.identity {
+            Some((cert, key)) => {
+                builder.with_client_auth_cert(parse_certs(cert)?, parse_key(key)?)?
+            }
+            None => builder.with_no_client_auth(),
+        };
+        config.alpn_protocols = self.alpn_protocols.clone();
+        config.enable_sni = self.sni;
+
+        Ok(config)
+    }
+
+    /// The server name to use instead of the host of the URL, if set.
+    pub(crate) fn parsed_server_name(&self) -> Result<Option<ServerName<'static>>, rustls::Error> {
+        let Some(name) = &self.server_name else {
+            return Ok(None);
+        };
+        // IPv6 addresses may be given in URL form
+        let trimmed = name
+            .strip_prefix('[')
+            .and_then(|n| n.strip_suffix(']'))
+            .unwrap_or(name);
+        ServerName::try_from(trimmed.to_string())
+            .map(Some)
+            .map_err(|e| rustls::Error::General(format!("invalid server name `{name}`: {e}")))
+    }
+
+    /// Built-in roots (if enabled) plus any user-supplied certificates.
+    fn root_store(&self) -> Result<rustls::RootCertStore, rustls::Error> {
+        let mut roots = rustls::RootCertStore::empty();
+        #[cfg(feature = "tls-native-roots")]
+        if self.native_roots {
+            super::connector::add_native_roots(&mut roots);
+        }
+        #[cfg(feature = "tls-webpki-roots")]
+        if self.webpki_roots {
+            super::connector::add_webpki_roots(&mut roots);
+        }
+
+        for cert in &self.root_certificates {
+            for der in parse_certs(cert)? {
+                roots.add(der)?;
+            }
+        }
+
+        Ok(roots)
+    }
+}
+
+impl std::fmt::Debug for TlsClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsClientConfig")
+            .field("root_certificates", &self.root_certificates.len())
+            .field("native_roots", &self.native_roots)
+            .field("webpki_roots", &self.webpki_roots)
+            .field("identity", &self.identity.is_some())
+            .field(
+                "danger_accept_invalid_certs",
+                &self.danger_accept_invalid_certs,
+            )
+            .field("min_tls_version", &self.min_tls_version)
+            .field("max_tls_version", &self.max_tls_version)
+            .field("alpn_protocols", &self.alpn_protocols)
+            .field("server_name", &self.server_name)
+            .field("sni", &self.sni)
+            .finish()
+    }
+}
+
+/// Returns true if the input looks like PEM rather than DER.
+fn is_pem(bytes: &[u8]) -> bool {
+    bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .is_some_and(|start| bytes[start..].starts_with(b"-----BEGIN"))
+}
+
+/// Parse one or more certificates from PEM, or a single DER certificate.
+fn parse_certs(bytes: &[u8]) -> Result<Vec<CertificateDer<'static>>, rustls::Error> {
+    if !is_pem(bytes) {
+        return Ok(vec![CertificateDer::from(bytes.to_vec())]);
+    }
+
+    let certs = CertificateDer::pem_slice_iter(bytes)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| rustls::Error::General(format!("invalid PEM certificate: {e}")))?;
+
+    if certs.is_empty() {
+        return Err(rustls::Error::General(
+            "no certificates found in PEM input".into(),
+        ));
+    }
+
+    Ok(certs)
+}
+
+/// Parse a private key from PEM or DER.
+fn parse_key(bytes: &[u8]) -> Result<PrivateKeyDer<'static>, rustls::Error> {
+    if is_pem(bytes) {
+        PrivateKeyDer::from_pem_slice(bytes)
+            .map_err(|e| rustls::Error::General(format!("invalid PEM private key: {e}")))
+    } else {
+        PrivateKeyDer::try_from(bytes.to_vec())
+            .map_err(|e| rustls::Error::General(format!("invalid DER private key: {e}")))
+    }
+}
+
+#[cfg(all(test, feature = "tls-ring"))]
+mod tests {
+    use super::*;
+
+    fn self_signed() -> rcgen::CertifiedKey<rcgen::KeyPair> {
+        rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap()
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = TlsClientConfig::new();
+        assert!(config.root_certificates.is_empty());
+        assert!(config.identity.is_none());
+        assert!(!config.danger_accept_invalid_certs);
+        assert_eq!(config.min_tls_version, TlsVersion::Tls12);
+        assert!(config.build().is_ok());
+    }
+
+    #[test]
+    fn test_add_root_certificate_pem_and_der() {
+        let ca = self_signed();
+        let pem = ca.cert.pem();
+        let der = ca.cert.der().to_vec();
+
+        let config = TlsClientConfig::new()
+            .add_root_certificate(pem.as_bytes())
+            .add_root_certificate(&der);
+        assert_eq!(config.root_certificates.len(), 2);
+        assert!(config.build().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_root_certificate() {
+        let config = TlsClientConfig::new()
+            .add_root_certificate(b"-----BEGIN CERTIFICATE-----\nnot base64\n");
+        assert!(config.build().is_err());
+    }
+
+    #[test]
+    fn test_identity() {
+        let client = self_signed();
+        let config = TlsClientConfig::new().identity(
+            client.cert.pem().as_bytes(),
+            client.signing_key.serialize_pem().as_bytes(),
+        );
+        let built = config.build().unwrap();
+        assert!(built.client_auth_cert_resolver.has_certs());
+    }
+
+    #[test]
+    fn test_identity_invalid_key() {
+        let client = self_signed();
+        let config = TlsClientConfig::new().identity(client.cert.pem().as_bytes(), b"garbage");
+        assert!(config.build().is_err());
+    }
+
+    #[test]
+    fn test_min_tls_version() {
+        assert_eq!(
+            protocol_versions(TlsVersion::Tls12, TlsVersion::Tls13)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            protocol_versions(TlsVersion::Tls13, TlsVersion::Tls13)
+                .unwrap()
+                .len(),
+            1
+        );
+        let config = TlsClientConfig::new().min_tls_version(TlsVersion::Tls13);
+        assert!(config.build().is_ok());
+    }
+
+    #[test]
+    fn test_max_tls_version() {
+        let config = TlsClientConfig::new().max_tls_version(TlsVersion::Tls12);
+        assert!(config.build().is_ok());
+
+        let config = config.min_tls_version(TlsVersion::Tls13);
+        assert!(config.build().is_err());
+    }
+
+    #[test]
+    fn test_built_in_roots() {
+        let config = TlsClientConfig::new();
+        assert_eq!(config.native_roots, DEFAULT_NATIVE_ROOTS);
+
+        let ca = self_signed();
+        let config = config
+            .built_in_roots(false)
+            .add_root_certificate(ca.cert.der());
+        assert!(!config.native_roots && !config.webpki_roots);
+        assert_eq!(config.root_store().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_alpn_sni_and_server_name() {
+        let config = TlsClientConfig::new()
+            .alpn_protocols(["h2", "http/1.1"])
+            .sni(false);
+        let built = config.build().unwrap();
+        assert_eq!(built.alpn_protocols, [b"h2".to_vec(), b"http/1.1".to_vec()]);
+        assert!(!built.enable_sni);
+        assert_eq!(config.parsed_server_name().unwrap(), None);
+
+        let config = config.server_name("[::1]");
+        assert!(matches!(
+            config.parsed_server_name(),
+            Ok(Some(ServerName::IpAddress(_)))
+        ));
+        assert!(
+            TlsClientConfig::new()
+                .server_name("not a name")
+                .parsed_server_name()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_danger_accept_invalid_certs() {
+        let config = TlsClientConfig::new().danger_accept_invalid_certs(true);
+        assert!(config.build().is_ok());
+    }
+
+    // ------------------------------------------------------------------------
+    // End-to-end tests against a local self-signed TLS server
+    // ------------------------------------------------------------------------
+
+    use crate::transport::{HyperTransportBuilder, TransportBody};
+    use hyper_util::rt::TokioIo;
+    use rustls::pki_types::PrivatePkcs8KeyDer;
+
+    fn provider() -> Arc<rustls::crypto::CryptoProvider> {
+        Arc::new(rustls::crypto::ring::default_provider())
+    }
+
+    fn server_config(
+        server: &rcgen::CertifiedKey<rcgen::KeyPair>,
+        versions: &[&'static rustls::SupportedProtocolVersion],
+        client_ca: Option<&rcgen::CertifiedKey<rcgen::KeyPair>>,
+    ) -> rustls::ServerConfig {
+        let builder = rustls::ServerConfig::builder_with_provider(provider())
+            .with_protocol_versions(versions)
+            .unwrap();
+        let builder = match client_ca {
+            Some(ca) => {
+                let mut roots = rustls::RootCertStore::empty();
+                roots.add(ca.cert.der().clone()).unwrap();
+                let verifier = rustls::server::WebPkiClientVerifier::builder_with_provider(
+                    Arc::new(roots),
+                    provider(),
+                )
+                .build()
+                .unwrap();
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        builder
+            .with_single_cert(
+                vec![server.cert.der().clone()],
+                PrivatePkcs8KeyDer::from(server.signing_key.serialize_der()).into(),
+            )
+            .unwrap()
+    }
+
+    /// Spawn an HTTP/1.1 server over TLS that answers every request with 200.
+    async fn serve(config: rustls::ServerConfig) -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(tls) = acceptor.accept(tcp).await else {
+                        return;
+                    };
+                    let service = hyper::service::service_fn(|_req| async {
+                        Ok::<_, std::convert::Infallible>(http::Response::new(
+                            http_body_util::Full::new(bytes::Bytes::from_static(b"ok")),
+                        ))
+                    });
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(tls), service)
+                        .await;
+                });
+            }
+        });
+
+        addr
+    }
+
+    async fn get(config: TlsClientConfig, addr: std::net::SocketAddr) -> Result<(), String> {
+        let transport = HyperTransportBuilder::new()
+            .with_tls_config(config)
+            .build()
+            .map_err(|e| e.to_string())?;
+        let request = http::Request::get(format!("https://localhost:{}/", addr.port()))
+            .body(TransportBody::empty())
+            .unwrap();
+        let response = transport
+            .request(request)
+            .await
+            .map_err(|e| e.to_string())?;
+        assert_eq!(response.status(), http::StatusCode::OK);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_self_signed_server_rejected_by_default() {
+        let server = self_signed();
+        let addr = serve(server_config(&server, rustls::ALL_VERSIONS, None)).await;
+
+        assert!(get(TlsClientConfig::new(), addr).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_self_signed_server_with_custom_root() {
+        let server = self_signed();
+        let addr = serve(server_config(&server, rustls::ALL_VERSIONS, None)).await;
+
+        let config = TlsClientConfig::new().add_root_certificate(server.cert.pem().as_bytes());
+        get(config, addr).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_self_signed_server_with_danger_accept_invalid_certs() {
+        let server = self_signed();
+        let addr = serve(server_config(&server, rustls::ALL_VERSIONS, None)).await;
+
+        let config = TlsClientConfig::new().danger_accept_invalid_certs(true);
+        get(config, addr).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mtls() {
+        let server = self_signed();
+        let client = self_signed();
+        let addr = serve(server_config(&server, rustls::ALL_VERSIONS, Some(&client))).await;
+
+        let without_identity =
+            TlsClientConfig::new().add_root_certificate(server.cert.pem().as_bytes());
+        assert!(get(without_identity.clone(), addr).await.is_err());
+
+        let with_identity = without_identity.identity(
+            client.cert.pem().as_bytes(),
+            client.signing_key.serialize_pem().as_bytes(),
+        );
+        get(with_identity, addr).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mtls_der_identity() {
+        let server = self_signed();
+        let client = self_signed();
+        let addr = serve(server_config(&server, rustls::ALL_VERSIONS, Some(&client))).await;
+
+        let config = TlsClientConfig::new()
+            .add_root_certificate(server.cert.der())
+            .identity(client.cert.der(), &client.signing_key.serialize_der());
+        get(config, addr).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_min_tls_version_enforced() {
+        let server = self_signed();
+        let addr = serve(server_config(&server, &[&rustls::version::TLS12], None)).await;
+        let base = TlsClientConfig::new().add_root_certificate(server.cert.der());
+
+        get(base.clone().min_tls_version(TlsVersion::Tls12), addr)
+            .await
+            .unwrap();
+        assert!(
+            get(base.min_tls_version(TlsVersion::Tls13), addr)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_name_override() {
+        let server = rcgen::generate_simple_self_signed(vec!["api.internal".to_string()]).unwrap();
+        let addr = serve(server_config(&server, rustls::ALL_VERSIONS, None)).await;
+        let base = TlsClientConfig::new().add_root_certificate(server.cert.der());
+
+        // The certificate doesn't name `localhost`, which the URL uses
+        assert!(get(base.clone(), addr).await.is_err());
+        get(base.clone().server_name("api.internal"), addr)
+            .await
+            .unwrap();
+
+        // An explicit ALPN list goes through the same connector
+        get(
+            base.server_name("api.internal")
+                .alpn_protocols(["http/1.1"]),
+            addr,
+        )
+        .await
+        .unwrap();
+    }
+}
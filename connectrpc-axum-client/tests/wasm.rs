@@ -0,0 +1,128 @@
+//! Tests of the fetch transport on wasm32, run in Node.js:
+//!
+//! ```sh
+//! CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//!     cargo test -p connectrpc-axum-client --target wasm32-unknown-unknown \
+//!     --no-default-features --features wasm --test wasm
+//! ```
+//!
+//! The global `fetch` is replaced by a stub answering from a `ReadableStream`
+//! of the given chunks, so no server is needed.
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use bytes::Bytes;
+use connectrpc_axum_client::{
+    ClientError, ConnectClient, FetchBody, FetchTransport, TransportBody,
+};
+use http_body::Body;
+use http_body_util::BodyExt;
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen(inline_js = r#"
+export function stub_fetch(chunks, contentType) {
+    globalThis.fetch = async (request) => {
+        const body = new ReadableStream({
+            start(controller) {
+                for (const chunk of chunks) controller.enqueue(chunk);
+                controller.close();
+            },
+        });
+        return new Response(body, {
+            headers: { "content-type": contentType, "x-request-url": request.url },
+        });
+    };
+}
+"#)]
+extern "C" {
+    fn stub_fetch(chunks: Array, content_type: &str);
+}
+
+/// Make `fetch` answer with a stream of `chunks`.
+fn stub(chunks: &[JsValue]) {
+    stub_fetch(chunks.iter().collect(), "application/proto");
+}
+
+fn bytes(data: &[u8]) -> JsValue {
+    Uint8Array::from(data).into()
+}
+
+async fn fetch() -> http::Response<FetchBody> {
+    let request = http::Request::get("http://stub/test.Service/Method")
+        .body(TransportBody::empty())
+        .unwrap();
+    FetchTransport::new().request(request).await.unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_fetch_copies_response_headers() {
+    stub(&[]);
+    let response = fetch().await;
+    assert_eq!(response.status(), http::StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/proto");
+    assert_eq!(
+        response.headers()["x-request-url"],
+        "http://stub/test.Service/Method"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_fetch_body_yields_each_chunk() {
+    stub(&[bytes(b"\x00\x01"), bytes(b"\xffhello")]);
+    let mut body = fetch().await.into_body();
+    assert!(!body.is_end_stream());
+
+    let mut chunks = Vec::new();
+    while let Some(frame) = body.frame().await {
+        chunks.push(frame.unwrap().into_data().unwrap());
+    }
+    assert_eq!(
+        chunks,
+        [
+            Bytes::from_static(b"\x00\x01"),
+            Bytes::from_static(b"\xffhello")
+        ]
+    );
+    assert!(body.is_end_stream());
+    assert!(body.frame().await.is_none());
+}
+
+#[wasm_bindgen_test]
+async fn test_fetch_body_empty() {
+    stub(&[]);
+    let body = fetch().await.into_body().collect().await.unwrap();
+    assert!(body.to_bytes().is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn test_fetch_body_rejects_non_byte_chunks() {
+    stub(&[JsValue::from_str("text")]);
+    let mut body = fetch().await.into_body();
+
+    let err = body.frame().await.unwrap().unwrap_err();
+    assert!(
+        matches!(&err, ClientError::Transport(message) if message.contains("Uint8Array")),
+        "{err:?}"
+    );
+    assert!(body.is_end_stream());
+}
+
+#[wasm_bindgen_test]
+async fn test_unary_call_over_fetch() {
+    stub(&[]);
+    let client = ConnectClient::builder("http://stub")
+        .use_proto()
+        .build()
+        .unwrap();
+
+    let response = client
+        .call_unary::<(), ()>("test.Service/Method", &())
+        .await
+        .unwrap();
+    assert_eq!(
+        response.metadata().get("x-request-url"),
+        Some("http://stub/test.Service/Method")
+    );
+}
@@ -5,7 +5,7 @@
 //! - [`CompressionLevel`]: Compression quality settings
 //! - [`CompressionConfig`]: Server/client compression configuration
 
-use crate::codec::BoxedCodec;
+use crate::codec::{BoxedCodec, Codec, DecompressError};
 
 #[cfg(feature = "compression-gzip-stream")]
 use crate::codec::GzipCodec;
@@ -35,6 +35,57 @@ pub enum CompressionEncoding {
     Brotli,
     #[cfg(feature = "compression-zstd-stream")]
     Zstd,
+    /// A user-provided codec, see [`CompressionEncoding::custom`].
+    Custom(CustomCodec),
+}
+
+/// A user-provided codec usable as a [`CompressionEncoding`].
+///
+/// Two custom codecs are equal if they have the same name.
+#[derive(Clone, Copy)]
+pub struct CustomCodec(&'static dyn Codec);
+
+impl CustomCodec {
+    /// The encoding name of the codec.
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+impl std::fmt::Debug for CustomCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomCodec").field(&self.name()).finish()
+    }
+}
+
+impl PartialEq for CustomCodec {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl Eq for CustomCodec {}
+
+impl Codec for CustomCodec {
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn compress(&self, data: &[u8]) -> std::io::Result<bytes::Bytes> {
+        self.0.compress(data)
+    }
+
+    fn decompress(&self, data: &[u8]) -> std::io::Result<bytes::Bytes> {
+        self.0.decompress(data)
+    }
+
+    fn decompress_limited(
+        &self,
+        data: &[u8],
+        max_output: usize,
+    ) -> Result<bytes::Bytes, DecompressError> {
+        self.0.decompress_limited(data, max_output)
+    }
 }
 
 impl CompressionEncoding {
@@ -55,6 +106,29 @@ impl CompressionEncoding {
         }
     }
 
+    /// Parse a header value, also accepting the given custom encodings.
+    ///
+    /// Built-in encodings take precedence over custom ones with the same name.
+    pub fn from_header_with(value: Option<&str>, custom: &[CompressionEncoding]) -> Option<Self> {
+        Self::from_header(value).or_else(|| {
+            let value = value?;
+            custom.iter().find(|enc| enc.as_str() == value).copied()
+        })
+    }
+
+    /// Create an encoding backed by a user-provided codec (e.g. lz4 or snappy).
+    ///
+    /// The codec is borrowed for `'static` so that the encoding stays `Copy`;
+    /// pass a reference to a unit struct or a `static`.
+    pub fn custom(codec: &'static dyn Codec) -> Self {
+        Self::Custom(CustomCodec(codec))
+    }
+
+    /// Returns true if this encoding is backed by a user-provided codec.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom(_))
+    }
+
     /// Get the header value string for this encoding.
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -67,6 +141,7 @@ impl CompressionEncoding {
             Self::Brotli => "br",
             #[cfg(feature = "compression-zstd-stream")]
             Self::Zstd => "zstd",
+            Self::Custom(codec) => codec.name(),
         }
     }
 
@@ -89,6 +164,7 @@ impl CompressionEncoding {
             Self::Brotli => Some(BoxedCodec::new(BrotliCodec::default())),
             #[cfg(feature = "compression-zstd-stream")]
             Self::Zstd => Some(BoxedCodec::new(ZstdCodec::default())),
+            Self::Custom(codec) => Some(BoxedCodec::new(*codec)),
         }
     }
 
@@ -113,6 +189,8 @@ impl CompressionEncoding {
             )))),
             #[cfg(feature = "compression-zstd-stream")]
             Self::Zstd => Some(BoxedCodec::new(ZstdCodec::with_level(level_to_zstd(level)))),
+            // Custom codecs choose their own level
+            Self::Custom(codec) => Some(BoxedCodec::new(*codec)),
         }
     }
 }
@@ -201,6 +279,13 @@ pub struct CompressionConfig {
     pub min_bytes: usize,
     /// Compression level/quality.
     pub level: CompressionLevel,
+    /// Send payloads uncompressed when compression doesn't pay off.
+    /// Default is false.
+    ///
+    /// When enabled, payloads that look already compressed (see
+    /// [`looks_incompressible`]) are not run through the codec, and payloads
+    /// whose compressed form is not smaller than the input are sent as-is.
+    pub skip_incompressible: bool,
 }
 
 impl Default for CompressionConfig {
@@ -208,6 +293,7 @@ impl Default for CompressionConfig {
         Self {
             min_bytes: 0,
             level: CompressionLevel::Default,
+            skip_incompressible: false,
         }
     }
 }
@@ -217,7 +303,7 @@ impl CompressionConfig {
     pub fn new(min_bytes: usize) -> Self {
         Self {
             min_bytes,
-            level: CompressionLevel::Default,
+            ..Self::default()
         }
     }
 
@@ -227,11 +313,19 @@ impl CompressionConfig {
         self
     }
 
+    /// Skip compression for payloads that don't benefit from it.
+    ///
+    /// See [`CompressionConfig::skip_incompressible`].
+    pub fn skip_incompressible(mut self, skip: bool) -> Self {
+        self.skip_incompressible = skip;
+        self
+    }
+
     /// Disable compression by setting threshold to usize::MAX.
     pub fn disabled() -> Self {
         Self {
             min_bytes: usize::MAX,
-            level: CompressionLevel::Default,
+            ..Self::default()
         }
     }
 
@@ -241,6 +335,46 @@ impl CompressionConfig {
     }
 }
 
+/// Number of leading bytes inspected by [`looks_incompressible`].
+const ENTROPY_SAMPLE_BYTES: usize = 4096;
+
+/// Payloads shorter than this are never classified as incompressible; the
+/// byte histogram of a short sample is too noisy.
+const ENTROPY_MIN_BYTES: usize = 256;
+
+/// Shannon entropy (bits per byte) above which a sample is treated as
+/// already compressed or encrypted. Compressed formats such as JPEG, zstd
+/// and gzip land close to 8.0; text and typical protobuf stay well below 7.
+const ENTROPY_THRESHOLD: f64 = 7.5;
+
+/// Heuristically detect payloads that are already compressed.
+///
+/// Estimates the byte entropy of the first 4 KiB of `data`. Nearly uniform
+/// byte distributions (compressed images, zstd/gzip blobs, encrypted data)
+/// are unlikely to shrink, so compressing them again only wastes CPU.
+pub fn looks_incompressible(data: &[u8]) -> bool {
+    if data.len() < ENTROPY_MIN_BYTES {
+        return false;
+    }
+    let sample = &data[..data.len().min(ENTROPY_SAMPLE_BYTES)];
+
+    let mut counts = [0u32; 256];
+    for &byte in sample {
+        counts[byte as usize] += 1;
+    }
+
+    let len = sample.len() as f64;
+    let entropy: f64 = counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum();
+    entropy > ENTROPY_THRESHOLD
+}
+
 /// Returns a comma-separated string of supported encodings for error messages.
 pub fn supported_encodings_str() -> &'static str {
     // Build string based on enabled features
@@ -396,6 +530,17 @@ pub fn supported_encodings_str() -> &'static str {
 /// Follows connect-go's approach: first supported encoding wins (client preference order).
 /// Respects `q=0` which means "not acceptable" per RFC 7231.
 pub fn negotiate_response_encoding(accept: Option<&str>) -> CompressionEncoding {
+    negotiate_response_encoding_with(accept, &[])
+}
+
+/// Negotiate response encoding, also considering the given custom encodings.
+///
+/// Like [`negotiate_response_encoding`], the client's first acceptable
+/// encoding wins.
+pub fn negotiate_response_encoding_with(
+    accept: Option<&str>,
+    custom: &[CompressionEncoding],
+) -> CompressionEncoding {
     let Some(accept) = accept else {
         return CompressionEncoding::Identity;
     };
@@ -434,7 +579,11 @@ pub fn negotiate_response_encoding(accept: Option<&str>) -> CompressionEncoding
             #[cfg(feature = "compression-zstd-stream")]
             "zstd" => return CompressionEncoding::Zstd,
             "identity" => return CompressionEncoding::Identity,
-            _ => continue,
+            other => {
+                if let Some(enc) = custom.iter().find(|enc| enc.as_str() == other) {
+                    return *enc;
+                }
+            }
         }
     }
 
@@ -507,6 +656,7 @@ mod tests {
         let config = CompressionConfig::default();
         assert_eq!(config.min_bytes, 0);
         assert_eq!(config.level, CompressionLevel::Default);
+        assert!(!config.skip_incompressible);
     }
 
     #[test]
@@ -522,6 +672,29 @@ mod tests {
         assert!(config.is_disabled());
     }
 
+    #[test]
+    fn test_looks_incompressible() {
+        // Short payloads are never classified.
+        let short: Vec<u8> = (0..=255).collect();
+        assert!(!looks_incompressible(&short[..200]));
+
+        // Repetitive text compresses well.
+        let text = "hello world, hello connect ".repeat(100);
+        assert!(!looks_incompressible(text.as_bytes()));
+
+        // Pseudo-random bytes look like an already-compressed blob.
+        let mut state = 0x2545_u32;
+        let random: Vec<u8> = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+        assert!(looks_incompressible(&random));
+    }
+
     #[test]
     fn test_negotiate_response_encoding_identity() {
         assert_eq!(
@@ -569,4 +742,50 @@ mod tests {
             CompressionEncoding::Gzip
         );
     }
+
+    /// Reverses the bytes; good enough to check the codec is used.
+    struct ReverseCodec;
+
+    impl Codec for ReverseCodec {
+        fn name(&self) -> &'static str {
+            "reverse"
+        }
+
+        fn compress(&self, data: &[u8]) -> std::io::Result<bytes::Bytes> {
+            Ok(data.iter().rev().copied().collect())
+        }
+
+        fn decompress(&self, data: &[u8]) -> std::io::Result<bytes::Bytes> {
+            self.compress(data)
+        }
+    }
+
+    #[test]
+    fn test_custom_encoding() {
+        let custom = [CompressionEncoding::custom(&ReverseCodec)];
+        assert_eq!(custom[0].as_str(), "reverse");
+        assert!(custom[0].is_custom());
+
+        assert_eq!(CompressionEncoding::from_header(Some("reverse")), None);
+        assert_eq!(
+            CompressionEncoding::from_header_with(Some("reverse"), &custom),
+            Some(custom[0])
+        );
+        assert_eq!(
+            CompressionEncoding::from_header_with(None, &custom),
+            Some(CompressionEncoding::Identity)
+        );
+        assert_eq!(
+            negotiate_response_encoding_with(Some("lz4, reverse;q=0.5"), &custom),
+            custom[0]
+        );
+        assert_eq!(
+            negotiate_response_encoding(Some("reverse")),
+            CompressionEncoding::Identity
+        );
+
+        let codec = custom[0].codec().unwrap();
+        assert_eq!(codec.name(), "reverse");
+        assert_eq!(&codec.compress(b"abc").unwrap()[..], b"cba");
+    }
 }
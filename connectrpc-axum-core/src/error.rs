@@ -7,6 +7,7 @@
 
 use std::str::FromStr;
 
+use http::StatusCode;
 use serde::{Serialize, Serializer};
 
 /// Connect RPC error codes, matching the codes defined in the Connect protocol.
@@ -87,6 +88,87 @@ impl Code {
     }
 }
 
+impl Code {
+    /// Get the numeric gRPC status code for this code.
+    ///
+    /// Connect and gRPC share the same code space, so this is the enum
+    /// discriminant (`Ok` = 0 through `Unauthenticated` = 16).
+    pub fn to_grpc_code(&self) -> u32 {
+        *self as u32
+    }
+
+    /// Convert a numeric gRPC status code into a [`Code`].
+    ///
+    /// Returns `None` for values outside the defined range (> 16).
+    pub fn from_grpc_code(code: u32) -> Option<Code> {
+        let code = match code {
+            0 => Code::Ok,
+            1 => Code::Canceled,
+            2 => Code::Unknown,
+            3 => Code::InvalidArgument,
+            4 => Code::DeadlineExceeded,
+            5 => Code::NotFound,
+            6 => Code::AlreadyExists,
+            7 => Code::PermissionDenied,
+            8 => Code::ResourceExhausted,
+            9 => Code::FailedPrecondition,
+            10 => Code::Aborted,
+            11 => Code::OutOfRange,
+            12 => Code::Unimplemented,
+            13 => Code::Internal,
+            14 => Code::Unavailable,
+            15 => Code::DataLoss,
+            16 => Code::Unauthenticated,
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    /// Get the HTTP status used for a unary Connect error with this code.
+    ///
+    /// Follows the Connect protocol's code to HTTP status table.
+    pub fn to_http_status(&self) -> StatusCode {
+        match self {
+            Code::Ok => StatusCode::OK,
+            // 499 Client Closed Request (nginx extension) - client canceled the operation
+            Code::Canceled => StatusCode::from_u16(499).expect("499 is a valid status code"),
+            Code::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
+            Code::InvalidArgument => StatusCode::BAD_REQUEST,
+            // 504 Gateway Timeout - server-side deadline exceeded
+            Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+            Code::NotFound => StatusCode::NOT_FOUND,
+            Code::AlreadyExists => StatusCode::CONFLICT,
+            Code::PermissionDenied => StatusCode::FORBIDDEN,
+            Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+            Code::FailedPrecondition => StatusCode::BAD_REQUEST,
+            Code::Aborted => StatusCode::CONFLICT,
+            Code::OutOfRange => StatusCode::BAD_REQUEST,
+            Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+            Code::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+            Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
+            Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    /// Infer a [`Code`] from an HTTP status when no Connect error body is available.
+    ///
+    /// Used for responses that fail before reaching an RPC handler (proxies,
+    /// load balancers, non-Connect servers). Follows the HTTP-to-code table
+    /// shared by the Connect and gRPC specs; statuses without a specific
+    /// mapping become [`Code::Unknown`].
+    pub fn from_http_status_connect(status: StatusCode) -> Code {
+        match status.as_u16() {
+            400 => Code::Internal,
+            401 => Code::Unauthenticated,
+            403 => Code::PermissionDenied,
+            404 => Code::Unimplemented,
+            429 | 502 | 503 | 504 => Code::Unavailable,
+            _ => Code::Unknown,
+        }
+    }
+}
+
 /// Error returned when parsing a [`Code`] from a string fails.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ParseCodeError(());
@@ -164,6 +246,43 @@ impl ErrorDetail {
     pub fn value(&self) -> &[u8] {
         &self.value
     }
+
+    /// Create an error detail by encoding a protobuf message.
+    ///
+    /// The type URL is taken from the message's [`prost::Name`] implementation,
+    /// e.g. `type.googleapis.com/google.rpc.RetryInfo`.
+    pub fn from_message<M: prost::Message + prost::Name>(message: &M) -> Self {
+        Self::new(M::type_url(), message.encode_to_vec())
+    }
+
+    /// Returns true if the detail holds a message of type `M`.
+    ///
+    /// Both full type URLs and the short type names used on the Connect wire
+    /// are accepted.
+    pub fn is<M: prost::Name>(&self) -> bool {
+        let type_name = self
+            .type_url
+            .strip_prefix("type.googleapis.com/")
+            .unwrap_or(&self.type_url);
+        type_name == M::full_name() || self.type_url == M::type_url()
+    }
+
+    /// Decode the value bytes as message type `M`.
+    ///
+    /// The type URL is not checked; use [`is`](Self::is) or
+    /// [`to_message`](Self::to_message) when the detail may hold another type.
+    pub fn decode<M: prost::Message + Default>(&self) -> Result<M, prost::DecodeError> {
+        M::decode(self.value.as_slice())
+    }
+
+    /// Decode the detail as message type `M`.
+    ///
+    /// Returns `None` if the detail holds a different type (see [`is`](Self::is)).
+    pub fn to_message<M: prost::Message + prost::Name + Default>(
+        &self,
+    ) -> Option<Result<M, prost::DecodeError>> {
+        self.is::<M>().then(|| self.decode())
+    }
 }
 
 impl Serialize for ErrorDetail {
@@ -294,12 +413,74 @@ impl Status {
         self
     }
 
+    /// Add an error detail by encoding a protobuf message.
+    ///
+    /// See [`error_details`](crate::error_details) for the standard `google.rpc` types.
+    pub fn add_message_detail<M: prost::Message + prost::Name>(self, message: &M) -> Self {
+        self.add_error_detail(ErrorDetail::from_message(message))
+    }
+
     /// Add a pre-constructed ErrorDetail.
     pub fn add_error_detail(mut self, detail: ErrorDetail) -> Self {
         self.details.push(detail);
         self
     }
 
+    /// Find the first detail of message type `M` and decode it.
+    ///
+    /// Details of type `M` that fail to decode are skipped.
+    ///
+    /// ```
+    /// use connectrpc_axum_core::error_details::RetryInfo;
+    /// use connectrpc_axum_core::Status;
+    /// use std::time::Duration;
+    ///
+    /// let status = Status::unavailable("overloaded")
+    ///     .add_message_detail(&RetryInfo::new(Duration::from_secs(2)));
+    ///
+    /// let retry = status.find_detail::<RetryInfo>().unwrap();
+    /// assert_eq!(retry.retry_delay.unwrap().seconds, 2);
+    /// ```
+    pub fn find_detail<M: prost::Message + prost::Name + Default>(&self) -> Option<M> {
+        self.details
+            .iter()
+            .filter_map(ErrorDetail::to_message::<M>)
+            .find_map(Result::ok)
+    }
+
+    /// Decode all details of message type `M`, in order.
+    ///
+    /// Useful for details that may be repeated, such as several
+    /// `google.rpc.ErrorInfo`. Details of type `M` that fail to decode are
+    /// skipped.
+    ///
+    /// ```
+    /// use connectrpc_axum_core::error_details::ErrorInfo;
+    /// use connectrpc_axum_core::Status;
+    ///
+    /// let info = |reason: &str| ErrorInfo {
+    ///     reason: reason.to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let status = Status::invalid_argument("bad request")
+    ///     .add_message_detail(&info("MISSING_NAME"))
+    ///     .add_message_detail(&info("MISSING_EMAIL"));
+    ///
+    /// let reasons: Vec<_> = status
+    ///     .find_details::<ErrorInfo>()
+    ///     .into_iter()
+    ///     .map(|info| info.reason)
+    ///     .collect();
+    /// assert_eq!(reasons, ["MISSING_NAME", "MISSING_EMAIL"]);
+    /// ```
+    pub fn find_details<M: prost::Message + prost::Name + Default>(&self) -> Vec<M> {
+        self.details
+            .iter()
+            .filter_map(ErrorDetail::to_message::<M>)
+            .filter_map(Result::ok)
+            .collect()
+    }
+
     /// Returns whether this error indicates a transient condition that may
     /// be resolved by retrying.
     ///
@@ -421,6 +602,110 @@ impl Serialize for Status {
 mod tests {
     use super::*;
 
+    const ALL_CODES: [Code; 17] = [
+        Code::Ok,
+        Code::Canceled,
+        Code::Unknown,
+        Code::InvalidArgument,
+        Code::DeadlineExceeded,
+        Code::NotFound,
+        Code::AlreadyExists,
+        Code::PermissionDenied,
+        Code::ResourceExhausted,
+        Code::FailedPrecondition,
+        Code::Aborted,
+        Code::OutOfRange,
+        Code::Unimplemented,
+        Code::Internal,
+        Code::Unavailable,
+        Code::DataLoss,
+        Code::Unauthenticated,
+    ];
+
+    #[test]
+    fn test_grpc_code_round_trip() {
+        for (n, code) in ALL_CODES.iter().enumerate() {
+            assert_eq!(code.to_grpc_code(), n as u32);
+            assert_eq!(Code::from_grpc_code(n as u32), Some(*code));
+        }
+    }
+
+    #[test]
+    fn test_from_grpc_code_out_of_range() {
+        assert_eq!(Code::from_grpc_code(17), None);
+        assert_eq!(Code::from_grpc_code(u32::MAX), None);
+    }
+
+    #[test]
+    fn test_to_http_status() {
+        let expected = [
+            200, 499, 500, 400, 504, 404, 409, 403, 429, 400, 409, 400, 501, 500, 503, 500, 401,
+        ];
+        for (code, status) in ALL_CODES.iter().zip(expected) {
+            assert_eq!(code.to_http_status().as_u16(), status, "{code:?}");
+        }
+    }
+
+    #[test]
+    fn test_from_http_status_connect_400() {
+        assert_eq!(
+            Code::from_http_status_connect(StatusCode::BAD_REQUEST),
+            Code::Internal
+        );
+    }
+
+    #[test]
+    fn test_from_http_status_connect_401() {
+        assert_eq!(
+            Code::from_http_status_connect(StatusCode::UNAUTHORIZED),
+            Code::Unauthenticated
+        );
+    }
+
+    #[test]
+    fn test_from_http_status_connect_403() {
+        assert_eq!(
+            Code::from_http_status_connect(StatusCode::FORBIDDEN),
+            Code::PermissionDenied
+        );
+    }
+
+    #[test]
+    fn test_from_http_status_connect_404() {
+        assert_eq!(
+            Code::from_http_status_connect(StatusCode::NOT_FOUND),
+            Code::Unimplemented
+        );
+    }
+
+    #[test]
+    fn test_from_http_status_connect_unavailable() {
+        for status in [
+            StatusCode::TOO_MANY_REQUESTS,
+            StatusCode::BAD_GATEWAY,
+            StatusCode::SERVICE_UNAVAILABLE,
+            StatusCode::GATEWAY_TIMEOUT,
+        ] {
+            assert_eq!(
+                Code::from_http_status_connect(status),
+                Code::Unavailable,
+                "{status}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_http_status_connect_other_is_unknown() {
+        for status in [200, 302, 408, 409, 412, 416, 418, 499, 500, 501] {
+            let status = StatusCode::from_u16(status).unwrap();
+            assert_eq!(
+                Code::from_http_status_connect(status),
+                Code::Unknown,
+                "{status}"
+            );
+        }
+    }
+
     #[test]
     fn test_code_as_str() {
         assert_eq!(Code::Ok.as_str(), "ok");
@@ -566,6 +851,27 @@ mod tests {
         assert_eq!(status.details()[0].value(), &[1, 2, 3]);
     }
 
+    #[test]
+    fn test_status_find_detail() {
+        use crate::error_details::{ErrorInfo, RetryInfo};
+
+        let status = Status::unavailable("overloaded")
+            .add_detail("type.googleapis.com/google.rpc.RetryInfo", vec![0xff])
+            .add_message_detail(&RetryInfo::new(std::time::Duration::from_secs(2)));
+
+        // The malformed RetryInfo is skipped
+        let retry = status.find_detail::<RetryInfo>().unwrap();
+        assert_eq!(retry.retry_delay.unwrap().seconds, 2);
+        assert!(status.find_detail::<ErrorInfo>().is_none());
+        assert_eq!(status.find_details::<RetryInfo>().len(), 1);
+        assert!(status.find_details::<ErrorInfo>().is_empty());
+
+        assert!(status.details()[1].is::<RetryInfo>());
+        assert!(!status.details()[1].is::<ErrorInfo>());
+        let retry: RetryInfo = status.details()[1].decode().unwrap();
+        assert_eq!(retry.retry_delay.unwrap().seconds, 2);
+    }
+
     #[test]
     fn test_status_is_retryable() {
         assert!(Status::unavailable("service down").is_retryable());
@@ -0,0 +1,413 @@
+//! Standard `google.rpc` error detail messages.
+//!
+//! These are the error payloads defined in
+//! [`google/rpc/error_details.proto`](https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto),
+//! wire-compatible with connect-go, grpc-go and other gRPC implementations.
+//!
+//! Attach them to an error with [`Status::add_message_detail`](crate::Status::add_message_detail)
+//! (or the typed builders on the server's `ConnectError`), and read them back
+//! with [`Status::find_detail`](crate::Status::find_detail) or
+//! [`ErrorDetail::to_message`](crate::ErrorDetail::to_message):
+//!
+//! ```
+//! use connectrpc_axum_core::error_details::{BadRequest, FieldViolation};
+//! use connectrpc_axum_core::{Code, Status};
+//!
+//! let status = Status::new(Code::InvalidArgument, "invalid request").add_message_detail(
+//!     &BadRequest {
+//!         field_violations: vec![FieldViolation::new("name", "must not be empty")],
+//!     },
+//! );
+//!
+//! let detail = status.find_detail::<BadRequest>().unwrap();
+//! assert_eq!(detail.field_violations[0].field, "name");
+//! ```
+
+use std::collections::HashMap;
+
+use prost::Name;
+
+const PACKAGE: &str = "google.rpc";
+
+macro_rules! impl_name {
+    ($ty:ty, $package:expr, $name:literal) => {
+        impl Name for $ty {
+            const NAME: &'static str = $name;
+            const PACKAGE: &'static str = $package;
+
+            fn full_name() -> String {
+                format!("{}.{}", $package, $name)
+            }
+
+            fn type_url() -> String {
+                format!("type.googleapis.com/{}.{}", $package, $name)
+            }
+        }
+    };
+}
+
+/// A `google.protobuf.Duration`, used by [`RetryInfo`].
+#[derive(Clone, Copy, PartialEq, Eq, ::prost::Message)]
+pub struct Duration {
+    /// Signed seconds of the span of time.
+    #[prost(int64, tag = "1")]
+    pub seconds: i64,
+    /// Signed fractions of a second at nanosecond resolution.
+    #[prost(int32, tag = "2")]
+    pub nanos: i32,
+}
+
+impl_name!(Duration, "google.protobuf", "Duration");
+
+impl From<std::time::Duration> for Duration {
+    fn from(duration: std::time::Duration) -> Self {
+        Self {
+            seconds: i64::try_from(duration.as_secs()).unwrap_or(i64::MAX),
+            nanos: duration.subsec_nanos() as i32,
+        }
+    }
+}
+
+impl TryFrom<Duration> for std::time::Duration {
+    type Error = Duration;
+
+    /// Fails for negative durations.
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        match (
+            u64::try_from(duration.seconds),
+            u32::try_from(duration.nanos),
+        ) {
+            (Ok(seconds), Ok(nanos)) => Ok(std::time::Duration::new(seconds, nanos)),
+            _ => Err(duration),
+        }
+    }
+}
+
+/// Describes when the client may retry a failed request.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RetryInfo {
+    /// Clients should wait at least this long before retrying.
+    #[prost(message, optional, tag = "1")]
+    pub retry_delay: Option<Duration>,
+}
+
+impl_name!(RetryInfo, PACKAGE, "RetryInfo");
+
+impl RetryInfo {
+    /// Create a `RetryInfo` with the given retry delay.
+    pub fn new(retry_delay: std::time::Duration) -> Self {
+        Self {
+            retry_delay: Some(retry_delay.into()),
+        }
+    }
+}
+
+/// Additional debugging information provided by the server.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DebugInfo {
+    /// The stack trace entries indicating where the error occurred.
+    #[prost(string, repeated, tag = "1")]
+    pub stack_entries: Vec<String>,
+    /// Additional debugging information provided by the server.
+    #[prost(string, tag = "2")]
+    pub detail: String,
+}
+
+impl_name!(DebugInfo, PACKAGE, "DebugInfo");
+
+/// Describes how a quota check failed.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuotaFailure {
+    /// Describes all quota violations.
+    #[prost(message, repeated, tag = "1")]
+    pub violations: Vec<QuotaViolation>,
+}
+
+impl_name!(QuotaFailure, PACKAGE, "QuotaFailure");
+
+/// A single quota violation, part of [`QuotaFailure`].
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuotaViolation {
+    /// The subject on which the quota check failed, e.g. `"clientip:<ip>"`.
+    #[prost(string, tag = "1")]
+    pub subject: String,
+    /// A description of how the quota check failed.
+    #[prost(string, tag = "2")]
+    pub description: String,
+}
+
+impl_name!(QuotaViolation, "google.rpc.QuotaFailure", "Violation");
+
+impl QuotaViolation {
+    /// Create a quota violation for a subject.
+    pub fn new(subject: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            description: description.into(),
+        }
+    }
+}
+
+/// Describes the cause of the error with structured details.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ErrorInfo {
+    /// The reason of the error, as a constant in UPPER_SNAKE_CASE.
+    #[prost(string, tag = "1")]
+    pub reason: String,
+    /// The logical grouping to which the reason belongs, e.g. a service name.
+    #[prost(string, tag = "2")]
+    pub domain: String,
+    /// Additional structured details about this error.
+    #[prost(map = "string, string", tag = "3")]
+    pub metadata: HashMap<String, String>,
+}
+
+impl_name!(ErrorInfo, PACKAGE, "ErrorInfo");
+
+impl ErrorInfo {
+    /// Create an `ErrorInfo` with a reason and domain.
+    pub fn new(reason: impl Into<String>, domain: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+            domain: domain.into(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Add a metadata entry.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Describes what preconditions have failed.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreconditionFailure {
+    /// Describes all precondition violations.
+    #[prost(message, repeated, tag = "1")]
+    pub violations: Vec<PreconditionViolation>,
+}
+
+impl_name!(PreconditionFailure, PACKAGE, "PreconditionFailure");
+
+/// A single precondition violation, part of [`PreconditionFailure`].
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreconditionViolation {
+    /// The type of precondition failure, e.g. `"TOS"`.
+    #[prost(string, tag = "1")]
+    pub r#type: String,
+    /// The subject, relative to the type, that failed.
+    #[prost(string, tag = "2")]
+    pub subject: String,
+    /// A description of how the precondition failed.
+    #[prost(string, tag = "3")]
+    pub description: String,
+}
+
+impl_name!(
+    PreconditionViolation,
+    "google.rpc.PreconditionFailure",
+    "Violation"
+);
+
+impl PreconditionViolation {
+    /// Create a precondition violation.
+    pub fn new(
+        r#type: impl Into<String>,
+        subject: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            r#type: r#type.into(),
+            subject: subject.into(),
+            description: description.into(),
+        }
+    }
+}
+
+/// Describes violations in a client request, focusing on syntactic aspects.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BadRequest {
+    /// Describes all violations in a client request.
+    #[prost(message, repeated, tag = "1")]
+    pub field_violations: Vec<FieldViolation>,
+}
+
+impl_name!(BadRequest, PACKAGE, "BadRequest");
+
+/// A single bad request field, part of [`BadRequest`].
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FieldViolation {
+    /// A path to the offending field, e.g. `"address.street"`.
+    #[prost(string, tag = "1")]
+    pub field: String,
+    /// A description of why the field is bad.
+    #[prost(string, tag = "2")]
+    pub description: String,
+}
+
+impl_name!(FieldViolation, "google.rpc.BadRequest", "FieldViolation");
+
+impl FieldViolation {
+    /// Create a field violation.
+    pub fn new(field: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            description: description.into(),
+        }
+    }
+}
+
+/// Metadata about the request that clients can attach when filing a bug.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RequestInfo {
+    /// An opaque string identifying the request in the service's logs.
+    #[prost(string, tag = "1")]
+    pub request_id: String,
+    /// Any data that was used to serve this request.
+    #[prost(string, tag = "2")]
+    pub serving_data: String,
+}
+
+impl_name!(RequestInfo, PACKAGE, "RequestInfo");
+
+/// Describes the resource that is being accessed.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResourceInfo {
+    /// The type of resource being accessed.
+    #[prost(string, tag = "1")]
+    pub resource_type: String,
+    /// The name of the resource being accessed.
+    #[prost(string, tag = "2")]
+    pub resource_name: String,
+    /// The owner of the resource (optional).
+    #[prost(string, tag = "3")]
+    pub owner: String,
+    /// Describes what error is encountered when accessing this resource.
+    #[prost(string, tag = "4")]
+    pub description: String,
+}
+
+impl_name!(ResourceInfo, PACKAGE, "ResourceInfo");
+
+/// Links to documentation or for performing an out-of-band action.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Help {
+    /// URL(s) pointing to additional information on handling the current error.
+    #[prost(message, repeated, tag = "1")]
+    pub links: Vec<HelpLink>,
+}
+
+impl_name!(Help, PACKAGE, "Help");
+
+/// A single link, part of [`Help`].
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HelpLink {
+    /// Describes what the link offers.
+    #[prost(string, tag = "1")]
+    pub description: String,
+    /// The URL of the link.
+    #[prost(string, tag = "2")]
+    pub url: String,
+}
+
+impl_name!(HelpLink, "google.rpc.Help", "Link");
+
+impl HelpLink {
+    /// Create a help link.
+    pub fn new(description: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            url: url.into(),
+        }
+    }
+}
+
+/// A localized error message that is safe to return to the user.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LocalizedMessage {
+    /// The locale used, following BCP 47, e.g. `"en-US"`.
+    #[prost(string, tag = "1")]
+    pub locale: String,
+    /// The localized error message in the above locale.
+    #[prost(string, tag = "2")]
+    pub message: String,
+}
+
+impl_name!(LocalizedMessage, PACKAGE, "LocalizedMessage");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Code, ErrorDetail, Status};
+    use prost::Message;
+
+    #[test]
+    fn test_type_urls() {
+        assert_eq!(
+            RetryInfo::type_url(),
+            "type.googleapis.com/google.rpc.RetryInfo"
+        );
+        assert_eq!(BadRequest::full_name(), "google.rpc.BadRequest");
+        assert_eq!(
+            FieldViolation::full_name(),
+            "google.rpc.BadRequest.FieldViolation"
+        );
+        assert_eq!(Duration::full_name(), "google.protobuf.Duration");
+    }
+
+    #[test]
+    fn test_retry_info_wire_format() {
+        let info = RetryInfo::new(std::time::Duration::from_millis(1500));
+        // field 1 (message, len 8): seconds = 1, nanos = 500_000_000
+        assert_eq!(
+            info.encode_to_vec(),
+            [0x0a, 0x08, 0x08, 0x01, 0x10, 0x80, 0xca, 0xb5, 0xee, 0x01]
+        );
+    }
+
+    #[test]
+    fn test_round_trip_through_status() {
+        let status = Status::new(Code::ResourceExhausted, "slow down")
+            .add_message_detail(&QuotaFailure {
+                violations: vec![QuotaViolation::new("clientip:1.2.3.4", "too many requests")],
+            })
+            .add_message_detail(&RetryInfo::new(std::time::Duration::from_secs(3)));
+
+        assert_eq!(status.details().len(), 2);
+        assert_eq!(
+            status.details()[0].type_url(),
+            "type.googleapis.com/google.rpc.QuotaFailure"
+        );
+
+        let quota: QuotaFailure = status.details()[0].to_message().unwrap().unwrap();
+        assert_eq!(quota.violations[0].subject, "clientip:1.2.3.4");
+
+        let retry: RetryInfo = status.details()[1].to_message().unwrap().unwrap();
+        let delay = std::time::Duration::try_from(retry.retry_delay.unwrap()).unwrap();
+        assert_eq!(delay, std::time::Duration::from_secs(3));
+
+        // Type mismatch yields None
+        assert!(status.details()[0].to_message::<RetryInfo>().is_none());
+    }
+
+    #[test]
+    fn test_to_message_accepts_short_type_name() {
+        // Details received over the Connect wire use the short type name
+        let info = ErrorInfo::new("API_DISABLED", "example.com").with_metadata("service", "foo");
+        let detail = ErrorDetail::new("google.rpc.ErrorInfo", info.encode_to_vec());
+
+        let decoded: ErrorInfo = detail.to_message().unwrap().unwrap();
+        assert_eq!(decoded, info);
+    }
+
+    #[test]
+    fn test_negative_duration_conversion_fails() {
+        let duration = Duration {
+            seconds: -1,
+            nanos: 0,
+        };
+        assert!(std::time::Duration::try_from(duration).is_err());
+    }
+}
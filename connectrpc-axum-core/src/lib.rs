@@ -6,6 +6,7 @@
 //! ## Modules
 //!
 //! - [`error`]: Protocol error codes and error types
+//! - [`error_details`]: Standard `google.rpc` error detail messages
 //! - [`codec`]: Compression codec trait and implementations
 //! - [`compression`]: Compression configuration types
 //! - [`envelope`]: Streaming envelope framing functions
@@ -14,6 +15,7 @@ mod codec;
 mod compression;
 mod envelope;
 mod error;
+pub mod error_details;
 
 pub use codec::*;
 pub use compression::*;
@@ -0,0 +1,79 @@
+//! TLS Client Example
+//!
+//! Demonstrates configuring TLS on the client with `TlsClientConfig`:
+//! trusting a private CA, presenting a client certificate (mTLS), and
+//! accepting a self-signed development certificate.
+//!
+//! ```ignore
+//! let client = HelloWorldServiceClient::builder("https://localhost:8443")
+//!     .with_tls_config(TlsClientConfig::new().add_root_certificate(&ca_pem))
+//!     .build()?;
+//! ```
+//!
+//! Usage:
+//!   # Trust a private CA (or a self-signed server certificate):
+//!   cargo run --bin tls-client --no-default-features -- ca https://localhost:8443 ca.pem
+//!
+//!   # Mutual TLS with a client certificate and key:
+//!   cargo run --bin tls-client --no-default-features -- \
+//!       mtls https://localhost:8443 ca.pem client.pem client-key.pem
+//!
+//!   # Development only: skip certificate verification entirely:
+//!   cargo run --bin tls-client --no-default-features -- insecure https://localhost:8443
+
+use connectrpc_axum_client::{TlsClientConfig, TlsVersion};
+use connectrpc_axum_examples::{
+    HelloRequest, hello_world_service_connect_client::HelloWorldServiceClient,
+};
+use std::env;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mode = args.first().map(String::as_str).unwrap_or("insecure");
+    let base_url = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| "https://localhost:8443".to_string());
+
+    let tls = match mode {
+        // Custom CA: trust certificates issued by a private CA in addition to
+        // the system roots. A self-signed server certificate can be passed here
+        // directly, too.
+        "ca" => {
+            let ca = std::fs::read(args.get(2).map(String::as_str).unwrap_or("ca.pem"))?;
+            TlsClientConfig::new().add_root_certificate(&ca)
+        }
+        // mTLS: trust the server's CA and present our own certificate.
+        "mtls" => {
+            let ca = std::fs::read(args.get(2).map(String::as_str).unwrap_or("ca.pem"))?;
+            let cert = std::fs::read(args.get(3).map(String::as_str).unwrap_or("client.pem"))?;
+            let key = std::fs::read(args.get(4).map(String::as_str).unwrap_or("client-key.pem"))?;
+            TlsClientConfig::new()
+                .add_root_certificate(&ca)
+                .identity(&cert, &key)
+                .min_tls_version(TlsVersion::Tls13)
+        }
+        // Self-signed development certs: WARNING, never use in production.
+        "insecure" => TlsClientConfig::new().danger_accept_invalid_certs(true),
+        other => anyhow::bail!("unknown mode {other:?}; expected ca, mtls or insecure"),
+    };
+
+    println!("=== TLS Client ({mode}) ===");
+    println!("Server URL: {}", base_url);
+
+    let client = HelloWorldServiceClient::builder(&base_url)
+        .with_tls_config(tls)
+        .build()?;
+
+    let request = HelloRequest {
+        name: Some("TLS".to_string()),
+        hobbies: vec![],
+        greeting_type: None,
+    };
+
+    let response = client.say_hello(&request).await?;
+    println!("  PASS: Response message = {:?}", response.message);
+
+    Ok(())
+}
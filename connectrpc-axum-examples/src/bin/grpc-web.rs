@@ -30,7 +30,7 @@ async fn say_hello(
         .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
     let name = req.name.unwrap_or_else(|| "World".to_string());
 
-    Ok(ConnectResponse(HelloResponse {
+    Ok(ConnectResponse::new(HelloResponse {
         message: format!("Hello #{}, {}! (via gRPC-Web)", count, name),
         response_type: None,
     }))
@@ -80,7 +80,7 @@ async fn say_hello_stream(
         });
     };
 
-    Ok(ConnectResponse(StreamBody::new(stream)))
+    Ok(ConnectResponse::new(StreamBody::new(stream)))
 }
 
 #[tokio::main]
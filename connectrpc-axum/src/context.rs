@@ -6,15 +6,25 @@
 //!
 //! [`ConnectLayer`]: crate::layer::ConnectLayer
 
+pub mod cancellation;
 pub mod config;
 pub mod envelope_compression;
 pub mod error;
 pub mod limit;
+pub mod peer;
 pub mod protocol;
 pub mod timeout;
+pub mod trace;
+pub mod values;
 
-use axum::http::{Method, Request};
+use crate::error_mapper::ErrorMapper;
+use crate::interceptor::RequestInterceptor;
+use crate::message::StreamWriteConfig;
+use crate::shutdown::Shutdown;
+use axum::http::{HeaderValue, Method, Request, header};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 // Re-export compression types and functions from envelope_compression
 pub use envelope_compression::{
@@ -29,16 +39,26 @@ pub use envelope_compression::{
     CompressionConfig,
     CompressionEncoding,
     CompressionLevel,
+    CustomCodec,
     EnvelopeCompression,
+    GRPC_ACCEPT_ENCODING,
+    GRPC_ENCODING,
     // Identity codec (always available)
     IdentityCodec,
     // Functions
     compress_bytes,
     decompress_bytes,
+    looks_incompressible,
     negotiate_response_encoding,
+    negotiate_response_encoding_with,
     parse_envelope_compression,
+    parse_grpc_compression,
     resolve_codec,
 };
+pub(crate) use envelope_compression::{
+    advertised_body_encodings, advertised_message_encodings, parse_message_compression,
+    parse_unary_compression,
+};
 
 // Feature-gated codec exports
 #[cfg(feature = "compression-br-stream")]
@@ -59,8 +79,12 @@ pub use envelope_compression::ZstdCodec;
 ))]
 pub use envelope_compression::to_tower_compression_level;
 
+// Re-export deadline and cancellation extractors
+pub use cancellation::{Cancellation, Deadline};
+
 // Re-export config types (crate-internal)
 pub(crate) use config::ServerConfig;
+pub use config::{CompressionPolicy, MethodConfig};
 
 // Re-export error types
 pub use error::{ContextError, ProtocolNegotiationError};
@@ -68,6 +92,12 @@ pub use error::{ContextError, ProtocolNegotiationError};
 // Re-export limit types
 pub use limit::MessageLimits;
 
+// Re-export connection info types
+pub use peer::{PeerInfo, UserAgent};
+
+// Re-export request-scoped values
+pub use values::{ContextValue, ContextValues};
+
 // Re-export protocol types and functions
 pub use protocol::{
     CONNECT_PROTOCOL_VERSION, CONNECT_PROTOCOL_VERSION_HEADER, IdempotencyLevel, RequestProtocol,
@@ -76,10 +106,14 @@ pub use protocol::{
     validate_streaming_content_type, validate_unary_content_type,
 };
 
+// Re-export trace context types
+pub use trace::{TRACEPARENT_HEADER, TraceParent};
+
 // Re-export timeout types and functions
+pub(crate) use timeout::TimeoutOverride;
 pub use timeout::{
-    CONNECT_TIMEOUT_MS_HEADER, ConnectTimeout, compute_effective_timeout, parse_timeout,
-    parse_timeout_ms,
+    CONNECT_TIMEOUT_MS_HEADER, ConnectTimeout, GRPC_TIMEOUT_HEADER, IdleTimeout,
+    compute_effective_timeout, parse_grpc_timeout, parse_timeout, parse_timeout_ms,
 };
 
 // ============================================================================
@@ -96,12 +130,44 @@ pub struct ConnectContext {
     pub protocol: RequestProtocol,
     /// Compression settings
     pub compression: CompressionContext,
-    /// Effective timeout (min of server and client)
+    /// Effective timeout (see [`compute_effective_timeout`])
     pub timeout: Option<Duration>,
+    /// Server-side timeout the effective timeout was computed from
+    pub server_timeout: Option<Duration>,
+    /// Timeout applied when the client sends none
+    pub default_timeout: Option<Duration>,
+    /// Cap on the client's timeout
+    pub max_timeout: Option<Duration>,
+    /// Instant the effective timeout expires
+    pub deadline: Option<tokio::time::Instant>,
+    /// Cancelled when the request ends (see [`Cancellation`])
+    pub cancellation: CancellationToken,
+    /// Per-message deadline for streaming request messages
+    pub stream_message_timeout: Option<Duration>,
+    /// Inactivity deadline shared by the request and response streams
+    pub stream_idle: Option<IdleTimeout>,
     /// Message size limits
     pub limits: MessageLimits,
     /// Whether protocol version header is required
     pub require_protocol_header: bool,
+    /// Interceptor chain bound to this request (set by ConnectLayer)
+    pub interceptor: Option<RequestInterceptor>,
+    /// Whether panics in response streams are converted into `internal` errors
+    pub catch_panic: bool,
+    /// Whether non-Connect failures carry a `google.rpc.DebugInfo` detail
+    pub debug_errors: bool,
+    /// Converts application errors returned by handlers
+    pub error_mapper: Option<Arc<ErrorMapper>>,
+    /// How streaming response frames are flushed
+    pub stream_write: StreamWriteConfig,
+    /// `If-None-Match` header of a GET request, for conditional responses
+    pub if_none_match: Option<HeaderValue>,
+    /// Remote address, HTTP version and user agent of the caller
+    pub peer: PeerInfo,
+    /// Request-scoped values from middleware and interceptors (see [`ContextValues`])
+    pub values: ContextValues,
+    /// Shutdown coordinator that ends open response streams when draining
+    pub shutdown: Option<Shutdown>,
 }
 
 /// Compression context for a single request.
@@ -112,8 +178,35 @@ pub struct ConnectContext {
 pub struct CompressionContext {
     /// Per-envelope compression for streaming RPCs (None for unary).
     pub envelope: Option<EnvelopeCompression>,
+    /// HTTP body compression of unary RPCs using a custom codec.
+    ///
+    /// `None` unless the request body or the negotiated response encoding is
+    /// a codec registered with [`ConnectLayer::codec`](crate::ConnectLayer::codec);
+    /// built-in encodings are handled by Tower.
+    pub unary: Option<EnvelopeCompression>,
     /// Full compression configuration (includes level and min_bytes).
     pub config: CompressionConfig,
+    /// Per-method policy applied on top of the negotiated encodings.
+    pub policy: CompressionPolicy,
+}
+
+impl CompressionContext {
+    /// Override the negotiated response encoding with `policy`.
+    pub(crate) fn apply_policy(&mut self, policy: CompressionPolicy) {
+        self.policy = policy;
+        let response = match policy {
+            CompressionPolicy::Negotiate => return,
+            CompressionPolicy::Always(encoding) => encoding,
+            CompressionPolicy::Never => CompressionEncoding::Identity,
+        };
+        match &mut self.envelope {
+            Some(envelope) => envelope.response = response,
+            None => {
+                let request = self.unary.map(|unary| unary.request).unwrap_or_default();
+                self.unary = Some(EnvelopeCompression { request, response });
+            }
+        }
+    }
 }
 
 impl ConnectContext {
@@ -132,26 +225,64 @@ impl ConnectContext {
         // Parse envelope compression for POST requests (streaming only, unary returns None)
         let compression = if *req.method() == Method::POST {
             let envelope =
-                parse_envelope_compression(req, protocol.is_streaming()).map_err(|err| {
+                parse_message_compression(req, protocol, &config.codecs).map_err(|err| {
                     ContextError::new(protocol, err, config.limits.get_send_max_bytes())
                 })?;
+            let unary = if envelope.is_none() {
+                parse_unary_compression(req, &config.codecs)
+            } else {
+                None
+            };
             CompressionContext {
                 envelope,
+                unary,
                 config: config.compression,
+                policy: CompressionPolicy::default(),
             }
         } else {
             CompressionContext::default()
         };
 
         let client_timeout = parse_timeout(req);
-        let timeout = compute_effective_timeout(config.server_timeout, client_timeout);
+        let timeout = compute_effective_timeout(
+            config.server_timeout,
+            config.default_timeout,
+            config.max_timeout,
+            client_timeout,
+        );
+
+        // Conditional requests only apply to cacheable (GET) RPCs
+        let if_none_match = if *req.method() == Method::GET {
+            req.headers().get(header::IF_NONE_MATCH).cloned()
+        } else {
+            None
+        };
 
         Ok(Self {
             protocol,
             compression,
             timeout,
+            server_timeout: config.server_timeout,
+            default_timeout: config.default_timeout,
+            max_timeout: config.max_timeout,
+            deadline: timeout.map(|timeout| tokio::time::Instant::now() + timeout),
+            cancellation: CancellationToken::new(),
+            stream_message_timeout: config.stream_message_timeout,
+            stream_idle: config
+                .stream_idle_timeout
+                .filter(|_| protocol.is_streaming())
+                .map(IdleTimeout::new),
             limits: config.limits,
             require_protocol_header: config.require_protocol_header,
+            interceptor: None,
+            catch_panic: config.catch_panic,
+            debug_errors: config.debug_errors,
+            error_mapper: config.error_mapper.clone(),
+            stream_write: config.stream_write,
+            if_none_match,
+            peer: PeerInfo::new(req.extensions(), req.headers(), req.version()),
+            values: ContextValues::default(),
+            shutdown: config.shutdown.clone(),
         })
     }
 
@@ -162,6 +293,7 @@ impl ConnectContext {
     /// - Connect-Protocol-Version header is present (if required by config)
     ///
     /// Checks for GET requests:
+    /// - The URL is within `MessageLimits::max_url_bytes` (if set)
     /// - `encoding` parameter is present and valid (json or proto)
     /// - `message` parameter is present
     /// - `connect` parameter is "v1" if present, or required when config requires it
@@ -171,6 +303,14 @@ impl ConnectContext {
     pub fn validate<B>(&self, req: &Request<B>) -> Result<(), ContextError> {
         // GET request validation
         if *req.method() == Method::GET {
+            let url_size = req.uri().path_and_query().map_or(0, |pq| pq.as_str().len());
+            if let Err(err) = self.limits.check_url_size(url_size) {
+                return Err(ContextError::new(
+                    self.protocol,
+                    err,
+                    self.limits.get_send_max_bytes(),
+                ));
+            }
             if let Some(err) = validate_get_query_params(req, self.require_protocol_header) {
                 return Err(ContextError::new(
                     self.protocol,
@@ -195,6 +335,11 @@ impl ConnectContext {
             ));
         }
 
+        // gRPC has no Connect-Protocol-Version header
+        if self.protocol.is_grpc() {
+            return Ok(());
+        }
+
         // Check protocol version header
         if let Some(err) = validate_protocol_version(req, self.require_protocol_header) {
             return Err(ContextError::new(
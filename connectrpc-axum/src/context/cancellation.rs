@@ -0,0 +1,258 @@
+//! Deadline and cancellation extractors for handlers.
+//!
+//! [`ConnectLayer`](crate::ConnectLayer) records the effective deadline
+//! (server timeout and `Connect-Timeout-Ms`, whichever is sooner) and a
+//! cancellation token in the [`ConnectContext`]. Handlers extract them to stop
+//! long-running work early:
+//!
+//! ```rust,ignore
+//! use connectrpc_axum::{Cancellation, Deadline};
+//!
+//! async fn report(
+//!     deadline: Deadline,
+//!     cancel: Cancellation,
+//!     ConnectRequest(req): ConnectRequest<ReportRequest>,
+//! ) -> Result<ConnectResponse<ReportResponse>, ConnectError> {
+//!     for chunk in req.chunks {
+//!         deadline.check()?;
+//!         cancel.check()?;
+//!         process(chunk).await;
+//!     }
+//!     // ...
+//! }
+//! ```
+//!
+//! The token is cancelled when the request ends: the response body finished
+//! or was dropped, the client disconnected, or the deadline fired. It can be
+//! handed to spawned tasks, which the handler's own future does not cover.
+
+use super::ConnectContext;
+use crate::message::error::{Code, ConnectError};
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// The effective deadline of the current RPC.
+///
+/// Extracting it never fails; without a timeout (or without `ConnectLayer`)
+/// the deadline is unset and never expires.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Deadline {
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    /// A deadline at the given instant.
+    pub fn at(at: Instant) -> Self {
+        Self { at: Some(at) }
+    }
+
+    /// A deadline that never expires.
+    pub fn none() -> Self {
+        Self { at: None }
+    }
+
+    /// The instant the deadline expires, if any.
+    pub fn instant(&self) -> Option<Instant> {
+        self.at
+    }
+
+    /// Time left before the deadline; `Duration::ZERO` once expired.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.at
+            .map(|at| at.saturating_duration_since(Instant::now()))
+    }
+
+    /// Whether the deadline has passed.
+    pub fn is_expired(&self) -> bool {
+        self.at.is_some_and(|at| Instant::now() >= at)
+    }
+
+    /// Return a `deadline_exceeded` error if the deadline has passed.
+    pub fn check(&self) -> Result<(), ConnectError> {
+        if self.is_expired() {
+            Err(ConnectError::new(
+                Code::DeadlineExceeded,
+                "request timeout exceeded",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Wait until the deadline passes (forever if there is none).
+    pub async fn expired(&self) {
+        match self.at {
+            Some(at) => tokio::time::sleep_until(at).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Deadline {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<ConnectContext>()
+            .and_then(|ctx| ctx.deadline)
+            .map(Deadline::at)
+            .unwrap_or_default())
+    }
+}
+
+/// Cooperative cancellation for the current RPC.
+///
+/// Wraps the request's [`CancellationToken`]. Extracting it never fails;
+/// without `ConnectLayer` the token is never cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation(CancellationToken);
+
+impl Cancellation {
+    /// Wrap an existing token.
+    pub fn new(token: CancellationToken) -> Self {
+        Self(token)
+    }
+
+    /// The underlying token, e.g. to pass to spawned tasks.
+    pub fn token(&self) -> &CancellationToken {
+        &self.0
+    }
+
+    /// Consume the extractor and return the token.
+    pub fn into_token(self) -> CancellationToken {
+        self.0
+    }
+
+    /// Whether the request has ended.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+
+    /// Return a `canceled` error if the request has ended.
+    pub fn check(&self) -> Result<(), ConnectError> {
+        if self.is_cancelled() {
+            Err(ConnectError::new(Code::Canceled, "request canceled"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Wait until the request ends.
+    pub async fn cancelled(&self) {
+        self.0.cancelled().await
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Cancellation {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<ConnectContext>()
+            .map(|ctx| Cancellation(ctx.cancellation.clone()))
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::{ConnectRequest, ConnectResponse};
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use tokio::sync::oneshot;
+    use tower::ServiceExt;
+
+    fn request(timeout_ms: Option<u64>) -> Request<Body> {
+        let mut builder =
+            Request::post("/test.Svc/Method").header(header::CONTENT_TYPE, "application/json");
+        if let Some(ms) = timeout_ms {
+            builder = builder.header("connect-timeout-ms", ms.to_string());
+        }
+        builder.body(Body::from("\"hi\"")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_deadline_follows_effective_timeout() {
+        async fn remaining(
+            deadline: Deadline,
+            ConnectRequest(_): ConnectRequest<String>,
+        ) -> Result<ConnectResponse<String>, ConnectError> {
+            deadline.check()?;
+            let remaining = deadline.remaining().map(|d| d.as_millis().to_string());
+            Ok(ConnectResponse::new(remaining.unwrap_or_default()))
+        }
+
+        let app = Router::new()
+            .route("/test.Svc/Method", post_connect(remaining))
+            .layer(ConnectLayer::new());
+
+        let response = app.clone().oneshot(request(Some(5000))).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let millis: String = serde_json::from_slice(&body).unwrap();
+        let millis: u64 = millis.parse().unwrap();
+        assert!(millis > 0 && millis <= 5000, "{millis}");
+
+        let response = app.oneshot(request(None)).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"\"\"");
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_on_timeout() {
+        let (tx, rx) = oneshot::channel();
+        let tx = std::sync::Arc::new(std::sync::Mutex::new(Some(tx)));
+
+        let app = Router::new()
+            .route(
+                "/test.Svc/Method",
+                post_connect(
+                    move |cancel: Cancellation, ConnectRequest(_): ConnectRequest<String>| {
+                        let tx = tx.lock().unwrap().take().unwrap();
+                        async move {
+                            let token = cancel.into_token();
+                            tokio::spawn(async move {
+                                token.cancelled().await;
+                                let _ = tx.send(());
+                            });
+                            std::future::pending::<Result<ConnectResponse<String>, ConnectError>>()
+                                .await
+                        }
+                    },
+                ),
+            )
+            .layer(ConnectLayer::new());
+
+        let response = app.oneshot(request(Some(20))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        tokio::time::timeout(Duration::from_secs(1), rx)
+            .await
+            .expect("token cancelled")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extractors_without_layer() {
+        let (mut parts, _) = request(Some(10)).into_parts();
+        let deadline = Deadline::from_request_parts(&mut parts, &()).await.unwrap();
+        let cancel = Cancellation::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+        assert_eq!(deadline, Deadline::none());
+        assert!(deadline.check().is_ok());
+        assert!(cancel.check().is_ok());
+    }
+}
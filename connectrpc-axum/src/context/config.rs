@@ -2,21 +2,165 @@
 //!
 //! Set once at startup, used to build Context for each request.
 
-use crate::context::{CompressionConfig, MessageLimits};
+use crate::context::{CompressionConfig, CompressionEncoding, MessageLimits};
+use crate::error_mapper::ErrorMapper;
+use crate::message::StreamWriteConfig;
+use crate::shutdown::Shutdown;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Server-wide configuration for the Connect RPC layer.
 ///
 /// Set once at startup, immutable per-request.
 /// Used by ConnectLayer to build Context.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct ServerConfig {
     /// Compression settings
     pub compression: CompressionConfig,
+    /// Custom codecs negotiated alongside the built-in ones
+    pub codecs: Arc<[CompressionEncoding]>,
     /// Message size limits
     pub limits: MessageLimits,
     /// Server-side timeout (optional)
     pub server_timeout: Option<Duration>,
+    /// Timeout for requests without a client timeout (optional)
+    pub default_timeout: Option<Duration>,
+    /// Cap on client-provided timeouts (optional)
+    pub max_timeout: Option<Duration>,
+    /// Per-message deadline for client-streaming input (optional)
+    pub stream_message_timeout: Option<Duration>,
+    /// Inactivity timeout for streaming RPCs (optional)
+    pub stream_idle_timeout: Option<Duration>,
     /// Whether to require Connect-Protocol-Version header
     pub require_protocol_header: bool,
+    /// Whether to convert handler panics into `internal` errors
+    pub catch_panic: bool,
+    /// Whether to attach `google.rpc.DebugInfo` to non-Connect failures
+    pub debug_errors: bool,
+    /// Conversions of application errors returned by handlers (optional)
+    pub error_mapper: Option<Arc<ErrorMapper>>,
+    /// How streaming response frames are flushed
+    pub stream_write: StreamWriteConfig,
+    /// Graceful shutdown coordinator (optional)
+    pub shutdown: Option<Shutdown>,
+}
+
+/// Per-method overrides of the server-wide configuration.
+///
+/// Unset fields fall back to the values configured on
+/// [`ConnectLayer`](crate::ConnectLayer) / [`MakeServiceBuilder`](crate::MakeServiceBuilder).
+/// Generated service builders accept it through `*_with_config` methods:
+///
+/// ```rust,ignore
+/// use connectrpc_axum::MethodConfig;
+///
+/// let router = HelloWorldServiceBuilder::new()
+///     .say_hello_with_config(say_hello, MethodConfig::new().max_message_size(1 << 20))
+///     .upload_with_config(upload, MethodConfig::new().timeout(Duration::from_secs(600)))
+///     .build();
+/// ```
+///
+/// `MethodConfig` is also a [`tower::Layer`], so it can be applied to any
+/// route served behind a `ConnectLayer`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MethodConfig {
+    pub(crate) receive_max_bytes: Option<usize>,
+    pub(crate) send_max_bytes: Option<usize>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) default_timeout: Option<Duration>,
+    pub(crate) max_timeout: Option<Duration>,
+    pub(crate) compression: Option<CompressionConfig>,
+    pub(crate) compression_policy: CompressionPolicy,
+}
+
+impl MethodConfig {
+    /// Create a config that overrides nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the maximum size of received messages.
+    pub fn receive_max_bytes(mut self, max: usize) -> Self {
+        self.receive_max_bytes = Some(max);
+        self
+    }
+
+    /// Override the maximum size of sent messages.
+    pub fn send_max_bytes(mut self, max: usize) -> Self {
+        self.send_max_bytes = Some(max);
+        self
+    }
+
+    /// Override both the receive and send message size limits.
+    pub fn max_message_size(self, max: usize) -> Self {
+        self.receive_max_bytes(max).send_max_bytes(max)
+    }
+
+    /// Override the server-side timeout.
+    ///
+    /// Replaces the server-wide timeout for this method, so it can be longer
+    /// or shorter. A shorter `Connect-Timeout-Ms` from the client still wins.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Override the timeout applied when the client sends none.
+    pub fn default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = Some(timeout);
+        self
+    }
+
+    /// Override the cap on client-provided timeouts.
+    pub fn max_timeout(mut self, timeout: Duration) -> Self {
+        self.max_timeout = Some(timeout);
+        self
+    }
+
+    /// Whether any timeout setting is overridden.
+    pub(crate) fn overrides_timeout(&self) -> bool {
+        self.timeout.is_some() || self.default_timeout.is_some() || self.max_timeout.is_some()
+    }
+
+    /// Override the compression configuration.
+    pub fn compression(mut self, config: CompressionConfig) -> Self {
+        self.compression = Some(config);
+        self
+    }
+
+    /// Override how response compression is chosen.
+    pub fn compression_policy(mut self, policy: CompressionPolicy) -> Self {
+        self.compression_policy = policy;
+        self
+    }
+}
+
+/// How the response encoding of a method is chosen.
+///
+/// Set per method with [`MethodConfig::compression_policy`]:
+///
+/// ```rust,ignore
+/// use connectrpc_axum::{CompressionEncoding, CompressionPolicy, MethodConfig};
+///
+/// let router = BlobServiceBuilder::new()
+///     // Blobs are stored pre-compressed
+///     .get_blob_with_config(get_blob, MethodConfig::new().compression_policy(CompressionPolicy::Never))
+///     .list_blobs_with_config(
+///         list_blobs,
+///         MethodConfig::new().compression_policy(CompressionPolicy::Always(CompressionEncoding::Gzip)),
+///     )
+///     .build();
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionPolicy {
+    /// Use the encoding negotiated from the client's accept headers.
+    #[default]
+    Negotiate,
+    /// Compress with the given encoding, whatever the client advertised.
+    ///
+    /// The `min_bytes` threshold still applies. Only use encodings the
+    /// clients of this method are known to support.
+    Always(CompressionEncoding),
+    /// Never compress responses.
+    Never,
 }
@@ -13,7 +13,9 @@
 //! - **Streaming RPCs**: Use `Connect-Content-Encoding`/`Connect-Accept-Encoding` headers.
 //!   Each message envelope is individually compressed. This module handles that.
 
+use crate::context::RequestProtocol;
 use crate::message::error::{Code, ConnectError};
+use axum::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
 
 // Re-export core types
 pub use connectrpc_axum_core::{
@@ -27,13 +29,16 @@ pub use connectrpc_axum_core::{
     CompressionConfig,
     CompressionEncoding,
     CompressionLevel,
+    CustomCodec,
     IdentityCodec,
     compress_bytes,
     // Envelope functions
     compress_payload,
     decompress_bytes,
+    looks_incompressible,
     // Helper
     negotiate_response_encoding,
+    negotiate_response_encoding_with,
     supported_encodings_str,
 };
 
@@ -71,6 +76,12 @@ pub use connectrpc_axum_core::ZstdCodec;
 // Server-specific types
 // ============================================================================
 
+/// Header name for gRPC per-message request compression.
+pub const GRPC_ENCODING: &str = "grpc-encoding";
+
+/// Header name for gRPC response compression negotiation.
+pub const GRPC_ACCEPT_ENCODING: &str = "grpc-accept-encoding";
+
 /// Per-envelope compression settings for streaming RPCs.
 ///
 /// Parsed from `Connect-Content-Encoding` and `Connect-Accept-Encoding` headers.
@@ -103,13 +114,135 @@ pub fn parse_envelope_compression<B>(
         return Ok(None);
     }
 
-    // Parse Connect-Content-Encoding for streaming request compression
+    parse_encoding_headers(req, CONNECT_CONTENT_ENCODING, CONNECT_ACCEPT_ENCODING, &[]).map(Some)
+}
+
+/// Parse per-message compression settings from gRPC request headers.
+///
+/// gRPC compresses individual messages like Connect streaming, but negotiates
+/// with `grpc-encoding` and `grpc-accept-encoding`. Returns
+/// `Err(ConnectError)` if `grpc-encoding` is unsupported.
+pub fn parse_grpc_compression<B>(
+    req: &axum::http::Request<B>,
+) -> Result<EnvelopeCompression, ConnectError> {
+    parse_encoding_headers(req, GRPC_ENCODING, GRPC_ACCEPT_ENCODING, &[])
+}
+
+/// Parse per-message compression for a POST request, including custom codecs.
+///
+/// Returns `None` for unary Connect requests.
+pub(crate) fn parse_message_compression<B>(
+    req: &axum::http::Request<B>,
+    protocol: RequestProtocol,
+    custom: &[CompressionEncoding],
+) -> Result<Option<EnvelopeCompression>, ConnectError> {
+    if protocol.is_grpc() {
+        parse_encoding_headers(req, GRPC_ENCODING, GRPC_ACCEPT_ENCODING, custom).map(Some)
+    } else if protocol.is_streaming() {
+        parse_encoding_headers(
+            req,
+            CONNECT_CONTENT_ENCODING,
+            CONNECT_ACCEPT_ENCODING,
+            custom,
+        )
+        .map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Negotiate custom-codec HTTP body compression for a unary request.
+///
+/// Built-in encodings are left to Tower's compression layers, so this returns
+/// `None` unless the request body or the negotiated response uses a custom codec.
+pub(crate) fn parse_unary_compression<B>(
+    req: &axum::http::Request<B>,
+    custom: &[CompressionEncoding],
+) -> Option<EnvelopeCompression> {
+    if custom.is_empty() {
+        return None;
+    }
+    let header = |name| req.headers().get(name).and_then(|v| v.to_str().ok());
+
+    let request = CompressionEncoding::from_header_with(header(CONTENT_ENCODING), custom)
+        .filter(CompressionEncoding::is_custom)
+        .unwrap_or_default();
+    let response = Some(negotiate_response_encoding_with(
+        header(ACCEPT_ENCODING),
+        custom,
+    ))
+    .filter(CompressionEncoding::is_custom)
+    .unwrap_or_default();
+
+    (request.is_custom() || response.is_custom())
+        .then_some(EnvelopeCompression { request, response })
+}
+
+/// Comma-separated list of supported encodings for error messages.
+fn supported_encodings(custom: &[CompressionEncoding]) -> String {
+    custom
+        .iter()
+        .map(CompressionEncoding::as_str)
+        .chain([supported_encodings_str()])
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Encodings to advertise in `Connect-Accept-Encoding` / `grpc-accept-encoding`.
+///
+/// Identity is implied and not listed. Returns `None` if there is nothing to
+/// advertise.
+pub(crate) fn advertised_message_encodings(custom: &[CompressionEncoding]) -> Option<String> {
+    let builtin = supported_encodings_str()
+        .split(", ")
+        .filter(|name| *name != "identity");
+    join_encodings(builtin, custom)
+}
+
+/// Encodings to advertise in `Accept-Encoding` on unary responses.
+///
+/// Lists the codecs of the enabled `compression-*-unary` features and the
+/// custom codecs.
+pub(crate) fn advertised_body_encodings(custom: &[CompressionEncoding]) -> Option<String> {
+    let builtin = [
+        #[cfg(feature = "compression-gzip-unary")]
+        "gzip",
+        #[cfg(feature = "compression-deflate-unary")]
+        "deflate",
+        #[cfg(feature = "compression-br-unary")]
+        "br",
+        #[cfg(feature = "compression-zstd-unary")]
+        "zstd",
+    ];
+    join_encodings(builtin.into_iter(), custom)
+}
+
+fn join_encodings<'a>(
+    builtin: impl Iterator<Item = &'a str>,
+    custom: &[CompressionEncoding],
+) -> Option<String> {
+    let mut names: Vec<&str> = builtin.collect();
+    for encoding in custom {
+        if !names.contains(&encoding.as_str()) {
+            names.push(encoding.as_str());
+        }
+    }
+    (!names.is_empty()).then(|| names.join(","))
+}
+
+fn parse_encoding_headers<B>(
+    req: &axum::http::Request<B>,
+    content_header: &str,
+    accept_header: &str,
+    custom: &[CompressionEncoding],
+) -> Result<EnvelopeCompression, ConnectError> {
+    // Encoding of the request messages
     let content_encoding = req
         .headers()
-        .get(CONNECT_CONTENT_ENCODING)
+        .get(content_header)
         .and_then(|v| v.to_str().ok());
 
-    let request_encoding = match CompressionEncoding::from_header(content_encoding) {
+    let request_encoding = match CompressionEncoding::from_header_with(content_encoding, custom) {
         Some(enc) => enc,
         None => {
             return Err(ConnectError::new(
@@ -117,23 +250,23 @@ pub fn parse_envelope_compression<B>(
                 format!(
                     "unsupported compression \"{}\": supported encodings are {}",
                     content_encoding.unwrap_or(""),
-                    supported_encodings_str()
+                    supported_encodings(custom)
                 ),
             ));
         }
     };
 
-    // Parse Connect-Accept-Encoding for streaming response compression
+    // Negotiate the encoding of the response messages
     let accept_encoding = req
         .headers()
-        .get(CONNECT_ACCEPT_ENCODING)
+        .get(accept_header)
         .and_then(|v| v.to_str().ok());
-    let response_encoding = negotiate_response_encoding(accept_encoding);
+    let response_encoding = negotiate_response_encoding_with(accept_encoding, custom);
 
-    Ok(Some(EnvelopeCompression {
+    Ok(EnvelopeCompression {
         request: request_encoding,
         response: response_encoding,
-    }))
+    })
 }
 
 /// Resolve a codec from an encoding name.
@@ -40,6 +40,10 @@ pub struct MessageLimits {
     receive_max_bytes: Option<usize>,
     /// Maximum size of outgoing messages in bytes.
     send_max_bytes: Option<usize>,
+    /// Maximum length of a GET request's path and query in bytes.
+    max_url_bytes: Option<usize>,
+    /// Maximum number of messages in a request stream.
+    max_messages_per_stream: Option<usize>,
 }
 
 impl MessageLimits {
@@ -74,6 +78,28 @@ impl MessageLimits {
         self
     }
 
+    /// Set the maximum URL length for GET requests.
+    ///
+    /// GET RPCs carry the message in the query string, so this bounds the
+    /// encoded (and possibly compressed) request before it is decoded. The
+    /// path and query are measured. Returns `ResourceExhausted` error if a
+    /// request exceeds this limit.
+    pub fn max_url_bytes(mut self, max: usize) -> Self {
+        self.max_url_bytes = Some(max);
+        self
+    }
+
+    /// Set the maximum number of messages a client-streaming or bidi request
+    /// may send.
+    ///
+    /// Complements `receive_max_bytes`, which only bounds each message, so a
+    /// client can't flood a handler with millions of tiny envelopes. The stream
+    /// ends with a `ResourceExhausted` error when the limit is exceeded.
+    pub fn max_messages_per_stream(mut self, max: usize) -> Self {
+        self.max_messages_per_stream = Some(max);
+        self
+    }
+
     /// Returns the maximum receive message size, or `None` if unlimited.
     pub fn get_receive_max_bytes(&self) -> Option<usize> {
         self.receive_max_bytes
@@ -84,6 +110,16 @@ impl MessageLimits {
         self.send_max_bytes
     }
 
+    /// Returns the maximum GET URL length, or `None` if unlimited.
+    pub fn get_max_url_bytes(&self) -> Option<usize> {
+        self.max_url_bytes
+    }
+
+    /// Returns the maximum number of messages per request stream, or `None` if unlimited.
+    pub fn get_max_messages_per_stream(&self) -> Option<usize> {
+        self.max_messages_per_stream
+    }
+
     /// Returns the maximum receive message size for use with axum::body::to_bytes.
     ///
     /// Returns `usize::MAX` if unlimited.
@@ -125,6 +161,39 @@ impl MessageLimits {
         Ok(())
     }
 
+    /// Check if a GET request's URL length exceeds the configured limit.
+    ///
+    /// Returns `Ok(())` if the length is within limits, or `Err(ConnectError)` if it exceeds.
+    pub fn check_url_size(&self, size: usize) -> Result<(), ConnectError> {
+        if let Some(max) = self.max_url_bytes
+            && size > max
+        {
+            return Err(ConnectError::new(
+                Code::ResourceExhausted,
+                format!(
+                    "URL length {} bytes exceeds maximum allowed length of {} bytes",
+                    size, max
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check if the number of messages received on a stream exceeds the configured limit.
+    ///
+    /// Returns `Ok(())` if the count is within limits, or `Err(ConnectError)` if it exceeds.
+    pub fn check_message_count(&self, count: usize) -> Result<(), ConnectError> {
+        if let Some(max) = self.max_messages_per_stream
+            && count > max
+        {
+            return Err(ConnectError::new(
+                Code::ResourceExhausted,
+                format!("request stream exceeds maximum of {} messages", max),
+            ));
+        }
+        Ok(())
+    }
+
     /// Check if an outgoing message size exceeds the configured send limit.
     ///
     /// Returns `Ok(())` if the size is within limits, or `Err(ConnectError)` if it exceeds.
@@ -153,6 +222,8 @@ mod tests {
         let limits = MessageLimits::default();
         assert_eq!(limits.get_receive_max_bytes(), None);
         assert_eq!(limits.get_send_max_bytes(), None);
+        assert_eq!(limits.get_max_url_bytes(), None);
+        assert_eq!(limits.get_max_messages_per_stream(), None);
     }
 
     #[test]
@@ -229,4 +300,13 @@ mod tests {
         let limits = MessageLimits::new();
         assert!(limits.check_send_size(usize::MAX).is_ok());
     }
+
+    #[test]
+    fn test_check_url_size() {
+        let limits = MessageLimits::new().max_url_bytes(100);
+        assert!(limits.check_url_size(100).is_ok());
+        let err = limits.check_url_size(101).unwrap_err();
+        assert!(matches!(err.code(), Code::ResourceExhausted));
+        assert!(MessageLimits::new().check_url_size(usize::MAX).is_ok());
+    }
 }
@@ -0,0 +1,138 @@
+//! Connection info about the caller.
+//!
+//! [`ConnectLayer`](crate::ConnectLayer) records a [`PeerInfo`] in the
+//! [`ConnectContext`] of every request, and interceptor contexts carry it too.
+//! Handlers extract it directly:
+//!
+//! ```rust,ignore
+//! use connectrpc_axum::PeerInfo;
+//!
+//! async fn say_hello(
+//!     peer: PeerInfo,
+//!     ConnectRequest(req): ConnectRequest<HelloRequest>,
+//! ) -> Result<ConnectResponse<HelloResponse>, ConnectError> {
+//!     tracing::info!(addr = ?peer.remote_addr, agent = ?peer.user_agent, "hello");
+//!     // ...
+//! }
+//! ```
+//!
+//! The remote address comes from axum's `ConnectInfo<SocketAddr>`, so serve
+//! the app with `into_make_service_with_connect_info::<SocketAddr>()`;
+//! otherwise it is `None`.
+
+use super::ConnectContext;
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::request::Parts;
+use axum::http::{Extensions, HeaderMap, Version, header};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+/// Header browser clients (gRPC-Web, connect-web) use to identify themselves,
+/// since they cannot set `User-Agent`.
+const X_USER_AGENT: &str = "x-user-agent";
+
+/// Who is calling: remote address, HTTP version and user agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerInfo {
+    /// Address of the remote end of the connection, if known.
+    pub remote_addr: Option<SocketAddr>,
+    /// HTTP version of the request.
+    pub http_version: Version,
+    /// The client's `X-User-Agent`, or `User-Agent` if there is none.
+    pub user_agent: Option<UserAgent>,
+}
+
+impl PeerInfo {
+    pub(crate) fn new(extensions: &Extensions, headers: &HeaderMap, version: Version) -> Self {
+        let user_agent = headers
+            .get(X_USER_AGENT)
+            .or_else(|| headers.get(header::USER_AGENT))
+            .and_then(|value| value.to_str().ok())
+            .map(UserAgent::parse);
+        Self {
+            remote_addr: extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|info| info.0),
+            http_version: version,
+            user_agent,
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for PeerInfo {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(match parts.extensions.get::<ConnectContext>() {
+            Some(ctx) => ctx.peer.clone(),
+            None => PeerInfo::new(&parts.extensions, &parts.headers, parts.version),
+        })
+    }
+}
+
+/// A user agent string split into its leading `product/version` token.
+///
+/// RPC clients put their name first, e.g. `connect-go/1.16.0 (go1.22.0)` or
+/// `grpc-java-netty/1.62.2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAgent {
+    /// The full header value.
+    pub raw: String,
+    /// Product name of the first token, e.g. `connect-go`.
+    pub product: String,
+    /// Version of the first token, e.g. `1.16.0`.
+    pub version: Option<String>,
+}
+
+impl UserAgent {
+    /// Parse a `User-Agent` header value.
+    pub fn parse(raw: &str) -> Self {
+        let raw = raw.trim();
+        let token = raw.split_whitespace().next().unwrap_or_default();
+        let (product, version) = match token.split_once('/') {
+            Some((product, version)) => (product, Some(version.to_string())),
+            None => (token, None),
+        };
+        Self {
+            raw: raw.to_string(),
+            product: product.to_string(),
+            version,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    #[test]
+    fn test_parse_user_agent() {
+        let agent = UserAgent::parse("connect-go/1.16.0 (go1.22.0)");
+        assert_eq!(agent.product, "connect-go");
+        assert_eq!(agent.version.as_deref(), Some("1.16.0"));
+        assert_eq!(agent.raw, "connect-go/1.16.0 (go1.22.0)");
+
+        let agent = UserAgent::parse("curl");
+        assert_eq!(agent.product, "curl");
+        assert_eq!(agent.version, None);
+    }
+
+    #[tokio::test]
+    async fn test_peer_info_from_request() {
+        let addr: SocketAddr = "10.0.0.7:5000".parse().unwrap();
+        let (mut parts, _) = Request::builder()
+            .version(Version::HTTP_2)
+            .header(header::USER_AGENT, "Mozilla/5.0 (X11)")
+            .header(X_USER_AGENT, "grpc-web-javascript/0.1")
+            .extension(ConnectInfo(addr))
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        let Ok(peer) = PeerInfo::from_request_parts(&mut parts, &()).await;
+        assert_eq!(peer.remote_addr, Some(addr));
+        assert_eq!(peer.http_version, Version::HTTP_2);
+        assert_eq!(peer.user_agent.unwrap().product, "grpc-web-javascript");
+    }
+}
@@ -22,9 +22,8 @@ pub const CONNECT_PROTOCOL_VERSION_HEADER: &str = "connect-protocol-version";
 /// Supported content types for the Accept-Post header.
 ///
 /// Used in 415 Unsupported Media Type responses to indicate which
-/// content types are supported by the Connect protocol.
-pub const SUPPORTED_CONTENT_TYPES: &str =
-    "application/json, application/proto, application/connect+json, application/connect+proto";
+/// content types are supported by the Connect, gRPC and gRPC-Web protocols.
+pub const SUPPORTED_CONTENT_TYPES: &str = "application/json, application/proto, application/connect+json, application/connect+proto, application/grpc, application/grpc+json, application/grpc-web, application/grpc-web+json";
 
 // ============================================================================
 // IdempotencyLevel enum
@@ -40,7 +39,7 @@ pub const SUPPORTED_CONTENT_TYPES: &str =
 /// idempotent query methods, allowing caching by CDNs and browsers.
 ///
 /// These values match `google.protobuf.MethodOptions.IdempotencyLevel`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 #[repr(i32)]
 pub enum IdempotencyLevel {
     /// The default idempotency level. A procedure with this idempotency level
@@ -120,8 +119,9 @@ impl std::fmt::Display for IdempotencyLevel {
 /// - Whether envelope framing is needed
 /// - Protobuf vs JSON encoding
 ///
-/// Note: gRPC and gRPC-web are handled by `ContentTypeSwitch` which routes
-/// them to Tonic, so there's no `GrpcProto` variant here.
+/// gRPC and gRPC-Web requests are served natively by the same handlers. When
+/// tonic services are added to `MakeServiceBuilder`, `ContentTypeSwitch` routes
+/// gRPC to tonic before it reaches this code.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RequestProtocol {
     /// Connect unary with JSON encoding (`application/json`)
@@ -141,6 +141,22 @@ pub enum RequestProtocol {
     /// Response: framed protobuf messages with EndStream
     ConnectStreamProto,
 
+    /// gRPC with protobuf encoding (`application/grpc`, `application/grpc+proto`)
+    /// Response: framed protobuf messages, status in HTTP trailers
+    GrpcProto,
+
+    /// gRPC-Web with protobuf encoding (`application/grpc-web`, `application/grpc-web+proto`)
+    /// Response: framed protobuf messages, status in a trailer frame
+    GrpcWebProto,
+
+    /// gRPC with JSON encoding (`application/grpc+json`)
+    /// Response: framed JSON messages, status in HTTP trailers
+    GrpcJson,
+
+    /// gRPC-Web with JSON encoding (`application/grpc-web+json`)
+    /// Response: framed JSON messages, status in a trailer frame
+    GrpcWebJson,
+
     /// Unknown or unsupported content-type.
     /// Requests with this protocol should be rejected.
     Unknown,
@@ -149,12 +165,24 @@ pub enum RequestProtocol {
 impl RequestProtocol {
     /// Detect protocol from Content-Type header value.
     ///
-    /// Note: gRPC (`application/grpc*`) is handled by `ContentTypeSwitch` before
-    /// reaching this code, so we don't need to detect it here.
+    /// gRPC and gRPC-Web accept the `proto` and `json` codecs. gRPC-Web text
+    /// mode (`application/grpc-web-text`) is not supported.
     ///
     /// Returns `Unknown` for unrecognized content-types.
     pub fn from_content_type(content_type: &str) -> Self {
-        if content_type.starts_with("application/connect+proto") {
+        let media_type = content_type.split(';').next().unwrap_or("").trim();
+        if matches!(media_type, "application/grpc" | "application/grpc+proto") {
+            Self::GrpcProto
+        } else if matches!(
+            media_type,
+            "application/grpc-web" | "application/grpc-web+proto"
+        ) {
+            Self::GrpcWebProto
+        } else if media_type == "application/grpc+json" {
+            Self::GrpcJson
+        } else if media_type == "application/grpc-web+json" {
+            Self::GrpcWebJson
+        } else if content_type.starts_with("application/connect+proto") {
             Self::ConnectStreamProto
         } else if content_type.starts_with("application/connect+json") {
             Self::ConnectStreamJson
@@ -175,13 +203,17 @@ impl RequestProtocol {
             Self::ConnectUnaryProto => "application/proto",
             Self::ConnectStreamJson => "application/connect+json",
             Self::ConnectStreamProto => "application/connect+proto",
+            Self::GrpcProto => "application/grpc",
+            Self::GrpcWebProto => "application/grpc-web+proto",
+            Self::GrpcJson => "application/grpc+json",
+            Self::GrpcWebJson => "application/grpc-web+json",
         }
     }
 
     /// Response Content-Type for error responses.
     ///
     /// For Connect unary, errors are always JSON regardless of request encoding.
-    /// For streaming, errors use the same encoding as success responses.
+    /// For streaming and gRPC, errors use the same encoding as success responses.
     /// For unknown protocols, errors are JSON.
     pub fn error_content_type(&self) -> &'static str {
         match self {
@@ -190,6 +222,10 @@ impl RequestProtocol {
             Self::ConnectUnaryJson | Self::ConnectUnaryProto | Self::Unknown => "application/json",
             Self::ConnectStreamJson => "application/connect+json",
             Self::ConnectStreamProto => "application/connect+proto",
+            Self::GrpcProto => "application/grpc",
+            Self::GrpcWebProto => "application/grpc-web+proto",
+            Self::GrpcJson => "application/grpc+json",
+            Self::GrpcWebJson => "application/grpc-web+json",
         }
     }
 
@@ -197,19 +233,63 @@ impl RequestProtocol {
     ///
     /// - Connect unary: no framing (raw bytes)
     /// - Connect streaming: framing with EndStream message
+    /// - gRPC / gRPC-Web: framing for every RPC type
     /// - Unknown: no framing (error responses are unary-style)
     pub fn needs_envelope(&self) -> bool {
-        matches!(self, Self::ConnectStreamJson | Self::ConnectStreamProto)
+        self.is_streaming()
     }
 
     /// Whether to encode message bodies as protobuf (vs JSON).
     pub fn is_proto(&self) -> bool {
-        matches!(self, Self::ConnectUnaryProto | Self::ConnectStreamProto)
+        matches!(
+            self,
+            Self::ConnectUnaryProto
+                | Self::ConnectStreamProto
+                | Self::GrpcProto
+                | Self::GrpcWebProto
+        )
     }
 
     /// Whether this is a streaming protocol variant.
+    ///
+    /// gRPC and gRPC-Web always use the streaming wire format, even for
+    /// unary RPCs.
     pub fn is_streaming(&self) -> bool {
-        matches!(self, Self::ConnectStreamJson | Self::ConnectStreamProto)
+        matches!(
+            self,
+            Self::ConnectStreamJson
+                | Self::ConnectStreamProto
+                | Self::GrpcProto
+                | Self::GrpcWebProto
+                | Self::GrpcJson
+                | Self::GrpcWebJson
+        )
+    }
+
+    /// Whether this is gRPC or gRPC-Web.
+    pub fn is_grpc(&self) -> bool {
+        matches!(
+            self,
+            Self::GrpcProto | Self::GrpcWebProto | Self::GrpcJson | Self::GrpcWebJson
+        )
+    }
+
+    /// Whether this is gRPC-Web, which reports the status in a trailer frame.
+    pub fn is_grpc_web(&self) -> bool {
+        matches!(self, Self::GrpcWebProto | Self::GrpcWebJson)
+    }
+
+    /// Name of the wire protocol: `connect`, `grpc`, `grpc-web` or `unknown`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ConnectUnaryJson
+            | Self::ConnectUnaryProto
+            | Self::ConnectStreamJson
+            | Self::ConnectStreamProto => "connect",
+            Self::GrpcProto | Self::GrpcJson => "grpc",
+            Self::GrpcWebProto | Self::GrpcWebJson => "grpc-web",
+            Self::Unknown => "unknown",
+        }
     }
 
     /// Whether this is a unary protocol variant.
@@ -227,7 +307,9 @@ impl RequestProtocol {
     /// For server-streaming endpoints where the request is unary but the
     /// response is streaming, use this to get the correct streaming content-type.
     pub fn streaming_response_content_type(&self) -> &'static str {
-        if self.is_proto() {
+        if self.is_grpc() {
+            self.response_content_type()
+        } else if self.is_proto() {
             "application/connect+proto"
         } else {
             "application/connect+json"
@@ -371,10 +453,10 @@ pub fn can_handle_get_encoding<B>(req: &Request<B>) -> bool {
 /// Returns `Some(ConnectError)` if a streaming content-type is used for a unary RPC.
 /// Returns `None` if the content-type is valid for unary.
 ///
-/// Unary RPCs accept: `application/json`, `application/proto`
+/// Unary RPCs accept: `application/json`, `application/proto`, gRPC, gRPC-Web
 /// Unary RPCs reject: `application/connect+json`, `application/connect+proto`
 pub fn validate_unary_content_type(protocol: RequestProtocol) -> Option<ConnectError> {
-    if protocol.is_unary() {
+    if protocol.is_unary() || protocol.is_grpc() {
         None
     } else if protocol.is_streaming() {
         Some(ConnectError::new(
@@ -392,7 +474,7 @@ pub fn validate_unary_content_type(protocol: RequestProtocol) -> Option<ConnectE
 /// Returns `Some(ConnectError)` if a unary content-type is used for a streaming RPC.
 /// Returns `None` if the content-type is valid for streaming.
 ///
-/// Streaming RPCs accept: `application/connect+json`, `application/connect+proto`
+/// Streaming RPCs accept: `application/connect+json`, `application/connect+proto`, gRPC, gRPC-Web
 /// Streaming RPCs reject: `application/json`, `application/proto`
 pub fn validate_streaming_content_type(protocol: RequestProtocol) -> Option<ConnectError> {
     if protocol.is_streaming() {
@@ -560,6 +642,84 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_content_type_grpc() {
+        for content_type in ["application/grpc", "application/grpc+proto"] {
+            assert_eq!(
+                RequestProtocol::from_content_type(content_type),
+                RequestProtocol::GrpcProto
+            );
+        }
+        for content_type in ["application/grpc-web", "application/grpc-web+proto"] {
+            assert_eq!(
+                RequestProtocol::from_content_type(content_type),
+                RequestProtocol::GrpcWebProto
+            );
+        }
+        assert_eq!(
+            RequestProtocol::from_content_type("application/grpc+json"),
+            RequestProtocol::GrpcJson
+        );
+        assert_eq!(
+            RequestProtocol::from_content_type("application/grpc-web+json; charset=utf-8"),
+            RequestProtocol::GrpcWebJson
+        );
+        // Text mode and other codecs are not supported
+        for content_type in [
+            "application/grpc-web-text",
+            "application/grpc+thrift",
+            "application/grpcx",
+        ] {
+            assert_eq!(
+                RequestProtocol::from_content_type(content_type),
+                RequestProtocol::Unknown
+            );
+        }
+    }
+
+    #[test]
+    fn test_grpc_protocol_properties() {
+        for protocol in [RequestProtocol::GrpcProto, RequestProtocol::GrpcWebProto] {
+            assert!(protocol.is_grpc());
+            assert!(protocol.is_proto());
+            assert!(protocol.is_streaming());
+            assert!(protocol.needs_envelope());
+            assert_eq!(
+                protocol.streaming_response_content_type(),
+                protocol.response_content_type()
+            );
+            assert!(validate_unary_content_type(protocol).is_none());
+            assert!(validate_streaming_content_type(protocol).is_none());
+        }
+        assert!(!RequestProtocol::ConnectStreamProto.is_grpc());
+        assert_eq!(
+            RequestProtocol::GrpcWebProto.error_content_type(),
+            "application/grpc-web+proto"
+        );
+    }
+
+    #[test]
+    fn test_grpc_json_protocol_properties() {
+        for protocol in [RequestProtocol::GrpcJson, RequestProtocol::GrpcWebJson] {
+            assert!(protocol.is_grpc());
+            assert!(!protocol.is_proto());
+            assert!(protocol.is_streaming());
+            assert_eq!(
+                protocol.streaming_response_content_type(),
+                protocol.response_content_type()
+            );
+            assert!(validate_unary_content_type(protocol).is_none());
+            assert!(validate_streaming_content_type(protocol).is_none());
+        }
+        assert!(RequestProtocol::GrpcWebJson.is_grpc_web());
+        assert!(!RequestProtocol::GrpcJson.is_grpc_web());
+        assert_eq!(RequestProtocol::GrpcJson.name(), "grpc");
+        assert_eq!(
+            RequestProtocol::GrpcWebJson.error_content_type(),
+            "application/grpc-web+json"
+        );
+    }
+
     #[test]
     fn test_response_content_type() {
         assert_eq!(
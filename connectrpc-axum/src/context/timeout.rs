@@ -4,12 +4,23 @@
 //! Clients can set a `Connect-Timeout-Ms` header to specify how long they're
 //! willing to wait for a response.
 
+use crate::message::error::{Code, ConnectError};
 use axum::http::Request;
+use futures::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::task::{Context, Poll};
 use std::time::Duration;
+use tokio::time::{Instant, Sleep};
 
 /// Header name for Connect timeout in milliseconds.
 pub const CONNECT_TIMEOUT_MS_HEADER: &str = "connect-timeout-ms";
 
+/// Header name for the gRPC timeout (e.g. `100m`, `5S`).
+pub const GRPC_TIMEOUT_HEADER: &str = "grpc-timeout";
+
 // ============================================================================
 // ConnectTimeout (backwards compatibility)
 // ============================================================================
@@ -88,15 +99,46 @@ impl Default for ConnectTimeout {
 // Parsing functions
 // ============================================================================
 
-/// Parse the Connect-Timeout-Ms header from a request.
+/// Parse the client timeout from a request.
 ///
+/// Reads `Connect-Timeout-Ms`, or `grpc-timeout` for gRPC clients.
 /// Returns `Some(Duration)` if the header is present and valid,
 /// or `None` if the header is missing, invalid, or zero (which means no timeout per Connect spec).
 pub fn parse_timeout<B>(req: &Request<B>) -> Option<Duration> {
-    req.headers()
-        .get(CONNECT_TIMEOUT_MS_HEADER)
+    let headers = req.headers();
+    if let Some(value) = headers.get(CONNECT_TIMEOUT_MS_HEADER) {
+        return value.to_str().ok().and_then(parse_timeout_ms);
+    }
+    headers
+        .get(GRPC_TIMEOUT_HEADER)
         .and_then(|v| v.to_str().ok())
-        .and_then(parse_timeout_ms)
+        .and_then(parse_grpc_timeout)
+}
+
+/// Parse a `grpc-timeout` header value.
+///
+/// The value is at most 8 digits followed by a unit: `H`, `M`, `S`, `m`
+/// (milliseconds), `u` (microseconds) or `n` (nanoseconds). Returns `None`
+/// for invalid values or 0.
+pub fn parse_grpc_timeout(value: &str) -> Option<Duration> {
+    if value.len() < 2 || value.len() > 9 {
+        return None;
+    }
+    let (amount, unit) = value.split_at(value.len() - 1);
+    if !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount: u64 = amount.parse().ok()?;
+    let duration = match unit {
+        "H" => Duration::from_secs(amount * 60 * 60),
+        "M" => Duration::from_secs(amount * 60),
+        "S" => Duration::from_secs(amount),
+        "m" => Duration::from_millis(amount),
+        "u" => Duration::from_micros(amount),
+        "n" => Duration::from_nanos(amount),
+        _ => return None,
+    };
+    (!duration.is_zero()).then_some(duration)
 }
 
 /// Parse a timeout milliseconds string.
@@ -113,27 +155,165 @@ pub fn parse_timeout_ms(value: &str) -> Option<Duration> {
     }
 }
 
-/// Compute the effective timeout from server and client timeouts.
+/// Compute the effective timeout from the server settings and client timeout.
 ///
-/// The effective timeout is the minimum of the two, matching Connect-Go's behavior
-/// where the smaller timeout always wins.
+/// The client's timeout is clamped to `max_timeout`; without one,
+/// `default_timeout` applies instead. The result is then capped by
+/// `server_timeout`, matching Connect-Go's behavior where the smaller timeout
+/// always wins.
 ///
-/// Returns `None` if neither timeout is set (unlimited).
+/// Returns `None` if no timeout applies (unlimited).
 pub fn compute_effective_timeout(
     server_timeout: Option<Duration>,
+    default_timeout: Option<Duration>,
+    max_timeout: Option<Duration>,
     client_timeout: Option<Duration>,
 ) -> Option<Duration> {
-    match (server_timeout, client_timeout) {
+    let requested = match (client_timeout, max_timeout) {
+        // Clamp what the client asked for
+        (Some(client), Some(max)) => Some(client.min(max)),
+        (Some(client), None) => Some(client),
+        // No client timeout: fall back to the default
+        (None, _) => default_timeout,
+    };
+    match (server_timeout, requested) {
         // Both set: use the smaller
-        (Some(server), Some(client)) => Some(server.min(client)),
+        (Some(server), Some(requested)) => Some(server.min(requested)),
         // Only one set
         (Some(server), None) => Some(server),
-        (None, Some(client)) => Some(client),
+        (None, Some(requested)) => Some(requested),
         // Neither set
         (None, None) => None,
     }
 }
 
+/// Set by a per-method timeout to take over from the `ConnectLayer` deadline.
+///
+/// `ConnectLayer` stores this in request extensions when it enforces a timeout;
+/// if the deadline fires after a route has claimed it, `ConnectLayer` keeps
+/// waiting and the route's own deadline applies instead.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TimeoutOverride(Arc<AtomicBool>);
+
+impl TimeoutOverride {
+    pub(crate) fn claim(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn is_claimed(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+// ============================================================================
+// Stream idle timeout
+// ============================================================================
+
+/// Inactivity deadline of a streaming RPC.
+///
+/// Created by [`ConnectLayer`](crate::ConnectLayer) for streaming requests when
+/// [`stream_idle_timeout`](crate::ConnectLayer::stream_idle_timeout) is set.
+/// The request stream and the response stream share it: a message in either
+/// direction restarts the timer, and once neither side has moved for the
+/// timeout, whichever stream is waiting ends with `deadline_exceeded`.
+#[derive(Clone, Debug)]
+pub struct IdleTimeout {
+    timeout: Duration,
+    start: Instant,
+    /// Last activity, in nanoseconds since `start`.
+    last: Arc<AtomicU64>,
+}
+
+impl IdleTimeout {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            start: Instant::now(),
+            last: Arc::default(),
+        }
+    }
+
+    /// The configured inactivity timeout.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Instant the stream becomes idle unless a message is sent or received.
+    pub fn deadline(&self) -> Instant {
+        self.start + Duration::from_nanos(self.last.load(Ordering::Relaxed)) + self.timeout
+    }
+
+    /// Record a message sent or received.
+    pub(crate) fn touch(&self) {
+        let elapsed = self.start.elapsed().as_nanos();
+        self.last.fetch_max(
+            u64::try_from(elapsed).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// End `stream` with a `deadline_exceeded` error once the RPC is idle.
+    pub(crate) fn watch<S>(&self, stream: S) -> IdleStream<S> {
+        IdleStream {
+            stream,
+            sleep: Box::pin(tokio::time::sleep_until(self.deadline())),
+            idle: self.clone(),
+            done: false,
+        }
+    }
+
+    fn error(&self) -> ConnectError {
+        ConnectError::new(
+            Code::DeadlineExceeded,
+            format!(
+                "stream idle timeout: no message sent or received for {:?}",
+                self.timeout
+            ),
+        )
+    }
+}
+
+/// Message stream ended by an [`IdleTimeout`].
+pub(crate) struct IdleStream<S> {
+    stream: S,
+    idle: IdleTimeout,
+    sleep: Pin<Box<Sleep>>,
+    done: bool,
+}
+
+impl<S, T> Stream for IdleStream<S>
+where
+    S: Stream<Item = Result<T, ConnectError>> + Unpin,
+{
+    type Item = Result<T, ConnectError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        match Pin::new(&mut self.stream).poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                self.idle.touch();
+                Poll::Ready(Some(item))
+            }
+            Poll::Ready(None) => {
+                self.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => {
+                // The other direction may have moved the deadline
+                let deadline = self.idle.deadline();
+                if self.sleep.deadline() != deadline {
+                    self.sleep.as_mut().reset(deadline);
+                }
+                std::task::ready!(self.sleep.as_mut().poll(cx));
+                self.done = true;
+                Poll::Ready(Some(Err(self.idle.error())))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,8 +380,34 @@ mod tests {
         assert_eq!(parse_timeout_ms(""), None);
     }
 
+    #[test]
+    fn test_parse_grpc_timeout() {
+        assert_eq!(parse_grpc_timeout("2H"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_grpc_timeout("3M"), Some(Duration::from_secs(180)));
+        assert_eq!(parse_grpc_timeout("5S"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_grpc_timeout("100m"), Some(Duration::from_millis(100)));
+        assert_eq!(parse_grpc_timeout("250u"), Some(Duration::from_micros(250)));
+        assert_eq!(
+            parse_grpc_timeout("99999999n"),
+            Some(Duration::from_nanos(99999999))
+        );
+        for invalid in ["0S", "S", "5", "5s", "-5S", "+5S", "123456789S", ""] {
+            assert_eq!(parse_grpc_timeout(invalid), None, "{invalid}");
+        }
+    }
+
     // --- parse_timeout tests ---
 
+    #[test]
+    fn test_parse_timeout_grpc_header() {
+        let req = Request::builder()
+            .method(Method::POST)
+            .header(GRPC_TIMEOUT_HEADER, "250m")
+            .body(())
+            .unwrap();
+        assert_eq!(parse_timeout(&req), Some(Duration::from_millis(250)));
+    }
+
     #[test]
     fn test_parse_timeout_valid() {
         let req = Request::builder()
@@ -245,7 +451,7 @@ mod tests {
         let server = Some(Duration::from_secs(5));
         let client = Some(Duration::from_secs(10));
         assert_eq!(
-            compute_effective_timeout(server, client),
+            compute_effective_timeout(server, None, None, client),
             Some(Duration::from_secs(5))
         );
     }
@@ -255,7 +461,7 @@ mod tests {
         let server = Some(Duration::from_secs(10));
         let client = Some(Duration::from_secs(5));
         assert_eq!(
-            compute_effective_timeout(server, client),
+            compute_effective_timeout(server, None, None, client),
             Some(Duration::from_secs(5))
         );
     }
@@ -265,7 +471,7 @@ mod tests {
         let server = Some(Duration::from_secs(5));
         let client = None;
         assert_eq!(
-            compute_effective_timeout(server, client),
+            compute_effective_timeout(server, None, None, client),
             Some(Duration::from_secs(5))
         );
     }
@@ -275,13 +481,71 @@ mod tests {
         let server = None;
         let client = Some(Duration::from_secs(5));
         assert_eq!(
-            compute_effective_timeout(server, client),
+            compute_effective_timeout(server, None, None, client),
             Some(Duration::from_secs(5))
         );
     }
 
     #[test]
     fn test_compute_effective_timeout_neither() {
-        assert_eq!(compute_effective_timeout(None, None), None);
+        assert_eq!(compute_effective_timeout(None, None, None, None), None);
+    }
+
+    #[test]
+    fn test_compute_effective_timeout_default_without_client() {
+        let default = Some(Duration::from_secs(5));
+        assert_eq!(
+            compute_effective_timeout(None, default, None, None),
+            Some(Duration::from_secs(5))
+        );
+        // A client timeout replaces the default, even when longer
+        assert_eq!(
+            compute_effective_timeout(None, default, None, Some(Duration::from_secs(20))),
+            Some(Duration::from_secs(20))
+        );
+        // The server timeout still caps the default
+        assert_eq!(
+            compute_effective_timeout(Some(Duration::from_secs(2)), default, None, None),
+            Some(Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn test_compute_effective_timeout_max_clamps_client() {
+        let max = Some(Duration::from_secs(60));
+        assert_eq!(
+            compute_effective_timeout(None, None, max, Some(Duration::from_secs(86_400))),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            compute_effective_timeout(None, None, max, Some(Duration::from_secs(5))),
+            Some(Duration::from_secs(5))
+        );
+        // The cap only clamps client timeouts
+        assert_eq!(compute_effective_timeout(None, None, max, None), None);
+    }
+
+    // --- IdleTimeout tests ---
+
+    #[tokio::test]
+    async fn test_idle_timeout_is_shared_by_both_directions() {
+        use futures::StreamExt;
+
+        let idle = IdleTimeout::new(Duration::from_millis(100));
+        let (tx, rx) = futures::channel::mpsc::unbounded::<Result<u32, ConnectError>>();
+        let mut request = idle.watch(rx);
+        let response = idle.clone();
+
+        // Activity on the response side keeps the request stream alive
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        response.touch();
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        tx.unbounded_send(Ok(1)).unwrap();
+        assert_eq!(request.next().await.unwrap().unwrap(), 1);
+
+        let err = request.next().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::DeadlineExceeded);
+        assert!(idle.start.elapsed() >= Duration::from_millis(240));
+        assert!(request.next().await.is_none());
     }
 }
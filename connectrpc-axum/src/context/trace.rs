@@ -0,0 +1,154 @@
+//! W3C Trace Context propagation.
+//!
+//! Callers that are part of a distributed trace send a
+//! [`traceparent`](https://www.w3.org/TR/trace-context/#traceparent-header)
+//! header. [`TraceParent`] parses it; handlers and interceptors can extract it
+//! as `Option<TraceParent>`:
+//!
+//! ```rust,ignore
+//! use connectrpc_axum::TraceParent;
+//!
+//! async fn say_hello(
+//!     trace: Option<TraceParent>,
+//!     ConnectRequest(req): ConnectRequest<HelloRequest>,
+//! ) -> Result<ConnectResponse<HelloResponse>, ConnectError> {
+//!     if let Some(trace) = trace {
+//!         tracing::info!(trace_id = %trace.trace_id, "hello");
+//!     }
+//!     // ...
+//! }
+//! ```
+
+use axum::extract::OptionalFromRequestParts;
+use axum::http::HeaderMap;
+use axum::http::request::Parts;
+use std::convert::Infallible;
+
+/// Header carrying the caller's trace context.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Flag marking a sampled trace.
+const SAMPLED: u8 = 0x01;
+
+/// The caller's position in a distributed trace, from the `traceparent` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceParent {
+    /// Trace ID as 32 lowercase hex digits.
+    pub trace_id: String,
+    /// Span ID of the caller as 16 lowercase hex digits.
+    pub parent_id: String,
+    /// Trace flags; bit `0x01` marks a sampled trace.
+    pub flags: u8,
+}
+
+impl TraceParent {
+    /// Parse a `traceparent` header value.
+    ///
+    /// Returns `None` for malformed values, all-zero IDs and version `ff`, which
+    /// the specification says to ignore. Versions above `00` are parsed by their
+    /// version `00` prefix.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().splitn(5, '-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let parent_id = parts.next()?;
+        let flags = parts.next()?;
+        let rest = parts.next();
+
+        if !is_hex(version, 2) || version == "ff" || (version == "00" && rest.is_some()) {
+            return None;
+        }
+        if !is_hex(trace_id, 32) || !is_hex(parent_id, 16) || !is_hex(flags, 2) {
+            return None;
+        }
+        if is_zero(trace_id) || is_zero(parent_id) {
+            return None;
+        }
+
+        Some(Self {
+            trace_id: trace_id.to_owned(),
+            parent_id: parent_id.to_owned(),
+            flags: u8::from_str_radix(flags, 16).ok()?,
+        })
+    }
+
+    /// Parse the `traceparent` header of a request, if it has a valid one.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        Self::parse(headers.get(TRACEPARENT_HEADER)?.to_str().ok()?)
+    }
+
+    /// Whether the caller sampled the trace.
+    pub fn sampled(&self) -> bool {
+        self.flags & SAMPLED != 0
+    }
+}
+
+impl std::fmt::Display for TraceParent {
+    /// Format as a version `00` header value.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            self.trace_id, self.parent_id, self.flags
+        )
+    }
+}
+
+impl<S: Send + Sync> OptionalFromRequestParts<S> for TraceParent {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+/// Whether `value` is `len` lowercase hex digits.
+fn is_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn is_zero(value: &str) -> bool {
+    value.bytes().all(|b| b == b'0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_traceparent() {
+        let value = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let trace = TraceParent::parse(value).unwrap();
+        assert_eq!(trace.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(trace.parent_id, "00f067aa0ba902b7");
+        assert!(trace.sampled());
+        assert_eq!(trace.to_string(), value);
+
+        // Future versions may append fields
+        assert!(
+            TraceParent::parse("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-extra")
+                .is_some_and(|trace| !trace.sampled())
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_traceparent() {
+        for value in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert_eq!(TraceParent::parse(value), None, "{value}");
+        }
+    }
+}
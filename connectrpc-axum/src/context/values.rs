@@ -0,0 +1,190 @@
+//! Request-scoped values shared by middleware, interceptors and handlers.
+//!
+//! [`ContextValues`] is a typed map carried on the [`ConnectContext`]. Tower
+//! middleware in front of [`ConnectLayer`](crate::ConnectLayer) puts one into
+//! the request extensions; the layer moves it into the context, where header
+//! interceptors can add to it and message interceptors and handlers read it:
+//!
+//! ```rust,ignore
+//! use connectrpc_axum::{ContextValue, ContextValues};
+//!
+//! #[derive(Clone)]
+//! struct Tenant(String);
+//!
+//! // Middleware, e.g. `axum::middleware::from_fn`
+//! async fn tenancy(mut req: Request, next: Next) -> Response {
+//!     let tenant = Tenant(lookup_tenant(req.headers()));
+//!     ContextValues::from_extensions_mut(req.extensions_mut()).insert(tenant);
+//!     next.run(req).await
+//! }
+//!
+//! async fn list_orders(
+//!     ContextValue(tenant): ContextValue<Tenant>,
+//!     ConnectRequest(req): ConnectRequest<ListOrdersRequest>,
+//! ) -> Result<ConnectResponse<ListOrdersResponse>, ConnectError> {
+//!     // ...
+//! }
+//! ```
+
+use super::ConnectContext;
+use crate::message::error::ConnectError;
+use axum::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum::http::Extensions;
+use axum::http::request::Parts;
+use std::convert::Infallible;
+
+/// Typed map of request-scoped values, keyed by type.
+///
+/// Values must be `Clone + Send + Sync + 'static`. Cloning the map clones
+/// the values.
+#[derive(Debug, Clone, Default)]
+pub struct ContextValues {
+    values: Extensions,
+}
+
+impl ContextValues {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The map in `extensions`, inserted if missing.
+    ///
+    /// For middleware running before `ConnectLayer`, which takes the map out
+    /// of the request extensions when it builds the [`ConnectContext`].
+    pub fn from_extensions_mut(extensions: &mut Extensions) -> &mut Self {
+        extensions.get_or_insert_default::<Self>()
+    }
+
+    /// Insert a value, returning the previous value of the same type.
+    pub fn insert<T: Clone + Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+        self.values.insert(value)
+    }
+
+    /// Get a reference to the value of type `T`.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.values.get::<T>()
+    }
+
+    /// Get a mutable reference to the value of type `T`.
+    pub fn get_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut T> {
+        self.values.get_mut::<T>()
+    }
+
+    /// Remove the value of type `T`.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<T> {
+        self.values.remove::<T>()
+    }
+
+    /// Returns `true` if a value of type `T` is present.
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.values.get::<T>().is_some()
+    }
+
+    /// Returns `true` if the map holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Extracts a clone of the [`ContextValues`] entry of type `T`.
+///
+/// Rejects with an `internal` error if no value of type `T` was stored, since
+/// that means the middleware providing it isn't installed. Extract
+/// `Option<ContextValue<T>>` for values that may be absent.
+#[derive(Debug, Clone)]
+pub struct ContextValue<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ContextValue<T>
+where
+    S: Send + Sync,
+    T: Clone + Send + Sync + 'static,
+{
+    type Rejection = ConnectError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match <Self as OptionalFromRequestParts<S>>::from_request_parts(parts, state).await {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(ConnectError::new_internal(format!(
+                "missing context value of type {}",
+                std::any::type_name::<T>()
+            ))),
+            Err(infallible) => match infallible {},
+        }
+    }
+}
+
+impl<S, T> OptionalFromRequestParts<S> for ContextValue<T>
+where
+    S: Send + Sync,
+    T: Clone + Send + Sync + 'static,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        // Without ConnectLayer, middleware values are still in the extensions
+        let values = match parts.extensions.get::<ConnectContext>() {
+            Some(ctx) => Some(&ctx.values),
+            None => parts.extensions.get::<ContextValues>(),
+        };
+        Ok(values
+            .and_then(|values| values.get::<T>())
+            .cloned()
+            .map(ContextValue))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Tenant(&'static str);
+
+    #[test]
+    fn test_context_values() {
+        let mut values = ContextValues::new();
+        assert!(values.is_empty());
+        assert_eq!(values.insert(Tenant("a")), None);
+        assert_eq!(values.insert(Tenant("b")), Some(Tenant("a")));
+        assert!(values.contains::<Tenant>());
+        values.get_mut::<Tenant>().unwrap().0 = "c";
+        assert_eq!(values.get::<Tenant>(), Some(&Tenant("c")));
+        assert_eq!(values.remove::<Tenant>(), Some(Tenant("c")));
+        assert!(!values.contains::<Tenant>());
+    }
+
+    #[tokio::test]
+    async fn test_extractor() {
+        let mut ctx = ConnectContext::default();
+        ctx.values.insert(Tenant("acme"));
+        let (mut parts, ()) = Request::builder()
+            .extension(ctx)
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        let ContextValue(tenant) =
+            <ContextValue<Tenant> as FromRequestParts<()>>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap();
+        assert_eq!(tenant, Tenant("acme"));
+
+        let missing =
+            <ContextValue<u32> as FromRequestParts<()>>::from_request_parts(&mut parts, &())
+                .await
+                .unwrap_err();
+        assert_eq!(missing.code(), crate::message::error::Code::Internal);
+        let optional = <ContextValue<u32> as OptionalFromRequestParts<()>>::from_request_parts(
+            &mut parts,
+            &(),
+        )
+        .await
+        .unwrap();
+        assert!(optional.is_none());
+    }
+}
@@ -0,0 +1,144 @@
+//! Static descriptors for generated RPC methods.
+//!
+//! `connectrpc-axum-build` emits a `SERVICE_NAME` constant and a `METHODS`
+//! table in every generated service module. Middleware can use the table to
+//! look up a request's method by path instead of string-matching URLs:
+//!
+//! ```rust,ignore
+//! use connectrpc_axum::MethodDescriptor;
+//!
+//! let method = MethodDescriptor::find(hello_world_service_connect::METHODS, req.uri().path());
+//! if let Some(method) = method
+//!     && method.kind.is_streaming()
+//! {
+//!     // ...
+//! }
+//! ```
+
+use crate::context::IdempotencyLevel;
+
+/// Streaming kind of an RPC method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MethodKind {
+    /// One request message, one response message.
+    Unary,
+    /// One request message, a stream of response messages.
+    ServerStreaming,
+    /// A stream of request messages, one response message.
+    ClientStreaming,
+    /// Streams in both directions.
+    BidiStreaming,
+}
+
+impl MethodKind {
+    /// Build from the `client_streaming` / `server_streaming` flags of a
+    /// method descriptor.
+    pub const fn from_streaming(client_streaming: bool, server_streaming: bool) -> Self {
+        match (client_streaming, server_streaming) {
+            (false, false) => Self::Unary,
+            (false, true) => Self::ServerStreaming,
+            (true, false) => Self::ClientStreaming,
+            (true, true) => Self::BidiStreaming,
+        }
+    }
+
+    /// Returns `true` unless the method is unary.
+    pub const fn is_streaming(&self) -> bool {
+        !matches!(self, Self::Unary)
+    }
+
+    /// Returns `true` if the client sends a stream of messages.
+    pub const fn is_client_streaming(&self) -> bool {
+        matches!(self, Self::ClientStreaming | Self::BidiStreaming)
+    }
+
+    /// Returns `true` if the server sends a stream of messages.
+    pub const fn is_server_streaming(&self) -> bool {
+        matches!(self, Self::ServerStreaming | Self::BidiStreaming)
+    }
+}
+
+impl std::fmt::Display for MethodKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unary => write!(f, "unary"),
+            Self::ServerStreaming => write!(f, "server_streaming"),
+            Self::ClientStreaming => write!(f, "client_streaming"),
+            Self::BidiStreaming => write!(f, "bidi_streaming"),
+        }
+    }
+}
+
+/// Static description of one RPC method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MethodDescriptor {
+    /// Fully-qualified service name, e.g. `hello.HelloWorldService`.
+    pub service: &'static str,
+    /// Method name as declared in the proto file, e.g. `SayHello`.
+    pub name: &'static str,
+    /// Full procedure path, e.g. `/hello.HelloWorldService/SayHello`.
+    pub path: &'static str,
+    /// Streaming kind.
+    pub kind: MethodKind,
+    /// Declared idempotency level.
+    pub idempotency: IdempotencyLevel,
+}
+
+impl MethodDescriptor {
+    /// Find the descriptor whose [`path`](Self::path) equals `path`.
+    pub fn find<'a>(methods: &'a [MethodDescriptor], path: &str) -> Option<&'a MethodDescriptor> {
+        methods.iter().find(|method| method.path == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METHODS: &[MethodDescriptor] = &[
+        MethodDescriptor {
+            service: "hello.HelloWorldService",
+            name: "SayHello",
+            path: "/hello.HelloWorldService/SayHello",
+            kind: MethodKind::from_streaming(false, false),
+            idempotency: IdempotencyLevel::NoSideEffects,
+        },
+        MethodDescriptor {
+            service: "hello.HelloWorldService",
+            name: "Chat",
+            path: "/hello.HelloWorldService/Chat",
+            kind: MethodKind::from_streaming(true, true),
+            idempotency: IdempotencyLevel::Unknown,
+        },
+    ];
+
+    #[test]
+    fn test_method_kind_from_streaming() {
+        assert_eq!(MethodKind::from_streaming(false, false), MethodKind::Unary);
+        assert_eq!(
+            MethodKind::from_streaming(false, true),
+            MethodKind::ServerStreaming
+        );
+        assert_eq!(
+            MethodKind::from_streaming(true, false),
+            MethodKind::ClientStreaming
+        );
+        assert_eq!(
+            MethodKind::from_streaming(true, true),
+            MethodKind::BidiStreaming
+        );
+        assert!(!MethodKind::Unary.is_streaming());
+        assert!(MethodKind::BidiStreaming.is_client_streaming());
+        assert!(MethodKind::BidiStreaming.is_server_streaming());
+        assert!(!MethodKind::ClientStreaming.is_server_streaming());
+    }
+
+    #[test]
+    fn test_find_by_path() {
+        let method = MethodDescriptor::find(METHODS, "/hello.HelloWorldService/Chat").unwrap();
+        assert_eq!(method.name, "Chat");
+        assert_eq!(method.kind, MethodKind::BidiStreaming);
+
+        assert!(MethodDescriptor::find(METHODS, "/hello.HelloWorldService/Missing").is_none());
+    }
+}
@@ -0,0 +1,277 @@
+//! Runtime registry of Connect handlers.
+//!
+//! Generated service builders fix the set of RPC methods at compile time.
+//! [`DynamicServiceRegistry`] is for handlers discovered at runtime, e.g.
+//! plugins: register them under a `"pkg.Service/Method"` procedure name and
+//! mount the registry with
+//! [`MakeServiceBuilder::add_dynamic_services`](crate::MakeServiceBuilder::add_dynamic_services).
+//!
+//! The registry is mounted as a catch-all route, so handlers can be added and
+//! removed while the server is running:
+//!
+//! ```rust,ignore
+//! use connectrpc_axum::{DynamicServiceRegistry, MakeServiceBuilder};
+//!
+//! let registry = DynamicServiceRegistry::new();
+//! let app = MakeServiceBuilder::new()
+//!     .add_router(hello_router)
+//!     .add_dynamic_services(&registry)
+//!     .build();
+//!
+//! // Later, when a plugin is loaded:
+//! registry.register("plugin.v1.EchoService/Echo", echo);
+//! registry.register("plugin.v1.EchoService/EchoStream", echo_stream);
+//! ```
+//!
+//! Routes added with [`MakeServiceBuilder::add_router`](crate::MakeServiceBuilder::add_router)
+//! take precedence over registered procedures with the same path.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use axum::Router;
+use axum::extract::Request;
+use axum::handler::Handler;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{MethodRouter, any};
+use http::StatusCode;
+use tower::ServiceExt;
+
+use crate::handler::{ConnectHandlerWrapper, post_connect};
+use crate::service_builder::{method_not_allowed, unknown_procedure};
+
+/// Route pattern the registry is mounted under.
+const CATCH_ALL_ROUTE: &str = "/{*procedure}";
+
+/// Registry of Connect handlers keyed by procedure name.
+///
+/// Handlers are stored type-erased, so unary and streaming handlers with
+/// different message types share one registry.
+///
+/// Cheap to clone; all clones share the same handlers.
+#[derive(Clone, Default)]
+pub struct DynamicServiceRegistry {
+    routes: Arc<RwLock<HashMap<String, MethodRouter>>>,
+}
+
+impl std::fmt::Debug for DynamicServiceRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicServiceRegistry")
+            .field("procedures", &self.procedures())
+            .finish()
+    }
+}
+
+impl DynamicServiceRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a Connect handler (unary or streaming) under `procedure`.
+    ///
+    /// `procedure` is `"pkg.Service/Method"`; a leading `/` is accepted.
+    /// The handler takes the same shapes as with
+    /// [`post_connect`](crate::handler::post_connect). A handler already
+    /// registered under the same name is replaced.
+    pub fn register<F, T, Req, Resp>(&self, procedure: &str, handler: F)
+    where
+        ConnectHandlerWrapper<F, Req, Resp>: Handler<T, ()>,
+        T: 'static,
+    {
+        self.register_route(procedure, post_connect::<F, T, (), Req, Resp>(handler));
+    }
+
+    /// Register a prebuilt method router under `procedure`.
+    ///
+    /// Use this to serve a procedure over GET as well as POST, e.g.
+    /// `get_connect(handler.clone()).merge(post_connect(handler))`.
+    pub fn register_route(&self, procedure: &str, route: MethodRouter) {
+        let mut routes = self.routes.write().unwrap_or_else(|e| e.into_inner());
+        routes.insert(normalize(procedure).to_owned(), route);
+    }
+
+    /// Remove the handler registered under `procedure`.
+    ///
+    /// Returns `true` if a handler was registered.
+    pub fn unregister(&self, procedure: &str) -> bool {
+        let mut routes = self.routes.write().unwrap_or_else(|e| e.into_inner());
+        routes.remove(normalize(procedure)).is_some()
+    }
+
+    /// Returns `true` if a handler is registered under `procedure`.
+    pub fn contains(&self, procedure: &str) -> bool {
+        let routes = self.routes.read().unwrap_or_else(|e| e.into_inner());
+        routes.contains_key(normalize(procedure))
+    }
+
+    /// Names of all registered procedures, sorted.
+    pub fn procedures(&self) -> Vec<String> {
+        let routes = self.routes.read().unwrap_or_else(|e| e.into_inner());
+        let mut procedures: Vec<_> = routes.keys().cloned().collect();
+        procedures.sort();
+        procedures
+    }
+
+    fn route(&self, procedure: &str) -> Option<MethodRouter> {
+        let routes = self.routes.read().unwrap_or_else(|e| e.into_inner());
+        routes.get(normalize(procedure)).cloned()
+    }
+
+    /// A router with the catch-all route dispatching to registered handlers.
+    ///
+    /// With `fallback`, unknown procedures and unsupported HTTP methods get
+    /// Connect-formatted `unimplemented` errors; otherwise plain 404 and 405
+    /// responses.
+    pub(crate) fn router<S>(&self, fallback: bool) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let registry = self.clone();
+        Router::new().route(
+            CATCH_ALL_ROUTE,
+            any(move |req: Request| {
+                let registry = registry.clone();
+                async move { registry.dispatch(req, fallback).await }
+            }),
+        )
+    }
+
+    async fn dispatch(&self, req: Request, fallback: bool) -> Response {
+        let Some(route) = self.route(req.uri().path()) else {
+            if fallback {
+                return unknown_procedure(req).await;
+            }
+            return StatusCode::NOT_FOUND.into_response();
+        };
+
+        let route = if fallback {
+            route.fallback(method_not_allowed)
+        } else {
+            route
+        };
+        match route.oneshot(req).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        }
+    }
+}
+
+fn normalize(procedure: &str) -> &str {
+    procedure.strip_prefix('/').unwrap_or(procedure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MakeServiceBuilder;
+    use crate::message::error::ConnectError;
+    use crate::message::{ConnectRequest, ConnectResponse, StreamBody};
+    use axum::body::Body;
+    use axum::http::header::{ALLOW, CONTENT_TYPE};
+
+    async fn echo(
+        ConnectRequest(req): ConnectRequest<String>,
+    ) -> Result<ConnectResponse<String>, ConnectError> {
+        Ok(ConnectResponse::new(req))
+    }
+
+    fn post(path: &str) -> Request {
+        Request::post(path)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from("\"hi\""))
+            .unwrap()
+    }
+
+    async fn body_bytes(response: Response) -> axum::body::Bytes {
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_register_after_build() {
+        let registry = DynamicServiceRegistry::new();
+        let app: Router = MakeServiceBuilder::new()
+            .add_dynamic_services(&registry)
+            .build();
+
+        let response = app.clone().oneshot(post("/plugin.Echo/Say")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        registry.register("plugin.Echo/Say", echo);
+        assert!(registry.contains("/plugin.Echo/Say"));
+        assert_eq!(registry.procedures(), vec!["plugin.Echo/Say"]);
+
+        let response = app.clone().oneshot(post("/plugin.Echo/Say")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_bytes(response).await, "\"hi\"");
+
+        assert!(registry.unregister("plugin.Echo/Say"));
+        let response = app.oneshot(post("/plugin.Echo/Say")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_handler() {
+        let registry = DynamicServiceRegistry::new();
+        registry.register(
+            "/plugin.Echo/Repeat",
+            |ConnectRequest(req): ConnectRequest<String>| async move {
+                let stream = futures::stream::iter([Ok(req.clone()), Ok(req)]);
+                Ok::<_, ConnectError>(ConnectResponse::new(StreamBody::new(stream)))
+            },
+        );
+        let app: Router = MakeServiceBuilder::new()
+            .add_dynamic_services(&registry)
+            .build();
+
+        let mut frame = vec![0u8];
+        frame.extend_from_slice(&4u32.to_be_bytes());
+        frame.extend_from_slice(b"\"hi\"");
+        let request = Request::post("/plugin.Echo/Repeat")
+            .header(CONTENT_TYPE, "application/connect+json")
+            .body(Body::from(frame))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_bytes(response).await;
+        assert_eq!(body.windows(4).filter(|w| w == b"\"hi\"").count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_static_routes_take_precedence() {
+        let registry = DynamicServiceRegistry::new();
+        registry.register("plugin.Echo/Say", echo);
+        let static_router: Router = Router::new().route(
+            "/plugin.Echo/Say",
+            post_connect(|ConnectRequest(_): ConnectRequest<String>| async {
+                Ok::<_, ConnectError>(ConnectResponse::new("static".to_string()))
+            }),
+        );
+        let app: Router = MakeServiceBuilder::new()
+            .add_router(static_router)
+            .add_dynamic_services(&registry)
+            .build();
+
+        let response = app.oneshot(post("/plugin.Echo/Say")).await.unwrap();
+        assert_eq!(body_bytes(response).await, "\"static\"");
+    }
+
+    #[tokio::test]
+    async fn test_method_not_allowed() {
+        let registry = DynamicServiceRegistry::new();
+        registry.register("plugin.Echo/Say", echo);
+        let app: Router = MakeServiceBuilder::new()
+            .connect_fallback(true)
+            .add_dynamic_services(&registry)
+            .build();
+
+        let request = Request::get("/plugin.Echo/Say?encoding=json&message=%22hi%22")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(response.headers()[ALLOW], "POST");
+    }
+}
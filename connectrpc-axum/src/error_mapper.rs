@@ -0,0 +1,271 @@
+//! Conversion of application errors into Connect errors.
+//!
+//! Connect handlers may return any `std::error::Error` instead of
+//! [`ConnectError`]. The [`ErrorMapper`] configured on the server converts
+//! them, so handlers can use `?` on database or validation errors directly:
+//!
+//! ```rust,ignore
+//! use connectrpc_axum::{ConnectError, ErrorMapper, MakeServiceBuilder};
+//!
+//! async fn get_user(
+//!     State(db): State<PgPool>,
+//!     ConnectRequest(req): ConnectRequest<GetUserRequest>,
+//! ) -> Result<ConnectResponse<GetUserResponse>, sqlx::Error> {
+//!     let user = sqlx::query_as("SELECT ...").fetch_one(&db).await?;
+//!     Ok(ConnectResponse::new(user))
+//! }
+//!
+//! let mapper = ErrorMapper::new().map(|err: &sqlx::Error| match err {
+//!     sqlx::Error::RowNotFound => ConnectError::new_not_found("no such row"),
+//!     _ => ConnectError::new_unavailable("database error"),
+//! });
+//!
+//! let app = MakeServiceBuilder::new()
+//!     .error_mapper(mapper)
+//!     .add_router(router)
+//!     .build();
+//! ```
+//!
+//! Errors without a matching mapping become `internal` errors whose message
+//! is logged, not sent to the client.
+
+use std::any::Any;
+use std::error::Error;
+use std::sync::Arc;
+
+use crate::context::ConnectContext;
+use crate::message::error::{Code, ConnectError, Status, debug_info};
+
+type Mapping = Arc<dyn Fn(&(dyn Error + 'static)) -> Option<ConnectError> + Send + Sync>;
+
+/// Registry of conversions from application error types to [`ConnectError`].
+///
+/// Set it with [`ConnectLayer::error_mapper`](crate::ConnectLayer::error_mapper)
+/// or [`MakeServiceBuilder::error_mapper`](crate::MakeServiceBuilder::error_mapper).
+#[derive(Clone, Default)]
+pub struct ErrorMapper {
+    mappings: Vec<Mapping>,
+}
+
+impl std::fmt::Debug for ErrorMapper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorMapper")
+            .field("mappings", &self.mappings.len())
+            .finish()
+    }
+}
+
+impl ErrorMapper {
+    /// Create a mapper without mappings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert errors of type `E` with `map`.
+    ///
+    /// Mappings are tried in registration order.
+    pub fn map<E, M>(mut self, map: M) -> Self
+    where
+        E: Error + 'static,
+        M: Fn(&E) -> ConnectError + Send + Sync + 'static,
+    {
+        self.mappings
+            .push(Arc::new(move |err: &(dyn Error + 'static)| {
+                err.downcast_ref::<E>().map(&map)
+            }));
+        self
+    }
+
+    /// Convert `error` with the first matching mapping.
+    ///
+    /// The source chain is searched as well, so a mapping for `io::Error`
+    /// also applies to errors that wrap one. Returns `None` if no mapping
+    /// matches.
+    pub fn map_error(&self, error: &(dyn Error + 'static)) -> Option<ConnectError> {
+        let mut current = Some(error);
+        while let Some(err) = current {
+            if let Some(mapped) = self.mappings.iter().find_map(|mapping| mapping(err)) {
+                return Some(mapped);
+            }
+            current = err.source();
+        }
+        None
+    }
+}
+
+/// Error types a Connect handler can return.
+///
+/// Implemented for [`ConnectError`] and for every `std::error::Error`. A
+/// [`Status`] converts directly; other errors are converted by the request's
+/// [`ErrorMapper`].
+pub trait IntoConnectError: Send + 'static {
+    /// Convert into a [`ConnectError`] for the request described by `ctx`.
+    fn into_connect_error(self, ctx: &ConnectContext) -> ConnectError;
+}
+
+impl IntoConnectError for ConnectError {
+    fn into_connect_error(self, _ctx: &ConnectContext) -> ConnectError {
+        self
+    }
+}
+
+impl<E> IntoConnectError for E
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn into_connect_error(self, ctx: &ConnectContext) -> ConnectError {
+        let error: Box<dyn Any> = Box::new(self);
+        let error = match error.downcast::<Status>() {
+            Ok(status) => return ConnectError::from(*status),
+            Err(error) => *error
+                .downcast::<E>()
+                .expect("error has the handler's error type"),
+        };
+
+        if let Some(mapped) = ctx
+            .error_mapper
+            .as_ref()
+            .and_then(|mapper| mapper.map_error(&error))
+        {
+            return mapped;
+        }
+
+        let chain = source_chain(&error);
+        tracing::error!(target: "connectrpc_axum", error = %chain, "unmapped handler error");
+        let err = ConnectError::new(Code::Internal, "internal error");
+        if ctx.debug_errors {
+            err.add_message_detail(&debug_info(chain, false))
+        } else {
+            err
+        }
+    }
+}
+
+/// Format `error` followed by its sources, separated by `: `.
+fn source_chain(error: &(dyn Error + 'static)) -> String {
+    let mut chain = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        chain.push_str(": ");
+        chain.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::error::error_details::DebugInfo;
+
+    #[derive(Debug)]
+    struct NotFound;
+
+    impl std::fmt::Display for NotFound {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "row not found")
+        }
+    }
+
+    impl Error for NotFound {}
+
+    #[derive(Debug)]
+    struct QueryError(NotFound);
+
+    impl std::fmt::Display for QueryError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "query failed")
+        }
+    }
+
+    impl Error for QueryError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    fn ctx(mapper: Option<ErrorMapper>) -> ConnectContext {
+        ConnectContext {
+            error_mapper: mapper.map(Arc::new),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_mapped_error_and_source_chain() {
+        let mapper = ErrorMapper::new().map(|_: &NotFound| ConnectError::new_not_found("missing"));
+        let ctx = ctx(Some(mapper));
+
+        let err = NotFound.into_connect_error(&ctx);
+        assert_eq!(err.code(), Code::NotFound);
+        assert_eq!(err.message(), Some("missing"));
+
+        // Found through the source chain
+        let err = QueryError(NotFound).into_connect_error(&ctx);
+        assert_eq!(err.code(), Code::NotFound);
+    }
+
+    #[test]
+    fn test_unmapped_error_is_internal() {
+        let err = QueryError(NotFound).into_connect_error(&ctx(None));
+        assert_eq!(err.code(), Code::Internal);
+        assert_eq!(err.message(), Some("internal error"));
+        assert!(err.details().is_empty());
+
+        let ctx = ConnectContext {
+            debug_errors: true,
+            ..Default::default()
+        };
+        let err = QueryError(NotFound).into_connect_error(&ctx);
+        let info = err.find_detail::<DebugInfo>().unwrap();
+        assert_eq!(info.detail, "query failed: row not found");
+    }
+
+    #[test]
+    fn test_status_converts_directly() {
+        let status = Status::new(Code::PermissionDenied, "denied");
+        let err = status.into_connect_error(&ctx(None));
+        assert_eq!(err.code(), Code::PermissionDenied);
+        assert_eq!(err.message(), Some("denied"));
+    }
+
+    #[tokio::test]
+    async fn test_handler_returns_application_error() {
+        use crate::ConnectLayer;
+        use crate::handler::post_connect;
+        use crate::message::{ConnectRequest, ConnectResponse};
+        use axum::Router;
+        use axum::body::Body;
+        use axum::extract::Request;
+        use axum::http::header;
+        use tower::ServiceExt;
+
+        async fn lookup(
+            ConnectRequest(_): ConnectRequest<String>,
+        ) -> Result<ConnectResponse<String>, QueryError> {
+            Err(QueryError(NotFound))
+        }
+
+        let app = Router::new()
+            .route("/test.Svc/Lookup", post_connect(lookup))
+            .layer(
+                ConnectLayer::new().error_mapper(
+                    ErrorMapper::new()
+                        .map(|err: &NotFound| ConnectError::new_not_found(err.to_string())),
+                ),
+            );
+        let request = Request::post("/test.Svc/Lookup")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("\"id\""))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "not_found");
+        assert_eq!(body["message"], "row not found");
+    }
+}
@@ -8,6 +8,8 @@ use std::{any::Any, future::Future, marker::PhantomData, pin::Pin};
 
 use crate::{
     context::{ConnectContext, validate_streaming_content_type, validate_unary_content_type},
+    error_mapper::IntoConnectError,
+    message::error::{Code, debug_info},
     message::{ConnectError, ConnectRequest, ConnectResponse, StreamBody, Streaming},
 };
 use futures::Stream;
@@ -19,32 +21,74 @@ use serde::de::DeserializeOwned;
 /// If the rejection is a `ConnectError`, it's encoded using the protocol from the request.
 /// Otherwise, the rejection is returned as-is via `IntoResponse`. This allows extractors
 /// to return non-Connect responses like HTTP redirects for authentication flows.
-pub(crate) fn handle_extractor_rejection<R>(rejection: R, ctx: &ConnectContext) -> Response
+///
+/// With [`ConnectLayer::debug_errors`](crate::ConnectLayer::debug_errors), non-Connect
+/// rejections with an error status are re-encoded as Connect errors carrying a
+/// `google.rpc.DebugInfo` detail. The rejection is converted to a response before
+/// this returns, so the future is `Send` even when the rejection isn't. Callers
+/// must drop the extraction result before awaiting it.
+pub(crate) fn handle_extractor_rejection<R>(
+    rejection: R,
+    ctx: &ConnectContext,
+) -> impl Future<Output = Response> + Send
 where
     R: IntoResponse + Any,
 {
     let rejection_any: Box<dyn Any> = Box::new(rejection);
 
-    match rejection_any.downcast::<ConnectError>() {
-        Ok(connect_err) => connect_err.into_response_with_context(ctx),
+    let response = match rejection_any.downcast::<ConnectError>() {
+        Ok(connect_err) => Ok(connect_err.into_response_with_context(ctx)),
         Err(any_box) => {
-            tracing::warn!(
-                "Extractor rejection is not ConnectError, returning as-is. \
-                 If this is unintentional, consider using an extractor that returns ConnectError."
-            );
             // Downcast back to original type to call into_response
-            any_box
+            Err(any_box
                 .downcast::<R>()
                 .map(|r| r.into_response())
                 .unwrap_or_else(|_| {
                     // Shouldn't happen, but fallback to internal error
                     ConnectError::new_internal("extractor rejection")
                         .into_response_with_context(ctx)
-                })
+                }))
+        }
+    };
+    let rejection_type = std::any::type_name::<R>();
+
+    async move {
+        let response = match response {
+            Ok(response) => return response,
+            Err(response) => response,
+        };
+        let status = response.status();
+        if ctx.debug_errors && (status.is_client_error() || status.is_server_error()) {
+            return debug_rejection_response(rejection_type, response, ctx).await;
         }
+        tracing::warn!(
+            "Extractor rejection is not ConnectError, returning as-is. \
+             If this is unintentional, consider using an extractor that returns ConnectError."
+        );
+        response
     }
 }
 
+/// Upper bound on the rejection body copied into a `DebugInfo` detail.
+const DEBUG_REJECTION_BODY_LIMIT: usize = 64 * 1024;
+
+/// Re-encode a non-Connect rejection as a Connect error with a
+/// `google.rpc.DebugInfo` detail naming the rejection type and its body.
+async fn debug_rejection_response(
+    rejection_type: &'static str,
+    response: Response,
+    ctx: &ConnectContext,
+) -> Response {
+    let code = Code::from_http_status_connect(response.status());
+    let body = axum::body::to_bytes(response.into_body(), DEBUG_REJECTION_BODY_LIMIT)
+        .await
+        .unwrap_or_default();
+    let detail = format!("{rejection_type}: {}", String::from_utf8_lossy(&body));
+    ConnectError::new(code, "extractor rejection")
+        .add_message_detail(&debug_info(detail, false))
+        .into_response_with_context(ctx)
+}
+
 /// Validate protocol for unary handlers. Returns error response if invalid.
 ///
 /// Unary handlers only accept unary content-types (`application/json`, `application/proto`).
@@ -109,10 +153,12 @@ macro_rules! all_tuples_nonempty {
 // =============== 2) Handler implementations ===============
 
 // Special case implementation for zero extractors (S must be ())
-impl<F, Fut, Req, Resp> Handler<(ConnectRequest<Req>,), ()> for ConnectHandlerWrapper<F, Req, Resp>
+impl<F, Fut, E, Req, Resp> Handler<(ConnectRequest<Req>,), ()>
+    for ConnectHandlerWrapper<F, Req, Resp>
 where
     F: Fn(ConnectRequest<Req>) -> Fut + Clone + Send + Sync + 'static,
-    Fut: Future<Output = Result<ConnectResponse<Resp>, ConnectError>> + Send + 'static,
+    Fut: Future<Output = Result<ConnectResponse<Resp>, E>> + Send + 'static,
+    E: IntoConnectError,
     ConnectRequest<Req>: FromRequest<()>,
     Req: Send + Sync + 'static,
     Resp: prost::Message + serde::Serialize + Send + Clone + Sync + 'static,
@@ -146,7 +192,9 @@ where
             // Convert result to response using pipeline context
             match result {
                 Ok(response) => response.into_response_with_context(&ctx),
-                Err(err) => err.into_response_with_context(&ctx),
+                Err(err) => err
+                    .into_connect_error(&ctx)
+                    .into_response_with_context(&ctx),
             }
         })
     }
@@ -157,11 +205,12 @@ where
 macro_rules! impl_handler_for_connect_handler_wrapper {
     ([$($A:ident),*]) => {
         // Implement Handler for ConnectHandlerWrapper
-        impl<F, Fut, S, Req, Resp, $($A,)*> Handler<($($A,)* ConnectRequest<Req>,), S>
+        impl<F, Fut, E, S, Req, Resp, $($A,)*> Handler<($($A,)* ConnectRequest<Req>,), S>
             for ConnectHandlerWrapper<F, Req, Resp>
         where
             F: Fn($($A,)* ConnectRequest<Req>) -> Fut + Clone + Send + Sync + 'static,
-            Fut: Future<Output = Result<ConnectResponse<Resp>, ConnectError>> + Send + 'static,
+            Fut: Future<Output = Result<ConnectResponse<Resp>, E>> + Send + 'static,
+            E: IntoConnectError,
             S:Clone+Send+Sync+'static,
 
             // Constraints on extractors (rejection must be 'static for Any)
@@ -196,9 +245,15 @@ macro_rules! impl_handler_for_connect_handler_wrapper {
 
                     // Extract each FromRequestParts extractor
                     $(
+                        // Build the rejection future outside the match so the
+                        // extraction result isn't held across the await.
                         let $A = match $A::from_request_parts(&mut parts, &state).await {
+                            Ok(value) => Ok(value),
+                            Err(rejection) => Err(handle_extractor_rejection(rejection, &ctx)),
+                        };
+                        let $A = match $A {
                             Ok(value) => value,
-                            Err(rejection) => return handle_extractor_rejection(rejection, &ctx),
+                            Err(rejection) => return rejection.await,
                         };
                     )*
 
@@ -218,7 +273,7 @@ macro_rules! impl_handler_for_connect_handler_wrapper {
                     // Convert result to response using pipeline context
                     match result {
                         Ok(response) => response.into_response_with_context(&ctx),
-                        Err(err) => err.into_response_with_context(&ctx),
+                        Err(err) => err.into_connect_error(&ctx).into_response_with_context(&ctx),
                     }
                 })
             }
@@ -239,11 +294,12 @@ mod generated_handler_impls {
 
 macro_rules! impl_server_stream_handler_for_connect_handler_wrapper {
     ([$($A:ident),*]) => {
-        impl<F, Fut, S, Req, Resp, St, $($A,)*> Handler<($($A,)* ConnectRequest<Req>, StreamBody<St>), S>
+        impl<F, Fut, E, S, Req, Resp, St, $($A,)*> Handler<($($A,)* ConnectRequest<Req>, StreamBody<St>), S>
             for ConnectHandlerWrapper<F, Req, Resp>
         where
             F: Fn($($A,)* ConnectRequest<Req>) -> Fut + Clone + Send + Sync + 'static,
-            Fut: Future<Output = Result<ConnectResponse<StreamBody<St>>, ConnectError>> + Send + 'static,
+            Fut: Future<Output = Result<ConnectResponse<StreamBody<St>>, E>> + Send + 'static,
+            E: IntoConnectError,
             St: Stream<Item = Result<Resp, ConnectError>> + Send + 'static,
             S: Clone + Send + Sync + 'static,
 
@@ -274,9 +330,15 @@ macro_rules! impl_server_stream_handler_for_connect_handler_wrapper {
                     let (mut parts, body) = req.into_parts();
 
                     $(
+                        // Build the rejection future outside the match so the
+                        // extraction result isn't held across the await.
                         let $A = match $A::from_request_parts(&mut parts, &state).await {
+                            Ok(value) => Ok(value),
+                            Err(rejection) => Err(handle_extractor_rejection(rejection, &ctx)),
+                        };
+                        let $A = match $A {
                             Ok(value) => value,
-                            Err(rejection) => return handle_extractor_rejection(rejection, &ctx),
+                            Err(rejection) => return rejection.await,
                         };
                     )*
 
@@ -291,7 +353,7 @@ macro_rules! impl_server_stream_handler_for_connect_handler_wrapper {
 
                     match result {
                         Ok(response) => response.into_response_with_context(&ctx),
-                        Err(err) => err.into_response_with_context(&ctx),
+                        Err(err) => err.into_connect_error(&ctx).into_response_with_context(&ctx),
                     }
                 })
             }
@@ -310,11 +372,12 @@ mod generated_server_stream_handler_impls {
 
 macro_rules! impl_client_stream_handler_for_connect_handler_wrapper {
     ([$($A:ident),*]) => {
-        impl<F, Fut, S, Req, Resp, $($A,)*> Handler<($($A,)* ConnectRequest<Streaming<Req>>, Resp), S>
+        impl<F, Fut, E, S, Req, Resp, $($A,)*> Handler<($($A,)* ConnectRequest<Streaming<Req>>, Resp), S>
             for ConnectHandlerWrapper<F, Req, Resp>
         where
             F: Fn($($A,)* ConnectRequest<Streaming<Req>>) -> Fut + Clone + Send + Sync + 'static,
-            Fut: Future<Output = Result<ConnectResponse<Resp>, ConnectError>> + Send + 'static,
+            Fut: Future<Output = Result<ConnectResponse<Resp>, E>> + Send + 'static,
+            E: IntoConnectError,
             S: Clone + Send + Sync + 'static,
 
             // Constraints on extractors
@@ -344,9 +407,15 @@ macro_rules! impl_client_stream_handler_for_connect_handler_wrapper {
                     let (mut parts, body) = req.into_parts();
 
                     $(
+                        // Build the rejection future outside the match so the
+                        // extraction result isn't held across the await.
                         let $A = match $A::from_request_parts(&mut parts, &state).await {
+                            Ok(value) => Ok(value),
+                            Err(rejection) => Err(handle_extractor_rejection(rejection, &ctx)),
+                        };
+                        let $A = match $A {
                             Ok(value) => value,
-                            Err(rejection) => return handle_extractor_rejection(rejection, &ctx),
+                            Err(rejection) => return rejection.await,
                         };
                     )*
 
@@ -362,7 +431,7 @@ macro_rules! impl_client_stream_handler_for_connect_handler_wrapper {
 
                     match result {
                         Ok(response) => response.into_streaming_response_with_context(&ctx),
-                        Err(err) => err.into_response_with_context(&ctx),
+                        Err(err) => err.into_connect_error(&ctx).into_response_with_context(&ctx),
                     }
                 })
             }
@@ -381,11 +450,12 @@ mod generated_client_stream_handler_impls {
 
 macro_rules! impl_bidi_stream_handler_for_connect_handler_wrapper {
     ([$($A:ident),*]) => {
-        impl<F, Fut, S, Req, Resp, St, $($A,)*> Handler<($($A,)* ConnectRequest<Streaming<Req>>, StreamBody<St>), S>
+        impl<F, Fut, E, S, Req, Resp, St, $($A,)*> Handler<($($A,)* ConnectRequest<Streaming<Req>>, StreamBody<St>), S>
             for ConnectHandlerWrapper<F, Req, Resp>
         where
             F: Fn($($A,)* ConnectRequest<Streaming<Req>>) -> Fut + Clone + Send + Sync + 'static,
-            Fut: Future<Output = Result<ConnectResponse<StreamBody<St>>, ConnectError>> + Send + 'static,
+            Fut: Future<Output = Result<ConnectResponse<StreamBody<St>>, E>> + Send + 'static,
+            E: IntoConnectError,
             St: Stream<Item = Result<Resp, ConnectError>> + Send + 'static,
             S: Clone + Send + Sync + 'static,
 
@@ -416,9 +486,15 @@ macro_rules! impl_bidi_stream_handler_for_connect_handler_wrapper {
                     let (mut parts, body) = req.into_parts();
 
                     $(
+                        // Build the rejection future outside the match so the
+                        // extraction result isn't held across the await.
                         let $A = match $A::from_request_parts(&mut parts, &state).await {
+                            Ok(value) => Ok(value),
+                            Err(rejection) => Err(handle_extractor_rejection(rejection, &ctx)),
+                        };
+                        let $A = match $A {
                             Ok(value) => value,
-                            Err(rejection) => return handle_extractor_rejection(rejection, &ctx),
+                            Err(rejection) => return rejection.await,
                         };
                     )*
 
@@ -434,7 +510,7 @@ macro_rules! impl_bidi_stream_handler_for_connect_handler_wrapper {
 
                     match result {
                         Ok(response) => response.into_response_with_context(&ctx),
-                        Err(err) => err.into_response_with_context(&ctx),
+                        Err(err) => err.into_connect_error(&ctx).into_response_with_context(&ctx),
                     }
                 })
             }
@@ -453,11 +529,12 @@ mod generated_bidi_stream_handler_impls {
 
 /// Handler implementation for server streaming using the unified ConnectHandlerWrapper.
 /// Input: single message, Output: stream of messages
-impl<F, Fut, Req, Resp, St> Handler<(ConnectRequest<Req>, StreamBody<St>), ()>
+impl<F, Fut, E, Req, Resp, St> Handler<(ConnectRequest<Req>, StreamBody<St>), ()>
     for ConnectHandlerWrapper<F, Req, Resp>
 where
     F: Fn(ConnectRequest<Req>) -> Fut + Clone + Send + Sync + 'static,
-    Fut: Future<Output = Result<ConnectResponse<StreamBody<St>>, ConnectError>> + Send + 'static,
+    Fut: Future<Output = Result<ConnectResponse<StreamBody<St>>, E>> + Send + 'static,
+    E: IntoConnectError,
     St: Stream<Item = Result<Resp, ConnectError>> + Send + 'static,
     // Req must be a Message (not Streaming<T>) to distinguish from bidi streaming
     Req: Message + DeserializeOwned + Default + Send + Sync + 'static,
@@ -487,7 +564,9 @@ where
 
             match result {
                 Ok(response) => response.into_response_with_context(&ctx),
-                Err(err) => err.into_response_with_context(&ctx),
+                Err(err) => err
+                    .into_connect_error(&ctx)
+                    .into_response_with_context(&ctx),
             }
         })
     }
@@ -498,11 +577,12 @@ where
 
 /// Handler implementation for client streaming using the unified ConnectHandlerWrapper.
 /// Input: stream of messages, Output: single message
-impl<F, Fut, Req, Resp> Handler<(ConnectRequest<Streaming<Req>>, Resp), ()>
+impl<F, Fut, E, Req, Resp> Handler<(ConnectRequest<Streaming<Req>>, Resp), ()>
     for ConnectHandlerWrapper<F, Req, Resp>
 where
     F: Fn(ConnectRequest<Streaming<Req>>) -> Fut + Clone + Send + Sync + 'static,
-    Fut: Future<Output = Result<ConnectResponse<Resp>, ConnectError>> + Send + 'static,
+    Fut: Future<Output = Result<ConnectResponse<Resp>, E>> + Send + 'static,
+    E: IntoConnectError,
     Req: Message + DeserializeOwned + Default + Send + 'static,
     Resp: Message + serde::Serialize + Send + Clone + Sync + 'static,
 {
@@ -532,7 +612,9 @@ where
             // Client streaming uses streaming framing for the response
             match result {
                 Ok(response) => response.into_streaming_response_with_context(&ctx),
-                Err(err) => err.into_response_with_context(&ctx),
+                Err(err) => err
+                    .into_connect_error(&ctx)
+                    .into_response_with_context(&ctx),
             }
         })
     }
@@ -544,11 +626,12 @@ where
 /// Handler implementation for bidirectional streaming using the unified ConnectHandlerWrapper.
 /// Input: stream of messages, Output: stream of messages
 /// Note: Requires HTTP/2 for full-duplex communication.
-impl<F, Fut, Req, Resp, St> Handler<(ConnectRequest<Streaming<Req>>, StreamBody<St>), ()>
+impl<F, Fut, E, Req, Resp, St> Handler<(ConnectRequest<Streaming<Req>>, StreamBody<St>), ()>
     for ConnectHandlerWrapper<F, Req, Resp>
 where
     F: Fn(ConnectRequest<Streaming<Req>>) -> Fut + Clone + Send + Sync + 'static,
-    Fut: Future<Output = Result<ConnectResponse<StreamBody<St>>, ConnectError>> + Send + 'static,
+    Fut: Future<Output = Result<ConnectResponse<StreamBody<St>>, E>> + Send + 'static,
+    E: IntoConnectError,
     St: Stream<Item = Result<Resp, ConnectError>> + Send + 'static,
     Req: Message + DeserializeOwned + Default + Send + 'static,
     Resp: Message + serde::Serialize + Send + Sync + 'static,
@@ -578,7 +661,9 @@ where
 
             match result {
                 Ok(response) => response.into_response_with_context(&ctx),
-                Err(err) => err.into_response_with_context(&ctx),
+                Err(err) => err
+                    .into_connect_error(&ctx)
+                    .into_response_with_context(&ctx),
             }
         })
     }
@@ -0,0 +1,494 @@
+//! gRPC health checking service (`grpc.health.v1.Health`).
+//!
+//! Implements the [gRPC health checking protocol] over both Connect and gRPC,
+//! so Kubernetes gRPC probes, `grpc_health_probe` and connect-go health
+//! clients work without generating code for `health.proto`.
+//!
+//! - [`HealthReporter`]: Cloneable handle for setting per-service status
+//! - [`HealthService`]: Serves `Check` (unary) and `Watch` (server streaming)
+//!
+//! The overall server health is reported under the empty service name `""`
+//! and starts as [`ServingStatus::Serving`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use connectrpc_axum::health::HealthService;
+//! use connectrpc_axum::MakeServiceBuilder;
+//!
+//! let health = HealthService::new();
+//! let reporter = health.reporter();
+//! reporter.set_serving("hello.HelloWorldService");
+//!
+//! let app = MakeServiceBuilder::new()
+//!     .add_router(hello_router)
+//!     .add_health_service(&health)
+//!     // With the `tonic` feature, also answer gRPC health checks:
+//!     .add_grpc_service(health.grpc_service())
+//!     .build();
+//!
+//! // Later, e.g. while draining:
+//! reporter.set_not_serving("");
+//! ```
+//!
+//! [gRPC health checking protocol]: https://github.com/grpc/grpc/blob/master/doc/health-checking.md
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use axum::Router;
+use futures::Stream;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tokio::sync::watch;
+
+use crate::handler::post_connect;
+use crate::message::error::{Code, ConnectError};
+use crate::message::{ConnectRequest, ConnectResponse, StreamBody};
+
+/// Fully-qualified name of the health service.
+pub const SERVICE_NAME: &str = "grpc.health.v1.Health";
+
+/// Procedure path of the `Check` RPC.
+pub const CHECK_PROCEDURE: &str = "/grpc.health.v1.Health/Check";
+
+/// Procedure path of the `Watch` RPC.
+pub const WATCH_PROCEDURE: &str = "/grpc.health.v1.Health/Watch";
+
+// ============================================================================
+// Messages
+// ============================================================================
+
+/// `grpc.health.v1.HealthCheckRequest`.
+#[derive(Clone, PartialEq, ::prost::Message, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthCheckRequest {
+    /// The service to check; empty for the overall server health.
+    #[prost(string, tag = "1")]
+    pub service: String,
+}
+
+/// `grpc.health.v1.HealthCheckResponse`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HealthCheckResponse {
+    /// The serving status, as a [`ServingStatus`] value.
+    #[prost(enumeration = "ServingStatus", tag = "1")]
+    pub status: i32,
+}
+
+/// `grpc.health.v1.HealthCheckResponse.ServingStatus`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ServingStatus {
+    /// Status is not known.
+    Unknown = 0,
+    /// The service is serving requests.
+    Serving = 1,
+    /// The service is not serving requests.
+    NotServing = 2,
+    /// The requested service is not registered (used only by `Watch`).
+    ServiceUnknown = 3,
+}
+
+impl ServingStatus {
+    /// The protobuf enum value name, as used in JSON.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ServingStatus::Unknown => "UNKNOWN",
+            ServingStatus::Serving => "SERVING",
+            ServingStatus::NotServing => "NOT_SERVING",
+            ServingStatus::ServiceUnknown => "SERVICE_UNKNOWN",
+        }
+    }
+
+    /// Parse a protobuf enum value name.
+    pub fn from_str_name(value: &str) -> Option<Self> {
+        match value {
+            "UNKNOWN" => Some(ServingStatus::Unknown),
+            "SERVING" => Some(ServingStatus::Serving),
+            "NOT_SERVING" => Some(ServingStatus::NotServing),
+            "SERVICE_UNKNOWN" => Some(ServingStatus::ServiceUnknown),
+            _ => None,
+        }
+    }
+}
+
+impl From<ServingStatus> for HealthCheckResponse {
+    fn from(status: ServingStatus) -> Self {
+        Self {
+            status: status as i32,
+        }
+    }
+}
+
+// Proto3 JSON encodes enums by name: `{"status":"SERVING"}`.
+impl Serialize for HealthCheckResponse {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("HealthCheckResponse", 1)?;
+        match ServingStatus::try_from(self.status) {
+            Ok(status) => s.serialize_field("status", status.as_str_name())?,
+            Err(_) => s.serialize_field("status", &self.status)?,
+        }
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for HealthCheckResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Status {
+            Name(String),
+            Number(i32),
+        }
+
+        #[derive(Deserialize)]
+        struct Raw {
+            status: Option<Status>,
+        }
+
+        let status = match Raw::deserialize(deserializer)?.status {
+            None => 0,
+            Some(Status::Number(n)) => n,
+            Some(Status::Name(name)) => ServingStatus::from_str_name(&name)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown status {name:?}")))?
+                as i32,
+        };
+        Ok(Self { status })
+    }
+}
+
+// ============================================================================
+// HealthReporter
+// ============================================================================
+
+/// Handle for updating the health status reported by a [`HealthService`].
+///
+/// Cheap to clone; all clones share the same state.
+#[derive(Clone, Debug)]
+pub struct HealthReporter {
+    statuses: Arc<RwLock<HashMap<String, watch::Sender<ServingStatus>>>>,
+}
+
+impl Default for HealthReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthReporter {
+    /// Create a reporter with the overall server status (`""`) set to `SERVING`.
+    pub fn new() -> Self {
+        let reporter = Self {
+            statuses: Arc::default(),
+        };
+        reporter.set_serving("");
+        reporter
+    }
+
+    /// Mark a service as `SERVING`.
+    pub fn set_serving(&self, service: &str) {
+        self.set_service_status(service, ServingStatus::Serving);
+    }
+
+    /// Mark a service as `NOT_SERVING`.
+    pub fn set_not_serving(&self, service: &str) {
+        self.set_service_status(service, ServingStatus::NotServing);
+    }
+
+    /// Set the status of a service, notifying any `Watch` streams.
+    pub fn set_service_status(&self, service: &str, status: ServingStatus) {
+        let mut statuses = self.statuses.write().unwrap_or_else(|e| e.into_inner());
+        match statuses.get(service) {
+            Some(sender) => {
+                sender.send_replace(status);
+            }
+            None => {
+                statuses.insert(service.to_owned(), watch::channel(status).0);
+            }
+        }
+    }
+
+    /// Forget a service; `Check` reports it as not found afterwards.
+    pub fn clear_service_status(&self, service: &str) {
+        self.set_service_status(service, ServingStatus::ServiceUnknown);
+    }
+
+    /// Current status of a service, if it has been registered.
+    pub fn service_status(&self, service: &str) -> Option<ServingStatus> {
+        let statuses = self.statuses.read().unwrap_or_else(|e| e.into_inner());
+        statuses
+            .get(service)
+            .map(|sender| *sender.borrow())
+            .filter(|status| *status != ServingStatus::ServiceUnknown)
+    }
+
+    fn check(&self, service: &str) -> Result<HealthCheckResponse, ConnectError> {
+        self.service_status(service)
+            .map(HealthCheckResponse::from)
+            .ok_or_else(|| ConnectError::new(Code::NotFound, "unknown service"))
+    }
+
+    fn watch(&self, service: &str) -> impl Stream<Item = HealthCheckResponse> + Send + use<> {
+        let mut receiver = {
+            let mut statuses = self.statuses.write().unwrap_or_else(|e| e.into_inner());
+            statuses
+                .entry(service.to_owned())
+                .or_insert_with(|| watch::channel(ServingStatus::ServiceUnknown).0)
+                .subscribe()
+        };
+
+        async_stream::stream! {
+            let status = *receiver.borrow_and_update();
+            yield HealthCheckResponse::from(status);
+            while receiver.changed().await.is_ok() {
+                let status = *receiver.borrow_and_update();
+                yield HealthCheckResponse::from(status);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// HealthService
+// ============================================================================
+
+/// The `grpc.health.v1.Health` service.
+///
+/// Serve it over Connect with [`router`](Self::router) (or
+/// [`MakeServiceBuilder::add_health_service`](crate::MakeServiceBuilder::add_health_service)),
+/// and over gRPC with `grpc_service` when the `tonic` feature is enabled.
+#[derive(Clone, Debug, Default)]
+pub struct HealthService {
+    reporter: HealthReporter,
+}
+
+impl HealthService {
+    /// Create a health service with a fresh [`HealthReporter`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a health service backed by an existing reporter.
+    pub fn with_reporter(reporter: HealthReporter) -> Self {
+        Self { reporter }
+    }
+
+    /// The reporter used to update this service's statuses.
+    pub fn reporter(&self) -> HealthReporter {
+        self.reporter.clone()
+    }
+
+    /// Connect routes for `Check` and `Watch`.
+    pub fn router<S>(&self) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let check_reporter = self.reporter.clone();
+        let watch_reporter = self.reporter.clone();
+
+        Router::new()
+            .route(
+                CHECK_PROCEDURE,
+                post_connect(
+                    move |ConnectRequest(req): ConnectRequest<HealthCheckRequest>| {
+                        let reporter = check_reporter.clone();
+                        async move { reporter.check(&req.service).map(ConnectResponse::new) }
+                    },
+                ),
+            )
+            .route(
+                WATCH_PROCEDURE,
+                post_connect(
+                    move |ConnectRequest(req): ConnectRequest<HealthCheckRequest>| {
+                        let reporter = watch_reporter.clone();
+                        async move {
+                            use futures::StreamExt;
+
+                            let stream = reporter.watch(&req.service).map(Ok::<_, ConnectError>);
+                            Ok::<_, ConnectError>(ConnectResponse::new(StreamBody::new(stream)))
+                        }
+                    },
+                ),
+            )
+            .with_state(())
+    }
+
+    /// gRPC service for `Check` and `Watch`, for use with
+    /// [`MakeServiceBuilder::add_grpc_service`](crate::MakeServiceBuilder::add_grpc_service).
+    #[cfg(feature = "tonic")]
+    pub fn grpc_service(&self) -> HealthGrpcService {
+        HealthGrpcService {
+            reporter: self.reporter.clone(),
+        }
+    }
+}
+
+/// gRPC transport for [`HealthService`].
+#[cfg(feature = "tonic")]
+#[derive(Clone, Debug)]
+pub struct HealthGrpcService {
+    reporter: HealthReporter,
+}
+
+#[cfg(feature = "tonic")]
+impl tonic::server::NamedService for HealthGrpcService {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+#[cfg(feature = "tonic")]
+impl<B> tower::Service<http::Request<B>> for HealthGrpcService
+where
+    B: http_body::Body + Send + 'static,
+    B::Error: Into<tonic::codegen::StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = std::convert::Infallible;
+    type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        use futures::StreamExt;
+        use tonic::server::Grpc;
+        use tonic_prost::ProstCodec;
+
+        let reporter = self.reporter.clone();
+        match req.uri().path() {
+            CHECK_PROCEDURE => Box::pin(async move {
+                let check = tower::service_fn(move |req: tonic::Request<HealthCheckRequest>| {
+                    let result = reporter
+                        .check(&req.into_inner().service)
+                        .map(tonic::Response::new)
+                        .map_err(tonic::Status::from);
+                    std::future::ready(result)
+                });
+                Ok(Grpc::new(ProstCodec::default()).unary(check, req).await)
+            }),
+            WATCH_PROCEDURE => Box::pin(async move {
+                let watch = tower::service_fn(move |req: tonic::Request<HealthCheckRequest>| {
+                    let stream = reporter
+                        .watch(&req.into_inner().service)
+                        .map(Ok::<_, tonic::Status>)
+                        .boxed();
+                    std::future::ready(Ok::<_, tonic::Status>(tonic::Response::new(stream)))
+                });
+                Ok(Grpc::new(ProstCodec::default())
+                    .server_streaming(watch, req)
+                    .await)
+            }),
+            _ => Box::pin(
+                async move { Ok(tonic::Status::unimplemented("unknown method").into_http()) },
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use futures::StreamExt;
+    use tower::ServiceExt;
+
+    fn check_request(service: &str) -> Request<Body> {
+        Request::post(CHECK_PROCEDURE)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(format!(r#"{{"service":"{service}"}}"#)))
+            .unwrap()
+    }
+
+    async fn json_body(response: axum::response::Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_check_over_connect() {
+        let health = HealthService::new();
+        health.reporter().set_not_serving("svc.A");
+        let app: Router = health.router();
+
+        let response = app.clone().oneshot(check_request("")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(json_body(response).await["status"], "SERVING");
+
+        let response = app.clone().oneshot(check_request("svc.A")).await.unwrap();
+        assert_eq!(json_body(response).await["status"], "NOT_SERVING");
+
+        let response = app.oneshot(check_request("svc.Missing")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(json_body(response).await["code"], "not_found");
+    }
+
+    #[tokio::test]
+    async fn test_watch_reports_changes() {
+        let reporter = HealthReporter::new();
+        let mut stream = Box::pin(reporter.watch("svc.B"));
+
+        let first = stream.next().await.unwrap();
+        assert_eq!(first.status(), ServingStatus::ServiceUnknown);
+
+        reporter.set_serving("svc.B");
+        assert_eq!(
+            stream.next().await.unwrap().status(),
+            ServingStatus::Serving
+        );
+
+        reporter.clear_service_status("svc.B");
+        assert_eq!(
+            stream.next().await.unwrap().status(),
+            ServingStatus::ServiceUnknown
+        );
+        assert_eq!(reporter.service_status("svc.B"), None);
+    }
+
+    #[test]
+    fn test_response_json() {
+        let response = HealthCheckResponse::from(ServingStatus::NotServing);
+        let json = serde_json::to_string(&response).unwrap();
+        assert_eq!(json, r#"{"status":"NOT_SERVING"}"#);
+
+        let decoded: HealthCheckResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, response);
+        let decoded: HealthCheckResponse = serde_json::from_str(r#"{"status":1}"#).unwrap();
+        assert_eq!(decoded.status(), ServingStatus::Serving);
+    }
+
+    #[cfg(feature = "tonic")]
+    #[tokio::test]
+    async fn test_check_over_grpc() {
+        use prost::Message;
+
+        let health = HealthService::new();
+        let payload = HealthCheckRequest::default().encode_to_vec();
+        let mut frame = vec![0u8];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+
+        let req = Request::post(CHECK_PROCEDURE)
+            .header(header::CONTENT_TYPE, "application/grpc")
+            .body(Body::from(frame))
+            .unwrap();
+        let response = health.grpc_service().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let collected = http_body_util::BodyExt::collect(response.into_body())
+            .await
+            .unwrap();
+        let trailers = collected.trailers().cloned().unwrap_or_default();
+        let bytes = collected.to_bytes();
+        let decoded = HealthCheckResponse::decode(&bytes[5..]).unwrap();
+        assert_eq!(decoded.status(), ServingStatus::Serving);
+        assert_eq!(trailers["grpc-status"], "0");
+    }
+}
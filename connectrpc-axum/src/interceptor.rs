@@ -0,0 +1,1196 @@
+//! Server-side interceptors for Connect RPC.
+//!
+//! Two user-facing traits, mirroring the client crate:
+//! - [`Interceptor`]: Header-level access only (auth, logging, tracing IDs)
+//! - [`MessageInterceptor`]: Access to decoded request and response messages
+//!
+//! [`TypedMessageInterceptor`] is a variant of [`MessageInterceptor`] for one
+//! request/response pair, registered through [`TypedWrapper`].
+//!
+//! Both are wrapped into the unified [`InterceptorInternal`] trait and composed
+//! with [`Chain`]. Register them on [`ConnectLayer`] or [`MakeServiceBuilder`] and
+//! they run for every Connect route behind that layer, generated or hand-written.
+//!
+//! Interceptors run for Connect protocol requests. gRPC requests routed to tonic
+//! services bypass them; use tonic interceptors there.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use connectrpc_axum::interceptor::{AnyMessage, MessageContext, RequestContext};
+//! use connectrpc_axum::{ConnectError, Interceptor, MakeServiceBuilder, MessageInterceptor};
+//!
+//! // Header-only interceptor
+//! #[derive(Clone)]
+//! struct AuthInterceptor;
+//!
+//! impl Interceptor for AuthInterceptor {
+//!     fn on_request(&self, ctx: &mut RequestContext) -> Result<(), ConnectError> {
+//!         match ctx.headers.get("authorization") {
+//!             Some(_) => Ok(()),
+//!             None => Err(ConnectError::new_unauthenticated("missing token")),
+//!         }
+//!     }
+//! }
+//!
+//! // Message interceptor - downcast to validate a specific request type
+//! #[derive(Clone)]
+//! struct ValidateInterceptor;
+//!
+//! impl MessageInterceptor for ValidateInterceptor {
+//!     fn on_request(
+//!         &self,
+//!         _ctx: &MessageContext,
+//!         request: &mut dyn AnyMessage,
+//!     ) -> Result<(), ConnectError> {
+//!         if let Some(req) = request.downcast_ref::<HelloRequest>() {
+//!             if req.name.is_empty() {
+//!                 return Err(ConnectError::new_invalid_argument("name is required"));
+//!             }
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let app = MakeServiceBuilder::new()
+//!     .with_interceptor(AuthInterceptor)
+//!     .with_message_interceptor(ValidateInterceptor)
+//!     .add_router(router)
+//!     .build();
+//! ```
+//!
+//! [`ConnectLayer`]: crate::layer::ConnectLayer
+//! [`MakeServiceBuilder`]: crate::service_builder::MakeServiceBuilder
+
+use std::any::Any;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use axum::http::{HeaderMap, StatusCode};
+use prost::Message;
+
+use crate::context::{ContextValues, PeerInfo, RequestProtocol};
+use crate::message::error::ConnectError;
+
+// ============================================================================
+// Context Types
+// ============================================================================
+
+/// Context for intercepting an incoming request before it reaches the handler.
+///
+/// Provides access to the procedure path and mutable request headers.
+#[derive(Debug)]
+pub struct RequestContext<'a> {
+    /// The procedure being called (e.g., "/package.Service/Method").
+    pub procedure: &'a str,
+    /// Protocol variant of the request.
+    pub protocol: RequestProtocol,
+    /// Remote address, HTTP version and user agent of the caller.
+    pub peer: &'a PeerInfo,
+    /// HTTP headers of the request (mutable).
+    pub headers: &'a mut HeaderMap,
+    /// Request-scoped values (mutable); values added here reach message
+    /// interceptors and handlers.
+    pub values: &'a mut ContextValues,
+}
+
+/// Context for intercepting an outgoing response.
+///
+/// Provides access to the procedure path, response status and mutable
+/// response headers.
+#[derive(Debug)]
+pub struct ResponseContext<'a> {
+    /// The procedure being called (e.g., "/package.Service/Method").
+    pub procedure: &'a str,
+    /// Protocol variant of the request.
+    pub protocol: RequestProtocol,
+    /// Remote address, HTTP version and user agent of the caller.
+    pub peer: &'a PeerInfo,
+    /// HTTP status of the response.
+    pub status: StatusCode,
+    /// HTTP headers of the response (mutable).
+    pub headers: &'a mut HeaderMap,
+    /// Request-scoped values.
+    pub values: &'a ContextValues,
+}
+
+/// Context for intercepting individual request and response messages.
+#[derive(Debug, Clone, Copy)]
+pub struct MessageContext<'a> {
+    /// The procedure being called (e.g., "/package.Service/Method").
+    pub procedure: &'a str,
+    /// Protocol variant of the request.
+    pub protocol: RequestProtocol,
+    /// Remote address, HTTP version and user agent of the caller.
+    pub peer: &'a PeerInfo,
+    /// Request-scoped values.
+    pub values: &'a ContextValues,
+}
+
+// ============================================================================
+// Type-erased messages
+// ============================================================================
+
+/// A type-erased protobuf message passed to [`MessageInterceptor`] hooks.
+///
+/// Server interceptors are registered once on the layer and run for every
+/// route, so messages are passed as trait objects. Use
+/// [`downcast_ref`](trait.AnyMessage.html#method.downcast_ref) or
+/// [`downcast_mut`](trait.AnyMessage.html#method.downcast_mut) to get at a
+/// concrete message type.
+pub trait AnyMessage: Any + Send + Sync {
+    /// The fully qualified Rust type name of the message.
+    fn type_name(&self) -> &'static str;
+
+    /// The encoded protobuf length of the message, in bytes.
+    fn encoded_size(&self) -> usize;
+
+    /// Upcast to `&dyn Any`.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Upcast to `&mut dyn Any`.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Message + 'static> AnyMessage for T {
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn encoded_size(&self) -> usize {
+        self.encoded_len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl dyn AnyMessage {
+    /// Returns `true` if the message is of type `T`.
+    pub fn is<T: AnyMessage>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    /// Returns a reference to the message if it is of type `T`.
+    pub fn downcast_ref<T: AnyMessage>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+
+    /// Returns a mutable reference to the message if it is of type `T`.
+    pub fn downcast_mut<T: AnyMessage>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut::<T>()
+    }
+}
+
+// ============================================================================
+// User-Facing Traits
+// ============================================================================
+
+/// Header-level interceptor - no message access.
+///
+/// Use this for:
+/// - Authenticating requests from headers
+/// - Adding trace/correlation IDs to responses
+/// - Logging procedure names and status codes
+///
+/// Returning an error from [`on_request`](Interceptor::on_request) rejects the
+/// call before the handler runs; the error is encoded for the request's protocol.
+pub trait Interceptor: Send + Sync + 'static {
+    /// Called before the handler runs.
+    ///
+    /// Can modify request headers or return an error to reject the call.
+    fn on_request(&self, ctx: &mut RequestContext) -> Result<(), ConnectError> {
+        let _ = ctx;
+        Ok(())
+    }
+
+    /// Called after the handler produced a response.
+    ///
+    /// Can modify response headers or return an error to replace the response.
+    fn on_response(&self, ctx: &mut ResponseContext) -> Result<(), ConnectError> {
+        let _ = ctx;
+        Ok(())
+    }
+}
+
+/// Message-level interceptor - access to decoded messages.
+///
+/// Use this for:
+/// - Validating request fields before the handler runs
+/// - Redacting or transforming response messages
+/// - Per-message logic for streaming RPCs
+///
+/// Returning an error from a unary hook fails the call with that error. For
+/// streaming hooks, the error ends the stream: a receive error is yielded to
+/// the handler's `Streaming`, and a send error is written as the EndStream
+/// error.
+pub trait MessageInterceptor: Send + Sync + 'static {
+    /// Called after a unary request message is decoded, before the handler runs.
+    fn on_request(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        let _ = (ctx, request);
+        Ok(())
+    }
+
+    /// Called before a unary response message is encoded.
+    fn on_response(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        let _ = (ctx, response);
+        Ok(())
+    }
+
+    /// Called before a message is sent on a response stream.
+    fn on_stream_send(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        let _ = (ctx, response);
+        Ok(())
+    }
+
+    /// Called after a message is received from a request stream.
+    fn on_stream_receive(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        let _ = (ctx, request);
+        Ok(())
+    }
+}
+
+/// Message interceptor for a single request/response message pair.
+///
+/// Unlike [`MessageInterceptor`], hooks receive the concrete message types.
+/// Register it through [`TypedWrapper`], which skips messages of other types,
+/// so the interceptor only runs for the methods it was written for:
+///
+/// ```ignore
+/// struct RedactEmail;
+///
+/// impl TypedMessageInterceptor<GetUserRequest, GetUserResponse> for RedactEmail {
+///     fn on_response(
+///         &self,
+///         _ctx: &MessageContext,
+///         response: &mut GetUserResponse,
+///     ) -> Result<(), ConnectError> {
+///         response.email.clear();
+///         Ok(())
+///     }
+/// }
+///
+/// MakeServiceBuilder::new().with_message_interceptor(TypedWrapper::new(RedactEmail))
+/// ```
+pub trait TypedMessageInterceptor<Req, Resp>: Send + Sync + 'static {
+    /// Called after a unary request message is decoded, before the handler runs.
+    fn on_request(&self, ctx: &MessageContext, request: &mut Req) -> Result<(), ConnectError> {
+        let _ = (ctx, request);
+        Ok(())
+    }
+
+    /// Called before a unary response message is encoded.
+    fn on_response(&self, ctx: &MessageContext, response: &mut Resp) -> Result<(), ConnectError> {
+        let _ = (ctx, response);
+        Ok(())
+    }
+
+    /// Called before a message is sent on a response stream.
+    fn on_stream_send(
+        &self,
+        ctx: &MessageContext,
+        response: &mut Resp,
+    ) -> Result<(), ConnectError> {
+        let _ = (ctx, response);
+        Ok(())
+    }
+
+    /// Called after a message is received from a request stream.
+    fn on_stream_receive(
+        &self,
+        ctx: &MessageContext,
+        request: &mut Req,
+    ) -> Result<(), ConnectError> {
+        let _ = (ctx, request);
+        Ok(())
+    }
+}
+
+/// Adapts a [`TypedMessageInterceptor`] to [`MessageInterceptor`].
+///
+/// Requests that aren't `Req` and responses that aren't `Resp` pass through
+/// untouched.
+pub struct TypedWrapper<I, Req, Resp> {
+    inner: I,
+    _marker: PhantomData<fn() -> (Req, Resp)>,
+}
+
+impl<I, Req, Resp> TypedWrapper<I, Req, Resp>
+where
+    I: TypedMessageInterceptor<Req, Resp>,
+{
+    /// Wrap a typed interceptor.
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: Clone, Req, Resp> Clone for TypedWrapper<I, Req, Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: fmt::Debug, Req, Resp> fmt::Debug for TypedWrapper<I, Req, Resp> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedWrapper").field(&self.inner).finish()
+    }
+}
+
+impl<I, Req, Resp> MessageInterceptor for TypedWrapper<I, Req, Resp>
+where
+    I: TypedMessageInterceptor<Req, Resp>,
+    Req: AnyMessage,
+    Resp: AnyMessage,
+{
+    fn on_request(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        match request.downcast_mut::<Req>() {
+            Some(request) => self.inner.on_request(ctx, request),
+            None => Ok(()),
+        }
+    }
+
+    fn on_response(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        match response.downcast_mut::<Resp>() {
+            Some(response) => self.inner.on_response(ctx, response),
+            None => Ok(()),
+        }
+    }
+
+    fn on_stream_send(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        match response.downcast_mut::<Resp>() {
+            Some(response) => self.inner.on_stream_send(ctx, response),
+            None => Ok(()),
+        }
+    }
+
+    fn on_stream_receive(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        match request.downcast_mut::<Req>() {
+            Some(request) => self.inner.on_stream_receive(ctx, request),
+            None => Ok(()),
+        }
+    }
+}
+
+// ============================================================================
+// Internal Unified Trait
+// ============================================================================
+
+/// Internal trait that unifies both interceptor types.
+///
+/// Not intended for direct implementation - use [`Interceptor`] or
+/// [`MessageInterceptor`] instead.
+pub trait InterceptorInternal: Send + Sync + 'static {
+    /// Intercept incoming request headers.
+    fn intercept_request_headers(&self, ctx: &mut RequestContext) -> Result<(), ConnectError>;
+
+    /// Intercept outgoing response headers.
+    fn intercept_response_headers(&self, ctx: &mut ResponseContext) -> Result<(), ConnectError>;
+
+    /// Intercept a unary request message.
+    fn intercept_request(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError>;
+
+    /// Intercept a unary response message.
+    fn intercept_response(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError>;
+
+    /// Intercept a stream send.
+    fn intercept_stream_send(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError>;
+
+    /// Intercept a stream receive.
+    fn intercept_stream_receive(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError>;
+}
+
+// ============================================================================
+// Base Case: Unit Type
+// ============================================================================
+
+/// The unit type implements all interceptor traits as no-ops,
+/// serving as the base case for interceptor chains.
+impl Interceptor for () {}
+impl MessageInterceptor for () {}
+
+impl InterceptorInternal for () {
+    #[inline]
+    fn intercept_request_headers(&self, _ctx: &mut RequestContext) -> Result<(), ConnectError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn intercept_response_headers(&self, _ctx: &mut ResponseContext) -> Result<(), ConnectError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn intercept_request(
+        &self,
+        _ctx: &MessageContext,
+        _request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn intercept_response(
+        &self,
+        _ctx: &MessageContext,
+        _response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn intercept_stream_send(
+        &self,
+        _ctx: &MessageContext,
+        _response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn intercept_stream_receive(
+        &self,
+        _ctx: &MessageContext,
+        _request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Wrappers
+// ============================================================================
+
+/// Wrapper that adapts a header-level [`Interceptor`] to [`InterceptorInternal`].
+#[derive(Clone, Debug)]
+pub struct HeaderWrapper<I>(pub I);
+
+impl<I: Interceptor> InterceptorInternal for HeaderWrapper<I> {
+    fn intercept_request_headers(&self, ctx: &mut RequestContext) -> Result<(), ConnectError> {
+        self.0.on_request(ctx)
+    }
+
+    fn intercept_response_headers(&self, ctx: &mut ResponseContext) -> Result<(), ConnectError> {
+        self.0.on_response(ctx)
+    }
+
+    fn intercept_request(
+        &self,
+        _ctx: &MessageContext,
+        _request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        // Header interceptors don't see messages
+        Ok(())
+    }
+
+    fn intercept_response(
+        &self,
+        _ctx: &MessageContext,
+        _response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        Ok(())
+    }
+
+    fn intercept_stream_send(
+        &self,
+        _ctx: &MessageContext,
+        _response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        Ok(())
+    }
+
+    fn intercept_stream_receive(
+        &self,
+        _ctx: &MessageContext,
+        _request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        Ok(())
+    }
+}
+
+/// Wrapper that adapts a [`MessageInterceptor`] to [`InterceptorInternal`].
+#[derive(Clone, Debug)]
+pub struct MessageWrapper<I>(pub I);
+
+impl<I: MessageInterceptor> InterceptorInternal for MessageWrapper<I> {
+    fn intercept_request_headers(&self, _ctx: &mut RequestContext) -> Result<(), ConnectError> {
+        // Message interceptors don't see headers
+        Ok(())
+    }
+
+    fn intercept_response_headers(&self, _ctx: &mut ResponseContext) -> Result<(), ConnectError> {
+        Ok(())
+    }
+
+    fn intercept_request(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        self.0.on_request(ctx, request)
+    }
+
+    fn intercept_response(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        self.0.on_response(ctx, response)
+    }
+
+    fn intercept_stream_send(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        self.0.on_stream_send(ctx, response)
+    }
+
+    fn intercept_stream_receive(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        self.0.on_stream_receive(ctx, request)
+    }
+}
+
+// ============================================================================
+// Chain Combinator
+// ============================================================================
+
+/// A chain of two interceptors.
+///
+/// `Chain<A, B>` applies interceptor `A` first, then `B` for requests.
+/// For responses, they are applied in reverse order (`B` then `A`),
+/// following the middleware unwinding pattern.
+#[derive(Clone, Debug)]
+pub struct Chain<A, B>(pub A, pub B);
+
+impl<A, B> InterceptorInternal for Chain<A, B>
+where
+    A: InterceptorInternal,
+    B: InterceptorInternal,
+{
+    fn intercept_request_headers(&self, ctx: &mut RequestContext) -> Result<(), ConnectError> {
+        self.0.intercept_request_headers(ctx)?;
+        self.1.intercept_request_headers(ctx)
+    }
+
+    fn intercept_response_headers(&self, ctx: &mut ResponseContext) -> Result<(), ConnectError> {
+        // Reverse order for responses (middleware unwinding)
+        self.1.intercept_response_headers(ctx)?;
+        self.0.intercept_response_headers(ctx)
+    }
+
+    fn intercept_request(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        self.0.intercept_request(ctx, request)?;
+        self.1.intercept_request(ctx, request)
+    }
+
+    fn intercept_response(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        // Reverse order for responses (middleware unwinding)
+        self.1.intercept_response(ctx, response)?;
+        self.0.intercept_response(ctx, response)
+    }
+
+    fn intercept_stream_send(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        // Reverse order for responses
+        self.1.intercept_stream_send(ctx, response)?;
+        self.0.intercept_stream_send(ctx, response)
+    }
+
+    fn intercept_stream_receive(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        self.0.intercept_stream_receive(ctx, request)?;
+        self.1.intercept_stream_receive(ctx, request)
+    }
+}
+
+/// Shared, type-erased interceptor chain as stored on the layer.
+pub(crate) type SharedInterceptor = Arc<dyn InterceptorInternal>;
+
+impl InterceptorInternal for SharedInterceptor {
+    fn intercept_request_headers(&self, ctx: &mut RequestContext) -> Result<(), ConnectError> {
+        (**self).intercept_request_headers(ctx)
+    }
+
+    fn intercept_response_headers(&self, ctx: &mut ResponseContext) -> Result<(), ConnectError> {
+        (**self).intercept_response_headers(ctx)
+    }
+
+    fn intercept_request(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        (**self).intercept_request(ctx, request)
+    }
+
+    fn intercept_response(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        (**self).intercept_response(ctx, response)
+    }
+
+    fn intercept_stream_send(
+        &self,
+        ctx: &MessageContext,
+        response: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        (**self).intercept_stream_send(ctx, response)
+    }
+
+    fn intercept_stream_receive(
+        &self,
+        ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        (**self).intercept_stream_receive(ctx, request)
+    }
+}
+
+/// Append `next` to an optional existing chain.
+pub(crate) fn push_interceptor<I: InterceptorInternal>(
+    chain: Option<SharedInterceptor>,
+    next: I,
+) -> SharedInterceptor {
+    match chain {
+        Some(prev) => Arc::new(Chain(prev, next)),
+        None => Arc::new(next),
+    }
+}
+
+// ============================================================================
+// Per-request binding
+// ============================================================================
+
+/// The interceptor chain bound to a single request.
+///
+/// Built by `ConnectLayer` and stored in [`ConnectContext`](crate::context::ConnectContext)
+/// so the request extractors and response encoders can run message hooks.
+#[derive(Clone)]
+pub struct RequestInterceptor {
+    chain: SharedInterceptor,
+    procedure: Arc<str>,
+    protocol: RequestProtocol,
+    peer: Arc<PeerInfo>,
+    values: Arc<ContextValues>,
+}
+
+impl fmt::Debug for RequestInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestInterceptor")
+            .field("procedure", &self.procedure)
+            .field("protocol", &self.protocol)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RequestInterceptor {
+    pub(crate) fn new(
+        chain: SharedInterceptor,
+        procedure: &str,
+        protocol: RequestProtocol,
+        peer: PeerInfo,
+    ) -> Self {
+        Self {
+            chain,
+            procedure: procedure.into(),
+            protocol,
+            peer: Arc::new(peer),
+            values: Arc::default(),
+        }
+    }
+
+    /// The procedure this request is calling.
+    pub fn procedure(&self) -> &str {
+        &self.procedure
+    }
+
+    fn message_context(&self) -> MessageContext<'_> {
+        MessageContext {
+            procedure: &self.procedure,
+            protocol: self.protocol,
+            peer: &self.peer,
+            values: &self.values,
+        }
+    }
+
+    /// Run the request header hooks, then bind the resulting `values` for
+    /// the message and response hooks.
+    pub(crate) fn request_headers(
+        &mut self,
+        headers: &mut HeaderMap,
+        values: &mut ContextValues,
+    ) -> Result<(), ConnectError> {
+        self.chain.intercept_request_headers(&mut RequestContext {
+            procedure: &self.procedure,
+            protocol: self.protocol,
+            peer: &self.peer,
+            headers,
+            values,
+        })?;
+        self.values = Arc::new(values.clone());
+        Ok(())
+    }
+
+    pub(crate) fn response_headers(
+        &self,
+        status: StatusCode,
+        headers: &mut HeaderMap,
+    ) -> Result<(), ConnectError> {
+        self.chain.intercept_response_headers(&mut ResponseContext {
+            procedure: &self.procedure,
+            protocol: self.protocol,
+            peer: &self.peer,
+            status,
+            headers,
+            values: &self.values,
+        })
+    }
+
+    pub(crate) fn request(&self, request: &mut dyn AnyMessage) -> Result<(), ConnectError> {
+        self.chain
+            .intercept_request(&self.message_context(), request)
+    }
+
+    pub(crate) fn response(&self, response: &mut dyn AnyMessage) -> Result<(), ConnectError> {
+        self.chain
+            .intercept_response(&self.message_context(), response)
+    }
+
+    pub(crate) fn stream_send(&self, response: &mut dyn AnyMessage) -> Result<(), ConnectError> {
+        self.chain
+            .intercept_stream_send(&self.message_context(), response)
+    }
+
+    pub(crate) fn stream_receive(&self, request: &mut dyn AnyMessage) -> Result<(), ConnectError> {
+        self.chain
+            .intercept_stream_receive(&self.message_context(), request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::message::{ConnectRequest, ConnectResponse};
+    use crate::service_builder::MakeServiceBuilder;
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{Request, header};
+    use std::sync::Mutex;
+    use tower::ServiceExt;
+
+    #[derive(Clone)]
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Recorder {
+        fn record(&self, hook: &str) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:{}", self.name, hook));
+        }
+    }
+
+    impl MessageInterceptor for Recorder {
+        fn on_request(
+            &self,
+            _ctx: &MessageContext,
+            _request: &mut dyn AnyMessage,
+        ) -> Result<(), ConnectError> {
+            self.record("request");
+            Ok(())
+        }
+
+        fn on_response(
+            &self,
+            _ctx: &MessageContext,
+            _response: &mut dyn AnyMessage,
+        ) -> Result<(), ConnectError> {
+            self.record("response");
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct RequireAuth;
+
+    impl Interceptor for RequireAuth {
+        fn on_request(&self, ctx: &mut RequestContext) -> Result<(), ConnectError> {
+            if ctx.headers.contains_key(header::AUTHORIZATION) {
+                Ok(())
+            } else {
+                Err(ConnectError::new_unauthenticated("missing token"))
+            }
+        }
+
+        fn on_response(&self, ctx: &mut ResponseContext) -> Result<(), ConnectError> {
+            ctx.headers
+                .insert("x-procedure", ctx.procedure.parse().unwrap());
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct Shout;
+
+    impl MessageInterceptor for Shout {
+        fn on_request(
+            &self,
+            _ctx: &MessageContext,
+            request: &mut dyn AnyMessage,
+        ) -> Result<(), ConnectError> {
+            let name = request
+                .downcast_mut::<String>()
+                .ok_or_else(|| ConnectError::new_internal("unexpected message type"))?;
+            if name.is_empty() {
+                return Err(ConnectError::new_invalid_argument("name is required"));
+            }
+            *name = name.to_uppercase();
+            Ok(())
+        }
+    }
+
+    async fn greet(
+        ConnectRequest(name): ConnectRequest<String>,
+    ) -> Result<ConnectResponse<String>, ConnectError> {
+        Ok(ConnectResponse::new(format!("hello {name}")))
+    }
+
+    fn app() -> Router {
+        let router = Router::new().route("/test.Greeter/Greet", post_connect(greet));
+        MakeServiceBuilder::new()
+            .with_interceptor(RequireAuth)
+            .with_message_interceptor(Shout)
+            .add_router(router)
+            .build()
+    }
+
+    fn request(body: &str, auth: bool) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri("/test.Greeter/Greet")
+            .header(header::CONTENT_TYPE, "application/json");
+        if auth {
+            builder = builder.header(header::AUTHORIZATION, "Bearer token");
+        }
+        builder.body(Body::from(body.to_owned())).unwrap()
+    }
+
+    async fn body_string(resp: axum::response::Response) -> String {
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_chain_ordering() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let first = Recorder {
+            name: "first",
+            log: log.clone(),
+        };
+        let second = Recorder {
+            name: "second",
+            log: log.clone(),
+        };
+
+        let chain = push_interceptor(None, MessageWrapper(first));
+        let chain = push_interceptor(Some(chain), MessageWrapper(second));
+        let interceptor = RequestInterceptor::new(
+            chain,
+            "/svc/Method",
+            RequestProtocol::ConnectUnaryJson,
+            PeerInfo::default(),
+        );
+
+        let mut msg = String::from("hi");
+        interceptor.request(&mut msg).unwrap();
+        interceptor.response(&mut msg).unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "first:request",
+                "second:request",
+                "second:response",
+                "first:response"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_any_message_downcast() {
+        let mut msg = String::from("hi");
+        let any: &mut dyn AnyMessage = &mut msg;
+
+        assert!(any.is::<String>());
+        assert!(any.downcast_ref::<Vec<u8>>().is_none());
+        any.downcast_mut::<String>().unwrap().push('!');
+        assert_eq!(any.encoded_size(), String::from("hi!").encoded_len());
+        assert_eq!(msg, "hi!");
+    }
+
+    #[tokio::test]
+    async fn test_header_interceptor_rejects_request() {
+        let resp = app().oneshot(request("\"world\"", false)).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(body_string(resp).await.contains("unauthenticated"));
+    }
+
+    #[tokio::test]
+    async fn test_message_interceptor_mutates_request() {
+        let resp = app().oneshot(request("\"world\"", true)).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get("x-procedure").unwrap(),
+            "/test.Greeter/Greet"
+        );
+        assert_eq!(body_string(resp).await, "\"hello WORLD\"");
+    }
+
+    #[tokio::test]
+    async fn test_message_interceptor_rejects_request() {
+        let resp = app().oneshot(request("\"\"", true)).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert!(body_string(resp).await.contains("name is required"));
+    }
+
+    #[tokio::test]
+    async fn test_peer_info_reaches_interceptors_and_handlers() {
+        struct TagAgent;
+
+        impl Interceptor for TagAgent {
+            fn on_response(&self, ctx: &mut ResponseContext) -> Result<(), ConnectError> {
+                let agent = ctx.peer.user_agent.as_ref().unwrap();
+                ctx.headers
+                    .insert("x-agent", agent.product.parse().unwrap());
+                Ok(())
+            }
+        }
+
+        async fn whoami(
+            peer: PeerInfo,
+            ConnectRequest(_): ConnectRequest<String>,
+        ) -> Result<ConnectResponse<String>, ConnectError> {
+            Ok(ConnectResponse::new(format!(
+                "{} {:?}",
+                peer.remote_addr.unwrap(),
+                peer.http_version
+            )))
+        }
+
+        let router = Router::new().route("/test.Greeter/WhoAmI", post_connect(whoami));
+        let app = MakeServiceBuilder::new()
+            .with_interceptor(TagAgent)
+            .add_router(router)
+            .build();
+        let addr: std::net::SocketAddr = "192.0.2.1:4711".parse().unwrap();
+        let req = Request::builder()
+            .method("POST")
+            .uri("/test.Greeter/WhoAmI")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::USER_AGENT, "connect-go/1.16.0 (go1.22.0)")
+            .extension(axum::extract::ConnectInfo(addr))
+            .body(Body::from("\"hi\""))
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("x-agent").unwrap(), "connect-go");
+        assert_eq!(body_string(resp).await, "\"192.0.2.1:4711 HTTP/1.1\"");
+    }
+
+    #[tokio::test]
+    async fn test_typed_message_interceptor() {
+        struct Redact;
+
+        impl TypedMessageInterceptor<String, String> for Redact {
+            fn on_response(
+                &self,
+                _ctx: &MessageContext,
+                response: &mut String,
+            ) -> Result<(), ConnectError> {
+                *response = response.replace("secret", "***");
+                Ok(())
+            }
+        }
+
+        // Only runs for its own message types
+        struct Never;
+
+        impl TypedMessageInterceptor<Vec<u8>, Vec<u8>> for Never {
+            fn on_request(
+                &self,
+                _ctx: &MessageContext,
+                _request: &mut Vec<u8>,
+            ) -> Result<(), ConnectError> {
+                Err(ConnectError::new_internal("wrong message type"))
+            }
+        }
+
+        let router = Router::new().route("/test.Greeter/Greet", post_connect(greet));
+        let app = MakeServiceBuilder::new()
+            .with_message_interceptor(TypedWrapper::new(Redact))
+            .with_message_interceptor(TypedWrapper::new(Never))
+            .add_router(router)
+            .build();
+
+        let resp = app.oneshot(request("\"secret\"", false)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(body_string(resp).await, "\"hello ***\"");
+    }
+
+    #[tokio::test]
+    async fn test_context_values_reach_interceptors_and_handlers() {
+        use crate::context::{ContextValue, ContextValues};
+
+        #[derive(Clone)]
+        struct Tenant(&'static str);
+        #[derive(Clone)]
+        struct User(String);
+
+        // Reads the middleware's value and adds its own
+        struct Authenticate;
+
+        impl Interceptor for Authenticate {
+            fn on_request(&self, ctx: &mut RequestContext) -> Result<(), ConnectError> {
+                let tenant = ctx.values.get::<Tenant>().unwrap().0;
+                ctx.values.insert(User(format!("alice@{tenant}")));
+                Ok(())
+            }
+        }
+
+        struct RequireUser;
+
+        impl MessageInterceptor for RequireUser {
+            fn on_request(
+                &self,
+                ctx: &MessageContext,
+                _request: &mut dyn AnyMessage,
+            ) -> Result<(), ConnectError> {
+                match ctx.values.contains::<User>() {
+                    true => Ok(()),
+                    false => Err(ConnectError::new_unauthenticated("no user")),
+                }
+            }
+        }
+
+        async fn whoami(
+            ContextValue(user): ContextValue<User>,
+            ConnectRequest(_): ConnectRequest<String>,
+        ) -> Result<ConnectResponse<String>, ConnectError> {
+            Ok(ConnectResponse::new(user.0))
+        }
+
+        let router = Router::new().route("/test.Greeter/WhoAmI", post_connect(whoami));
+        let app = MakeServiceBuilder::new()
+            .with_interceptor(Authenticate)
+            .with_message_interceptor(RequireUser)
+            .add_router(router)
+            .build()
+            .layer(axum::middleware::from_fn(
+                |mut req: axum::extract::Request, next: axum::middleware::Next| async move {
+                    ContextValues::from_extensions_mut(req.extensions_mut()).insert(Tenant("acme"));
+                    next.run(req).await
+                },
+            ));
+        let req = Request::builder()
+            .method("POST")
+            .uri("/test.Greeter/WhoAmI")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("\"hi\""))
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(body_string(resp).await, "\"alice@acme\"");
+    }
+}
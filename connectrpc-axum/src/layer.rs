@@ -4,6 +4,15 @@
 //!
 //! - [`ConnectLayer`]: Protocol detection, context building, timeouts, and message limits.
 //! - [`BridgeLayer`]: Bridges Tower compression with Connect streaming requirements.
+//! - [`MethodConfig`](crate::MethodConfig): Per-route overrides of limits, timeout and compression.
+//! - [`ConcurrencyLimitLayer`]: Caps in-flight RPCs, rejecting with `resource_exhausted`.
+//! - [`RateLimitLayer`]: Token-bucket rate limits per caller, rejecting with `resource_exhausted`.
+//! - [`AccessLogLayer`]: Emits one structured record per RPC.
+//! - `MetricsLayer` (`metrics` feature): Records RPC metrics via the `metrics` facade.
+//!
+//! `ConnectLayer` also serves gRPC and gRPC-Web clients: requests are decoded
+//! like Connect streaming requests, and responses get their status in
+//! trailers instead of an EndStream frame.
 //!
 //! ## Layer Stack Order
 //!
@@ -59,8 +68,30 @@
 //! └─────────────────────────────────────────────┘
 //! ```
 
+mod access_log;
 mod bridge;
+mod concurrency;
 mod connect;
+mod grpc;
+mod method;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod rate_limit;
+mod status;
+#[cfg(feature = "tracing")]
+mod trace;
 
+pub use access_log::{
+    AccessLogLayer, AccessLogRecord, AccessLogService, AccessLogSink, TracingAccessLog,
+};
 pub use bridge::{BridgeLayer, BridgeService};
+pub use concurrency::{ConcurrencyLimitLayer, ConcurrencyLimitService};
 pub use connect::{ConnectLayer, ConnectService};
+pub use method::MethodConfigService;
+#[cfg(feature = "metrics")]
+pub use metrics::{MetricsLayer, MetricsService};
+pub use rate_limit::{RateLimitLayer, RateLimitService};
+
+#[cfg(feature = "tonic")]
+pub(crate) use concurrency::rejection_response;
+pub(crate) use grpc::into_grpc_response;
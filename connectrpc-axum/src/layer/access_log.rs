@@ -0,0 +1,447 @@
+//! Access logging layer.
+//!
+//! [`AccessLogLayer`] emits one [`AccessLogRecord`] per RPC once its response
+//! is finished: procedure, protocol, status code, duration, request and
+//! response sizes, and compression. Records go to a pluggable
+//! [`AccessLogSink`]; by default they become `tracing` events.
+//!
+//! The status code is read from where the protocol puts it (HTTP status,
+//! EndStream frame or `grpc-status`). A response dropped before its status
+//! was sent (e.g. the client went away mid-stream) is logged as `canceled`.
+//!
+//! Sizes are the bytes on the wire as seen by the layer, so apply it outside
+//! the compression layers (e.g. on the router returned by
+//! [`MakeServiceBuilder::build`](crate::MakeServiceBuilder::build)) to log
+//! compressed sizes.
+
+use super::status::StatusScan;
+use crate::context::{RequestProtocol, detect_protocol};
+use crate::message::error::Code;
+use axum::body::Body;
+use axum::http::{HeaderMap, Request, header};
+use axum::response::Response;
+use bytes::Bytes;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+use tower::{Layer, Service, ServiceExt};
+
+/// One finished RPC.
+#[derive(Debug, Clone)]
+pub struct AccessLogRecord {
+    /// RPC path, e.g. `/hello.v1.HelloService/SayHello`.
+    pub procedure: String,
+    /// Protocol the client spoke.
+    pub protocol: RequestProtocol,
+    /// Status code of the RPC; `Ok` on success.
+    pub code: Code,
+    /// Time from receiving the request to finishing the response.
+    pub duration: Duration,
+    /// Bytes of request body read.
+    pub request_bytes: u64,
+    /// Bytes of response body sent.
+    pub response_bytes: u64,
+    /// Compression of the request body, if any.
+    pub request_compression: Option<String>,
+    /// Compression of the response body, if any.
+    pub response_compression: Option<String>,
+}
+
+/// Destination of access log records.
+///
+/// Implemented for closures taking a `&AccessLogRecord`.
+pub trait AccessLogSink: Send + Sync + 'static {
+    /// Record one finished RPC.
+    fn log(&self, record: &AccessLogRecord);
+}
+
+impl<F> AccessLogSink for F
+where
+    F: Fn(&AccessLogRecord) + Send + Sync + 'static,
+{
+    fn log(&self, record: &AccessLogRecord) {
+        self(record)
+    }
+}
+
+/// Sink that emits each record as an `INFO` event with target
+/// `connectrpc_axum::access_log`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracingAccessLog;
+
+impl AccessLogSink for TracingAccessLog {
+    fn log(&self, record: &AccessLogRecord) {
+        tracing::info!(
+            target: "connectrpc_axum::access_log",
+            procedure = %record.procedure,
+            protocol = record.protocol.name(),
+            code = record.code.as_str(),
+            duration_ms = record.duration.as_secs_f64() * 1000.0,
+            request_bytes = record.request_bytes,
+            response_bytes = record.response_bytes,
+            request_compression = record.request_compression.as_deref(),
+            response_compression = record.response_compression.as_deref(),
+            "rpc finished"
+        );
+    }
+}
+
+/// Layer that logs one [`AccessLogRecord`] per RPC.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use connectrpc_axum::{AccessLogLayer, AccessLogRecord, MakeServiceBuilder};
+///
+/// // tracing events
+/// let app = MakeServiceBuilder::new()
+///     .add_router(router)
+///     .build()
+///     .layer(AccessLogLayer::new());
+///
+/// // custom sink
+/// let layer = AccessLogLayer::new().sink(|record: &AccessLogRecord| {
+///     metrics.observe(&record.procedure, record.code, record.duration);
+/// });
+/// ```
+#[derive(Clone)]
+pub struct AccessLogLayer {
+    sink: Arc<dyn AccessLogSink>,
+}
+
+impl AccessLogLayer {
+    /// Create a layer that logs to [`TracingAccessLog`].
+    pub fn new() -> Self {
+        Self {
+            sink: Arc::new(TracingAccessLog),
+        }
+    }
+
+    /// Send records to `sink` instead.
+    pub fn sink(mut self, sink: impl AccessLogSink) -> Self {
+        self.sink = Arc::new(sink);
+        self
+    }
+}
+
+impl Default for AccessLogLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for AccessLogLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessLogLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for AccessLogLayer {
+    type Service = AccessLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessLogService {
+            inner,
+            sink: self.sink.clone(),
+        }
+    }
+}
+
+/// Service wrapper created by [`AccessLogLayer`].
+#[derive(Clone)]
+pub struct AccessLogService<S> {
+    inner: S,
+    sink: Arc<dyn AccessLogSink>,
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for AccessLogService<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessLogService")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for AccessLogService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: http_body::Body<Data = Bytes> + Send + 'static,
+    ReqBody::Error: Into<axum::BoxError>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let start = Instant::now();
+        let protocol = detect_protocol(&req);
+        let procedure = req.uri().path().to_owned();
+        let request_compression = compression(req.headers());
+
+        let request_bytes = Arc::new(AtomicU64::new(0));
+        let req = req.map(|body| {
+            Body::new(CountingBody {
+                body: Body::new(body),
+                bytes: request_bytes.clone(),
+            })
+        });
+
+        let sink = self.sink.clone();
+        let inner = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, inner);
+
+        Box::pin(async move {
+            let response = inner.oneshot(req).await?;
+            let status = StatusScan::new(protocol, response.status(), response.headers());
+            let pending = PendingRecord {
+                sink,
+                start,
+                record: AccessLogRecord {
+                    procedure,
+                    protocol,
+                    code: Code::Ok,
+                    duration: Duration::ZERO,
+                    request_bytes: 0,
+                    response_bytes: 0,
+                    request_compression,
+                    response_compression: compression(response.headers()),
+                },
+                request_bytes,
+            };
+            Ok(response.map(|body| {
+                Body::new(LoggedBody {
+                    body,
+                    status,
+                    pending: Some(pending),
+                })
+            }))
+        })
+    }
+}
+
+/// The compression named by whichever encoding header the protocol uses.
+fn compression(headers: &HeaderMap) -> Option<String> {
+    [
+        header::CONTENT_ENCODING.as_str(),
+        "connect-content-encoding",
+        "grpc-encoding",
+    ]
+    .into_iter()
+    .filter_map(|name| headers.get(name)?.to_str().ok())
+    .find(|encoding| *encoding != "identity")
+    .map(str::to_owned)
+}
+
+/// Request body that counts the bytes read.
+struct CountingBody {
+    body: Body,
+    bytes: Arc<AtomicU64>,
+}
+
+impl http_body::Body for CountingBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        let frame = std::task::ready!(Pin::new(&mut self.body).poll_frame(cx));
+        if let Some(Ok(frame)) = &frame
+            && let Some(data) = frame.data_ref()
+        {
+            self.bytes.fetch_add(data.len() as u64, Ordering::Relaxed);
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.body.size_hint()
+    }
+}
+
+/// A record waiting for its response to finish.
+struct PendingRecord {
+    sink: Arc<dyn AccessLogSink>,
+    start: Instant,
+    record: AccessLogRecord,
+    request_bytes: Arc<AtomicU64>,
+}
+
+/// Response body that logs the RPC when finished or dropped.
+struct LoggedBody {
+    body: Body,
+    status: StatusScan,
+    pending: Option<PendingRecord>,
+}
+
+impl LoggedBody {
+    fn finish(&mut self, ended: bool) {
+        let Some(mut pending) = self.pending.take() else {
+            return;
+        };
+        pending.record.code = self.status.code(ended);
+        pending.record.duration = pending.start.elapsed();
+        pending.record.request_bytes = pending.request_bytes.load(Ordering::Relaxed);
+        pending.sink.log(&pending.record);
+    }
+}
+
+impl http_body::Body for LoggedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        let this = &mut *self;
+        let frame = std::task::ready!(Pin::new(&mut this.body).poll_frame(cx));
+        match &frame {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    if let Some(pending) = &mut this.pending {
+                        pending.record.response_bytes += data.len() as u64;
+                    }
+                    this.status.data(data);
+                } else if let Some(trailers) = frame.trailers_ref() {
+                    this.status.trailers(trailers);
+                }
+            }
+            Some(Err(_)) => this.finish(false),
+            None => this.finish(true),
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.body.size_hint()
+    }
+}
+
+impl Drop for LoggedBody {
+    fn drop(&mut self) {
+        let ended = http_body::Body::is_end_stream(&self.body);
+        self.finish(ended);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::error::ConnectError;
+    use crate::message::{ConnectRequest, ConnectResponse, StreamBody};
+    use axum::Router;
+    use futures::Stream;
+    use std::sync::Mutex;
+
+    fn request(path: &str, content_type: &str, text: &str) -> Request<Body> {
+        let body = if content_type == "application/json" {
+            Body::from(text.to_owned())
+        } else {
+            let mut frame = vec![0u8];
+            frame.extend_from_slice(&(text.len() as u32).to_be_bytes());
+            frame.extend_from_slice(text.as_bytes());
+            Body::from(frame)
+        };
+        Request::post(path)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(body)
+            .unwrap()
+    }
+
+    /// Streams the request back twice, failing on an empty request.
+    async fn stream(
+        ConnectRequest(req): ConnectRequest<String>,
+    ) -> Result<
+        ConnectResponse<StreamBody<impl Stream<Item = Result<String, ConnectError>>>>,
+        ConnectError,
+    > {
+        let last = if req.is_empty() {
+            Err(ConnectError::new(Code::InvalidArgument, "empty"))
+        } else {
+            Ok(req.clone())
+        };
+        let stream = futures::stream::iter(vec![Ok(req), last]);
+        Ok(ConnectResponse::new(StreamBody::new(stream)))
+    }
+
+    fn app(records: Arc<Mutex<Vec<AccessLogRecord>>>) -> Router {
+        let echo = |ConnectRequest(req): ConnectRequest<String>| async move {
+            if req.is_empty() {
+                return Err(ConnectError::new(Code::InvalidArgument, "empty"));
+            }
+            Ok(ConnectResponse::new(req))
+        };
+        Router::new()
+            .route("/test.Svc/Echo", post_connect(echo))
+            .route("/test.Svc/Stream", post_connect(stream))
+            .layer(ConnectLayer::new())
+            .layer(AccessLogLayer::new().sink(move |record: &AccessLogRecord| {
+                records.lock().unwrap().push(record.clone());
+            }))
+    }
+
+    async fn call(path: &str, content_type: &str, text: &str) -> AccessLogRecord {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let response = app(records.clone())
+            .oneshot(request(path, content_type, text))
+            .await
+            .unwrap();
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let mut records = records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        records.pop().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_logs_connect_unary() {
+        let record = call("/test.Svc/Echo", "application/json", "\"hi\"").await;
+        assert_eq!(record.procedure, "/test.Svc/Echo");
+        assert_eq!(record.protocol, RequestProtocol::ConnectUnaryJson);
+        assert_eq!(record.code, Code::Ok);
+        assert_eq!(record.request_bytes, 4);
+        assert_eq!(record.response_bytes, 4);
+        assert_eq!(record.request_compression, None);
+
+        let record = call("/test.Svc/Echo", "application/json", "\"\"").await;
+        assert_eq!(record.code, Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_logs_streaming_and_grpc_codes() {
+        let record = call("/test.Svc/Stream", "application/connect+json", "\"hi\"").await;
+        assert_eq!(record.code, Code::Ok);
+        assert_eq!(record.request_bytes, 9);
+
+        let record = call("/test.Svc/Stream", "application/connect+json", "\"\"").await;
+        assert_eq!(record.code, Code::InvalidArgument);
+
+        let record = call("/test.Svc/Stream", "application/grpc-web+proto", "").await;
+        assert_eq!(record.protocol.name(), "grpc-web");
+        assert_eq!(record.code, Code::InvalidArgument);
+    }
+}
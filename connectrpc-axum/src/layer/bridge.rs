@@ -133,16 +133,19 @@ where
                             content_length, max_size
                         ),
                     );
-                    let send_max_bytes = self.send_max_bytes;
-                    return Box::pin(async move {
-                        Ok(err.into_response_with_send_limit(protocol, send_max_bytes))
-                    });
+                    let mut response =
+                        err.into_response_with_send_limit(protocol, self.send_max_bytes);
+                    if protocol.is_grpc() {
+                        response = super::grpc::into_grpc_response(response, protocol);
+                    }
+                    return Box::pin(async move { Ok(response) });
                 }
             }
         }
 
         if is_connect_streaming(&req) {
-            // Streaming uses per-envelope compression via Connect-Content-Encoding/Connect-Accept-Encoding,
+            // Streaming and gRPC use per-envelope compression via Connect-Content-Encoding/Connect-Accept-Encoding
+            // (grpc-encoding/grpc-accept-encoding for gRPC),
             // NOT HTTP body compression via Content-Encoding/Accept-Encoding.
             // Remove Content-Encoding to prevent Tower from decompressing the request body.
             req.headers_mut().remove(CONTENT_ENCODING);
@@ -167,16 +170,17 @@ fn get_content_length<B>(req: &Request<B>) -> Option<usize> {
         .and_then(|s| s.parse().ok())
 }
 
-/// Check if the request is a Connect streaming request.
+/// Check if the request is a Connect streaming or gRPC request.
 ///
-/// Connect streaming uses content types like:
+/// These use content types like:
 /// - `application/connect+json`
 /// - `application/connect+proto`
+/// - `application/grpc`, `application/grpc-web`
 fn is_connect_streaming<B>(req: &Request<B>) -> bool {
     req.headers()
         .get(CONTENT_TYPE)
         .and_then(|v| v.to_str().ok())
-        .map(|ct| ct.starts_with("application/connect+"))
+        .map(|ct| ct.starts_with("application/connect+") || ct.starts_with("application/grpc"))
         .unwrap_or(false)
 }
 
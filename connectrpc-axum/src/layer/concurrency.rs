@@ -0,0 +1,357 @@
+//! Concurrency limit layer.
+//!
+//! [`ConcurrencyLimitLayer`] caps the number of in-flight RPCs, across the
+//! whole router and/or per method. Unlike tower's `ConcurrencyLimitLayer`
+//! (which queues) or `LoadShedLayer` (which answers `503`), saturated requests
+//! are rejected immediately with a Connect `resource_exhausted` error in the
+//! request's protocol.
+//!
+//! An RPC holds its slot until the response body is finished or dropped, so
+//! long-running streams count against the limit for their whole lifetime.
+
+use crate::context::{ConnectContext, detect_protocol};
+use crate::message::error::{Code, ConnectError};
+use axum::body::Body;
+use axum::http::Request;
+use axum::response::Response;
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower::{Layer, Service, ServiceExt};
+
+/// Layer that limits the number of concurrent RPCs.
+///
+/// Limits are shared by every service the layer is applied to, so applying it
+/// with `Router::layer` caps the router as a whole.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use connectrpc_axum::ConcurrencyLimitLayer;
+///
+/// let router = Router::new()
+///     .route("/service/Method", post(handler))
+///     .layer(ConnectLayer::new())
+///     .layer(
+///         ConcurrencyLimitLayer::new()
+///             .max_in_flight(1000)                        // whole router
+///             .per_method(100)                            // every method
+///             .method("/reports.v1.ReportService/Export", 4),  // one method
+///     );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ConcurrencyLimitLayer {
+    global: Option<Arc<Semaphore>>,
+    per_method: Option<usize>,
+    methods: Arc<HashMap<String, usize>>,
+    semaphores: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+impl ConcurrencyLimitLayer {
+    /// Create a layer with no limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the number of in-flight RPCs across all methods.
+    pub fn max_in_flight(mut self, max: usize) -> Self {
+        self.global = Some(Arc::new(Semaphore::new(max)));
+        self
+    }
+
+    /// Limit the number of in-flight RPCs of each method.
+    ///
+    /// Every method gets its own budget of `max`. Use [`method`](Self::method)
+    /// to give a method a different limit.
+    pub fn per_method(mut self, max: usize) -> Self {
+        self.per_method = Some(max);
+        self
+    }
+
+    /// Limit the number of in-flight RPCs of one method.
+    ///
+    /// `path` is the RPC path, e.g. `/hello.v1.HelloService/SayHello`.
+    pub fn method(mut self, path: impl Into<String>, max: usize) -> Self {
+        Arc::make_mut(&mut self.methods).insert(path.into(), max);
+        self
+    }
+
+    /// The semaphore limiting `path`, if the method is limited.
+    fn method_semaphore(&self, path: &str) -> Option<Arc<Semaphore>> {
+        let max = self.methods.get(path).copied().or(self.per_method)?;
+        let mut semaphores = self
+            .semaphores
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Some(
+            semaphores
+                .entry(path.to_owned())
+                .or_insert_with(|| Arc::new(Semaphore::new(max)))
+                .clone(),
+        )
+    }
+
+    /// Take a slot in every limit that applies to `path`.
+    fn acquire(&self, path: &str) -> Result<Permits, ConnectError> {
+        let global = match &self.global {
+            Some(semaphore) => Some(semaphore.clone().try_acquire_owned().map_err(|_| {
+                ConnectError::new(Code::ResourceExhausted, "too many concurrent requests")
+            })?),
+            None => None,
+        };
+        let method = match self.method_semaphore(path) {
+            Some(semaphore) => Some(semaphore.try_acquire_owned().map_err(|_| {
+                ConnectError::new(
+                    Code::ResourceExhausted,
+                    format!("too many concurrent requests for {path}"),
+                )
+            })?),
+            None => None,
+        };
+        Ok(Permits {
+            _global: global,
+            _method: method,
+        })
+    }
+}
+
+impl<S> Layer<S> for ConcurrencyLimitLayer {
+    type Service = ConcurrencyLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConcurrencyLimitService {
+            inner,
+            limits: self.clone(),
+        }
+    }
+}
+
+/// Service wrapper created by [`ConcurrencyLimitLayer`].
+#[derive(Clone, Debug)]
+pub struct ConcurrencyLimitService<S> {
+    inner: S,
+    limits: ConcurrencyLimitLayer,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for ConcurrencyLimitService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response> + Clone + Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let permits = match self.limits.acquire(req.uri().path()) {
+            Ok(permits) => permits,
+            Err(err) => {
+                let response = rejection_response(&req, err);
+                return Box::pin(async move { Ok(response) });
+            }
+        };
+
+        let inner = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, inner);
+
+        Box::pin(async move {
+            let response = inner.oneshot(req).await?;
+            Ok(response.map(|body| {
+                Body::new(LimitedBody {
+                    body,
+                    _permits: permits,
+                })
+            }))
+        })
+    }
+}
+
+/// Encode the rejection in the request's protocol.
+///
+/// Inside `ConnectLayer` the context is available and `ConnectLayer` turns
+/// the response into gRPC if needed; outside it, the protocol is detected
+/// from the request and the response is translated here.
+pub(crate) fn rejection_response<B>(req: &Request<B>, err: ConnectError) -> Response {
+    if let Some(ctx) = req.extensions().get::<ConnectContext>() {
+        return err.into_response_with_context(ctx);
+    }
+    let protocol = detect_protocol(req);
+    let response = err.into_response_with_protocol(protocol);
+    if protocol.is_grpc() {
+        return super::grpc::into_grpc_response(response, protocol);
+    }
+    response
+}
+
+/// Slots held by an in-flight RPC.
+struct Permits {
+    _global: Option<OwnedSemaphorePermit>,
+    _method: Option<OwnedSemaphorePermit>,
+}
+
+/// Response body that releases the RPC's slots when finished or dropped.
+struct LimitedBody {
+    body: Body,
+    _permits: Permits,
+}
+
+impl http_body::Body for LimitedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        Pin::new(&mut self.body).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.body.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::{ConnectRequest, ConnectResponse};
+    use axum::Router;
+    use axum::http::{StatusCode, header};
+    use tokio::sync::Notify;
+
+    fn request(path: &str, content_type: &str) -> Request<Body> {
+        let body = if content_type == "application/json" {
+            Body::from("\"hi\"")
+        } else {
+            let mut frame = vec![0u8];
+            frame.extend_from_slice(&4u32.to_be_bytes());
+            frame.extend_from_slice(b"\"hi\"");
+            Body::from(frame)
+        };
+        Request::post(path)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(body)
+            .unwrap()
+    }
+
+    /// A router whose `Block` method waits until `release` is notified.
+    fn app(limits: ConcurrencyLimitLayer, release: Arc<Notify>) -> Router {
+        let block = move |ConnectRequest(req): ConnectRequest<String>| {
+            let release = release.clone();
+            async move {
+                release.notified().await;
+                Ok::<_, ConnectError>(ConnectResponse::new(req))
+            }
+        };
+        let echo = |ConnectRequest(req): ConnectRequest<String>| async move {
+            Ok::<_, ConnectError>(ConnectResponse::new(req))
+        };
+        Router::new()
+            .route("/test.Svc/Block", post_connect(block))
+            .route("/test.Svc/Echo", post_connect(echo))
+            .layer(ConnectLayer::new())
+            .layer(limits)
+    }
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_global_limit_rejects_with_resource_exhausted() {
+        let release = Arc::new(Notify::new());
+        let app = app(
+            ConcurrencyLimitLayer::new().max_in_flight(1),
+            release.clone(),
+        );
+
+        let blocked = tokio::spawn(
+            app.clone()
+                .oneshot(request("/test.Svc/Block", "application/json")),
+        );
+        tokio::task::yield_now().await;
+
+        let response = app
+            .clone()
+            .oneshot(request("/test.Svc/Echo", "application/json"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body_json(response).await["code"], "resource_exhausted");
+
+        release.notify_one();
+        let response = blocked.await.unwrap().unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        drop(response);
+
+        // The slot is free again once the first response is gone
+        let response = app
+            .oneshot(request("/test.Svc/Echo", "application/json"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_method_limit_only_affects_that_method() {
+        let release = Arc::new(Notify::new());
+        let app = app(
+            ConcurrencyLimitLayer::new().method("/test.Svc/Block", 1),
+            release.clone(),
+        );
+
+        let blocked = tokio::spawn(
+            app.clone()
+                .oneshot(request("/test.Svc/Block", "application/json")),
+        );
+        tokio::task::yield_now().await;
+
+        let response = app
+            .clone()
+            .oneshot(request("/test.Svc/Echo", "application/json"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Streaming requests get an EndStream error
+        let response = app
+            .clone()
+            .oneshot(request("/test.Svc/Block", "application/connect+json"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let end: serde_json::Value = serde_json::from_slice(&body[5..]).unwrap();
+        assert_eq!(end["error"]["code"], "resource_exhausted");
+        assert_eq!(
+            end["error"]["message"],
+            "too many concurrent requests for /test.Svc/Block"
+        );
+
+        release.notify_one();
+        blocked.await.unwrap().unwrap();
+    }
+}
@@ -5,20 +5,40 @@
 
 use crate::context::error::ProtocolNegotiationError;
 use crate::context::protocol::{can_handle_content_type, can_handle_get_encoding, detect_protocol};
-use crate::context::{CompressionConfig, ConnectContext, MessageLimits, ServerConfig};
-use crate::message::error::{Code, ConnectError};
-use axum::http::{Method, Request};
+use crate::context::{
+    CONNECT_ACCEPT_ENCODING, Codec, CompressionConfig, CompressionEncoding, ConnectContext,
+    ContextValues, GRPC_ACCEPT_ENCODING, MessageLimits, RequestProtocol, ServerConfig,
+    TimeoutOverride, advertised_body_encodings, advertised_message_encodings,
+};
+use crate::error_mapper::ErrorMapper;
+use crate::interceptor::{
+    HeaderWrapper, Interceptor, MessageInterceptor, MessageWrapper, RequestInterceptor,
+    SharedInterceptor, push_interceptor,
+};
+use crate::message::StreamWriteConfig;
+use crate::message::error::{Code, ConnectError, panic_error};
+use crate::shutdown::{InFlight, Shutdown};
+use axum::body::Body;
+use axum::http::{HeaderValue, Method, Request, header};
 use axum::response::Response;
+use bytes::Bytes;
+use futures::FutureExt;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
 use std::time::Duration;
 use std::{
     future::Future,
     pin::Pin,
     task::{Context as TaskContext, Poll},
 };
+use tokio_util::sync::DropGuard;
 use tower::{Layer, Service, ServiceExt};
 
 /// Layer that wraps services with Connect protocol detection and message limits.
 ///
+/// Besides Connect, the layer serves gRPC (`application/grpc`) and gRPC-Web
+/// (`application/grpc-web`) clients with the same handlers.
+///
 /// This layer:
 /// 1. Detects the protocol variant from the request (Content-Type header or query params)
 /// 2. Validates protocol version header (if configured)
@@ -45,9 +65,19 @@ use tower::{Layer, Service, ServiceExt};
 ///     );
 /// ```
 ///
-#[derive(Debug, Clone, Copy)]
+#[derive(Clone)]
 pub struct ConnectLayer {
     config: ServerConfig,
+    interceptor: Option<SharedInterceptor>,
+}
+
+impl std::fmt::Debug for ConnectLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectLayer")
+            .field("config", &self.config)
+            .field("interceptor", &self.interceptor.is_some())
+            .finish()
+    }
 }
 
 impl Default for ConnectLayer {
@@ -61,6 +91,7 @@ impl ConnectLayer {
     pub fn new() -> Self {
         Self {
             config: ServerConfig::default(),
+            interceptor: None,
         }
     }
 
@@ -104,6 +135,97 @@ impl ConnectLayer {
         self
     }
 
+    /// Set the timeout for requests that don't send `Connect-Timeout-Ms`
+    /// (or `grpc-timeout`).
+    ///
+    /// Unlike [`timeout`](Self::timeout), a longer client timeout replaces it.
+    pub fn default_timeout(mut self, timeout: Duration) -> Self {
+        self.config.default_timeout = Some(timeout);
+        self
+    }
+
+    /// Clamp client-provided timeouts to at most `timeout`.
+    ///
+    /// Requests without a client timeout are not affected; combine with
+    /// [`default_timeout`](Self::default_timeout) to bound those as well.
+    ///
+    /// ```rust,ignore
+    /// let layer = ConnectLayer::new()
+    ///     .default_timeout(Duration::from_secs(10))
+    ///     .max_timeout(Duration::from_secs(60));
+    /// ```
+    pub fn max_timeout(mut self, timeout: Duration) -> Self {
+        self.config.max_timeout = Some(timeout);
+        self
+    }
+
+    /// Set a per-message deadline for streaming request bodies.
+    ///
+    /// For client-streaming and bidirectional RPCs, each message of the
+    /// request stream must arrive within `timeout` of the previous one. If the
+    /// client stops sending without closing the stream, the handler's
+    /// [`Streaming`](crate::message::Streaming) yields a `deadline_exceeded`
+    /// error instead of waiting forever.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    /// use connectrpc_axum::ConnectLayer;
+    ///
+    /// let layer = ConnectLayer::new()
+    ///     .stream_message_timeout(Duration::from_secs(10));
+    /// ```
+    pub fn stream_message_timeout(mut self, timeout: Duration) -> Self {
+        self.config.stream_message_timeout = Some(timeout);
+        self
+    }
+
+    /// Set an inactivity timeout for streaming RPCs.
+    ///
+    /// A streaming RPC that neither receives nor sends a message for
+    /// `timeout` is ended with a `deadline_exceeded` error, so abandoned
+    /// bidi streams don't pile up. Unlike
+    /// [`stream_message_timeout`](Self::stream_message_timeout), messages in
+    /// either direction keep the stream alive; combine with heartbeats (see
+    /// [`StreamBody::heartbeat`](crate::message::StreamBody::heartbeat)) for
+    /// streams that are legitimately quiet.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let layer = ConnectLayer::new()
+    ///     .stream_idle_timeout(Duration::from_secs(300));
+    /// ```
+    pub fn stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.config.stream_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set how streaming response frames are flushed.
+    ///
+    /// Every message is flushed immediately by default. See
+    /// [`StreamWriteConfig`] for batching, and [`StreamBody::write_config`](crate::message::StreamBody::write_config)
+    /// to override it for one response.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::{ConnectLayer, StreamWriteConfig};
+    ///
+    /// let layer = ConnectLayer::new().stream_write(
+    ///     StreamWriteConfig::batched().flush_interval(Duration::from_millis(5)),
+    /// );
+    /// ```
+    pub fn stream_write(mut self, config: StreamWriteConfig) -> Self {
+        self.config.stream_write = config;
+        self
+    }
+
     /// Set compression configuration.
     ///
     /// Controls response compression behavior:
@@ -126,6 +248,167 @@ impl ConnectLayer {
         self.config.compression = config;
         self
     }
+
+    /// Register a custom compression codec (e.g. lz4 or snappy).
+    ///
+    /// The codec is negotiated by name through `Connect-Content-Encoding` /
+    /// `Connect-Accept-Encoding` (and the gRPC equivalents) for streaming
+    /// envelopes, and through `Content-Encoding` / `Accept-Encoding` for unary
+    /// bodies. Responses advertise it in the accept-encoding headers.
+    ///
+    /// Built-in encodings keep their built-in codec; registering a codec with
+    /// the same name as an earlier one replaces it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::ConnectLayer;
+    ///
+    /// let layer = ConnectLayer::new().codec(&Lz4Codec);
+    /// ```
+    pub fn codec(self, codec: &'static dyn Codec) -> Self {
+        self.custom_encoding(CompressionEncoding::custom(codec))
+    }
+
+    /// Register a custom encoding created with [`CompressionEncoding::custom`].
+    pub(crate) fn custom_encoding(mut self, encoding: CompressionEncoding) -> Self {
+        let mut codecs: Vec<_> = self
+            .config
+            .codecs
+            .iter()
+            .filter(|existing| existing.as_str() != encoding.as_str())
+            .copied()
+            .collect();
+        codecs.push(encoding);
+        self.config.codecs = codecs.into();
+        self
+    }
+
+    /// Convert handler panics into Connect `internal` errors.
+    ///
+    /// When enabled, a panic while the handler runs is caught and answered with
+    /// a Connect error response (or an EndStream error frame for streaming
+    /// RPCs). A panic in a server-streaming response stream ends the stream
+    /// with an `internal` EndStream error. The panic message is logged, not
+    /// sent to the client.
+    ///
+    /// Disabled by default: panics propagate to the HTTP server, which drops
+    /// the connection.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::ConnectLayer;
+    ///
+    /// let layer = ConnectLayer::new().catch_panic(true);
+    /// ```
+    pub fn catch_panic(mut self, enable: bool) -> Self {
+        self.config.catch_panic = enable;
+        self
+    }
+
+    /// Attach a `google.rpc.DebugInfo` detail to errors that don't come from
+    /// a handler's `ConnectError`.
+    ///
+    /// Covers caught panics (see [`catch_panic`](Self::catch_panic)),
+    /// extractor rejections that aren't `ConnectError`s, and internal
+    /// failures while encoding a response. The detail carries the panic
+    /// message, rejection or error chain, and a backtrace where one is
+    /// available. Rejections are re-encoded as Connect errors so the detail
+    /// reaches the client; redirects and other non-error rejections pass
+    /// through unchanged.
+    ///
+    /// Disabled by default. Debug details expose server internals: enable
+    /// this in development and staging, not in production.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::ConnectLayer;
+    ///
+    /// let layer = ConnectLayer::new().catch_panic(true).debug_errors(true);
+    /// ```
+    pub fn debug_errors(mut self, enable: bool) -> Self {
+        self.config.debug_errors = enable;
+        self
+    }
+
+    /// Convert application errors returned by handlers with `mapper`.
+    ///
+    /// Handlers may return any `std::error::Error` instead of `ConnectError`;
+    /// see [`ErrorMapper`]. Without a matching mapping the error becomes an
+    /// `internal` error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::{ConnectError, ConnectLayer, ErrorMapper};
+    ///
+    /// let layer = ConnectLayer::new().error_mapper(
+    ///     ErrorMapper::new().map(|err: &ValidationError| ConnectError::new_invalid_argument(err.to_string())),
+    /// );
+    /// ```
+    pub fn error_mapper(mut self, mapper: ErrorMapper) -> Self {
+        self.config.error_mapper = Some(Arc::new(mapper));
+        self
+    }
+
+    /// Drain RPCs gracefully with the given [`Shutdown`] coordinator.
+    ///
+    /// Once [`Shutdown::drain`] is called, new RPCs are refused with
+    /// `unavailable` and a retry delay, in-flight RPCs get the grace period to
+    /// finish, and response streams still open after it are ended with an
+    /// `unavailable` EndStream frame. See [`crate::shutdown`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::{ConnectLayer, Shutdown};
+    ///
+    /// let shutdown = Shutdown::new().grace_period(Duration::from_secs(10));
+    /// let layer = ConnectLayer::new().shutdown(shutdown.clone());
+    /// ```
+    pub fn shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.config.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Add a header-level interceptor.
+    ///
+    /// Interceptors run in the order they are added for requests, and in
+    /// reverse order for responses. See [`crate::interceptor`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::ConnectLayer;
+    ///
+    /// let layer = ConnectLayer::new()
+    ///     .with_interceptor(AuthInterceptor);
+    /// ```
+    pub fn with_interceptor<I: Interceptor>(self, interceptor: I) -> Self {
+        self.with_interceptor_internal(HeaderWrapper(interceptor))
+    }
+
+    /// Add a message-level interceptor with access to decoded messages.
+    ///
+    /// See [`with_interceptor`](Self::with_interceptor) for ordering.
+    pub fn with_message_interceptor<I: MessageInterceptor>(self, interceptor: I) -> Self {
+        self.with_interceptor_internal(MessageWrapper(interceptor))
+    }
+
+    fn with_interceptor_internal<I>(mut self, interceptor: I) -> Self
+    where
+        I: crate::interceptor::InterceptorInternal,
+    {
+        self.interceptor = Some(push_interceptor(self.interceptor.take(), interceptor));
+        self
+    }
+
+    /// Append an already-composed chain (used by `MakeServiceBuilder`).
+    pub(crate) fn with_shared_interceptor(self, interceptor: SharedInterceptor) -> Self {
+        self.with_interceptor_internal(interceptor)
+    }
 }
 
 impl<S> Layer<S> for ConnectLayer {
@@ -134,7 +417,8 @@ impl<S> Layer<S> for ConnectLayer {
     fn layer(&self, inner: S) -> Self::Service {
         ConnectService {
             inner,
-            config: self.config,
+            config: self.config.clone(),
+            interceptor: self.interceptor.clone(),
         }
     }
 }
@@ -171,10 +455,21 @@ fn check_protocol_negotiation<B>(req: &Request<B>) -> Option<ProtocolNegotiation
 // ============================================================================
 
 /// Service wrapper that provides per-request protocol context and message limits.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ConnectService<S> {
     inner: S,
     config: ServerConfig,
+    interceptor: Option<SharedInterceptor>,
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for ConnectService<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectService")
+            .field("inner", &self.inner)
+            .field("config", &self.config)
+            .field("interceptor", &self.interceptor.is_some())
+            .finish()
+    }
 }
 
 impl<S, ReqBody> Service<Request<ReqBody>> for ConnectService<S>
@@ -192,7 +487,30 @@ where
         self.inner.poll_ready(cx)
     }
 
-    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+    #[cfg(not(feature = "tracing"))]
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        self.dispatch(req)
+    }
+
+    #[cfg(feature = "tracing")]
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let rpc = super::trace::RpcSpan::new(&req);
+        let call = rpc.span.in_scope(|| self.dispatch(req));
+        Box::pin(rpc.instrument(call))
+    }
+}
+
+impl<S> ConnectService<S> {
+    fn dispatch<ReqBody>(
+        &mut self,
+        mut req: Request<ReqBody>,
+    ) -> Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>
+    where
+        S: Service<Request<ReqBody>, Response = Response> + Clone + Send + 'static,
+        S::Error: Send + 'static,
+        S::Future: Send + 'static,
+        ReqBody: Send + 'static,
+    {
         // 0. Pre-protocol validation (can produce HTTP 415)
         if let Some(nego_err) = check_protocol_negotiation(&req) {
             let response = nego_err.into_response();
@@ -200,27 +518,82 @@ where
         }
 
         // 1. Build request context from request headers
-        let request_ctx = match ConnectContext::from_request(&req, &self.config) {
+        let mut request_ctx = match ConnectContext::from_request(&req, &self.config) {
             Ok(ctx) => ctx,
             Err(err) => {
-                let response = err.into_response();
+                let protocol = err.protocol();
+                let response = finish_response(err.into_response(), protocol, &self.config.codecs);
                 return Box::pin(async move { Ok(response) });
             }
         };
 
+        // Move values stashed by earlier middleware into the context
+        if let Some(values) = req.extensions_mut().remove::<ContextValues>() {
+            request_ctx.values = values;
+        }
+
         // 2. Validate protocol requirements
         if let Err(err) = request_ctx.validate(&req) {
-            let response = err.into_response();
+            let response = finish_response(
+                err.into_response(),
+                request_ctx.protocol,
+                &self.config.codecs,
+            );
             return Box::pin(async move { Ok(response) });
         }
 
-        // 3. Extract values needed for async block before moving context
+        // 3. Refuse new RPCs while shutting down; count the others until done
+        let in_flight = match self.config.shutdown.as_ref().map(Shutdown::start) {
+            Some(Ok(in_flight)) => Some(in_flight),
+            Some(Err(err)) => {
+                let response = finish_response(
+                    err.into_response_with_context(&request_ctx),
+                    request_ctx.protocol,
+                    &self.config.codecs,
+                );
+                return Box::pin(async move { Ok(response) });
+            }
+            None => None,
+        };
+
+        // 4. Run request interceptors and bind the chain to the request
+        if let Some(chain) = &self.interceptor {
+            let mut interceptor = RequestInterceptor::new(
+                chain.clone(),
+                req.uri().path(),
+                request_ctx.protocol,
+                request_ctx.peer.clone(),
+            );
+            if let Err(err) =
+                interceptor.request_headers(req.headers_mut(), &mut request_ctx.values)
+            {
+                let response = finish_response(
+                    err.into_response_with_context(&request_ctx),
+                    request_ctx.protocol,
+                    &self.config.codecs,
+                );
+                return Box::pin(async move { Ok(response) });
+            }
+            request_ctx.interceptor = Some(interceptor);
+        }
+
+        // 5. Extract values needed for async block before moving context
         let timeout = request_ctx.timeout;
         let protocol = request_ctx.protocol;
         let send_max_bytes = request_ctx.limits.get_send_max_bytes();
+        let interceptor = request_ctx.interceptor.clone();
+        let catch_panic = request_ctx.catch_panic;
+        let debug_errors = request_ctx.debug_errors;
+        let cancellation = request_ctx.cancellation.clone();
+        let codecs = self.config.codecs.clone();
 
-        // 4. Store context in request extensions
+        // 6. Store context in request extensions; a per-method timeout
+        // (see `MethodConfig`) may claim the deadline from us
         req.extensions_mut().insert(request_ctx);
+        let timeout_override = TimeoutOverride::default();
+        if timeout.is_some() {
+            req.extensions_mut().insert(timeout_override.clone());
+        }
 
         // Clone inner service for the async block
         let inner = self.inner.clone();
@@ -228,12 +601,33 @@ where
         let inner = std::mem::replace(&mut self.inner, inner);
 
         Box::pin(async move {
+            // Cancels the request token when this future (client disconnect,
+            // timeout) or, later, the response body is dropped
+            let cancel_guard = cancellation.clone().drop_guard();
+
+            // Convert handler panics into internal errors if configured
+            let call = async move {
+                if !catch_panic {
+                    return inner.oneshot(req).await;
+                }
+                match AssertUnwindSafe(inner.oneshot(req)).catch_unwind().await {
+                    Ok(result) => result,
+                    Err(panic) => {
+                        let err = panic_error(panic.as_ref(), debug_errors);
+                        Ok(err.into_response_with_send_limit(protocol, send_max_bytes))
+                    }
+                }
+            };
+
             // Apply timeout if configured
-            match timeout {
+            let result = match timeout {
                 Some(duration) => {
-                    match tokio::time::timeout(duration, inner.oneshot(req)).await {
+                    let mut call = std::pin::pin!(call);
+                    match tokio::time::timeout(duration, call.as_mut()).await {
                         Ok(result) => result,
+                        Err(_elapsed) if timeout_override.is_claimed() => call.await,
                         Err(_elapsed) => {
+                            cancellation.cancel();
                             // Timeout exceeded - return Connect deadline_exceeded error
                             let err = ConnectError::new(
                                 Code::DeadlineExceeded,
@@ -243,8 +637,417 @@ where
                         }
                     }
                 }
-                None => inner.oneshot(req).await,
-            }
+                None => call.await,
+            };
+
+            // Run response interceptors (reverse order)
+            let result = match (result, interceptor) {
+                (Ok(mut response), Some(interceptor)) => {
+                    let status = response.status();
+                    match interceptor.response_headers(status, response.headers_mut()) {
+                        Ok(()) => Ok(response),
+                        Err(err) => Ok(err.into_response_with_send_limit(protocol, send_max_bytes)),
+                    }
+                }
+                (result, _) => result,
+            };
+
+            result.map(|response| {
+                finish_response(response, protocol, &codecs).map(|body| {
+                    Body::new(CancelOnDrop {
+                        body,
+                        _guard: cancel_guard,
+                        _in_flight: in_flight,
+                    })
+                })
+            })
         })
     }
 }
+
+/// Advertise the accepted encodings and, for gRPC, move the status from the
+/// EndStream frame into trailers.
+fn finish_response(
+    mut response: Response,
+    protocol: RequestProtocol,
+    codecs: &[CompressionEncoding],
+) -> Response {
+    let (name, encodings) = if protocol.is_grpc() {
+        (GRPC_ACCEPT_ENCODING, advertised_message_encodings(codecs))
+    } else if protocol.is_streaming() {
+        (
+            CONNECT_ACCEPT_ENCODING,
+            advertised_message_encodings(codecs),
+        )
+    } else {
+        (
+            header::ACCEPT_ENCODING.as_str(),
+            advertised_body_encodings(codecs),
+        )
+    };
+    if let Some(encodings) = encodings
+        && !response.headers().contains_key(name)
+        && let Ok(value) = HeaderValue::from_str(&encodings)
+    {
+        response.headers_mut().insert(name, value);
+    }
+
+    if protocol.is_grpc() {
+        super::grpc::into_grpc_response(response, protocol)
+    } else {
+        response
+    }
+}
+
+/// Response body that keeps the request's cancellation token alive until the
+/// body is finished or dropped (e.g. a streaming response the client abandons).
+///
+/// It also keeps the RPC counted as in flight for a graceful shutdown.
+struct CancelOnDrop {
+    body: Body,
+    _guard: DropGuard,
+    _in_flight: Option<InFlight>,
+}
+
+impl http_body::Body for CancelOnDrop {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        Pin::new(&mut self.body).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.body.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::message::{ConnectRequest, ConnectResponse, StreamBody};
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{StatusCode, header};
+    use futures::{Stream, StreamExt};
+
+    async fn panic_unary(
+        ConnectRequest(_): ConnectRequest<String>,
+    ) -> Result<ConnectResponse<String>, ConnectError> {
+        panic!("boom");
+    }
+
+    async fn panic_stream(
+        ConnectRequest(_): ConnectRequest<String>,
+    ) -> Result<
+        ConnectResponse<StreamBody<impl Stream<Item = Result<String, ConnectError>>>>,
+        ConnectError,
+    > {
+        let stream = futures::stream::iter(0..2).map(|i| {
+            if i == 1 {
+                panic!("boom");
+            }
+            Ok(String::from("first"))
+        });
+        Ok(ConnectResponse::new(StreamBody::new(stream)))
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route("/test.Svc/Unary", post_connect(panic_unary))
+            .route("/test.Svc/Stream", post_connect(panic_stream))
+            .layer(ConnectLayer::new().catch_panic(true))
+    }
+
+    async fn body_bytes(response: Response) -> axum::body::Bytes {
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_catch_panic_unary() {
+        let req = Request::post("/test.Svc/Unary")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("\"hi\""))
+            .unwrap();
+
+        let response = app().oneshot(req).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(body["code"], "internal");
+        assert_eq!(body["message"], "handler panicked");
+    }
+
+    #[tokio::test]
+    async fn test_catch_panic_server_stream() {
+        let mut frame = vec![0u8];
+        frame.extend_from_slice(&4u32.to_be_bytes());
+        frame.extend_from_slice(b"\"hi\"");
+        let req = Request::post("/test.Svc/Stream")
+            .header(header::CONTENT_TYPE, "application/connect+json")
+            .body(Body::from(frame))
+            .unwrap();
+
+        let response = app().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = body_bytes(response).await;
+        // First message frame, then an EndStream frame carrying the error
+        let first_len = u32::from_be_bytes(body[1..5].try_into().unwrap()) as usize;
+        assert_eq!(&body[5..5 + first_len], b"\"first\"");
+        let end = &body[5 + first_len..];
+        assert_eq!(end[0], crate::message::request::envelope_flags::END_STREAM);
+        let end: serde_json::Value = serde_json::from_slice(&end[5..]).unwrap();
+        assert_eq!(end["error"]["code"], "internal");
+        assert_eq!(end["error"]["message"], "handler panicked");
+    }
+
+    #[tokio::test]
+    async fn test_debug_errors() {
+        let app = |debug: bool| {
+            Router::new()
+                .route("/test.Svc/Unary", post_connect(panic_unary))
+                .route(
+                    "/test.Svc/Extension",
+                    post_connect(
+                        |axum::Extension(_): axum::Extension<u32>,
+                         ConnectRequest(req): ConnectRequest<String>| async move {
+                            Ok::<_, ConnectError>(ConnectResponse::new(req))
+                        },
+                    ),
+                )
+                .layer(ConnectLayer::new().catch_panic(true).debug_errors(debug))
+        };
+        let request = |path| {
+            Request::post(path)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from("\"hi\""))
+                .unwrap()
+        };
+
+        let response = app(true).oneshot(request("/test.Svc/Unary")).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(body["message"], "handler panicked");
+        assert_eq!(body["details"][0]["type"], "google.rpc.DebugInfo");
+
+        // Non-Connect rejections are re-encoded as Connect errors
+        let response = app(true)
+            .oneshot(request("/test.Svc/Extension"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(body["code"], "unknown");
+        assert_eq!(body["details"][0]["type"], "google.rpc.DebugInfo");
+
+        // Without debug errors they pass through untouched
+        let response = app(false)
+            .oneshot(request("/test.Svc/Extension"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(serde_json::from_slice::<serde_json::Value>(&body_bytes(response).await).is_err());
+    }
+
+    /// An extractor whose rejection isn't `Send`.
+    struct Tenant;
+
+    struct TenantRejection(std::rc::Rc<str>);
+
+    impl axum::response::IntoResponse for TenantRejection {
+        fn into_response(self) -> Response {
+            (StatusCode::UNAUTHORIZED, self.0.to_string()).into_response()
+        }
+    }
+
+    impl<S: Sync> axum::extract::FromRequestParts<S> for Tenant {
+        type Rejection = TenantRejection;
+
+        async fn from_request_parts(
+            _parts: &mut axum::http::request::Parts,
+            _state: &S,
+        ) -> Result<Self, Self::Rejection> {
+            Err(TenantRejection("missing tenant".into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_debug_errors_non_send_rejection() {
+        let app = Router::new()
+            .route(
+                "/test.Svc/Unary",
+                post_connect(
+                    |_: Tenant, ConnectRequest(req): ConnectRequest<String>| async move {
+                        Ok::<_, ConnectError>(ConnectResponse::new(req))
+                    },
+                ),
+            )
+            .layer(ConnectLayer::new().debug_errors(true));
+        let request = Request::post("/test.Svc/Unary")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("\"hi\""))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(body["code"], "unauthenticated");
+        assert_eq!(body["message"], "extractor rejection");
+        assert_eq!(body["details"][0]["type"], "google.rpc.DebugInfo");
+    }
+
+    /// Reverses the bytes; good enough to check the codec is used.
+    struct ReverseCodec;
+
+    impl Codec for ReverseCodec {
+        fn name(&self) -> &'static str {
+            "reverse"
+        }
+
+        fn compress(&self, data: &[u8]) -> std::io::Result<Bytes> {
+            Ok(data.iter().rev().copied().collect())
+        }
+
+        fn decompress(&self, data: &[u8]) -> std::io::Result<Bytes> {
+            self.compress(data)
+        }
+    }
+
+    async fn echo(
+        ConnectRequest(req): ConnectRequest<String>,
+    ) -> Result<ConnectResponse<String>, ConnectError> {
+        Ok(ConnectResponse::new(req))
+    }
+
+    async fn echo_stream(
+        ConnectRequest(req): ConnectRequest<String>,
+    ) -> Result<
+        ConnectResponse<StreamBody<impl Stream<Item = Result<String, ConnectError>>>>,
+        ConnectError,
+    > {
+        Ok(ConnectResponse::new(StreamBody::new(
+            futures::stream::iter([Ok(req)]),
+        )))
+    }
+
+    fn codec_app() -> Router {
+        Router::new()
+            .route("/test.Svc/Echo", post_connect(echo))
+            .route("/test.Svc/EchoStream", post_connect(echo_stream))
+            .layer(ConnectLayer::new().codec(&ReverseCodec))
+    }
+
+    #[tokio::test]
+    async fn test_custom_codec_unary() {
+        let req = Request::post("/test.Svc/Echo")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_ENCODING, "reverse")
+            .header(header::ACCEPT_ENCODING, "reverse")
+            .body(Body::from("\"ih\""))
+            .unwrap();
+
+        let response = codec_app().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "reverse");
+        assert!(
+            response.headers()[header::ACCEPT_ENCODING]
+                .to_str()
+                .unwrap()
+                .contains("reverse")
+        );
+        assert_eq!(body_bytes(response).await, "\"ih\"");
+    }
+
+    #[tokio::test]
+    async fn test_custom_codec_streaming() {
+        let mut frame = vec![1u8];
+        frame.extend_from_slice(&4u32.to_be_bytes());
+        frame.extend_from_slice(b"\"ih\"");
+        let req = Request::post("/test.Svc/EchoStream")
+            .header(header::CONTENT_TYPE, "application/connect+json")
+            .header(crate::context::CONNECT_CONTENT_ENCODING, "reverse")
+            .header(CONNECT_ACCEPT_ENCODING, "lz4, reverse")
+            .body(Body::from(frame))
+            .unwrap();
+
+        let response = codec_app().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[crate::context::CONNECT_CONTENT_ENCODING],
+            "reverse"
+        );
+        assert!(
+            response.headers()[CONNECT_ACCEPT_ENCODING]
+                .to_str()
+                .unwrap()
+                .contains("reverse")
+        );
+
+        let body = body_bytes(response).await;
+        assert_eq!(body[0], 1);
+        assert_eq!(&body[5..9], b"\"ih\"");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_codec_lists_custom_codecs() {
+        let req = Request::post("/test.Svc/EchoStream")
+            .header(header::CONTENT_TYPE, "application/connect+json")
+            .header(crate::context::CONNECT_CONTENT_ENCODING, "lz4")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = codec_app().oneshot(req).await.unwrap();
+        let body = body_bytes(response).await;
+        let end: serde_json::Value = serde_json::from_slice(&body[5..]).unwrap();
+        assert_eq!(end["error"]["code"], "unimplemented");
+        assert!(
+            end["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("supported encodings are reverse, ")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_require_protocol_header() {
+        let request = |version: Option<&str>| {
+            let mut req =
+                Request::post("/test.Svc/Echo").header(header::CONTENT_TYPE, "application/json");
+            if let Some(version) = version {
+                req = req.header(crate::context::CONNECT_PROTOCOL_VERSION_HEADER, version);
+            }
+            req.body(Body::from("\"hi\"")).unwrap()
+        };
+        let app = |require: bool| {
+            Router::new()
+                .route("/test.Svc/Echo", post_connect(echo))
+                .layer(ConnectLayer::new().require_protocol_header(require))
+        };
+
+        // Strict: the header is required
+        let response = app(true).oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(body["code"], "invalid_argument");
+
+        let response = app(true).oneshot(request(Some("1"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Lenient: the header is optional, but must be valid when present
+        let response = app(false).oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app(false).oneshot(request(Some("2"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,597 @@
+//! gRPC and gRPC-Web response framing.
+//!
+//! Handlers and pipelines answer gRPC requests in the Connect streaming wire
+//! format: length-prefixed messages followed by an EndStream frame. gRPC uses
+//! the same message frames but reports the status out of band, so
+//! [`ConnectLayer`](super::ConnectLayer) rewrites the EndStream frame on the way out:
+//!
+//! - **gRPC**: HTTP/2 trailers (`grpc-status`, `grpc-message`,
+//!   `grpc-status-details-bin` and the response trailers)
+//! - **gRPC-Web**: a trailer frame (flag `0x80`) holding the same fields as
+//!   `name: value` lines
+//!
+//! A response that is only an EndStream frame (the stream failed before its
+//! first message) becomes a trailers-only response, with the status in the
+//! headers. `Connect-Content-Encoding` becomes `grpc-encoding`. Responses that are not
+//! framed (e.g. a non-Connect extractor rejection) become trailers-only
+//! responses with a status mapped from the HTTP status code.
+
+use crate::context::{GRPC_ENCODING, RequestProtocol};
+use crate::message::error::{Code, EndStreamOnly};
+use crate::message::request::envelope_flags;
+use axum::body::Body;
+use axum::http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::response::Response;
+use bytes::{Buf, Bytes, BytesMut};
+use http_body::Frame;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Flag marking the trailer frame of a gRPC-Web response.
+const GRPC_WEB_TRAILERS: u8 = 0x80;
+
+/// Header carrying the gRPC status code.
+const GRPC_STATUS: &str = "grpc-status";
+
+/// Header carrying the percent-encoded gRPC status message.
+const GRPC_MESSAGE: &str = "grpc-message";
+
+/// Header carrying the binary `google.rpc.Status` with error details.
+const GRPC_STATUS_DETAILS_BIN: &str = "grpc-status-details-bin";
+
+/// Rewrite a Connect streaming response into a gRPC or gRPC-Web response.
+pub(crate) fn into_grpc_response(response: Response, protocol: RequestProtocol) -> Response {
+    let content_type = protocol.response_content_type();
+    let framed = response.status() == StatusCode::OK
+        && response
+            .headers()
+            .get(CONTENT_TYPE)
+            .is_some_and(|value| value == content_type);
+    if !framed {
+        return trailers_only_response(response, content_type);
+    }
+
+    let (mut parts, body) = response.into_parts();
+    parts.headers.remove(CONTENT_LENGTH);
+
+    // Failed before the first message: put the status in the headers
+    if let Some(EndStreamOnly(frame)) = parts.extensions.remove::<EndStreamOnly>() {
+        parts.headers.remove("connect-content-encoding");
+        let trailers = end_stream_trailers(frame.get(5..).unwrap_or_default());
+        for (name, value) in &trailers {
+            parts.headers.append(name, value.clone());
+        }
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    if let Some(encoding) = parts.headers.remove("connect-content-encoding") {
+        parts.headers.insert(GRPC_ENCODING, encoding);
+    }
+
+    let body = GrpcBody {
+        inner: body,
+        buffer: BytesMut::new(),
+        web: protocol.is_grpc_web(),
+        done: false,
+    };
+    Response::from_parts(parts, Body::new(body))
+}
+
+/// Answer with the status in the headers and no body.
+fn trailers_only_response(response: Response, content_type: &'static str) -> Response {
+    let status = response.status();
+    let (mut parts, _) = response.into_parts();
+    let code = Code::from_http_status_connect(status);
+    parts.status = StatusCode::OK;
+    parts.headers.remove(CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    parts.headers.insert(GRPC_STATUS, status_value(code));
+    if code != Code::Ok
+        && let Some(reason) = status.canonical_reason()
+        && let Ok(value) = HeaderValue::from_str(&percent_encode(reason))
+    {
+        parts.headers.insert(GRPC_MESSAGE, value);
+    }
+    Response::from_parts(parts, Body::empty())
+}
+
+fn status_value(code: Code) -> HeaderValue {
+    HeaderValue::from(code.to_grpc_code())
+}
+
+/// Response body that passes message frames through and turns the EndStream
+/// frame into gRPC trailers.
+struct GrpcBody {
+    inner: Body,
+    buffer: BytesMut,
+    web: bool,
+    done: bool,
+}
+
+impl GrpcBody {
+    /// Split the next complete frame off the buffer.
+    fn next_frame(&mut self) -> Option<(u8, Bytes)> {
+        if self.buffer.len() < 5 {
+            return None;
+        }
+        let length = u32::from_be_bytes([
+            self.buffer[1],
+            self.buffer[2],
+            self.buffer[3],
+            self.buffer[4],
+        ]) as usize;
+        if self.buffer.len() < 5 + length {
+            return None;
+        }
+        let flags = self.buffer[0];
+        Some((flags, self.buffer.split_to(5 + length).freeze()))
+    }
+
+    fn trailers_frame(&mut self, trailers: HeaderMap) -> Frame<Bytes> {
+        self.done = true;
+        if self.web {
+            Frame::data(web_trailers_frame(&trailers))
+        } else {
+            Frame::trailers(trailers)
+        }
+    }
+}
+
+impl http_body::Body for GrpcBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = &mut *self;
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            if let Some((flags, mut frame)) = this.next_frame() {
+                if flags & envelope_flags::END_STREAM == 0 {
+                    return Poll::Ready(Some(Ok(Frame::data(frame))));
+                }
+                frame.advance(5);
+                let trailers = end_stream_trailers(&frame);
+                return Poll::Ready(Some(Ok(this.trailers_frame(trailers))));
+            }
+
+            match std::task::ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    if let Some(data) = frame.data_ref() {
+                        this.buffer.extend_from_slice(data);
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => {
+                    // The Connect pipeline always ends with EndStream
+                    let mut trailers = HeaderMap::new();
+                    trailers.insert(GRPC_STATUS, status_value(Code::Internal));
+                    trailers.insert(
+                        GRPC_MESSAGE,
+                        HeaderValue::from_static("response ended without status"),
+                    );
+                    return Poll::Ready(Some(Ok(this.trailers_frame(trailers))));
+                }
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.done
+    }
+}
+
+// ============================================================================
+// EndStream → trailers
+// ============================================================================
+
+#[derive(Deserialize, Default)]
+struct EndStream {
+    error: Option<EndStreamError>,
+    #[serde(default)]
+    metadata: HashMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct EndStreamError {
+    code: String,
+    message: Option<String>,
+    #[serde(default)]
+    details: Vec<EndStreamDetail>,
+}
+
+#[derive(Deserialize)]
+struct EndStreamDetail {
+    #[serde(rename = "type")]
+    type_name: String,
+    value: String,
+}
+
+/// `google.rpc.Status`, sent base64-encoded in `grpc-status-details-bin`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<Any>,
+}
+
+/// `google.protobuf.Any`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct Any {
+    #[prost(string, tag = "1")]
+    type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    value: Vec<u8>,
+}
+
+/// Build gRPC trailers from the JSON payload of a Connect EndStream frame.
+fn end_stream_trailers(payload: &[u8]) -> HeaderMap {
+    use base64::Engine as _;
+    use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+    use prost::Message as _;
+
+    let end: EndStream = serde_json::from_slice(payload).unwrap_or_default();
+
+    let mut trailers = HeaderMap::new();
+    for (name, values) in &end.metadata {
+        let Ok(name) = HeaderName::try_from(name.as_str()) else {
+            continue;
+        };
+        for value in values {
+            if let Ok(value) = HeaderValue::from_str(value) {
+                trailers.append(name.clone(), value);
+            }
+        }
+    }
+
+    let Some(error) = end.error else {
+        trailers.insert(GRPC_STATUS, status_value(Code::Ok));
+        return trailers;
+    };
+
+    let code = error.code.parse().unwrap_or(Code::Unknown);
+    let message = error.message.unwrap_or_default();
+    trailers.insert(GRPC_STATUS, status_value(code));
+    if !message.is_empty()
+        && let Ok(value) = HeaderValue::from_str(&percent_encode(&message))
+    {
+        trailers.insert(GRPC_MESSAGE, value);
+    }
+
+    if !error.details.is_empty() {
+        let details = error
+            .details
+            .iter()
+            .filter_map(|detail| {
+                let value = STANDARD_NO_PAD
+                    .decode(detail.value.trim_end_matches('='))
+                    .ok()?;
+                Some(Any {
+                    type_url: format!("type.googleapis.com/{}", detail.type_name),
+                    value,
+                })
+            })
+            .collect();
+        let status = RpcStatus {
+            code: code.to_grpc_code() as i32,
+            message,
+            details,
+        };
+        let encoded = STANDARD.encode(status.encode_to_vec());
+        if let Ok(value) = HeaderValue::from_str(&encoded) {
+            trailers.insert(GRPC_STATUS_DETAILS_BIN, value);
+        }
+    }
+
+    trailers
+}
+
+/// Encode trailers as a gRPC-Web trailer frame.
+fn web_trailers_frame(trailers: &HeaderMap) -> Bytes {
+    let mut block = Vec::new();
+    for (name, value) in trailers {
+        block.extend_from_slice(name.as_str().as_bytes());
+        block.extend_from_slice(b": ");
+        block.extend_from_slice(value.as_bytes());
+        block.extend_from_slice(b"\r\n");
+    }
+
+    let mut frame = Vec::with_capacity(5 + block.len());
+    frame.push(GRPC_WEB_TRAILERS);
+    frame.extend_from_slice(&(block.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&block);
+    Bytes::from(frame)
+}
+
+/// Percent-encode a `grpc-message` value.
+///
+/// Bytes outside printable ASCII, and `%` itself, are encoded as `%XX`.
+fn percent_encode(message: &str) -> String {
+    let mut encoded = String::with_capacity(message.len());
+    for &byte in message.as_bytes() {
+        if (0x20..=0x7e).contains(&byte) && byte != b'%' {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::{ConnectError, ConnectRequest, ConnectResponse, StreamBody};
+    use axum::Router;
+    use axum::http::{Request, header};
+    use futures::Stream;
+    use http_body_util::BodyExt;
+    use prost::Message as _;
+    use tower::ServiceExt;
+
+    #[derive(Clone, PartialEq, prost::Message, serde::Serialize, serde::Deserialize)]
+    struct Echo {
+        #[prost(string, tag = "1")]
+        text: String,
+    }
+
+    async fn unary(
+        ConnectRequest(req): ConnectRequest<Echo>,
+    ) -> Result<ConnectResponse<Echo>, ConnectError> {
+        if req.text == "fail" {
+            return Err(ConnectError::new(Code::NotFound, "no such échó"));
+        }
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-trailer", HeaderValue::from_static("done"));
+        Ok(ConnectResponse::new(req).with_trailers(trailers))
+    }
+
+    async fn server_stream(
+        ConnectRequest(req): ConnectRequest<Echo>,
+    ) -> Result<
+        ConnectResponse<StreamBody<impl Stream<Item = Result<Echo, ConnectError>>>>,
+        ConnectError,
+    > {
+        let stream = futures::stream::iter(vec![
+            Ok(req.clone()),
+            Ok(req),
+            Err(ConnectError::new(Code::Aborted, "stop")),
+        ]);
+        Ok(ConnectResponse::new(StreamBody::new(stream)))
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route("/test.Svc/Unary", post_connect(unary))
+            .route("/test.Svc/Stream", post_connect(server_stream))
+            .layer(ConnectLayer::new())
+    }
+
+    fn frame(text: &str) -> Vec<u8> {
+        let payload = Echo {
+            text: text.to_owned(),
+        }
+        .encode_to_vec();
+        let mut frame = vec![0u8];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        frame
+    }
+
+    fn request(path: &str, content_type: &str, text: &str) -> Request<Body> {
+        Request::post(path)
+            .header(header::CONTENT_TYPE, content_type)
+            .header("te", "trailers")
+            .body(Body::from(frame(text)))
+            .unwrap()
+    }
+
+    /// Split a body into message payloads.
+    fn messages(mut body: &[u8]) -> Vec<(u8, Vec<u8>)> {
+        let mut messages = Vec::new();
+        while !body.is_empty() {
+            let len = u32::from_be_bytes(body[1..5].try_into().unwrap()) as usize;
+            messages.push((body[0], body[5..5 + len].to_vec()));
+            body = &body[5 + len..];
+        }
+        messages
+    }
+
+    #[tokio::test]
+    async fn test_grpc_unary() {
+        let response = app()
+            .oneshot(request("/test.Svc/Unary", "application/grpc", "hi"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/grpc");
+
+        let collected = response.into_body().collect().await.unwrap();
+        let trailers = collected.trailers().cloned().unwrap();
+        let body = collected.to_bytes();
+
+        let messages = messages(&body);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(Echo::decode(&messages[0].1[..]).unwrap().text, "hi");
+        assert_eq!(trailers[GRPC_STATUS], "0");
+        assert_eq!(trailers["x-trailer"], "done");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_unary_error() {
+        let response = app()
+            .oneshot(request("/test.Svc/Unary", "application/grpc+proto", "fail"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Trailers-only: the status is in the headers
+        assert_eq!(response.headers()[GRPC_STATUS], "5");
+        assert_eq!(response.headers()[GRPC_MESSAGE], "no such %C3%A9ch%C3%B3");
+        let collected = response.into_body().collect().await.unwrap();
+        assert!(collected.trailers().is_none());
+        assert!(collected.to_bytes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_grpc_stream_failing_before_first_message_is_trailers_only() {
+        async fn fail_first(
+            ConnectRequest(_): ConnectRequest<Echo>,
+        ) -> Result<
+            ConnectResponse<StreamBody<impl Stream<Item = Result<Echo, ConnectError>>>>,
+            ConnectError,
+        > {
+            let stream =
+                futures::stream::iter(vec![Err(ConnectError::new(Code::PermissionDenied, "nope"))]);
+            Ok(ConnectResponse::new(StreamBody::new(stream)))
+        }
+
+        let app = Router::new()
+            .route("/test.Svc/Fail", post_connect(fail_first))
+            .layer(ConnectLayer::new());
+        let response = app
+            .oneshot(request("/test.Svc/Fail", "application/grpc", "hi"))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[GRPC_STATUS], "7");
+        assert_eq!(response.headers()[GRPC_MESSAGE], "nope");
+        let collected = response.into_body().collect().await.unwrap();
+        assert!(collected.trailers().is_none());
+        assert!(collected.to_bytes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_grpc_server_stream() {
+        let response = app()
+            .oneshot(request("/test.Svc/Stream", "application/grpc", "hi"))
+            .await
+            .unwrap();
+
+        let collected = response.into_body().collect().await.unwrap();
+        let trailers = collected.trailers().cloned().unwrap();
+        assert_eq!(messages(&collected.to_bytes()).len(), 2);
+        assert_eq!(trailers[GRPC_STATUS], "10");
+        assert_eq!(trailers[GRPC_MESSAGE], "stop");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_web_trailer_frame() {
+        let response = app()
+            .oneshot(request("/test.Svc/Unary", "application/grpc-web", "hi"))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/grpc-web+proto"
+        );
+
+        let collected = response.into_body().collect().await.unwrap();
+        assert!(collected.trailers().is_none());
+        let messages = messages(&collected.to_bytes());
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].0, 0);
+        assert_eq!(messages[1].0, GRPC_WEB_TRAILERS);
+        let block = String::from_utf8(messages[1].1.clone()).unwrap();
+        assert!(block.contains("grpc-status: 0\r\n"), "{block}");
+        assert!(block.contains("x-trailer: done\r\n"), "{block}");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_json_codec() {
+        let payload = br#"{"text":"hi"}"#;
+        let mut body = vec![0u8];
+        body.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        body.extend_from_slice(payload);
+        let request = Request::post("/test.Svc/Unary")
+            .header(header::CONTENT_TYPE, "application/grpc+json")
+            .header("te", "trailers")
+            .body(Body::from(body))
+            .unwrap();
+
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/grpc+json"
+        );
+        let collected = response.into_body().collect().await.unwrap();
+        let trailers = collected.trailers().cloned().unwrap();
+        let messages = messages(&collected.to_bytes());
+        assert_eq!(messages.len(), 1);
+        let echo: Echo = serde_json::from_slice(&messages[0].1).unwrap();
+        assert_eq!(echo.text, "hi");
+        assert_eq!(trailers[GRPC_STATUS], "0");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_timeout_header() {
+        async fn slow(
+            ConnectRequest(req): ConnectRequest<Echo>,
+        ) -> Result<ConnectResponse<Echo>, ConnectError> {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            Ok(ConnectResponse::new(req))
+        }
+
+        let app = Router::new()
+            .route("/test.Svc/Slow", post_connect(slow))
+            .layer(ConnectLayer::new());
+        let mut req = request("/test.Svc/Slow", "application/grpc", "hi");
+        req.headers_mut()
+            .insert("grpc-timeout", HeaderValue::from_static("20m"));
+
+        let response = app.oneshot(req).await.unwrap();
+        assert_eq!(response.headers()[GRPC_STATUS], "4");
+    }
+
+    #[test]
+    fn test_error_details_in_status_details_bin() {
+        use base64::Engine as _;
+
+        let err = ConnectError::new(Code::InvalidArgument, "bad")
+            .add_detail("google.rpc.ErrorInfo", vec![1, 2, 3]);
+        let frame = crate::message::error::build_end_stream_frame(Some(&err), None);
+        let trailers = end_stream_trailers(&frame[5..]);
+
+        assert_eq!(trailers[GRPC_STATUS], "3");
+        let bin = base64::engine::general_purpose::STANDARD
+            .decode(trailers[GRPC_STATUS_DETAILS_BIN].as_bytes())
+            .unwrap();
+        let status = RpcStatus::decode(&bin[..]).unwrap();
+        assert_eq!(status.code, 3);
+        assert_eq!(status.message, "bad");
+        assert_eq!(
+            status.details[0].type_url,
+            "type.googleapis.com/google.rpc.ErrorInfo"
+        );
+        assert_eq!(status.details[0].value, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_trailers_only_for_unframed_response() {
+        let response = Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .header(CONTENT_TYPE, "text/plain")
+            .body(Body::from("nope"))
+            .unwrap();
+        let response = into_grpc_response(response, RequestProtocol::GrpcProto);
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/grpc");
+        assert_eq!(response.headers()[GRPC_STATUS], "16");
+        assert_eq!(response.headers()[GRPC_MESSAGE], "Unauthorized");
+    }
+}
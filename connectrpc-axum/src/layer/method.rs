@@ -0,0 +1,341 @@
+//! Per-method configuration layer.
+//!
+//! [`MethodConfig`] is applied to a single route, inside [`ConnectLayer`](super::ConnectLayer).
+//! It rewrites the [`ConnectContext`] that `ConnectLayer` stored in request
+//! extensions and, when it overrides a timeout setting, enforces its own deadline.
+
+use crate::context::{
+    ConnectContext, MethodConfig, TimeoutOverride, compute_effective_timeout, parse_timeout,
+};
+use crate::message::error::{Code, ConnectError};
+use axum::http::Request;
+use axum::response::Response;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+use tower::{Layer, Service, ServiceExt};
+
+impl<S> Layer<S> for MethodConfig {
+    type Service = MethodConfigService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MethodConfigService {
+            inner,
+            config: *self,
+        }
+    }
+}
+
+/// Service wrapper that applies a [`MethodConfig`] to each request.
+#[derive(Clone, Debug)]
+pub struct MethodConfigService<S> {
+    inner: S,
+    config: MethodConfig,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for MethodConfigService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response> + Clone + Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        let inner = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, inner);
+        let config = self.config;
+        let client_timeout = parse_timeout(&req);
+
+        // Without a ConnectLayer there is nothing to override
+        let Some(ctx) = req.extensions_mut().get_mut::<ConnectContext>() else {
+            return Box::pin(inner.oneshot(req));
+        };
+
+        if let Some(max) = config.receive_max_bytes {
+            ctx.limits = ctx.limits.receive_max_bytes(max);
+        }
+        if let Some(max) = config.send_max_bytes {
+            ctx.limits = ctx.limits.send_max_bytes(max);
+        }
+        if let Some(compression) = config.compression {
+            ctx.compression.config = compression;
+        }
+        ctx.compression.apply_policy(config.compression_policy);
+
+        if !config.overrides_timeout() {
+            return Box::pin(inner.oneshot(req));
+        }
+
+        ctx.server_timeout = config.timeout.or(ctx.server_timeout);
+        ctx.default_timeout = config.default_timeout.or(ctx.default_timeout);
+        ctx.max_timeout = config.max_timeout.or(ctx.max_timeout);
+        let timeout = compute_effective_timeout(
+            ctx.server_timeout,
+            ctx.default_timeout,
+            ctx.max_timeout,
+            client_timeout,
+        );
+        ctx.timeout = timeout;
+        ctx.deadline = timeout.map(|timeout| tokio::time::Instant::now() + timeout);
+        let protocol = ctx.protocol;
+        let cancellation = ctx.cancellation.clone();
+        let send_max_bytes = ctx.limits.get_send_max_bytes();
+        if let Some(timeout_override) = req.extensions().get::<TimeoutOverride>() {
+            timeout_override.claim();
+        }
+
+        Box::pin(async move {
+            match timeout {
+                Some(duration) => match tokio::time::timeout(duration, inner.oneshot(req)).await {
+                    Ok(result) => result,
+                    Err(_elapsed) => {
+                        cancellation.cancel();
+                        let err =
+                            ConnectError::new(Code::DeadlineExceeded, "request timeout exceeded");
+                        Ok(err.into_response_with_send_limit(protocol, send_max_bytes))
+                    }
+                },
+                None => inner.oneshot(req).await,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{CompressionEncoding, CompressionPolicy};
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::{ConnectRequest, ConnectResponse};
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{StatusCode, header};
+    use std::time::Duration;
+
+    async fn echo(
+        ConnectRequest(req): ConnectRequest<String>,
+    ) -> Result<ConnectResponse<String>, ConnectError> {
+        Ok(ConnectResponse::new(req))
+    }
+
+    async fn slow(
+        ConnectRequest(req): ConnectRequest<String>,
+    ) -> Result<ConnectResponse<String>, ConnectError> {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        Ok(ConnectResponse::new(req))
+    }
+
+    fn request(path: &str, body: &str) -> Request<Body> {
+        Request::post(path)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_owned()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_method_limits_override_server_limits() {
+        let app = Router::new()
+            .route(
+                "/test.Svc/Small",
+                post_connect(echo).layer(MethodConfig::new().receive_max_bytes(8)),
+            )
+            .route("/test.Svc/Default", post_connect(echo))
+            .layer(ConnectLayer::new());
+
+        let response = app
+            .clone()
+            .oneshot(request("/test.Svc/Small", "\"0123456789\""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let response = app
+            .oneshot(request("/test.Svc/Default", "\"0123456789\""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_method_timeout_replaces_server_timeout() {
+        let app = Router::new()
+            .route(
+                "/test.Svc/Long",
+                post_connect(slow).layer(MethodConfig::new().timeout(Duration::from_secs(5))),
+            )
+            .route(
+                "/test.Svc/Short",
+                post_connect(slow).layer(MethodConfig::new().timeout(Duration::from_millis(20))),
+            )
+            .route("/test.Svc/Default", post_connect(slow))
+            .layer(ConnectLayer::new().timeout(Duration::from_millis(50)));
+
+        let response = app
+            .clone()
+            .oneshot(request("/test.Svc/Long", "\"hi\""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        for path in ["/test.Svc/Short", "/test.Svc/Default"] {
+            let response = app.clone().oneshot(request(path, "\"hi\"")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT, "{path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_and_max_timeout() {
+        let app = Router::new()
+            .route(
+                "/test.Svc/Capped",
+                post_connect(slow)
+                    .layer(MethodConfig::new().max_timeout(Duration::from_millis(20))),
+            )
+            .route("/test.Svc/Default", post_connect(slow))
+            .layer(ConnectLayer::new().default_timeout(Duration::from_millis(20)));
+        let with_client_timeout = |path| {
+            let mut req = request(path, "\"hi\"");
+            req.headers_mut()
+                .insert("connect-timeout-ms", "5000".parse().unwrap());
+            req
+        };
+
+        // The client's timeout replaces the default
+        let response = app
+            .clone()
+            .oneshot(with_client_timeout("/test.Svc/Default"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(request("/test.Svc/Default", "\"hi\""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        // But not beyond the method's cap
+        let response = app
+            .oneshot(with_client_timeout("/test.Svc/Capped"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    /// Reverses the bytes; good enough to check the codec is used.
+    struct ReverseCodec;
+
+    impl crate::context::Codec for ReverseCodec {
+        fn name(&self) -> &'static str {
+            "reverse"
+        }
+
+        fn compress(&self, data: &[u8]) -> std::io::Result<bytes::Bytes> {
+            Ok(data.iter().rev().copied().collect())
+        }
+
+        fn decompress(&self, data: &[u8]) -> std::io::Result<bytes::Bytes> {
+            self.compress(data)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compression_policy_overrides_negotiation() {
+        let reverse = CompressionEncoding::custom(&ReverseCodec);
+        let app = Router::new()
+            .route(
+                "/test.Svc/Always",
+                post_connect(echo).layer(
+                    MethodConfig::new().compression_policy(CompressionPolicy::Always(reverse)),
+                ),
+            )
+            .route(
+                "/test.Svc/Never",
+                post_connect(echo)
+                    .layer(MethodConfig::new().compression_policy(CompressionPolicy::Never)),
+            )
+            .layer(ConnectLayer::new().codec(&ReverseCodec));
+        let body = |response: Response| async move {
+            axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap()
+        };
+
+        // Compressed although the client didn't ask for it
+        let response = app
+            .clone()
+            .oneshot(request("/test.Svc/Always", "\"hi\""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "reverse");
+        assert_eq!(body(response).await, "\"ih\"");
+
+        // Uncompressed although the client accepts the codec
+        let mut req = request("/test.Svc/Never", "\"hi\"");
+        req.headers_mut()
+            .insert(header::ACCEPT_ENCODING, "reverse".parse().unwrap());
+        let response = app.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "identity");
+        assert_eq!(body(response).await, "\"hi\"");
+    }
+
+    #[tokio::test]
+    async fn test_compression_policy_overrides_accept_encoding() {
+        let reverse = CompressionEncoding::custom(&ReverseCodec);
+        let app = Router::new()
+            .route("/test.Svc/Negotiate", post_connect(echo))
+            .route(
+                "/test.Svc/Always",
+                post_connect(echo).layer(
+                    MethodConfig::new().compression_policy(CompressionPolicy::Always(reverse)),
+                ),
+            )
+            .route(
+                "/test.Svc/Never",
+                post_connect(echo)
+                    .layer(MethodConfig::new().compression_policy(CompressionPolicy::Never)),
+            )
+            .layer(ConnectLayer::new().codec(&ReverseCodec));
+        let send = |path, accept_encoding| {
+            let mut req = request(path, "\"hi\"");
+            req.headers_mut()
+                .insert(header::ACCEPT_ENCODING, accept_encoding);
+            app.clone().oneshot(req)
+        };
+        let encoding = |response: &Response| response.headers()[header::CONTENT_ENCODING].clone();
+
+        // Without a policy the client's Accept-Encoding decides
+        let response = send("/test.Svc/Negotiate", "reverse".parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(encoding(&response), "reverse");
+
+        // Always compresses even when the client asks for identity
+        let response = send("/test.Svc/Always", "identity".parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(encoding(&response), "reverse");
+
+        // Never wins over an accepted codec
+        let response = send("/test.Svc/Never", "reverse".parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(encoding(&response), "identity");
+    }
+}
@@ -0,0 +1,463 @@
+//! Metrics layer.
+//!
+//! With the `metrics` feature, [`MetricsLayer`] records RPC metrics through
+//! the [`metrics`] facade, so whichever exporter the application installs
+//! (e.g. `metrics-exporter-prometheus`) collects them:
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `rpc_server_started_total` | counter | `service`, `method`, `protocol` |
+//! | `rpc_server_handled_total` | counter | `service`, `method`, `protocol`, `code` |
+//! | `rpc_server_handling_seconds` | histogram | `service`, `method`, `protocol` |
+//! | `rpc_server_request_message_bytes` | histogram | `service`, `method`, `protocol` |
+//! | `rpc_server_response_message_bytes` | histogram | `service`, `method`, `protocol` |
+//!
+//! An RPC is handled once its response body is finished, so streams are
+//! timed for their whole lifetime. Message sizes are recorded per message for
+//! streaming and gRPC, and per body for unary Connect; apply the layer outside
+//! the compression layers to see compressed sizes.
+
+use super::status::{FrameScan, StatusScan, is_message_frame};
+use crate::context::{RequestProtocol, detect_protocol};
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
+use axum::response::Response;
+use bytes::Bytes;
+use metrics::{Label, counter, histogram};
+use std::sync::Arc;
+use std::time::Instant;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+use tower::{Layer, Service, ServiceExt};
+
+const STARTED_TOTAL: &str = "rpc_server_started_total";
+const HANDLED_TOTAL: &str = "rpc_server_handled_total";
+const HANDLING_SECONDS: &str = "rpc_server_handling_seconds";
+const REQUEST_MESSAGE_BYTES: &str = "rpc_server_request_message_bytes";
+const RESPONSE_MESSAGE_BYTES: &str = "rpc_server_response_message_bytes";
+
+/// Layer that records RPC metrics through the `metrics` facade.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use connectrpc_axum::{MakeServiceBuilder, MetricsLayer};
+///
+/// metrics_exporter_prometheus::PrometheusBuilder::new().install()?;
+///
+/// let app = MakeServiceBuilder::new()
+///     .add_router(router)
+///     .build()
+///     .layer(MetricsLayer::new());
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MetricsLayer;
+
+impl MetricsLayer {
+    /// Create a metrics layer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService { inner }
+    }
+}
+
+/// Service wrapper created by [`MetricsLayer`].
+#[derive(Clone, Debug)]
+pub struct MetricsService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for MetricsService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: http_body::Body<Data = Bytes> + Send + 'static,
+    ReqBody::Error: Into<axum::BoxError>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let start = Instant::now();
+        let protocol = detect_protocol(&req);
+        let path = req.uri().path().trim_start_matches('/');
+        let (service, method) = path.rsplit_once('/').unwrap_or((path, ""));
+        let labels: Arc<[Label]> = Arc::new([
+            Label::new("service", service.to_owned()),
+            Label::new("method", method.to_owned()),
+            Label::new("protocol", protocol.name()),
+        ]);
+        counter!(STARTED_TOTAL, labels.iter()).increment(1);
+
+        // A GET request carries its message in the query
+        let messages = if req.method() == Method::GET {
+            Messages::Skip
+        } else {
+            Messages::new(protocol)
+        };
+        let request_sizes = MessageSizes::new(REQUEST_MESSAGE_BYTES, labels.clone(), messages);
+        let req = req.map(|body| {
+            Body::new(MeteredBody {
+                body: Body::new(body),
+                sizes: request_sizes,
+                handled: None,
+            })
+        });
+
+        let inner = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, inner);
+
+        Box::pin(async move {
+            let response = inner.oneshot(req).await?;
+            // Only successful unary responses carry a message
+            let messages = if protocol.needs_envelope() || response.status() == StatusCode::OK {
+                Messages::new(protocol)
+            } else {
+                Messages::Skip
+            };
+            let handled = Handled {
+                status: StatusScan::new(protocol, response.status(), response.headers()),
+                labels: labels.clone(),
+                start,
+            };
+            let sizes = MessageSizes::new(RESPONSE_MESSAGE_BYTES, labels, messages);
+            Ok(response.map(|body| {
+                Body::new(MeteredBody {
+                    body,
+                    sizes,
+                    handled: Some(handled),
+                })
+            }))
+        })
+    }
+}
+
+/// How a body splits into messages.
+enum Messages {
+    /// Envelope frames, one message each.
+    Framed(FrameScan),
+    /// The whole body is one message of this many bytes so far.
+    Unary(u64),
+    /// No messages to record.
+    Skip,
+}
+
+impl Messages {
+    fn new(protocol: RequestProtocol) -> Self {
+        if protocol.needs_envelope() {
+            Self::Framed(FrameScan::new(0))
+        } else {
+            Self::Unary(0)
+        }
+    }
+}
+
+/// Records the size of each message of a body.
+struct MessageSizes {
+    name: &'static str,
+    labels: Arc<[Label]>,
+    messages: Messages,
+}
+
+impl MessageSizes {
+    fn new(name: &'static str, labels: Arc<[Label]>, messages: Messages) -> Self {
+        Self {
+            name,
+            labels,
+            messages,
+        }
+    }
+
+    fn data(&mut self, data: &[u8]) {
+        let Self {
+            name,
+            labels,
+            messages,
+        } = self;
+        match messages {
+            Messages::Framed(frames) => frames.frames(data, |flags, len| {
+                if is_message_frame(flags) {
+                    histogram!(*name, labels.iter()).record(len as f64);
+                }
+            }),
+            Messages::Unary(bytes) => *bytes += data.len() as u64,
+            Messages::Skip => {}
+        }
+    }
+
+    fn finish(&mut self) {
+        if let Messages::Unary(bytes) = std::mem::replace(&mut self.messages, Messages::Skip) {
+            histogram!(self.name, self.labels.iter()).record(bytes as f64);
+        }
+    }
+}
+
+/// Completion metrics of an RPC, recorded when its response finishes.
+struct Handled {
+    status: StatusScan,
+    labels: Arc<[Label]>,
+    start: Instant,
+}
+
+impl Handled {
+    fn record(self, ended: bool) {
+        let code = self.status.code(ended);
+        let mut labels = self.labels.to_vec();
+        labels.push(Label::new("code", code.as_str()));
+        counter!(HANDLED_TOTAL, labels).increment(1);
+        histogram!(HANDLING_SECONDS, self.labels.iter()).record(self.start.elapsed());
+    }
+}
+
+/// Request or response body that records metrics as it streams through.
+struct MeteredBody {
+    body: Body,
+    sizes: MessageSizes,
+    /// Set on response bodies.
+    handled: Option<Handled>,
+}
+
+impl MeteredBody {
+    fn finish(&mut self, ended: bool) {
+        self.sizes.finish();
+        if let Some(handled) = self.handled.take() {
+            handled.record(ended);
+        }
+    }
+}
+
+impl http_body::Body for MeteredBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        let this = &mut *self;
+        let frame = std::task::ready!(Pin::new(&mut this.body).poll_frame(cx));
+        match &frame {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    this.sizes.data(data);
+                    if let Some(handled) = &mut this.handled {
+                        handled.status.data(data);
+                    }
+                } else if let Some(trailers) = frame.trailers_ref()
+                    && let Some(handled) = &mut this.handled
+                {
+                    handled.status.trailers(trailers);
+                }
+            }
+            Some(Err(_)) => this.finish(false),
+            None => this.finish(true),
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.body.size_hint()
+    }
+}
+
+impl Drop for MeteredBody {
+    fn drop(&mut self) {
+        let ended = http_body::Body::is_end_stream(&self.body);
+        self.finish(ended);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::error::{Code, ConnectError};
+    use crate::message::{ConnectRequest, ConnectResponse, StreamBody};
+    use axum::Router;
+    use axum::http::header;
+    use futures::Stream;
+    use metrics::{
+        Counter, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder, SharedString,
+        Unit,
+    };
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Recorder that keeps counters and histogram samples by `name{labels}`.
+    #[derive(Default)]
+    struct TestRecorder {
+        counters: Mutex<HashMap<String, Arc<AtomicU64>>>,
+        histograms: Mutex<HashMap<String, Arc<Samples>>>,
+    }
+
+    #[derive(Default)]
+    struct Samples(Mutex<Vec<f64>>);
+
+    impl HistogramFn for Samples {
+        fn record(&self, value: f64) {
+            self.0.lock().unwrap().push(value);
+        }
+    }
+
+    fn key_string(key: &Key) -> String {
+        let labels: Vec<_> = key
+            .labels()
+            .map(|label| format!("{}={}", label.key(), label.value()))
+            .collect();
+        format!("{}{{{}}}", key.name(), labels.join(","))
+    }
+
+    impl TestRecorder {
+        fn counter(&self, key: &str) -> u64 {
+            self.counters.lock().unwrap()[key].load(Ordering::Relaxed)
+        }
+
+        fn samples(&self, key: &str) -> Vec<f64> {
+            self.histograms.lock().unwrap()[key]
+                .0
+                .lock()
+                .unwrap()
+                .clone()
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let mut counters = self.counters.lock().unwrap();
+            Counter::from_arc(counters.entry(key_string(key)).or_default().clone())
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            let mut histograms = self.histograms.lock().unwrap();
+            Histogram::from_arc(histograms.entry(key_string(key)).or_default().clone())
+        }
+    }
+
+    /// Streams the request back twice, failing on an empty request.
+    async fn stream(
+        ConnectRequest(req): ConnectRequest<String>,
+    ) -> Result<
+        ConnectResponse<StreamBody<impl Stream<Item = Result<String, ConnectError>>>>,
+        ConnectError,
+    > {
+        if req.is_empty() {
+            return Err(ConnectError::new(Code::InvalidArgument, "empty"));
+        }
+        let stream = futures::stream::iter(vec![Ok(req.clone()), Ok(req)]);
+        Ok(ConnectResponse::new(StreamBody::new(stream)))
+    }
+
+    fn app() -> Router {
+        let echo = |ConnectRequest(req): ConnectRequest<String>| async move {
+            Ok::<_, ConnectError>(ConnectResponse::new(req))
+        };
+        Router::new()
+            .route("/test.v1.Svc/Echo", post_connect(echo))
+            .route("/test.v1.Svc/Stream", post_connect(stream))
+            .layer(ConnectLayer::new())
+            .layer(MetricsLayer::new())
+    }
+
+    async fn call(path: &str, content_type: &str, body: Vec<u8>) {
+        let request = Request::post(path)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+    }
+
+    fn frame(text: &str) -> Vec<u8> {
+        let mut frame = vec![0u8];
+        frame.extend_from_slice(&(text.len() as u32).to_be_bytes());
+        frame.extend_from_slice(text.as_bytes());
+        frame
+    }
+
+    #[tokio::test]
+    async fn test_records_rpc_metrics() {
+        let recorder = TestRecorder::default();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        call("/test.v1.Svc/Echo", "application/json", b"\"hi\"".to_vec()).await;
+        call(
+            "/test.v1.Svc/Stream",
+            "application/connect+json",
+            frame("\"hey\""),
+        )
+        .await;
+        call(
+            "/test.v1.Svc/Stream",
+            "application/connect+json",
+            frame("\"\""),
+        )
+        .await;
+
+        let echo = "service=test.v1.Svc,method=Echo,protocol=connect";
+        let stream = "service=test.v1.Svc,method=Stream,protocol=connect";
+        assert_eq!(
+            recorder.counter(&format!("rpc_server_started_total{{{echo}}}")),
+            1
+        );
+        assert_eq!(
+            recorder.counter(&format!("rpc_server_handled_total{{{echo},code=ok}}")),
+            1
+        );
+        assert_eq!(
+            recorder.counter(&format!("rpc_server_handled_total{{{stream},code=ok}}")),
+            1
+        );
+        assert_eq!(
+            recorder.counter(&format!(
+                "rpc_server_handled_total{{{stream},code=invalid_argument}}"
+            )),
+            1
+        );
+        assert_eq!(
+            recorder
+                .samples(&format!("rpc_server_handling_seconds{{{stream}}}"))
+                .len(),
+            2
+        );
+        assert_eq!(
+            recorder.samples(&format!("rpc_server_request_message_bytes{{{echo}}}")),
+            [4.0]
+        );
+        assert_eq!(
+            recorder.samples(&format!("rpc_server_response_message_bytes{{{stream}}}")),
+            [5.0, 5.0]
+        );
+    }
+}
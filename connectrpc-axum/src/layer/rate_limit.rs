@@ -0,0 +1,347 @@
+//! Rate limit layer.
+//!
+//! [`RateLimitLayer`] gives every caller a token bucket: each RPC takes a
+//! token, and tokens refill at a steady rate up to a burst size. Callers are
+//! told apart by a key taken from the request (the peer IP, an API key
+//! header, the method, or anything else). Calls over the limit are rejected
+//! with a Connect `resource_exhausted` error in the request's protocol,
+//! carrying a `google.rpc.RetryInfo` detail with the time until the next
+//! token.
+
+use super::concurrency::rejection_response;
+use crate::message::error::{Code, ConnectError};
+use axum::extract::ConnectInfo;
+use axum::http::request::Parts;
+use axum::http::{HeaderName, Request};
+use axum::response::Response;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+use tower::{Layer, Service, ServiceExt};
+
+/// Number of buckets kept before idle ones are pruned.
+const PRUNE_AT: usize = 1024;
+
+type KeyFn = dyn Fn(&Parts) -> String + Send + Sync;
+
+/// Layer that rate limits RPCs with a token bucket per key.
+///
+/// Buckets are shared by every service the layer is applied to. By default all
+/// requests share one bucket; use one of the `by_*` methods or
+/// [`key_by`](Self::key_by) to give each caller its own.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use connectrpc_axum::RateLimitLayer;
+///
+/// // 10 calls per second per client IP, bursts of up to 20
+/// let router = Router::new()
+///     .route("/service/Method", post(handler))
+///     .layer(ConnectLayer::new())
+///     .layer(
+///         RateLimitLayer::new(10, Duration::from_secs(1))
+///             .burst(20)
+///             .by_peer_ip(),
+///     );
+/// ```
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    /// Tokens added per second.
+    rate: f64,
+    burst: f64,
+    key: Arc<KeyFn>,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+impl std::fmt::Debug for RateLimitLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimitLayer")
+            .field("rate", &self.rate)
+            .field("burst", &self.burst)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RateLimitLayer {
+    /// Allow `calls` RPCs per `period` for each key.
+    ///
+    /// The burst size defaults to `calls`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `calls` or `period` is zero.
+    pub fn new(calls: u32, period: Duration) -> Self {
+        assert!(
+            calls > 0 && !period.is_zero(),
+            "rate limit must allow at least one call per non-zero period"
+        );
+        Self {
+            rate: f64::from(calls) / period.as_secs_f64(),
+            burst: f64::from(calls),
+            key: Arc::new(|_: &Parts| String::new()),
+            buckets: Arc::default(),
+        }
+    }
+
+    /// Allow up to `burst` calls in quick succession.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = f64::from(burst.max(1));
+        self
+    }
+
+    /// Key requests with `key`.
+    ///
+    /// ```rust,ignore
+    /// // Limit each tenant
+    /// let layer = RateLimitLayer::new(100, Duration::from_secs(60))
+    ///     .key_by(|parts| tenant_of(&parts.headers).unwrap_or_default());
+    /// ```
+    pub fn key_by<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&Parts) -> String + Send + Sync + 'static,
+    {
+        self.key = Arc::new(key);
+        self
+    }
+
+    /// Key requests by the caller's IP address.
+    ///
+    /// The address comes from axum's `ConnectInfo<SocketAddr>`, so serve the
+    /// app with `into_make_service_with_connect_info::<SocketAddr>()`;
+    /// otherwise all requests share one bucket.
+    pub fn by_peer_ip(self) -> Self {
+        self.key_by(|parts| {
+            parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|info| info.0.ip().to_string())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Key requests by the value of a header, e.g. an API key.
+    ///
+    /// Requests without the header share one bucket.
+    pub fn by_header(self, name: HeaderName) -> Self {
+        self.key_by(move |parts| {
+            parts
+                .headers
+                .get(&name)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_owned()
+        })
+    }
+
+    /// Key requests by RPC path, limiting each method separately.
+    pub fn by_method(self) -> Self {
+        self.key_by(|parts| parts.uri.path().to_owned())
+    }
+
+    /// Take a token from the bucket of `key`.
+    fn acquire(&self, key: String) -> Result<(), ConnectError> {
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        if buckets.map.len() >= buckets.prune_at {
+            buckets.prune(now, self.rate, self.burst);
+        }
+        let bucket = buckets.map.entry(key).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        bucket.take(now, self.rate, self.burst).map_err(|wait| {
+            ConnectError::new(Code::ResourceExhausted, "rate limit exceeded").with_retry_info(wait)
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Buckets {
+    map: HashMap<String, Bucket>,
+    prune_at: usize,
+}
+
+impl Default for Buckets {
+    fn default() -> Self {
+        Self {
+            map: HashMap::new(),
+            prune_at: PRUNE_AT,
+        }
+    }
+}
+
+impl Buckets {
+    /// Drop buckets that have refilled; they behave like new ones.
+    fn prune(&mut self, now: Instant, rate: f64, burst: f64) {
+        self.map
+            .retain(|_, bucket| bucket.refilled(now, rate, burst) < burst);
+        self.prune_at = PRUNE_AT.max(self.map.len() * 2);
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn refilled(&self, now: Instant, rate: f64, burst: f64) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        (self.tokens + elapsed * rate).min(burst)
+    }
+
+    /// Take a token, or return how long until one is available.
+    fn take(&mut self, now: Instant, rate: f64, burst: f64) -> Result<(), Duration> {
+        self.tokens = self.refilled(now, rate, burst);
+        self.updated = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limits: self.clone(),
+        }
+    }
+}
+
+/// Service wrapper created by [`RateLimitLayer`].
+#[derive(Clone, Debug)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limits: RateLimitLayer,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for RateLimitService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response> + Clone + Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let (parts, body) = req.into_parts();
+        let key = (self.limits.key)(&parts);
+        let req = Request::from_parts(parts, body);
+        if let Err(err) = self.limits.acquire(key) {
+            let response = rejection_response(&req, err);
+            return Box::pin(async move { Ok(response) });
+        }
+
+        let inner = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, inner);
+        Box::pin(inner.oneshot(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::{ConnectRequest, ConnectResponse};
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{StatusCode, header};
+
+    fn app(limits: RateLimitLayer) -> Router {
+        let echo = |ConnectRequest(req): ConnectRequest<String>| async move {
+            Ok::<_, ConnectError>(ConnectResponse::new(req))
+        };
+        Router::new()
+            .route("/test.Svc/Echo", post_connect(echo))
+            .layer(ConnectLayer::new())
+            .layer(limits)
+    }
+
+    async fn call(app: &Router, api_key: &str) -> Response {
+        let request = Request::post("/test.Svc/Echo")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-api-key", api_key)
+            .body(Body::from("\"hi\""))
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rejects_excess_calls_with_retry_info() {
+        let app = app(RateLimitLayer::new(2, Duration::from_secs(60)));
+
+        assert_eq!(call(&app, "a").await.status(), StatusCode::OK);
+        assert_eq!(call(&app, "b").await.status(), StatusCode::OK);
+
+        let response = call(&app, "a").await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "resource_exhausted");
+        assert_eq!(body["details"][0]["type"], "google.rpc.RetryInfo");
+    }
+
+    #[tokio::test]
+    async fn test_keys_have_separate_buckets() {
+        let app = app(RateLimitLayer::new(1, Duration::from_secs(60))
+            .by_header(HeaderName::from_static("x-api-key")));
+
+        assert_eq!(call(&app, "a").await.status(), StatusCode::OK);
+        assert_eq!(call(&app, "b").await.status(), StatusCode::OK);
+        assert_eq!(
+            call(&app, "a").await.status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+
+    #[test]
+    fn test_bucket_refills_up_to_burst() {
+        let start = Instant::now();
+        let mut bucket = Bucket {
+            tokens: 2.0,
+            updated: start,
+        };
+        // 1 token per second, burst of 2
+        assert!(bucket.take(start, 1.0, 2.0).is_ok());
+        assert!(bucket.take(start, 1.0, 2.0).is_ok());
+        let wait = bucket.take(start, 1.0, 2.0).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(1));
+
+        let later = start + Duration::from_millis(1500);
+        assert!(bucket.take(later, 1.0, 2.0).is_ok());
+        assert_eq!(
+            bucket.take(later, 1.0, 2.0).unwrap_err(),
+            Duration::from_millis(500)
+        );
+
+        let much_later = later + Duration::from_secs(60);
+        assert_eq!(bucket.refilled(much_later, 1.0, 2.0), 2.0);
+    }
+}
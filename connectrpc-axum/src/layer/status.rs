@@ -0,0 +1,241 @@
+//! Status code of a response as it streams through.
+//!
+//! Layers outside the handler see the response in its wire format, where the
+//! status code sits wherever the protocol puts it: the HTTP status and JSON
+//! error body for Connect unary, the EndStream frame for Connect streaming,
+//! and `grpc-status` in the headers, trailers or trailer frame for gRPC and
+//! gRPC-Web. [`StatusScan`] follows the body and finds it.
+
+use crate::context::RequestProtocol;
+use crate::message::error::Code;
+use crate::message::request::envelope_flags;
+use axum::http::{HeaderMap, StatusCode};
+use serde::Deserialize;
+
+/// Flag marking the trailer frame of a gRPC-Web response.
+const GRPC_WEB_TRAILERS: u8 = 0x80;
+
+/// Largest error body or status frame read to find the status code.
+const MAX_STATUS_BYTES: usize = 64 * 1024;
+
+/// Finds the status code of an RPC while its response streams through.
+pub(super) enum StatusScan {
+    /// Known from the response head.
+    Known(Code),
+    /// Connect unary error: the code is in the JSON body.
+    ErrorBody { status: StatusCode, body: Vec<u8> },
+    /// Connect streaming or gRPC-Web: the code is in the last frame.
+    Frames(FrameScan),
+    /// gRPC: the code is in the trailers.
+    Trailers(Option<Code>),
+}
+
+impl StatusScan {
+    pub(super) fn new(protocol: RequestProtocol, status: StatusCode, headers: &HeaderMap) -> Self {
+        if protocol.is_grpc() {
+            if let Some(code) = grpc_status(headers) {
+                return Self::Known(code);
+            }
+            if protocol.is_grpc_web() {
+                return Self::Frames(FrameScan::new(GRPC_WEB_TRAILERS));
+            }
+            return Self::Trailers(None);
+        }
+        if status != StatusCode::OK {
+            return Self::ErrorBody {
+                status,
+                body: Vec::new(),
+            };
+        }
+        if protocol.is_streaming() {
+            return Self::Frames(FrameScan::new(envelope_flags::END_STREAM));
+        }
+        Self::Known(Code::Ok)
+    }
+
+    pub(super) fn data(&mut self, data: &[u8]) {
+        match self {
+            Self::ErrorBody { body, .. } => {
+                let room = MAX_STATUS_BYTES.saturating_sub(body.len());
+                body.extend_from_slice(&data[..data.len().min(room)]);
+            }
+            Self::Frames(frames) => frames.data(data),
+            Self::Known(_) | Self::Trailers(_) => {}
+        }
+    }
+
+    pub(super) fn trailers(&mut self, trailers: &HeaderMap) {
+        if let Self::Trailers(code) = self {
+            *code = grpc_status(trailers);
+        }
+    }
+
+    /// The status code; `ended` tells whether the body was read to the end.
+    pub(super) fn code(&self, ended: bool) -> Code {
+        let found = match self {
+            Self::Known(code) => Some(*code),
+            Self::ErrorBody { status, body } => {
+                #[derive(Deserialize)]
+                struct ErrorBody {
+                    code: String,
+                }
+                let code = serde_json::from_slice::<ErrorBody>(body)
+                    .ok()
+                    .and_then(|error| error.code.parse().ok());
+                Some(code.unwrap_or_else(|| Code::from_http_status_connect(*status)))
+            }
+            Self::Frames(frames) => frames.code(),
+            Self::Trailers(code) => *code,
+        };
+        match found {
+            Some(code) => code,
+            None if ended => Code::Unknown,
+            None => Code::Canceled,
+        }
+    }
+}
+
+/// Follows envelope frames and keeps the payload of the status frame.
+pub(super) struct FrameScan {
+    /// Flag marking the status frame.
+    status_flag: u8,
+    header: [u8; 5],
+    header_len: usize,
+    remaining: usize,
+    /// Payload of the status frame being read.
+    current: Option<Vec<u8>>,
+    /// Payload of the completed status frame.
+    status: Option<Vec<u8>>,
+}
+
+impl FrameScan {
+    pub(super) fn new(status_flag: u8) -> Self {
+        Self {
+            status_flag,
+            header: [0; 5],
+            header_len: 0,
+            remaining: 0,
+            current: None,
+            status: None,
+        }
+    }
+
+    fn data(&mut self, data: &[u8]) {
+        self.frames(data, |_, _| {});
+    }
+
+    /// Follow `data`, calling `on_frame` with the flags and payload length of
+    /// each frame once its header is complete.
+    pub(super) fn frames(&mut self, mut data: &[u8], mut on_frame: impl FnMut(u8, usize)) {
+        while !data.is_empty() {
+            if self.header_len < 5 {
+                let n = (5 - self.header_len).min(data.len());
+                self.header[self.header_len..self.header_len + n].copy_from_slice(&data[..n]);
+                self.header_len += n;
+                data = &data[n..];
+                if self.header_len == 5 {
+                    let [flags, length @ ..] = self.header;
+                    self.remaining = u32::from_be_bytes(length) as usize;
+                    on_frame(flags, self.remaining);
+                    if flags & self.status_flag != 0 {
+                        self.current = Some(Vec::new());
+                    }
+                    if self.remaining == 0 {
+                        self.end_frame();
+                    }
+                }
+                continue;
+            }
+
+            let n = self.remaining.min(data.len());
+            if let Some(current) = &mut self.current {
+                let room = MAX_STATUS_BYTES.saturating_sub(current.len());
+                current.extend_from_slice(&data[..n.min(room)]);
+            }
+            self.remaining -= n;
+            data = &data[n..];
+            if self.remaining == 0 {
+                self.end_frame();
+            }
+        }
+    }
+
+    fn end_frame(&mut self) {
+        self.header_len = 0;
+        if let Some(current) = self.current.take() {
+            self.status = Some(current);
+        }
+    }
+
+    fn code(&self) -> Option<Code> {
+        let payload = self.status.as_deref()?;
+        if self.status_flag == GRPC_WEB_TRAILERS {
+            return payload
+                .split(|&byte| byte == b'\n')
+                .filter_map(|line| std::str::from_utf8(line).ok())
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("grpc-status"))
+                .and_then(|(_, value)| value.trim().parse().ok())
+                .map(|code| Code::from_grpc_code(code).unwrap_or(Code::Unknown));
+        }
+
+        #[derive(Deserialize, Default)]
+        struct EndStream {
+            error: Option<EndStreamError>,
+        }
+        #[derive(Deserialize)]
+        struct EndStreamError {
+            code: String,
+        }
+        let end: EndStream = serde_json::from_slice(payload).unwrap_or_default();
+        Some(match end.error {
+            Some(error) => error.code.parse().unwrap_or(Code::Unknown),
+            None => Code::Ok,
+        })
+    }
+}
+
+/// Whether a frame with these flags carries a message rather than a status.
+#[cfg(feature = "metrics")]
+pub(super) fn is_message_frame(flags: u8) -> bool {
+    flags & (envelope_flags::END_STREAM | GRPC_WEB_TRAILERS) == 0
+}
+
+/// The `grpc-status` header, if present.
+fn grpc_status(headers: &HeaderMap) -> Option<Code> {
+    let code = headers.get("grpc-status")?.to_str().ok()?.parse().ok()?;
+    Some(Code::from_grpc_code(code).unwrap_or(Code::Unknown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dropped_stream_is_canceled() {
+        let mut scan = StatusScan::new(
+            RequestProtocol::ConnectStreamJson,
+            StatusCode::OK,
+            &HeaderMap::new(),
+        );
+        scan.data(&[0, 0, 0, 0, 2, b'"']);
+        assert_eq!(scan.code(false), Code::Canceled);
+
+        scan.data(&[b'"', envelope_flags::END_STREAM, 0, 0, 0, 2, b'{', b'}']);
+        assert_eq!(scan.code(true), Code::Ok);
+    }
+
+    #[test]
+    fn test_grpc_web_trailer_frame() {
+        let block = b"grpc-status: 5\r\ngrpc-message: missing\r\n";
+        let mut frame = vec![GRPC_WEB_TRAILERS];
+        frame.extend_from_slice(&(block.len() as u32).to_be_bytes());
+        frame.extend_from_slice(block);
+
+        let mut scan = FrameScan::new(GRPC_WEB_TRAILERS);
+        for chunk in frame.chunks(3) {
+            scan.data(chunk);
+        }
+        assert_eq!(scan.code(), Some(Code::NotFound));
+    }
+}
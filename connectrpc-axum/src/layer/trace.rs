@@ -0,0 +1,347 @@
+//! Server spans following the OpenTelemetry RPC semantic conventions.
+//!
+//! With the `tracing` feature, [`ConnectService`](super::ConnectService) runs
+//! each RPC in an `rpc.server` span that stays open until the response body is
+//! finished. The span carries:
+//!
+//! - `otel.name`: `{service}/{method}`, and `otel.kind`: `server`
+//! - `rpc.system`: `connect_rpc`, or `grpc` for gRPC and gRPC-Web
+//! - `rpc.service` and `rpc.method`
+//! - `rpc.connect_rpc.error_code` (Connect errors) or `rpc.grpc.status_code`
+//! - `otel.status_code`: `ERROR` for server-side failures (`unknown`,
+//!   `deadline_exceeded`, `unimplemented`, `internal`, `unavailable`,
+//!   `data_loss`)
+//! - `trace_id` and `parent_span_id` from the caller's `traceparent` header
+//!
+//! With only `tracing`, the caller's trace context is recorded as these two
+//! fields and the span is not parented to the remote span. The
+//! `opentelemetry` feature also sets the remote span (with the `tracestate`
+//! header) as the parent of the span's OpenTelemetry context, as recorded by a
+//! `tracing-opentelemetry` layer, so the server's spans join the caller's
+//! trace.
+
+use super::status::StatusScan;
+use crate::context::{RequestProtocol, TraceParent, detect_protocol};
+use crate::message::error::Code;
+use axum::body::Body;
+use axum::http::Request;
+use axum::response::Response;
+use bytes::Bytes;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+use tracing::field::Empty;
+use tracing::{Instrument, Span, info_span};
+
+/// The span of one RPC.
+pub(super) struct RpcSpan {
+    pub(super) span: Span,
+    protocol: RequestProtocol,
+}
+
+impl RpcSpan {
+    pub(super) fn new<B>(req: &Request<B>) -> Self {
+        let protocol = detect_protocol(req);
+        let path = req.uri().path().trim_start_matches('/');
+        let (service, method) = path.rsplit_once('/').unwrap_or((path, ""));
+        let system = if protocol.is_grpc() {
+            "grpc"
+        } else {
+            "connect_rpc"
+        };
+
+        let span = info_span!(
+            "rpc.server",
+            otel.name = %path,
+            otel.kind = "server",
+            otel.status_code = Empty,
+            rpc.system = system,
+            rpc.service = %service,
+            rpc.method = %method,
+            rpc.connect_rpc.error_code = Empty,
+            rpc.grpc.status_code = Empty,
+            trace_id = Empty,
+            parent_span_id = Empty,
+        );
+        if let Some(parent) = TraceParent::from_headers(req.headers()) {
+            span.record("trace_id", parent.trace_id.as_str());
+            span.record("parent_span_id", parent.parent_id.as_str());
+            #[cfg(feature = "opentelemetry")]
+            set_remote_parent(&span, &parent, req.headers());
+        }
+
+        Self { span, protocol }
+    }
+
+    /// Run `call` in the span and keep the span open until the response
+    /// body is finished.
+    pub(super) fn instrument<E>(
+        self,
+        call: impl Future<Output = Result<Response, E>>,
+    ) -> impl Future<Output = Result<Response, E>> {
+        let span = self.span.clone();
+        async move {
+            let response = call.await?;
+            let status = StatusScan::new(self.protocol, response.status(), response.headers());
+            Ok(response.map(|body| {
+                Body::new(TracedBody {
+                    body,
+                    status,
+                    rpc: Some(self),
+                })
+            }))
+        }
+        .instrument(span)
+    }
+
+    fn record(&self, code: Code) {
+        if self.protocol.is_grpc() {
+            self.span
+                .record("rpc.grpc.status_code", code.to_grpc_code());
+        } else if code != Code::Ok {
+            self.span
+                .record("rpc.connect_rpc.error_code", code.as_str());
+        }
+        if matches!(
+            code,
+            Code::Unknown
+                | Code::DeadlineExceeded
+                | Code::Unimplemented
+                | Code::Internal
+                | Code::Unavailable
+                | Code::DataLoss
+        ) {
+            self.span.record("otel.status_code", "ERROR");
+        }
+    }
+}
+
+/// Make the remote span of `parent` the parent of `span`.
+///
+/// Does nothing if the span isn't tracked by an OpenTelemetry layer.
+#[cfg(feature = "opentelemetry")]
+fn set_remote_parent(span: &Span, parent: &TraceParent, headers: &axum::http::HeaderMap) {
+    use opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+    };
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let (Ok(trace_id), Ok(span_id)) = (
+        TraceId::from_hex(&parent.trace_id),
+        SpanId::from_hex(&parent.parent_id),
+    ) else {
+        return;
+    };
+    let trace_state = headers
+        .get("tracestate")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<TraceState>().ok())
+        .unwrap_or_default();
+    let remote = SpanContext::new(
+        trace_id,
+        span_id,
+        TraceFlags::new(parent.flags),
+        true,
+        trace_state,
+    );
+    let _ = span.set_parent(opentelemetry::Context::new().with_remote_span_context(remote));
+}
+
+/// Response body that records the status code on the span and closes it.
+struct TracedBody {
+    body: Body,
+    status: StatusScan,
+    rpc: Option<RpcSpan>,
+}
+
+impl TracedBody {
+    fn finish(&mut self, ended: bool) {
+        if let Some(rpc) = self.rpc.take() {
+            rpc.record(self.status.code(ended));
+        }
+    }
+}
+
+impl http_body::Body for TracedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        let this = &mut *self;
+        let frame = match &this.rpc {
+            Some(rpc) => {
+                let _entered = rpc.span.enter();
+                std::task::ready!(Pin::new(&mut this.body).poll_frame(cx))
+            }
+            None => std::task::ready!(Pin::new(&mut this.body).poll_frame(cx)),
+        };
+        match &frame {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    this.status.data(data);
+                } else if let Some(trailers) = frame.trailers_ref() {
+                    this.status.trailers(trailers);
+                }
+            }
+            Some(Err(_)) => this.finish(false),
+            None => this.finish(true),
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.body.size_hint()
+    }
+}
+
+impl Drop for TracedBody {
+    fn drop(&mut self) {
+        let ended = http_body::Body::is_end_stream(&self.body);
+        self.finish(ended);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::error::ConnectError;
+    use crate::message::{ConnectRequest, ConnectResponse};
+    use axum::Router;
+    use axum::http::header;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    type Fields = Arc<Mutex<HashMap<String, String>>>;
+
+    /// Subscriber that keeps the fields of every span.
+    struct Recorder {
+        fields: Fields,
+        next_id: AtomicU64,
+    }
+
+    impl Visit for &Recorder {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            let mut fields = self.fields.lock().unwrap();
+            fields.insert(field.name().to_owned(), value.to_owned());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            let mut fields = self.fields.lock().unwrap();
+            fields.insert(field.name().to_owned(), format!("{value:?}"));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            span.record(&mut &*self);
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _span: &Id, values: &Record<'_>) {
+            values.record(&mut &*self);
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event<'_>) {}
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[tokio::test]
+    async fn test_rpc_span_fields() {
+        let fields = Fields::default();
+        let _guard = tracing::subscriber::set_default(Recorder {
+            fields: fields.clone(),
+            next_id: AtomicU64::new(0),
+        });
+
+        let fail = |ConnectRequest(_): ConnectRequest<String>| async move {
+            Err::<ConnectResponse<String>, _>(ConnectError::new(Code::InvalidArgument, "no"))
+        };
+        let app = Router::new()
+            .route("/test.v1.Svc/Fail", post_connect(fail))
+            .layer(ConnectLayer::new());
+        let request = Request::post("/test.v1.Svc/Fail")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .body(Body::from("\"hi\""))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        let fields = fields.lock().unwrap();
+        assert_eq!(fields["otel.name"], "test.v1.Svc/Fail");
+        assert_eq!(fields["otel.kind"], "server");
+        assert_eq!(fields["rpc.system"], "connect_rpc");
+        assert_eq!(fields["rpc.service"], "test.v1.Svc");
+        assert_eq!(fields["rpc.method"], "Fail");
+        assert_eq!(fields["rpc.connect_rpc.error_code"], "invalid_argument");
+        assert_eq!(fields["trace_id"], "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(fields["parent_span_id"], "00f067aa0ba902b7");
+        assert!(!fields.contains_key("otel.status_code"));
+    }
+
+    #[cfg(feature = "opentelemetry")]
+    #[tokio::test]
+    async fn test_rpc_span_joins_remote_trace() {
+        use opentelemetry::trace::{TraceContextExt, TracerProvider};
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry()
+                .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test"))),
+        );
+
+        // Answers with the trace ID of the span the handler runs in
+        let trace_id = |ConnectRequest(_): ConnectRequest<String>| async move {
+            let context = Span::current().context();
+            let trace_id = context.span().span_context().trace_id();
+            Ok::<_, ConnectError>(ConnectResponse::new(format!("{trace_id:032x}")))
+        };
+        let app = Router::new()
+            .route("/test.v1.Svc/TraceId", post_connect(trace_id))
+            .layer(ConnectLayer::new());
+        let request = Request::post("/test.v1.Svc/TraceId")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .body(Body::from("\"hi\""))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        assert_eq!(&body[..], b"\"4bf92f3577b34da6a3ce929d0e0e4736\"");
+    }
+}
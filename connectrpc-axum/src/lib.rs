@@ -1,10 +1,19 @@
+pub mod auth;
 pub mod context;
+pub mod descriptor;
+pub mod dynamic;
+pub mod error_mapper;
 pub mod handler;
+pub mod health;
+pub mod interceptor;
 pub mod layer;
 pub mod message;
+pub mod reflection;
 pub mod service_builder;
+pub mod shutdown;
 #[cfg(feature = "tonic")]
 pub mod tonic;
+pub mod validate;
 
 // Re-export key types at the crate root for convenience
 #[cfg(feature = "tonic")]
@@ -31,6 +40,9 @@ pub use crate::tonic::{
     TonicCompatible,
     // Unified handler wrapper and RPC markers
     TonicHandlerWrapper,
+    // Tonic interceptors
+    TonicInterceptor,
+    TonicInterceptorService,
     Unary,
     // Routing functions
     post_tonic,
@@ -43,6 +55,7 @@ pub use crate::tonic::{
     unimplemented_boxed_client_stream_call,
     unimplemented_boxed_stream_call,
 };
+pub use auth::{ApiKey, Basic, Bearer, PeerCertificates, PeerCertificatesLayer};
 // Re-export from context module
 pub use context::{
     BoxedCodec,
@@ -50,6 +63,8 @@ pub use context::{
     CONNECT_ACCEPT_ENCODING,
     CONNECT_CONTENT_ENCODING,
     CONNECT_TIMEOUT_MS_HEADER,
+    // Deadline and cancellation extractors
+    Cancellation,
     // Codec trait and boxed type
     Codec,
     // Compression types
@@ -57,19 +72,35 @@ pub use context::{
     CompressionContext,
     CompressionEncoding,
     CompressionLevel,
+    CompressionPolicy,
     ConnectContext,
     ConnectTimeout,
     // Errors
     ContextError,
+    // Request-scoped values
+    ContextValue,
+    ContextValues,
+    CustomCodec,
+    Deadline,
     // Envelope compression for streaming
     EnvelopeCompression,
     // Idempotency
     IdempotencyLevel,
     // Identity codec (always available)
     IdentityCodec,
+    // Stream inactivity deadline
+    IdleTimeout,
     // Limits
     MessageLimits,
+    // Per-method overrides
+    MethodConfig,
+    // Connection info
+    PeerInfo,
     RequestProtocol,
+    // Trace context
+    TRACEPARENT_HEADER,
+    TraceParent,
+    UserAgent,
     // Compression functions
     compress_bytes,
     // Timeout
@@ -91,11 +122,25 @@ pub use context::DeflateCodec;
 pub use context::GzipCodec;
 #[cfg(feature = "compression-zstd-stream")]
 pub use context::ZstdCodec;
+pub use descriptor::{MethodDescriptor, MethodKind};
+pub use dynamic::DynamicServiceRegistry;
+pub use error_mapper::{ErrorMapper, IntoConnectError};
 // Re-export from message module
 pub use handler::{ConnectHandler, ConnectHandlerWrapper, get_connect, post_connect};
-pub use layer::{BridgeLayer, BridgeService, ConnectLayer, ConnectService};
+pub use interceptor::{
+    AnyMessage, Interceptor, MessageInterceptor, TypedMessageInterceptor, TypedWrapper,
+};
+pub use layer::{
+    AccessLogLayer, AccessLogRecord, AccessLogService, AccessLogSink, BridgeLayer, BridgeService,
+    ConcurrencyLimitLayer, ConcurrencyLimitService, ConnectLayer, ConnectService,
+    MethodConfigService, RateLimitLayer, RateLimitService, TracingAccessLog,
+};
+#[cfg(feature = "metrics")]
+pub use layer::{MetricsLayer, MetricsService};
 pub use message::{RequestPipeline, ResponsePipeline};
 pub use service_builder::MakeServiceBuilder;
+pub use shutdown::Shutdown;
+pub use validate::{Validate, ValidationInterceptor};
 
 // Re-export several crates
 pub use futures;
@@ -103,6 +148,7 @@ pub use pbjson;
 pub use pbjson_types;
 pub use prost;
 pub use serde;
+pub use tower;
 
 pub use prelude::*;
 
@@ -114,16 +160,24 @@ pub mod prelude {
         CONNECT_ACCEPT_ENCODING,
         CONNECT_CONTENT_ENCODING,
         CONNECT_TIMEOUT_MS_HEADER,
+        // Deadline and cancellation extractors
+        Cancellation,
         // Codec trait and boxed type
         Codec,
         CompressionConfig,
         CompressionContext,
         CompressionEncoding,
         CompressionLevel,
+        CompressionPolicy,
         ConnectContext,
         ConnectTimeout,
         // Errors
         ContextError,
+        // Request-scoped values
+        ContextValue,
+        ContextValues,
+        CustomCodec,
+        Deadline,
         // Compression types
         EnvelopeCompression,
         // Idempotency
@@ -154,11 +208,18 @@ pub mod prelude {
     #[cfg(feature = "compression-zstd-stream")]
     pub use crate::context::ZstdCodec;
 
+    pub use crate::descriptor::{MethodDescriptor, MethodKind};
+    pub use crate::error_mapper::{ErrorMapper, IntoConnectError};
     pub use crate::handler::{ConnectHandler, ConnectHandlerWrapper, get_connect, post_connect};
-    pub use crate::layer::{BridgeLayer, BridgeService, ConnectLayer, ConnectService};
+    pub use crate::layer::{
+        BridgeLayer, BridgeService, ConcurrencyLimitLayer, ConcurrencyLimitService, ConnectLayer,
+        ConnectService, MethodConfigService,
+    };
     pub use crate::message::error::{Code, ConnectError, ErrorDetail, Status};
     pub use crate::message::{
-        ConnectRequest, ConnectResponse, RequestPipeline, ResponsePipeline, StreamBody, Streaming,
+        ChannelStream, ConnectRequest, ConnectResponse, Heartbeat, OnCancel, RequestPipeline,
+        ResponsePipeline, StreamBody, StreamSender, StreamWriteConfig, Streaming, TimeoutStream,
+        TrailerSetter,
     };
     pub use crate::service_builder::MakeServiceBuilder;
     #[cfg(feature = "tonic")]
@@ -182,6 +243,7 @@ pub mod prelude {
         TonicCompatible,
         // Unified wrapper and markers
         TonicHandlerWrapper,
+        TonicInterceptor,
         Unary,
         // Routing functions
         post_tonic,
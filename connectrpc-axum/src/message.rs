@@ -28,6 +28,7 @@ pub use request::{
     ConnectRequest,
     RequestPipeline,
     Streaming,
+    TimeoutStream,
     // Primitive functions
     decode_json,
     decode_proto,
@@ -39,9 +40,15 @@ pub use request::{
     read_frame_bytes,
 };
 pub use response::{
+    ChannelStream,
     ConnectResponse,
+    Heartbeat,
+    OnCancel,
     ResponsePipeline,
     StreamBody,
+    StreamSender,
+    StreamWriteConfig,
+    TrailerSetter,
     // Primitive functions
     compress_bytes,
     encode_json,
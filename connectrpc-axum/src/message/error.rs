@@ -5,7 +5,7 @@
 
 use axum::{
     Json,
-    body::Body,
+    body::{Body, Bytes},
     http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header},
     response::{IntoResponse, Response},
 };
@@ -15,7 +15,12 @@ use std::collections::HashMap;
 use crate::context::{ConnectContext, RequestProtocol};
 
 // Re-export core types
-pub use connectrpc_axum_core::{Code, ErrorDetail, Status};
+pub use connectrpc_axum_core::{Code, ErrorDetail, Status, error_details};
+use error_details::{
+    BadRequest, DebugInfo, ErrorInfo, FieldViolation, Help, HelpLink, LocalizedMessage,
+    PreconditionFailure, PreconditionViolation, QuotaFailure, QuotaViolation, RequestInfo,
+    ResourceInfo, RetryInfo,
+};
 
 // ============================================================================
 // ConnectError - Server-side error with HTTP response generation
@@ -122,6 +127,20 @@ impl ConnectError {
         self.inner.details()
     }
 
+    /// Find the first detail of message type `M` and decode it.
+    ///
+    /// See [`Status::find_detail`].
+    pub fn find_detail<M: prost::Message + prost::Name + Default>(&self) -> Option<M> {
+        self.inner.find_detail()
+    }
+
+    /// Decode all details of message type `M`.
+    ///
+    /// See [`Status::find_details`].
+    pub fn find_details<M: prost::Message + prost::Name + Default>(&self) -> Vec<M> {
+        self.inner.find_details()
+    }
+
     /// Add an error detail with type URL and protobuf-encoded bytes.
     pub fn add_detail<S: Into<String>>(mut self, type_url: S, value: Vec<u8>) -> Self {
         self.inner = self.inner.add_detail(type_url, value);
@@ -134,6 +153,109 @@ impl ConnectError {
         self
     }
 
+    /// Add an error detail by encoding a protobuf message.
+    ///
+    /// The type URL comes from the message's [`prost::Name`] implementation.
+    /// The typed builders below cover the standard [`error_details`] messages.
+    pub fn add_message_detail<M: prost::Message + prost::Name>(mut self, message: &M) -> Self {
+        self.inner = self.inner.add_message_detail(message);
+        self
+    }
+
+    /// Add a `google.rpc.RetryInfo` detail telling clients how long to wait
+    /// before retrying.
+    pub fn with_retry_info(self, retry_delay: std::time::Duration) -> Self {
+        self.add_message_detail(&RetryInfo::new(retry_delay))
+    }
+
+    /// Add a `google.rpc.BadRequest` detail listing invalid request fields.
+    ///
+    /// ```ignore
+    /// ConnectError::new_invalid_argument("invalid request")
+    ///     .with_bad_request([FieldViolation::new("name", "must not be empty")])
+    /// ```
+    pub fn with_bad_request(
+        self,
+        field_violations: impl IntoIterator<Item = FieldViolation>,
+    ) -> Self {
+        self.add_message_detail(&BadRequest {
+            field_violations: field_violations.into_iter().collect(),
+        })
+    }
+
+    /// Add a `google.rpc.QuotaFailure` detail describing exhausted quotas.
+    pub fn with_quota_failure(self, violations: impl IntoIterator<Item = QuotaViolation>) -> Self {
+        self.add_message_detail(&QuotaFailure {
+            violations: violations.into_iter().collect(),
+        })
+    }
+
+    /// Add a `google.rpc.PreconditionFailure` detail describing failed preconditions.
+    pub fn with_precondition_failure(
+        self,
+        violations: impl IntoIterator<Item = PreconditionViolation>,
+    ) -> Self {
+        self.add_message_detail(&PreconditionFailure {
+            violations: violations.into_iter().collect(),
+        })
+    }
+
+    /// Add a `google.rpc.ErrorInfo` detail with a machine-readable reason.
+    pub fn with_error_info(self, info: ErrorInfo) -> Self {
+        self.add_message_detail(&info)
+    }
+
+    /// Add a `google.rpc.ResourceInfo` detail describing the accessed resource.
+    pub fn with_resource_info(self, info: ResourceInfo) -> Self {
+        self.add_message_detail(&info)
+    }
+
+    /// Add a `google.rpc.RequestInfo` detail identifying the request.
+    pub fn with_request_info(
+        self,
+        request_id: impl Into<String>,
+        serving_data: impl Into<String>,
+    ) -> Self {
+        self.add_message_detail(&RequestInfo {
+            request_id: request_id.into(),
+            serving_data: serving_data.into(),
+        })
+    }
+
+    /// Add a `google.rpc.Help` detail with documentation links.
+    pub fn with_help(self, links: impl IntoIterator<Item = HelpLink>) -> Self {
+        self.add_message_detail(&Help {
+            links: links.into_iter().collect(),
+        })
+    }
+
+    /// Add a `google.rpc.LocalizedMessage` detail with a user-facing message.
+    pub fn with_localized_message(
+        self,
+        locale: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.add_message_detail(&LocalizedMessage {
+            locale: locale.into(),
+            message: message.into(),
+        })
+    }
+
+    /// Add a `google.rpc.DebugInfo` detail.
+    ///
+    /// Debug details are sent to the client as-is; avoid leaking internals in
+    /// production.
+    pub fn with_debug_info(
+        self,
+        stack_entries: impl IntoIterator<Item = String>,
+        detail: impl Into<String>,
+    ) -> Self {
+        self.add_message_detail(&DebugInfo {
+            stack_entries: stack_entries.into_iter().collect(),
+            detail: detail.into(),
+        })
+    }
+
     /// Get a reference to the inner Status.
     pub fn status(&self) -> &Status {
         &self.inner
@@ -268,27 +390,7 @@ impl IntoResponse for ConnectError {
 impl ConnectError {
     /// Convert error code to HTTP status code (for unary responses only)
     fn http_status_code(&self) -> StatusCode {
-        match self.inner.code() {
-            Code::Ok => StatusCode::OK,
-            // 499 Client Closed Request (nginx extension) - client canceled the operation
-            Code::Canceled => StatusCode::from_u16(499).unwrap(),
-            Code::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
-            Code::InvalidArgument => StatusCode::BAD_REQUEST,
-            // 504 Gateway Timeout - server-side deadline exceeded
-            Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
-            Code::NotFound => StatusCode::NOT_FOUND,
-            Code::AlreadyExists => StatusCode::CONFLICT,
-            Code::PermissionDenied => StatusCode::FORBIDDEN,
-            Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
-            Code::FailedPrecondition => StatusCode::BAD_REQUEST,
-            Code::Aborted => StatusCode::CONFLICT,
-            Code::OutOfRange => StatusCode::BAD_REQUEST,
-            Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
-            Code::Internal => StatusCode::INTERNAL_SERVER_ERROR,
-            Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
-            Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
-            Code::Unauthenticated => StatusCode::UNAUTHORIZED,
-        }
+        self.inner.code().to_http_status()
     }
 
     /// Create a streaming error response with proper EndStream framing.
@@ -336,13 +438,7 @@ impl ConnectError {
         // Use build_end_stream_frame which properly includes error metadata in the
         // EndStream JSON payload's "metadata" field (per Connect protocol spec)
         let frame = build_end_stream_frame_with_limit(Some(&self), None, send_max_bytes);
-
-        // Build the response with HTTP 200 and streaming content-type
-        Response::builder()
-            .status(StatusCode::OK)
-            .header(header::CONTENT_TYPE, HeaderValue::from_static(content_type))
-            .body(Body::from(frame))
-            .unwrap_or_else(|_| internal_error_streaming_response(content_type))
+        end_stream_only_response(content_type, frame)
     }
 }
 
@@ -376,21 +472,9 @@ impl From<(StatusCode, String)> for ConnectError {
 /// Convert an HTTP status code to a Connect error code.
 ///
 /// This is used when translating HTTP errors to Connect errors.
+/// See [`Code::from_http_status_connect`].
 pub fn code_from_status(status: StatusCode) -> Code {
-    match status {
-        StatusCode::OK => Code::Ok,
-        StatusCode::BAD_REQUEST => Code::InvalidArgument,
-        StatusCode::UNAUTHORIZED => Code::Unauthenticated,
-        StatusCode::FORBIDDEN => Code::PermissionDenied,
-        StatusCode::NOT_FOUND => Code::NotFound,
-        StatusCode::CONFLICT => Code::AlreadyExists,
-        StatusCode::REQUEST_TIMEOUT => Code::DeadlineExceeded,
-        StatusCode::TOO_MANY_REQUESTS => Code::ResourceExhausted,
-        StatusCode::NOT_IMPLEMENTED => Code::Unimplemented,
-        StatusCode::SERVICE_UNAVAILABLE => Code::Unavailable,
-        StatusCode::INTERNAL_SERVER_ERROR => Code::Internal,
-        _ => Code::Unknown,
-    }
+    Code::from_http_status_connect(status)
 }
 
 // ============================================================================
@@ -414,49 +498,13 @@ impl Serialize for ConnectError {
 /// Convert a tonic Code to a Connect Code.
 #[cfg(feature = "tonic")]
 pub fn code_from_tonic(code: ::tonic::Code) -> Code {
-    match code {
-        ::tonic::Code::Ok => Code::Ok,
-        ::tonic::Code::Cancelled => Code::Canceled,
-        ::tonic::Code::Unknown => Code::Unknown,
-        ::tonic::Code::InvalidArgument => Code::InvalidArgument,
-        ::tonic::Code::DeadlineExceeded => Code::DeadlineExceeded,
-        ::tonic::Code::NotFound => Code::NotFound,
-        ::tonic::Code::AlreadyExists => Code::AlreadyExists,
-        ::tonic::Code::PermissionDenied => Code::PermissionDenied,
-        ::tonic::Code::ResourceExhausted => Code::ResourceExhausted,
-        ::tonic::Code::FailedPrecondition => Code::FailedPrecondition,
-        ::tonic::Code::Aborted => Code::Aborted,
-        ::tonic::Code::OutOfRange => Code::OutOfRange,
-        ::tonic::Code::Unimplemented => Code::Unimplemented,
-        ::tonic::Code::Internal => Code::Internal,
-        ::tonic::Code::Unavailable => Code::Unavailable,
-        ::tonic::Code::DataLoss => Code::DataLoss,
-        ::tonic::Code::Unauthenticated => Code::Unauthenticated,
-    }
+    Code::from_grpc_code(code as u32).unwrap_or(Code::Unknown)
 }
 
 /// Convert a Connect Code to a tonic Code.
 #[cfg(feature = "tonic")]
 pub fn code_to_tonic(code: Code) -> ::tonic::Code {
-    match code {
-        Code::Ok => ::tonic::Code::Ok,
-        Code::Canceled => ::tonic::Code::Cancelled,
-        Code::Unknown => ::tonic::Code::Unknown,
-        Code::InvalidArgument => ::tonic::Code::InvalidArgument,
-        Code::DeadlineExceeded => ::tonic::Code::DeadlineExceeded,
-        Code::NotFound => ::tonic::Code::NotFound,
-        Code::AlreadyExists => ::tonic::Code::AlreadyExists,
-        Code::PermissionDenied => ::tonic::Code::PermissionDenied,
-        Code::ResourceExhausted => ::tonic::Code::ResourceExhausted,
-        Code::FailedPrecondition => ::tonic::Code::FailedPrecondition,
-        Code::Aborted => ::tonic::Code::Aborted,
-        Code::OutOfRange => ::tonic::Code::OutOfRange,
-        Code::Unimplemented => ::tonic::Code::Unimplemented,
-        Code::Internal => ::tonic::Code::Internal,
-        Code::Unavailable => ::tonic::Code::Unavailable,
-        Code::DataLoss => ::tonic::Code::DataLoss,
-        Code::Unauthenticated => ::tonic::Code::Unauthenticated,
-    }
+    ::tonic::Code::from_i32(code.to_grpc_code() as i32)
 }
 
 #[cfg(feature = "tonic")]
@@ -659,6 +707,76 @@ pub(crate) fn build_end_stream_frame_with_limit(
     wrap_end_stream_payload(&reduced_payload)
 }
 
+/// Marks a streaming response whose body is a single EndStream frame.
+///
+/// Holds the frame so the gRPC translation can send a trailers-only response
+/// without reading the body.
+#[derive(Clone, Debug)]
+pub(crate) struct EndStreamOnly(pub(crate) Bytes);
+
+/// Build a streaming response whose body is only the given EndStream frame.
+///
+/// Used when a stream fails before its first message, skipping the message
+/// pipeline entirely.
+pub(crate) fn end_stream_only_response(content_type: &'static str, frame: Vec<u8>) -> Response {
+    let frame = Bytes::from(frame);
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, HeaderValue::from_static(content_type))
+        .extension(EndStreamOnly(frame.clone()))
+        .body(Body::from(frame))
+        .unwrap_or_else(|_| internal_error_streaming_response(content_type))
+}
+
+/// Log a caught handler panic and build the error sent to the client.
+///
+/// The panic message stays in the logs; clients only see a generic message,
+/// unless `debug_errors` attaches it as a `google.rpc.DebugInfo` detail.
+pub(crate) fn panic_error(panic: &(dyn std::any::Any + Send), debug_errors: bool) -> ConnectError {
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("<non-string panic payload>");
+    tracing::error!(target: "connectrpc_axum", panic = message, "handler panicked");
+    let err = ConnectError::new(Code::Internal, "handler panicked");
+    if debug_errors {
+        // The stack has already unwound; the panic hook prints the backtrace
+        err.add_message_detail(&debug_info(format!("panic: {message}"), false))
+    } else {
+        err
+    }
+}
+
+/// Build the `google.rpc.DebugInfo` detail attached when
+/// [`ConnectLayer::debug_errors`](crate::ConnectLayer::debug_errors) is enabled.
+///
+/// With `backtrace`, the stack entries are a backtrace captured by the caller's
+/// thread at this point, one frame line per entry.
+pub(crate) fn debug_info(detail: impl Into<String>, backtrace: bool) -> DebugInfo {
+    let stack_entries = if backtrace {
+        std::backtrace::Backtrace::force_capture()
+            .to_string()
+            .lines()
+            .map(|line| line.trim().to_owned())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    DebugInfo {
+        stack_entries,
+        detail: detail.into(),
+    }
+}
+
+/// Attach a `google.rpc.DebugInfo` with the error's message and a backtrace.
+///
+/// Used for internal failures, whose messages are not sent to clients otherwise.
+pub(crate) fn with_debug_backtrace(err: ConnectError) -> ConnectError {
+    let detail = err.message().unwrap_or_default().to_owned();
+    err.add_message_detail(&debug_info(detail, true))
+}
+
 // ============================================================================
 // Safe Fallback Responses
 // ============================================================================
@@ -778,6 +896,36 @@ mod tests {
         assert_eq!(err.details()[0].value(), &[1, 2, 3]);
     }
 
+    #[test]
+    fn test_typed_error_details() {
+        use error_details::{BadRequest, FieldViolation, QuotaViolation, RetryInfo};
+
+        let err = ConnectError::new_invalid_argument("invalid request")
+            .with_bad_request([FieldViolation::new("name", "must not be empty")])
+            .with_quota_failure([QuotaViolation::new("user:1", "daily limit")])
+            .with_retry_info(std::time::Duration::from_secs(2));
+
+        let types: Vec<_> = err.details().iter().map(|d| d.type_url()).collect();
+        assert_eq!(
+            types,
+            [
+                "type.googleapis.com/google.rpc.BadRequest",
+                "type.googleapis.com/google.rpc.QuotaFailure",
+                "type.googleapis.com/google.rpc.RetryInfo",
+            ]
+        );
+
+        let bad_request: BadRequest = err.details()[0].to_message().unwrap().unwrap();
+        assert_eq!(bad_request.field_violations[0].field, "name");
+        let retry: RetryInfo = err.details()[2].to_message().unwrap().unwrap();
+        assert_eq!(retry.retry_delay.unwrap().seconds, 2);
+
+        // Connect JSON uses the short type name
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["details"][2]["type"], "google.rpc.RetryInfo");
+        assert_eq!(json["details"][2]["value"], "CgIIAg");
+    }
+
     #[test]
     fn test_is_protocol_header_filters_http_headers() {
         assert!(is_protocol_header("Content-Type"));
@@ -881,4 +1029,25 @@ mod tests {
         let parsed: serde_json::Value = serde_json::from_slice(payload).unwrap();
         assert_eq!(parsed["error"]["code"], "internal");
     }
+
+    #[test]
+    fn test_panic_error_debug_info() {
+        let panic: Box<dyn std::any::Any + Send> = Box::new("boom");
+
+        let err = panic_error(panic.as_ref(), false);
+        assert!(err.details().is_empty());
+
+        let err = panic_error(panic.as_ref(), true);
+        assert_eq!(err.message(), Some("handler panicked"));
+        let info = err.details()[0].to_message::<DebugInfo>().unwrap().unwrap();
+        assert_eq!(info.detail, "panic: boom");
+    }
+
+    #[test]
+    fn test_with_debug_backtrace() {
+        let err = with_debug_backtrace(ConnectError::new_internal("encode failed"));
+        let info = err.details()[0].to_message::<DebugInfo>().unwrap().unwrap();
+        assert_eq!(info.detail, "encode failed");
+        assert!(!info.stack_entries.is_empty());
+    }
 }
@@ -21,10 +21,11 @@ use bytes::{Bytes, BytesMut};
 use futures::Stream;
 use http_body_util::BodyExt;
 use prost::Message;
-use serde::Deserialize;
 use serde::de::DeserializeOwned;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 // ============================================================================
 // Primitive Decode Functions
@@ -32,13 +33,26 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Read HTTP body bytes with a size limit.
 ///
-/// Returns `ResourceExhausted` error if the body exceeds `max_size`.
+/// The body is read incrementally, so when an HTTP decompression layer sits in
+/// front of the handler the limit applies to the decompressed bytes and reading
+/// stops as soon as it is exceeded.
+///
+/// Returns `ResourceExhausted` error if the body exceeds `max_size`, or
+/// `InvalidArgument` if the body can't be read (e.g. corrupt compressed data).
 pub async fn read_body(body: Body, max_size: usize) -> Result<Bytes, ConnectError> {
     axum::body::to_bytes(body, max_size).await.map_err(|e| {
-        ConnectError::new(
-            Code::ResourceExhausted,
-            format!("failed to read request body: {e}"),
-        )
+        let e = e.into_inner();
+        if e.is::<http_body_util::LengthLimitError>() {
+            ConnectError::new(
+                Code::ResourceExhausted,
+                format!("request body exceeds maximum allowed size of {max_size} bytes"),
+            )
+        } else {
+            ConnectError::new(
+                Code::InvalidArgument,
+                format!("failed to read request body: {e}"),
+            )
+        }
     })
 }
 
@@ -272,11 +286,24 @@ impl RequestPipeline {
     /// Decode from raw bytes (for use when body is already read).
     ///
     /// Note: For unary RPCs, decompression and size checking are handled by
-    /// Tower's DecompressionLayer and BridgeLayer respectively.
+    /// Tower's DecompressionLayer and BridgeLayer respectively. Bodies
+    /// compressed with a custom codec (see [`CompressionContext::unary`]) are
+    /// decompressed here.
+    ///
+    /// [`CompressionContext::unary`]: crate::context::CompressionContext::unary
     pub fn decode_bytes<T>(ctx: &ConnectContext, body: Bytes) -> Result<T, ContextError>
     where
         T: Message + DeserializeOwned + Default,
     {
+        let body = match ctx.compression.unary {
+            Some(unary) if !unary.request.is_identity() => {
+                decompress_bytes(body, unary.request, ctx.limits.receive_max_bytes_or_max())
+                    .map_err(|e| {
+                        ContextError::new(ctx.protocol, e, ctx.limits.get_send_max_bytes())
+                    })?
+            }
+            _ => body,
+        };
         Self::decode_message(ctx, &body)
     }
 
@@ -417,6 +444,24 @@ impl<T> Streaming<T> {
         self.inner
     }
 
+    /// Apply a per-message deadline to the stream.
+    ///
+    /// If the next message does not arrive within `timeout` (measured from the
+    /// previous message, or from this call for the first one), the stream yields
+    /// a `deadline_exceeded` error and ends. Guards long client-streaming RPCs
+    /// against peers that stop sending without closing the stream.
+    ///
+    /// Applied automatically when the server is configured with
+    /// [`ConnectLayer::stream_message_timeout`](crate::layer::ConnectLayer::stream_message_timeout).
+    pub fn with_message_timeout(self, timeout: Duration) -> TimeoutStream<T> {
+        TimeoutStream {
+            inner: self.inner,
+            timeout,
+            sleep: Box::pin(tokio::time::sleep(timeout)),
+            done: false,
+        }
+    }
+
     /// Create a Streaming from a tonic::Streaming.
     ///
     /// This is used internally by the TonicCompatibleBuilder to convert
@@ -445,15 +490,66 @@ impl<T> Stream for Streaming<T> {
     }
 }
 
+/// A message stream with a per-message deadline.
+///
+/// Created by [`Streaming::with_message_timeout`]. Yields
+/// `Err(ConnectError)` with [`Code::DeadlineExceeded`] if a message does not
+/// arrive in time, then ends.
+pub struct TimeoutStream<T> {
+    inner: Pin<Box<dyn Stream<Item = Result<T, ConnectError>> + Send>>,
+    timeout: Duration,
+    sleep: Pin<Box<tokio::time::Sleep>>,
+    done: bool,
+}
+
+impl<T> Stream for TimeoutStream<T> {
+    type Item = Result<T, ConnectError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        match this.inner.as_mut().poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                // Restart the deadline for the next message
+                let deadline = tokio::time::Instant::now() + this.timeout;
+                this.sleep.as_mut().reset(deadline);
+                Poll::Ready(Some(item))
+            }
+            Poll::Ready(None) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => match this.sleep.as_mut().poll(cx) {
+                Poll::Ready(()) => {
+                    this.done = true;
+                    Poll::Ready(Some(Err(ConnectError::new(
+                        Code::DeadlineExceeded,
+                        "message timeout",
+                    ))))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+}
+
 impl<S, T> FromRequest<S> for ConnectRequest<T>
 where
     S: Send + Sync,
-    T: Message + DeserializeOwned + Default,
+    T: Message + DeserializeOwned + Default + 'static,
 {
     type Rejection = ConnectError;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        match *req.method() {
+        let interceptor = req
+            .extensions()
+            .get::<ConnectContext>()
+            .and_then(|ctx| ctx.interceptor.clone());
+
+        let ConnectRequest(mut message) = match *req.method() {
             Method::POST => {
                 // Get context (with fallback to default if layer is missing)
                 let ctx = get_context_or_default(&req);
@@ -470,7 +566,14 @@ where
                 Code::Unimplemented,
                 "HTTP method not supported".to_string(),
             )),
+        }?;
+
+        // Run message interceptors on the decoded request
+        if let Some(interceptor) = interceptor {
+            interceptor.request(&mut message)?;
         }
+
+        Ok(ConnectRequest(message))
     }
 }
 
@@ -512,30 +615,65 @@ where
 /// Query parameters for GET unary requests.
 ///
 /// Note: Validation of required parameters (encoding, message) and their values
-/// is done in the layer via `validate_get_query_params()`. This struct uses
-/// Option for all fields to handle parse errors gracefully.
-#[derive(Deserialize, Default)]
+/// is done in the layer via `validate_get_query_params()`. All fields are
+/// optional here so a missing layer still yields a sensible error.
+#[derive(Debug, Default)]
 struct GetRequestQuery {
     /// Connect protocol version (should be "v1" when present).
     /// Validation done in layer; kept here for secondary validation.
-    #[serde(default)]
     connect: Option<String>,
-    /// Message encoding - not used here since protocol is from Context.
-    /// Protocol detection uses Context set by layer, not this field.
-    #[serde(default)]
-    #[allow(dead_code)]
-    encoding: Option<String>,
-    /// The message payload (required, but validated in layer).
-    #[serde(default)]
-    message: Option<String>,
+    /// The percent-decoded message payload (required, but validated in layer).
+    message: Option<Vec<u8>>,
     /// Whether the message is base64-encoded ("1" if true).
-    #[serde(default)]
     base64: Option<String>,
     /// Compression algorithm used on the message (e.g., "gzip").
-    #[serde(default)]
     compression: Option<String>,
 }
 
+impl GetRequestQuery {
+    /// Parse a query string.
+    ///
+    /// Values are percent-decoded (with `+` as a space) into bytes, so an
+    /// unencoded binary `message` survives intact. The encoding is not parsed
+    /// here; the protocol comes from the context.
+    fn parse(query: &str) -> Result<Self, ConnectError> {
+        let mut params = Self::default();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value: Vec<u8> =
+                percent_encoding::percent_decode_str(&value.replace('+', " ")).collect();
+            let text = |value: Vec<u8>| {
+                String::from_utf8(value).map_err(|_| {
+                    ConnectError::new(
+                        Code::InvalidArgument,
+                        format!("invalid {key} parameter: not valid UTF-8"),
+                    )
+                })
+            };
+            match key {
+                "connect" => params.connect = Some(text(value)?),
+                "message" => params.message = Some(value),
+                "base64" => params.base64 = Some(text(value)?),
+                "compression" => params.compression = Some(text(value)?),
+                _ => {}
+            }
+        }
+        Ok(params)
+    }
+
+    /// Whether `message` is base64-encoded.
+    fn is_base64(&self) -> Result<bool, ConnectError> {
+        match self.base64.as_deref() {
+            Some("1") => Ok(true),
+            None | Some("") | Some("0") => Ok(false),
+            Some(other) => Err(ConnectError::new(
+                Code::InvalidArgument,
+                format!("invalid base64 parameter: must be \"1\" or \"0\", got \"{other}\""),
+            )),
+        }
+    }
+}
+
 async fn from_get_request<S, T>(req: Request, _state: &S) -> Result<ConnectRequest<T>, ConnectError>
 where
     S: Send + Sync,
@@ -544,9 +682,7 @@ where
     // Get context (with fallback to default if layer is missing)
     let ctx = get_context_or_default(&req);
 
-    let query = req.uri().query().unwrap_or("");
-    let params: GetRequestQuery = serde_qs::from_str(query)
-        .map_err(|err| ConnectError::new(Code::InvalidArgument, err.to_string()))?;
+    let params = GetRequestQuery::parse(req.uri().query().unwrap_or(""))?;
 
     // Secondary connect version check (primary validation in layer)
     // This handles edge cases like connect being empty vs missing
@@ -560,11 +696,12 @@ where
         ));
     }
 
+    let base64 = params.is_base64()?;
     // Get message content (layer validation ensures this is present)
-    let message_str = params.message.unwrap_or_default();
+    let message = params.message.unwrap_or_default();
 
     // 1. Decode base64 if specified (handle both padded and unpadded)
-    let bytes = if params.base64.as_deref() == Some("1") {
+    let bytes = if base64 {
         use base64::{
             Engine as _, alphabet,
             engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
@@ -574,34 +711,34 @@ where
             &alphabet::URL_SAFE,
             GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
         );
-        URL_SAFE_INDIFFERENT
-            .decode(&message_str)
-            .map_err(|err| ConnectError::new(Code::InvalidArgument, err.to_string()))?
+        URL_SAFE_INDIFFERENT.decode(&message).map_err(|err| {
+            ConnectError::new(
+                Code::InvalidArgument,
+                format!("invalid message parameter: not URL-safe base64 ({err})"),
+            )
+        })?
     } else {
-        message_str.into_bytes()
+        message
     };
 
     // 2. Decompress if compression is specified (bounded to guard against bombs)
-    let bytes = match params.compression.as_deref() {
-        #[cfg(feature = "compression-gzip-stream")]
-        Some("gzip") => decompress_bytes(
-            bytes.into(),
-            CompressionEncoding::Gzip,
-            ctx.limits.receive_max_bytes_or_max(),
-        )?,
-        Some("identity") | Some("") | None => bytes.into(),
-        Some(other) => {
-            // This should be caught by layer validation, but handle as fallback
-            return Err(ConnectError::new(
-                Code::Unimplemented,
-                format!(
-                    "unknown compression \"{}\": supported encodings are {}",
-                    other,
-                    connectrpc_axum_core::supported_encodings_str()
-                ),
-            ));
-        }
+    let compression = params.compression.as_deref();
+    let Some(encoding) = CompressionEncoding::from_header(compression) else {
+        // This should be caught by layer validation, but handle as fallback
+        return Err(ConnectError::new(
+            Code::Unimplemented,
+            format!(
+                "unknown compression \"{}\": supported encodings are {}",
+                compression.unwrap_or_default(),
+                connectrpc_axum_core::supported_encodings_str()
+            ),
+        ));
     };
+    let bytes = decompress_bytes(
+        bytes.into(),
+        encoding,
+        ctx.limits.receive_max_bytes_or_max(),
+    )?;
 
     // 3. Check size after decompression
     let bytes = read_frame_bytes(bytes, ctx.limits.receive_max_bytes_or_max())?;
@@ -650,7 +787,28 @@ where
         let body = req.into_body();
 
         let stream = create_frame_stream::<T>(body, use_proto, ctx.limits, request_encoding);
-        Ok(ConnectRequest(Streaming::new(Box::pin(stream))))
+        let streaming = match ctx.interceptor {
+            // Run message interceptors on each received message
+            Some(interceptor) => {
+                use futures::StreamExt;
+                Streaming::new(Box::pin(stream.map(move |result| {
+                    let mut message = result?;
+                    interceptor.stream_receive(&mut message)?;
+                    Ok(message)
+                })))
+            }
+            None => Streaming::new(Box::pin(stream)),
+        };
+        let streaming = match ctx.stream_message_timeout {
+            Some(timeout) => Streaming::new(Box::pin(streaming.with_message_timeout(timeout))),
+            None => streaming,
+        };
+        match ctx.stream_idle {
+            Some(idle) => Ok(ConnectRequest(Streaming::new(Box::pin(
+                idle.watch(streaming),
+            )))),
+            None => Ok(ConnectRequest(streaming)),
+        }
     }
 }
 
@@ -670,6 +828,7 @@ where
     async_stream::stream! {
         let mut buffer = BytesMut::new();
         let mut body = body;
+        let mut received = 0;
 
         loop {
             // Try to parse a complete frame from the buffer
@@ -712,6 +871,13 @@ where
                     }
                 };
 
+                // Bound the number of messages, not just their size
+                received += 1;
+                if let Err(err) = limits.check_message_count(received) {
+                    yield Err(err);
+                    return;
+                }
+
                 // Decode the message using pipeline primitives
                 let message = if use_proto {
                     decode_proto(&payload)
@@ -950,3 +1116,308 @@ mod decompression_bomb_tests {
         assert_eq!(err.code(), Code::ResourceExhausted);
     }
 }
+
+#[cfg(all(test, feature = "compression-gzip-unary"))]
+mod unary_decompression_bomb_tests {
+    //! Unary bodies are decompressed by Tower's `RequestDecompressionLayer`;
+    //! `receive_max_bytes` must bound the decompressed body, not only the
+    //! compressed `Content-Length`.
+    use super::*;
+    use crate::context::CompressionConfig;
+    use crate::handler::post_connect;
+    use crate::message::ConnectResponse;
+    use crate::service_builder::MakeServiceBuilder;
+    use axum::Router;
+    use axum::http::header;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+    use tower::ServiceExt;
+
+    async fn echo(
+        ConnectRequest(req): ConnectRequest<String>,
+    ) -> Result<ConnectResponse<String>, ConnectError> {
+        Ok(ConnectResponse::new(req))
+    }
+
+    fn app() -> Router {
+        MakeServiceBuilder::new()
+            .compression(CompressionConfig::default())
+            .receive_max_bytes(64 * 1024)
+            .add_router(Router::new().route("/test.Svc/Echo", post_connect(echo)))
+            .build()
+    }
+
+    fn gzip_request(body: &[u8]) -> Request {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        let compressed = encoder.finish().unwrap();
+        Request::post("/test.Svc/Echo")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_ENCODING, "gzip")
+            .body(Body::from(compressed))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn unary_gzip_bomb_is_rejected() {
+        // A JSON string of 2 MiB compresses to a few KiB
+        let mut json = vec![b'"'];
+        json.resize(2 * 1024 * 1024, b'a');
+        json.push(b'"');
+
+        let response = app().oneshot(gzip_request(&json)).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::TOO_MANY_REQUESTS);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "resource_exhausted");
+    }
+
+    #[tokio::test]
+    async fn unary_corrupt_gzip_is_invalid_argument() {
+        let request = Request::post("/test.Svc/Echo")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::CONTENT_ENCODING, "gzip")
+            .body(Body::from("not gzip"))
+            .unwrap();
+
+        let response = app().oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "invalid_argument");
+    }
+
+    #[tokio::test]
+    async fn unary_gzip_within_limit() {
+        let response = app().oneshot(gzip_request(b"\"hi\"")).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+    }
+}
+
+#[cfg(test)]
+mod message_count_tests {
+    use super::*;
+    use futures::StreamExt;
+
+    fn frames(count: usize) -> Body {
+        let mut body = Vec::new();
+        for _ in 0..count {
+            body.push(0);
+            body.extend_from_slice(&2u32.to_be_bytes());
+            body.extend_from_slice(b"{}");
+        }
+        Body::from(body)
+    }
+
+    #[tokio::test]
+    async fn stream_ends_once_message_count_exceeded() {
+        let limits = MessageLimits::new().max_messages_per_stream(2);
+        let stream = create_frame_stream::<pbjson_types::Empty>(
+            frames(5),
+            false,
+            limits,
+            CompressionEncoding::Identity,
+        );
+        let items: Vec<_> = stream.collect().await;
+
+        assert_eq!(items.len(), 3);
+        assert!(items[0].is_ok() && items[1].is_ok());
+        let err = items[2].as_ref().unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+        assert_eq!(
+            err.message(),
+            Some("request stream exceeds maximum of 2 messages")
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_within_message_count_passes() {
+        let limits = MessageLimits::new().max_messages_per_stream(2);
+        let stream = create_frame_stream::<pbjson_types::Empty>(
+            frames(2),
+            false,
+            limits,
+            CompressionEncoding::Identity,
+        );
+        let items: Vec<_> = stream.collect().await;
+
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(Result::is_ok));
+    }
+}
+
+#[cfg(test)]
+mod message_timeout_tests {
+    use super::*;
+    use crate::context::RequestProtocol;
+    use futures::StreamExt;
+
+    const TIMEOUT: Duration = Duration::from_millis(100);
+
+    fn delayed(delay: Duration) -> Streaming<u32> {
+        let stream = async_stream::stream! {
+            yield Ok(1);
+            tokio::time::sleep(delay).await;
+            yield Ok(2);
+        };
+        Streaming::new(Box::pin(stream))
+    }
+
+    #[tokio::test]
+    async fn delayed_message_exceeds_timeout() {
+        let mut stream = delayed(Duration::from_millis(500)).with_message_timeout(TIMEOUT);
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::DeadlineExceeded);
+        assert_eq!(err.message(), Some("message timeout"));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn messages_within_timeout_pass_through() {
+        let mut stream = delayed(Duration::from_millis(20)).with_message_timeout(TIMEOUT);
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+        assert_eq!(stream.next().await.unwrap().unwrap(), 2);
+        assert!(stream.next().await.is_none());
+    }
+
+    // The server-configured timeout is applied by the streaming extractor.
+    #[tokio::test]
+    async fn extractor_applies_stream_message_timeout() {
+        // One empty proto message, then the client goes silent without
+        // sending EndStream.
+        let body = Body::from_stream(async_stream::stream! {
+            yield Ok::<_, std::io::Error>(Bytes::from_static(&[0, 0, 0, 0, 0]));
+            futures::future::pending::<()>().await;
+        });
+        let mut req = Request::post("/svc/Method").body(body).unwrap();
+        req.extensions_mut().insert(ConnectContext {
+            protocol: RequestProtocol::ConnectStreamProto,
+            stream_message_timeout: Some(TIMEOUT),
+            ..Default::default()
+        });
+
+        let ConnectRequest(mut stream) =
+            ConnectRequest::<Streaming<pbjson_types::Empty>>::from_request(req, &())
+                .await
+                .unwrap();
+
+        assert!(stream.next().await.unwrap().is_ok());
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::DeadlineExceeded);
+    }
+}
+
+#[cfg(test)]
+mod get_request_tests {
+    use super::*;
+    use crate::handler::get_connect;
+    use crate::layer::ConnectLayer;
+    use axum::http::StatusCode;
+    use tower::ServiceExt;
+
+    fn app(limits: MessageLimits) -> axum::Router {
+        async fn echo(
+            ConnectRequest(req): ConnectRequest<String>,
+        ) -> Result<crate::message::ConnectResponse<String>, ConnectError> {
+            Ok(crate::message::ConnectResponse::new(req))
+        }
+        axum::Router::new()
+            .route("/test.Svc/Echo", get_connect(echo))
+            .layer(ConnectLayer::new().limits(limits))
+    }
+
+    async fn get(app: axum::Router, query: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::get(format!("/test.Svc/Echo?{query}"))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[test]
+    fn query_values_are_percent_decoded_to_bytes() {
+        let params = GetRequestQuery::parse("message=%FF%00a+b&base64=0&extra").unwrap();
+        assert_eq!(params.message.as_deref(), Some(&b"\xff\x00a b"[..]));
+        assert_eq!(params.base64.as_deref(), Some("0"));
+        assert!(!params.is_base64().unwrap());
+    }
+
+    #[tokio::test]
+    async fn base64_message_is_decoded() {
+        // "\"hi\"" in URL-safe base64, unpadded
+        let (status, body) = get(
+            app(MessageLimits::new()),
+            "encoding=json&base64=1&message=ImhpIg",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "hi");
+    }
+
+    #[tokio::test]
+    async fn malformed_params_are_invalid_argument() {
+        let (status, body) = get(
+            app(MessageLimits::new()),
+            "encoding=json&base64=1&message=*",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "invalid_argument");
+        assert!(
+            body["message"]
+                .as_str()
+                .unwrap()
+                .starts_with("invalid message parameter: not URL-safe base64")
+        );
+
+        let (status, body) = get(
+            app(MessageLimits::new()),
+            "encoding=json&base64=yes&message=ImhpIg",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body["message"],
+            "invalid base64 parameter: must be \"1\" or \"0\", got \"yes\""
+        );
+    }
+
+    #[tokio::test]
+    async fn long_urls_are_rejected() {
+        let limits = MessageLimits::new().max_url_bytes(48);
+        let (status, _) = get(app(limits), "encoding=json&message=%22hi%22").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = get(app(limits), "encoding=json&message=%22hello%20world%22").await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["code"], "resource_exhausted");
+    }
+
+    #[cfg(feature = "compression-gzip-stream")]
+    #[tokio::test]
+    async fn compressed_message_is_decompressed() {
+        use base64::Engine as _;
+        use flate2::{Compression, write::GzEncoder};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"\"hi\"").unwrap();
+        let message =
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(encoder.finish().unwrap());
+        let query = format!("encoding=json&compression=gzip&base64=1&message={message}");
+        let (status, body) = get(app(MessageLimits::new()), &query).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "hi");
+    }
+}
@@ -9,20 +9,33 @@
 //! - [`compress_bytes`]: Compress bytes if beneficial
 //! - [`wrap_envelope`]: Wrap payload in a Connect streaming frame
 //! - [`set_connect_content_encoding`]: Set Connect-Content-Encoding header
-use crate::context::{CompressionConfig, CompressionEncoding, ConnectContext};
+use crate::context::{
+    CompressionConfig, CompressionEncoding, CompressionPolicy, ConnectContext, looks_incompressible,
+};
+use crate::interceptor::RequestInterceptor;
 use crate::message::error::{
-    Code, ConnectError, build_end_stream_frame_with_limit, internal_error_end_stream_frame,
-    internal_error_response, internal_error_streaming_response,
+    Code, ConnectError, build_end_stream_frame_with_limit, end_stream_only_response,
+    internal_error_end_stream_frame, internal_error_response, internal_error_streaming_response,
+    panic_error, with_debug_backtrace,
 };
 use crate::message::request::envelope_flags;
 use axum::{
     body::{Body, Bytes},
-    http::{HeaderValue, StatusCode, header},
+    http::{
+        HeaderMap, HeaderName, HeaderValue, StatusCode,
+        header::{self, AsHeaderName, IntoHeaderName},
+    },
     response::Response,
 };
+use bytes::BytesMut;
 use futures::Stream;
 use prost::Message;
 use serde::Serialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 // ============================================================================
 // Primitive Encode Functions
@@ -58,6 +71,9 @@ where
 /// - encoding is not Identity
 /// - data length >= min_bytes threshold
 ///
+/// With [`CompressionConfig::skip_incompressible`], data that looks already
+/// compressed, or whose compressed form is not smaller, is returned as-is.
+///
 /// Returns an error if compression fails (matching connect-go behavior).
 pub fn compress_bytes(
     data: Bytes,
@@ -72,7 +88,14 @@ pub fn compress_bytes(
         return Ok((data, false));
     }
 
+    if config.skip_incompressible && looks_incompressible(&data) {
+        return Ok((data, false));
+    }
+
     match codec.compress(&data) {
+        Ok(compressed) if config.skip_incompressible && compressed.len() >= data.len() => {
+            Ok((data, false))
+        }
         Ok(compressed) => Ok((compressed, true)),
         Err(e) => Err(ConnectError::new(Code::Internal, format!("compress: {e}"))),
     }
@@ -116,36 +139,234 @@ pub fn set_connect_content_encoding(
 
 /// Response wrapper for Connect RPC handlers.
 ///
-/// A simple tuple struct that wraps the response value.
-/// Protocol encoding is handled at the framework level, not stored in the response.
+/// Wraps the response value together with optional response headers and
+/// trailing metadata. Protocol encoding is handled at the framework level,
+/// not stored in the response.
+///
+/// Construct it with [`ConnectResponse::new`]; the message stays accessible
+/// as `.0`.
+///
+/// ```ignore
+/// let mut headers = HeaderMap::new();
+/// headers.insert("x-request-id", HeaderValue::from_static("abc"));
+/// let mut trailers = HeaderMap::new();
+/// trailers.insert("x-cache", HeaderValue::from_static("miss"));
+///
+/// Ok(ConnectResponse::new(reply)
+///     .with_metadata(headers)
+///     .with_trailers(trailers))
+/// ```
 #[derive(Debug, Clone)]
-pub struct ConnectResponse<T>(pub T);
+pub struct ConnectResponse<T>(pub T, ResponseMetadata);
+
+/// Headers and trailers a handler attached to its response.
+#[derive(Debug, Clone, Default)]
+struct ResponseMetadata {
+    headers: HeaderMap,
+    trailers: HeaderMap,
+}
 
 impl<T> ConnectResponse<T> {
     /// Create a new ConnectResponse wrapping the given value.
     pub fn new(inner: T) -> Self {
-        Self(inner)
+        Self(inner, ResponseMetadata::default())
     }
 
     /// Extract the inner value from the ConnectResponse wrapper.
+    ///
+    /// Any headers or trailers attached to the response are dropped.
     pub fn into_inner(self) -> T {
         self.0
     }
+
+    /// Add response headers.
+    ///
+    /// Headers are appended to the HTTP response for every protocol.
+    pub fn with_metadata(mut self, headers: HeaderMap) -> Self {
+        append_all(&mut self.1.headers, headers);
+        self
+    }
+
+    /// Add trailing metadata.
+    ///
+    /// For unary Connect responses, trailers are sent as headers with a
+    /// `Trailer-` prefix. For streaming responses, they are sent in the
+    /// `metadata` field of the EndStream message.
+    pub fn with_trailers(mut self, trailers: HeaderMap) -> Self {
+        append_all(&mut self.1.trailers, trailers);
+        self
+    }
+
+    /// Response headers attached to this response.
+    pub fn metadata(&self) -> &HeaderMap {
+        &self.1.headers
+    }
+
+    /// Mutable access to the response headers.
+    pub fn metadata_mut(&mut self) -> &mut HeaderMap {
+        &mut self.1.headers
+    }
+
+    /// Trailing metadata attached to this response.
+    pub fn trailers(&self) -> &HeaderMap {
+        &self.1.trailers
+    }
+
+    /// Mutable access to the trailing metadata.
+    pub fn trailers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.1.trailers
+    }
+
+    /// Set the response's entity tag.
+    ///
+    /// The tag is sent in the `ETag` header and quoted if it is not already
+    /// (`W/"..."` weak tags are kept as is). For Connect GET requests, a
+    /// matching `If-None-Match` header turns the response into an empty
+    /// `304 Not Modified`. Tags that are not valid header values are ignored.
+    ///
+    /// ```ignore
+    /// Ok(ConnectResponse::new(user).with_etag(&user.version.to_string()))
+    /// ```
+    pub fn with_etag(mut self, etag: &str) -> Self {
+        let etag = if etag.starts_with('"') || etag.starts_with("W/\"") {
+            etag.to_owned()
+        } else {
+            format!("\"{etag}\"")
+        };
+        if let Ok(value) = HeaderValue::from_str(&etag) {
+            self.1.headers.insert(header::ETAG, value);
+        }
+        self
+    }
+
+    /// Map the inner value, keeping headers and trailers.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ConnectResponse<U> {
+        ConnectResponse(f(self.0), self.1)
+    }
+}
+
+fn append_all(dst: &mut HeaderMap, src: HeaderMap) {
+    let mut name = None;
+    for (key, value) in src {
+        if key.is_some() {
+            name = key;
+        }
+        if let Some(name) = &name {
+            dst.append(name.clone(), value);
+        }
+    }
+}
+
+/// Whether an `If-None-Match` header value matches the response's `ETag`.
+///
+/// Uses the weak comparison required for `If-None-Match`: a `W/` prefix is
+/// ignored on both sides, and `*` matches any tag.
+pub(crate) fn etag_matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let (Ok(if_none_match), Ok(etag)) = (if_none_match.to_str(), etag.to_str()) else {
+        return false;
+    };
+    let opaque = |tag: &str| {
+        let tag = tag.trim();
+        tag.strip_prefix("W/").unwrap_or(tag).to_owned()
+    };
+    let etag = opaque(etag);
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == etag)
+}
+
+/// Build a `304 Not Modified` response if the request's `If-None-Match`
+/// matches the `ETag` attached to the response.
+///
+/// The 304 carries the handler's headers (including `ETag`) but no body.
+fn not_modified_response(ctx: &ConnectContext, metadata: &ResponseMetadata) -> Option<Response> {
+    let if_none_match = ctx.if_none_match.as_ref()?;
+    let etag = metadata.headers.get(header::ETAG)?;
+    if !etag_matches(if_none_match, etag) {
+        return None;
+    }
+    let mut response = Response::builder()
+        .status(StatusCode::NOT_MODIFIED)
+        .body(Body::empty())
+        .ok()?;
+    apply_response_metadata(&mut response, metadata, false);
+    Some(response)
+}
+
+/// Compress a unary response body with the negotiated custom codec, if any.
+///
+/// Returns the body and the `Content-Encoding` to send with it. Built-in
+/// encodings are left to Tower's `CompressionLayer`, unless a
+/// [`CompressionPolicy`] pins the encoding: then uncompressed bodies are sent
+/// with `Content-Encoding: identity` so that Tower leaves them alone.
+fn compress_unary_body(
+    ctx: &ConnectContext,
+    body: Bytes,
+) -> Result<(Bytes, Option<&'static str>), ConnectError> {
+    let uncompressed = (ctx.compression.policy != CompressionPolicy::Negotiate)
+        .then(|| CompressionEncoding::Identity.as_str());
+    let Some(unary) = ctx.compression.unary.filter(|u| !u.response.is_identity()) else {
+        return Ok((body, uncompressed));
+    };
+    let (body, compressed) = compress_bytes(body, unary.response, &ctx.compression.config)?;
+    let encoding = if compressed {
+        Some(unary.response.as_str())
+    } else {
+        uncompressed
+    };
+    Ok((body, encoding))
+}
+
+/// Append handler-provided headers to an HTTP response.
+///
+/// For unary responses, trailers are also written as `trailer-`-prefixed headers.
+fn apply_response_metadata(response: &mut Response, metadata: &ResponseMetadata, unary: bool) {
+    let headers = response.headers_mut();
+    for (name, value) in &metadata.headers {
+        headers.append(name, value.clone());
+    }
+    if unary {
+        for (name, value) in &metadata.trailers {
+            if let Ok(name) = HeaderName::try_from(format!("trailer-{name}")) {
+                headers.append(name, value.clone());
+            }
+        }
+    }
 }
 
 impl<T> ConnectResponse<T>
 where
-    T: Message + Serialize,
+    T: Message + Serialize + 'static,
 {
     /// Encode the response using pipeline context.
     /// This is called by handler wrappers for unary responses.
-    pub(crate) fn into_response_with_context(self, ctx: &ConnectContext) -> Response {
+    pub(crate) fn into_response_with_context(mut self, ctx: &ConnectContext) -> Response {
+        // gRPC frames unary responses too; the layer turns EndStream into trailers
+        if ctx.protocol.is_grpc() {
+            return self.into_streaming_response_with_context(ctx);
+        }
+
+        // 0. Run message interceptors on the outgoing response
+        if let Some(interceptor) = &ctx.interceptor
+            && let Err(err) = interceptor.response(&mut self.0)
+        {
+            return err.into_response_with_context(ctx);
+        }
+
+        // Answer conditional GET requests without encoding the message
+        if let Some(response) = not_modified_response(ctx, &self.1) {
+            return response;
+        }
+
         // 1. Encode based on protocol
         let body: Bytes = if ctx.protocol.is_proto() {
             Bytes::from(encode_proto(&self.0))
         } else {
             match encode_json(&self.0) {
                 Ok(bytes) => Bytes::from(bytes),
+                Err(err) if ctx.debug_errors => {
+                    return with_debug_backtrace(err).into_response_with_context(ctx);
+                }
                 Err(_) => return internal_error_response(ctx.protocol.error_content_type()),
             }
         };
@@ -164,23 +385,42 @@ where
         // 3. Build HTTP response
         // Note: Compression is handled by Tower's CompressionLayer for unary RPCs.
         // We don't set Content-Encoding here; Tower will add it based on Accept-Encoding.
+        // Custom codecs are the exception, as Tower doesn't know them.
+        let Ok((body, content_encoding)) = compress_unary_body(ctx, body) else {
+            return internal_error_response(ctx.protocol.error_content_type());
+        };
         let builder = Response::builder().status(StatusCode::OK).header(
             header::CONTENT_TYPE,
             HeaderValue::from_static(ctx.protocol.response_content_type()),
         );
+        let builder = match content_encoding {
+            Some(encoding) => builder.header(header::CONTENT_ENCODING, encoding),
+            None => builder,
+        };
 
-        builder
-            .body(Body::from(body))
-            .unwrap_or_else(|_| internal_error_response(ctx.protocol.error_content_type()))
+        match builder.body(Body::from(body)) {
+            Ok(mut response) => {
+                apply_response_metadata(&mut response, &self.1, true);
+                response
+            }
+            Err(_) => internal_error_response(ctx.protocol.error_content_type()),
+        }
     }
 
     /// Encode the response as a streaming response (single message frame + EndStreamResponse).
     ///
     /// This is used for client streaming RPCs where the response is a single message
     /// but must be sent in streaming format with framing.
-    pub(crate) fn into_streaming_response_with_context(self, ctx: &ConnectContext) -> Response {
+    pub(crate) fn into_streaming_response_with_context(mut self, ctx: &ConnectContext) -> Response {
         let content_type = ctx.protocol.streaming_response_content_type();
 
+        // 0. Run message interceptors on the outgoing response
+        if let Some(interceptor) = &ctx.interceptor
+            && let Err(err) = interceptor.response(&mut self.0)
+        {
+            return err.into_response_with_context(ctx);
+        }
+
         // Get envelope compression settings (for streaming, this should be Some)
         let response_encoding = ctx
             .compression
@@ -194,6 +434,9 @@ where
         } else {
             match encode_json(&self.0) {
                 Ok(bytes) => Bytes::from(bytes),
+                Err(err) if ctx.debug_errors => {
+                    return with_debug_backtrace(err).into_response_with_context(ctx);
+                }
                 Err(_) => return internal_error_streaming_response(content_type),
             }
         };
@@ -227,8 +470,11 @@ where
         let message_frame = wrap_envelope(&data, compressed);
 
         // 5. Build EndStream frame
-        let end_stream_frame =
-            build_end_stream_frame_with_limit(None, None, ctx.limits.get_send_max_bytes());
+        let end_stream_frame = build_end_stream_frame_with_limit(
+            None,
+            Some(&self.1.trailers),
+            ctx.limits.get_send_max_bytes(),
+        );
 
         // 6. Combine frames
         let mut body = message_frame;
@@ -239,9 +485,13 @@ where
             .header(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
         let builder = set_connect_content_encoding(builder, response_encoding);
 
-        builder
-            .body(Body::from(body))
-            .unwrap_or_else(|_| internal_error_streaming_response(content_type))
+        match builder.body(Body::from(body)) {
+            Ok(mut response) => {
+                apply_response_metadata(&mut response, &self.1, false);
+                response
+            }
+            Err(_) => internal_error_streaming_response(content_type),
+        }
     }
 }
 
@@ -256,24 +506,504 @@ impl<T> From<ConnectResponse<T>> for Result<ConnectResponse<T>, ConnectError> {
 // Streaming Response Support
 // ============================================================================
 
+/// How encoded frames of a streaming response are written out.
+///
+/// By default every message is flushed as soon as it is encoded, which keeps
+/// latency low. High-throughput streams of small messages can batch frames
+/// instead: frames are buffered until `max_buffered_bytes` is reached, the
+/// stream has nothing more ready, or `flush_interval` has passed since the
+/// first buffered frame. The EndStream frame always flushes the buffer.
+///
+/// Set it server-wide with [`ConnectLayer::stream_write`](crate::ConnectLayer::stream_write)
+/// or per response with [`StreamBody::write_config`].
+///
+/// ```rust,ignore
+/// use connectrpc_axum::StreamWriteConfig;
+///
+/// // Batch up to 64 KiB, waiting at most 5 ms for more messages
+/// let config = StreamWriteConfig::batched()
+///     .max_buffered_bytes(64 * 1024)
+///     .flush_interval(Duration::from_millis(5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamWriteConfig {
+    max_buffered_bytes: usize,
+    flush_interval: Option<Duration>,
+    flush_per_message: bool,
+}
+
+impl Default for StreamWriteConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamWriteConfig {
+    /// Default size at which a batch is flushed.
+    pub const DEFAULT_MAX_BUFFERED_BYTES: usize = 16 * 1024;
+
+    /// Flush every message immediately.
+    pub fn new() -> Self {
+        Self {
+            max_buffered_bytes: Self::DEFAULT_MAX_BUFFERED_BYTES,
+            flush_interval: None,
+            flush_per_message: true,
+        }
+    }
+
+    /// Batch frames that are ready together, up to 16 KiB.
+    pub fn batched() -> Self {
+        Self::new().flush_per_message(false)
+    }
+
+    /// Flush once this many bytes are buffered.
+    pub fn max_buffered_bytes(mut self, max: usize) -> Self {
+        self.max_buffered_bytes = max;
+        self
+    }
+
+    /// Wait up to `interval` for more messages before flushing a partial
+    /// batch.
+    ///
+    /// Without an interval, a batch is flushed as soon as the stream has no
+    /// message ready.
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = Some(interval);
+        self
+    }
+
+    /// Flush every message immediately, ignoring the batching settings.
+    pub fn flush_per_message(mut self, enable: bool) -> Self {
+        self.flush_per_message = enable;
+        self
+    }
+
+    /// Whether frames are batched.
+    pub fn is_batched(&self) -> bool {
+        !self.flush_per_message && self.max_buffered_bytes > 0
+    }
+}
+
 /// Wrapper type for streaming response bodies.
 /// This allows us to use `ConnectResponse<StreamBody<S>>` for server streaming
 /// without conflicting with the single-message `ConnectResponse<T>` implementation.
 #[derive(Debug)]
 pub struct StreamBody<S> {
     stream: S,
+    write: Option<StreamWriteConfig>,
+    trailers: Option<TrailerSetter>,
 }
 
 impl<S> StreamBody<S> {
     /// Create a new StreamBody wrapping a stream.
     pub fn new(stream: S) -> Self {
-        Self { stream }
+        Self {
+            stream,
+            write: None,
+            trailers: None,
+        }
+    }
+
+    /// Write this stream with `config` instead of the server-wide
+    /// [`StreamWriteConfig`].
+    ///
+    /// ```rust,ignore
+    /// // A chatty metrics feed: batch small messages
+    /// Ok(ConnectResponse::new(
+    ///     StreamBody::new(samples).write_config(StreamWriteConfig::batched()),
+    /// ))
+    /// ```
+    pub fn write_config(mut self, config: StreamWriteConfig) -> Self {
+        self.write = Some(config);
+        self
+    }
+
+    /// Send the trailers in `setter` in the EndStream frame (trailers for gRPC).
+    ///
+    /// The setter is read when the stream ends, so values computed while
+    /// producing messages, such as item counts or checksums, make it into the
+    /// EndStream metadata. They are appended after trailers set with
+    /// [`ConnectResponse::with_trailers`].
+    ///
+    /// ```rust,ignore
+    /// let trailers = TrailerSetter::new();
+    /// let counter = trailers.clone();
+    /// let stream = rows.enumerate().map(move |(i, row)| {
+    ///     counter.insert("x-row-count", (i + 1).into());
+    ///     Ok(row)
+    /// });
+    /// Ok(ConnectResponse::new(StreamBody::new(stream).trailers(trailers)))
+    /// ```
+    pub fn trailers(mut self, setter: TrailerSetter) -> Self {
+        self.trailers = Some(setter);
+        self
     }
 
     /// Extract the underlying stream.
     pub fn into_inner(self) -> S {
         self.stream
     }
+
+    /// Run `f` if the response stream is dropped before it ends.
+    ///
+    /// The stream is dropped when the client goes away (disconnect, cancel,
+    /// reset), so producers feeding it through a channel or a spawned task can
+    /// stop promptly. `f` does not run once the stream has returned its last
+    /// item or an error.
+    ///
+    /// ```rust,ignore
+    /// let (tx, rx) = tokio::sync::mpsc::channel(16);
+    /// let token = CancellationToken::new();
+    /// tokio::spawn(produce(tx, token.clone()));
+    ///
+    /// let stream = ReceiverStream::new(rx).map(Ok);
+    /// Ok(ConnectResponse::new(
+    ///     StreamBody::new(stream).on_cancel(move || token.cancel()),
+    /// ))
+    /// ```
+    pub fn on_cancel<F>(self, f: F) -> StreamBody<OnCancel<S>>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        StreamBody {
+            stream: OnCancel {
+                stream: Box::pin(self.stream),
+                on_cancel: Some(Box::new(f)),
+            },
+            write: self.write,
+            trailers: self.trailers,
+        }
+    }
+
+    /// Send `heartbeat()` whenever the stream has been idle for `interval`.
+    ///
+    /// Keeps long-lived streams alive behind proxies and load balancers that
+    /// close idle connections. The Connect and gRPC framings have no no-op
+    /// frame, so the heartbeat is an ordinary message that clients need to
+    /// recognize and skip (e.g. a `oneof` variant or an empty message).
+    /// Heartbeats stop once the stream ends.
+    ///
+    /// For keep-alive that is invisible to the application, configure HTTP/2
+    /// PINGs on the server connection instead.
+    ///
+    /// ```rust,ignore
+    /// let stream = events.map(|e| Ok(WatchResponse { event: Some(e) }));
+    /// Ok(ConnectResponse::new(
+    ///     StreamBody::new(stream).heartbeat(Duration::from_secs(30), WatchResponse::default),
+    /// ))
+    /// ```
+    pub fn heartbeat<T, F>(self, interval: Duration, heartbeat: F) -> StreamBody<Heartbeat<S, T>>
+    where
+        F: Fn() -> T + Send + 'static,
+    {
+        StreamBody {
+            stream: Heartbeat {
+                stream: Box::pin(self.stream),
+                heartbeat: Box::new(heartbeat),
+                interval,
+                sleep: Box::pin(tokio::time::sleep(interval)),
+                done: false,
+            },
+            write: self.write,
+            trailers: self.trailers,
+        }
+    }
+}
+
+/// Stream wrapper created by [`StreamBody::heartbeat`].
+pub struct Heartbeat<S, T> {
+    stream: Pin<Box<S>>,
+    heartbeat: Box<dyn Fn() -> T + Send>,
+    interval: Duration,
+    sleep: Pin<Box<tokio::time::Sleep>>,
+    done: bool,
+}
+
+impl<S, T> std::fmt::Debug for Heartbeat<S, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Heartbeat")
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, T, E> Stream for Heartbeat<S, T>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        if let Poll::Ready(item) = self.stream.as_mut().poll_next(cx) {
+            if item.is_none() {
+                self.done = true;
+            }
+            let deadline = tokio::time::Instant::now() + self.interval;
+            self.sleep.as_mut().reset(deadline);
+            return Poll::Ready(item);
+        }
+        std::task::ready!(self.sleep.as_mut().poll(cx));
+        let deadline = tokio::time::Instant::now() + self.interval;
+        self.sleep.as_mut().reset(deadline);
+        Poll::Ready(Some(Ok((self.heartbeat)())))
+    }
+}
+
+/// Stream wrapper created by [`StreamBody::on_cancel`].
+pub struct OnCancel<S> {
+    stream: Pin<Box<S>>,
+    on_cancel: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl<S> std::fmt::Debug for OnCancel<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OnCancel")
+            .field("armed", &self.on_cancel.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, T, E> Stream for OnCancel<S>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = std::task::ready!(self.stream.as_mut().poll_next(cx));
+        // The stream ended on its own (or with an error, which ends the response)
+        if !matches!(item, Some(Ok(_))) {
+            self.on_cancel = None;
+        }
+        Poll::Ready(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S> Drop for OnCancel<S> {
+    fn drop(&mut self) {
+        if let Some(on_cancel) = self.on_cancel.take() {
+            on_cancel();
+        }
+    }
+}
+
+impl<T> StreamBody<ChannelStream<T>> {
+    /// Create a response stream fed through a bounded channel.
+    ///
+    /// At most `capacity` messages wait in the channel; once it is full,
+    /// [`StreamSender::send`] waits until the response has been written
+    /// further. The response is only polled as fast as the connection accepts
+    /// data (HTTP/2 flow control, socket buffers), so a producer running in a
+    /// background task cannot outrun a slow client.
+    ///
+    /// The stream ends when every sender is dropped.
+    ///
+    /// ```rust,ignore
+    /// async fn export(
+    ///     ConnectRequest(req): ConnectRequest<ExportRequest>,
+    /// ) -> Result<ConnectResponse<StreamBody<ChannelStream<Row>>>, ConnectError> {
+    ///     let (tx, body) = StreamBody::channel(32);
+    ///     tokio::spawn(async move {
+    ///         for row in query(req).await {
+    ///             // Fails once the client is gone
+    ///             if tx.send(row).await.is_err() {
+    ///                 return;
+    ///             }
+    ///         }
+    ///     });
+    ///     Ok(ConnectResponse::new(body))
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn channel(capacity: usize) -> (StreamSender<T>, Self) {
+        let (tx, rx) = tokio::sync::mpsc::channel(capacity);
+        (StreamSender { tx }, StreamBody::new(ChannelStream { rx }))
+    }
+}
+
+/// Sending half of [`StreamBody::channel`].
+///
+/// Clone it to produce from several tasks.
+#[derive(Debug)]
+pub struct StreamSender<T> {
+    tx: tokio::sync::mpsc::Sender<Result<T, ConnectError>>,
+}
+
+impl<T> Clone for StreamSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<T> StreamSender<T> {
+    /// Send a message, waiting while the channel is full.
+    ///
+    /// Returns a `canceled` error once the response stream is gone (the
+    /// client disconnected or the response ended with an error).
+    pub async fn send(&self, message: T) -> Result<(), ConnectError> {
+        self.tx.send(Ok(message)).await.map_err(|_| closed_error())
+    }
+
+    /// End the response stream with `err`.
+    ///
+    /// Messages sent before are still delivered; the error goes out in the
+    /// EndStream frame (trailers for gRPC).
+    pub async fn error(self, err: ConnectError) -> Result<(), ConnectError> {
+        self.tx.send(Err(err)).await.map_err(|_| closed_error())
+    }
+
+    /// Whether the response stream is gone.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Wait until the response stream is gone.
+    pub async fn closed(&self) {
+        self.tx.closed().await
+    }
+}
+
+fn closed_error() -> ConnectError {
+    ConnectError::new(Code::Canceled, "response stream closed")
+}
+
+/// Handle for trailers computed while a response stream is produced.
+///
+/// Attach it with [`StreamBody::trailers`] and keep a clone in the code
+/// producing messages. Whatever it holds when the stream ends, successfully
+/// or with an error, is sent in the EndStream frame's `metadata` (trailers
+/// for gRPC).
+///
+/// Cheap to clone; all clones share the same trailers.
+#[derive(Clone, Debug, Default)]
+pub struct TrailerSetter {
+    trailers: Arc<Mutex<HeaderMap>>,
+}
+
+impl TrailerSetter {
+    /// Create an empty setter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a trailer, replacing any values with the same name.
+    pub fn insert(&self, name: impl IntoHeaderName, value: HeaderValue) {
+        self.lock().insert(name, value);
+    }
+
+    /// Add a trailer value, keeping existing values with the same name.
+    pub fn append(&self, name: impl IntoHeaderName, value: HeaderValue) {
+        self.lock().append(name, value);
+    }
+
+    /// Remove all values of a trailer.
+    pub fn remove(&self, name: impl AsHeaderName) {
+        self.lock().remove(name);
+    }
+
+    /// The trailers set so far.
+    pub fn get(&self) -> HeaderMap {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HeaderMap> {
+        self.trailers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Stream created by [`StreamBody::channel`].
+#[derive(Debug)]
+pub struct ChannelStream<T> {
+    rx: tokio::sync::mpsc::Receiver<Result<T, ConnectError>>,
+}
+
+impl<T> Stream for ChannelStream<T> {
+    type Item = Result<T, ConnectError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+/// Frame stream that coalesces frames according to a [`StreamWriteConfig`].
+struct WriteBuffer<S> {
+    frames: S,
+    config: StreamWriteConfig,
+    buffer: BytesMut,
+    /// Flushes a partial batch once `flush_interval` has passed.
+    flush_at: Option<Pin<Box<tokio::time::Sleep>>>,
+    done: bool,
+}
+
+impl<S> WriteBuffer<S> {
+    fn new(frames: S, config: StreamWriteConfig) -> Self {
+        Self {
+            frames,
+            config,
+            buffer: BytesMut::new(),
+            flush_at: None,
+            done: false,
+        }
+    }
+
+    fn flush(&mut self) -> Option<Bytes> {
+        self.flush_at = None;
+        (!self.buffer.is_empty()).then(|| self.buffer.split().freeze())
+    }
+}
+
+impl<S> Stream for WriteBuffer<S>
+where
+    S: Stream<Item = Bytes> + Unpin,
+{
+    type Item = Bytes;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.done {
+                return Poll::Ready(this.flush());
+            }
+            match Pin::new(&mut this.frames).poll_next(cx) {
+                Poll::Ready(Some(frame)) => {
+                    if this.buffer.is_empty() {
+                        if frame.len() >= this.config.max_buffered_bytes {
+                            return Poll::Ready(Some(frame));
+                        }
+                        if let Some(interval) = this.config.flush_interval {
+                            this.flush_at = Some(Box::pin(tokio::time::sleep(interval)));
+                        }
+                    }
+                    this.buffer.extend_from_slice(&frame);
+                    if this.buffer.len() >= this.config.max_buffered_bytes {
+                        return Poll::Ready(this.flush());
+                    }
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => {
+                    if this.buffer.is_empty() {
+                        return Poll::Pending;
+                    }
+                    if let Some(flush_at) = &mut this.flush_at {
+                        std::task::ready!(flush_at.as_mut().poll(cx));
+                    }
+                    return Poll::Ready(this.flush());
+                }
+            }
+        }
+    }
 }
 
 impl<S, T> ConnectResponse<StreamBody<S>>
@@ -291,15 +1021,57 @@ where
             .map(|e| e.response)
             .unwrap_or(CompressionEncoding::Identity);
 
-        self.into_response_with_context_inner(
+        let write = self.0.write.unwrap_or(ctx.stream_write);
+
+        // Convert panics while producing messages into an internal EndStream error
+        let catch_panic = ctx.catch_panic;
+        let debug_errors = ctx.debug_errors;
+        let shutdown = ctx.shutdown.clone();
+        let idle = ctx.stream_idle.clone();
+        let response = self.map(|body| {
+            use futures::StreamExt;
+            use futures::stream::BoxStream;
+            use std::panic::AssertUnwindSafe;
+
+            let stream: BoxStream<'static, Result<T, ConnectError>> = if catch_panic {
+                AssertUnwindSafe(body.stream)
+                    .catch_unwind()
+                    .map(move |item| {
+                        item.unwrap_or_else(|panic| Err(panic_error(panic.as_ref(), debug_errors)))
+                    })
+                    .boxed()
+            } else {
+                body.stream.boxed()
+            };
+            // End the stream with an unavailable error once a shutdown's grace period is over
+            let stream = match shutdown {
+                Some(shutdown) => shutdown.close_stream(stream).boxed(),
+                None => stream,
+            };
+            // End the stream with deadline_exceeded once neither side has sent a message
+            let stream = match idle {
+                Some(idle) => idle.watch(stream).boxed(),
+                None => stream,
+            };
+            StreamBody {
+                stream,
+                write: None,
+                trailers: body.trailers,
+            }
+        });
+
+        response.into_response_with_context_inner(
             ctx.protocol.is_proto(),
             ctx.protocol.streaming_response_content_type(),
             response_encoding,
             &ctx.compression.config,
             ctx.limits.get_send_max_bytes(),
+            ctx.interceptor.clone(),
+            write,
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn into_response_with_context_inner(
         self,
         use_proto: bool,
@@ -307,10 +1079,11 @@ where
         response_encoding: CompressionEncoding,
         config: &CompressionConfig,
         send_max_bytes: Option<usize>,
+        interceptor: Option<RequestInterceptor>,
+        write: StreamWriteConfig,
     ) -> Response {
         use crate::message::error::Code;
-        use futures::StreamExt;
-        use std::sync::Arc;
+        use futures::{FutureExt, StreamExt};
         use std::sync::atomic::{AtomicBool, Ordering};
 
         // Copy config for use in closure (CompressionConfig is Copy)
@@ -320,11 +1093,63 @@ where
         let error_sent = Arc::new(AtomicBool::new(false));
         let error_sent_clone = error_sent.clone();
 
-        let body_stream = self
-            .0
-            .stream
+        // Trailers are sent in the EndStream frame, whether or not the stream fails.
+        // Those from a `TrailerSetter` are read when the frame is built.
+        let ConnectResponse(body, metadata) = self;
+        let end_trailers = {
+            let fixed = Arc::new(metadata.trailers.clone());
+            let setter = body.trailers.clone();
+            move || -> HeaderMap {
+                let mut trailers = (*fixed).clone();
+                if let Some(setter) = &setter {
+                    append_all(&mut trailers, setter.get());
+                }
+                trailers
+            }
+        };
+
+        // Fast path: a stream that fails before its first message gets an
+        // EndStream-only response (trailers-only for gRPC)
+        let mut stream = body.stream.boxed();
+        let first = match stream.next().now_or_never() {
+            Some(Some(Err(err))) => {
+                let mut response = ResponsePipeline::end_stream_only(
+                    content_type,
+                    &err,
+                    &end_trailers(),
+                    send_max_bytes,
+                );
+                apply_response_metadata(&mut response, &metadata, false);
+                return response;
+            }
+            Some(Some(Ok(msg))) => Some(Ok(msg)),
+            Some(None) => {
+                // Don't poll a finished stream again
+                stream = futures::stream::empty().boxed();
+                None
+            }
+            None => None,
+        };
+        let stream = futures::stream::iter(first).chain(stream);
+
+        let trailers = end_trailers.clone();
+        let trailers_end = end_trailers;
+
+        let body_stream = stream
             .map(move |result| match result {
-                Ok(msg) => {
+                Ok(mut msg) => {
+                    // 0. Run message interceptors on the outgoing message
+                    if let Some(interceptor) = &interceptor
+                        && let Err(err) = interceptor.stream_send(&mut msg)
+                    {
+                        let frame = build_end_stream_frame_with_limit(
+                            Some(&err),
+                            Some(&trailers()),
+                            send_max_bytes,
+                        );
+                        return (Bytes::from(frame), true);
+                    }
+
                     // 1. Encode based on protocol
                     let payload: Bytes = if use_proto {
                         Bytes::from(encode_proto(&msg))
@@ -359,8 +1184,11 @@ where
                                 format!("message size {} exceeds sendMaxBytes {}", data.len(), max)
                             };
                             let err = ConnectError::new(Code::ResourceExhausted, msg);
-                            let frame =
-                                build_end_stream_frame_with_limit(Some(&err), None, send_max_bytes);
+                            let frame = build_end_stream_frame_with_limit(
+                                Some(&err),
+                                Some(&trailers()),
+                                send_max_bytes,
+                            );
                             return (Bytes::from(frame), true);
                         }
                     }
@@ -371,7 +1199,11 @@ where
                 }
                 Err(err) => {
                     // Send Error EndStreamResponse (includes error metadata in the frame)
-                    let frame = build_end_stream_frame_with_limit(Some(&err), None, send_max_bytes);
+                    let frame = build_end_stream_frame_with_limit(
+                        Some(&err),
+                        Some(&trailers()),
+                        send_max_bytes,
+                    );
                     (Bytes::from(frame), true)
                 }
             })
@@ -395,26 +1227,37 @@ where
                     } else {
                         Some(Bytes::from(build_end_stream_frame_with_limit(
                             None,
-                            None,
+                            Some(&trailers_end()),
                             send_max_bytes,
                         )))
                     }
                 })
                 .filter_map(|x| async { x }),
             )
-            // Wrap in Result for Body::from_stream
-            .map(|bytes| Ok::<_, std::convert::Infallible>(bytes));
+            .boxed();
+
+        // Batch frames if configured
+        let body_stream = if write.is_batched() {
+            WriteBuffer::new(body_stream, write).boxed()
+        } else {
+            body_stream
+        };
 
-        let body = Body::from_stream(body_stream);
+        // Wrap in Result for Body::from_stream
+        let body = Body::from_stream(body_stream.map(Ok::<_, std::convert::Infallible>));
 
         let builder = Response::builder()
             .status(StatusCode::OK)
             .header(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
         let builder = set_connect_content_encoding(builder, response_encoding);
 
-        builder
-            .body(body)
-            .unwrap_or_else(|_| internal_error_streaming_response(content_type))
+        match builder.body(body) {
+            Ok(mut response) => {
+                apply_response_metadata(&mut response, &metadata, false);
+                response
+            }
+            Err(_) => internal_error_streaming_response(content_type),
+        }
     }
 }
 
@@ -470,13 +1313,577 @@ impl ResponsePipeline {
                 })?)
             };
 
-        // 2. Build HTTP response (compression handled by Tower's CompressionLayer)
-        Response::builder()
+        // 2. Build HTTP response (compression handled by Tower's CompressionLayer,
+        // except for custom codecs)
+        let (body, content_encoding) = compress_unary_body(ctx, body)
+            .map_err(|e| ContextError::new(ctx.protocol, e, ctx.limits.get_send_max_bytes()))?;
+        let builder = Response::builder()
             .status(StatusCode::OK)
-            .header(header::CONTENT_TYPE, ctx.protocol.response_content_type())
-            .body(Body::from(body))
-            .map_err(|e| {
-                ContextError::internal(ctx.protocol, ctx.limits.get_send_max_bytes(), e.to_string())
+            .header(header::CONTENT_TYPE, ctx.protocol.response_content_type());
+        let builder = match content_encoding {
+            Some(encoding) => builder.header(header::CONTENT_ENCODING, encoding),
+            None => builder,
+        };
+        builder.body(Body::from(body)).map_err(|e| {
+            ContextError::internal(ctx.protocol, ctx.limits.get_send_max_bytes(), e.to_string())
+        })
+    }
+
+    /// Encode a unary [`ConnectResponse`] with explicit context.
+    ///
+    /// Like [`encode_with_context`](Self::encode_with_context), but also writes
+    /// the headers and trailers attached to the response. If the request's
+    /// `If-None-Match` matches the response's `ETag`, a `304 Not Modified`
+    /// without a body is returned instead.
+    pub fn encode_response_with_context<T>(
+        ctx: &ConnectContext,
+        response: &ConnectResponse<T>,
+    ) -> Result<Response<Body>, ContextError>
+    where
+        T: Message + Serialize,
+    {
+        if let Some(not_modified) = not_modified_response(ctx, &response.1) {
+            return Ok(not_modified);
+        }
+        let mut http_response = Self::encode_with_context(ctx, &response.0)?;
+        apply_response_metadata(&mut http_response, &response.1, true);
+        Ok(http_response)
+    }
+
+    /// Encode a streaming response that fails before sending any message.
+    ///
+    /// Returns an EndStream-only response carrying `err` and `trailers`,
+    /// without setting up the message encoding pipeline. Through
+    /// `ConnectLayer`, gRPC clients get a trailers-only response.
+    pub fn encode_stream_error_with_context(
+        ctx: &ConnectContext,
+        err: &ConnectError,
+        trailers: &HeaderMap,
+    ) -> Response<Body> {
+        Self::end_stream_only(
+            ctx.protocol.streaming_response_content_type(),
+            err,
+            trailers,
+            ctx.limits.get_send_max_bytes(),
+        )
+    }
+
+    fn end_stream_only(
+        content_type: &'static str,
+        err: &ConnectError,
+        trailers: &HeaderMap,
+        send_max_bytes: Option<usize>,
+    ) -> Response<Body> {
+        let frame = build_end_stream_frame_with_limit(Some(err), Some(trailers), send_max_bytes);
+        end_stream_only_response(content_type, frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::RequestProtocol;
+    use crate::message::error::EndStreamOnly;
+
+    fn header_map(name: &'static str, value: &'static str) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        map.insert(name, HeaderValue::from_static(value));
+        map
+    }
+
+    fn streaming_context() -> ConnectContext {
+        ConnectContext {
+            protocol: RequestProtocol::ConnectStreamJson,
+            ..Default::default()
+        }
+    }
+
+    async fn body_bytes(response: Response) -> Bytes {
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+    }
+
+    /// Returns the JSON payload of the trailing EndStream frame.
+    fn end_stream_payload(body: &[u8]) -> serde_json::Value {
+        let mut rest = body;
+        loop {
+            let flags = rest[0];
+            let len = u32::from_be_bytes(rest[1..5].try_into().unwrap()) as usize;
+            let payload = &rest[5..5 + len];
+            if flags & envelope_flags::END_STREAM != 0 {
+                return serde_json::from_slice(payload).unwrap();
+            }
+            rest = &rest[5 + len..];
+        }
+    }
+
+    /// Codec whose output is always one byte larger than its input.
+    struct PaddingCodec;
+
+    impl crate::context::Codec for PaddingCodec {
+        fn name(&self) -> &'static str {
+            "padding"
+        }
+
+        fn compress(&self, data: &[u8]) -> std::io::Result<Bytes> {
+            let mut out = data.to_vec();
+            out.push(0);
+            Ok(out.into())
+        }
+
+        fn decompress(&self, data: &[u8]) -> std::io::Result<Bytes> {
+            Ok(Bytes::copy_from_slice(&data[..data.len() - 1]))
+        }
+    }
+
+    #[test]
+    fn test_compress_bytes_skips_larger_output() {
+        let encoding = CompressionEncoding::custom(&PaddingCodec);
+        let data = Bytes::from("hello ".repeat(10));
+
+        let config = CompressionConfig::default();
+        let (out, compressed) = compress_bytes(data.clone(), encoding, &config).unwrap();
+        assert!(compressed);
+        assert_eq!(out.len(), data.len() + 1);
+
+        let config = config.skip_incompressible(true);
+        let (out, compressed) = compress_bytes(data.clone(), encoding, &config).unwrap();
+        assert!(!compressed);
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn test_compress_bytes_skips_high_entropy_data() {
+        let mut state = 0x2545_u32;
+        let data: Bytes = (0..1024)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
             })
+            .collect::<Vec<u8>>()
+            .into();
+        assert!(looks_incompressible(&data));
+
+        // The codec must not run at all.
+        struct FailingCodec;
+        impl crate::context::Codec for FailingCodec {
+            fn name(&self) -> &'static str {
+                "failing"
+            }
+
+            fn compress(&self, _data: &[u8]) -> std::io::Result<Bytes> {
+                Err(std::io::Error::other("compress called"))
+            }
+
+            fn decompress(&self, _data: &[u8]) -> std::io::Result<Bytes> {
+                Err(std::io::Error::other("decompress called"))
+            }
+        }
+
+        let encoding = CompressionEncoding::custom(&FailingCodec);
+        let config = CompressionConfig::default().skip_incompressible(true);
+        let (out, compressed) = compress_bytes(data.clone(), encoding, &config).unwrap();
+        assert!(!compressed);
+        assert_eq!(out, data);
+    }
+
+    #[tokio::test]
+    async fn test_unary_response_headers_and_trailers() {
+        let response = ConnectResponse::new(String::from("hi"))
+            .with_metadata(header_map("x-request-id", "abc"))
+            .with_trailers(header_map("x-cache", "miss"))
+            .into_response_with_context(&ConnectContext::default());
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-request-id"], "abc");
+        assert_eq!(response.headers()["trailer-x-cache"], "miss");
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(body_bytes(response).await, "\"hi\"");
+    }
+
+    #[tokio::test]
+    async fn test_client_streaming_response_trailers_in_end_stream() {
+        let response = ConnectResponse::new(String::from("hi"))
+            .with_metadata(header_map("x-request-id", "abc"))
+            .with_trailers(header_map("x-cache", "miss"))
+            .into_streaming_response_with_context(&streaming_context());
+
+        assert_eq!(response.headers()["x-request-id"], "abc");
+        assert!(response.headers().get("trailer-x-cache").is_none());
+
+        let end = end_stream_payload(&body_bytes(response).await);
+        assert_eq!(end["metadata"]["x-cache"], serde_json::json!(["miss"]));
+    }
+
+    #[tokio::test]
+    async fn test_server_streaming_response_trailers_in_end_stream() {
+        let stream = futures::stream::iter(vec![
+            Ok(String::from("a")),
+            Err(ConnectError::new(Code::Aborted, "stop")),
+        ]);
+        let mut response = ConnectResponse::new(StreamBody::new(stream))
+            .with_metadata(header_map("x-request-id", "abc"));
+        response
+            .trailers_mut()
+            .insert("x-cache", HeaderValue::from_static("miss"));
+        let response = response.into_response_with_context(&streaming_context());
+
+        assert_eq!(response.headers()["x-request-id"], "abc");
+
+        let end = end_stream_payload(&body_bytes(response).await);
+        assert_eq!(end["error"]["code"], "aborted");
+        assert_eq!(end["metadata"]["x-cache"], serde_json::json!(["miss"]));
+    }
+
+    #[tokio::test]
+    async fn test_trailer_setter_read_when_stream_ends() {
+        use futures::StreamExt;
+
+        let setter = TrailerSetter::new();
+        let counter = setter.clone();
+        let stream = futures::stream::iter(["a", "b", "c"])
+            .enumerate()
+            .map(move |(i, item)| {
+                counter.insert("x-count", HeaderValue::from(i + 1));
+                Ok::<_, ConnectError>(item.to_string())
+            });
+        let response = ConnectResponse::new(StreamBody::new(stream).trailers(setter))
+            .with_trailers(header_map("x-cache", "miss"))
+            .into_response_with_context(&streaming_context());
+
+        let end = end_stream_payload(&body_bytes(response).await);
+        assert!(end.get("error").is_none());
+        assert_eq!(end["metadata"]["x-count"], serde_json::json!(["3"]));
+        assert_eq!(end["metadata"]["x-cache"], serde_json::json!(["miss"]));
+    }
+
+    #[tokio::test]
+    async fn test_trailer_setter_on_error() {
+        let setter = TrailerSetter::new();
+        setter.append("x-checksum", HeaderValue::from_static("abc"));
+        let stream = futures::stream::iter(vec![
+            Ok(String::from("a")),
+            Err(ConnectError::new(Code::Aborted, "stop")),
+        ]);
+        let response = ConnectResponse::new(StreamBody::new(stream).trailers(setter))
+            .into_response_with_context(&streaming_context());
+
+        let end = end_stream_payload(&body_bytes(response).await);
+        assert_eq!(end["error"]["code"], "aborted");
+        assert_eq!(end["metadata"]["x-checksum"], serde_json::json!(["abc"]));
+    }
+
+    #[tokio::test]
+    async fn test_on_cancel_runs_when_stream_dropped_early() {
+        use futures::StreamExt;
+        use http_body_util::BodyExt;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let stream = futures::stream::iter(0..).map(|i| Ok::<_, ConnectError>(i.to_string()));
+        let response = ConnectResponse::new(StreamBody::new(stream).on_cancel(move || {
+            flag.store(true, Ordering::SeqCst);
+        }))
+        .into_response_with_context(&streaming_context());
+
+        // Read one frame, then go away like a disconnecting client
+        let mut body = response.into_body();
+        body.frame().await.unwrap().unwrap();
+        assert!(!cancelled.load(Ordering::SeqCst));
+        drop(body);
+        assert!(cancelled.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_on_cancel_skipped_when_stream_completes() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        for items in [
+            vec![Ok(String::from("a"))],
+            vec![
+                Ok(String::from("a")),
+                Err(ConnectError::new(Code::Aborted, "stop")),
+            ],
+        ] {
+            let flag = cancelled.clone();
+            let stream = futures::stream::iter(items);
+            let response = ConnectResponse::new(StreamBody::new(stream).on_cancel(move || {
+                flag.store(true, Ordering::SeqCst);
+            }))
+            .into_response_with_context(&streaming_context());
+            body_bytes(response).await;
+        }
+        assert!(!cancelled.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_stream_failing_before_first_message_is_end_stream_only() {
+        let stream = futures::stream::iter(vec![Err::<String, _>(ConnectError::new(
+            Code::NotFound,
+            "gone",
+        ))]);
+        let response = ConnectResponse::new(StreamBody::new(stream))
+            .with_metadata(header_map("x-request-id", "abc"))
+            .with_trailers(header_map("x-cache", "miss"))
+            .into_response_with_context(&streaming_context());
+
+        assert!(response.extensions().get::<EndStreamOnly>().is_some());
+        assert_eq!(response.headers()["x-request-id"], "abc");
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/connect+json"
+        );
+
+        let body = body_bytes(response).await;
+        assert_eq!(body[0], envelope_flags::END_STREAM);
+        let end = end_stream_payload(&body);
+        assert_eq!(end["error"]["code"], "not_found");
+        assert_eq!(end["metadata"]["x-cache"], serde_json::json!(["miss"]));
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_sent_while_idle() {
+        use futures::StreamExt;
+
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let mut stream = StreamBody::new(rx)
+            .heartbeat(Duration::from_millis(20), || String::from("ping"))
+            .into_inner();
+
+        // Idle for the whole interval: heartbeat
+        assert_eq!(stream.next().await.unwrap().unwrap(), "ping");
+
+        // Pending messages go out first
+        tx.unbounded_send(Ok::<_, ConnectError>(String::from("a")))
+            .unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "a");
+
+        // No heartbeats once the stream has ended
+        drop(tx);
+        assert!(stream.next().await.is_none());
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_channel_applies_backpressure() {
+        let (tx, body) = StreamBody::channel(1);
+        let response = ConnectResponse::new(body).into_response_with_context(&streaming_context());
+
+        tx.send(String::from("a")).await.unwrap();
+        // The channel is full until the response is read
+        let blocked = tokio::time::timeout(Duration::from_millis(10), tx.send(String::from("x")));
+        assert!(blocked.await.is_err());
+
+        let producer = tokio::spawn(async move {
+            tx.send(String::from("b")).await.unwrap();
+            tx.error(ConnectError::new(Code::Aborted, "stop"))
+                .await
+                .unwrap();
+        });
+        let body = body_bytes(response).await;
+        producer.await.unwrap();
+
+        assert_eq!(&body[5..8], b"\"a\"");
+        assert_eq!(&body[13..16], b"\"b\"");
+        assert_eq!(end_stream_payload(&body)["error"]["code"], "aborted");
+    }
+
+    #[tokio::test]
+    async fn test_channel_send_fails_once_response_is_gone() {
+        let (tx, body) = StreamBody::channel(4);
+        let response = ConnectResponse::new(body).into_response_with_context(&streaming_context());
+        assert!(!tx.is_closed());
+
+        drop(response);
+        tx.closed().await;
+        let err = tx.send(String::from("a")).await.unwrap_err();
+        assert_eq!(err.code(), Code::Canceled);
+    }
+
+    #[tokio::test]
+    async fn test_idle_stream_ends_with_deadline_exceeded() {
+        let ctx = ConnectContext {
+            stream_idle: Some(crate::context::IdleTimeout::new(Duration::from_millis(30))),
+            ..streaming_context()
+        };
+        let (tx, body) = StreamBody::channel(4);
+        let response = ConnectResponse::new(body).into_response_with_context(&ctx);
+        tx.send(String::from("a")).await.unwrap();
+
+        // The sender stays open but goes quiet
+        let body = body_bytes(response).await;
+        assert_eq!(&body[5..8], b"\"a\"");
+        let end = end_stream_payload(&body);
+        assert_eq!(end["error"]["code"], "deadline_exceeded");
+        assert_eq!(
+            end["error"]["message"],
+            "stream idle timeout: no message sent or received for 30ms"
+        );
+        drop(tx);
+    }
+
+    /// Lengths of the data chunks the response body is written in.
+    async fn chunk_lengths(response: Response) -> Vec<usize> {
+        use http_body_util::BodyExt;
+
+        let mut body = response.into_body();
+        let mut lengths = Vec::new();
+        while let Some(frame) = body.frame().await {
+            lengths.push(frame.unwrap().into_data().unwrap().len());
+        }
+        lengths
+    }
+
+    #[tokio::test]
+    async fn test_stream_write_batches_ready_frames() {
+        let messages = || futures::stream::iter(["a", "b", "c"].map(|m| Ok(String::from(m))));
+        // Each message frame is `[flags][len]"m"`: 8 bytes; the EndStream frame 7
+        let ctx = streaming_context();
+
+        let response =
+            ConnectResponse::new(StreamBody::new(messages())).into_response_with_context(&ctx);
+        assert_eq!(chunk_lengths(response).await, [8, 8, 8, 7]);
+
+        let ctx = ConnectContext {
+            stream_write: StreamWriteConfig::batched(),
+            ..streaming_context()
+        };
+        let response =
+            ConnectResponse::new(StreamBody::new(messages())).into_response_with_context(&ctx);
+        assert_eq!(chunk_lengths(response).await, [31]);
+
+        let response = ConnectResponse::new(
+            StreamBody::new(messages())
+                .write_config(StreamWriteConfig::batched().max_buffered_bytes(16)),
+        )
+        .into_response_with_context(&ctx);
+        assert_eq!(chunk_lengths(response).await, [16, 15]);
+
+        // The per-response setting wins over the server-wide one
+        let response = ConnectResponse::new(
+            StreamBody::new(messages()).write_config(StreamWriteConfig::new()),
+        )
+        .into_response_with_context(&ctx);
+        assert_eq!(chunk_lengths(response).await, [8, 8, 8, 7]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_write_flush_interval() {
+        use futures::StreamExt;
+
+        // One message now, the next after a pause
+        let messages = || {
+            futures::stream::iter([Ok(String::from("a"))]).chain(futures::stream::once(async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(String::from("b"))
+            }))
+        };
+        let write = |config: StreamWriteConfig| {
+            ConnectResponse::new(StreamBody::new(messages()).write_config(config))
+                .into_response_with_context(&streaming_context())
+        };
+
+        // Without an interval the first frame is flushed while the stream waits
+        let response = write(StreamWriteConfig::batched());
+        assert_eq!(chunk_lengths(response).await, [8, 15]);
+
+        let response = write(StreamWriteConfig::batched().flush_interval(Duration::from_secs(5)));
+        assert_eq!(chunk_lengths(response).await, [23]);
+    }
+
+    #[test]
+    fn test_map_keeps_metadata() {
+        let response = ConnectResponse::new(1u32)
+            .with_metadata(header_map("x-a", "1"))
+            .with_trailers(header_map("x-b", "2"))
+            .map(|n| n + 1);
+
+        assert_eq!(response.0, 2);
+        assert_eq!(response.metadata()["x-a"], "1");
+        assert_eq!(response.trailers()["x-b"], "2");
+    }
+
+    #[test]
+    fn test_etag_matches() {
+        let etag = HeaderValue::from_static("\"v1\"");
+        let matches = |value: &'static str| etag_matches(&HeaderValue::from_static(value), &etag);
+
+        assert!(matches("\"v1\""));
+        assert!(matches("W/\"v1\""));
+        assert!(matches("\"v0\", \"v1\""));
+        assert!(matches("*"));
+        assert!(!matches("\"v2\""));
+        assert!(!matches("v1"));
+    }
+
+    #[tokio::test]
+    async fn test_with_etag_answers_not_modified() {
+        let ctx = ConnectContext {
+            if_none_match: Some(HeaderValue::from_static("W/\"v1\"")),
+            ..Default::default()
+        };
+        let response = ConnectResponse::new(String::from("hi"))
+            .with_etag("v1")
+            .with_metadata(header_map("cache-control", "max-age=60"))
+            .into_response_with_context(&ctx);
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], "\"v1\"");
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "max-age=60");
+        assert!(response.headers().get(header::CONTENT_TYPE).is_none());
+        assert!(body_bytes(response).await.is_empty());
+
+        let response = ConnectResponse::new(String::from("hi"))
+            .with_etag("v2")
+            .into_response_with_context(&ctx);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::ETAG], "\"v2\"");
+        assert_eq!(body_bytes(response).await, "\"hi\"");
+    }
+
+    #[tokio::test]
+    async fn test_if_none_match_only_applies_to_get() {
+        use crate::handler::{get_connect, post_connect};
+        use crate::layer::ConnectLayer;
+        use crate::message::ConnectRequest;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        async fn versioned(
+            ConnectRequest(req): ConnectRequest<String>,
+        ) -> Result<ConnectResponse<String>, ConnectError> {
+            Ok(ConnectResponse::new(req).with_etag("v1"))
+        }
+
+        let app = axum::Router::new()
+            .route(
+                "/test.Svc/Get",
+                get_connect(versioned).merge(post_connect(versioned)),
+            )
+            .layer(ConnectLayer::new());
+
+        let get = Request::get("/test.Svc/Get?connect=v1&encoding=json&message=%22hi%22")
+            .header(header::IF_NONE_MATCH, "\"v1\"")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(get).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], "\"v1\"");
+
+        let post = Request::post("/test.Svc/Get")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::IF_NONE_MATCH, "\"v1\"")
+            .body(Body::from("\"hi\""))
+            .unwrap();
+        let response = app.oneshot(post).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_bytes(response).await, "\"hi\"");
     }
 }
@@ -0,0 +1,949 @@
+//! gRPC server reflection (`grpc.reflection.v1` and `grpc.reflection.v1alpha`).
+//!
+//! Serves the [gRPC server reflection protocol] from a `FileDescriptorSet`, so
+//! `grpcurl` and `buf curl` can list services and describe messages without
+//! local `.proto` files. Both the `v1` and the older `v1alpha` service names are
+//! served, over Connect and, with the `tonic` feature, gRPC.
+//!
+//! The descriptor set is produced at build time with
+//! `connectrpc_axum_build::CompileBuilder::file_descriptor_set`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use connectrpc_axum::reflection::ReflectionService;
+//! use connectrpc_axum::MakeServiceBuilder;
+//!
+//! const FILE_DESCRIPTOR_SET: &[u8] =
+//!     include_bytes!(concat!(env!("OUT_DIR"), "/descriptor_set.bin"));
+//!
+//! let reflection = ReflectionService::new()
+//!     .register_file_descriptor_set(FILE_DESCRIPTOR_SET)?;
+//!
+//! let app = MakeServiceBuilder::new()
+//!     .add_router(hello_router)
+//!     .add_reflection_service(&reflection)
+//!     // With the `tonic` feature, also answer gRPC reflection requests:
+//!     .add_grpc_service(reflection.grpc_service())
+//!     .add_grpc_service(reflection.grpc_service_v1alpha())
+//!     .build();
+//! ```
+//!
+//! [gRPC server reflection protocol]: https://github.com/grpc/grpc/blob/master/doc/server-reflection.md
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use axum::Router;
+use futures::{Stream, StreamExt};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::handler::post_connect;
+use crate::message::error::{Code, ConnectError};
+use crate::message::{ConnectRequest, ConnectResponse, StreamBody, Streaming};
+
+/// Fully-qualified name of the `v1` reflection service.
+pub const SERVICE_NAME: &str = "grpc.reflection.v1.ServerReflection";
+
+/// Fully-qualified name of the `v1alpha` reflection service.
+pub const V1ALPHA_SERVICE_NAME: &str = "grpc.reflection.v1alpha.ServerReflection";
+
+/// Procedure path of the `v1` `ServerReflectionInfo` RPC.
+pub const SERVER_REFLECTION_INFO_PROCEDURE: &str =
+    "/grpc.reflection.v1.ServerReflection/ServerReflectionInfo";
+
+/// Procedure path of the `v1alpha` `ServerReflectionInfo` RPC.
+pub const V1ALPHA_SERVER_REFLECTION_INFO_PROCEDURE: &str =
+    "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo";
+
+// ============================================================================
+// Messages
+// ============================================================================
+//
+// `v1` and `v1alpha` share the same wire format, so one set of types serves both.
+
+/// `grpc.reflection.v1.ServerReflectionRequest`.
+#[derive(Clone, PartialEq, ::prost::Message, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerReflectionRequest {
+    /// Host the request is for; echoed back as `valid_host`.
+    #[prost(string, tag = "1")]
+    pub host: String,
+    /// The request to perform.
+    #[prost(
+        oneof = "server_reflection_request::MessageRequest",
+        tags = "3, 4, 5, 6, 7"
+    )]
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub message_request: Option<server_reflection_request::MessageRequest>,
+}
+
+/// Nested types for [`ServerReflectionRequest`].
+pub mod server_reflection_request {
+    use serde::{Deserialize, Serialize};
+
+    /// The reflection request kinds.
+    #[derive(Clone, PartialEq, ::prost::Oneof, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub enum MessageRequest {
+        /// Find a proto file by its name, e.g. `hello.proto`.
+        #[prost(string, tag = "3")]
+        FileByFilename(String),
+        /// Find the proto file that declares a fully-qualified symbol.
+        #[prost(string, tag = "4")]
+        FileContainingSymbol(String),
+        /// Find the proto file that declares an extension.
+        #[prost(message, tag = "5")]
+        FileContainingExtension(super::ExtensionRequest),
+        /// List the extension numbers of a fully-qualified message type.
+        #[prost(string, tag = "6")]
+        AllExtensionNumbersOfType(String),
+        /// List all services; the content is ignored.
+        #[prost(string, tag = "7")]
+        ListServices(String),
+    }
+}
+
+/// `grpc.reflection.v1.ExtensionRequest`.
+#[derive(Clone, PartialEq, ::prost::Message, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExtensionRequest {
+    /// Fully-qualified name of the extended message type.
+    #[prost(string, tag = "1")]
+    pub containing_type: String,
+    /// The extension field number.
+    #[prost(int32, tag = "2")]
+    pub extension_number: i32,
+}
+
+/// `grpc.reflection.v1.ServerReflectionResponse`.
+#[derive(Clone, PartialEq, ::prost::Message, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerReflectionResponse {
+    /// The `host` of the request.
+    #[prost(string, tag = "1")]
+    pub valid_host: String,
+    /// The request this response answers.
+    #[prost(message, optional, tag = "2")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_request: Option<ServerReflectionRequest>,
+    /// The response payload.
+    #[prost(
+        oneof = "server_reflection_response::MessageResponse",
+        tags = "4, 5, 6, 7"
+    )]
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub message_response: Option<server_reflection_response::MessageResponse>,
+}
+
+/// Nested types for [`ServerReflectionResponse`].
+pub mod server_reflection_response {
+    use serde::{Deserialize, Serialize};
+
+    /// The reflection response kinds.
+    #[derive(Clone, PartialEq, ::prost::Oneof, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub enum MessageResponse {
+        /// Answer to the `file_*` requests.
+        #[prost(message, tag = "4")]
+        FileDescriptorResponse(super::FileDescriptorResponse),
+        /// Answer to `all_extension_numbers_of_type`.
+        #[prost(message, tag = "5")]
+        AllExtensionNumbersResponse(super::ExtensionNumberResponse),
+        /// Answer to `list_services`.
+        #[prost(message, tag = "6")]
+        ListServicesResponse(super::ListServiceResponse),
+        /// Returned when a request fails.
+        #[prost(message, tag = "7")]
+        ErrorResponse(super::ErrorResponse),
+    }
+}
+
+/// `grpc.reflection.v1.FileDescriptorResponse`.
+#[derive(Clone, PartialEq, ::prost::Message, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FileDescriptorResponse {
+    /// Serialized `FileDescriptorProto`s: the requested file and its dependencies.
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    #[serde(with = "base64_list")]
+    pub file_descriptor_proto: Vec<Vec<u8>>,
+}
+
+/// `grpc.reflection.v1.ExtensionNumberResponse`.
+#[derive(Clone, PartialEq, ::prost::Message, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExtensionNumberResponse {
+    /// Fully-qualified name of the extended message type.
+    #[prost(string, tag = "1")]
+    pub base_type_name: String,
+    /// The known extension numbers.
+    #[prost(int32, repeated, tag = "2")]
+    pub extension_number: Vec<i32>,
+}
+
+/// `grpc.reflection.v1.ListServiceResponse`.
+#[derive(Clone, PartialEq, ::prost::Message, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListServiceResponse {
+    /// The services in the registered descriptor sets.
+    #[prost(message, repeated, tag = "1")]
+    pub service: Vec<ServiceResponse>,
+}
+
+/// `grpc.reflection.v1.ServiceResponse`.
+#[derive(Clone, PartialEq, ::prost::Message, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServiceResponse {
+    /// Fully-qualified service name.
+    #[prost(string, tag = "1")]
+    pub name: String,
+}
+
+/// `grpc.reflection.v1.ErrorResponse`.
+#[derive(Clone, PartialEq, ::prost::Message, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ErrorResponse {
+    /// gRPC status code.
+    #[prost(int32, tag = "1")]
+    pub error_code: i32,
+    /// Error description.
+    #[prost(string, tag = "2")]
+    pub error_message: String,
+}
+
+// Proto3 JSON encodes `bytes` as standard base64.
+mod base64_list {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(value.iter().map(|bytes| STANDARD.encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<u8>>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| STANDARD.decode(s).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+// ============================================================================
+// Descriptor index
+// ============================================================================
+
+/// The subset of `google/protobuf/descriptor.proto` needed to index symbols.
+///
+/// Unknown fields are skipped on decode; responses always carry the original
+/// file bytes, so nothing is lost.
+mod descriptor {
+    /// `FileDescriptorSet`, with each file kept as raw bytes.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct FileDescriptorSet {
+        #[prost(bytes = "vec", repeated, tag = "1")]
+        pub file: Vec<Vec<u8>>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct FileDescriptorProto {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(string, tag = "2")]
+        pub package: String,
+        #[prost(string, repeated, tag = "3")]
+        pub dependency: Vec<String>,
+        #[prost(message, repeated, tag = "4")]
+        pub message_type: Vec<DescriptorProto>,
+        #[prost(message, repeated, tag = "5")]
+        pub enum_type: Vec<EnumDescriptorProto>,
+        #[prost(message, repeated, tag = "6")]
+        pub service: Vec<ServiceDescriptorProto>,
+        #[prost(message, repeated, tag = "7")]
+        pub extension: Vec<FieldDescriptorProto>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct DescriptorProto {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(message, repeated, tag = "3")]
+        pub nested_type: Vec<DescriptorProto>,
+        #[prost(message, repeated, tag = "4")]
+        pub enum_type: Vec<EnumDescriptorProto>,
+        #[prost(message, repeated, tag = "6")]
+        pub extension: Vec<FieldDescriptorProto>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct FieldDescriptorProto {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(string, tag = "2")]
+        pub extendee: String,
+        #[prost(int32, tag = "3")]
+        pub number: i32,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct EnumDescriptorProto {
+        #[prost(string, tag = "1")]
+        pub name: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ServiceDescriptorProto {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(message, repeated, tag = "2")]
+        pub method: Vec<MethodDescriptorProto>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct MethodDescriptorProto {
+        #[prost(string, tag = "1")]
+        pub name: String,
+    }
+}
+
+/// Lookup tables built from registered descriptor sets.
+#[derive(Clone, Debug, Default)]
+struct DescriptorIndex {
+    /// File name → serialized `FileDescriptorProto`.
+    files: HashMap<String, Vec<u8>>,
+    /// File name → names of the files it imports.
+    dependencies: HashMap<String, Vec<String>>,
+    /// Fully-qualified symbol → declaring file name.
+    symbols: HashMap<String, String>,
+    /// Fully-qualified service names, in registration order.
+    services: Vec<String>,
+    /// Extended message type → extension number → declaring file name.
+    extensions: HashMap<String, BTreeMap<i32, String>>,
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{scope}.{name}")
+    }
+}
+
+impl DescriptorIndex {
+    fn register(&mut self, file_descriptor_set: &[u8]) -> Result<(), prost::DecodeError> {
+        let set = descriptor::FileDescriptorSet::decode(file_descriptor_set)?;
+        for raw in set.file {
+            let file = descriptor::FileDescriptorProto::decode(raw.as_slice())?;
+            if self.files.contains_key(&file.name) {
+                continue;
+            }
+
+            for service in &file.service {
+                let name = qualify(&file.package, &service.name);
+                for method in &service.method {
+                    self.add_symbol(qualify(&name, &method.name), &file.name);
+                }
+                self.add_symbol(name.clone(), &file.name);
+                self.services.push(name);
+            }
+            for message in &file.message_type {
+                self.add_message(&file.package, message, &file.name);
+            }
+            for enum_type in &file.enum_type {
+                self.add_symbol(qualify(&file.package, &enum_type.name), &file.name);
+            }
+            for extension in &file.extension {
+                self.add_extension(&file.package, extension, &file.name);
+            }
+
+            self.dependencies
+                .insert(file.name.clone(), file.dependency.clone());
+            self.files.insert(file.name, raw);
+        }
+        Ok(())
+    }
+
+    fn add_symbol(&mut self, symbol: String, file: &str) {
+        self.symbols.insert(symbol, file.to_owned());
+    }
+
+    fn add_message(&mut self, scope: &str, message: &descriptor::DescriptorProto, file: &str) {
+        let name = qualify(scope, &message.name);
+        for nested in &message.nested_type {
+            self.add_message(&name, nested, file);
+        }
+        for enum_type in &message.enum_type {
+            self.add_symbol(qualify(&name, &enum_type.name), file);
+        }
+        for extension in &message.extension {
+            self.add_extension(&name, extension, file);
+        }
+        self.add_symbol(name, file);
+    }
+
+    fn add_extension(&mut self, scope: &str, field: &descriptor::FieldDescriptorProto, file: &str) {
+        self.add_symbol(qualify(scope, &field.name), file);
+        let extendee = field.extendee.trim_start_matches('.').to_owned();
+        self.extensions
+            .entry(extendee)
+            .or_default()
+            .insert(field.number, file.to_owned());
+    }
+
+    /// The file and its transitive dependencies, the requested file first.
+    fn file_with_dependencies(&self, name: &str) -> Option<Vec<Vec<u8>>> {
+        self.files.get(name)?;
+
+        let mut seen = HashSet::new();
+        let mut pending = vec![name];
+        let mut files = Vec::new();
+        while let Some(name) = pending.pop() {
+            if !seen.insert(name) {
+                continue;
+            }
+            if let Some(bytes) = self.files.get(name) {
+                files.push(bytes.clone());
+            }
+            if let Some(deps) = self.dependencies.get(name) {
+                pending.extend(deps.iter().rev().map(String::as_str));
+            }
+        }
+        Some(files)
+    }
+
+    fn handle(&self, request: ServerReflectionRequest) -> ServerReflectionResponse {
+        use server_reflection_request::MessageRequest;
+        use server_reflection_response::MessageResponse;
+
+        let files = |files: Option<Vec<Vec<u8>>>, what: String| {
+            files
+                .map(|file_descriptor_proto| {
+                    MessageResponse::FileDescriptorResponse(FileDescriptorResponse {
+                        file_descriptor_proto,
+                    })
+                })
+                .ok_or_else(|| not_found(format!("{what} not found")))
+        };
+
+        let result = match &request.message_request {
+            Some(MessageRequest::FileByFilename(name)) => {
+                files(self.file_with_dependencies(name), format!("file {name:?}"))
+            }
+            Some(MessageRequest::FileContainingSymbol(symbol)) => files(
+                self.symbols
+                    .get(symbol.trim_start_matches('.'))
+                    .and_then(|file| self.file_with_dependencies(file)),
+                format!("symbol {symbol:?}"),
+            ),
+            Some(MessageRequest::FileContainingExtension(ext)) => files(
+                self.extensions
+                    .get(ext.containing_type.trim_start_matches('.'))
+                    .and_then(|numbers| numbers.get(&ext.extension_number))
+                    .and_then(|file| self.file_with_dependencies(file)),
+                format!(
+                    "extension {} of {:?}",
+                    ext.extension_number, ext.containing_type
+                ),
+            ),
+            Some(MessageRequest::AllExtensionNumbersOfType(type_name)) => {
+                let type_name = type_name.trim_start_matches('.');
+                if self.symbols.contains_key(type_name) {
+                    Ok(MessageResponse::AllExtensionNumbersResponse(
+                        ExtensionNumberResponse {
+                            base_type_name: type_name.to_owned(),
+                            extension_number: self
+                                .extensions
+                                .get(type_name)
+                                .map(|numbers| numbers.keys().copied().collect())
+                                .unwrap_or_default(),
+                        },
+                    ))
+                } else {
+                    Err(not_found(format!("type {type_name:?} not found")))
+                }
+            }
+            Some(MessageRequest::ListServices(_)) => {
+                Ok(MessageResponse::ListServicesResponse(ListServiceResponse {
+                    service: self
+                        .services
+                        .iter()
+                        .map(|name| ServiceResponse { name: name.clone() })
+                        .collect(),
+                }))
+            }
+            None => Err(ErrorResponse {
+                error_code: Code::InvalidArgument.to_grpc_code() as i32,
+                error_message: "empty reflection request".to_owned(),
+            }),
+        };
+
+        ServerReflectionResponse {
+            valid_host: request.host.clone(),
+            original_request: Some(request),
+            message_response: Some(result.unwrap_or_else(MessageResponse::ErrorResponse)),
+        }
+    }
+}
+
+fn not_found(error_message: String) -> ErrorResponse {
+    ErrorResponse {
+        error_code: Code::NotFound.to_grpc_code() as i32,
+        error_message,
+    }
+}
+
+// ============================================================================
+// ReflectionService
+// ============================================================================
+
+/// The `grpc.reflection` service, answering from registered descriptor sets.
+///
+/// Serve it over Connect with [`router`](Self::router) (or
+/// [`MakeServiceBuilder::add_reflection_service`](crate::MakeServiceBuilder::add_reflection_service)),
+/// and over gRPC with `grpc_service` / `grpc_service_v1alpha` when the `tonic`
+/// feature is enabled.
+#[derive(Clone, Debug, Default)]
+pub struct ReflectionService {
+    index: Arc<DescriptorIndex>,
+}
+
+impl ReflectionService {
+    /// Create a reflection service with no registered files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an encoded `google.protobuf.FileDescriptorSet`.
+    ///
+    /// Every service in the set is listed by `list_services`. Files already
+    /// registered under the same name are kept.
+    pub fn register_file_descriptor_set(
+        mut self,
+        file_descriptor_set: &[u8],
+    ) -> Result<Self, prost::DecodeError> {
+        Arc::make_mut(&mut self.index).register(file_descriptor_set)?;
+        Ok(self)
+    }
+
+    /// Fully-qualified names of the registered services.
+    pub fn service_names(&self) -> &[String] {
+        &self.index.services
+    }
+
+    fn handle_stream<R, E>(
+        &self,
+        requests: R,
+    ) -> impl Stream<Item = Result<ServerReflectionResponse, E>> + Send + use<R, E>
+    where
+        R: Stream<Item = Result<ServerReflectionRequest, E>> + Send + 'static,
+    {
+        let index = self.index.clone();
+        requests.map(move |request| request.map(|request| index.handle(request)))
+    }
+
+    /// Connect routes for the `v1` and `v1alpha` `ServerReflectionInfo` RPCs.
+    ///
+    /// `ServerReflectionInfo` is a bidi stream, so clients must use HTTP/2.
+    pub fn router<S>(&self) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let v1 = self.clone();
+        let v1alpha = self.clone();
+
+        Router::new()
+            .route(
+                SERVER_REFLECTION_INFO_PROCEDURE,
+                post_connect(
+                    move |ConnectRequest(requests): ConnectRequest<
+                        Streaming<ServerReflectionRequest>,
+                    >| {
+                        let stream = v1.handle_stream(requests);
+                        async move {
+                            Ok::<_, ConnectError>(ConnectResponse::new(StreamBody::new(stream)))
+                        }
+                    },
+                ),
+            )
+            .route(
+                V1ALPHA_SERVER_REFLECTION_INFO_PROCEDURE,
+                post_connect(
+                    move |ConnectRequest(requests): ConnectRequest<
+                        Streaming<ServerReflectionRequest>,
+                    >| {
+                        let stream = v1alpha.handle_stream(requests);
+                        async move {
+                            Ok::<_, ConnectError>(ConnectResponse::new(StreamBody::new(stream)))
+                        }
+                    },
+                ),
+            )
+            .with_state(())
+    }
+
+    /// gRPC service for `grpc.reflection.v1`, for use with
+    /// [`MakeServiceBuilder::add_grpc_service`](crate::MakeServiceBuilder::add_grpc_service).
+    #[cfg(feature = "tonic")]
+    pub fn grpc_service(&self) -> ReflectionGrpcService {
+        ReflectionGrpcService {
+            service: self.clone(),
+        }
+    }
+
+    /// gRPC service for `grpc.reflection.v1alpha`, still used by older clients.
+    #[cfg(feature = "tonic")]
+    pub fn grpc_service_v1alpha(&self) -> ReflectionV1AlphaGrpcService {
+        ReflectionV1AlphaGrpcService {
+            service: self.clone(),
+        }
+    }
+
+    #[cfg(feature = "tonic")]
+    fn call_grpc<B>(
+        &self,
+        req: http::Request<B>,
+        procedure: &'static str,
+    ) -> futures::future::BoxFuture<
+        'static,
+        Result<http::Response<tonic::body::Body>, std::convert::Infallible>,
+    >
+    where
+        B: http_body::Body + Send + 'static,
+        B::Error: Into<tonic::codegen::StdError> + Send + 'static,
+    {
+        use tonic::server::Grpc;
+        use tonic_prost::ProstCodec;
+
+        if req.uri().path() != procedure {
+            return Box::pin(async move {
+                Ok(tonic::Status::unimplemented("unknown method").into_http())
+            });
+        }
+
+        let service = self.clone();
+        Box::pin(async move {
+            let info = tower::service_fn(
+                move |req: tonic::Request<tonic::Streaming<ServerReflectionRequest>>| {
+                    let stream = service.handle_stream(req.into_inner()).boxed();
+                    std::future::ready(Ok::<_, tonic::Status>(tonic::Response::new(stream)))
+                },
+            );
+            Ok(Grpc::new(ProstCodec::default()).streaming(info, req).await)
+        })
+    }
+}
+
+/// gRPC transport for [`ReflectionService`] (`grpc.reflection.v1`).
+#[cfg(feature = "tonic")]
+#[derive(Clone, Debug)]
+pub struct ReflectionGrpcService {
+    service: ReflectionService,
+}
+
+/// gRPC transport for [`ReflectionService`] (`grpc.reflection.v1alpha`).
+#[cfg(feature = "tonic")]
+#[derive(Clone, Debug)]
+pub struct ReflectionV1AlphaGrpcService {
+    service: ReflectionService,
+}
+
+#[cfg(feature = "tonic")]
+impl tonic::server::NamedService for ReflectionGrpcService {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+#[cfg(feature = "tonic")]
+impl tonic::server::NamedService for ReflectionV1AlphaGrpcService {
+    const NAME: &'static str = V1ALPHA_SERVICE_NAME;
+}
+
+#[cfg(feature = "tonic")]
+impl<B> tower::Service<http::Request<B>> for ReflectionGrpcService
+where
+    B: http_body::Body + Send + 'static,
+    B::Error: Into<tonic::codegen::StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = std::convert::Infallible;
+    type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        self.service
+            .call_grpc(req, SERVER_REFLECTION_INFO_PROCEDURE)
+    }
+}
+
+#[cfg(feature = "tonic")]
+impl<B> tower::Service<http::Request<B>> for ReflectionV1AlphaGrpcService
+where
+    B: http_body::Body + Send + 'static,
+    B::Error: Into<tonic::codegen::StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = std::convert::Infallible;
+    type Future = futures::future::BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        self.service
+            .call_grpc(req, V1ALPHA_SERVER_REFLECTION_INFO_PROCEDURE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::server_reflection_request::MessageRequest;
+    use super::server_reflection_response::MessageResponse;
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use tower::ServiceExt;
+
+    /// `common.proto` (a message with an extension range) imported by
+    /// `hello.proto` (a service plus an extension of `common.Base`).
+    fn descriptor_set() -> Vec<u8> {
+        use descriptor::*;
+
+        let common = FileDescriptorProto {
+            name: "common.proto".into(),
+            package: "common".into(),
+            message_type: vec![DescriptorProto {
+                name: "Base".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let hello = FileDescriptorProto {
+            name: "hello.proto".into(),
+            package: "hello".into(),
+            dependency: vec!["common.proto".into()],
+            message_type: vec![DescriptorProto {
+                name: "HelloRequest".into(),
+                nested_type: vec![DescriptorProto {
+                    name: "Inner".into(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            service: vec![ServiceDescriptorProto {
+                name: "HelloWorldService".into(),
+                method: vec![MethodDescriptorProto {
+                    name: "SayHello".into(),
+                }],
+            }],
+            extension: vec![FieldDescriptorProto {
+                name: "greeting".into(),
+                extendee: ".common.Base".into(),
+                number: 100,
+            }],
+            ..Default::default()
+        };
+        FileDescriptorSet {
+            file: vec![common.encode_to_vec(), hello.encode_to_vec()],
+        }
+        .encode_to_vec()
+    }
+
+    fn service() -> ReflectionService {
+        ReflectionService::new()
+            .register_file_descriptor_set(&descriptor_set())
+            .unwrap()
+    }
+
+    fn ask(service: &ReflectionService, request: MessageRequest) -> MessageResponse {
+        service
+            .index
+            .handle(ServerReflectionRequest {
+                host: "localhost".into(),
+                message_request: Some(request),
+            })
+            .message_response
+            .unwrap()
+    }
+
+    fn file_names(response: MessageResponse) -> Vec<String> {
+        match response {
+            MessageResponse::FileDescriptorResponse(files) => files
+                .file_descriptor_proto
+                .iter()
+                .map(|bytes| {
+                    descriptor::FileDescriptorProto::decode(bytes.as_slice())
+                        .unwrap()
+                        .name
+                })
+                .collect(),
+            other => panic!("expected files, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_list_services() {
+        let service = service();
+        assert_eq!(service.service_names(), ["hello.HelloWorldService"]);
+
+        match ask(&service, MessageRequest::ListServices("*".into())) {
+            MessageResponse::ListServicesResponse(list) => {
+                assert_eq!(list.service[0].name, "hello.HelloWorldService");
+            }
+            other => panic!("expected services, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_file_lookups_include_dependencies() {
+        let service = service();
+        let expected = ["hello.proto", "common.proto"];
+
+        let by_name = ask(
+            &service,
+            MessageRequest::FileByFilename("hello.proto".into()),
+        );
+        assert_eq!(file_names(by_name), expected);
+
+        for symbol in [
+            "hello.HelloWorldService",
+            "hello.HelloWorldService.SayHello",
+            "hello.HelloRequest.Inner",
+            ".hello.greeting",
+        ] {
+            let response = ask(
+                &service,
+                MessageRequest::FileContainingSymbol(symbol.into()),
+            );
+            assert_eq!(file_names(response), expected, "{symbol}");
+        }
+
+        let by_extension = ask(
+            &service,
+            MessageRequest::FileContainingExtension(ExtensionRequest {
+                containing_type: "common.Base".into(),
+                extension_number: 100,
+            }),
+        );
+        assert_eq!(file_names(by_extension), expected);
+
+        match ask(
+            &service,
+            MessageRequest::AllExtensionNumbersOfType("common.Base".into()),
+        ) {
+            MessageResponse::AllExtensionNumbersResponse(numbers) => {
+                assert_eq!(numbers.extension_number, [100]);
+            }
+            other => panic!("expected extension numbers, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_symbol_is_not_found() {
+        let response = ask(
+            &service(),
+            MessageRequest::FileContainingSymbol("nope.Nope".into()),
+        );
+        match response {
+            MessageResponse::ErrorResponse(error) => {
+                assert_eq!(error.error_code, Code::NotFound.to_grpc_code() as i32);
+            }
+            other => panic!("expected error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_json_shape() {
+        let request: ServerReflectionRequest =
+            serde_json::from_str(r#"{"host":"h","fileByFilename":"hello.proto"}"#).unwrap();
+        assert_eq!(
+            request.message_request,
+            Some(MessageRequest::FileByFilename("hello.proto".into()))
+        );
+
+        let response = ServerReflectionResponse {
+            message_response: Some(MessageResponse::FileDescriptorResponse(
+                FileDescriptorResponse {
+                    file_descriptor_proto: vec![vec![1, 2, 3]],
+                },
+            )),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(
+            json["fileDescriptorResponse"]["fileDescriptorProto"][0],
+            "AQID"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_services_over_connect() {
+        let body = r#"{"listServices":""}"#;
+        let mut frame = vec![0u8];
+        frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        frame.extend_from_slice(body.as_bytes());
+
+        let app: Router = service().router().layer(crate::ConnectLayer::new());
+        let req = Request::post(V1ALPHA_SERVER_REFLECTION_INFO_PROCEDURE)
+            .header(header::CONTENT_TYPE, "application/connect+json")
+            .body(Body::from(frame))
+            .unwrap();
+        let response = app.oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let len = u32::from_be_bytes(bytes[1..5].try_into().unwrap()) as usize;
+        let message: serde_json::Value = serde_json::from_slice(&bytes[5..5 + len]).unwrap();
+        assert_eq!(
+            message["listServicesResponse"]["service"][0]["name"],
+            "hello.HelloWorldService"
+        );
+    }
+
+    #[cfg(feature = "tonic")]
+    #[tokio::test]
+    async fn test_list_services_over_grpc() {
+        let payload = ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(MessageRequest::ListServices(String::new())),
+        }
+        .encode_to_vec();
+        let mut frame = vec![0u8];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+
+        let req = Request::post(SERVER_REFLECTION_INFO_PROCEDURE)
+            .header(header::CONTENT_TYPE, "application/grpc")
+            .body(Body::from(frame))
+            .unwrap();
+        let response = service().grpc_service().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let collected = http_body_util::BodyExt::collect(response.into_body())
+            .await
+            .unwrap();
+        let trailers = collected.trailers().cloned().unwrap_or_default();
+        let bytes = collected.to_bytes();
+        let decoded = ServerReflectionResponse::decode(&bytes[5..]).unwrap();
+        match decoded.message_response.unwrap() {
+            MessageResponse::ListServicesResponse(list) => {
+                assert_eq!(list.service[0].name, "hello.HelloWorldService");
+            }
+            other => panic!("expected services, got {other:?}"),
+        }
+        assert_eq!(trailers["grpc-status"], "0");
+    }
+}
@@ -41,7 +41,9 @@
 //! The builder uses `tonic::service::Routes` internally to handle multiple services.
 
 use axum::Router;
-use http::StatusCode;
+use axum::extract::Request;
+use axum::response::Response;
+use http::{HeaderValue, StatusCode};
 #[cfg(not(feature = "tonic"))]
 use std::marker::PhantomData;
 use std::time::Duration;
@@ -77,8 +79,22 @@ use tower_http::decompression::RequestDecompressionLayer;
     feature = "compression-zstd-unary"
 ))]
 use crate::context::to_tower_compression_level;
-use crate::context::{CompressionConfig, MessageLimits};
-use crate::layer::{BridgeLayer, ConnectLayer};
+use crate::context::{
+    Codec, CompressionConfig, CompressionEncoding, MessageLimits, SUPPORTED_CONTENT_TYPES,
+    detect_protocol,
+};
+use crate::dynamic::DynamicServiceRegistry;
+use crate::error_mapper::ErrorMapper;
+use crate::health::HealthService;
+use crate::interceptor::{
+    HeaderWrapper, Interceptor, MessageInterceptor, MessageWrapper, SharedInterceptor,
+    push_interceptor,
+};
+use crate::layer::{BridgeLayer, ConnectLayer, into_grpc_response};
+use crate::message::StreamWriteConfig;
+use crate::message::error::{Code, ConnectError};
+use crate::reflection::ReflectionService;
+use crate::shutdown::Shutdown;
 
 #[cfg(feature = "tonic")]
 use crate::tonic::ContentTypeSwitch;
@@ -92,7 +108,20 @@ struct BuilderConfig {
     limits: Option<MessageLimits>,
     require_protocol_header: bool,
     compression: Option<CompressionConfig>,
+    codecs: Vec<CompressionEncoding>,
     timeout: Option<Duration>,
+    default_timeout: Option<Duration>,
+    max_timeout: Option<Duration>,
+    stream_message_timeout: Option<Duration>,
+    stream_idle_timeout: Option<Duration>,
+    stream_write: StreamWriteConfig,
+    catch_panic: bool,
+    debug_errors: bool,
+    error_mapper: Option<ErrorMapper>,
+    shutdown: Option<Shutdown>,
+    interceptor: Option<SharedInterceptor>,
+    connect_fallback: bool,
+    dynamic_services: Option<DynamicServiceRegistry>,
 }
 
 /// Built layers ready for router construction.
@@ -120,6 +149,8 @@ struct BuiltLayers {
 #[cfg(feature = "tonic")]
 pub struct WithGrpc {
     routes: tonic::service::Routes,
+    /// gRPC services nested under a path prefix, one entry per prefix.
+    mounted_routes: Vec<(String, tonic::service::Routes)>,
     /// Whether to capture HTTP request parts for `FromRequestParts` extractors.
     /// Enabled by default.
     capture_request_parts: bool,
@@ -173,6 +204,7 @@ pub struct MakeServiceBuilder<S = (), G = ConnectOnly> {
     connect_router: Router<S>,
     axum_router: Option<Router<S>>,
     raw_axum_router: Option<Router<S>>,
+    mounted_routers: Vec<(String, Router<S>)>,
     #[cfg(feature = "tonic")]
     grpc_state: G,
     #[cfg(not(feature = "tonic"))]
@@ -207,6 +239,7 @@ where
             connect_router: Router::new(),
             axum_router: None,
             raw_axum_router: None,
+            mounted_routers: Vec::new(),
             #[cfg(feature = "tonic")]
             grpc_state: ConnectOnly,
             #[cfg(not(feature = "tonic"))]
@@ -337,6 +370,27 @@ where
         self
     }
 
+    /// Register a custom compression codec (e.g. lz4 or snappy).
+    ///
+    /// See [`ConnectLayer::codec`]. Responses are only compressed when
+    /// [`compression`](Self::compression) is configured.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::{MakeServiceBuilder, CompressionConfig};
+    ///
+    /// let app = MakeServiceBuilder::new()
+    ///     .compression(CompressionConfig::new(512))
+    ///     .codec(&Lz4Codec)
+    ///     .add_router(router)
+    ///     .build();
+    /// ```
+    pub fn codec(mut self, codec: &'static dyn Codec) -> Self {
+        self.config.codecs.push(CompressionEncoding::custom(codec));
+        self
+    }
+
     /// Set the server-side maximum timeout.
     ///
     /// When set, the effective timeout for each request is the minimum of:
@@ -362,6 +416,241 @@ where
         self
     }
 
+    /// Set the timeout for requests without a client timeout.
+    ///
+    /// A client's `Connect-Timeout-Ms` or `grpc-timeout` replaces it, even
+    /// when longer. See [`ConnectLayer::default_timeout`].
+    pub fn default_timeout(mut self, timeout: Duration) -> Self {
+        self.config.default_timeout = Some(timeout);
+        self
+    }
+
+    /// Clamp client-provided timeouts to at most `timeout`.
+    ///
+    /// See [`ConnectLayer::max_timeout`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let app = MakeServiceBuilder::new()
+    ///     .default_timeout(Duration::from_secs(10))
+    ///     .max_timeout(Duration::from_secs(60))
+    ///     .add_router(router)
+    ///     .build();
+    /// ```
+    pub fn max_timeout(mut self, timeout: Duration) -> Self {
+        self.config.max_timeout = Some(timeout);
+        self
+    }
+
+    /// Set a per-message deadline for client-streaming and bidi request streams.
+    ///
+    /// If the client stops sending without closing the stream, the handler's
+    /// `Streaming` yields a `deadline_exceeded` error once no message has
+    /// arrived for `timeout`. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use std::time::Duration;
+    /// use connectrpc_axum::MakeServiceBuilder;
+    ///
+    /// let app = MakeServiceBuilder::new()
+    ///     .stream_message_timeout(Duration::from_secs(10))
+    ///     .add_router(router)
+    ///     .build();
+    /// ```
+    pub fn stream_message_timeout(mut self, timeout: Duration) -> Self {
+        self.config.stream_message_timeout = Some(timeout);
+        self
+    }
+
+    /// End streaming RPCs that neither send nor receive a message for `timeout`.
+    ///
+    /// See [`ConnectLayer::stream_idle_timeout`]. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let app = MakeServiceBuilder::new()
+    ///     .stream_idle_timeout(Duration::from_secs(300))
+    ///     .add_router(router)
+    ///     .build();
+    /// ```
+    pub fn stream_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.config.stream_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set how streaming response frames are flushed.
+    ///
+    /// See [`ConnectLayer::stream_write`]. Every message is flushed
+    /// immediately by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::{MakeServiceBuilder, StreamWriteConfig};
+    ///
+    /// let app = MakeServiceBuilder::new()
+    ///     .stream_write(StreamWriteConfig::batched().max_buffered_bytes(64 * 1024))
+    ///     .add_router(router)
+    ///     .build();
+    /// ```
+    pub fn stream_write(mut self, config: StreamWriteConfig) -> Self {
+        self.config.stream_write = config;
+        self
+    }
+
+    /// Convert handler panics into Connect `internal` errors.
+    ///
+    /// See [`ConnectLayer::catch_panic`]. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::MakeServiceBuilder;
+    ///
+    /// let app = MakeServiceBuilder::new()
+    ///     .catch_panic(true)
+    ///     .add_router(router)
+    ///     .build();
+    /// ```
+    pub fn catch_panic(mut self, enable: bool) -> Self {
+        self.config.catch_panic = enable;
+        self
+    }
+
+    /// Attach `google.rpc.DebugInfo` details to non-Connect failures.
+    ///
+    /// See [`ConnectLayer::debug_errors`]. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::MakeServiceBuilder;
+    ///
+    /// let app = MakeServiceBuilder::new()
+    ///     .catch_panic(true)
+    ///     .debug_errors(std::env::var("APP_ENV").as_deref() == Ok("staging"))
+    ///     .add_router(router)
+    ///     .build();
+    /// ```
+    pub fn debug_errors(mut self, enable: bool) -> Self {
+        self.config.debug_errors = enable;
+        self
+    }
+
+    /// Convert application errors returned by handlers with `mapper`.
+    ///
+    /// See [`ConnectLayer::error_mapper`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::{ConnectError, ErrorMapper, MakeServiceBuilder};
+    ///
+    /// let app = MakeServiceBuilder::new()
+    ///     .error_mapper(ErrorMapper::new().map(|_: &sqlx::Error| {
+    ///         ConnectError::new_unavailable("database error")
+    ///     }))
+    ///     .add_router(router)
+    ///     .build();
+    /// ```
+    pub fn error_mapper(mut self, mapper: ErrorMapper) -> Self {
+        self.config.error_mapper = Some(mapper);
+        self
+    }
+
+    /// Drain Connect RPCs gracefully with the given [`Shutdown`] coordinator.
+    ///
+    /// See [`ConnectLayer::shutdown`]. Tonic services added with
+    /// `add_grpc_service` are not covered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::{MakeServiceBuilder, Shutdown};
+    ///
+    /// let shutdown = Shutdown::new().grace_period(Duration::from_secs(10));
+    /// let app = MakeServiceBuilder::new()
+    ///     .shutdown(shutdown.clone())
+    ///     .add_router(router)
+    ///     .build();
+    ///
+    /// axum::serve(listener, app)
+    ///     .with_graceful_shutdown(async move {
+    ///         tokio::signal::ctrl_c().await.ok();
+    ///         shutdown.drain().await;
+    ///     })
+    ///     .await?;
+    /// ```
+    pub fn shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.config.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Answer unknown procedures and wrong HTTP methods with Connect errors.
+    ///
+    /// When enabled, a request matching no route gets a 404, and a Connect
+    /// route called with the wrong method a 405, both carrying an
+    /// `unimplemented` error encoded for the request's protocol and an
+    /// `Accept-Post` header.
+    ///
+    /// Disabled by default, leaving axum's plain responses. The 404 handler
+    /// becomes the fallback of the whole router, so it replaces any fallback
+    /// set on a router passed to [`add_axum_router`](Self::add_axum_router);
+    /// leave this off if you have one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::MakeServiceBuilder;
+    ///
+    /// let app = MakeServiceBuilder::new()
+    ///     .connect_fallback(true)
+    ///     .add_router(router)
+    ///     .build();
+    /// ```
+    pub fn connect_fallback(mut self, enable: bool) -> Self {
+        self.config.connect_fallback = enable;
+        self
+    }
+
+    /// Add a header-level interceptor to every Connect route.
+    ///
+    /// Interceptors run in the order they are added for requests, and in
+    /// reverse order for responses. Raw axum routers are not intercepted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::MakeServiceBuilder;
+    ///
+    /// let app = MakeServiceBuilder::new()
+    ///     .with_interceptor(AuthInterceptor)
+    ///     .add_router(router)
+    ///     .build();
+    /// ```
+    pub fn with_interceptor<I: Interceptor>(mut self, interceptor: I) -> Self {
+        self.config.interceptor = Some(push_interceptor(
+            self.config.interceptor.take(),
+            HeaderWrapper(interceptor),
+        ));
+        self
+    }
+
+    /// Add a message-level interceptor to every Connect route.
+    ///
+    /// See [`with_interceptor`](Self::with_interceptor) for ordering.
+    pub fn with_message_interceptor<I: MessageInterceptor>(mut self, interceptor: I) -> Self {
+        self.config.interceptor = Some(push_interceptor(
+            self.config.interceptor.take(),
+            MessageWrapper(interceptor),
+        ));
+        self
+    }
+
     /// Adds a single Connect RPC router to the builder.
     ///
     /// The router will be merged with any previously added routers using
@@ -382,6 +671,75 @@ where
         self
     }
 
+    /// Adds the `grpc.health.v1.Health` service's Connect routes.
+    ///
+    /// For gRPC health checks (e.g. Kubernetes gRPC probes), also pass
+    /// `health.grpc_service()` to `add_grpc_service` (requires the `tonic` feature).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use connectrpc_axum::MakeServiceBuilder;
+    /// use connectrpc_axum::health::HealthService;
+    ///
+    /// let health = HealthService::new();
+    /// health.reporter().set_serving("hello.HelloWorldService");
+    ///
+    /// let builder: MakeServiceBuilder = MakeServiceBuilder::new()
+    ///     .add_health_service(&health);
+    /// ```
+    pub fn add_health_service(self, health: &HealthService) -> Self {
+        self.add_router(health.router())
+    }
+
+    /// Adds the Connect routes of a [`ReflectionService`]
+    /// (`grpc.reflection.v1` and `v1alpha`).
+    ///
+    /// For gRPC clients such as `grpcurl`, also pass `reflection.grpc_service()`
+    /// and `reflection.grpc_service_v1alpha()` to `add_grpc_service`
+    /// (requires the `tonic` feature).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::MakeServiceBuilder;
+    /// use connectrpc_axum::reflection::ReflectionService;
+    ///
+    /// let reflection = ReflectionService::new()
+    ///     .register_file_descriptor_set(FILE_DESCRIPTOR_SET)?;
+    ///
+    /// let builder = MakeServiceBuilder::new()
+    ///     .add_reflection_service(&reflection);
+    /// ```
+    pub fn add_reflection_service(self, reflection: &ReflectionService) -> Self {
+        self.add_router(reflection.router())
+    }
+
+    /// Mounts a [`DynamicServiceRegistry`] as a catch-all Connect route.
+    ///
+    /// Requests that match no other route are dispatched to the handler
+    /// registered for their path, with the same layers as routes added via
+    /// [`add_router`](Self::add_router). Handlers registered after
+    /// [`build`](Self::build) are picked up on the next request.
+    ///
+    /// The catch-all route takes the place of fallbacks set on routers passed
+    /// to [`add_axum_router`](Self::add_axum_router). Calling this again
+    /// replaces the previous registry.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use connectrpc_axum::{DynamicServiceRegistry, MakeServiceBuilder};
+    ///
+    /// let registry = DynamicServiceRegistry::new();
+    /// let builder: MakeServiceBuilder = MakeServiceBuilder::new()
+    ///     .add_dynamic_services(&registry);
+    /// ```
+    pub fn add_dynamic_services(mut self, registry: &DynamicServiceRegistry) -> Self {
+        self.config.dynamic_services = Some(registry.clone());
+        self
+    }
+
     /// Adds multiple Connect RPC routers to the builder.
     ///
     /// All routers will be merged together using [`Router::merge`].
@@ -405,6 +763,41 @@ where
         self
     }
 
+    /// Adds a Connect RPC router nested under a path prefix.
+    ///
+    /// Use this for deployments behind path-based gateways, where procedures
+    /// are served at e.g. `/api/hello.HelloWorldService/SayHello`. The router
+    /// gets the same layers as routers added with [`add_router`](Self::add_router);
+    /// the prefix is stripped before they run, so interceptors, logs and metrics
+    /// see the plain procedure path.
+    ///
+    /// Routers mounted under the same prefix are merged. An empty or `/`
+    /// prefix is the same as [`add_router`](Self::add_router).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use connectrpc_axum::MakeServiceBuilder;
+    /// # use axum::Router;
+    /// # let hello_router: Router<()> = Router::new();
+    /// # let user_router: Router<()> = Router::new();
+    ///
+    /// let app = MakeServiceBuilder::new()
+    ///     .mount("/api", hello_router)
+    ///     .mount("/api", user_router)
+    ///     .build();
+    /// ```
+    pub fn mount(mut self, prefix: &str, router: Router<S>) -> Self {
+        let Some(prefix) = normalize_prefix(prefix) else {
+            return self.add_router(router);
+        };
+        match self.mounted_routers.iter_mut().find(|(p, _)| *p == prefix) {
+            Some((_, existing)) => *existing = std::mem::take(existing).merge(router),
+            None => self.mounted_routers.push((prefix, router)),
+        }
+        self
+    }
+
     /// Adds an axum router that bypasses [`ConnectLayer`].
     ///
     /// Use this for routes that don't need Connect protocol handling:
@@ -538,12 +931,47 @@ where
         let mut layer = ConnectLayer::new()
             .limits(limits)
             .require_protocol_header(self.config.require_protocol_header)
-            .compression(compression);
+            .compression(compression)
+            .stream_write(self.config.stream_write)
+            .catch_panic(self.config.catch_panic)
+            .debug_errors(self.config.debug_errors);
+
+        if let Some(mapper) = &self.config.error_mapper {
+            layer = layer.error_mapper(mapper.clone());
+        }
 
         if let Some(timeout) = self.config.timeout {
             layer = layer.timeout(timeout);
         }
 
+        if let Some(timeout) = self.config.default_timeout {
+            layer = layer.default_timeout(timeout);
+        }
+
+        if let Some(timeout) = self.config.max_timeout {
+            layer = layer.max_timeout(timeout);
+        }
+
+        if let Some(timeout) = self.config.stream_message_timeout {
+            layer = layer.stream_message_timeout(timeout);
+        }
+
+        if let Some(timeout) = self.config.stream_idle_timeout {
+            layer = layer.stream_idle_timeout(timeout);
+        }
+
+        if let Some(shutdown) = &self.config.shutdown {
+            layer = layer.shutdown(shutdown.clone());
+        }
+
+        if let Some(interceptor) = &self.config.interceptor {
+            layer = layer.with_shared_interceptor(interceptor.clone());
+        }
+
+        for encoding in &self.config.codecs {
+            layer = layer.custom_encoding(*encoding);
+        }
+
         layer
     }
 
@@ -598,7 +1026,8 @@ where
         #[cfg(feature = "compression-zstd-unary")]
         let layer = layer.zstd(true);
 
-        layer
+        // Let bodies in custom encodings through to ConnectLayer
+        layer.pass_through_unaccepted(!self.config.codecs.is_empty())
     }
 
     /// Builds the layers needed for router construction.
@@ -685,7 +1114,10 @@ where
             self.connect_router,
             self.axum_router,
             self.raw_axum_router,
+            self.config.dynamic_services,
+            self.mounted_routers,
             layers,
+            self.config.connect_fallback,
         )
     }
 }
@@ -698,14 +1130,55 @@ fn build_connect_and_axum_router<S>(
     connect_router: Router<S>,
     axum_router: Option<Router<S>>,
     raw_axum_router: Option<Router<S>>,
+    dynamic_services: Option<DynamicServiceRegistry>,
+    mounted_routers: Vec<(String, Router<S>)>,
     layers: BuiltLayers,
+    fallback: bool,
 ) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    // The catch-all goes into the Connect router so registered handlers get
+    // the same layers; matchit prefers the static routes merged alongside it.
+    let connect_router = match dynamic_services {
+        Some(registry) => connect_router.merge(registry.router(fallback)),
+        None => connect_router,
+    };
+
+    let mut router = apply_connect_layers(connect_router, &layers, fallback);
+
+    // Mounted routers get their own layer stack inside the nest, so the
+    // layers see the procedure path with the prefix stripped.
+    for (prefix, mounted) in mounted_routers {
+        router = router.nest(&prefix, apply_connect_layers(mounted, &layers, fallback));
+    }
+
+    // Build and merge axum router if present
+    if let Some(axum_router) = axum_router {
+        let axum_router = apply_axum_layers(axum_router, &layers);
+        router = router.merge(axum_router);
+    }
+
+    // Merge raw axum router (no shared layers)
+    if let Some(raw) = raw_axum_router {
+        router = router.merge(raw);
+    }
+
+    if fallback {
+        router = router.fallback(unknown_procedure);
+    }
+
+    router
+}
+
+/// Applies the Connect layer stack shared by all Connect routers.
+fn apply_connect_layers<S>(router: Router<S>, layers: &BuiltLayers, fallback: bool) -> Router<S>
 where
     S: Clone + Send + Sync + 'static,
 {
     let bridge_layer = BridgeLayer::with_limits(layers.limits.unwrap_or_default());
 
-    let mut router = connect_router.layer(layers.connect_layer);
+    let mut router = router.layer(layers.connect_layer.clone());
 
     // Apply compression layers if enabled
     #[cfg(any(
@@ -721,18 +1194,57 @@ where
     // Always apply bridge layer for Connect protocol
     router = router.layer(bridge_layer);
 
-    // Build and merge axum router if present
-    if let Some(axum_router) = axum_router {
-        let axum_router = apply_axum_layers(axum_router, &layers);
-        router = router.merge(axum_router);
+    if fallback {
+        router = router.method_not_allowed_fallback(method_not_allowed);
     }
 
-    // Merge raw axum router (no shared layers)
-    if let Some(raw) = raw_axum_router {
-        router = router.merge(raw);
+    router
+}
+
+/// Normalizes a mount prefix to `/segment[/segment...]` without a trailing slash.
+///
+/// Returns `None` for the root prefix.
+fn normalize_prefix(prefix: &str) -> Option<String> {
+    let trimmed = prefix.trim_matches('/');
+    if trimmed.is_empty() {
+        return None;
     }
+    Some(format!("/{trimmed}"))
+}
 
-    router
+/// Fallback for requests that match no route.
+pub(crate) async fn unknown_procedure(req: Request) -> Response {
+    let message = format!("{} is not implemented", req.uri().path());
+    unimplemented_response(&req, StatusCode::NOT_FOUND, message)
+}
+
+/// Fallback for Connect routes called with an HTTP method they don't serve.
+///
+/// axum adds the `Allow` header listing the methods the route does serve.
+pub(crate) async fn method_not_allowed(req: Request) -> Response {
+    let message = format!("{} does not support {}", req.uri().path(), req.method());
+    unimplemented_response(&req, StatusCode::METHOD_NOT_ALLOWED, message)
+}
+
+/// An `unimplemented` error encoded for the request's protocol.
+///
+/// gRPC reports it in a trailers-only response; other protocols keep the
+/// HTTP status of the routing failure.
+fn unimplemented_response(req: &Request, status: StatusCode, message: String) -> Response {
+    let protocol = detect_protocol(req);
+    let response =
+        ConnectError::new(Code::Unimplemented, message).into_response_with_protocol(protocol);
+    if protocol.is_grpc() {
+        return into_grpc_response(response, protocol);
+    }
+
+    let (mut parts, body) = response.into_parts();
+    parts.status = status;
+    parts.headers.insert(
+        "accept-post",
+        HeaderValue::from_static(SUPPORTED_CONTENT_TYPES),
+    );
+    Response::from_parts(parts, body)
 }
 
 fn apply_axum_layers<S>(mut router: Router<S>, layers: &BuiltLayers) -> Router<S>
@@ -858,13 +1370,61 @@ where
             connect_router: self.connect_router,
             axum_router: self.axum_router,
             raw_axum_router: self.raw_axum_router,
+            mounted_routers: self.mounted_routers,
             grpc_state: WithGrpc {
                 routes,
+                mounted_routes: Vec::new(),
                 capture_request_parts: true,
             },
             config: self.config,
         }
     }
+
+    /// Adds the first gRPC service, nested under a path prefix.
+    ///
+    /// The gRPC counterpart of [`mount`](Self::mount): the service answers
+    /// `{prefix}/{NamedService::NAME}/*` and sees the path with the prefix
+    /// stripped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use connectrpc_axum::MakeServiceBuilder;
+    ///
+    /// let (hello_router, hello_grpc) = HelloWorldServiceBuilder::new()
+    ///     .say_hello(handler)
+    ///     .build();
+    ///
+    /// let app = MakeServiceBuilder::new()
+    ///     .mount("/api", hello_router)
+    ///     .mount_grpc_service("/api", hello_grpc)
+    ///     .build();
+    /// ```
+    pub fn mount_grpc_service<G>(self, prefix: &str, service: G) -> MakeServiceBuilder<S, WithGrpc>
+    where
+        G: tower::Service<http::Request<tonic::body::Body>, Error = std::convert::Infallible>
+            + tonic::server::NamedService
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        G::Response: axum::response::IntoResponse,
+        G::Future: Send + 'static,
+    {
+        MakeServiceBuilder {
+            connect_router: self.connect_router,
+            axum_router: self.axum_router,
+            raw_axum_router: self.raw_axum_router,
+            mounted_routers: self.mounted_routers,
+            grpc_state: WithGrpc {
+                routes: tonic::service::Routes::default(),
+                mounted_routes: Vec::new(),
+                capture_request_parts: true,
+            },
+            config: self.config,
+        }
+        .mount_grpc_service(prefix, service)
+    }
 }
 
 #[cfg(feature = "tonic")]
@@ -937,6 +1497,35 @@ where
         self
     }
 
+    /// Adds a gRPC service nested under a path prefix.
+    ///
+    /// See [`MakeServiceBuilder::mount`]. Services mounted under the same
+    /// prefix share one nested route table.
+    pub fn mount_grpc_service<G>(mut self, prefix: &str, service: G) -> Self
+    where
+        G: tower::Service<http::Request<tonic::body::Body>, Error = std::convert::Infallible>
+            + tonic::server::NamedService
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        G::Response: axum::response::IntoResponse,
+        G::Future: Send + 'static,
+    {
+        let Some(prefix) = normalize_prefix(prefix) else {
+            return self.add_grpc_service(service);
+        };
+        let mounted = &mut self.grpc_state.mounted_routes;
+        match mounted.iter_mut().find(|(p, _)| *p == prefix) {
+            Some((_, routes)) => *routes = std::mem::take(routes).add_service(service),
+            None => mounted.push((
+                prefix,
+                tonic::service::Routes::default().add_service(service),
+            )),
+        }
+        self
+    }
+
     /// Disable `FromRequestParts` extractor support for gRPC services.
     ///
     /// By default, `FromRequestPartsLayer` is applied to capture HTTP request parts
@@ -1011,11 +1600,24 @@ where
             self.connect_router,
             self.axum_router,
             self.raw_axum_router,
+            self.config.dynamic_services,
+            self.mounted_routers,
             layers,
+            self.config.connect_fallback,
         );
 
+        // Nest prefixed gRPC services; tonic routes by the stripped path.
+        let mut grpc_routes = self.grpc_state.routes;
+        if !self.grpc_state.mounted_routes.is_empty() {
+            let mut grpc_router = grpc_routes.into_axum_router();
+            for (prefix, routes) in self.grpc_state.mounted_routes {
+                grpc_router = grpc_router.nest(&prefix, routes.into_axum_router());
+            }
+            grpc_routes = tonic::service::Routes::from(grpc_router);
+        }
+
         // Build gRPC service with optional FromRequestParts layer
-        let grpc_routes = self.grpc_state.routes.prepare();
+        let grpc_routes = grpc_routes.prepare();
         let grpc_service = if self.grpc_state.capture_request_parts {
             Either::Left(
                 ServiceBuilder::new()
@@ -1033,7 +1635,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use axum::routing::get;
+    use axum::body::Body;
+    use axum::routing::{get, post};
+    use http::header::{ALLOW, CONTENT_TYPE};
+    use tower::ServiceExt;
 
     #[test]
     fn test_single_router() {
@@ -1177,4 +1782,159 @@ mod tests {
 
         assert!(format!("{:?}", app).contains("Router"));
     }
+
+    async fn json_body(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unknown_procedure_fallback() {
+        let connect_router: Router<()> =
+            Router::new().route("/svc.Hello/Say", post(|| async { "hi" }));
+        let app = MakeServiceBuilder::new()
+            .connect_fallback(true)
+            .add_router(connect_router)
+            .build();
+
+        let request = Request::post("/svc.Hello/Missing")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(response.headers()["accept-post"], SUPPORTED_CONTENT_TYPES);
+        let body = json_body(response).await;
+        assert_eq!(body["code"], "unimplemented");
+        assert_eq!(body["message"], "/svc.Hello/Missing is not implemented");
+
+        let request = Request::post("/svc.Hello/Missing")
+            .header(CONTENT_TYPE, "application/grpc")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["grpc-status"], "12");
+    }
+
+    #[tokio::test]
+    async fn test_method_not_allowed_fallback() {
+        let connect_router: Router<()> =
+            Router::new().route("/svc.Hello/Say", post(|| async { "hi" }));
+        let app = MakeServiceBuilder::new()
+            .connect_fallback(true)
+            .add_router(connect_router)
+            .build();
+
+        let request = Request::get("/svc.Hello/Say?encoding=proto")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(response.headers()[ALLOW], "POST");
+        assert_eq!(json_body(response).await["code"], "unimplemented");
+    }
+
+    #[tokio::test]
+    async fn test_fallback_disabled_by_default() {
+        let connect_router: Router<()> =
+            Router::new().route("/svc.Hello/Say", post(|| async { "hi" }));
+        let axum_router: Router<()> = Router::new().fallback(|| async { "custom" });
+        let app = MakeServiceBuilder::new()
+            .add_router(connect_router)
+            .add_axum_router(axum_router)
+            .build();
+
+        // The user's fallback is kept
+        let request = Request::post("/svc.Hello/Missing")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap(),
+            "custom"
+        );
+
+        // Nor do wrong methods get the Connect error
+        let request = Request::get("/svc.Hello/Say?encoding=proto")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert!(response.headers().get("accept-post").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mount_under_prefix() {
+        use crate::message::{ConnectRequest, ConnectResponse};
+
+        let say = |ConnectRequest(req): ConnectRequest<String>| async move {
+            Ok::<_, ConnectError>(ConnectResponse::new(req))
+        };
+        let hello_router: Router<()> =
+            Router::new().route("/svc.Hello/Say", crate::handler::post_connect(say));
+        let user_router: Router<()> =
+            Router::new().route("/svc.User/Get", post(|| async { "user" }));
+        let app = MakeServiceBuilder::new()
+            .connect_fallback(true)
+            .mount("/api/", hello_router)
+            .mount("api", user_router)
+            .build();
+
+        let request = |path: &str| {
+            Request::post(path)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from("\"hi\""))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request("/api/svc.Hello/Say"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(bytes, "\"hi\"");
+
+        let response = app
+            .clone()
+            .oneshot(request("/api/svc.User/Get"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(request("/svc.Hello/Say"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(json_body(response).await["code"], "unimplemented");
+
+        let response = app
+            .oneshot(
+                Request::get("/api/svc.Hello/Say?encoding=json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[test]
+    fn test_normalize_prefix() {
+        assert_eq!(normalize_prefix("/api"), Some("/api".to_string()));
+        assert_eq!(normalize_prefix("api/v1/"), Some("/api/v1".to_string()));
+        assert_eq!(normalize_prefix("/"), None);
+        assert_eq!(normalize_prefix(""), None);
+    }
 }
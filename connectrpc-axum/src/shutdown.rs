@@ -0,0 +1,322 @@
+//! Graceful shutdown with stream draining.
+//!
+//! A [`Shutdown`] coordinator given to [`ConnectLayer::shutdown`](crate::ConnectLayer::shutdown)
+//! or [`MakeServiceBuilder::shutdown`](crate::MakeServiceBuilder::shutdown)
+//! tracks in-flight RPCs. Once [`drain`](Shutdown::drain) is called:
+//!
+//! 1. New RPCs are refused with `unavailable` and a `google.rpc.RetryInfo`
+//!    detail, so clients retry elsewhere.
+//! 2. In-flight RPCs, unary and streaming, get the grace period to finish.
+//! 3. Response streams still open after the grace period are ended with an
+//!    `unavailable` EndStream frame (trailers for gRPC). Unary calls are left
+//!    to finish.
+//!
+//! `drain` returns when the last RPC is done, so it fits axum's graceful
+//! shutdown signal, which then stops accepting connections:
+//!
+//! ```rust,ignore
+//! use connectrpc_axum::{MakeServiceBuilder, Shutdown};
+//!
+//! let shutdown = Shutdown::new().grace_period(Duration::from_secs(10));
+//! let app = MakeServiceBuilder::new()
+//!     .shutdown(shutdown.clone())
+//!     .add_router(router)
+//!     .build();
+//!
+//! axum::serve(listener, app)
+//!     .with_graceful_shutdown(async move {
+//!         tokio::signal::ctrl_c().await.ok();
+//!         shutdown.drain().await;
+//!     })
+//!     .await?;
+//! ```
+
+use crate::message::error::{Code, ConnectError};
+use futures::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
+
+/// Default time in-flight RPCs get to finish.
+const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Default retry delay suggested to refused clients.
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Coordinates graceful shutdown of the RPCs served by a `ConnectLayer`.
+///
+/// Clones share their state: keep one to call [`drain`](Self::drain) and give
+/// the others to the layers. Configure the coordinator before cloning it.
+#[derive(Clone, Debug)]
+pub struct Shutdown {
+    grace_period: Duration,
+    retry_delay: Duration,
+    state: Arc<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    /// Cancelled when draining starts.
+    draining: CancellationToken,
+    /// Cancelled when the grace period is over.
+    closed: CancellationToken,
+    /// Number of in-flight RPCs.
+    in_flight: watch::Sender<usize>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    /// Create a coordinator with a 30 second grace period.
+    pub fn new() -> Self {
+        Self {
+            grace_period: DEFAULT_GRACE_PERIOD,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            state: Arc::new(State {
+                draining: CancellationToken::new(),
+                closed: CancellationToken::new(),
+                in_flight: watch::Sender::new(0),
+            }),
+        }
+    }
+
+    /// Set how long in-flight RPCs get to finish before open response
+    /// streams are ended.
+    pub fn grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Set the retry delay sent to clients refused while draining.
+    ///
+    /// Defaults to one second.
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Stop accepting new RPCs without waiting for in-flight ones.
+    ///
+    /// [`drain`](Self::drain) calls this first.
+    pub fn trigger(&self) {
+        self.state.draining.cancel();
+    }
+
+    /// Whether new RPCs are being refused.
+    pub fn is_draining(&self) -> bool {
+        self.state.draining.is_cancelled()
+    }
+
+    /// Number of RPCs currently in flight.
+    pub fn in_flight(&self) -> usize {
+        *self.state.in_flight.borrow()
+    }
+
+    /// Stop accepting new RPCs and wait for in-flight ones to finish.
+    ///
+    /// Response streams still open after the grace period are ended with an
+    /// `unavailable` error; the future then waits for them (and any unary
+    /// call still running) to complete.
+    pub async fn drain(&self) {
+        self.trigger();
+        let mut in_flight = self.state.in_flight.subscribe();
+        let idle = in_flight.wait_for(|count| *count == 0);
+        if tokio::time::timeout(self.grace_period, idle).await.is_err() {
+            tracing::info!(
+                in_flight = self.in_flight(),
+                "shutdown grace period over, closing open streams"
+            );
+            self.state.closed.cancel();
+            let _ = in_flight.wait_for(|count| *count == 0).await;
+        }
+    }
+
+    /// Count a new RPC, or refuse it if draining.
+    pub(crate) fn start(&self) -> Result<InFlight, ConnectError> {
+        // Count first so `drain` can't miss an RPC admitted while it starts
+        self.state.in_flight.send_modify(|count| *count += 1);
+        let guard = InFlight(self.state.clone());
+        if self.is_draining() {
+            return Err(self.error());
+        }
+        Ok(guard)
+    }
+
+    /// End `stream` with an `unavailable` error once the grace period is over.
+    pub(crate) fn close_stream<S>(&self, stream: S) -> UntilClosed<S> {
+        UntilClosed {
+            stream,
+            closed: Box::pin(self.state.closed.clone().cancelled_owned()),
+            error: Some(self.error()),
+        }
+    }
+
+    fn error(&self) -> ConnectError {
+        ConnectError::new(Code::Unavailable, "server is shutting down")
+            .with_retry_info(self.retry_delay)
+    }
+}
+
+/// An in-flight RPC, counted until dropped.
+pub(crate) struct InFlight(Arc<State>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.in_flight.send_modify(|count| *count -= 1);
+    }
+}
+
+/// Response stream cut short when the shutdown grace period is over.
+pub(crate) struct UntilClosed<S> {
+    stream: S,
+    closed: Pin<Box<WaitForCancellationFutureOwned>>,
+    /// The error to end with; taken once the stream is done.
+    error: Option<ConnectError>,
+}
+
+impl<S, T> Stream for UntilClosed<S>
+where
+    S: Stream<Item = Result<T, ConnectError>> + Unpin,
+{
+    type Item = Result<T, ConnectError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.error.is_none() {
+            return Poll::Ready(None);
+        }
+        if self.closed.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(self.error.take().map(Err));
+        }
+        let item = std::task::ready!(Pin::new(&mut self.stream).poll_next(cx));
+        if !matches!(item, Some(Ok(_))) {
+            self.error = None;
+        }
+        Poll::Ready(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::{ConnectRequest, ConnectResponse, StreamBody};
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use axum::response::Response;
+    use futures::StreamExt;
+    use tokio::sync::Notify;
+    use tower::ServiceExt;
+
+    /// A router whose `Block` method waits for `release` and whose `Watch`
+    /// method streams one message and then nothing.
+    fn app(shutdown: &Shutdown, release: Arc<Notify>) -> Router {
+        let block = move |ConnectRequest(req): ConnectRequest<String>| {
+            let release = release.clone();
+            async move {
+                release.notified().await;
+                Ok::<_, ConnectError>(ConnectResponse::new(req))
+            }
+        };
+        let watch = |ConnectRequest(req): ConnectRequest<String>| async move {
+            let stream = futures::stream::iter([Ok(req)]).chain(futures::stream::pending());
+            Ok::<_, ConnectError>(ConnectResponse::new(StreamBody::new(stream)))
+        };
+        Router::new()
+            .route("/test.Svc/Block", post_connect(block))
+            .route("/test.Svc/Watch", post_connect(watch))
+            .layer(ConnectLayer::new().shutdown(shutdown.clone()))
+    }
+
+    fn request(path: &str, content_type: &str) -> Request<Body> {
+        let body = if content_type == "application/json" {
+            Body::from("\"hi\"")
+        } else {
+            let mut frame = vec![0u8];
+            frame.extend_from_slice(&4u32.to_be_bytes());
+            frame.extend_from_slice(b"\"hi\"");
+            Body::from(frame)
+        };
+        Request::post(path)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(body)
+            .unwrap()
+    }
+
+    async fn body_bytes(response: Response) -> axum::body::Bytes {
+        axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_drain_refuses_new_rpcs_and_waits_for_unary() {
+        let shutdown = Shutdown::new();
+        let release = Arc::new(Notify::new());
+        let app = app(&shutdown, release.clone());
+
+        let blocked = tokio::spawn(
+            app.clone()
+                .oneshot(request("/test.Svc/Block", "application/json")),
+        );
+        tokio::task::yield_now().await;
+        assert_eq!(shutdown.in_flight(), 1);
+
+        let drain = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.drain().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(shutdown.is_draining());
+
+        let response = app
+            .oneshot(request("/test.Svc/Block", "application/json"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(body["code"], "unavailable");
+        assert_eq!(body["details"][0]["type"], "google.rpc.RetryInfo");
+        assert!(!drain.is_finished());
+
+        release.notify_one();
+        let response = blocked.await.unwrap().unwrap();
+        assert_eq!(body_bytes(response).await, "\"hi\"");
+        drain.await.unwrap();
+        assert_eq!(shutdown.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_drain_ends_open_streams_after_grace_period() {
+        let shutdown = Shutdown::new().grace_period(Duration::from_millis(20));
+        let app = app(&shutdown, Arc::new(Notify::new()));
+
+        let response = app
+            .oneshot(request("/test.Svc/Watch", "application/connect+json"))
+            .await
+            .unwrap();
+        let drain = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.drain().await }
+        });
+
+        let body = body_bytes(response).await;
+        assert_eq!(&body[5..9], b"\"hi\"");
+        assert_eq!(body[9], crate::message::request::envelope_flags::END_STREAM);
+        let end: serde_json::Value = serde_json::from_slice(&body[14..]).unwrap();
+        assert_eq!(end["error"]["code"], "unavailable");
+        assert_eq!(end["error"]["message"], "server is shutting down");
+
+        drain.await.unwrap();
+        assert_eq!(shutdown.in_flight(), 0);
+    }
+}
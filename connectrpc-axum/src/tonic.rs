@@ -5,15 +5,18 @@
 //!
 //! # Overview
 //!
-//! - [`ContentTypeSwitch`] - Routes requests to gRPC or Connect based on content-type
+//! - [`ContentTypeSwitch`] - Routes requests to gRPC, gRPC-Web or Connect based on content-type
 //! - [`TonicCompatibleHandlerWrapper`] - Handler wrapper with full extractor support
 //! - [`FromRequestPartsLayer`] - Middleware enabling `FromRequestParts` extractors
+//! - [`TonicInterceptor`] - Runs a `tonic::service::Interceptor` on Connect and gRPC calls
 //! - [`RequestContext`] - Full request context for extractor support
 
 mod handler;
+mod interceptor;
 mod parts;
 
 pub use handler::*;
+pub use interceptor::*;
 pub use parts::*;
 
 use std::{
@@ -34,10 +37,19 @@ use hyper::http::{Request, Response, StatusCode};
 /// Matches both `application/grpc*` and `application/grpc-web*` content types.
 /// For grpc-web support, wrap your Tonic service with `tonic_web::GrpcWebLayer`.
 fn is_grpc(req: &Request<AxumBody>) -> bool {
+    content_type_starts_with(req, "application/grpc")
+}
+
+/// Returns true if the request is a gRPC-Web call (`application/grpc-web*`).
+fn is_grpc_web(req: &Request<AxumBody>) -> bool {
+    content_type_starts_with(req, "application/grpc-web")
+}
+
+fn content_type_starts_with(req: &Request<AxumBody>, prefix: &str) -> bool {
     req.headers()
         .get(CONTENT_TYPE)
         .and_then(|v| v.to_str().ok())
-        .is_some_and(|s| s.starts_with("application/grpc"))
+        .is_some_and(|s| s.starts_with(prefix))
 }
 
 /// Map any `http_body::Body` into `axum::Body`, preserving trailers.
@@ -63,19 +75,67 @@ fn internal_error<E: std::fmt::Display>(err: E) -> AxumResponse {
 ///
 /// This allows serving both Tonic gRPC and Connect/Axum routes on the same port.
 /// Previously named `TonicCompatible`.
+///
+/// gRPC-Web requests go to the gRPC service unless a separate service is
+/// mounted for them with [`grpc_web`](Self::grpc_web):
+///
+/// ```rust,ignore
+/// // - application/grpc-web* -> tonic-web wrapped server
+/// // - application/grpc*     -> plain tonic server
+/// // - otherwise             -> Connect router
+/// let grpc_web = tower::ServiceBuilder::new()
+///     .layer(tonic_web::GrpcWebLayer::new())
+///     .service(grpc_server.clone());
+/// let service = ContentTypeSwitch::new(grpc_server, connect_router).grpc_web(grpc_web);
+/// ```
 #[derive(Clone, Debug)]
-pub struct ContentTypeSwitch<G, H> {
+pub struct ContentTypeSwitch<G, H, W = G> {
     grpc: G,
     http: H,
+    grpc_web: Option<W>,
 }
 
 impl<G, H> ContentTypeSwitch<G, H> {
     pub fn new(grpc: G, http: H) -> Self {
-        Self { grpc, http }
+        Self {
+            grpc,
+            http,
+            grpc_web: None,
+        }
+    }
+}
+
+impl<G, H, W> ContentTypeSwitch<G, H, W> {
+    /// Send `application/grpc-web*` requests to `grpc_web` instead of the gRPC service.
+    ///
+    /// `grpc_web` can be a tonic server wrapped in `tonic_web::GrpcWebLayer`, or a
+    /// Connect router, which serves gRPC-Web natively.
+    pub fn grpc_web<W2>(self, grpc_web: W2) -> ContentTypeSwitch<G, H, W2> {
+        ContentTypeSwitch {
+            grpc: self.grpc,
+            http: self.http,
+            grpc_web: Some(grpc_web),
+        }
+    }
+}
+
+/// Call `svc` and map its response body into `axum::Body`.
+async fn dispatch<S, B>(svc: S, req: Request<AxumBody>) -> Result<AxumResponse, Infallible>
+where
+    S: tower::Service<Request<AxumBody>, Response = Response<B>, Error = Infallible>,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: std::error::Error + Send + Sync + 'static,
+{
+    match tower::ServiceExt::oneshot(svc, req).await {
+        Ok(res) => {
+            let (parts, body) = res.into_parts();
+            Ok(Response::from_parts(parts, to_axum_body(body)))
+        }
+        Err(e) => Ok(internal_error(e)),
     }
 }
 
-impl<G, GB, H, HB> tower::Service<Request<AxumBody>> for ContentTypeSwitch<G, H>
+impl<G, GB, H, HB, W, WB> tower::Service<Request<AxumBody>> for ContentTypeSwitch<G, H, W>
 where
     // Tonic server
     G: tower::Service<Request<AxumBody>, Response = Response<GB>, Error = Infallible>
@@ -93,6 +153,14 @@ where
     HB: HttpBody<Data = Bytes> + Send + 'static,
     HB::Error: std::error::Error + Send + Sync + 'static,
     H::Future: Send + 'static,
+    // gRPC-Web service
+    W: tower::Service<Request<AxumBody>, Response = Response<WB>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    WB: HttpBody<Data = Bytes> + Send + 'static,
+    WB::Error: std::error::Error + Send + Sync + 'static,
+    W::Future: Send + 'static,
 {
     type Response = AxumResponse;
     type Error = Infallible;
@@ -104,31 +172,69 @@ where
     }
 
     fn call(&mut self, req: Request<AxumBody>) -> Self::Future {
-        let is_grpc_req = is_grpc(&req);
-        let grpc = self.grpc.clone();
-        let http = self.http.clone();
-
-        Box::pin(async move {
-            if is_grpc_req {
-                match tower::ServiceExt::oneshot(grpc, req).await {
-                    Ok(res) => {
-                        let (parts, body) = res.into_parts();
-                        Ok(Response::from_parts(parts, to_axum_body(body)))
-                    }
-                    Err(e) => Ok(internal_error(e)),
-                }
-            } else {
-                match tower::ServiceExt::oneshot(http, req).await {
-                    Ok(res) => {
-                        let (parts, body) = res.into_parts();
-                        Ok(Response::from_parts(parts, to_axum_body(body)))
-                    }
-                    Err(e) => Ok(internal_error(e)),
-                }
-            }
-        })
+        if let Some(grpc_web) = self.grpc_web.as_ref().filter(|_| is_grpc_web(&req)) {
+            return Box::pin(dispatch(grpc_web.clone(), req));
+        }
+        if is_grpc(&req) {
+            Box::pin(dispatch(self.grpc.clone(), req))
+        } else {
+            Box::pin(dispatch(self.http.clone(), req))
+        }
     }
 }
 
 /// Backwards name for the content-type dispatcher.
 pub type TonicCompatible<G, H> = ContentTypeSwitch<G, H>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::routing::post;
+
+    fn named(name: &'static str) -> Router {
+        Router::new().route("/svc/Method", post(move || async move { name }))
+    }
+
+    async fn route<S>(switch: S, content_type: &str) -> String
+    where
+        S: tower::Service<Request<AxumBody>, Response = AxumResponse, Error = Infallible>,
+    {
+        let req = Request::post("/svc/Method")
+            .header(CONTENT_TYPE, content_type)
+            .body(AxumBody::empty())
+            .unwrap();
+        let res = tower::ServiceExt::oneshot(switch, req).await.unwrap();
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_grpc_web_goes_to_grpc_by_default() {
+        let switch = ContentTypeSwitch::new(named("grpc"), named("connect"));
+        assert_eq!(route(switch.clone(), "application/grpc").await, "grpc");
+        assert_eq!(route(switch.clone(), "application/grpc-web").await, "grpc");
+        assert_eq!(route(switch, "application/json").await, "connect");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_web_arm() {
+        let switch =
+            ContentTypeSwitch::new(named("grpc"), named("connect")).grpc_web(named("grpc-web"));
+        assert_eq!(
+            route(switch.clone(), "application/grpc+proto").await,
+            "grpc"
+        );
+        assert_eq!(
+            route(switch.clone(), "application/grpc-web-text").await,
+            "grpc-web"
+        );
+        assert_eq!(
+            route(switch.clone(), "application/grpc-web+proto").await,
+            "grpc-web"
+        );
+        assert_eq!(route(switch, "application/proto").await, "connect");
+    }
+}
@@ -46,7 +46,7 @@ pub struct BidiStream;
 /// Unified tonic-style handler wrapper with axum extractor support.
 ///
 /// This wrapper accepts handlers following tonic-like patterns extended with
-/// axum's `FromRequestParts` extractors (1-8 extractors supported).
+/// axum's `FromRequestParts` extractors (1-16 extractors supported).
 /// The final argument must always be `ConnectRequest<Req>` (or `ConnectRequest<Streaming<Req>>`
 /// for client/bidi streaming).
 ///
@@ -261,9 +261,10 @@ where
                     let fut = f(req);
                     Box::pin(async move {
                         fut.await.map(|response| {
-                            let stream = response.into_inner().into_inner();
-                            let boxed_stream: BoxedStream<Resp> = Box::pin(stream);
-                            ConnectResponse::new(StreamBody::new(boxed_stream))
+                            response.map(|body| {
+                                let boxed_stream: BoxedStream<Resp> = Box::pin(body.into_inner());
+                                StreamBody::new(boxed_stream)
+                            })
                         })
                     })
                 },
@@ -325,9 +326,10 @@ where
                     let fut = f(req);
                     Box::pin(async move {
                         fut.await.map(|response| {
-                            let stream = response.into_inner().into_inner();
-                            let boxed_stream: BoxedStream<Resp> = Box::pin(stream);
-                            ConnectResponse::new(StreamBody::new(boxed_stream))
+                            response.map(|body| {
+                                let boxed_stream: BoxedStream<Resp> = Box::pin(body.into_inner());
+                                StreamBody::new(boxed_stream)
+                            })
                         })
                     })
                 },
@@ -338,7 +340,7 @@ where
 
 // =============== N-extractor implementations ===============
 
-// Macro for 1-8 extractors
+// Macro for 1-16 extractors
 macro_rules! all_extractor_tuples {
     ($m:ident) => {
         $m!([A1]);
@@ -349,6 +351,18 @@ macro_rules! all_extractor_tuples {
         $m!([A1, A2, A3, A4, A5, A6]);
         $m!([A1, A2, A3, A4, A5, A6, A7]);
         $m!([A1, A2, A3, A4, A5, A6, A7, A8]);
+        $m!([A1, A2, A3, A4, A5, A6, A7, A8, A9]);
+        $m!([A1, A2, A3, A4, A5, A6, A7, A8, A9, A10]);
+        $m!([A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11]);
+        $m!([A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12]);
+        $m!([A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13]);
+        $m!([A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14]);
+        $m!([
+            A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15
+        ]);
+        $m!([
+            A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16
+        ]);
     };
 }
 
@@ -432,9 +446,10 @@ macro_rules! impl_into_stream_factory_with_extractors {
                                     .map_err(Into::into)?;
                             )+
                             f($($A,)+ req).await.map(|response| {
-                                let stream = response.into_inner().into_inner();
-                                let boxed_stream: BoxedStream<Resp> = Box::pin(stream);
-                                ConnectResponse::new(StreamBody::new(boxed_stream))
+                                response.map(|body| {
+                                    let boxed_stream: BoxedStream<Resp> = Box::pin(body.into_inner());
+                                    StreamBody::new(boxed_stream)
+                                })
                             })
                         })
                     })
@@ -523,9 +538,10 @@ macro_rules! impl_into_bidi_stream_factory_with_extractors {
                                     .map_err(Into::into)?;
                             )+
                             f($($A,)+ req).await.map(|response| {
-                                let stream = response.into_inner().into_inner();
-                                let boxed_stream: BoxedStream<Resp> = Box::pin(stream);
-                                ConnectResponse::new(StreamBody::new(boxed_stream))
+                                response.map(|body| {
+                                    let boxed_stream: BoxedStream<Resp> = Box::pin(body.into_inner());
+                                    StreamBody::new(boxed_stream)
+                                })
                             })
                         })
                     })
@@ -535,7 +551,7 @@ macro_rules! impl_into_bidi_stream_factory_with_extractors {
     };
 }
 
-// Generate implementations for 1-8 extractors
+// Generate implementations for 1-16 extractors
 all_extractor_tuples!(impl_into_factory_with_extractors);
 all_extractor_tuples!(impl_into_stream_factory_with_extractors);
 all_extractor_tuples!(impl_into_client_stream_factory_with_extractors);
@@ -603,8 +619,13 @@ macro_rules! impl_handler_for_unary_with_extractors {
                     $(
                         #[allow(non_snake_case)]
                         let $A = match $A::from_request_parts(&mut parts, &state).await {
+                            Ok(v) => Ok(v),
+                            Err(e) => Err(handle_extractor_rejection(e, &ctx)),
+                        };
+                        #[allow(non_snake_case)]
+                        let $A = match $A {
                             Ok(v) => v,
-                            Err(e) => return handle_extractor_rejection(e, &ctx),
+                            Err(rejection) => return rejection.await,
                         };
                     )+
 
@@ -625,7 +646,7 @@ macro_rules! impl_handler_for_unary_with_extractors {
     };
 }
 
-// Generate implementations for 1-8 extractors
+// Generate implementations for 1-16 extractors
 all_extractor_tuples!(impl_handler_for_unary_with_extractors);
 
 // =============== Streaming Handler Implementations ===============
@@ -694,8 +715,13 @@ macro_rules! impl_handler_for_server_stream_with_extractors {
                     $(
                         #[allow(non_snake_case)]
                         let $A = match $A::from_request_parts(&mut parts, &state).await {
+                            Ok(v) => Ok(v),
+                            Err(e) => Err(handle_extractor_rejection(e, &ctx)),
+                        };
+                        #[allow(non_snake_case)]
+                        let $A = match $A {
                             Ok(v) => v,
-                            Err(e) => return handle_extractor_rejection(e, &ctx),
+                            Err(rejection) => return rejection.await,
                         };
                     )+
 
@@ -716,7 +742,7 @@ macro_rules! impl_handler_for_server_stream_with_extractors {
     };
 }
 
-// Generate implementations for 1-8 extractors
+// Generate implementations for 1-16 extractors
 all_extractor_tuples!(impl_handler_for_server_stream_with_extractors);
 
 // =============== Client Streaming Handler Implementations ===============
@@ -793,8 +819,13 @@ macro_rules! impl_handler_for_client_stream_with_extractors {
                     $(
                         #[allow(non_snake_case)]
                         let $A = match $A::from_request_parts(&mut parts, &state).await {
+                            Ok(v) => Ok(v),
+                            Err(e) => Err(handle_extractor_rejection(e, &ctx)),
+                        };
+                        #[allow(non_snake_case)]
+                        let $A = match $A {
                             Ok(v) => v,
-                            Err(e) => return handle_extractor_rejection(e, &ctx),
+                            Err(rejection) => return rejection.await,
                         };
                     )+
 
@@ -815,7 +846,7 @@ macro_rules! impl_handler_for_client_stream_with_extractors {
     };
 }
 
-// Generate implementations for 1-8 extractors
+// Generate implementations for 1-16 extractors
 all_extractor_tuples!(impl_handler_for_client_stream_with_extractors);
 
 // =============== Bidi Streaming Handler Implementations ===============
@@ -894,8 +925,13 @@ macro_rules! impl_handler_for_bidi_stream_with_extractors {
                     $(
                         #[allow(non_snake_case)]
                         let $A = match $A::from_request_parts(&mut parts, &state).await {
+                            Ok(v) => Ok(v),
+                            Err(e) => Err(handle_extractor_rejection(e, &ctx)),
+                        };
+                        #[allow(non_snake_case)]
+                        let $A = match $A {
                             Ok(v) => v,
-                            Err(e) => return handle_extractor_rejection(e, &ctx),
+                            Err(rejection) => return rejection.await,
                         };
                     )+
 
@@ -916,7 +952,7 @@ macro_rules! impl_handler_for_bidi_stream_with_extractors {
     };
 }
 
-// Generate implementations for 1-8 extractors
+// Generate implementations for 1-16 extractors
 all_extractor_tuples!(impl_handler_for_bidi_stream_with_extractors);
 
 // =============== Routing Functions ===============
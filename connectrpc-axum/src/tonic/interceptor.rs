@@ -0,0 +1,248 @@
+//! Tonic interceptor support for tonic-compatible services.
+//!
+//! A [`TonicInterceptor`] runs a [`tonic::service::Interceptor`] on both paths of a
+//! `TonicCompatibleBuilder`: gRPC calls served by the generated tonic service and
+//! Connect calls served by the axum router. On the Connect path the interceptor sees
+//! the HTTP headers as metadata and the request extensions, and whatever it inserts
+//! is handed back to the request, so handlers read it with `Extension<T>` just like
+//! they would on the gRPC path.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::http::Request;
+use axum::response::Response;
+use tonic::Status;
+use tonic::metadata::MetadataMap;
+use tonic::service::Interceptor;
+use tower::{Layer, Service, ServiceExt};
+
+use crate::context::ConnectContext;
+use crate::layer::rejection_response;
+use crate::message::error::ConnectError;
+
+type InterceptFn = dyn Fn(tonic::Request<()>) -> Result<tonic::Request<()>, Status> + Send + Sync;
+
+/// Type-erased [`tonic::service::Interceptor`] shared by the Connect and gRPC paths.
+///
+/// Used as a tower [`Layer`] on the Connect router, and called directly by the
+/// generated tonic service for gRPC requests. The interceptor is cloned for every
+/// call, the same way tonic's `InterceptedService` is.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// fn check_auth(req: tonic::Request<()>) -> Result<tonic::Request<()>, tonic::Status> {
+///     match req.metadata().get("authorization") {
+///         Some(_) => Ok(req),
+///         None => Err(tonic::Status::unauthenticated("missing token")),
+///     }
+/// }
+///
+/// let (router, grpc) = HelloWorldServiceTonicCompatibleBuilder::new()
+///     .say_hello(say_hello)
+///     .with_tonic_interceptor(check_auth)
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct TonicInterceptor {
+    intercept: Arc<InterceptFn>,
+}
+
+impl std::fmt::Debug for TonicInterceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TonicInterceptor").finish_non_exhaustive()
+    }
+}
+
+impl TonicInterceptor {
+    /// Wrap a tonic interceptor.
+    pub fn new<I>(interceptor: I) -> Self
+    where
+        I: Interceptor + Clone + Send + Sync + 'static,
+    {
+        Self {
+            intercept: Arc::new(move |request| interceptor.clone().call(request)),
+        }
+    }
+
+    /// Run `next` after this interceptor, if this one lets the request through.
+    pub fn then(self, next: TonicInterceptor) -> Self {
+        Self {
+            intercept: Arc::new(move |request| (next.intercept)((self.intercept)(request)?)),
+        }
+    }
+
+    /// Run the interceptor on a tonic request.
+    ///
+    /// The message is set aside while the interceptor sees the metadata and
+    /// extensions, mirroring tonic's `InterceptedService`.
+    pub fn intercept<T>(&self, request: tonic::Request<T>) -> Result<tonic::Request<T>, Status> {
+        let (metadata, extensions, message) = request.into_parts();
+        let request = (self.intercept)(tonic::Request::from_parts(metadata, extensions, ()))?;
+        let (metadata, extensions, ()) = request.into_parts();
+        Ok(tonic::Request::from_parts(metadata, extensions, message))
+    }
+}
+
+impl<S> Layer<S> for TonicInterceptor {
+    type Service = TonicInterceptorService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TonicInterceptorService {
+            inner,
+            interceptor: self.clone(),
+        }
+    }
+}
+
+/// Service wrapper created by [`TonicInterceptor`] for Connect routes.
+///
+/// Rejections are returned as Connect errors converted from the interceptor's
+/// [`Status`], in the request's protocol.
+#[derive(Clone, Debug)]
+pub struct TonicInterceptorService<S> {
+    inner: S,
+    interceptor: TonicInterceptor,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for TonicInterceptorService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response> + Clone + Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let (mut parts, body) = req.into_parts();
+        // Kept aside so a rejection is still written in the negotiated protocol
+        let ctx = parts.extensions.get::<ConnectContext>().cloned();
+        let request = tonic::Request::from_parts(
+            MetadataMap::from_headers(parts.headers.clone()),
+            std::mem::take(&mut parts.extensions),
+            (),
+        );
+
+        match (self.interceptor.intercept)(request) {
+            Ok(request) => {
+                let (metadata, extensions, ()) = request.into_parts();
+                parts.headers = metadata.into_headers();
+                parts.extensions = extensions;
+            }
+            Err(status) => {
+                if let Some(ctx) = ctx {
+                    parts.extensions.insert(ctx);
+                }
+                let req = Request::from_parts(parts, body);
+                let response = rejection_response(&req, ConnectError::from(status));
+                return Box::pin(async move { Ok(response) });
+            }
+        }
+
+        let req = Request::from_parts(parts, body);
+        let inner = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, inner);
+        Box::pin(inner.oneshot(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::{ConnectRequest, ConnectResponse};
+    use axum::body::Body;
+    use axum::http::{StatusCode, header};
+    use axum::{Extension, Router};
+
+    #[derive(Clone)]
+    struct User(String);
+
+    fn authenticate(mut req: tonic::Request<()>) -> Result<tonic::Request<()>, Status> {
+        let user = req
+            .metadata()
+            .get("x-user")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("missing user"))?
+            .to_owned();
+        req.extensions_mut().insert(User(user));
+        Ok(req)
+    }
+
+    fn app(interceptor: TonicInterceptor) -> Router {
+        let whoami = |Extension(User(user)): Extension<User>,
+                      ConnectRequest(_): ConnectRequest<String>| async move {
+            Ok::<_, ConnectError>(ConnectResponse::new(user))
+        };
+        Router::new()
+            .route("/test.Svc/WhoAmI", post_connect(whoami))
+            .layer(interceptor)
+            .layer(ConnectLayer::new())
+    }
+
+    fn request(user: Option<&str>) -> Request<Body> {
+        let mut builder =
+            Request::post("/test.Svc/WhoAmI").header(header::CONTENT_TYPE, "application/json");
+        if let Some(user) = user {
+            builder = builder.header("x-user", user);
+        }
+        builder.body(Body::from("\"\"")).unwrap()
+    }
+
+    async fn json(response: Response) -> serde_json::Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_extensions_reach_connect_handler() {
+        let app = app(TonicInterceptor::new(authenticate));
+
+        let response = app.oneshot(request(Some("alice"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(json(response).await, "alice");
+    }
+
+    #[tokio::test]
+    async fn test_rejection_is_a_connect_error() {
+        let app = app(TonicInterceptor::new(authenticate));
+
+        let response = app.oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body = json(response).await;
+        assert_eq!(body["code"], "unauthenticated");
+        assert_eq!(body["message"], "missing user");
+    }
+
+    #[test]
+    fn test_then_runs_in_order() {
+        let rename = |mut req: tonic::Request<()>| {
+            let User(name) = req.extensions_mut().remove::<User>().unwrap();
+            req.extensions_mut().insert(User(name.to_uppercase()));
+            Ok(req)
+        };
+        let interceptor = TonicInterceptor::new(authenticate).then(TonicInterceptor::new(rename));
+
+        let mut req = tonic::Request::new(7);
+        req.metadata_mut().insert("x-user", "bob".parse().unwrap());
+        let req = interceptor.intercept(req).unwrap();
+        assert_eq!(req.extensions().get::<User>().unwrap().0, "BOB");
+        assert_eq!(*req.get_ref(), 7);
+
+        let err = interceptor.intercept(tonic::Request::new(7)).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+    }
+}
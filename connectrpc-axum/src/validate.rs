@@ -0,0 +1,247 @@
+//! Request validation.
+//!
+//! Messages implement [`Validate`] to check their own constraints, usually
+//! through code generated from `buf.validate` annotations by
+//! `connectrpc-axum-build` (see `CompileBuilder::with_validation`).
+//! [`ValidationInterceptor`] runs those checks on every decoded request
+//! message before it reaches the handler, failing the call with
+//! `invalid_argument` and a `google.rpc.BadRequest` detail listing each
+//! violation.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use connectrpc_axum::validate::ValidationInterceptor;
+//!
+//! let app = MakeServiceBuilder::new()
+//!     .with_message_interceptor(hello::register_validators(ValidationInterceptor::new()))
+//!     .add_router(router)
+//!     .build();
+//! ```
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::interceptor::{AnyMessage, MessageContext, MessageInterceptor};
+use crate::message::error::{Code, ConnectError};
+
+pub use crate::message::error::error_details::FieldViolation;
+
+/// A message that can check its own constraints.
+pub trait Validate {
+    /// Append a violation for each constraint the message breaks.
+    ///
+    /// `prefix` is the path of this message within the request (empty at the
+    /// root); field paths are built with [`field_path`].
+    fn collect_violations(&self, prefix: &str, violations: &mut Vec<FieldViolation>);
+
+    /// Check the message, returning an `invalid_argument` error with a
+    /// `BadRequest` detail if any constraint is broken.
+    fn validate(&self) -> Result<(), ConnectError> {
+        let mut violations = Vec::new();
+        self.collect_violations("", &mut violations);
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        let summary = violations
+            .iter()
+            .map(|violation| format!("{}: {}", violation.field, violation.description))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Err(ConnectError::new(
+            Code::InvalidArgument,
+            format!("validation error: {summary}"),
+        )
+        .with_bad_request(violations))
+    }
+}
+
+/// Join a field name onto the path of its parent message.
+pub fn field_path(prefix: &str, field: &str) -> String {
+    if prefix.is_empty() {
+        field.to_string()
+    } else {
+        format!("{prefix}.{field}")
+    }
+}
+
+type ValidateFn = fn(&dyn AnyMessage) -> Result<(), ConnectError>;
+
+/// Message interceptor that validates request messages.
+///
+/// Only message types registered with [`message`](Self::message) are
+/// checked; other requests pass through. Streaming requests are checked one
+/// message at a time, so a bad message ends the stream with the error.
+#[derive(Clone, Default)]
+pub struct ValidationInterceptor {
+    validators: Arc<HashMap<TypeId, ValidateFn>>,
+}
+
+impl fmt::Debug for ValidationInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidationInterceptor")
+            .field("messages", &self.validators.len())
+            .finish()
+    }
+}
+
+impl ValidationInterceptor {
+    /// Create an interceptor that validates nothing yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate request messages of type `T`.
+    pub fn message<T: Validate + AnyMessage>(mut self) -> Self {
+        Arc::make_mut(&mut self.validators).insert(TypeId::of::<T>(), validate_as::<T>);
+        self
+    }
+
+    fn check(&self, message: &dyn AnyMessage) -> Result<(), ConnectError> {
+        match self.validators.get(&message.as_any().type_id()) {
+            Some(validate) => validate(message),
+            None => Ok(()),
+        }
+    }
+}
+
+fn validate_as<T: Validate + AnyMessage>(message: &dyn AnyMessage) -> Result<(), ConnectError> {
+    match message.downcast_ref::<T>() {
+        Some(message) => message.validate(),
+        None => Ok(()),
+    }
+}
+
+impl MessageInterceptor for ValidationInterceptor {
+    fn on_request(
+        &self,
+        _ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        self.check(request)
+    }
+
+    fn on_stream_receive(
+        &self,
+        _ctx: &MessageContext,
+        request: &mut dyn AnyMessage,
+    ) -> Result<(), ConnectError> {
+        self.check(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::post_connect;
+    use crate::layer::ConnectLayer;
+    use crate::message::error::error_details::BadRequest;
+    use crate::message::{ConnectRequest, ConnectResponse};
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use tower::ServiceExt;
+
+    #[derive(Clone, PartialEq, prost::Message, serde::Serialize, serde::Deserialize)]
+    struct Greeting {
+        #[prost(string, tag = "1")]
+        name: String,
+        #[prost(message, optional, tag = "2")]
+        reply_to: Option<Address>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message, serde::Serialize, serde::Deserialize)]
+    struct Address {
+        #[prost(string, tag = "1")]
+        email: String,
+    }
+
+    impl Validate for Greeting {
+        fn collect_violations(&self, prefix: &str, violations: &mut Vec<FieldViolation>) {
+            if self.name.chars().count() < 2 {
+                violations.push(FieldViolation::new(
+                    field_path(prefix, "name"),
+                    "value length must be at least 2 characters",
+                ));
+            }
+            if let Some(reply_to) = &self.reply_to {
+                reply_to.collect_violations(&field_path(prefix, "reply_to"), violations);
+            }
+        }
+    }
+
+    impl Validate for Address {
+        fn collect_violations(&self, prefix: &str, violations: &mut Vec<FieldViolation>) {
+            if !self.email.contains('@') {
+                violations.push(FieldViolation::new(
+                    field_path(prefix, "email"),
+                    "value does not contain substring `@`",
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_collects_nested_violations() {
+        let greeting = Greeting {
+            name: "a".to_string(),
+            reply_to: Some(Address {
+                email: "nobody".to_string(),
+            }),
+        };
+
+        let err = greeting.validate().unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert_eq!(
+            err.message(),
+            Some(
+                "validation error: name: value length must be at least 2 characters; \
+                 reply_to.email: value does not contain substring `@`"
+            )
+        );
+        let detail: BadRequest = err.details()[0].to_message().unwrap().unwrap();
+        assert_eq!(detail.field_violations.len(), 2);
+        assert_eq!(detail.field_violations[1].field, "reply_to.email");
+    }
+
+    async fn greet(
+        ConnectRequest(req): ConnectRequest<Greeting>,
+    ) -> Result<ConnectResponse<Greeting>, ConnectError> {
+        Ok(ConnectResponse::new(req))
+    }
+
+    #[tokio::test]
+    async fn test_interceptor_rejects_invalid_requests() {
+        let app = Router::new()
+            .route("/test.Svc/Greet", post_connect(greet))
+            .layer(
+                ConnectLayer::new()
+                    .with_message_interceptor(ValidationInterceptor::new().message::<Greeting>()),
+            );
+        let request = |body: &'static str| {
+            Request::post("/test.Svc/Greet")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request(r#"{"name":"ada"}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(request(r#"{"name":"a"}"#)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "invalid_argument");
+        assert_eq!(body["details"][0]["type"], "google.rpc.BadRequest");
+    }
+}
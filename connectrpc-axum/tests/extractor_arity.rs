@@ -0,0 +1,81 @@
+use axum::http::{HeaderMap, Method, Uri};
+use connectrpc_axum::prelude::*;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+pub struct HelloRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, Message, Serialize, Deserialize)]
+pub struct HelloResponse {
+    #[prost(string, tag = "1")]
+    pub message: String,
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn nine_extractors(
+    _a1: Method,
+    _a2: Uri,
+    _a3: HeaderMap,
+    _a4: Method,
+    _a5: Uri,
+    _a6: HeaderMap,
+    _a7: Method,
+    _a8: Uri,
+    _a9: HeaderMap,
+    ConnectRequest(_req): ConnectRequest<HelloRequest>,
+) -> Result<ConnectResponse<HelloResponse>, ConnectError> {
+    Ok(ConnectResponse::new(HelloResponse::default()))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn sixteen_extractors(
+    _a1: Method,
+    _a2: Uri,
+    _a3: HeaderMap,
+    _a4: Method,
+    _a5: Uri,
+    _a6: HeaderMap,
+    _a7: Method,
+    _a8: Uri,
+    _a9: HeaderMap,
+    _a10: Method,
+    _a11: Uri,
+    _a12: HeaderMap,
+    _a13: Method,
+    _a14: Uri,
+    _a15: HeaderMap,
+    _a16: Method,
+    ConnectRequest(_req): ConnectRequest<HelloRequest>,
+) -> Result<ConnectResponse<HelloResponse>, ConnectError> {
+    Ok(ConnectResponse::new(HelloResponse::default()))
+}
+
+#[test]
+fn post_connect_accepts_up_to_sixteen_extractors() {
+    let _router: axum::Router = axum::Router::new()
+        .route("/nine", post_connect(nine_extractors))
+        .route("/sixteen", post_connect(sixteen_extractors));
+}
+
+#[cfg(feature = "tonic")]
+#[test]
+fn post_tonic_accepts_up_to_sixteen_extractors() {
+    use connectrpc_axum::{IntoFactory, TonicHandlerWrapper, post_tonic};
+
+    fn assert_into_factory<H, T>(_: H)
+    where
+        H: IntoFactory<T, HelloRequest, HelloResponse, ()>,
+    {
+    }
+
+    let _router: axum::Router = axum::Router::new()
+        .route("/nine", post_tonic(nine_extractors))
+        .route("/sixteen", post_tonic(sixteen_extractors));
+
+    assert_into_factory(TonicHandlerWrapper::unary(nine_extractors));
+    assert_into_factory(TonicHandlerWrapper::unary(sixteen_extractors));
+}
@@ -55,11 +55,15 @@ export default defineConfig({
               items: [
                 { text: 'Message Limits', link: '/guide/limits' },
                 { text: 'Timeout', link: '/guide/timeout' },
-                { text: 'Compression', link: '/guide/compression' }
+                { text: 'Compression', link: '/guide/compression' },
+                { text: 'Health Checking', link: '/guide/health' },
+                { text: 'Server Reflection', link: '/guide/reflection' }
               ]
             },
             { text: 'Axum Router', link: '/guide/axum-router' },
+            { text: 'Validation', link: '/guide/validation' },
             { text: 'Client', link: '/guide/client' },
+            { text: 'Native gRPC', link: '/guide/grpc' },
             {
               text: 'Tonic gRPC',
               link: '/guide/tonic',